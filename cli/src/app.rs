@@ -68,8 +68,14 @@ pub enum Command {
     /// Manage Kafka subscriptions
     #[clap(subcommand)]
     Subscriptions(subscriptions::Subscriptions),
-    /// Manage active invocations
+    /// Manage cron schedules
     #[clap(subcommand)]
+    Schedules(schedules::Schedules),
+    /// Invoke a handler of a service, virtual object or workflow
+    Invoke(invoke::Invoke),
+    /// Manage active invocations
+    // Keeps `restate inv` unambiguous next to `invoke`
+    #[clap(subcommand, alias = "inv")]
     Invocations(invocations::Invocations),
     /// Manage concurrency-limit rules
     #[clap(subcommand)]
@@ -39,6 +39,7 @@ pub const CLI_CONFIG_HOME_ENV: &str = "RESTATE_CLI_CONFIG_HOME";
 pub const CLI_CONFIG_FILE_ENV: &str = "RESTATE_CLI_CONFIG";
 
 pub const RESTATE_AUTH_TOKEN_ENV: &str = "RESTATE_AUTH_TOKEN";
+pub const RESTATE_INGRESS_API_KEY_ENV: &str = "RESTATE_INGRESS_API_KEY";
 // TODO: Deprecated, will be removed once this is provided by the admin server
 pub const INGRESS_URL_ENV: &str = "RESTATE_INGRESS_URL";
 pub const ADMIN_URL_ENV: &str = "RESTATE_ADMIN_URL";
@@ -51,6 +52,8 @@ pub struct CliConfig {
     pub ingress_base_url: Option<AdvertisedAddress<HttpIngressPort>>,
     pub admin_base_url: Option<AdvertisedAddress<AdminPort>>,
     pub bearer_token: Option<String>,
+    /// API key sent to the ingress in the `x-restate-api-key` header.
+    pub ingress_api_key: Option<String>,
 
     #[cfg(feature = "cloud")]
     pub cloud: crate::commands::cloud::CloudConfig,
@@ -66,6 +69,7 @@ impl CliConfig {
             ingress_base_url: Some(AdvertisedAddress::default()),
             admin_base_url: Some(AdvertisedAddress::default()),
             bearer_token: None,
+            ingress_api_key: None,
 
             #[cfg(feature = "cloud")]
             cloud: crate::commands::cloud::CloudConfig::default(),
@@ -225,6 +229,12 @@ impl CliEnv {
             figment
         };
 
+        let figment = if let Some(api_key) = os_env.get(RESTATE_INGRESS_API_KEY_ENV) {
+            figment.merge(("ingress_api_key", api_key))
+        } else {
+            figment
+        };
+
         Ok(figment)
     }
 
@@ -457,4 +467,19 @@ mod tests {
         let cli_env = CliEnv::load_from_env(&os_env, &GlobalOpts::default()).unwrap();
         assert_eq!(cli_env.config.bearer_token, Some("token".to_string()));
     }
+
+    #[test]
+    fn ingress_api_key_applied() {
+        let mut os_env = OsEnv::default();
+        // avoid using any files from the test runner
+        os_env.insert(CLI_CONFIG_HOME_ENV, "/dev/null".into());
+        os_env.insert(RESTATE_AUTH_TOKEN_ENV, "token".to_string());
+        let cli_env = CliEnv::load_from_env(&os_env, &GlobalOpts::default()).unwrap();
+        // the admin token is not used as ingress API key
+        assert_eq!(cli_env.config.ingress_api_key, None);
+
+        os_env.insert(RESTATE_INGRESS_API_KEY_ENV, "key".to_string());
+        let cli_env = CliEnv::load_from_env(&os_env, &GlobalOpts::default()).unwrap();
+        assert_eq!(cli_env.config.ingress_api_key, Some("key".to_string()));
+    }
 }
@@ -19,6 +19,9 @@ use thiserror::Error;
 use tracing::{debug, info};
 use url::Url;
 
+use restate_admin_rest_model::services::{
+    ImportServiceStateResponse, ImportVersionCheck, ServiceStateFormat,
+};
 use restate_admin_rest_model::version::{AdminApiVersion, VersionInformation};
 use restate_cli_util::{CliContext, c_warn};
 use restate_types::SemanticRestateVersion;
@@ -259,10 +262,18 @@ impl AdminClient {
 
     /// Prepare a request builder for the given method and path.
     pub(crate) fn prepare(&self, method: reqwest::Method, path: Url) -> reqwest::RequestBuilder {
-        let request_builder = self
-            .inner
-            .request(method, path)
-            .timeout(self.request_timeout);
+        self.prepare_streaming(method, path)
+            .timeout(self.request_timeout)
+    }
+
+    /// Prepare a request builder for the given method and path, without request timeout.
+    /// Used for long-lived streaming responses.
+    pub(crate) fn prepare_streaming(
+        &self,
+        method: reqwest::Method,
+        path: Url,
+    ) -> reqwest::RequestBuilder {
+        let request_builder = self.inner.request(method, path);
 
         match self.bearer_token.as_deref() {
             Some(token) => request_builder.bearer_auth(token),
@@ -301,6 +312,74 @@ impl AdminClient {
         }
     }
 
+    /// Follow the given invocation, returning the newline-delimited JSON stream of its events.
+    pub(crate) async fn tail_invocation(&self, id: &str) -> Result<reqwest::Response, Error> {
+        let url = self.versioned_url(["invocations", id, "tail"]);
+        debug!("Sending request GET ({})", url);
+        let resp = self
+            .prepare_streaming(reqwest::Method::GET, url.clone())
+            .send()
+            .await?;
+        debug!("Response from {} ({})", url, resp.status());
+        if !resp.status().is_success() {
+            let api_error = Envelope::<()>::from(resp).into_api_error().await?;
+            return Err(Error::Api(Box::new(api_error)));
+        }
+        Ok(resp)
+    }
+
+    /// Export the state of the given service, returning the streamed export.
+    pub(crate) async fn export_service_state(
+        &self,
+        service: &str,
+        format: ServiceStateFormat,
+    ) -> Result<reqwest::Response, Error> {
+        let mut url = self.versioned_url(["services", service, "state", "export"]);
+        url.set_query(Some(&format!("format={}", format_query_value(format))));
+        debug!("Sending request GET ({})", url);
+        let resp = self
+            .prepare_streaming(reqwest::Method::GET, url.clone())
+            .send()
+            .await?;
+        debug!("Response from {} ({})", url, resp.status());
+        if !resp.status().is_success() {
+            let api_error = Envelope::<()>::from(resp).into_api_error().await?;
+            return Err(Error::Api(Box::new(api_error)));
+        }
+        Ok(resp)
+    }
+
+    /// Import state entries of the given service, in the export format.
+    pub(crate) async fn import_service_state(
+        &self,
+        service: &str,
+        format: ServiceStateFormat,
+        version_check: ImportVersionCheck,
+        keys_per_second: Option<u32>,
+        body: reqwest::Body,
+    ) -> Result<ImportServiceStateResponse, Error> {
+        let mut url = self.versioned_url(["services", service, "state", "import"]);
+        {
+            let mut query = url.query_pairs_mut();
+            query.append_pair("format", format_query_value(format));
+            query.append_pair("version_check", version_check_query_value(version_check));
+            if let Some(keys_per_second) = keys_per_second {
+                query.append_pair("keys_per_second", &keys_per_second.to_string());
+            }
+        }
+        debug!("Sending request POST ({})", url);
+        let resp = self
+            .prepare_streaming(reqwest::Method::POST, url.clone())
+            .header(http::header::CONTENT_TYPE, "application/octet-stream")
+            .body(body)
+            .send()
+            .await?;
+        debug!("Response from {} ({})", url, resp.status());
+        Envelope::<ImportServiceStateResponse>::from(resp)
+            .into_body()
+            .await
+    }
+
     pub(crate) fn run_with_body<T, B>(
         &self,
         method: reqwest::Method,
@@ -326,3 +405,18 @@ const _: () = {
     const fn assert_send<T: Send + Sync>() {}
     assert_send::<AdminClient>();
 };
+
+fn format_query_value(format: ServiceStateFormat) -> &'static str {
+    match format {
+        ServiceStateFormat::Ndjson => "ndjson",
+        ServiceStateFormat::Parquet => "parquet",
+    }
+}
+
+fn version_check_query_value(version_check: ImportVersionCheck) -> &'static str {
+    match version_check {
+        ImportVersionCheck::Empty => "empty",
+        ImportVersionCheck::Unchanged => "unchanged",
+        ImportVersionCheck::Overwrite => "overwrite",
+    }
+}
@@ -15,16 +15,19 @@ use futures::stream;
 use http::{Uri, Version};
 use indicatif::ProgressBar;
 use restate_admin_rest_model::deployments::*;
-use restate_admin_rest_model::invocations::RestartAsNewInvocationResponse;
+use restate_admin_rest_model::invocations::{
+    BulkInvocationOperationJob, BulkInvocationOperationRequest, RestartAsNewInvocationResponse,
+};
 use restate_admin_rest_model::kafka_clusters::*;
 use restate_admin_rest_model::rules::*;
+use restate_admin_rest_model::schedules::*;
 use restate_admin_rest_model::services::*;
 use restate_admin_rest_model::subscriptions::*;
 use restate_admin_rest_model::version::VersionInformation;
 use restate_futures_util::streams::StreamExt as RestateStreamExt;
 use restate_serde_util::SerdeableHeaderHashMap;
 use restate_types::identifiers::{DeploymentId, LambdaARN};
-use restate_types::schema::deployment::ProtocolType;
+use restate_types::schema::deployment::{DeploymentLimits, ProtocolType};
 use restate_types::schema::service::ServiceMetadata;
 use std::collections::HashMap;
 
@@ -62,6 +65,11 @@ pub trait AdminClientInterface {
         &self,
         body: RegisterDeploymentRequest,
     ) -> impl Future<Output = reqwest::Result<Envelope<RegisterDeploymentResponse>>> + Send + 'static;
+    fn patch_deployment(
+        &self,
+        id: &str,
+        body: UpdateDeploymentRequest,
+    ) -> impl Future<Output = reqwest::Result<Envelope<DetailedDeploymentResponse>>> + Send + 'static;
 
     fn cancel_invocation(
         &self,
@@ -94,6 +102,16 @@ pub trait AdminClientInterface {
         id: &str,
     ) -> impl Future<Output = reqwest::Result<Envelope<()>>> + Send + 'static;
 
+    fn submit_bulk_invocation_operation(
+        &self,
+        body: BulkInvocationOperationRequest,
+    ) -> impl Future<Output = reqwest::Result<Envelope<BulkInvocationOperationJob>>> + Send + 'static;
+
+    fn get_bulk_invocation_operation(
+        &self,
+        job_id: &str,
+    ) -> impl Future<Output = reqwest::Result<Envelope<BulkInvocationOperationJob>>> + Send + 'static;
+
     fn patch_state(
         &self,
         service: &str,
@@ -156,6 +174,49 @@ pub trait AdminClientInterface {
         id: &str,
     ) -> impl Future<Output = reqwest::Result<Envelope<()>>> + Send + 'static;
 
+    fn pause_subscription(
+        &self,
+        id: &str,
+    ) -> impl Future<Output = reqwest::Result<Envelope<SubscriptionResponse>>> + Send + 'static;
+
+    fn resume_subscription(
+        &self,
+        id: &str,
+    ) -> impl Future<Output = reqwest::Result<Envelope<SubscriptionResponse>>> + Send + 'static;
+
+    fn seek_subscription(
+        &self,
+        id: &str,
+        body: SeekSubscriptionRequest,
+    ) -> impl Future<Output = reqwest::Result<Envelope<SubscriptionResponse>>> + Send + 'static;
+
+    // --- Schedules ---------------------------------------------------------
+
+    fn list_schedules(
+        &self,
+    ) -> impl Future<Output = reqwest::Result<Envelope<ListSchedulesResponse>>> + Send + 'static;
+
+    fn get_schedule(
+        &self,
+        id: &str,
+    ) -> impl Future<Output = reqwest::Result<Envelope<ScheduleResponse>>> + Send + 'static;
+
+    fn create_schedule(
+        &self,
+        body: CreateScheduleRequest,
+    ) -> impl Future<Output = reqwest::Result<Envelope<ScheduleResponse>>> + Send + 'static;
+
+    fn modify_schedule(
+        &self,
+        id: &str,
+        body: ModifyScheduleRequest,
+    ) -> impl Future<Output = reqwest::Result<Envelope<ScheduleResponse>>> + Send + 'static;
+
+    fn delete_schedule(
+        &self,
+        id: &str,
+    ) -> impl Future<Output = reqwest::Result<Envelope<()>>> + Send + 'static;
+
     // --- Rules -------------------------------------------------------------
 
     fn upsert_rules(
@@ -237,6 +298,16 @@ impl AdminClientInterface for AdminClient {
         self.run_with_body(reqwest::Method::POST, url, body)
     }
 
+    fn patch_deployment(
+        &self,
+        id: &str,
+        body: UpdateDeploymentRequest,
+    ) -> impl Future<Output = reqwest::Result<Envelope<DetailedDeploymentResponse>>> + Send + 'static
+    {
+        let url = self.versioned_url(["deployments", id]);
+        self.run_with_body(reqwest::Method::PATCH, url, body)
+    }
+
     fn cancel_invocation(
         &self,
         id: &str,
@@ -296,6 +367,24 @@ impl AdminClientInterface for AdminClient {
         self.run(reqwest::Method::PATCH, url)
     }
 
+    fn submit_bulk_invocation_operation(
+        &self,
+        body: BulkInvocationOperationRequest,
+    ) -> impl Future<Output = reqwest::Result<Envelope<BulkInvocationOperationJob>>> + Send + 'static
+    {
+        let url = self.versioned_url(["invocations", "bulk-operations"]);
+        self.run_with_body(reqwest::Method::POST, url, body)
+    }
+
+    fn get_bulk_invocation_operation(
+        &self,
+        job_id: &str,
+    ) -> impl Future<Output = reqwest::Result<Envelope<BulkInvocationOperationJob>>> + Send + 'static
+    {
+        let url = self.versioned_url(["invocations", "bulk-operations", job_id]);
+        self.run(reqwest::Method::GET, url)
+    }
+
     fn patch_state(
         &self,
         service: &str,
@@ -416,6 +505,77 @@ impl AdminClientInterface for AdminClient {
         self.run(reqwest::Method::DELETE, url)
     }
 
+    fn pause_subscription(
+        &self,
+        id: &str,
+    ) -> impl Future<Output = reqwest::Result<Envelope<SubscriptionResponse>>> + Send + 'static
+    {
+        let url = self.versioned_url(["subscriptions", id, "pause"]);
+        self.run(reqwest::Method::PATCH, url)
+    }
+
+    fn resume_subscription(
+        &self,
+        id: &str,
+    ) -> impl Future<Output = reqwest::Result<Envelope<SubscriptionResponse>>> + Send + 'static
+    {
+        let url = self.versioned_url(["subscriptions", id, "resume"]);
+        self.run(reqwest::Method::PATCH, url)
+    }
+
+    fn seek_subscription(
+        &self,
+        id: &str,
+        body: SeekSubscriptionRequest,
+    ) -> impl Future<Output = reqwest::Result<Envelope<SubscriptionResponse>>> + Send + 'static
+    {
+        let url = self.versioned_url(["subscriptions", id, "seek"]);
+        self.run_with_body(reqwest::Method::PATCH, url, body)
+    }
+
+    // --- Schedules ---------------------------------------------------------
+
+    fn list_schedules(
+        &self,
+    ) -> impl Future<Output = reqwest::Result<Envelope<ListSchedulesResponse>>> + Send + 'static
+    {
+        let url = self.versioned_url(["schedules"]);
+        self.run(reqwest::Method::GET, url)
+    }
+
+    fn get_schedule(
+        &self,
+        id: &str,
+    ) -> impl Future<Output = reqwest::Result<Envelope<ScheduleResponse>>> + Send + 'static {
+        let url = self.versioned_url(["schedules", id]);
+        self.run(reqwest::Method::GET, url)
+    }
+
+    fn create_schedule(
+        &self,
+        body: CreateScheduleRequest,
+    ) -> impl Future<Output = reqwest::Result<Envelope<ScheduleResponse>>> + Send + 'static {
+        let url = self.versioned_url(["schedules"]);
+        self.run_with_body(reqwest::Method::POST, url, body)
+    }
+
+    fn modify_schedule(
+        &self,
+        id: &str,
+        body: ModifyScheduleRequest,
+    ) -> impl Future<Output = reqwest::Result<Envelope<ScheduleResponse>>> + Send + 'static {
+        let url = self.versioned_url(["schedules", id]);
+        self.run_with_body(reqwest::Method::PATCH, url, body)
+    }
+
+    fn delete_schedule(
+        &self,
+        id: &str,
+    ) -> impl Future<Output = reqwest::Result<Envelope<()>>> + Send + 'static {
+        let url = self.versioned_url(["schedules", id]);
+        self.run(reqwest::Method::DELETE, url)
+    }
+
     // --- Rules -------------------------------------------------------------
 
     fn upsert_rules(
@@ -490,6 +650,7 @@ pub enum Deployment {
         metadata: HashMap<String, String>,
         sdk_version: Option<String>,
         auth: Option<restate_admin_rest_model::deployments::HttpAuth>,
+        limits: DeploymentLimits,
     },
     Lambda {
         arn: LambdaARN,
@@ -500,6 +661,7 @@ pub enum Deployment {
         max_protocol_version: i32,
         metadata: HashMap<String, String>,
         sdk_version: Option<String>,
+        limits: DeploymentLimits,
     },
 }
 
@@ -511,6 +673,13 @@ impl Deployment {
         }
     }
 
+    pub fn limits(&self) -> DeploymentLimits {
+        match self {
+            Self::Http { limits, .. } => *limits,
+            Self::Lambda { limits, .. } => *limits,
+        }
+    }
+
     pub fn from_deployment_response(
         deployment_response: DeploymentResponse,
     ) -> (DeploymentId, Self, Vec<ServiceNameRevPair>) {
@@ -528,6 +697,7 @@ impl Deployment {
                 metadata,
                 sdk_version,
                 auth,
+                limits,
                 ..
             } => (
                 id,
@@ -542,6 +712,7 @@ impl Deployment {
                     metadata,
                     sdk_version,
                     auth,
+                    limits,
                 },
                 services,
             ),
@@ -556,6 +727,7 @@ impl Deployment {
                 services,
                 metadata,
                 sdk_version,
+                limits,
                 ..
             } => (
                 id,
@@ -568,6 +740,7 @@ impl Deployment {
                     max_protocol_version,
                     metadata,
                     sdk_version,
+                    limits,
                 },
                 services,
             ),
@@ -591,6 +764,7 @@ impl Deployment {
                 metadata,
                 sdk_version,
                 auth,
+                limits,
                 ..
             } => (
                 id,
@@ -605,6 +779,7 @@ impl Deployment {
                     metadata,
                     sdk_version,
                     auth,
+                    limits,
                 },
                 services,
             ),
@@ -619,6 +794,7 @@ impl Deployment {
                 services,
                 metadata,
                 sdk_version,
+                limits,
                 ..
             } => (
                 id,
@@ -631,6 +807,7 @@ impl Deployment {
                     max_protocol_version,
                     metadata,
                     sdk_version,
+                    limits,
                 },
                 services,
             ),
@@ -23,6 +23,7 @@ use arrow::{
 };
 use bytes::Buf;
 use itertools::Itertools;
+use restate_admin_rest_model::query::QueryFormat;
 use restate_types::SemanticRestateVersion;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -37,6 +38,10 @@ pub enum Error {
         "The Restate server '{0}' lacks JSON /query support. Please update the CLI to match the Restate server version '{1}'."
     )]
     JSONSupport(Url, String),
+    #[error(
+        "The Restate server '{0}' can't write query results as {2:?}. Please update the Restate server, its version is '{1}'."
+    )]
+    FormatSupport(Url, String, QueryFormat),
     #[error("(Protocol error) {0}")]
     Serialization(#[from] serde_json::Error),
     Network(#[from] reqwest::Error),
@@ -70,6 +75,15 @@ impl DataFusionHttpClient {
             .prepare(reqwest::Method::POST, self.inner.versioned_url(["query"])))
     }
 
+    /// Invocation operations have their own endpoint, so that `/query` stays read-only.
+    fn query_url(&self, request: &SqlQueryRequest) -> Url {
+        if request.operations.is_some() {
+            self.inner.versioned_url(["query", "operations"])
+        } else {
+            self.inner.versioned_url(["query"])
+        }
+    }
+
     pub async fn run_json_query<T: serde::de::DeserializeOwned>(
         &self,
         query: String,
@@ -78,7 +92,7 @@ impl DataFusionHttpClient {
         let resp = self
             .prepare()?
             .header(http::header::ACCEPT, "application/json")
-            .json(&SqlQueryRequest { query })
+            .json(&SqlQueryRequest::new(query))
             .send()
             .await?;
 
@@ -115,10 +129,18 @@ impl DataFusionHttpClient {
     }
 
     pub async fn run_arrow_query(&self, query: String) -> Result<SqlResponse, Error> {
-        debug!("Sending request sql query with arrow output '{}'", query);
+        self.run_arrow_request(SqlQueryRequest::new(query)).await
+    }
+
+    pub async fn run_arrow_request(&self, request: SqlQueryRequest) -> Result<SqlResponse, Error> {
+        debug!(
+            "Sending request sql query with arrow output '{}'",
+            request.query
+        );
         let resp = self
-            .prepare()?
-            .json(&SqlQueryRequest { query })
+            .inner
+            .prepare(reqwest::Method::POST, self.query_url(&request))
+            .json(&request)
             .send()
             .await?;
 
@@ -151,6 +173,52 @@ impl DataFusionHttpClient {
         Ok(SqlResponse { schema, batches })
     }
 
+    /// Run the query, returning the response to stream the results in the request's format.
+    pub async fn run_streaming_request(
+        &self,
+        request: SqlQueryRequest,
+    ) -> Result<reqwest::Response, Error> {
+        debug!(
+            "Sending request sql query with {:?} output '{}'",
+            request.format, request.query
+        );
+        let resp = self
+            .inner
+            .prepare_streaming(reqwest::Method::POST, self.query_url(&request))
+            .json(&request)
+            .send()
+            .await?;
+
+        let http_status_code = resp.status();
+        let url = resp.url().clone();
+        if !resp.status().is_success() {
+            let body = resp.text().await?;
+            info!("Response from {} ({})", url, http_status_code);
+            info!("  {}", body);
+            return Err(Error::Api(Box::new(ApiError {
+                http_status_code,
+                url,
+                body: serde_json::from_str(&body)?,
+            })));
+        }
+
+        // Older servers ignore the requested format and return an Arrow IPC stream
+        if let Some(format) = request.format
+            && resp
+                .headers()
+                .get(http::header::CONTENT_TYPE)
+                .is_none_or(|content_type| content_type != format.content_type())
+        {
+            return Err(Error::FormatSupport(
+                self.inner.base_url.clone(),
+                self.inner.restate_server_version.to_string(),
+                format,
+            ));
+        }
+
+        Ok(resp)
+    }
+
     pub async fn run_count_agg_query(&self, query: String) -> Result<i64, Error> {
         let resp = self.run_arrow_query(query).await?;
 
@@ -186,6 +254,29 @@ impl DataFusionHttpClient {
 #[derive(Serialize, Debug, Clone)]
 pub struct SqlQueryRequest {
     pub query: String,
+    /// Set when the query selects an invocation function, sending it to `/query/operations`.
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub operations: Option<SqlOperationsRequest>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<QueryFormat>,
+}
+
+impl SqlQueryRequest {
+    pub fn new(query: String) -> Self {
+        Self {
+            query,
+            operations: None,
+            format: None,
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct SqlOperationsRequest {
+    /// Only report the invocations the invocation function would be applied to.
+    pub dry_run: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operations_per_second: Option<u32>,
 }
 
 pub struct SqlResponse {
@@ -0,0 +1,112 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! A wrapper client for the ingress HTTP service.
+
+use serde::Deserialize;
+use tracing::debug;
+use url::Url;
+
+use restate_cli_util::CliContext;
+use restate_types::net::address::PeerNetAddress;
+
+use crate::build_info;
+use crate::cli_env::CliEnv;
+
+/// Response of the ingress to `/send` requests.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SendResponse {
+    pub invocation_id: String,
+    pub status: SendStatus,
+    pub execution_time: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub enum SendStatus {
+    Accepted,
+    PreviouslyAccepted,
+}
+
+const API_KEY_HEADER: &str = "x-restate-api-key";
+
+/// A handy client for the ingress HTTP service.
+///
+/// Requests have no timeout, since invocations can take arbitrarily long to complete. The
+/// credentials of the admin API are never sent to the ingress.
+#[derive(Clone)]
+pub struct IngressClient {
+    inner: reqwest::Client,
+    base_url: Url,
+    api_key: Option<String>,
+}
+
+impl IngressClient {
+    /// Creates a client for the ingress of the environment. `api_key` overrides the ingress API
+    /// key of the environment.
+    pub fn new(env: &CliEnv, api_key: Option<&str>) -> anyhow::Result<Self> {
+        let advertised_address = env.ingress_base_url()?.clone();
+        let builder = reqwest::Client::builder()
+            .user_agent(format!(
+                "{}/{} {}-{}",
+                env!("CARGO_PKG_NAME"),
+                build_info::RESTATE_CLI_VERSION,
+                std::env::consts::OS,
+                std::env::consts::ARCH,
+            ))
+            .connect_timeout(CliContext::get().connect_timeout())
+            .danger_accept_invalid_certs(CliContext::get().insecure_skip_tls_verify());
+
+        let (inner, base_url) = match advertised_address.into_address()? {
+            PeerNetAddress::Uds(path_buf) => {
+                let client = builder.unix_socket(path_buf).build()?;
+                (client, "http://localhost/".parse().unwrap())
+            }
+            PeerNetAddress::Http(uri) => {
+                let client = builder.build()?;
+                // forced to go to string and back because those are two types (Uri vs. Url)
+                (client, uri.to_string().parse()?)
+            }
+        };
+
+        Ok(Self {
+            inner,
+            base_url,
+            api_key: api_key
+                .or(env.config.ingress_api_key.as_deref())
+                .map(str::to_string),
+        })
+    }
+
+    pub fn base_url(&self) -> &Url {
+        &self.base_url
+    }
+
+    /// Returns the url of the given ingress path, percent-encoding the segments.
+    pub fn url(&self, path: impl IntoIterator<Item = impl AsRef<str>>) -> Url {
+        let mut url = self.base_url.clone();
+        url.path_segments_mut()
+            .expect("Bad url!")
+            .pop_if_empty()
+            .extend(path);
+        url
+    }
+
+    /// Prepare a request builder for the given method and url.
+    pub fn prepare(&self, method: reqwest::Method, url: Url) -> reqwest::RequestBuilder {
+        debug!("Preparing request {} ({})", method, url);
+        let request_builder = self.inner.request(method, url);
+
+        match self.api_key.as_deref() {
+            Some(api_key) => request_builder.header(API_KEY_HEADER, api_key),
+            None => request_builder,
+        }
+    }
+}
@@ -15,10 +15,14 @@ pub mod cloud;
 pub mod datafusion_helpers;
 mod datafusion_http_client;
 mod errors;
+mod ingress_client;
 
 pub use self::admin_client::AdminClient;
 pub use self::admin_client::Error as MetasClientError;
 pub use self::admin_client::{MAX_ADMIN_API_VERSION, MIN_ADMIN_API_VERSION};
 pub use self::admin_interface::Deployment;
 pub use self::admin_interface::{AdminClientInterface, batch_execute};
-pub use self::datafusion_http_client::DataFusionHttpClient;
+pub use self::datafusion_http_client::{
+    DataFusionHttpClient, SqlOperationsRequest, SqlQueryRequest,
+};
+pub use self::ingress_client::{IngressClient, SendResponse, SendStatus};
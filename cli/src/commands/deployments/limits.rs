@@ -0,0 +1,107 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::num::NonZeroU32;
+
+use anyhow::Result;
+use cling::prelude::*;
+use comfy_table::Table;
+
+use restate_admin_rest_model::deployments::UpdateDeploymentRequest;
+use restate_cli_util::ui::console::{StyledTable, confirm_or_exit};
+use restate_cli_util::{c_println, c_success};
+use restate_types::rate::Rate;
+use restate_types::schema::deployment::DeploymentLimits;
+
+use crate::cli_env::CliEnv;
+use crate::clients::{AdminClient, AdminClientInterface, Deployment};
+
+#[derive(Run, Parser, Collect, Clone)]
+#[cling(run = "run_limits")]
+pub struct Limits {
+    /// Deployment ID
+    deployment_id: String,
+
+    /// Maximum number of concurrently running invocation attempts against the deployment, per
+    /// partition. Unlimited if omitted.
+    #[clap(long)]
+    concurrency: Option<NonZeroU32>,
+
+    /// Maximum rate at which invocation attempts against the deployment start, per partition,
+    /// for example `100/s`. Unlimited if omitted.
+    #[clap(long)]
+    rate: Option<Rate>,
+
+    /// Maximum number of invocation attempts starting in a burst. Defaults to the number of
+    /// attempts allowed per rate period.
+    #[clap(long, requires = "rate")]
+    burst: Option<NonZeroU32>,
+}
+
+pub async fn run_limits(State(env): State<CliEnv>, opts: &Limits) -> Result<()> {
+    let client = AdminClient::new(&env).await?;
+
+    let deployment = client
+        .get_deployment(&opts.deployment_id)
+        .await?
+        .into_body()
+        .await?;
+    let (deployment_id, deployment, _) = Deployment::from_detailed_deployment_response(deployment);
+
+    let limits = DeploymentLimits {
+        concurrency: opts.concurrency,
+        rate: opts.rate,
+        burst: opts.burst,
+    };
+
+    let mut table = Table::new_styled();
+    table.add_kv_row("ID:", deployment_id);
+    table.add_kv_row("Current limits:", deployment.limits());
+    table.add_kv_row("New limits:", limits);
+    c_println!("{}", table);
+    c_println!(
+        "Limits are enforced by every partition on its own, the deployment can see up to the \
+        number of partitions times these values."
+    );
+    confirm_or_exit("Are you sure you want to apply these limits?")?;
+
+    let request = match deployment {
+        Deployment::Http { .. } => UpdateDeploymentRequest::Http {
+            uri: None,
+            additional_headers: None,
+            use_http_11: None,
+            limits: Some(limits),
+            overwrite: false,
+            dry_run: false,
+        },
+        Deployment::Lambda { .. } => UpdateDeploymentRequest::Lambda {
+            arn: None,
+            assume_role_arn: None,
+            additional_headers: None,
+            limits: Some(limits),
+            overwrite: false,
+            dry_run: false,
+        },
+    };
+    let response = client
+        .patch_deployment(&opts.deployment_id, request)
+        .await?
+        .into_body()
+        .await?;
+    let (_, deployment, _) = Deployment::from_detailed_deployment_response(response);
+
+    c_println!();
+    c_success!(
+        "Limits of deployment {} set to: {}",
+        deployment_id,
+        deployment.limits()
+    );
+    Ok(())
+}
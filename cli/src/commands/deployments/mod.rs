@@ -9,6 +9,7 @@
 // by the Apache License, Version 2.0.
 
 mod describe;
+mod limits;
 mod list;
 mod register;
 mod remove;
@@ -26,4 +27,6 @@ pub enum Deployments {
     Describe(describe::Describe),
     /// Remove a drained deployment
     Remove(remove::Remove),
+    /// Set the concurrency and rate limits of a deployment
+    Limits(limits::Limits),
 }
@@ -27,6 +27,7 @@ use restate_admin_rest_model::version::AdminApiVersion;
 use restate_cli_util::ui::console::{Styled, StyledTable, confirm_or_exit};
 use restate_cli_util::ui::stylesheet::Style;
 use restate_cli_util::{c_eprintln, c_error, c_indent_table, c_indentln, c_success, c_warn};
+use restate_types::deployment::parse_deployment_uri;
 use restate_types::identifiers::LambdaARN;
 use restate_types::schema::service::ServiceMetadata;
 
@@ -99,8 +100,10 @@ pub struct Register {
 
     /// The URL or ARN that Restate server needs to fetch service information from.
     ///
-    /// The URL must be network-accessible from Restate server. In case of using
-    /// Lambda ARN, the ARN should include the function version.
+    /// The URL must be network-accessible from Restate server. Deployments listening
+    /// on a unix domain socket on the Restate server host can be registered with
+    /// `unix:///path/to/socket`. In case of using Lambda ARN, the ARN should include
+    /// the function version.
     #[clap(value_parser = parse_deployment)]
     deployment: DeploymentEndpoint,
 
@@ -185,7 +188,7 @@ fn parse_deployment(
     let deployment = if raw.starts_with("arn:") {
         DeploymentEndpoint::Lambda(LambdaARN::from_str(raw)?)
     } else {
-        let mut uri = Uri::from_str(raw).map_err(|e| format!("invalid URL({e})"))?;
+        let mut uri = parse_deployment_uri(raw).map_err(|e| format!("invalid URL({e})"))?;
         let mut parts = uri.into_parts();
         if parts.scheme.is_none() {
             parts.scheme = Some(http::uri::Scheme::HTTP);
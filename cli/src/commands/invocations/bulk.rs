@@ -0,0 +1,142 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! `--filter` support of the invocation commands, backed by the bulk operations API.
+
+use std::time::Duration;
+
+use anyhow::{Context, Result, anyhow, bail};
+use comfy_table::{Cell, Color, Table};
+
+use restate_admin_rest_model::invocations::{
+    BulkInvocationOperation, BulkInvocationOperationRequest, BulkInvocationOperationStatus,
+    InvocationFilter,
+};
+use restate_cli_util::ui::console::{StyledTable, confirm_or_exit};
+use restate_cli_util::{c_indent_table, c_println, c_success, c_warn};
+use restate_util_time::FriendlyDuration;
+
+use crate::cli_env::CliEnv;
+use crate::clients::{self, AdminClientInterface};
+use crate::ui::with_progress;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Builds the filter out of `KEY=VALUE` pairs. Supported keys are `service`, `handler`,
+/// `status`, `deployment` and `older-than`.
+pub(super) fn invocation_filter(pairs: &[(String, String)]) -> Result<InvocationFilter> {
+    let mut filter = InvocationFilter::default();
+    for (key, value) in pairs {
+        let field = match key.as_str() {
+            "service" => &mut filter.service,
+            "handler" => &mut filter.handler,
+            "status" => &mut filter.status,
+            "deployment" => &mut filter.deployment,
+            "older-than" => {
+                let older_than: FriendlyDuration = value
+                    .parse()
+                    .with_context(|| format!("invalid duration for older-than: `{value}`"))?;
+                filter.older_than = Some(older_than.to_std());
+                continue;
+            }
+            _ => bail!(
+                "unknown filter `{key}`, expected one of service, handler, status, deployment, older-than"
+            ),
+        };
+        *field = Some(value.clone());
+    }
+    Ok(filter)
+}
+
+/// Selects the invocations matching the filter, asks for confirmation, then applies the
+/// operation and waits for it to complete.
+pub(super) async fn run_bulk_operation(
+    env: &CliEnv,
+    operation: BulkInvocationOperation,
+    verb: &str,
+    filter: InvocationFilter,
+) -> Result<()> {
+    let client = clients::AdminClient::new(env).await?;
+    let request = |dry_run| BulkInvocationOperationRequest {
+        operation,
+        invocation_ids: None,
+        filter: Some(filter.clone()),
+        deployment: None,
+        operations_per_second: None,
+        dry_run,
+    };
+
+    let selected = with_progress(
+        "Selecting invocations...",
+        client.submit_bulk_invocation_operation(request(true)),
+    )
+    .await?
+    .into_body()
+    .await?;
+    if selected.invocation_ids.is_empty() {
+        bail!("No invocations match the filter");
+    }
+
+    c_println!(
+        "The filter matches {} invocations",
+        selected.invocation_ids.len()
+    );
+    confirm_or_exit(&format!(
+        "Are you sure you want to {verb} these invocations?"
+    ))?;
+
+    let job = client
+        .submit_bulk_invocation_operation(request(false))
+        .await?
+        .into_body()
+        .await?;
+    let job = with_progress("Applying the operation...", async {
+        let mut job = job;
+        while job.status == BulkInvocationOperationStatus::Running {
+            tokio::time::sleep(POLL_INTERVAL).await;
+            job = client
+                .get_bulk_invocation_operation(&job.job_id)
+                .await?
+                .into_body()
+                .await?;
+        }
+        anyhow::Ok(job)
+    })
+    .await?;
+
+    c_println!();
+    c_success!(
+        "Operation succeeded for {} invocations",
+        job.succeeded.len()
+    );
+
+    // Print failed ones, if any
+    if !job.failed.is_empty() {
+        c_println!();
+        c_warn!("Failed to {verb}:");
+        let mut failed_table = Table::new_styled();
+        failed_table.set_styled_header(vec!["ID", "REASON"]);
+        for failed in &job.failed {
+            failed_table.add_row(vec![
+                Cell::new(failed.invocation_id),
+                Cell::new(&failed.error).fg(Color::DarkRed),
+            ]);
+        }
+        c_indent_table!(0, failed_table);
+
+        return Err(anyhow!(
+            "Failed to {verb} {} invocations out of {}",
+            job.failed.len(),
+            job.invocation_ids.len()
+        ));
+    }
+
+    Ok(())
+}
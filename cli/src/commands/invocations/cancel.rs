@@ -15,16 +15,20 @@ use restate_cli_util::ui::console::{Styled, StyledTable, confirm_or_exit};
 use restate_cli_util::ui::stylesheet::Style;
 use restate_cli_util::{c_indent_table, c_println, c_success, c_warn};
 
+use restate_admin_rest_model::invocations::BulkInvocationOperation;
+
 use crate::cli_env::CliEnv;
 use crate::clients::batch_execute;
 use crate::clients::datafusion_helpers::find_active_invocations_simple;
 use crate::clients::{self, AdminClientInterface};
+use crate::commands::invocations::bulk::{invocation_filter, run_bulk_operation};
 use crate::commands::invocations::{
     DEFAULT_BATCH_INVOCATIONS_OPERATION_LIMIT, DEFAULT_BATCH_INVOCATIONS_OPERATION_PRINT_LIMIT,
     create_query_filter,
 };
 use crate::ui::invocations::render_simple_invocation_list;
 use crate::ui::with_progress;
+use crate::util::properties::parse_kv_arg;
 
 #[derive(Run, Parser, Collect, Clone)]
 #[cling(run = "run_cancel")]
@@ -39,7 +43,13 @@ pub struct Cancel {
     /// * `workflowName`
     /// * `workflowName/key`
     /// * `workflowName/key/handler`
-    pub(super) query: String,
+    #[clap(required_unless_present = "filter")]
+    pub(super) query: Option<String>,
+    /// Operate on all the invocations matching a `KEY=VALUE` filter, instead of a query.
+    /// Repeatable, invocations must match all filters. Supported keys are `service`, `handler`,
+    /// `status`, `deployment` and `older-than`, e.g. `--filter status=backing-off --filter older-than=1h`
+    #[clap(long, value_name = "KEY=VALUE", value_parser = parse_kv_arg, conflicts_with = "query")]
+    pub(super) filter: Vec<(String, String)>,
     /// Ungracefully kill the invocation and its children
     #[clap(long)]
     pub(super) kill: bool,
@@ -49,12 +59,21 @@ pub struct Cancel {
 }
 
 pub async fn run_cancel(State(env): State<CliEnv>, opts: &Cancel) -> Result<()> {
+    let Some(query) = &opts.query else {
+        let (operation, verb) = if opts.kill {
+            (BulkInvocationOperation::Kill, "kill")
+        } else {
+            (BulkInvocationOperation::Cancel, "cancel")
+        };
+        return run_bulk_operation(&env, operation, verb, invocation_filter(&opts.filter)?).await;
+    };
+
     let client = clients::AdminClient::new(&env).await?;
     let sql_client = clients::DataFusionHttpClient::from(client.clone());
 
     let filter = format!(
         "{} AND status != 'completed' LIMIT {}",
-        create_query_filter(&opts.query),
+        create_query_filter(query),
         opts.limit
     );
 
@@ -67,7 +86,7 @@ pub async fn run_cancel(State(env): State<CliEnv>, opts: &Cancel) -> Result<()>
         bail!(
             "No invocations found for query {}! Note that the cancel command only works on non-completed invocations. \
             If you want to remove a completed invocation, consider using the purge command instead.",
-            opts.query
+            query
         );
     };
 
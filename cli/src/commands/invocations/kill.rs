@@ -15,6 +15,7 @@ use cling::prelude::*;
 
 use crate::cli_env::CliEnv;
 use crate::commands::invocations::cancel::{Cancel, run_cancel};
+use crate::util::properties::parse_kv_arg;
 
 #[derive(Run, Parser, Collect, Clone)]
 #[cling(run = "run_kill")]
@@ -29,7 +30,13 @@ pub struct Kill {
     /// * `workflowName`
     /// * `workflowName/key`
     /// * `workflowName/key/handler`
-    query: String,
+    #[clap(required_unless_present = "filter")]
+    query: Option<String>,
+    /// Operate on all the invocations matching a `KEY=VALUE` filter, instead of a query.
+    /// Repeatable, invocations must match all filters. Supported keys are `service`, `handler`,
+    /// `status`, `deployment` and `older-than`, e.g. `--filter status=backing-off --filter older-than=1h`
+    #[clap(long, value_name = "KEY=VALUE", value_parser = parse_kv_arg, conflicts_with = "query")]
+    filter: Vec<(String, String)>,
     /// Limit the number of fetched invocations
     #[clap(long, default_value_t = DEFAULT_BATCH_INVOCATIONS_OPERATION_LIMIT)]
     limit: usize,
@@ -40,6 +47,7 @@ pub async fn run_kill(state: State<CliEnv>, opts: &Kill) -> Result<()> {
         state,
         &Cancel {
             query: opts.query.clone(),
+            filter: opts.filter.clone(),
             kill: true,
             limit: opts.limit,
         },
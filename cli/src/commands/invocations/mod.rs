@@ -8,6 +8,7 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+mod bulk;
 mod cancel;
 mod describe;
 mod kill;
@@ -16,6 +17,7 @@ mod pause;
 mod purge;
 mod restart_as_new;
 mod resume;
+mod tail;
 
 use cling::prelude::*;
 use restate_types::identifiers::InvocationId;
@@ -42,6 +44,8 @@ pub enum Invocations {
     Resume(resume::Resume),
     /// Pause an invocation, or a set of invocations.
     Pause(pause::Pause),
+    /// Follow the progress of an invocation, printing its journal and status changes as they happen.
+    Tail(tail::Tail),
 }
 
 /// See [cancel::Cancel] for more details on query
@@ -11,15 +11,18 @@
 use crate::cli_env::CliEnv;
 use crate::clients::datafusion_helpers::find_active_invocations_simple;
 use crate::clients::{self, AdminClientInterface, batch_execute};
+use crate::commands::invocations::bulk::{invocation_filter, run_bulk_operation};
 use crate::commands::invocations::{
     DEFAULT_BATCH_INVOCATIONS_OPERATION_LIMIT, DEFAULT_BATCH_INVOCATIONS_OPERATION_PRINT_LIMIT,
     create_query_filter,
 };
 use crate::ui::invocations::render_simple_invocation_list;
+use crate::util::properties::parse_kv_arg;
 
 use anyhow::{Result, anyhow, bail};
 use cling::prelude::*;
 use comfy_table::{Cell, Color, Table};
+use restate_admin_rest_model::invocations::BulkInvocationOperation;
 use restate_cli_util::ui::console::{StyledTable, confirm_or_exit};
 use restate_cli_util::{c_indent_table, c_println, c_success, c_warn};
 
@@ -37,19 +40,35 @@ pub struct Purge {
     /// * `workflowName`
     /// * `workflowName/key`
     /// * `workflowName/key/handler`
-    query: String,
+    #[clap(required_unless_present = "filter")]
+    query: Option<String>,
+    /// Operate on all the invocations matching a `KEY=VALUE` filter, instead of a query.
+    /// Repeatable, invocations must match all filters. Supported keys are `service`, `handler`,
+    /// `status`, `deployment` and `older-than`, e.g. `--filter status=backing-off --filter older-than=1h`
+    #[clap(long, value_name = "KEY=VALUE", value_parser = parse_kv_arg, conflicts_with = "query")]
+    filter: Vec<(String, String)>,
     /// Limit the number of fetched invocations
     #[clap(long, default_value_t = DEFAULT_BATCH_INVOCATIONS_OPERATION_LIMIT)]
     limit: usize,
 }
 
 pub async fn run_purge(State(env): State<CliEnv>, opts: &Purge) -> Result<()> {
+    let Some(query) = &opts.query else {
+        return run_bulk_operation(
+            &env,
+            BulkInvocationOperation::Purge,
+            "purge",
+            invocation_filter(&opts.filter)?,
+        )
+        .await;
+    };
+
     let client = clients::AdminClient::new(&env).await?;
     let sql_client = clients::DataFusionHttpClient::from(client.clone());
 
     let filter = format!(
         "{} AND status = 'completed' LIMIT {}",
-        create_query_filter(&opts.query),
+        create_query_filter(query),
         opts.limit
     );
 
@@ -58,7 +77,7 @@ pub async fn run_purge(State(env): State<CliEnv>, opts: &Purge) -> Result<()> {
         bail!(
             "No invocations found for query {}! Note that the purge command only works on completed invocations. \
             If you need to cancel/kill an invocation, consider using the cancel command instead.",
-            opts.query
+            query
         );
     };
 
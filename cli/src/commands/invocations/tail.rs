@@ -0,0 +1,222 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use anyhow::{Context, Result};
+use cling::prelude::*;
+use dialoguer::console::style;
+use futures::StreamExt;
+
+use restate_admin_rest_model::invocations::{
+    InvocationTailEvent, InvocationTailJournalEntry, InvocationTailJournalEvent,
+    InvocationTailStatus,
+};
+use restate_cli_util::{c_println, c_success, c_warn};
+
+use crate::cli_env::CliEnv;
+use crate::clients;
+use crate::clients::datafusion_helpers::InvocationState;
+use crate::ui::invocations::invocation_status;
+
+#[derive(Run, Parser, Collect, Clone)]
+#[cling(run = "run_tail")]
+pub struct Tail {
+    /// The ID of the invocation
+    invocation_id: String,
+
+    /// Print the events as newline-delimited JSON
+    #[clap(long)]
+    json: bool,
+}
+
+pub async fn run_tail(State(env): State<CliEnv>, opts: &Tail) -> Result<()> {
+    let client = clients::AdminClient::new(&env).await?;
+
+    let mut body = client
+        .tail_invocation(opts.invocation_id.trim())
+        .await?
+        .bytes_stream();
+    let mut buffer = Vec::new();
+    let mut completed = false;
+    while let Some(chunk) = body.next().await {
+        buffer.extend_from_slice(&chunk.context("The connection to the server was interrupted")?);
+        for event in drain_events(&mut buffer)? {
+            if let InvocationTailEvent::Status(status) = &event {
+                completed = status.is_completed();
+            }
+
+            if opts.json {
+                c_println!("{}", serde_json::to_string(&event)?);
+            } else {
+                render_event(&event);
+            }
+        }
+    }
+
+    if !opts.json {
+        c_println!();
+        if completed {
+            c_success!("Invocation {} completed", opts.invocation_id);
+        } else {
+            c_warn!(
+                "The invocation {} is not available anymore, it might have been purged",
+                opts.invocation_id
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Decodes the complete lines of the buffer, leaving the last incomplete line in it.
+fn drain_events(buffer: &mut Vec<u8>) -> Result<Vec<InvocationTailEvent>> {
+    let mut events = Vec::new();
+    while let Some(pos) = buffer.iter().position(|b| *b == b'\n') {
+        let line: Vec<_> = buffer.drain(..=pos).collect();
+        events.push(serde_json::from_slice(&line)?);
+    }
+    Ok(events)
+}
+
+fn render_event(event: &InvocationTailEvent) {
+    let line = match event {
+        InvocationTailEvent::Status(status) => format_status(status),
+        InvocationTailEvent::JournalEntry(entry) => format_journal_entry(entry),
+        InvocationTailEvent::JournalEvent(event) => format_journal_event(event),
+    };
+    c_println!("{line}");
+}
+
+fn format_status(status: &InvocationTailStatus) -> String {
+    let state: InvocationState = status.status.parse().unwrap_or_default();
+    let mut line = format!(
+        "{} Status: {}",
+        timestamp(status.modified_at.as_deref()),
+        invocation_status(state)
+    );
+    if let Some(retry_count) = status.retry_count.filter(|c| *c > 0) {
+        line.push_str(&format!(" (attempt {})", retry_count + 1));
+    }
+    if let Some(next_retry_at) = &status.next_retry_at {
+        line.push_str(&format!(", next retry at {next_retry_at}"));
+    }
+    match status.completion_result.as_deref() {
+        Some("success") => line.push_str(&format!(" {}", style("succeeded").green())),
+        Some("failure") => line.push_str(&format!(
+            " {}: {}",
+            style("failed").red(),
+            status.completion_failure.as_deref().unwrap_or_default()
+        )),
+        _ => {}
+    }
+    line
+}
+
+fn format_journal_entry(entry: &InvocationTailJournalEntry) -> String {
+    let mut line = format!(
+        "{} #{} {}",
+        timestamp(entry.appended_at.as_deref()),
+        entry.index,
+        style(&entry.entry_type).bold()
+    );
+    if let Some(name) = &entry.name {
+        line.push_str(&format!(" {}", style(name).italic()));
+    }
+    match entry.completed {
+        Some(true) => line.push_str(&format!(" {}", style("✓").green())),
+        Some(false) => line.push_str(&format!(" {}", style("⧗").dim())),
+        None => {}
+    }
+    line
+}
+
+fn format_journal_event(event: &InvocationTailJournalEvent) -> String {
+    let mut line = format!(
+        "{} {} after #{}",
+        timestamp(event.appended_at.as_deref()),
+        style(&event.event_type).yellow(),
+        event.after_journal_entry_index
+    );
+    // Paused events carry the failure that caused the pause, if any
+    let error_message = event.event.as_ref().and_then(|event| {
+        event
+            .get("error_message")
+            .or_else(|| event.get("last_failure")?.get("error_message"))?
+            .as_str()
+    });
+    if let Some(error_message) = error_message {
+        line.push_str(&format!(": {}", style(error_message).red()));
+    }
+    line
+}
+
+fn timestamp(timestamp: Option<&str>) -> String {
+    style(format!("[{}]", timestamp.unwrap_or("-")))
+        .dim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use dialoguer::console::strip_ansi_codes;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn events_are_decoded_across_chunks() {
+        let mut buffer = Vec::new();
+        buffer.extend_from_slice(br#"{"type":"status","status":"running"}"#);
+        buffer.extend_from_slice(b"\n");
+        buffer.extend_from_slice(br#"{"type":"journal_entry","index":0,"#);
+
+        let events = drain_events(&mut buffer).unwrap();
+        assert!(matches!(&events[..], [InvocationTailEvent::Status(s)] if s.status == "running"));
+
+        // The incomplete line is decoded once the rest of it arrives
+        buffer.extend_from_slice(br#""entry_type":"Command: Input"}"#);
+        assert!(drain_events(&mut buffer).unwrap().is_empty());
+        buffer.extend_from_slice(b"\n");
+        let events = drain_events(&mut buffer).unwrap();
+        assert!(
+            matches!(&events[..], [InvocationTailEvent::JournalEntry(e)] if e.entry_type == "Command: Input")
+        );
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn format_failed_status() {
+        let status = InvocationTailStatus {
+            status: "completed".to_owned(),
+            modified_at: Some("2026-01-01T00:00:00Z".to_owned()),
+            retry_count: Some(2),
+            next_retry_at: None,
+            last_failure: None,
+            last_failure_error_code: None,
+            completion_result: Some("failure".to_owned()),
+            completion_failure: Some("[500] boom".to_owned()),
+        };
+        let line = format_status(&status);
+        let line = strip_ansi_codes(&line);
+        assert!(line.starts_with("[2026-01-01T00:00:00Z] Status: "));
+        assert!(line.contains("(attempt 3)"));
+        assert!(line.ends_with("failed: [500] boom"));
+    }
+
+    #[test]
+    fn format_paused_event_with_its_failure() {
+        let event = InvocationTailJournalEvent {
+            event_type: "Paused".to_owned(),
+            after_journal_entry_index: 3,
+            appended_at: None,
+            event: Some(json!({"last_failure": {"error_message": "boom"}})),
+        };
+        let line = format_journal_event(&event);
+        assert_eq!(strip_ansi_codes(&line), "[-] Paused after #3: boom");
+    }
+}
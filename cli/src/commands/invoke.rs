@@ -0,0 +1,350 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::io::Read;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result, anyhow, bail};
+use cling::prelude::*;
+use http::header::CONTENT_TYPE;
+
+use restate_cli_util::ui::console::Styled;
+use restate_cli_util::ui::stylesheet::Style;
+use restate_cli_util::{c_println, c_success, c_tip, c_warn};
+use restate_types::schema::service::{HandlerMetadata, ServiceMetadata};
+
+use crate::cli_env::CliEnv;
+use crate::clients::{self, AdminClientInterface, IngressClient, SendResponse, SendStatus};
+use crate::ui::with_progress;
+
+const IDEMPOTENCY_KEY: &str = "idempotency-key";
+const X_RESTATE_ID: &str = "x-restate-id";
+
+#[derive(Run, Parser, Collect, Clone)]
+#[cling(run = "run_invoke")]
+pub struct Invoke {
+    /// The handler to invoke, e.g.:
+    /// * `serviceName/handler`
+    /// * `virtualObjectName/key/handler`
+    /// * `workflowName/key/handler`
+    target: String,
+
+    /// The JSON input of the handler
+    #[clap(conflicts_with = "file")]
+    input: Option<String>,
+
+    /// Read the JSON input of the handler from a file, or from stdin with `-`
+    #[clap(long, short)]
+    file: Option<PathBuf>,
+
+    /// Send the invocation without waiting for its output
+    #[clap(long)]
+    send: bool,
+
+    /// Send the invocation, executing it after the given delay, e.g. `10s` or `1h`
+    #[clap(long)]
+    delay: Option<String>,
+
+    /// Idempotency key of the invocation. Invoking again with the same idempotency key
+    /// attaches to the existing invocation instead of starting a new one.
+    #[clap(long)]
+    idempotency_key: Option<String>,
+
+    /// After sending the invocation, wait for its output
+    #[clap(long)]
+    attach: bool,
+
+    /// Don't validate the input against the JSON schema of the handler
+    #[clap(long)]
+    skip_validation: bool,
+
+    /// API key sent to the ingress. Defaults to $RESTATE_INGRESS_API_KEY, or the
+    /// `ingress_api_key` of the environment config
+    #[clap(long)]
+    ingress_api_key: Option<String>,
+}
+
+#[derive(Debug)]
+struct Target<'a> {
+    service: &'a str,
+    key: Option<&'a str>,
+    handler: &'a str,
+}
+
+impl<'a> Target<'a> {
+    fn parse(target: &'a str) -> Result<Self> {
+        let (service, rest) = target
+            .split_once('/')
+            .ok_or_else(|| anyhow!("Invalid target '{target}', expected service/handler"))?;
+        let (key, handler) = match rest.rsplit_once('/') {
+            Some((key, handler)) => (Some(key), handler),
+            None => (None, rest),
+        };
+        if service.is_empty() || handler.is_empty() {
+            bail!("Invalid target '{target}', expected service/handler or service/key/handler");
+        }
+
+        Ok(Self {
+            service,
+            key,
+            handler,
+        })
+    }
+}
+
+pub async fn run_invoke(State(env): State<CliEnv>, opts: &Invoke) -> Result<()> {
+    let target = Target::parse(&opts.target)?;
+    let send = opts.send || opts.delay.is_some();
+    if opts.attach && !send {
+        bail!("--attach can only be used together with --send or --delay");
+    }
+    let input = read_input(opts)?;
+
+    let admin_client = clients::AdminClient::new(&env).await?;
+    let service = admin_client
+        .get_service(target.service)
+        .await?
+        .into_body()
+        .await?;
+    let handler = resolve_handler(&service, &target)?;
+    if let (Some(schema), Some(input)) = (&handler.input_json_schema, &input)
+        && !opts.skip_validation
+    {
+        validate_input(schema, input)?;
+    }
+
+    let ingress_client = IngressClient::new(&env, opts.ingress_api_key.as_deref())?;
+    let mut path = vec![target.service];
+    path.extend(target.key);
+    path.push(target.handler);
+    if send {
+        path.push("send");
+    }
+    let mut url = ingress_client.url(path);
+    if let Some(delay) = &opts.delay {
+        url.query_pairs_mut().append_pair("delay", delay);
+    }
+
+    let mut request = ingress_client.prepare(reqwest::Method::POST, url);
+    if let Some(input) = input {
+        request = request
+            .header(CONTENT_TYPE, "application/json")
+            .body(serde_json::to_vec(&input)?);
+    }
+    if let Some(idempotency_key) = &opts.idempotency_key {
+        request = request.header(IDEMPOTENCY_KEY, idempotency_key);
+    }
+
+    let response = with_progress(
+        if send {
+            "Sending invocation..."
+        } else {
+            "Waiting for the invocation to complete..."
+        },
+        request.send(),
+    )
+    .await
+    .with_context(|| {
+        format!(
+            "Unable to reach the Restate ingress '{}'",
+            ingress_client.base_url()
+        )
+    })?;
+    let response = error_for_status(response).await?;
+
+    let invocation_id = if send {
+        let send_response: SendResponse = response.json().await?;
+        match send_response.status {
+            SendStatus::Accepted => c_success!("Invocation sent"),
+            SendStatus::PreviouslyAccepted => c_success!(
+                "An invocation with idempotency key '{}' was already sent",
+                opts.idempotency_key.as_deref().unwrap_or_default()
+            ),
+        }
+        c_println!("Invocation id: {}", send_response.invocation_id);
+        if let Some(execution_time) = &send_response.execution_time {
+            c_println!("Scheduled at: {execution_time}");
+        }
+
+        if opts.attach {
+            let attach_url = ingress_client.url([
+                "restate",
+                "invocation",
+                send_response.invocation_id.as_str(),
+                "attach",
+            ]);
+            let response = with_progress(
+                "Waiting for the invocation to complete...",
+                ingress_client
+                    .prepare(reqwest::Method::GET, attach_url)
+                    .send(),
+            )
+            .await?;
+            print_output(error_for_status(response).await?).await?;
+        }
+        Some(send_response.invocation_id)
+    } else {
+        let invocation_id = response
+            .headers()
+            .get(X_RESTATE_ID)
+            .and_then(|id| id.to_str().ok())
+            .map(str::to_owned);
+        c_success!("Invocation completed");
+        if let Some(invocation_id) = &invocation_id {
+            c_println!("Invocation id: {invocation_id}");
+        }
+        print_output(response).await?;
+        invocation_id
+    };
+
+    if let Some(invocation_id) = invocation_id {
+        c_println!();
+        c_tip!(
+            "To inspect the invocation, use:\n  restate invocations describe {}",
+            invocation_id
+        );
+    }
+
+    Ok(())
+}
+
+fn read_input(opts: &Invoke) -> Result<Option<serde_json::Value>> {
+    let raw = match (&opts.input, &opts.file) {
+        (Some(input), _) => input.as_bytes().to_vec(),
+        (None, Some(file)) if file.as_os_str() == "-" => {
+            let mut buf = Vec::new();
+            std::io::stdin()
+                .read_to_end(&mut buf)
+                .context("Failed to read the input from stdin")?;
+            buf
+        }
+        (None, Some(file)) => std::fs::read(file)
+            .with_context(|| format!("Failed to read the input from {}", file.display()))?,
+        (None, None) => return Ok(None),
+    };
+
+    serde_json::from_slice(&raw)
+        .map(Some)
+        .context("The input is not valid JSON")
+}
+
+fn resolve_handler<'a>(
+    service: &'a ServiceMetadata,
+    target: &Target<'_>,
+) -> Result<&'a HandlerMetadata> {
+    match (service.ty.is_keyed(), target.key) {
+        (true, None) => bail!(
+            "{} is a {}, specify the key of the invocation as {}/<key>/{}",
+            service.name,
+            service.ty,
+            service.name,
+            target.handler
+        ),
+        (false, Some(_)) => bail!(
+            "{} is a {}, which has no key. Specify the handler as {}/{}",
+            service.name,
+            service.ty,
+            service.name,
+            target.handler
+        ),
+        _ => {}
+    }
+
+    service.handlers.get(target.handler).ok_or_else(|| {
+        let mut handlers: Vec<_> = service.handlers.keys().map(String::as_str).collect();
+        handlers.sort_unstable();
+        anyhow!(
+            "Handler {} not found in {}, available handlers: {}",
+            target.handler,
+            service.name,
+            handlers.join(", ")
+        )
+    })
+}
+
+fn validate_input(schema: &serde_json::Value, input: &serde_json::Value) -> Result<()> {
+    let validator = match jsonschema::validator_for(schema) {
+        Ok(validator) => validator,
+        Err(err) => {
+            c_warn!(
+                "Skipping the input validation, the JSON schema of the handler is not supported: {err}"
+            );
+            return Ok(());
+        }
+    };
+
+    let errors: Vec<_> = validator
+        .iter_errors(input)
+        .map(|err| format!("  - {err}"))
+        .collect();
+    if !errors.is_empty() {
+        bail!(
+            "The input doesn't match the JSON schema of the handler:\n{}\nUse --skip-validation to send it anyway.",
+            errors.join("\n")
+        );
+    }
+    Ok(())
+}
+
+async fn error_for_status(response: reqwest::Response) -> Result<reqwest::Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    let body = response.text().await.unwrap_or_default();
+    // The ingress returns errors as {"message": "..."}
+    let message = serde_json::from_str::<serde_json::Value>(&body)
+        .ok()
+        .and_then(|body| body.get("message")?.as_str().map(str::to_owned))
+        .unwrap_or(body);
+    Err(anyhow!(
+        "{} {}",
+        Styled(Style::Danger, status),
+        message.trim()
+    ))
+}
+
+async fn print_output(response: reqwest::Response) -> Result<()> {
+    let body = response.bytes().await?;
+    if body.is_empty() {
+        return Ok(());
+    }
+
+    c_println!();
+    match serde_json::from_slice::<serde_json::Value>(&body) {
+        Ok(output) => c_println!("{}", serde_json::to_string_pretty(&output)?),
+        Err(_) => c_println!("{}", String::from_utf8_lossy(&body)),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_target() {
+        let target = Target::parse("Greeter/greet").unwrap();
+        assert_eq!(
+            (target.service, target.key, target.handler),
+            ("Greeter", None, "greet")
+        );
+
+        let target = Target::parse("Counter/my/key/add").unwrap();
+        assert_eq!(
+            (target.service, target.key, target.handler),
+            ("Counter", Some("my/key"), "add")
+        );
+
+        assert!(Target::parse("Greeter").is_err());
+        assert!(Target::parse("Greeter/").is_err());
+    }
+}
@@ -17,8 +17,10 @@ pub mod deployments;
 pub mod dev;
 pub mod examples;
 pub mod invocations;
+pub mod invoke;
 pub mod kafkaclusters;
 pub mod rules;
+pub mod schedules;
 pub mod services;
 pub mod sql;
 pub mod state;
@@ -18,7 +18,7 @@ use restate_cli_util::c_success;
 use restate_cli_util::ui::console::{StyledTable, confirm_or_exit};
 use restate_types::Version;
 
-use super::{fetch_rule, is_conflict, parse_pattern, render_concurrency};
+use super::{fetch_rule, is_conflict, parse_pattern, render_concurrency, render_rate};
 use crate::cli_env::CliEnv;
 use crate::clients::{AdminClient, AdminClientInterface, DataFusionHttpClient};
 
@@ -42,6 +42,7 @@ pub async fn run_delete(State(env): State<CliEnv>, opts: &Delete) -> Result<()>
     let mut table = Table::new_styled();
     table.add_kv_row("Pattern:", &canonical);
     table.add_kv_row("Concurrency:", render_concurrency(current.concurrency));
+    table.add_kv_row("Rate:", render_rate(current.rate.as_deref(), current.burst));
     if let Some(description) = &current.description {
         table.add_kv_row("Description:", description);
     }
@@ -16,7 +16,7 @@ use restate_cli_util::c_println;
 use restate_cli_util::ui::console::StyledTable;
 use restate_cli_util::ui::watcher::Watch;
 
-use super::{RuleRow, render_concurrency};
+use super::{RuleRow, render_concurrency, render_rate};
 use crate::cli_env::CliEnv;
 use crate::clients::DataFusionHttpClient;
 use crate::ui::datetime::DateTimeExt;
@@ -41,8 +41,8 @@ async fn list(env: &CliEnv, opts: &List) -> Result<()> {
     let client = DataFusionHttpClient::new(env).await?;
     let rows: Vec<RuleRow> = client
         .run_json_query(
-            "SELECT pattern, concurrency, description, disabled, version, last_modified \
-             FROM sys_rules ORDER BY pattern"
+            "SELECT pattern, concurrency, rate, burst, description, disabled, version, \
+             last_modified FROM sys_rules ORDER BY pattern"
                 .to_string(),
         )
         .await?;
@@ -57,13 +57,14 @@ async fn list(env: &CliEnv, opts: &List) -> Result<()> {
         table.set_styled_header(vec![
             "PATTERN",
             "CONCURRENCY",
+            "RATE",
             "DISABLED",
             "DESCRIPTION",
             "VERSION",
             "LAST MODIFIED",
         ]);
     } else {
-        table.set_styled_header(vec!["PATTERN", "CONCURRENCY", "DISABLED"]);
+        table.set_styled_header(vec!["PATTERN", "CONCURRENCY", "RATE", "DISABLED"]);
     }
 
     for row in rows {
@@ -73,6 +74,7 @@ async fn list(env: &CliEnv, opts: &List) -> Result<()> {
             table.add_row(vec![
                 Cell::new(row.pattern),
                 Cell::new(render_concurrency(row.concurrency)),
+                Cell::new(render_rate(row.rate.as_deref(), row.burst)),
                 Cell::new(disabled),
                 Cell::new(row.description.unwrap_or_default()),
                 Cell::new(row.version),
@@ -82,6 +84,7 @@ async fn list(env: &CliEnv, opts: &List) -> Result<()> {
             table.add_row(vec![
                 Cell::new(row.pattern),
                 Cell::new(render_concurrency(row.concurrency)),
+                Cell::new(render_rate(row.rate.as_deref(), row.burst)),
                 Cell::new(disabled),
             ]);
         }
@@ -26,6 +26,7 @@ use restate_admin_rest_model::rules::{RuleResponse, UpsertRuleRequest};
 use restate_cli_util::{c_println, c_success};
 use restate_limiter::{Precondition, RulePattern, UserLimits};
 use restate_types::Version;
+use restate_types::rate::Rate;
 use restate_util_string::ReString;
 
 use crate::cli_env::CliEnv;
@@ -34,7 +35,7 @@ use crate::clients::{AdminClient, AdminClientInterface, DataFusionHttpClient, Me
 #[derive(Run, Subcommand, Clone)]
 #[clap(visible_alias = "rule")]
 pub enum Rules {
-    /// List the configured concurrency-limit rules
+    /// List the configured concurrency and rate limit rules
     List(list::List),
     /// Create or update a rule
     Set(set::Set),
@@ -53,6 +54,10 @@ pub(crate) struct RuleRow {
     #[serde(default)]
     pub concurrency: Option<u32>,
     #[serde(default)]
+    pub rate: Option<String>,
+    #[serde(default)]
+    pub burst: Option<u32>,
+    #[serde(default)]
     pub description: Option<String>,
     pub disabled: bool,
     pub version: u32,
@@ -65,6 +70,26 @@ impl RuleRow {
     fn concurrency(&self) -> Option<NonZeroU32> {
         self.concurrency.and_then(NonZeroU32::new)
     }
+
+    /// The rate limit in its runtime shape.
+    fn rate(&self) -> Result<Option<Rate>> {
+        self.rate
+            .as_deref()
+            .map(|rate| {
+                rate.parse()
+                    .map_err(|e| anyhow!("Invalid rate '{rate}' of rule '{}': {e}", self.pattern))
+            })
+            .transpose()
+    }
+
+    fn burst(&self) -> Option<NonZeroU32> {
+        self.burst.and_then(NonZeroU32::new)
+    }
+
+    /// The limits of the rule in their runtime shape.
+    fn limits(&self) -> Result<UserLimits> {
+        Ok(UserLimits::new(self.concurrency()).with_rate(self.rate()?, self.burst()))
+    }
 }
 
 /// Renders a concurrency limit for display (`unlimited` when unset).
@@ -75,6 +100,15 @@ pub(crate) fn render_concurrency(limit: Option<u32>) -> String {
     }
 }
 
+/// Renders a rate limit and its burst for display (`unlimited` when unset).
+pub(crate) fn render_rate(rate: Option<&str>, burst: Option<u32>) -> String {
+    match (rate, burst) {
+        (Some(rate), Some(burst)) => format!("{rate} (burst {burst})"),
+        (Some(rate), None) => rate.to_string(),
+        (None, _) => "unlimited".to_string(),
+    }
+}
+
 /// Parses and validates a rule pattern, canonicalizing it client-side so we
 /// fail fast on bad input and can match against the `sys_rules` table.
 pub(crate) fn parse_pattern(pattern: &str) -> Result<RulePattern<ReString>> {
@@ -88,8 +122,8 @@ pub(crate) async fn fetch_rule(
     canonical_pattern: &str,
 ) -> Result<Option<RuleRow>> {
     let query = format!(
-        "SELECT pattern, concurrency, description, disabled, version, last_modified \
-         FROM sys_rules WHERE pattern = '{}'",
+        "SELECT pattern, concurrency, rate, burst, description, disabled, version, \
+         last_modified FROM sys_rules WHERE pattern = '{}'",
         escape_sql(canonical_pattern)
     );
     let rows: Vec<RuleRow> = client.run_json_query(query).await?;
@@ -142,7 +176,7 @@ pub(crate) async fn toggle_disabled(env: &CliEnv, pattern: &str, disabled: bool)
     let client = AdminClient::new(env).await?;
     let request = UpsertRuleRequest {
         pattern,
-        limits: UserLimits::new(current.concurrency()),
+        limits: current.limits()?,
         description: current.description.clone(),
         disabled,
         precondition: Precondition::Matches(Version::from(current.version)),
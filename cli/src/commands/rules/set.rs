@@ -17,6 +17,7 @@ use restate_admin_rest_model::rules::UpsertRuleRequest;
 use restate_cli_util::c_success;
 use restate_limiter::{Precondition, UserLimits};
 use restate_types::Version;
+use restate_types::rate::Rate;
 
 use super::{fetch_rule, parse_pattern, upsert_one};
 use crate::cli_env::CliEnv;
@@ -37,6 +38,21 @@ pub struct Set {
     #[clap(long)]
     unlimited: bool,
 
+    /// Maximum rate at which invocations can start, e.g. `100/s`, `60/m` or `1000/h`.
+    /// On a new rule, omitting this means unlimited; on an existing rule it leaves the
+    /// current rate unchanged.
+    #[clap(long, conflicts_with = "unlimited_rate")]
+    rate: Option<Rate>,
+
+    /// Maximum number of invocations that can start in a burst. Defaults to the number of
+    /// invocations allowed per rate period.
+    #[clap(long, conflicts_with = "unlimited_rate")]
+    burst: Option<NonZeroU32>,
+
+    /// Remove the rate limit of the rule
+    #[clap(long)]
+    unlimited_rate: bool,
+
     /// Description for the rule
     #[clap(long)]
     description: Option<String>,
@@ -62,7 +78,8 @@ pub async fn run_set(State(env): State<CliEnv>, opts: &Set) -> Result<()> {
                 None
             } else {
                 opts.concurrency
-            }),
+            })
+            .with_rate(opts.rate, opts.burst),
             description: opts.description.clone(),
             disabled: opts.disabled,
             precondition: Precondition::DoesNotExist,
@@ -78,13 +95,21 @@ pub async fn run_set(State(env): State<CliEnv>, opts: &Set) -> Result<()> {
             } else {
                 opts.concurrency.or_else(|| rule.concurrency())
             };
+            let (rate, burst) = if opts.unlimited_rate {
+                (None, None)
+            } else if opts.rate.is_some() {
+                // A new rate resets the burst, unless given as well
+                (opts.rate, opts.burst)
+            } else {
+                (rule.rate()?, opts.burst.or_else(|| rule.burst()))
+            };
             let description = opts
                 .description
                 .clone()
                 .or_else(|| rule.description.clone());
             UpsertRuleRequest {
                 pattern,
-                limits: UserLimits::new(concurrency),
+                limits: UserLimits::new(concurrency).with_rate(rate, burst),
                 description,
                 disabled: rule.disabled,
                 precondition: Precondition::Matches(Version::from(rule.version)),
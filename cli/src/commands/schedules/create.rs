@@ -0,0 +1,100 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use anyhow::{Context, Result};
+use cling::prelude::*;
+
+use restate_admin_rest_model::schedules::CreateScheduleRequest;
+use restate_cli_util::ui::console::{StyledTable, confirm_or_exit};
+use restate_cli_util::{c_println, c_success};
+use restate_types::schema::schedules::OverlapPolicy;
+
+use crate::cli_env::CliEnv;
+use crate::clients::{AdminClient, AdminClientInterface};
+
+#[derive(Run, Parser, Collect, Clone)]
+#[cling(run = "run_create")]
+pub struct Create {
+    /// Cron expression with 5 fields (`minute hour day-of-month month day-of-week`) or 6 fields
+    /// (with a leading `second` field), e.g. `"0 9 * * MON-FRI"`
+    cron: String,
+
+    /// Target handler, either `<service>/<handler>` or `<virtual_object>/<key>/<handler>`
+    target: String,
+
+    /// IANA timezone the cron expression is evaluated in, e.g. `Europe/Berlin`. Defaults to UTC.
+    #[clap(long, short)]
+    timezone: Option<String>,
+
+    /// JSON payload sent as request body on every tick
+    #[clap(long, short)]
+    payload: Option<String>,
+
+    /// Skip a tick if the invocation started by the previous tick is still running
+    #[clap(long)]
+    skip_overlapping: bool,
+}
+
+pub async fn run_create(State(env): State<CliEnv>, opts: &Create) -> Result<()> {
+    let (service, key, handler) = parse_target(&opts.target)?;
+    let payload = opts
+        .payload
+        .as_deref()
+        .map(serde_json::from_str)
+        .transpose()
+        .context("the payload must be valid JSON")?;
+
+    let request = CreateScheduleRequest {
+        cron: opts.cron.clone(),
+        timezone: opts.timezone.clone(),
+        service,
+        handler,
+        key,
+        payload,
+        overlap_policy: if opts.skip_overlapping {
+            OverlapPolicy::Skip
+        } else {
+            OverlapPolicy::Allow
+        },
+    };
+
+    let client = AdminClient::new(&env).await?;
+
+    let mut table = comfy_table::Table::new_styled();
+    table.add_kv_row("Cron:", &request.cron);
+    table.add_kv_row("Timezone:", request.timezone.as_deref().unwrap_or("UTC"));
+    table.add_kv_row("Target:", &opts.target);
+    table.add_kv_row("Overlap policy:", request.overlap_policy.to_string());
+    c_println!("{table}");
+    confirm_or_exit("Create this schedule?")?;
+
+    let response = client.create_schedule(request).await?.into_body().await?;
+
+    c_success!(
+        "Schedule {} created, next tick at {}",
+        response.id,
+        super::next_tick(&response)
+    );
+    Ok(())
+}
+
+fn parse_target(target: &str) -> Result<(String, Option<String>, String)> {
+    match target.split('/').collect::<Vec<_>>().as_slice() {
+        [service, handler] => Ok((service.to_string(), None, handler.to_string())),
+        [service, key, handler] => Ok((
+            service.to_string(),
+            Some(key.to_string()),
+            handler.to_string(),
+        )),
+        _ => anyhow::bail!(
+            "invalid target `{target}`, expected `<service>/<handler>` or `<virtual_object>/<key>/<handler>`"
+        ),
+    }
+}
@@ -0,0 +1,41 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use anyhow::Result;
+use cling::prelude::*;
+
+use restate_cli_util::c_println;
+use restate_cli_util::c_success;
+use restate_cli_util::ui::console::confirm_or_exit;
+
+use crate::cli_env::CliEnv;
+use crate::clients::{AdminClient, AdminClientInterface};
+
+#[derive(Run, Parser, Collect, Clone)]
+#[cling(run = "run_delete")]
+#[clap(visible_alias = "rm", alias = "remove")]
+pub struct Delete {
+    /// Schedule ID
+    id: String,
+}
+
+pub async fn run_delete(State(env): State<CliEnv>, opts: &Delete) -> Result<()> {
+    let client = AdminClient::new(&env).await?;
+    let schedule = client.get_schedule(&opts.id).await?.into_body().await?;
+
+    c_println!("{}", super::summary_table(&schedule));
+
+    confirm_or_exit(&format!("Delete schedule {}?", opts.id))?;
+
+    client.delete_schedule(&opts.id).await?.success_or_error()?;
+
+    c_success!("Schedule {} deleted", &opts.id);
+    Ok(())
+}
@@ -0,0 +1,61 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use anyhow::Result;
+use cling::prelude::*;
+
+use restate_cli_util::ui::console::StyledTable;
+use restate_cli_util::ui::watcher::Watch;
+use restate_cli_util::{c_println, c_title};
+
+use crate::cli_env::CliEnv;
+use crate::clients::{AdminClient, AdminClientInterface};
+use crate::ui::datetime::DateTimeExt;
+
+#[derive(Run, Parser, Collect, Clone)]
+#[cling(run = "run_describe")]
+#[clap(visible_alias = "get")]
+pub struct Describe {
+    /// Schedule ID
+    id: String,
+
+    #[clap(flatten)]
+    watch: Watch,
+}
+
+pub async fn run_describe(State(env): State<CliEnv>, opts: &Describe) -> Result<()> {
+    opts.watch.run(|| describe(&env, opts)).await
+}
+
+async fn describe(env: &CliEnv, opts: &Describe) -> Result<()> {
+    let client = AdminClient::new(env).await?;
+    let schedule = client.get_schedule(&opts.id).await?.into_body().await?;
+
+    let mut summary = super::summary_table(&schedule);
+    summary.add_kv_row("Revision:", schedule.revision);
+    summary.add_kv_row("Next tick:", super::next_tick(&schedule));
+    if let Some(created_at) = i64::try_from(schedule.created_at.as_u64())
+        .ok()
+        .and_then(chrono::DateTime::from_timestamp_millis)
+    {
+        summary.add_kv_row("Created at:", created_at.display());
+    }
+
+    c_title!("⏰", "Schedule");
+    c_println!("{summary}");
+
+    if let Some(payload) = &schedule.payload {
+        c_println!();
+        c_title!("📦", "Payload");
+        c_println!("{}", serde_json::to_string_pretty(payload)?);
+    }
+
+    Ok(())
+}
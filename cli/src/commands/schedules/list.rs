@@ -0,0 +1,56 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use anyhow::Result;
+use cling::prelude::*;
+use comfy_table::{Cell, Table};
+
+use restate_cli_util::c_println;
+use restate_cli_util::ui::console::StyledTable;
+use restate_cli_util::ui::watcher::Watch;
+
+use crate::cli_env::CliEnv;
+use crate::clients::{AdminClient, AdminClientInterface};
+
+#[derive(Run, Parser, Collect, Clone)]
+#[cling(run = "run_list")]
+#[clap(visible_alias = "ls")]
+pub struct List {
+    #[clap(flatten)]
+    watch: Watch,
+}
+
+pub async fn run_list(State(env): State<CliEnv>, opts: &List) -> Result<()> {
+    opts.watch.run(|| list(&env)).await
+}
+
+async fn list(env: &CliEnv) -> Result<()> {
+    let client = AdminClient::new(env).await?;
+    let schedules = client.list_schedules().await?.into_body().await?.schedules;
+
+    if schedules.is_empty() {
+        c_println!("No schedules registered.");
+        return Ok(());
+    }
+
+    let mut table = Table::new_styled();
+    table.set_styled_header(vec!["ID", "CRON", "TIMEZONE", "TARGET", "NEXT TICK"]);
+    for schedule in schedules {
+        table.add_row(vec![
+            Cell::new(schedule.id.to_string()),
+            Cell::new(&schedule.cron),
+            Cell::new(&schedule.timezone),
+            Cell::new(super::target(&schedule)),
+            Cell::new(super::next_tick(&schedule)),
+        ]);
+    }
+    c_println!("{table}");
+    Ok(())
+}
@@ -0,0 +1,67 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+mod create;
+mod delete;
+mod describe;
+mod list;
+mod update;
+
+use chrono::DateTime;
+use comfy_table::Table;
+
+use cling::prelude::*;
+use restate_admin_rest_model::schedules::ScheduleResponse;
+use restate_cli_util::ui::console::StyledTable;
+
+use crate::ui::datetime::DateTimeExt;
+
+#[derive(Run, Subcommand, Clone)]
+#[clap(visible_alias = "sched", alias = "schedule")]
+pub enum Schedules {
+    /// List the registered schedules
+    List(list::List),
+    /// Register a new schedule
+    #[clap(alias = "register")]
+    Create(create::Create),
+    /// Print detailed information about a schedule
+    Describe(describe::Describe),
+    /// Change the cron expression, timezone, payload or overlap policy of a schedule
+    Update(update::Update),
+    /// Remove a schedule
+    Delete(delete::Delete),
+}
+
+pub(crate) fn target(schedule: &ScheduleResponse) -> String {
+    match &schedule.key {
+        Some(key) => format!("{}/{}/{}", schedule.service, key, schedule.handler),
+        None => format!("{}/{}", schedule.service, schedule.handler),
+    }
+}
+
+pub(crate) fn next_tick(schedule: &ScheduleResponse) -> String {
+    schedule
+        .next_tick_at
+        .and_then(|next_tick_at| {
+            DateTime::from_timestamp_millis(i64::try_from(next_tick_at.as_u64()).ok()?)
+        })
+        .map(|next_tick_at| next_tick_at.display())
+        .unwrap_or_else(|| "(none)".to_owned())
+}
+
+pub(crate) fn summary_table(schedule: &ScheduleResponse) -> Table {
+    let mut table = Table::new_styled();
+    table.add_kv_row("ID:", schedule.id.to_string());
+    table.add_kv_row("Cron:", &schedule.cron);
+    table.add_kv_row("Timezone:", &schedule.timezone);
+    table.add_kv_row("Target:", target(schedule));
+    table.add_kv_row("Overlap policy:", schedule.overlap_policy.to_string());
+    table
+}
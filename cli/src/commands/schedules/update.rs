@@ -0,0 +1,95 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use anyhow::{Context, Result, bail};
+use cling::prelude::*;
+
+use restate_admin_rest_model::schedules::ModifyScheduleRequest;
+use restate_cli_util::ui::console::confirm_or_exit;
+use restate_cli_util::{c_println, c_success};
+use restate_types::schema::schedules::OverlapPolicy;
+
+use crate::cli_env::CliEnv;
+use crate::clients::{AdminClient, AdminClientInterface};
+
+#[derive(Run, Parser, Collect, Clone)]
+#[cling(run = "run_update")]
+pub struct Update {
+    /// Schedule ID
+    id: String,
+
+    /// New cron expression
+    #[clap(long, short)]
+    cron: Option<String>,
+
+    /// New IANA timezone the cron expression is evaluated in
+    #[clap(long, short)]
+    timezone: Option<String>,
+
+    /// New JSON payload sent as request body on every tick
+    #[clap(long, short)]
+    payload: Option<String>,
+
+    /// New overlap policy
+    #[clap(long, value_parser = parse_overlap_policy)]
+    overlap_policy: Option<OverlapPolicy>,
+}
+
+pub async fn run_update(State(env): State<CliEnv>, opts: &Update) -> Result<()> {
+    if opts.cron.is_none()
+        && opts.timezone.is_none()
+        && opts.payload.is_none()
+        && opts.overlap_policy.is_none()
+    {
+        bail!(
+            "nothing to update, pass at least one of --cron, --timezone, --payload or --overlap-policy"
+        );
+    }
+    let payload = opts
+        .payload
+        .as_deref()
+        .map(serde_json::from_str)
+        .transpose()
+        .context("the payload must be valid JSON")?;
+
+    let client = AdminClient::new(&env).await?;
+    let schedule = client.get_schedule(&opts.id).await?.into_body().await?;
+    c_println!("{}", super::summary_table(&schedule));
+    confirm_or_exit(&format!("Update schedule {}?", opts.id))?;
+
+    let response = client
+        .modify_schedule(
+            &opts.id,
+            ModifyScheduleRequest {
+                cron: opts.cron.clone(),
+                timezone: opts.timezone.clone(),
+                payload,
+                overlap_policy: opts.overlap_policy,
+            },
+        )
+        .await?
+        .into_body()
+        .await?;
+
+    c_success!(
+        "Schedule {} updated, next tick at {}",
+        response.id,
+        super::next_tick(&response)
+    );
+    Ok(())
+}
+
+fn parse_overlap_policy(value: &str) -> Result<OverlapPolicy> {
+    match value {
+        "allow" => Ok(OverlapPolicy::Allow),
+        "skip" => Ok(OverlapPolicy::Skip),
+        _ => bail!("unknown overlap policy `{value}`, expected `allow` or `skip`"),
+    }
+}
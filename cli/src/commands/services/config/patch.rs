@@ -86,6 +86,7 @@ async fn patch(env: &CliEnv, opts: &Patch) -> Result<()> {
         journal_retention: opts.journal_retention.map(FriendlyDuration::to_std),
         inactivity_timeout: opts.inactivity_timeout.map(FriendlyDuration::to_std),
         abort_timeout: opts.abort_timeout.map(FriendlyDuration::to_std),
+        ..Default::default()
     };
 
     apply_service_configuration_patch(&opts.service, admin_client, modify_request).await
@@ -103,6 +104,10 @@ pub(super) async fn apply_service_configuration_patch(
         && modify_request.inactivity_timeout.is_none()
         && modify_request.journal_retention.is_none()
         && modify_request.abort_timeout.is_none()
+        && modify_request.retry_policy_on_max_attempts.is_none()
+        && modify_request.retry_policy_dead_letter.is_none()
+        && modify_request.traffic_split.is_none()
+        && modify_request.handlers.is_empty()
     {
         c_println!("No changes requested");
         return Ok(());
@@ -140,6 +145,23 @@ pub(super) async fn apply_service_configuration_patch(
     if let Some(abort_timeout) = &modify_request.abort_timeout {
         table.add_kv_row("Abort timeout:", abort_timeout.friendly().to_days_span());
     }
+    if let Some(on_max_attempts) = &modify_request.retry_policy_on_max_attempts {
+        table.add_kv_row("On max attempts:", format!("{on_max_attempts:?}"));
+    }
+    if let Some(dead_letter) = &modify_request.retry_policy_dead_letter {
+        table.add_kv_row("Dead letter:", dead_letter);
+    }
+    for (handler_name, handler) in &modify_request.handlers {
+        if let Some(on_max_attempts) = &handler.retry_policy_on_max_attempts {
+            table.add_kv_row(
+                &format!("{handler_name} on max attempts:"),
+                format!("{on_max_attempts:?}"),
+            );
+        }
+        if let Some(dead_letter) = &handler.retry_policy_dead_letter {
+            table.add_kv_row(&format!("{handler_name} dead letter:"), dead_letter);
+        }
+    }
     c_println!("{table}");
     confirm_or_exit("Are you sure you want to apply these changes?")?;
 
@@ -172,6 +172,9 @@ async fn view(env: &CliEnv, opts: &View) -> Result<()> {
         "  On max attempts:",
         format!("{:?}", service.retry_policy.on_max_attempts),
     );
+    if let Some(dead_letter) = &service.retry_policy.dead_letter {
+        table.add_kv_row("  Dead letter:", dead_letter);
+    }
     table.add_kv_row(
         "  Initial interval:",
         service.retry_policy.initial_interval.friendly(),
@@ -251,6 +254,10 @@ async fn view(env: &CliEnv, opts: &View) -> Result<()> {
                     table.add_kv_row("    On max attempts:", format!("{:?}", on_max_attempts));
                 }
 
+                if let Some(dead_letter) = &handler.retry_policy.dead_letter {
+                    table.add_kv_row("    Dead letter:", dead_letter);
+                }
+
                 if let Some(initial_interval) = handler.retry_policy.initial_interval {
                     table.add_kv_row("    Initial interval:", initial_interval.friendly());
                 }
@@ -281,4 +288,5 @@ fn is_retry_policy_empty(
         && retry_policy.max_attempts.is_none()
         && retry_policy.max_interval.is_none()
         && retry_policy.on_max_attempts.is_none()
+        && retry_policy.dead_letter.is_none()
 }
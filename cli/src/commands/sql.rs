@@ -9,16 +9,21 @@
 // by the Apache License, Version 2.0.
 
 use std::io;
+use std::num::NonZeroU32;
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 
-use anyhow::Result;
+use anyhow::{Context, Result, bail};
 use arrow::error::ArrowError;
 use arrow::util::display::ArrayFormatter;
 use arrow::util::display::FormatOptions;
 use cling::prelude::*;
 use comfy_table::Cell;
 use comfy_table::Table;
+use futures::StreamExt;
+use tokio::io::AsyncWriteExt;
 
+use restate_admin_rest_model::query::QueryFormat;
 use restate_cli_util::c_eprintln;
 use restate_cli_util::c_println;
 use restate_cli_util::ui::console::Styled;
@@ -27,13 +32,28 @@ use restate_cli_util::ui::stylesheet::Style;
 use restate_cli_util::ui::watcher::Watch;
 
 use crate::cli_env::CliEnv;
+use crate::clients::{DataFusionHttpClient, SqlOperationsRequest, SqlQueryRequest};
 
 #[derive(Run, Parser, Collect, Clone)]
 #[cling(run = "run_sql")]
 pub struct Sql {
     /// The SQL query to run.
+    ///
+    /// Selecting exactly one of the functions restate_kill(id), restate_cancel(id),
+    /// restate_purge(id), restate_purge_journal(id), restate_pause(id), restate_resume(id) or
+    /// restate_restart_as_new(id) shows the invocations the operation would be applied to, for
+    /// example "SELECT restate_cancel(id) FROM sys_invocation WHERE status = 'backing-off'".
+    /// Pass --apply to apply it.
     query: String,
 
+    /// Apply the invocation function to the selected invocations, instead of only showing them.
+    #[arg(long)]
+    apply: bool,
+
+    /// Maximum number of invocations per second an invocation function is applied to.
+    #[arg(long)]
+    operations_per_second: Option<NonZeroU32>,
+
     #[clap(flatten)]
     watch: Watch,
 
@@ -44,16 +64,74 @@ pub struct Sql {
     /// Print result as json array instead of using the tabular format
     #[arg(long)]
     pub json: bool,
+
+    /// Write the result to a file instead of printing it. The format is chosen by the file
+    /// extension: `.parquet`, `.arrow` (Arrow IPC stream), `.csv` or `.json`.
+    #[arg(long, short, conflicts_with_all = ["json", "jsonl"])]
+    pub output: Option<PathBuf>,
+}
+
+/// The functions applying an operation to the selected invocations.
+const INVOCATION_FUNCTIONS: &[&str] = &[
+    "restate_kill",
+    "restate_cancel",
+    "restate_purge",
+    "restate_purge_journal",
+    "restate_pause",
+    "restate_resume",
+    "restate_restart_as_new",
+];
+
+/// Whether the query calls one of the invocation functions. Queries mentioning them otherwise,
+/// for example in a string literal, are rejected by the operations endpoint.
+fn is_operation_query(query: &str) -> bool {
+    let query: String = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    INVOCATION_FUNCTIONS
+        .iter()
+        .any(|function| query.contains(&format!("{function}(")))
 }
 
 pub async fn run_sql(State(env): State<CliEnv>, opts: &Sql) -> Result<()> {
-    opts.watch.run(|| run_query(&env, opts)).await
+    let is_operation_query = is_operation_query(&opts.query);
+    if is_operation_query && opts.watch.is_enabled() {
+        bail!("Queries applying an operation to invocations cannot be watched");
+    }
+    if !is_operation_query && (opts.apply || opts.operations_per_second.is_some()) {
+        bail!(
+            "--apply and --operations-per-second require the query to select one of the functions {}",
+            INVOCATION_FUNCTIONS.join(", ")
+        );
+    }
+    opts.watch
+        .run(|| run_query(&env, opts, is_operation_query))
+        .await?;
+    if is_operation_query && !opts.apply {
+        c_eprintln!("Dry run, no invocation was modified. Pass --apply to apply the operation.");
+    }
+    Ok(())
 }
 
-async fn run_query(env: &CliEnv, sql_opts: &Sql) -> Result<()> {
+async fn run_query(env: &CliEnv, sql_opts: &Sql, is_operation_query: bool) -> Result<()> {
     let client = crate::clients::DataFusionHttpClient::new(env).await?;
     let start_time = Instant::now();
-    let resp = client.run_arrow_query(sql_opts.query.clone()).await?;
+    let request = SqlQueryRequest {
+        query: sql_opts.query.clone(),
+        operations: is_operation_query.then(|| SqlOperationsRequest {
+            dry_run: !sql_opts.apply,
+            operations_per_second: sql_opts.operations_per_second.map(NonZeroU32::get),
+        }),
+        format: None,
+    };
+
+    if let Some(path) = &sql_opts.output {
+        return write_query_result(&client, request, path, start_time).await;
+    }
+
+    let resp = client.run_arrow_request(request).await?;
 
     let mut table = Table::new_styled();
     // add headers.
@@ -114,3 +192,66 @@ async fn run_query(env: &CliEnv, sql_opts: &Sql) -> Result<()> {
     );
     Ok(())
 }
+
+async fn write_query_result(
+    client: &DataFusionHttpClient,
+    request: SqlQueryRequest,
+    path: &Path,
+    start_time: Instant,
+) -> Result<()> {
+    let format = match path.extension().and_then(|extension| extension.to_str()) {
+        Some("parquet") => QueryFormat::Parquet,
+        Some("arrow" | "arrows" | "ipc") => QueryFormat::ArrowIpc,
+        Some("csv") => QueryFormat::Csv,
+        Some("json") => QueryFormat::Json,
+        _ => bail!(
+            "Cannot infer the format of {}, use one of the extensions .parquet, .arrow, .csv or .json",
+            path.display()
+        ),
+    };
+
+    let mut body = client
+        .run_streaming_request(SqlQueryRequest {
+            format: Some(format),
+            ..request
+        })
+        .await?
+        .bytes_stream();
+    let mut file = tokio::fs::File::create(path)
+        .await
+        .with_context(|| format!("Failed to create {}", path.display()))?;
+    let mut size = 0;
+    while let Some(chunk) = body.next().await {
+        let chunk = chunk.context("The connection to the server was interrupted")?;
+        size += chunk.len();
+        file.write_all(&chunk).await?;
+    }
+    file.sync_all().await?;
+
+    c_eprintln!(
+        "Wrote {} bytes to {}. Query took {:?}",
+        size,
+        path.display(),
+        Styled(Style::Notice, start_time.elapsed())
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_operation_queries() {
+        assert!(is_operation_query(
+            "SELECT restate_cancel(id) FROM sys_invocation"
+        ));
+        assert!(is_operation_query(
+            "select RESTATE_PURGE_JOURNAL ( id ) from sys_invocation"
+        ));
+        assert!(!is_operation_query(
+            "SELECT id FROM sys_invocation WHERE target_service_name = 'restate_kill'"
+        ));
+        assert!(!is_operation_query("SELECT * FROM sys_invocation"));
+    }
+}
@@ -0,0 +1,80 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::io::Write;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result, bail};
+use cling::prelude::*;
+use futures::StreamExt;
+use tokio::io::AsyncWriteExt;
+
+use restate_admin_rest_model::services::ServiceStateFormat;
+use restate_cli_util::c_success;
+
+use crate::cli_env::CliEnv;
+use crate::clients::AdminClient;
+use crate::commands::state::util::format_of;
+
+#[derive(Run, Parser, Collect, Clone)]
+#[cling(run = "run_export")]
+pub struct Export {
+    /// Name of the Virtual Object or Workflow
+    service: String,
+
+    /// File to write the export to. Files with the `.parquet` extension are written in the
+    /// Parquet format, others as newline-delimited JSON. Prints newline-delimited JSON if not set.
+    #[clap(long, short)]
+    output: Option<PathBuf>,
+}
+
+pub async fn run_export(State(env): State<CliEnv>, opts: &Export) -> Result<()> {
+    let format = opts
+        .output
+        .as_deref()
+        .map(format_of)
+        .unwrap_or(ServiceStateFormat::Ndjson);
+
+    let client = AdminClient::new(&env).await?;
+    let mut body = client
+        .export_service_state(&opts.service, format)
+        .await?
+        .bytes_stream();
+
+    let Some(path) = &opts.output else {
+        let mut stdout = std::io::stdout().lock();
+        while let Some(chunk) = body.next().await {
+            stdout.write_all(&chunk.context("The connection to the server was interrupted")?)?;
+        }
+        return Ok(());
+    };
+
+    if path.exists() {
+        bail!("The file {} already exists", path.display());
+    }
+    let mut file = tokio::fs::File::create(path)
+        .await
+        .with_context(|| format!("Failed to create {}", path.display()))?;
+    let mut size = 0;
+    while let Some(chunk) = body.next().await {
+        let chunk = chunk.context("The connection to the server was interrupted")?;
+        size += chunk.len();
+        file.write_all(&chunk).await?;
+    }
+    file.sync_all().await?;
+
+    c_success!(
+        "Exported the state of {} to {} ({} bytes)",
+        opts.service,
+        path.display(),
+        size
+    );
+    Ok(())
+}
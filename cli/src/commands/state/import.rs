@@ -0,0 +1,119 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::path::PathBuf;
+
+use anyhow::{Context, Result, bail};
+use cling::prelude::*;
+
+use restate_admin_rest_model::services::ImportVersionCheck;
+use restate_cli_util::ui::console::confirm_or_exit;
+use restate_cli_util::{c_error, c_println, c_success};
+
+use crate::cli_env::CliEnv;
+use crate::clients::AdminClient;
+use crate::commands::state::util::format_of;
+use crate::ui::with_progress;
+
+#[derive(Run, Parser, Collect, Clone)]
+#[cling(run = "run_import")]
+pub struct Import {
+    /// Name of the Virtual Object or Workflow
+    service: String,
+
+    /// File created by `restate state export`. Files with the `.parquet` extension are read in
+    /// the Parquet format, others as newline-delimited JSON.
+    file: PathBuf,
+
+    /// Replace the state of keys that already have state. By default, only keys without state
+    /// are imported.
+    #[clap(long)]
+    overwrite: bool,
+
+    /// Only replace the state of keys that weren't modified since the file was exported, for
+    /// example to write back state modified offline.
+    #[clap(long, conflicts_with = "overwrite")]
+    if_unchanged: bool,
+
+    /// Maximum number of keys imported per second
+    #[clap(long)]
+    keys_per_second: Option<u32>,
+}
+
+pub async fn run_import(State(env): State<CliEnv>, opts: &Import) -> Result<()> {
+    let file = tokio::fs::File::open(&opts.file)
+        .await
+        .with_context(|| format!("Failed to read {}", opts.file.display()))?;
+
+    let version_check = if opts.overwrite {
+        ImportVersionCheck::Overwrite
+    } else if opts.if_unchanged {
+        ImportVersionCheck::Unchanged
+    } else {
+        ImportVersionCheck::Empty
+    };
+    if opts.overwrite {
+        confirm_or_exit(&format!(
+            "Are you sure you want to replace the state of {} with the content of {}?",
+            opts.service,
+            opts.file.display()
+        ))?;
+    }
+
+    let client = AdminClient::new(&env).await?;
+    let response = with_progress(
+        "Importing state...",
+        client.import_service_state(
+            &opts.service,
+            format_of(&opts.file),
+            version_check,
+            opts.keys_per_second,
+            reqwest::Body::from(file),
+        ),
+    )
+    .await?;
+
+    c_success!(
+        "Submitted {} state entries for {} keys",
+        response.entries,
+        response.objects
+    );
+    match version_check {
+        ImportVersionCheck::Empty => {
+            c_println!("Keys that already had state are left untouched.")
+        }
+        ImportVersionCheck::Unchanged => {
+            c_println!("Keys modified since the export are left untouched.")
+        }
+        ImportVersionCheck::Overwrite => {}
+    }
+    for failed in &response.failed {
+        match &failed.scope {
+            Some(scope) => c_error!(
+                "Failed to import key '{}' of scope '{}': {}",
+                failed.service_key,
+                scope,
+                failed.error
+            ),
+            None => c_error!(
+                "Failed to import key '{}': {}",
+                failed.service_key,
+                failed.error
+            ),
+        }
+    }
+    if let Some(error) = &response.error {
+        bail!("The import stopped early: {error}");
+    }
+    if !response.failed.is_empty() {
+        bail!("Failed to import {} keys", response.failed.len());
+    }
+    Ok(())
+}
@@ -10,7 +10,9 @@
 
 mod clear;
 mod edit;
+mod export;
 mod get;
+mod import;
 mod patch;
 mod util;
 
@@ -26,4 +28,8 @@ pub enum ServiceState {
     Patch(patch::Patch),
     /// Clear of the state of a given service
     Clear(clear::Clear),
+    /// Export the state of all keys of a service to a file
+    Export(export::Export),
+    /// Import the state of a service from a file created by the export command
+    Import(import::Import),
 }
@@ -23,7 +23,7 @@ use restate_cli_util::c_warn;
 use serde::Deserialize;
 use serde_json::Value;
 
-use restate_admin_rest_model::services::ModifyServiceStateRequest;
+use restate_admin_rest_model::services::{ModifyServiceStateRequest, ServiceStateFormat};
 use restate_cli_util::ui::console::StyledTable;
 use restate_types::invocation::ServiceType;
 use restate_types::state_mut::StateMutationVersion;
@@ -107,6 +107,18 @@ pub(crate) async fn update_state(
     Ok(())
 }
 
+/// Exports and imports use Parquet for files with the `.parquet` extension.
+pub(crate) fn format_of(path: &Path) -> ServiceStateFormat {
+    if path
+        .extension()
+        .is_some_and(|extension| extension == "parquet")
+    {
+        ServiceStateFormat::Parquet
+    } else {
+        ServiceStateFormat::Ndjson
+    }
+}
+
 pub(crate) fn compute_version(user_state: &HashMap<String, Bytes>) -> String {
     let kvs: Vec<(Bytes, Bytes)> = user_state
         .iter()
@@ -32,12 +32,15 @@ use crate::util::properties::{
 #[derive(Run, Parser, Collect, Clone)]
 #[cling(run = "run_create")]
 pub struct Create {
-    /// Source URI, e.g. `kafka://<cluster_name>/<topic>`. May be omitted
-    /// when `--from-file` or `--edit` is used.
+    /// Source URI, e.g. `kafka://<cluster_name>/<topic>`,
+    /// `nats://<server>/<stream>`, `webhook://<name>`, `topic://<name>` or
+    /// `output://<service>/<handler>`.
+    /// May be omitted when `--from-file` or `--edit` is used.
     source: Option<String>,
 
-    /// Sink URI, e.g. `service://<service>/<handler>`. May be omitted when
-    /// `--from-file` or `--edit` is used.
+    /// Sink URI, e.g. `service://<service>/<handler>`, or
+    /// `kafka://<cluster_name>/<topic>` for `topic://` and `output://` sources. May be omitted
+    /// when `--from-file` or `--edit` is used.
     sink: Option<String>,
 
     /// Read source/sink/options from a file. `.properties` / `.conf` / `-`
@@ -159,8 +162,11 @@ fn edit_template(env: &CliEnv) -> Result<(String, String, HashMap<String, String
          # Format: librdkafka properties (key=value, # comments).\n\
          # Required:\n\
          #   source=kafka://<cluster_name>/<topic_name>\n\
+         #     (or nats://<server>/<stream>, webhook://<name>, topic://<name>\n\
+         #     or output://<service_name>/<handler_name>)\n\
          #   sink=service://<service_name>/<handler_name>\n\
-         # All other keys are passed through as Kafka consumer options.\n\
+         #     (or kafka://<cluster_name>/<topic_name> for topic:// and output:// sources)\n\
+         # All other keys are passed through as source options.\n\
          #\n\
          # Example:\n\
          # source=kafka://my-cluster/orders\n\
@@ -45,6 +45,9 @@ async fn describe(env: &CliEnv, opts: &Describe) -> Result<()> {
     summary.add_kv_row("ID:", sub.id.to_string());
     summary.add_kv_row("Source:", &sub.source);
     summary.add_kv_row("Sink:", &sub.sink);
+    if sub.paused {
+        summary.add_kv_row("Status:", "paused");
+    }
 
     // Best-effort cluster resolution. Failures are logged at debug only — we
     // never want describe to fail because the cluster lookup tripped.
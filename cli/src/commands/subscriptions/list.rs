@@ -56,13 +56,14 @@ async fn list(env: &CliEnv, opts: &List) -> Result<()> {
     subs.sort_by_key(|a| a.id.to_string());
 
     let mut table = Table::new_styled();
-    table.set_styled_header(vec!["ID", "SOURCE", "SINK", "OPTIONS"]);
+    table.set_styled_header(vec!["ID", "SOURCE", "SINK", "OPTIONS", "STATUS"]);
     for sub in subs {
         table.add_row(vec![
             Cell::new(sub.id.to_string()),
             Cell::new(sub.source),
             Cell::new(sub.sink),
             Cell::new(sub.options.len()),
+            Cell::new(if sub.paused { "paused" } else { "active" }),
         ]);
     }
     c_println!("{table}");
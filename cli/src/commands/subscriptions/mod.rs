@@ -12,6 +12,9 @@ mod create;
 mod delete;
 mod describe;
 mod list;
+mod pause;
+mod resume;
+mod seek;
 
 use cling::prelude::*;
 
@@ -27,6 +30,12 @@ pub enum Subscriptions {
     Describe(describe::Describe),
     /// Remove a subscription
     Delete(delete::Delete),
+    /// Pause the consumption of a Kafka subscription
+    Pause(pause::Pause),
+    /// Resume the consumption of a paused Kafka subscription
+    Resume(resume::Resume),
+    /// Move the consumers of a Kafka subscription to an offset, a timestamp, or the beginning or end of the topic
+    Seek(seek::Seek),
 }
 
 /// Parses `kafka://<cluster>/<topic>` and returns the cluster name. Returns
@@ -0,0 +1,92 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::time::UNIX_EPOCH;
+
+use anyhow::{Context, Result};
+use cling::prelude::*;
+
+use restate_admin_rest_model::subscriptions::{SeekSubscriptionRequest, SubscriptionSeekPosition};
+use restate_cli_util::ui::console::confirm_or_exit;
+use restate_cli_util::{c_success, c_warn};
+
+use crate::cli_env::CliEnv;
+use crate::clients::{AdminClient, AdminClientInterface};
+
+#[derive(Run, Parser, Collect, Clone)]
+#[cling(run = "run_seek")]
+#[clap(group = clap::ArgGroup::new("position").required(true))]
+pub struct Seek {
+    /// Subscription ID
+    id: String,
+
+    /// Move to the oldest record retained by the topic
+    #[clap(long, group = "position")]
+    earliest: bool,
+
+    /// Move to the end of the topic, skipping the records not consumed yet
+    #[clap(long, group = "position")]
+    latest: bool,
+
+    /// Move to the record with this offset
+    #[clap(long, group = "position")]
+    offset: Option<i64>,
+
+    /// Move to the first record produced at or after this RFC 3339 timestamp,
+    /// e.g. 2026-01-31T12:00:00Z
+    #[clap(long, group = "position")]
+    timestamp: Option<humantime::Timestamp>,
+
+    /// Only move this partition of the topic, instead of all of them
+    #[clap(long)]
+    partition: Option<i32>,
+}
+
+pub async fn run_seek(State(env): State<CliEnv>, opts: &Seek) -> Result<()> {
+    let position = if opts.earliest {
+        SubscriptionSeekPosition::Earliest
+    } else if opts.latest {
+        SubscriptionSeekPosition::Latest
+    } else if let Some(offset) = opts.offset {
+        SubscriptionSeekPosition::Offset(offset)
+    } else if let Some(timestamp) = &opts.timestamp {
+        let millis = timestamp
+            .duration_since(UNIX_EPOCH)
+            .context("the timestamp must be after the Unix epoch")?
+            .as_millis();
+        SubscriptionSeekPosition::Timestamp(millis as u64)
+    } else {
+        unreachable!("clap requires a position");
+    };
+
+    c_warn!(
+        "Records consumed again after moving back are ingested again, creating new invocations. Records skipped by moving forward are never ingested."
+    );
+    confirm_or_exit(&format!("Seek subscription {}?", opts.id))?;
+
+    let client = AdminClient::new(&env).await?;
+    client
+        .seek_subscription(
+            &opts.id,
+            SeekSubscriptionRequest {
+                position,
+                partition: opts.partition,
+            },
+        )
+        .await?
+        .into_body()
+        .await?;
+
+    c_success!(
+        "Seek of subscription {} requested, the consumers will move shortly",
+        &opts.id
+    );
+    Ok(())
+}
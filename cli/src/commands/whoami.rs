@@ -57,6 +57,10 @@ pub async fn run(State(env): State<CliEnv>) {
         table.add_row(vec!["Authentication Token", "(set)"]);
     }
 
+    if env.config.ingress_api_key.is_some() {
+        table.add_row(vec!["Ingress API Key", "(set)"]);
+    }
+
     c_println!("{}", table);
 
     c_println!();
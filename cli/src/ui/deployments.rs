@@ -193,6 +193,7 @@ pub fn add_deployment_to_kv_table(deployment: &Deployment, table: &mut Table) {
             .unwrap_or("unknown"),
     );
     table.add_kv_row("Created at:", created_at.display());
+    table.add_kv_row("Limits:", deployment.limits());
     for (header, value) in additional_headers.iter() {
         table.add_kv_row(
             "Deployment Additional Header:",
@@ -12,7 +12,9 @@ use http::{Uri, Version};
 use restate_serde_util::SerdeableHeaderHashMap;
 use restate_types::identifiers::ServiceRevision;
 use restate_types::identifiers::{DeploymentId, LambdaARN};
-use restate_types::schema::deployment::{EndpointLambdaCompression, ProtocolType};
+use restate_types::schema::deployment::{
+    DeploymentLimits, EndpointLambdaCompression, ProtocolType,
+};
 use restate_types::schema::info::SchemaInfo;
 use restate_types::schema::service::ServiceMetadata;
 use serde::{Deserialize, Serialize};
@@ -119,7 +121,8 @@ pub enum RegisterDeploymentRequest {
         /// # Uri
         ///
         /// Uri to use to discover/invoke the http deployment.
-        #[serde_as(as = "serde_with::DisplayFromStr")]
+        /// Deployments listening on a unix domain socket can be registered with `unix:///path/to/socket`.
+        #[serde_as(as = "restate_types::deployment::DeploymentUriAsString")]
         #[cfg_attr(feature = "schema", schema(value_type = String, format = "uri"))]
         uri: Uri,
 
@@ -358,6 +361,12 @@ pub enum DeploymentResponse {
         #[serde(default, skip_serializing_if = "Vec::is_empty")]
         info: Vec<SchemaInfo>,
 
+        /// # Limits
+        ///
+        /// Concurrency and rate limits of the invocations of this deployment.
+        #[serde(default, skip_serializing_if = "DeploymentLimits::is_unlimited")]
+        limits: DeploymentLimits,
+
         /// # Authentication
         ///
         /// Per-deployment authentication, if configured.
@@ -429,6 +438,12 @@ pub enum DeploymentResponse {
         /// List of configuration/deprecation information related to this deployment.
         #[serde(default, skip_serializing_if = "Vec::is_empty")]
         info: Vec<SchemaInfo>,
+
+        /// # Limits
+        ///
+        /// Concurrency and rate limits of the invocations of this deployment.
+        #[serde(default, skip_serializing_if = "DeploymentLimits::is_unlimited")]
+        limits: DeploymentLimits,
     },
 }
 
@@ -514,6 +529,12 @@ pub enum DetailedDeploymentResponse {
         #[serde(default, skip_serializing_if = "Vec::is_empty")]
         info: Vec<SchemaInfo>,
 
+        /// # Limits
+        ///
+        /// Concurrency and rate limits of the invocations of this deployment.
+        #[serde(default, skip_serializing_if = "DeploymentLimits::is_unlimited")]
+        limits: DeploymentLimits,
+
         /// # Authentication
         ///
         /// Per-deployment authentication, if configured.
@@ -586,6 +607,12 @@ pub enum DetailedDeploymentResponse {
         /// List of configuration/deprecation information related to this deployment.
         #[serde(default, skip_serializing_if = "Vec::is_empty")]
         info: Vec<SchemaInfo>,
+
+        /// # Limits
+        ///
+        /// Concurrency and rate limits of the invocations of this deployment.
+        #[serde(default, skip_serializing_if = "DeploymentLimits::is_unlimited")]
+        limits: DeploymentLimits,
     },
 }
 
@@ -608,8 +635,9 @@ pub enum UpdateDeploymentRequest {
         /// # Uri
         ///
         /// Uri to use to discover/invoke the http deployment.
+        /// Deployments listening on a unix domain socket can be registered with `unix:///path/to/socket`.
         #[serde(
-            with = "serde_with::As::<Option<serde_with::DisplayFromStr>>",
+            with = "serde_with::As::<Option<restate_types::deployment::DeploymentUriAsString>>",
             skip_serializing_if = "Option::is_none"
         )]
         #[cfg_attr(feature = "schema", schema(value_type = Option<String>, format = "uri"))]
@@ -630,6 +658,13 @@ pub enum UpdateDeploymentRequest {
         /// request-response mode.
         use_http_11: Option<bool>,
 
+        /// # Limits
+        ///
+        /// If set, replaces the concurrency and rate limits of the invocations of this deployment.
+        /// Changing only the limits doesn't run discovery again.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        limits: Option<DeploymentLimits>,
+
         /// # Overwrite
         ///
         /// If `true`, the update will overwrite the schema information, including the exposed service and handlers and service configuration, allowing **breaking changes** too. Use with caution.
@@ -665,6 +700,13 @@ pub enum UpdateDeploymentRequest {
         #[serde(default, skip_serializing_if = "Option::is_none")]
         additional_headers: Option<SerdeableHeaderHashMap>,
 
+        /// # Limits
+        ///
+        /// If set, replaces the concurrency and rate limits of the invocations of this deployment.
+        /// Changing only the limits doesn't run discovery again.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        limits: Option<DeploymentLimits>,
+
         /// # Overwrite
         ///
         /// If `true`, the update will overwrite the schema information, including the exposed service and handlers and service configuration, allowing **breaking changes** too. Use with caution.
@@ -8,8 +8,12 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::time::Duration;
+
 use restate_types::identifiers::{DeploymentId, InvocationId};
 use restate_types::invocation::client as invocation_client;
+use restate_types::time::MillisSinceEpoch;
+use restate_util_time::FriendlyDuration;
 use serde::{Deserialize, Serialize};
 
 /// Specifies which deployment to use when resuming or restarting an invocation.
@@ -50,6 +54,80 @@ pub struct RestartAsNewInvocationResponse {
     pub new_invocation_id: InvocationId,
 }
 
+// --- Tail types ---
+
+/// Event of the newline-delimited JSON stream returned when tailing an invocation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(utoipa::ToSchema))]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum InvocationTailEvent {
+    /// The status of the invocation changed.
+    Status(InvocationTailStatus),
+    /// A new entry was appended to the journal of the invocation.
+    JournalEntry(InvocationTailJournalEntry),
+    /// A new event was recorded for the invocation, e.g. a transient error.
+    JournalEvent(InvocationTailJournalEvent),
+}
+
+/// Status of the invocation, as reported by `sys_invocation`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(utoipa::ToSchema))]
+pub struct InvocationTailStatus {
+    /// One of `pending`, `scheduled`, `ready`, `running`, `backing-off`, `suspended`, `paused` or `completed`.
+    pub status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modified_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_count: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_retry_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_failure: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_failure_error_code: Option<String>,
+    /// Either `success` or `failure`, set once the invocation is completed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completion_result: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completion_failure: Option<String>,
+}
+
+impl InvocationTailStatus {
+    pub fn is_completed(&self) -> bool {
+        self.status == "completed"
+    }
+}
+
+/// Journal entry of the invocation, as reported by `sys_journal`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(utoipa::ToSchema))]
+pub struct InvocationTailJournalEntry {
+    pub index: u32,
+    pub entry_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub completed: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub appended_at: Option<String>,
+    /// The entry, without its payloads.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entry: Option<serde_json::Value>,
+}
+
+/// Event of the invocation, as reported by `sys_journal_events`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(utoipa::ToSchema))]
+pub struct InvocationTailJournalEvent {
+    /// One of `TransientError`, `Paused` or `Suspended`.
+    pub event_type: String,
+    pub after_journal_entry_index: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub appended_at: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event: Option<serde_json::Value>,
+}
+
 // --- Batch operation types ---
 
 /// Maximum number of invocations in a single batch operation
@@ -88,7 +166,7 @@ pub struct BatchRestartAsNewRequest {
 }
 
 /// Information about a failed invocation operation
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "schema", derive(utoipa::ToSchema))]
 pub struct FailedInvocationOperation {
     /// The invocation ID that failed
@@ -108,7 +186,7 @@ pub struct BatchOperationResult {
 }
 
 /// Successful restart-as-new operation result
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "schema", derive(utoipa::ToSchema))]
 pub struct RestartedInvocation {
     /// The original invocation ID
@@ -126,3 +204,139 @@ pub struct BatchRestartAsNewResult {
     /// Invocations that failed with error details
     pub failed: Vec<FailedInvocationOperation>,
 }
+
+// --- Bulk operation types ---
+
+/// Maximum number of invocations a single bulk operation can select
+pub const BULK_OPERATION_MAX_SIZE: usize = 10_000;
+
+/// Operation applied by a bulk operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum BulkInvocationOperation {
+    Cancel,
+    Kill,
+    Purge,
+    PurgeJournal,
+    Pause,
+    Resume,
+    RestartAsNew,
+}
+
+/// Selects invocations by their properties. Only invocations matching all the set fields are
+/// selected, at least one field must be set.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(utoipa::ToSchema))]
+pub struct InvocationFilter {
+    /// # Service
+    ///
+    /// Name of the target service.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub service: Option<String>,
+
+    /// # Handler
+    ///
+    /// Name of the target handler.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub handler: Option<String>,
+
+    /// # Status
+    ///
+    /// Status of the invocation, as reported by the `status` column of `sys_invocation`, for
+    /// example `backing-off` or `suspended`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+
+    /// # Deployment
+    ///
+    /// Id of the deployment the invocation is pinned to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deployment: Option<String>,
+
+    /// # Older than
+    ///
+    /// Minimum age of the invocation, since its creation.
+    ///
+    /// Can be configured using the [`jiff::fmt::friendly`](https://docs.rs/jiff/latest/jiff/fmt/friendly/index.html) format or ISO8601, for example `5 hours`.
+    #[serde(
+        default,
+        with = "serde_with::As::<Option<FriendlyDuration>>",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[cfg_attr(feature = "schema", schema(value_type = Option<String>))]
+    pub older_than: Option<Duration>,
+}
+
+impl InvocationFilter {
+    pub fn is_empty(&self) -> bool {
+        self.service.is_none()
+            && self.handler.is_none()
+            && self.status.is_none()
+            && self.deployment.is_none()
+            && self.older_than.is_none()
+    }
+}
+
+/// Request body of bulk operations. Exactly one of `invocation_ids` and `filter` must be set.
+#[derive(Debug, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(utoipa::ToSchema))]
+pub struct BulkInvocationOperationRequest {
+    pub operation: BulkInvocationOperation,
+
+    /// Invocations to operate on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub invocation_ids: Option<Vec<InvocationId>>,
+
+    /// Operate on the invocations matching this filter.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filter: Option<InvocationFilter>,
+
+    /// Deployment to use when resuming or restarting as new.
+    /// Defaults to "Keep" when resuming, and to "Latest" when restarting as new.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deployment: Option<PatchDeploymentId>,
+
+    /// Maximum number of invocations per second the operation is applied to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub operations_per_second: Option<u32>,
+
+    /// Only select the invocations, without applying the operation.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum BulkInvocationOperationStatus {
+    /// The operation is being applied.
+    Running,
+    /// The operation was applied to all selected invocations.
+    Completed,
+    /// The operation wasn't applied, because the request was a dry run.
+    DryRun,
+}
+
+/// A bulk operation, with the results for the invocations it was applied to so far.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(utoipa::ToSchema))]
+pub struct BulkInvocationOperationJob {
+    pub job_id: String,
+    pub operation: BulkInvocationOperation,
+    pub status: BulkInvocationOperationStatus,
+    /// All the invocations selected by the request
+    pub invocation_ids: Vec<InvocationId>,
+    /// Invocations the operation was applied to successfully
+    pub succeeded: Vec<InvocationId>,
+    /// Invocations restarted as new, with their new IDs
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub restarted: Vec<RestartedInvocation>,
+    /// Invocations the operation failed for, with error details
+    pub failed: Vec<FailedInvocationOperation>,
+    #[cfg_attr(feature = "schema", schema(value_type = u64))]
+    pub created_at: MillisSinceEpoch,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schema", schema(value_type = Option<u64>))]
+    pub completed_at: Option<MillisSinceEpoch>,
+}
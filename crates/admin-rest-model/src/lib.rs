@@ -14,6 +14,8 @@ pub mod invocations;
 pub mod kafka_clusters;
 pub mod query;
 pub mod rules;
+pub mod schedules;
 pub mod services;
 pub mod subscriptions;
+pub mod time;
 pub mod version;
@@ -8,7 +8,7 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 
 #[serde_as]
@@ -17,4 +17,58 @@ use serde_with::serde_as;
 pub struct QueryRequest {
     /// SQL query to run against the storage
     pub query: String,
+    /// Format of the query results. If not set, the results are returned as JSON when the
+    /// `Accept` header is `application/json`, and as an Arrow IPC stream otherwise.
+    #[serde(default)]
+    pub format: Option<QueryFormat>,
+}
+
+#[derive(Debug, Deserialize)]
+#[cfg_attr(feature = "schema", derive(utoipa::ToSchema))]
+pub struct QueryOperationsRequest {
+    /// SQL query selecting one of the invocation functions, for example
+    /// `SELECT restate_cancel(id) FROM sys_invocation WHERE status = 'backing-off'`
+    pub query: String,
+    /// Only return the selected invocations, without applying the operation. Defaults to `true`,
+    /// so applying the operation must be requested explicitly.
+    #[serde(default = "default_dry_run")]
+    pub dry_run: bool,
+    /// Limits the number of invocations the operation is applied to per second.
+    #[serde(default)]
+    pub operations_per_second: Option<u32>,
+    /// Format of the query results. If not set, the results are returned as JSON when the
+    /// `Accept` header is `application/json`, and as an Arrow IPC stream otherwise.
+    #[serde(default)]
+    pub format: Option<QueryFormat>,
+}
+
+fn default_dry_run() -> bool {
+    true
+}
+
+/// Format of the query results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schema", derive(utoipa::ToSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum QueryFormat {
+    /// A JSON object, with the rows as array under the `rows` key.
+    Json,
+    /// An Arrow IPC stream.
+    ArrowIpc,
+    /// A Parquet file.
+    Parquet,
+    /// CSV, with a header row.
+    Csv,
+}
+
+impl QueryFormat {
+    /// Content type of the query results in this format.
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            QueryFormat::Json => "application/json",
+            QueryFormat::ArrowIpc => "application/vnd.apache.arrow.stream",
+            QueryFormat::Parquet => "application/vnd.apache.parquet",
+            QueryFormat::Csv => "text/csv",
+        }
+    }
 }
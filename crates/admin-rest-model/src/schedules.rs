@@ -0,0 +1,130 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use serde::{Deserialize, Serialize};
+
+use restate_types::identifiers::ScheduleId;
+use restate_types::schema::schedules::{OverlapPolicy, Schedule};
+use restate_types::time::MillisSinceEpoch;
+
+#[cfg_attr(feature = "schema", derive(utoipa::ToSchema))]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreateScheduleRequest {
+    /// # Cron expression
+    ///
+    /// Cron expression with 5 (`minute hour day-of-month month day-of-week`) or 6 fields
+    /// (with a leading `second` field), e.g. `0 9 * * MON-FRI`.
+    pub cron: String,
+    /// # Timezone
+    ///
+    /// IANA timezone the cron expression is evaluated in, e.g. `Europe/Berlin`. Defaults to `UTC`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    /// # Service
+    ///
+    /// Name of the service or virtual object to invoke.
+    pub service: String,
+    /// # Handler
+    ///
+    /// Name of the handler to invoke.
+    pub handler: String,
+    /// # Key
+    ///
+    /// Key of the virtual object to invoke. Required for virtual objects, must be empty for services.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    /// # Payload
+    ///
+    /// JSON payload sent as the request body on every tick.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload: Option<serde_json::Value>,
+    /// # Overlap policy
+    ///
+    /// What to do when a tick is due while the invocation started by the previous tick is still running.
+    #[serde(default)]
+    pub overlap_policy: OverlapPolicy,
+}
+
+#[cfg_attr(feature = "schema", derive(utoipa::ToSchema))]
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ModifyScheduleRequest {
+    /// # Cron expression
+    ///
+    /// If set, replaces the cron expression of the schedule.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cron: Option<String>,
+    /// # Timezone
+    ///
+    /// If set, replaces the timezone of the schedule.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    /// # Payload
+    ///
+    /// If set, replaces the payload sent on every tick.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload: Option<serde_json::Value>,
+    /// # Overlap policy
+    ///
+    /// If set, replaces the overlap policy of the schedule.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub overlap_policy: Option<OverlapPolicy>,
+}
+
+/// Schedule details.
+#[cfg_attr(feature = "schema", derive(utoipa::ToSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ScheduleResponse {
+    pub id: ScheduleId,
+    pub cron: String,
+    pub timezone: String,
+    pub service: String,
+    pub handler: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload: Option<serde_json::Value>,
+    pub overlap_policy: OverlapPolicy,
+    /// Incremented on every modification of the schedule.
+    pub revision: u32,
+    /// Time of the next tick, if the cron expression has any further occurrence.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "schema", schema(value_type = Option<u64>))]
+    pub next_tick_at: Option<MillisSinceEpoch>,
+    #[cfg_attr(feature = "schema", schema(value_type = u64))]
+    pub created_at: MillisSinceEpoch,
+}
+
+impl From<Schedule> for ScheduleResponse {
+    fn from(value: Schedule) -> Self {
+        Self {
+            id: value.id(),
+            cron: value.cron().to_owned(),
+            timezone: value.timezone().to_owned(),
+            service: value.target().service_name().to_owned(),
+            handler: value.target().handler_name().to_owned(),
+            key: value.target().key().map(str::to_owned),
+            // The payload is validated as JSON when the schedule is registered
+            payload: (!value.payload().is_empty())
+                .then(|| serde_json::from_slice(value.payload()).ok())
+                .flatten(),
+            overlap_policy: value.overlap_policy(),
+            revision: value.revision(),
+            next_tick_at: value.next_tick_after(MillisSinceEpoch::now()),
+            created_at: value.created_at(),
+        }
+    }
+}
+
+/// List of all schedules.
+#[cfg_attr(feature = "schema", derive(utoipa::ToSchema))]
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ListSchedulesResponse {
+    pub schedules: Vec<ScheduleResponse>,
+}
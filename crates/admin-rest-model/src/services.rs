@@ -13,8 +13,11 @@ use std::time::Duration;
 
 use bytes::Bytes;
 use serde::{Deserialize, Serialize};
+use serde_with::serde_as;
 
-use restate_types::schema::service::ServiceMetadata;
+use restate_types::identifiers::DeploymentId;
+use restate_types::schema::invocation_target::{DeadLetterTarget, OnMaxAttempts};
+use restate_types::schema::service::{CanaryRollbackPolicy, ServiceMetadata};
 use restate_util_time::FriendlyDuration;
 
 /// List of all registered services.
@@ -25,7 +28,7 @@ pub struct ListServicesResponse {
 }
 
 #[cfg_attr(feature = "schema", derive(utoipa::ToSchema))]
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct ModifyServiceRequest {
     /// # Public
     ///
@@ -91,6 +94,69 @@ pub struct ModifyServiceRequest {
     /// This overrides the default abort timeout set in invoker options.
     #[serde(default, with = "serde_with::As::<Option<FriendlyDuration>>")]
     pub abort_timeout: Option<Duration>,
+
+    /// # Retry policy on max attempts
+    ///
+    /// Behavior when the retry policy max attempts are reached, for all the handlers of this service.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_policy_on_max_attempts: Option<OnMaxAttempts>,
+
+    /// # Retry policy dead letter
+    ///
+    /// Handler receiving the invocations that exhausted their retries, when `retry_policy_on_max_attempts` is `DeadLetter`.
+    /// The dead-letter handler must belong to another service of type `Service`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_policy_dead_letter: Option<DeadLetterTarget>,
+
+    /// # Traffic split
+    ///
+    /// Split new invocations between the latest deployment of the service and a deployment exposing a previous revision.
+    /// Invocations that already started stay on their deployment.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub traffic_split: Option<ModifyTrafficSplitRequest>,
+
+    /// # Handlers
+    ///
+    /// Handler level overrides, keyed by handler name.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub handlers: HashMap<String, ModifyHandlerRequest>,
+}
+
+#[cfg_attr(feature = "schema", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModifyTrafficSplitRequest {
+    /// # Stable deployment id
+    ///
+    /// Deployment exposing a previous revision of the service. It receives the new invocations not routed to the latest deployment.
+    pub stable_deployment_id: DeploymentId,
+
+    /// # Canary weight
+    ///
+    /// Percentage of new invocations routed to the latest deployment of the service, between 0 and 100.
+    /// Set it to 100 to remove the split, or to 0 to route all new invocations to the stable deployment.
+    pub canary_weight: u8,
+
+    /// # Rollback
+    ///
+    /// If set, the split is rolled back automatically when the latest deployment fails too often.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rollback: Option<CanaryRollbackPolicy>,
+}
+
+#[cfg_attr(feature = "schema", derive(utoipa::ToSchema))]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ModifyHandlerRequest {
+    /// # Retry policy on max attempts
+    ///
+    /// Behavior when the retry policy max attempts are reached for this handler.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_policy_on_max_attempts: Option<OnMaxAttempts>,
+
+    /// # Retry policy dead letter
+    ///
+    /// Handler receiving the invocations of this handler that exhausted their retries, when `retry_policy_on_max_attempts` is `DeadLetter`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_policy_dead_letter: Option<DeadLetterTarget>,
 }
 
 #[cfg_attr(feature = "schema", derive(utoipa::ToSchema))]
@@ -120,3 +186,108 @@ pub struct ModifyServiceStateRequest {
     #[cfg_attr(feature = "schema", schema(value_type = HashMap<String, Vec<u8>>))]
     pub new_state: HashMap<String, Bytes>,
 }
+
+/// Format of service state exports and imports.
+#[cfg_attr(feature = "schema", derive(utoipa::ToSchema))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ServiceStateFormat {
+    /// Newline-delimited JSON, one [`ServiceStateEntry`] per line.
+    #[default]
+    Ndjson,
+    /// Apache Parquet, with the columns of [`ServiceStateEntry`].
+    Parquet,
+}
+
+/// A single state entry of a Virtual Object or Workflow, as exported and imported.
+#[serde_as]
+#[cfg_attr(feature = "schema", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServiceStateEntry {
+    /// # Scope
+    ///
+    /// Scope of the Virtual Object instance, if scoped.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+
+    /// # Service key
+    ///
+    /// Key of the Virtual Object or Workflow.
+    pub service_key: String,
+
+    /// # Key
+    ///
+    /// The state key.
+    pub key: String,
+
+    /// # Value
+    ///
+    /// The hex-encoded state value.
+    #[serde_as(as = "serde_with::hex::Hex")]
+    #[cfg_attr(feature = "schema", schema(value_type = String))]
+    pub value: Vec<u8>,
+
+    /// # Version
+    ///
+    /// Version of the whole state of the key when it was exported, checked by imports using the
+    /// `unchanged` version check.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+/// Condition the state of a key must meet for the import to replace it.
+#[cfg_attr(feature = "schema", derive(utoipa::ToSchema))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ImportVersionCheck {
+    /// Only import keys without state.
+    #[default]
+    Empty,
+    /// Only import keys whose state still has the `version` of the imported entries, meaning
+    /// it wasn't modified since it was exported.
+    Unchanged,
+    /// Replace the state of keys regardless of their current state.
+    Overwrite,
+}
+
+#[cfg_attr(feature = "schema", derive(utoipa::ToSchema))]
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ImportServiceStateResponse {
+    /// # Objects
+    ///
+    /// Number of Virtual Objects or Workflows whose state mutation was submitted. The version
+    /// check is applied when the mutation is processed, mutations failing it are ignored.
+    pub objects: usize,
+
+    /// # Entries
+    ///
+    /// Number of state entries of the submitted mutations.
+    pub entries: usize,
+
+    /// # Failed
+    ///
+    /// Keys whose state mutation couldn't be submitted.
+    #[serde(default)]
+    pub failed: Vec<FailedServiceStateImport>,
+
+    /// # Error
+    ///
+    /// Set when the import stopped early, for example because of an invalid entry. The keys
+    /// counted in `objects` were imported, the following ones weren't.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[cfg_attr(feature = "schema", derive(utoipa::ToSchema))]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FailedServiceStateImport {
+    /// # Scope
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+
+    /// # Service key
+    pub service_key: String,
+
+    /// # Error
+    pub error: String,
+}
@@ -15,7 +15,8 @@ use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 
 use restate_types::identifiers::SubscriptionId;
-use restate_types::schema::subscriptions::Subscription;
+use restate_types::schema::subscriptions::{SeekPosition, Subscription};
+use restate_types::time::MillisSinceEpoch;
 
 #[serde_as]
 #[cfg_attr(feature = "schema", derive(utoipa::ToSchema))]
@@ -26,6 +27,12 @@ pub struct CreateSubscriptionRequest {
     /// Source uri. Accepted forms:
     ///
     /// * `kafka://<cluster_name>/<topic_name>`, e.g. `kafka://my-cluster/my-topic`
+    /// * `nats://<server>/<stream_name>`, e.g. `nats://localhost:4222/orders`
+    /// * `webhook://<name>`, e.g. `webhook://github`
+    /// * `topic://<name>`, e.g. `topic://order-events`. Virtual topic that handlers publish to
+    ///   with one-way calls to the `restate.topic` service. Requires a Kafka sink.
+    /// * `output://<service>/<handler>`, e.g. `output://Checkout/process`. Outputs of the
+    ///   completed invocations of the handler. Requires a Kafka sink.
     #[serde_as(as = "serde_with::DisplayFromStr")]
     #[cfg_attr(feature = "schema", schema(value_type = String, format = "uri"))]
     pub source: Uri,
@@ -34,6 +41,8 @@ pub struct CreateSubscriptionRequest {
     /// Sink uri. Accepted forms:
     ///
     /// * `service://<service_name>/<service_name>`, e.g. `service://Counter/count`
+    /// * `kafka://<cluster_name>/<topic_name>`, e.g. `kafka://my-cluster/my-topic`. Requires a
+    ///   topic source.
     #[serde_as(as = "serde_with::DisplayFromStr")]
     #[cfg_attr(feature = "schema", schema(value_type = String, format = "uri"))]
     pub sink: Uri,
@@ -51,6 +60,16 @@ pub struct SubscriptionResponse {
     pub source: String,
     pub sink: String,
     pub options: HashMap<String, String>,
+    /// # Paused
+    ///
+    /// If true, the subscription doesn't consume from its source.
+    #[serde(default)]
+    pub paused: bool,
+    /// # Last seek
+    ///
+    /// Last seek requested for the subscription, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_seek: Option<SeekSubscriptionRequest>,
 }
 
 impl From<Subscription> for SubscriptionResponse {
@@ -60,10 +79,75 @@ impl From<Subscription> for SubscriptionResponse {
             source: value.source().to_string(),
             sink: value.sink().to_string(),
             options: value.metadata().clone(),
+            paused: value.consumption().is_paused(),
+            last_seek: value
+                .consumption()
+                .last_seek()
+                .map(|seek| SeekSubscriptionRequest {
+                    position: seek.position.into(),
+                    partition: seek.partition,
+                }),
+        }
+    }
+}
+
+/// Position to move the consumers of a Kafka subscription to.
+#[cfg_attr(feature = "schema", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SubscriptionSeekPosition {
+    /// Oldest record retained by the topic.
+    Earliest,
+    /// Next record produced to the topic, skipping the records not consumed yet.
+    Latest,
+    /// Record with the given offset.
+    Offset(i64),
+    /// First record whose timestamp, in milliseconds since the Unix epoch, is greater than or
+    /// equal to the given one.
+    Timestamp(u64),
+}
+
+impl From<SeekPosition> for SubscriptionSeekPosition {
+    fn from(value: SeekPosition) -> Self {
+        match value {
+            SeekPosition::Earliest => SubscriptionSeekPosition::Earliest,
+            SeekPosition::Latest => SubscriptionSeekPosition::Latest,
+            SeekPosition::Offset(offset) => SubscriptionSeekPosition::Offset(offset),
+            SeekPosition::Timestamp(timestamp) => {
+                SubscriptionSeekPosition::Timestamp(timestamp.as_u64())
+            }
         }
     }
 }
 
+impl From<SubscriptionSeekPosition> for SeekPosition {
+    fn from(value: SubscriptionSeekPosition) -> Self {
+        match value {
+            SubscriptionSeekPosition::Earliest => SeekPosition::Earliest,
+            SubscriptionSeekPosition::Latest => SeekPosition::Latest,
+            SubscriptionSeekPosition::Offset(offset) => SeekPosition::Offset(offset),
+            SubscriptionSeekPosition::Timestamp(timestamp) => {
+                SeekPosition::Timestamp(MillisSinceEpoch::new(timestamp))
+            }
+        }
+    }
+}
+
+#[cfg_attr(feature = "schema", derive(utoipa::ToSchema))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeekSubscriptionRequest {
+    /// # Position
+    ///
+    /// Position to move the consumers to: `"earliest"`, `"latest"`, `{"offset": <offset>}` or
+    /// `{"timestamp": <milliseconds since the Unix epoch>}`.
+    pub position: SubscriptionSeekPosition,
+    /// # Partition
+    ///
+    /// Partition of the topic to move. If unset, all the partitions are moved.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub partition: Option<i32>,
+}
+
 #[cfg_attr(feature = "schema", derive(utoipa::IntoParams))]
 #[derive(Debug, Deserialize, Serialize)]
 pub struct ListSubscriptionsParams {
@@ -0,0 +1,47 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use restate_types::time::MillisSinceEpoch;
+use restate_util_time::FriendlyDuration;
+
+/// Exactly one of `by` and `to` must be set.
+#[cfg_attr(feature = "schema", derive(utoipa::ToSchema))]
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AdvanceTimeRequest {
+    /// # By
+    ///
+    /// Fire the timers due until this duration from now.
+    ///
+    /// Can be configured using the [`jiff::fmt::friendly`](https://docs.rs/jiff/latest/jiff/fmt/friendly/index.html) format or ISO8601, for example `5 hours`.
+    #[serde(default, with = "serde_with::As::<Option<FriendlyDuration>>")]
+    #[cfg_attr(feature = "schema", schema(value_type = Option<String>))]
+    pub by: Option<Duration>,
+
+    /// # To
+    ///
+    /// Fire the timers due until this point in time, in milliseconds since the Unix epoch.
+    #[serde(default)]
+    #[cfg_attr(feature = "schema", schema(value_type = Option<u64>))]
+    pub to: Option<MillisSinceEpoch>,
+}
+
+#[cfg_attr(feature = "schema", derive(utoipa::ToSchema))]
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AdvanceTimeResponse {
+    /// # Until
+    ///
+    /// The timers due until this point in time are fired, in milliseconds since the Unix epoch.
+    #[cfg_attr(feature = "schema", schema(value_type = u64))]
+    pub until: MillisSinceEpoch,
+}
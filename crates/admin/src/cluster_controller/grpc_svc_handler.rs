@@ -30,8 +30,9 @@ use restate_core::protobuf::cluster_ctrl_svc::{
     ListLogsRequest, ListLogsResponse, MigrateMetadataRequest, MigrateMetadataResponse,
     QueryRequest, QueryResponse, QueryWarning, SealAndExtendChainRequest,
     SealAndExtendChainResponse, SealChainRequest, SealChainResponse, SealedSegment,
-    SetClusterConfigurationRequest, SetClusterConfigurationResponse, SyncEpochMetadataRequest,
-    SyncEpochMetadataResponse, TailState, TrimLogRequest,
+    SetClusterConfigurationRequest, SetClusterConfigurationResponse, SplitPartitionRequest,
+    SplitPartitionResponse, SyncEpochMetadataRequest, SyncEpochMetadataResponse, TailState,
+    TrimLogRequest,
     cluster_ctrl_svc_server::{ClusterCtrlSvc, ClusterCtrlSvcServer},
 };
 use restate_core::{Metadata, MetadataWriter};
@@ -57,7 +58,7 @@ use restate_types::{PlainNodeId, Version, Versioned};
 use crate::query_utils::WriteRecordBatchStream;
 
 use super::ClusterControllerHandle;
-use super::service::ChainExtension;
+use super::service::{ChainExtension, PartitionSplit, missing_split_cluster_version};
 
 pub(crate) struct ClusterCtrlSvcHandler {
     controller_handle: ClusterControllerHandle,
@@ -577,6 +578,46 @@ impl ClusterCtrlSvc for ClusterCtrlSvcHandler {
 
         Ok(Response::new(SyncEpochMetadataResponse {}))
     }
+
+    /// Handles partition split requests, as sent by `restatectl partitions split`.
+    async fn split_partition(
+        &self,
+        request: Request<SplitPartitionRequest>,
+    ) -> Result<Response<SplitPartitionResponse>, Status> {
+        let request = request.into_inner();
+        let partition_id = PartitionId::from(
+            u16::try_from(request.partition_id)
+                .map_err(|id| Status::invalid_argument(format!("Invalid partition id: {id}")))?,
+        );
+        if let Some(min_version) = missing_split_cluster_version() {
+            return Err(Status::failed_precondition(format!(
+                "Cannot split partition {partition_id} until all nodes run Restate {min_version} or newer"
+            )));
+        }
+
+        let PartitionSplit {
+            sealed_lsn,
+            snapshot,
+            partitions,
+        } = self
+            .controller_handle
+            .split_partition(partition_id)
+            .await
+            .map_err(|_| Status::aborted("Node is shutting down"))?
+            .map_err(|err| {
+                info!("Failed to split partition: {err:#}");
+                Status::internal(format!("{err:#}"))
+            })?;
+
+        Ok(Response::new(SplitPartitionResponse {
+            partition_ids: partitions
+                .iter()
+                .map(|partition| u32::from(partition.partition_id))
+                .collect(),
+            snapshot_id: snapshot.snapshot_id.to_string(),
+            sealed_lsn: sealed_lsn.as_u64(),
+        }))
+    }
 }
 
 fn serialize_value<T: StorageEncode>(value: &T) -> Bytes {
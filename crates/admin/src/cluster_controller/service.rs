@@ -15,7 +15,7 @@ mod scheduler_task;
 use std::sync::Arc;
 use std::time::Duration;
 
-use anyhow::{Context, anyhow};
+use anyhow::{Context, anyhow, bail};
 use codederror::CodedError;
 use futures::never::Never;
 use rand::rng;
@@ -23,8 +23,9 @@ use rand::seq::IteratorRandom;
 use tokio::sync::{mpsc, oneshot};
 use tokio::time;
 use tokio::time::{Instant, Interval, MissedTickBehavior};
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
+use restate_bifrost::loglet::FindTailOptions;
 use restate_bifrost::{Bifrost, MaybeSealedSegment};
 use restate_core::network::tonic_service_filter::{TonicServiceFilter, WaitForReady};
 use restate_core::network::{
@@ -48,7 +49,7 @@ use restate_types::logs::metadata::{
     LogletParams, Logs, LogsConfiguration, ProviderConfiguration, ProviderKind,
     ReplicatedLogletConfig, SealMetadata, SegmentIndex,
 };
-use restate_types::logs::{self, LogId, LogletId, Lsn};
+use restate_types::logs::{self, LogId, LogletId, Lsn, SequenceNumber, TailState};
 use restate_types::net::node::NodeState;
 use restate_types::net::partition_processor_manager::{CreateSnapshotRequest, Snapshot};
 use restate_types::nodes_config::{NodesConfiguration, StorageState};
@@ -60,7 +61,7 @@ use restate_types::partitions::state::{MembershipState, PartitionReplicaSetState
 use restate_types::protobuf::common::AdminStatus;
 use restate_types::replicated_loglet::ReplicatedLogletParams;
 use restate_types::replication::{NodeSet, NodeSetChecker, ReplicationProperty};
-use restate_types::{GenerationalNodeId, NodeId, Version};
+use restate_types::{GenerationalNodeId, NodeId, SemanticRestateVersion, Version};
 
 use crate::cluster_controller::cluster_state_refresher::ClusterStateRefresher;
 use crate::cluster_controller::grpc_svc_handler::ClusterCtrlSvcHandler;
@@ -220,6 +221,29 @@ enum ClusterControllerCommand {
         partition_ids: Vec<PartitionId>,
         response_tx: oneshot::Sender<anyhow::Result<()>>,
     },
+    SplitPartition {
+        partition_id: PartitionId,
+        response_tx: oneshot::Sender<anyhow::Result<PartitionSplit>>,
+    },
+}
+
+/// Returns the version every node must run before partitions can be split, if the cluster
+/// doesn't satisfy it yet.
+pub(crate) fn missing_split_cluster_version() -> Option<&'static SemanticRestateVersion> {
+    let min_version = Partition::split_min_required_version();
+    let nodes_config = Metadata::with_current(|m| m.nodes_config_ref());
+    (!nodes_config.all_nodes_at_least(min_version)).then_some(min_version)
+}
+
+/// Outcome of splitting a partition.
+#[derive(Debug)]
+pub struct PartitionSplit {
+    /// The tail at which the log of the split partition was sealed.
+    pub sealed_lsn: Lsn,
+    /// Snapshot of the split partition, covering its sealed log.
+    pub snapshot: Snapshot,
+    /// The partitions replacing the split partition, ordered by key range.
+    pub partitions: Vec<Partition>,
 }
 
 pub struct ClusterControllerHandle {
@@ -374,6 +398,23 @@ impl ClusterControllerHandle {
 
         response_rx.await.map_err(|_| ShutdownError)
     }
+
+    pub async fn split_partition(
+        &self,
+        partition_id: PartitionId,
+    ) -> Result<anyhow::Result<PartitionSplit>, ShutdownError> {
+        let (response_tx, response_rx) = oneshot::channel();
+
+        let _ = self
+            .tx
+            .send(ClusterControllerCommand::SplitPartition {
+                partition_id,
+                response_tx,
+            })
+            .await;
+
+        response_rx.await.map_err(|_| ShutdownError)
+    }
 }
 
 impl<T: TransportConnect> Service<T> {
@@ -640,6 +681,24 @@ impl<T: TransportConnect> Service<T> {
                     let _ = response_tx.send(Err(anyhow!("Not the cluster controller leader")));
                 }
             },
+            ClusterControllerCommand::SplitPartition {
+                partition_id,
+                response_tx,
+            } => {
+                info!(%partition_id, "Split partition command received");
+                let task = SplitPartitionTask {
+                    partition_id,
+                    controller_handle: self.handle(),
+                    bifrost: self.bifrost.clone(),
+                    metadata_writer: self.metadata_writer.clone(),
+                };
+
+                // receiver will get error if response_tx is dropped
+                _ = TaskCenter::spawn(TaskKind::Disposable, "split-partition", async move {
+                    _ = response_tx.send(task.run().await);
+                    Ok(())
+                });
+            }
         }
     }
 }
@@ -811,6 +870,203 @@ impl SealChainTask {
     }
 }
 
+/// Splits a partition into two partitions covering one half of its key range each:
+///
+/// 1. A snapshot of the partition is created to make sure that the snapshot repository is
+///    usable. Nothing has changed if this fails.
+/// 2. The log of the partition is permanently sealed, which fences off all further writes.
+///    Leaders reject requests for a permanently sealed log as not-leader so that callers retry.
+/// 3. Once the partition has applied its log up to the sealed tail, a snapshot is created.
+/// 4. The partition is replaced by the new partitions in the partition table. Ingress and
+///    partition processors route to the new partitions from this partition table version on.
+///
+/// The new partitions bootstrap their state from the snapshot of the split partition. If the
+/// split fails after sealing the log, the permanent seal is lifted and the log is extended
+/// again, so the partition resumes processing and the split can be retried.
+struct SplitPartitionTask {
+    partition_id: PartitionId,
+    controller_handle: ClusterControllerHandle,
+    bifrost: Bifrost,
+    metadata_writer: MetadataWriter,
+}
+
+impl SplitPartitionTask {
+    const SNAPSHOT_ATTEMPTS: usize = 30;
+    const SNAPSHOT_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+    async fn run(self) -> anyhow::Result<PartitionSplit> {
+        // older nodes can't bootstrap the new partitions, and the sealed log would stay sealed
+        if let Some(min_version) = missing_split_cluster_version() {
+            bail!(
+                "cannot split partition {} until all nodes run Restate {min_version} or newer",
+                self.partition_id
+            );
+        }
+
+        let partition_table = Metadata::with_current(|m| m.partition_table_snapshot());
+        let log_id = partition_table
+            .get(&self.partition_id)
+            .map(Partition::log_id)
+            .ok_or_else(|| anyhow!("Partition {} does not exist", self.partition_id))?;
+        // fail early if the partition cannot be split
+        partition_table
+            .as_ref()
+            .clone()
+            .into_builder()
+            .split_partition(self.partition_id)?;
+
+        // the new partitions can only bootstrap from a snapshot, don't seal the log unless we
+        // know that snapshots can be created
+        self.controller_handle
+            .create_partition_snapshot(self.partition_id, None, false)
+            .await?
+            .context(format!(
+                "cannot split partition {} without a snapshot; is a snapshot repository configured?",
+                self.partition_id
+            ))?;
+
+        let sealed_lsn = self.seal_log(log_id).await?;
+        info!(partition_id = %self.partition_id, %log_id, %sealed_lsn, "Sealed the log of the partition to split");
+
+        let (snapshot, partition_table) = match self.snapshot_and_split(sealed_lsn).await {
+            Ok(result) => result,
+            Err(err) => {
+                warn!(
+                    partition_id = %self.partition_id,
+                    %log_id,
+                    %err,
+                    "Failed to split partition, unsealing its log"
+                );
+                self.unseal_log(log_id).await.context(format!(
+                    "failed to unseal log {log_id} after aborting the split of partition {}",
+                    self.partition_id
+                ))?;
+                return Err(err);
+            }
+        };
+        let partition_id = self.partition_id;
+
+        let partitions: Vec<_> = partition_table
+            .iter()
+            .map(|(_, partition)| partition)
+            .filter(|partition| partition.split_from() == Some(partition_id))
+            .cloned()
+            .collect();
+        info!(
+            %partition_id,
+            new_partitions = ?partitions.iter().map(Partition::id).collect::<Vec<_>>(),
+            "Split partition"
+        );
+
+        Ok(PartitionSplit {
+            sealed_lsn,
+            snapshot,
+            partitions,
+        })
+    }
+
+    async fn snapshot_and_split(
+        &self,
+        sealed_lsn: Lsn,
+    ) -> anyhow::Result<(Snapshot, Arc<PartitionTable>)> {
+        let snapshot = self.create_snapshot(sealed_lsn.prev()).await?;
+        info!(
+            partition_id = %self.partition_id,
+            snapshot_id = %snapshot.snapshot_id,
+            "Created the snapshot to bootstrap the new partitions from"
+        );
+
+        let partition_id = self.partition_id;
+        let partition_table = self
+            .metadata_writer
+            .global_metadata()
+            .read_modify_write(|current: Option<Arc<PartitionTable>>| {
+                let partition_table =
+                    current.ok_or(ClusterConfigurationUpdateError::MissingPartitionTable)?;
+                let mut builder = PartitionTableBuilder::from(partition_table.as_ref().clone());
+                builder.split_partition(partition_id)?;
+                Ok::<_, ClusterConfigurationUpdateError>(builder.build())
+            })
+            .await?;
+
+        Ok((snapshot, partition_table))
+    }
+
+    /// Backs out of the permanent seal so that the partition can continue writing to its log.
+    async fn unseal_log(&self, log_id: LogId) -> anyhow::Result<()> {
+        self.bifrost.admin().lift_permanent_seal(log_id).await?;
+        let segment = self
+            .controller_handle
+            .seal_and_extend_chain(log_id, Version::MIN, None)
+            .await??;
+        info!(
+            partition_id = %self.partition_id,
+            %log_id,
+            sealed_lsn = %segment.tail.offset(),
+            "Unsealed the log of the partition after aborting the split"
+        );
+        Ok(())
+    }
+
+    async fn seal_log(&self, log_id: LogId) -> anyhow::Result<Lsn> {
+        let result = SealChainTask {
+            log_id,
+            segment_index: None,
+            permanent_seal: true,
+            context: std::collections::HashMap::from([
+                ("node".to_owned(), my_node_id().to_string()),
+                ("reason".to_owned(), "partition-split".to_owned()),
+            ]),
+            bifrost: self.bifrost.clone(),
+        }
+        .run()
+        .await;
+
+        match result {
+            Ok(sealed_lsn) => Ok(sealed_lsn),
+            // the log might have been sealed by a previous attempt
+            Err(err) => match self
+                .bifrost
+                .find_tail(log_id, FindTailOptions::default())
+                .await?
+            {
+                TailState::Sealed(sealed_lsn) => Ok(sealed_lsn),
+                TailState::Open(_) => Err(err),
+            },
+        }
+    }
+
+    /// Creates a snapshot covering `min_target_lsn`, waiting for the partition processors to
+    /// catch up with the log.
+    async fn create_snapshot(&self, min_target_lsn: Lsn) -> anyhow::Result<Snapshot> {
+        let mut attempt = 1;
+        loop {
+            match self
+                .controller_handle
+                .create_partition_snapshot(self.partition_id, Some(min_target_lsn), false)
+                .await?
+            {
+                Ok(snapshot) => return Ok(snapshot),
+                Err(err) if attempt < Self::SNAPSHOT_ATTEMPTS => {
+                    debug!(
+                        partition_id = %self.partition_id,
+                        %err,
+                        "Snapshot of the partition to split not yet possible, retrying"
+                    );
+                    attempt += 1;
+                    time::sleep(Self::SNAPSHOT_RETRY_DELAY).await;
+                }
+                Err(err) => {
+                    return Err(err.context(format!(
+                        "failed to snapshot partition {} at LSN {min_target_lsn}",
+                        self.partition_id
+                    )));
+                }
+            }
+        }
+    }
+}
+
 struct SealAndExtendTask {
     log_id: LogId,
     min_version: Version,
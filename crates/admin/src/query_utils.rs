@@ -8,17 +8,25 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 use std::pin::Pin;
+use std::sync::Arc;
 use std::task::{Context, Poll};
 
 use bytes::Bytes;
 use datafusion::arrow::datatypes::Schema;
 use datafusion::arrow::ipc::writer::StreamWriter;
+use datafusion::arrow::json::ArrayWriter;
 use datafusion::arrow::record_batch::RecordBatch;
 use datafusion::error::DataFusionError;
 use datafusion::execution::SendableRecordBatchStream;
-use futures::{Stream, StreamExt, ready};
+use futures::{Stream, StreamExt, TryStreamExt, ready};
+use parquet::arrow::ArrowWriter;
+use parquet::basic::{Compression, ZstdLevel};
+use parquet::file::properties::WriterProperties;
+use serde::de::DeserializeOwned;
 use tracing::{Level, enabled, warn};
 
+use restate_storage_query_datafusion::context::QueryContext;
+
 pub trait RecordBatchWriter
 where
     Self: Sized,
@@ -53,6 +61,38 @@ impl RecordBatchWriter for StreamWriter<Vec<u8>> {
     }
 }
 
+/// Maximum number of rows buffered by the [`ParquetWriter`] before writing a row group.
+const PARQUET_MAX_ROW_GROUP_SIZE: usize = 64 * 1024;
+
+/// Writes Parquet files. Row groups are returned once complete, the footer when finishing.
+pub struct ParquetWriter(ArrowWriter<Vec<u8>>);
+
+impl RecordBatchWriter for ParquetWriter {
+    fn new(schema: &Schema) -> Result<Self, DataFusionError> {
+        let properties = WriterProperties::builder()
+            .set_compression(Compression::ZSTD(ZstdLevel::default()))
+            .set_max_row_group_size(PARQUET_MAX_ROW_GROUP_SIZE)
+            .build();
+        ArrowWriter::try_new(Vec::new(), Arc::new(schema.clone()), Some(properties))
+            .map(Self)
+            .map_err(|e| DataFusionError::External(Box::new(e)))
+    }
+
+    fn write(&mut self, batch: &RecordBatch) -> Result<Bytes, DataFusionError> {
+        self.0
+            .write(batch)
+            .map_err(|e| DataFusionError::External(Box::new(e)))?;
+        Ok(Bytes::from(std::mem::take(self.0.inner_mut())))
+    }
+
+    fn finish(&mut self) -> Result<Bytes, DataFusionError> {
+        self.0
+            .finish()
+            .map_err(|e| DataFusionError::External(Box::new(e)))?;
+        Ok(Bytes::from(std::mem::take(self.0.inner_mut())))
+    }
+}
+
 pub struct WriteRecordBatchStream<W> {
     done: bool,
     record_batch_stream: SendableRecordBatchStream,
@@ -107,3 +147,68 @@ impl<W: RecordBatchWriter + Unpin> Stream for WriteRecordBatchStream<W> {
         }
     }
 }
+
+/// Runs `sql` and deserializes the resulting rows from their JSON representation.
+pub(crate) async fn query_rows<T: DeserializeOwned>(
+    query_context: &QueryContext,
+    sql: &str,
+) -> Result<Vec<T>, DataFusionError> {
+    let batches: Vec<_> = query_context
+        .execute(sql)
+        .await?
+        .stream
+        .try_collect()
+        .await?;
+
+    let mut writer = ArrayWriter::new(Vec::new());
+    for batch in &batches {
+        writer.write(batch)?;
+    }
+    writer.finish()?;
+    let json = writer.into_inner();
+    if json.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    serde_json::from_slice(&json).map_err(|e| DataFusionError::External(Box::new(e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use datafusion::arrow::array::{Int64Array, StringArray};
+    use datafusion::arrow::datatypes::{DataType, Field};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    #[test]
+    fn parquet_writer_output_is_readable() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Utf8, false),
+            Field::new("count", DataType::Int64, false),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(StringArray::from(vec!["a", "b"])),
+                Arc::new(Int64Array::from(vec![1, 2])),
+            ],
+        )
+        .unwrap();
+
+        let mut writer = ParquetWriter::new(&schema).unwrap();
+        let mut file = Vec::new();
+        file.extend_from_slice(&writer.write(&batch).unwrap());
+        file.extend_from_slice(&writer.write(&batch).unwrap());
+        file.extend_from_slice(&writer.finish().unwrap());
+
+        let batches: Vec<_> = ParquetRecordBatchReaderBuilder::try_new(Bytes::from(file))
+            .unwrap()
+            .build()
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 4);
+        assert_eq!(batches[0].schema(), schema);
+    }
+}
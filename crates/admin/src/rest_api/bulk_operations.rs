@@ -0,0 +1,371 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::HashMap;
+use std::num::NonZeroU32;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use futures::StreamExt;
+use parking_lot::Mutex;
+use serde::Deserialize;
+use tracing::warn;
+use ulid::Ulid;
+
+use restate_admin_rest_model::invocations::{
+    BULK_OPERATION_MAX_SIZE, BulkInvocationOperation, BulkInvocationOperationJob,
+    BulkInvocationOperationRequest, BulkInvocationOperationStatus, FailedInvocationOperation,
+    InvocationFilter, PatchDeploymentId, RestartedInvocation,
+};
+use restate_core::{TaskCenter, TaskKind};
+use restate_types::identifiers::InvocationId;
+use restate_types::invocation::client::InvocationClient;
+use restate_types::time::MillisSinceEpoch;
+
+use super::error::*;
+use super::invocation_operations::InvocationOperation;
+use crate::generate_meta_api_error;
+use crate::query_utils::query_rows;
+use crate::state::AdminServiceState;
+
+/// How long finished bulk operations can be looked up.
+const FINISHED_JOB_RETENTION: Duration = Duration::from_secs(60 * 60);
+
+/// Bulk operations of this node, kept in memory only. They are lost when the node restarts, and
+/// other nodes don't know about them.
+#[derive(Clone, Default)]
+pub(crate) struct BulkOperationJobs(Arc<Mutex<HashMap<String, BulkInvocationOperationJob>>>);
+
+impl BulkOperationJobs {
+    fn insert(&self, job: BulkInvocationOperationJob) {
+        let retained_since = MillisSinceEpoch::new(
+            MillisSinceEpoch::now()
+                .as_u64()
+                .saturating_sub(FINISHED_JOB_RETENTION.as_millis() as u64),
+        );
+        let mut jobs = self.0.lock();
+        jobs.retain(|_, job| {
+            job.completed_at
+                .is_none_or(|completed_at| completed_at > retained_since)
+        });
+        jobs.insert(job.job_id.clone(), job);
+    }
+
+    fn get(&self, job_id: &str) -> Option<BulkInvocationOperationJob> {
+        self.0.lock().get(job_id).cloned()
+    }
+
+    fn remove(&self, job_id: &str) {
+        self.0.lock().remove(job_id);
+    }
+
+    fn update(&self, job_id: &str, f: impl FnOnce(&mut BulkInvocationOperationJob)) {
+        if let Some(job) = self.0.lock().get_mut(job_id) {
+            f(job);
+        }
+    }
+}
+
+generate_meta_api_error!(SubmitBulkOperationError: [
+    InvalidFieldError,
+    BatchTooLargeError,
+    QueryUnavailableError,
+    QueryFailedError,
+    BulkOperationNotStartedError,
+]);
+
+/// Submit a bulk operation
+///
+/// Applies an operation to the given invocations, or to all invocations matching a filter.
+/// The operation runs in the background, use the returned job id to follow its progress.
+/// Jobs are kept in memory by the node that accepted them, for an hour after completion: they
+/// are lost when that node restarts, and other nodes return 404 for them.
+#[utoipa::path(
+    post,
+    path = "/invocations/bulk-operations",
+    operation_id = "submit_bulk_invocation_operation",
+    tag = "invocation",
+    request_body = BulkInvocationOperationRequest,
+    responses(
+        (status = 202, description = "The operation is being applied", body = BulkInvocationOperationJob),
+        (status = 200, description = "Dry run, the operation wasn't applied", body = BulkInvocationOperationJob),
+        SubmitBulkOperationError,
+    )
+)]
+pub async fn submit_bulk_invocation_operation<
+    Metadata,
+    Discovery,
+    Telemetry,
+    Invocations,
+    Transport,
+>(
+    State(state): State<AdminServiceState<Metadata, Discovery, Telemetry, Invocations, Transport>>,
+    Json(request): Json<BulkInvocationOperationRequest>,
+) -> Result<(StatusCode, Json<BulkInvocationOperationJob>), SubmitBulkOperationError>
+where
+    Invocations: InvocationClient + Clone + Send + Sync + 'static,
+{
+    let operations_per_second = match request.operations_per_second {
+        Some(operations_per_second) => {
+            Some(NonZeroU32::new(operations_per_second).ok_or_else(|| {
+                InvalidFieldError("operations_per_second", "must be greater than zero".into())
+            })?)
+        }
+        None => None,
+    };
+    let operation = invocation_operation(request.operation, request.deployment)?;
+
+    let invocation_ids = match (request.invocation_ids, request.filter) {
+        (Some(invocation_ids), None) => {
+            if invocation_ids.len() > BULK_OPERATION_MAX_SIZE {
+                return Err(
+                    BatchTooLargeError(invocation_ids.len(), BULK_OPERATION_MAX_SIZE).into(),
+                );
+            }
+            invocation_ids
+        }
+        (None, Some(filter)) => {
+            let query_context = state.query_context.as_ref().ok_or(QueryUnavailableError)?;
+            select_invocations(query_context, &filter).await?
+        }
+        _ => {
+            return Err(InvalidFieldError(
+                "invocation_ids",
+                "exactly one of 'invocation_ids' and 'filter' must be set".into(),
+            )
+            .into());
+        }
+    };
+
+    let mut job = BulkInvocationOperationJob {
+        job_id: Ulid::new().to_string(),
+        operation: request.operation,
+        status: BulkInvocationOperationStatus::Running,
+        invocation_ids,
+        succeeded: Vec::new(),
+        restarted: Vec::new(),
+        failed: Vec::new(),
+        created_at: MillisSinceEpoch::now(),
+        completed_at: None,
+    };
+    if request.dry_run {
+        job.status = BulkInvocationOperationStatus::DryRun;
+        return Ok((StatusCode::OK, Json(job)));
+    }
+
+    state.bulk_operations.insert(job.clone());
+
+    let jobs = state.bulk_operations.clone();
+    let invocation_client = state.invocation_client.clone();
+    let job_id = job.job_id.clone();
+    let invocation_ids = job.invocation_ids.clone();
+    let spawned = TaskCenter::spawn(
+        TaskKind::Disposable,
+        "bulk-invocation-operation",
+        async move {
+            let mut results = operation.apply_throttled(
+                &invocation_client,
+                invocation_ids,
+                operations_per_second,
+            );
+            while let Some((invocation_id, result)) = results.next().await {
+                jobs.update(&job_id, |job| match result {
+                    Ok(new_invocation_id) => {
+                        job.succeeded.push(invocation_id);
+                        if let Some(new_invocation_id) = new_invocation_id {
+                            job.restarted.push(RestartedInvocation {
+                                old_invocation_id: invocation_id,
+                                new_invocation_id,
+                            });
+                        }
+                    }
+                    Err(error) => job.failed.push(FailedInvocationOperation {
+                        invocation_id,
+                        error,
+                    }),
+                });
+            }
+            jobs.update(&job_id, |job| {
+                job.status = BulkInvocationOperationStatus::Completed;
+                job.completed_at = Some(MillisSinceEpoch::now());
+            });
+            Ok(())
+        },
+    );
+    if let Err(err) = spawned {
+        warn!(job_id = %job.job_id, "Failed to start the bulk operation: {err}");
+        state.bulk_operations.remove(&job.job_id);
+        return Err(BulkOperationNotStartedError(err).into());
+    }
+
+    Ok((StatusCode::ACCEPTED, Json(job)))
+}
+
+generate_meta_api_error!(GetBulkOperationError: [BulkOperationNotFoundError]);
+
+/// Get a bulk operation
+///
+/// Returns the progress of a bulk operation submitted to this node. Jobs are not shared between
+/// nodes and don't survive restarts.
+#[utoipa::path(
+    get,
+    path = "/invocations/bulk-operations/{job_id}",
+    operation_id = "get_bulk_invocation_operation",
+    tag = "invocation",
+    params(
+        ("job_id" = String, Path, description = "Bulk operation job identifier."),
+    ),
+    responses(
+        (status = 200, description = "The bulk operation", body = BulkInvocationOperationJob),
+        GetBulkOperationError,
+    )
+)]
+pub async fn get_bulk_invocation_operation<
+    Metadata,
+    Discovery,
+    Telemetry,
+    Invocations,
+    Transport,
+>(
+    State(state): State<AdminServiceState<Metadata, Discovery, Telemetry, Invocations, Transport>>,
+    Path(job_id): Path<String>,
+) -> Result<Json<BulkInvocationOperationJob>, GetBulkOperationError> {
+    state
+        .bulk_operations
+        .get(&job_id)
+        .map(Json)
+        .ok_or_else(|| BulkOperationNotFoundError(job_id).into())
+}
+
+fn invocation_operation(
+    operation: BulkInvocationOperation,
+    deployment: Option<PatchDeploymentId>,
+) -> Result<InvocationOperation, InvalidFieldError> {
+    let patch_deployment = |default: PatchDeploymentId| {
+        deployment
+            .clone()
+            .unwrap_or(default)
+            .into_client()
+            .map_err(|e| InvalidFieldError("deployment", e))
+    };
+    let operation = match operation {
+        BulkInvocationOperation::Resume => {
+            return Ok(InvocationOperation::Resume(patch_deployment(
+                PatchDeploymentId::Keep,
+            )?));
+        }
+        BulkInvocationOperation::RestartAsNew => {
+            return Ok(InvocationOperation::RestartAsNew(patch_deployment(
+                PatchDeploymentId::Latest,
+            )?));
+        }
+        BulkInvocationOperation::Cancel => InvocationOperation::Cancel,
+        BulkInvocationOperation::Kill => InvocationOperation::Kill,
+        BulkInvocationOperation::Purge => InvocationOperation::Purge,
+        BulkInvocationOperation::PurgeJournal => InvocationOperation::PurgeJournal,
+        BulkInvocationOperation::Pause => InvocationOperation::Pause,
+    };
+    if deployment.is_some() {
+        return Err(InvalidFieldError(
+            "deployment",
+            "can only be set when resuming or restarting as new".into(),
+        ));
+    }
+    Ok(operation)
+}
+
+#[derive(Deserialize)]
+struct IdRow {
+    id: InvocationId,
+}
+
+async fn select_invocations(
+    query_context: &restate_storage_query_datafusion::context::QueryContext,
+    filter: &InvocationFilter,
+) -> Result<Vec<InvocationId>, SubmitBulkOperationError> {
+    if filter.is_empty() {
+        return Err(InvalidFieldError("filter", "at least one field must be set".into()).into());
+    }
+
+    let rows: Vec<IdRow> = query_rows(
+        query_context,
+        &filter_query(filter, MillisSinceEpoch::now()),
+    )
+    .await
+    .map_err(QueryFailedError)?;
+    if rows.len() > BULK_OPERATION_MAX_SIZE {
+        return Err(InvalidFieldError(
+            "filter",
+            format!("selects more than {BULK_OPERATION_MAX_SIZE} invocations"),
+        )
+        .into());
+    }
+
+    Ok(rows.into_iter().map(|row| row.id).collect())
+}
+
+fn filter_query(filter: &InvocationFilter, now: MillisSinceEpoch) -> String {
+    let mut conditions = Vec::new();
+    if let Some(service) = &filter.service {
+        conditions.push(format!("target_service_name = {}", sql_string(service)));
+    }
+    if let Some(handler) = &filter.handler {
+        conditions.push(format!("target_handler_name = {}", sql_string(handler)));
+    }
+    if let Some(status) = &filter.status {
+        conditions.push(format!("status = {}", sql_string(status)));
+    }
+    if let Some(deployment) = &filter.deployment {
+        conditions.push(format!("pinned_deployment_id = {}", sql_string(deployment)));
+    }
+    if let Some(older_than) = filter.older_than {
+        conditions.push(format!(
+            "created_at < to_timestamp_millis({})",
+            now.as_u64().saturating_sub(older_than.as_millis() as u64)
+        ));
+    }
+
+    // Select one more than allowed, to detect filters selecting too many invocations
+    format!(
+        "SELECT id FROM sys_invocation WHERE {} LIMIT {}",
+        conditions.join(" AND "),
+        BULK_OPERATION_MAX_SIZE + 1
+    )
+}
+
+fn sql_string(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filter_to_query() {
+        let filter = InvocationFilter {
+            service: Some("Greeter".to_owned()),
+            status: Some("backing-off".to_owned()),
+            deployment: Some("dp_1'; --".to_owned()),
+            older_than: Some(Duration::from_secs(60)),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            filter_query(&filter, MillisSinceEpoch::new(100_000)),
+            "SELECT id FROM sys_invocation WHERE target_service_name = 'Greeter' \
+            AND status = 'backing-off' AND pinned_deployment_id = 'dp_1''; --' \
+            AND created_at < to_timestamp_millis(40000) LIMIT 10001"
+        );
+    }
+}
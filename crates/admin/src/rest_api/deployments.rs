@@ -21,7 +21,7 @@ use serde::Deserialize;
 use restate_admin_rest_model::deployments::*;
 use restate_admin_rest_model::version::AdminApiVersion;
 use restate_errors::warn_it;
-use restate_types::deployment::{HttpDeploymentAddress, LambdaDeploymentAddress};
+use restate_types::deployment::{HttpDeploymentAddress, LambdaDeploymentAddress, unix_socket_path};
 use restate_types::identifiers::{DeploymentId, InvalidLambdaARN, ServiceRevision};
 use restate_types::schema;
 use restate_types::schema::deployment::{Deployment, DeploymentType};
@@ -345,14 +345,19 @@ where
     } else {
         ApplyMode::Apply
     };
-    let (update_deployment_address, additional_headers) = match payload {
+    let (update_deployment_address, additional_headers, limits) = match payload {
         UpdateDeploymentRequest::Http {
             uri,
             additional_headers,
             use_http_11,
+            limits,
             ..
         } => {
-            if uri.is_none() && additional_headers.is_none() && use_http_11.is_none() {
+            if uri.is_none()
+                && additional_headers.is_none()
+                && use_http_11.is_none()
+                && limits.is_none()
+            {
                 // No changes to do, just return 200
                 let (deployment, services) = state
                     .schema_registry
@@ -398,15 +403,21 @@ where
                     Some(schema::registry::UpdateDeploymentAddress::Http { uri, use_http_11 })
                 },
                 additional_headers,
+                limits,
             )
         }
         UpdateDeploymentRequest::Lambda {
             arn,
             assume_role_arn,
             additional_headers,
+            limits,
             ..
         } => {
-            if arn.is_none() && additional_headers.is_none() && assume_role_arn.is_none() {
+            if arn.is_none()
+                && additional_headers.is_none()
+                && assume_role_arn.is_none()
+                && limits.is_none()
+            {
                 // No changes to do, just return 200
                 let (deployment, services) = state
                     .schema_registry
@@ -432,6 +443,7 @@ where
                     })
                 },
                 additional_headers,
+                limits,
             )
         }
     };
@@ -443,6 +455,7 @@ where
             schema::registry::UpdateDeploymentRequest {
                 update_deployment_address,
                 additional_headers: additional_headers.map(Into::into),
+                limits,
                 overwrite,
                 apply_mode,
             },
@@ -483,6 +496,7 @@ fn to_deployment_response(
         created_at,
         metadata,
         info,
+        limits,
         ..
     }: Deployment,
     services: Vec<(String, ServiceRevision)>,
@@ -509,6 +523,7 @@ fn to_deployment_response(
                 .map(|(name, revision)| ServiceNameRevPair { name, revision })
                 .collect(),
             info,
+            limits,
             auth: auth.map(Into::into),
         },
         DeploymentType::Lambda {
@@ -531,6 +546,7 @@ fn to_deployment_response(
                 .map(|(name, revision)| ServiceNameRevPair { name, revision })
                 .collect(),
             info,
+            limits,
         },
     }
 }
@@ -545,6 +561,7 @@ fn to_detailed_deployment_response(
         created_at,
         metadata,
         info,
+        limits,
         ..
     }: Deployment,
     services: Vec<ServiceMetadata>,
@@ -568,6 +585,7 @@ fn to_detailed_deployment_response(
             sdk_version,
             services,
             info,
+            limits,
             auth: auth.map(Into::into),
         },
         DeploymentType::Lambda {
@@ -587,6 +605,7 @@ fn to_detailed_deployment_response(
             sdk_version,
             services,
             info,
+            limits,
         },
     }
 }
@@ -600,5 +619,15 @@ fn validate_uri(uri: &Uri) -> Result<(), MetaApiError> {
             format!("The provided uri {uri} is not absolute, only absolute URIs can be used."),
         ));
     }
+    if unix_socket_path(uri).is_some()
+        && (uri.host() != Some("localhost") || uri.query().is_some() || uri.path() == "/")
+    {
+        return Err(MetaApiError::InvalidField(
+            "uri",
+            format!(
+                "The provided uri {uri} is not a valid unix domain socket address, use the form unix:///path/to/socket."
+            ),
+        ));
+    }
     Ok(())
 }
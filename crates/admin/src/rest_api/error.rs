@@ -16,7 +16,7 @@ use axum::response::{IntoResponse, Response};
 use codederror::{Code, CodedError};
 
 use restate_core::ShutdownError;
-use restate_types::identifiers::{DeploymentId, SubscriptionId};
+use restate_types::identifiers::{DeploymentId, ScheduleId, SubscriptionId};
 use restate_types::invocation::ServiceType;
 use restate_types::schema::registry::{HttpAuthValidationError, SchemaRegistryError};
 use restate_util_string::RestrictedValueError;
@@ -146,6 +146,50 @@ impl_meta_api_error!(BatchTooLargeError: BAD_REQUEST "The batch size exceeds the
 pub(crate) struct InvocationNotFoundError(pub(crate) String);
 impl_meta_api_error!(InvocationNotFoundError: NOT_FOUND);
 
+#[derive(Debug, thiserror::Error)]
+#[error("The requested bulk operation '{0}' does not exist")]
+pub(crate) struct BulkOperationNotFoundError(pub(crate) String);
+impl_meta_api_error!(BulkOperationNotFoundError: NOT_FOUND "The bulk operation doesn't exist, it was submitted to another node, the node restarted since, or it completed more than an hour ago.");
+
+#[derive(Debug, thiserror::Error)]
+#[error("The bulk operation could not be started: {0}")]
+pub(crate) struct BulkOperationNotStartedError(#[from] pub(crate) ShutdownError);
+impl_meta_api_error!(BulkOperationNotStartedError: SERVICE_UNAVAILABLE "The node is shutting down, submit the bulk operation to another node.");
+
+#[derive(Debug, thiserror::Error)]
+#[error("Query service not available")]
+pub(crate) struct QueryUnavailableError;
+impl_meta_api_error!(QueryUnavailableError: SERVICE_UNAVAILABLE "The query service is not available on this node.");
+
+#[derive(Debug, thiserror::Error)]
+#[error("Error when querying the system tables. Reason: {0}")]
+pub(crate) struct QueryFailedError(#[from] pub(crate) datafusion::error::DataFusionError);
+impl_meta_api_error!(QueryFailedError: INTERNAL_SERVER_ERROR "Error when querying the system tables.");
+
+#[derive(Debug, thiserror::Error)]
+#[error("Partition logs not available")]
+pub(crate) struct LogUnavailableError;
+impl_meta_api_error!(LogUnavailableError: SERVICE_UNAVAILABLE "The partition logs are not readable from this node.");
+
+#[derive(Debug, thiserror::Error)]
+#[error("Error when reading the partition log. Reason: {0}")]
+pub(crate) struct LogReadError(#[from] pub(crate) restate_bifrost::Error);
+impl_meta_api_error!(LogReadError: SERVICE_UNAVAILABLE "Error when reading the log of the partition.");
+
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "The controllable time is disabled, enable the 'experimental-enable-controllable-time' option to use it"
+)]
+pub(crate) struct ControllableTimeDisabledError;
+impl_meta_api_error!(ControllableTimeDisabledError: FORBIDDEN "The controllable time is disabled on this node.");
+
+#[derive(Debug, thiserror::Error)]
+#[error(
+    "Schedules are disabled, enable the 'experimental-enable-schedules' option on every node to use them"
+)]
+pub(crate) struct SchedulesDisabledError;
+impl_meta_api_error!(SchedulesDisabledError: FORBIDDEN "Schedules are disabled on this node.");
+
 #[derive(Debug, thiserror::Error)]
 #[error("Error when routing the request internally. Reason: {0}")]
 pub(crate) struct InvocationClientError(
@@ -283,6 +327,8 @@ pub enum MetaApiError {
     SubscriptionNotFound(SubscriptionId),
     #[error("The requested Kafka cluster '{0}' does not exist")]
     KafkaClusterNotFound(String),
+    #[error("The requested schedule '{0}' does not exist")]
+    ScheduleNotFound(ScheduleId),
     #[error("Cannot {0} for service type {1}")]
     UnsupportedOperation(&'static str, ServiceType),
     #[error(transparent)]
@@ -304,7 +350,8 @@ impl IntoResponse for MetaApiError {
             | MetaApiError::HandlerNotFound { .. }
             | MetaApiError::DeploymentNotFound(_)
             | MetaApiError::SubscriptionNotFound(_)
-            | MetaApiError::KafkaClusterNotFound(_) => StatusCode::NOT_FOUND,
+            | MetaApiError::KafkaClusterNotFound(_)
+            | MetaApiError::ScheduleNotFound(_) => StatusCode::NOT_FOUND,
             MetaApiError::InvalidField(_, _) | MetaApiError::UnsupportedOperation(_, _) => {
                 StatusCode::BAD_REQUEST
             }
@@ -0,0 +1,296 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::num::NonZeroU32;
+use std::time::Duration;
+
+use futures::{FutureExt, Stream, StreamExt, future, stream};
+use tokio::time::MissedTickBehavior;
+
+use restate_admin_rest_model::invocations::{BatchOperationResult, FailedInvocationOperation};
+use restate_types::identifiers::{InvocationId, PartitionProcessorRpcRequestId};
+use restate_types::invocation::client::{
+    self, CancelInvocationResponse, InvocationClient, KillInvocationResponse,
+    PauseInvocationResponse, PurgeInvocationResponse, ResumeInvocationResponse,
+};
+use restate_types::journal_v2::EntryIndex;
+
+/// How many operations are in flight at the same time when applying an operation to many
+/// invocations.
+const MAX_CONCURRENT_OPERATIONS: usize = 32;
+
+/// An operation that can be applied to many invocations at once, shared by the batch endpoints
+/// and the invocation functions of the SQL query endpoint.
+#[derive(Debug, Clone)]
+pub(crate) enum InvocationOperation {
+    Kill,
+    Cancel,
+    Purge,
+    PurgeJournal,
+    Pause,
+    Resume(client::PatchDeploymentId),
+    /// Always restarts from the beginning.
+    RestartAsNew(client::PatchDeploymentId),
+}
+
+/// Outcome of an [`InvocationOperation`] on a single invocation. Restarting as new returns the
+/// id of the new invocation.
+pub(crate) type InvocationOperationResult = Result<Option<InvocationId>, String>;
+
+impl InvocationOperation {
+    pub(crate) async fn apply<Invocations: InvocationClient>(
+        &self,
+        client: &Invocations,
+        invocation_id: InvocationId,
+    ) -> InvocationOperationResult {
+        let request_id = PartitionProcessorRpcRequestId::new();
+        match self {
+            InvocationOperation::Kill => {
+                match client
+                    .kill_invocation(request_id, invocation_id)
+                    .await
+                    .map_err(|e| e.to_string())?
+                {
+                    KillInvocationResponse::Ok => Ok(None),
+                    KillInvocationResponse::NotFound => Err(not_found(invocation_id)),
+                    KillInvocationResponse::AlreadyCompleted => {
+                        Err(already_completed(invocation_id))
+                    }
+                }
+            }
+            InvocationOperation::Cancel => {
+                match client
+                    .cancel_invocation(request_id, invocation_id)
+                    .await
+                    .map_err(|e| e.to_string())?
+                {
+                    CancelInvocationResponse::Done | CancelInvocationResponse::Appended => Ok(None),
+                    CancelInvocationResponse::NotFound => Err(not_found(invocation_id)),
+                    CancelInvocationResponse::AlreadyCompleted => {
+                        Err(already_completed(invocation_id))
+                    }
+                }
+            }
+            InvocationOperation::Purge | InvocationOperation::PurgeJournal => {
+                let response = if matches!(self, InvocationOperation::Purge) {
+                    client.purge_invocation(request_id, invocation_id).await
+                } else {
+                    client.purge_journal(request_id, invocation_id).await
+                };
+                match response.map_err(|e| e.to_string())? {
+                    PurgeInvocationResponse::Ok => Ok(None),
+                    PurgeInvocationResponse::NotFound => Err(not_found(invocation_id)),
+                    PurgeInvocationResponse::NotCompleted => Err(format!(
+                        "Invocation '{}' is not yet completed",
+                        invocation_id
+                    )),
+                }
+            }
+            InvocationOperation::Pause => {
+                match client
+                    .pause_invocation(request_id, invocation_id)
+                    .await
+                    .map_err(|e| e.to_string())?
+                {
+                    PauseInvocationResponse::Accepted | PauseInvocationResponse::AlreadyPaused => {
+                        Ok(None)
+                    }
+                    PauseInvocationResponse::NotFound => Err(not_found(invocation_id)),
+                    PauseInvocationResponse::NotRunning => Err(format!(
+                        "Invocation '{}' is not running, cannot be paused",
+                        invocation_id
+                    )),
+                }
+            }
+            InvocationOperation::Resume(deployment) => {
+                match client
+                    .resume_invocation(request_id, invocation_id, deployment.clone())
+                    .await
+                    .map_err(|e| e.to_string())?
+                {
+                    ResumeInvocationResponse::Ok => Ok(None),
+                    ResumeInvocationResponse::NotFound => Err(not_found(invocation_id)),
+                    ResumeInvocationResponse::NotStarted => Err(format!(
+                        "Invocation '{}' is either inboxed or scheduled, cannot be resumed",
+                        invocation_id
+                    )),
+                    ResumeInvocationResponse::Completed => Err(format!(
+                        "Invocation '{}' is completed, cannot be resumed",
+                        invocation_id
+                    )),
+                    ResumeInvocationResponse::CannotChangeDeploymentId => Err(format!(
+                        "Cannot change deployment ID for invocation '{}'",
+                        invocation_id
+                    )),
+                    ResumeInvocationResponse::DeploymentNotFound => Err(format!(
+                        "Deployment not found when resuming invocation '{}'",
+                        invocation_id
+                    )),
+                    ResumeInvocationResponse::IncompatibleDeploymentId {
+                        pinned_protocol_version,
+                        deployment_id,
+                        supported_protocol_versions,
+                    } => Err(incompatible_deployment(
+                        invocation_id,
+                        pinned_protocol_version,
+                        deployment_id,
+                        supported_protocol_versions,
+                    )),
+                }
+            }
+            InvocationOperation::RestartAsNew(deployment) => {
+                match client
+                    .restart_as_new_invocation(
+                        request_id,
+                        invocation_id,
+                        EntryIndex::default(),
+                        deployment.clone(),
+                    )
+                    .await
+                    .map_err(|e| e.to_string())?
+                {
+                    client::RestartAsNewInvocationResponse::Ok { new_invocation_id } => {
+                        Ok(Some(new_invocation_id))
+                    }
+                    client::RestartAsNewInvocationResponse::NotFound => {
+                        Err(not_found(invocation_id))
+                    }
+                    client::RestartAsNewInvocationResponse::StillRunning => {
+                        Err(format!("Invocation '{}' is still running", invocation_id))
+                    }
+                    client::RestartAsNewInvocationResponse::Unsupported => Err(format!(
+                        "Restarting invocation '{}' is not supported",
+                        invocation_id
+                    )),
+                    client::RestartAsNewInvocationResponse::MissingInput => Err(format!(
+                        "Invocation '{}' cannot be restarted because the input is not available",
+                        invocation_id
+                    )),
+                    client::RestartAsNewInvocationResponse::NotStarted => Err(format!(
+                        "Invocation '{}' cannot be restarted because it's not running yet",
+                        invocation_id
+                    )),
+                    client::RestartAsNewInvocationResponse::JournalIndexOutOfRange => Err(format!(
+                        "Journal index out of range for invocation '{}'",
+                        invocation_id
+                    )),
+                    client::RestartAsNewInvocationResponse::JournalCopyRangeInvalid => {
+                        Err(format!(
+                            "Journal copy range invalid for invocation '{}'",
+                            invocation_id
+                        ))
+                    }
+                    client::RestartAsNewInvocationResponse::CannotPatchDeploymentId => {
+                        Err(format!(
+                            "Cannot change deployment ID for invocation '{}'",
+                            invocation_id
+                        ))
+                    }
+                    client::RestartAsNewInvocationResponse::DeploymentNotFound => Err(format!(
+                        "Deployment not found when restarting invocation '{}'",
+                        invocation_id
+                    )),
+                    client::RestartAsNewInvocationResponse::IncompatibleDeploymentId {
+                        pinned_protocol_version,
+                        deployment_id,
+                        supported_protocol_versions,
+                    } => Err(incompatible_deployment(
+                        invocation_id,
+                        pinned_protocol_version,
+                        deployment_id,
+                        supported_protocol_versions,
+                    )),
+                }
+            }
+        }
+    }
+
+    /// Applies the operation to all invocations concurrently.
+    pub(crate) async fn apply_all<Invocations: InvocationClient>(
+        &self,
+        client: &Invocations,
+        invocation_ids: Vec<InvocationId>,
+    ) -> Vec<(InvocationId, InvocationOperationResult)> {
+        future::join_all(invocation_ids.into_iter().map(|invocation_id| async move {
+            (invocation_id, self.apply(client, invocation_id).await)
+        }))
+        .await
+    }
+
+    /// Applies the operation to the invocations with bounded concurrency, at most
+    /// `operations_per_second` if set. Results are returned in completion order.
+    pub(crate) fn apply_throttled<'a, Invocations: InvocationClient>(
+        &'a self,
+        client: &'a Invocations,
+        invocation_ids: Vec<InvocationId>,
+        operations_per_second: Option<NonZeroU32>,
+    ) -> impl Stream<Item = (InvocationId, InvocationOperationResult)> + 'a {
+        let interval = operations_per_second.map(|operations_per_second| {
+            let mut interval =
+                tokio::time::interval(Duration::from_secs(1) / operations_per_second.get());
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+            interval
+        });
+        stream::unfold(
+            (invocation_ids.into_iter(), interval),
+            |(mut invocation_ids, mut interval)| async move {
+                let invocation_id = invocation_ids.next()?;
+                if let Some(interval) = interval.as_mut() {
+                    interval.tick().await;
+                }
+                Some((invocation_id, (invocation_ids, interval)))
+            },
+        )
+        .map(move |invocation_id| {
+            self.apply(client, invocation_id)
+                .map(move |result| (invocation_id, result))
+        })
+        .buffer_unordered(MAX_CONCURRENT_OPERATIONS)
+    }
+}
+
+pub(crate) fn into_batch_operation_result(
+    results: Vec<(InvocationId, InvocationOperationResult)>,
+) -> BatchOperationResult {
+    let mut succeeded = Vec::new();
+    let mut failed = Vec::new();
+
+    for (invocation_id, result) in results {
+        match result {
+            Ok(_) => succeeded.push(invocation_id),
+            Err(error) => failed.push(FailedInvocationOperation {
+                invocation_id,
+                error,
+            }),
+        }
+    }
+
+    BatchOperationResult { succeeded, failed }
+}
+
+fn not_found(invocation_id: InvocationId) -> String {
+    format!("Invocation '{}' not found", invocation_id)
+}
+
+fn already_completed(invocation_id: InvocationId) -> String {
+    format!("Invocation '{}' was already completed", invocation_id)
+}
+
+fn incompatible_deployment(
+    invocation_id: InvocationId,
+    pinned_protocol_version: impl std::fmt::Display,
+    deployment_id: impl std::fmt::Display,
+    supported_protocol_versions: impl std::fmt::Debug,
+) -> String {
+    format!(
+        "Invocation '{}' is running on protocol version '{}', while the chosen deployment '{}' supports the range {:?}",
+        invocation_id, pinned_protocol_version, deployment_id, supported_protocol_versions
+    )
+}
@@ -0,0 +1,530 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::HashSet;
+use std::time::Duration;
+
+use axum::extract::{Path, State};
+use axum::http;
+use axum::response::{IntoResponse, Response};
+use bytes::Bytes;
+use datafusion::error::DataFusionError;
+use futures::{StreamExt, TryStreamExt, stream};
+use http_body::Frame;
+use http_body_util::StreamBody;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use tokio::time::{Instant, Interval, MissedTickBehavior};
+
+use restate_admin_rest_model::invocations::{
+    InvocationTailEvent, InvocationTailJournalEntry, InvocationTailJournalEvent,
+    InvocationTailStatus,
+};
+use restate_bifrost::loglet::FindTailOptions;
+use restate_bifrost::{Bifrost, LogReadStream};
+use restate_core::Metadata;
+use restate_storage_query_datafusion::context::QueryContext;
+use restate_types::identifiers::{InvocationId, PartitionKey, WithPartitionKey};
+use restate_types::logs::{KeyFilter, Keys, LogId, Lsn};
+use restate_types::partition_table::FindPartition;
+use restate_types::retries::RetryPolicy;
+
+use super::error::*;
+use crate::generate_meta_api_error;
+use crate::query_utils::query_rows;
+use crate::state::AdminServiceState;
+
+generate_meta_api_error!(TailInvocationError: [
+    InvocationNotFoundError,
+    InvalidFieldError,
+    QueryUnavailableError,
+    QueryFailedError,
+    LogUnavailableError,
+    LogReadError,
+]);
+
+/// Tail an invocation
+///
+/// Streams the journal entries, the journal events and the status changes of the invocation
+/// as newline-delimited JSON, until the invocation completes.
+#[utoipa::path(
+    get,
+    path = "/invocations/{invocation_id}/tail",
+    operation_id = "tail_invocation",
+    tag = "invocation",
+    params(
+        ("invocation_id" = String, Path, description = "Invocation identifier."),
+    ),
+    responses(
+        (status = 200, description = "Stream of invocation events, one JSON object per line",
+            content_type = "application/x-ndjson", body = InvocationTailEvent),
+        TailInvocationError,
+    )
+)]
+pub async fn tail_invocation<Metadata, Discovery, Telemetry, Invocations, Transport>(
+    State(state): State<AdminServiceState<Metadata, Discovery, Telemetry, Invocations, Transport>>,
+    Path(invocation_id): Path<String>,
+) -> Result<Response, TailInvocationError> {
+    let invocation_id = invocation_id
+        .parse::<InvocationId>()
+        .map_err(|e| InvalidFieldError("invocation_id", e.to_string()))?;
+    let query_context = state.query_context.clone().ok_or(QueryUnavailableError)?;
+    let bifrost = state.bifrost.as_ref().ok_or(LogUnavailableError)?;
+
+    let partition_key = invocation_id.partition_key();
+    let log_id = partition_log_id(partition_key)
+        .ok_or_else(|| InvocationNotFoundError(invocation_id.to_string()))?;
+    // Follow the log from its current tail, so that no record applied after the first read of
+    // the system tables is missed
+    let records = follow_partition_log(bifrost, log_id, partition_key)
+        .await
+        .map_err(LogReadError)?;
+    let mut tail = InvocationTail::new(query_context, invocation_id, records);
+    // Read once upfront, to return a proper error if the invocation doesn't exist
+    let first_events = tail.read().await.map_err(QueryFailedError)?;
+    if tail.state.last_status.is_none() {
+        return Err(InvocationNotFoundError(invocation_id.to_string()).into());
+    }
+
+    let next_events = stream::unfold(tail, |mut tail| async move {
+        if tail.state.done {
+            return None;
+        }
+        let events = tail.next_events().await;
+        if events.is_err() {
+            tail.state.done = true;
+        }
+        Some((events, tail))
+    });
+
+    let body = stream::once(futures::future::ready(Ok(first_events)))
+        .chain(next_events)
+        .map_ok(|events| stream::iter(events.into_iter().map(Ok)))
+        .try_flatten()
+        .map_ok(|event| {
+            let mut line = serde_json::to_vec(&event).expect("tail events are serializable");
+            line.push(b'\n');
+            Frame::data(Bytes::from(line))
+        });
+
+    Ok(Response::builder()
+        .header(http::header::CONTENT_TYPE, "application/x-ndjson")
+        .body(StreamBody::new(body))
+        .expect("content-type header is correct")
+        .into_response())
+}
+
+fn partition_log_id(partition_key: PartitionKey) -> Option<LogId> {
+    Metadata::with_current(|m| {
+        let partition_table = m.partition_table_ref();
+        let partition_id = partition_table.find_partition_id(partition_key).ok()?;
+        partition_table
+            .get(&partition_id)
+            .map(|partition| partition.log_id())
+    })
+}
+
+/// Reads the records of the partition key, from the tail of the partition log.
+async fn follow_partition_log(
+    bifrost: &Bifrost,
+    log_id: LogId,
+    partition_key: PartitionKey,
+) -> Result<LogReadStream, restate_bifrost::Error> {
+    let tail = bifrost
+        .find_tail(log_id, FindTailOptions::default())
+        .await?;
+    bifrost.create_reader(
+        log_id,
+        KeyFilter::Include(partition_key),
+        tail.offset(),
+        Lsn::MAX,
+    )
+}
+
+/// Delays between the reads of the system tables after a record of the invocation is appended,
+/// until the partition processor applied it.
+fn apply_retry_policy() -> RetryPolicy {
+    RetryPolicy::exponential(Duration::from_millis(20), 2.0, Some(5), None)
+}
+
+/// Interval of the reads of the system tables while no record of the invocation is appended,
+/// which catches up with the records applied after the [`apply_retry_policy`] gave up.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Deserialize)]
+struct JournalEntryRow {
+    index: u32,
+    entry_type: String,
+    name: Option<String>,
+    completed: Option<bool>,
+    appended_at: Option<String>,
+    entry_lite_json: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct JournalEventRow {
+    after_journal_entry_index: u32,
+    appended_at: Option<String>,
+    event_type: String,
+    event_json: Option<String>,
+}
+
+/// Rows of the system tables read for the tailed invocation.
+struct Snapshot {
+    status: Option<InvocationTailStatus>,
+    entries: Vec<JournalEntryRow>,
+    events: Vec<JournalEventRow>,
+}
+
+/// Follows the tailed invocation: the system tables are read again whenever a record of its
+/// partition key is appended to the partition log, since only such records change it, and
+/// periodically until the invocation completes.
+struct InvocationTail {
+    query_context: QueryContext,
+    invocation_id: InvocationId,
+    records: LogReadStream,
+    poll_interval: Interval,
+    state: TailState,
+}
+
+impl InvocationTail {
+    fn new(
+        query_context: QueryContext,
+        invocation_id: InvocationId,
+        records: LogReadStream,
+    ) -> Self {
+        let mut poll_interval =
+            tokio::time::interval_at(Instant::now() + POLL_INTERVAL, POLL_INTERVAL);
+        poll_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        Self {
+            query_context,
+            invocation_id,
+            records,
+            poll_interval,
+            state: TailState::default(),
+        }
+    }
+
+    /// Waits for the next events of the invocation. The system tables are read after each record
+    /// of the invocation's partition key, and on every tick of the poll interval. Records may
+    /// concern other invocations of the same key, in which case the tail keeps waiting.
+    async fn next_events(&mut self) -> Result<Vec<InvocationTailEvent>, DataFusionError> {
+        loop {
+            let events = tokio::select! {
+                entry = self.records.next() => {
+                    // The reader only ends or fails on shutdown
+                    let Some(Ok(entry)) = entry else {
+                        self.state.done = true;
+                        return Ok(Vec::new());
+                    };
+                    // Control records addressed to key ranges, such as schema updates, don't
+                    // change the invocation
+                    if !entry
+                        .as_record()
+                        .is_some_and(|record| matches!(record.keys(), Keys::Single(_) | Keys::Pair(..)))
+                    {
+                        continue;
+                    }
+                    self.poll_interval.reset();
+                    self.read_applied().await?
+                }
+                _ = self.poll_interval.tick() => self.read().await?,
+            };
+            if !events.is_empty() || self.state.done {
+                return Ok(events);
+            }
+        }
+    }
+
+    /// Reads the system tables after a record of the invocation was appended, until the
+    /// partition processor applied it or the [`apply_retry_policy`] gives up.
+    async fn read_applied(&mut self) -> Result<Vec<InvocationTailEvent>, DataFusionError> {
+        // The partition processor applies the record shortly after it was appended
+        let mut delays = apply_retry_policy().into_iter();
+        loop {
+            let events = self.read().await?;
+            if !events.is_empty() || self.state.done {
+                return Ok(events);
+            }
+            match delays.next() {
+                Some(delay) => tokio::time::sleep(delay).await,
+                None => return Ok(events),
+            }
+        }
+    }
+
+    /// Reads the system tables, and returns the events not reported yet.
+    ///
+    /// The status is read before the journal, so that no entry is missed when the invocation
+    /// completes in between the queries.
+    async fn read(&mut self) -> Result<Vec<InvocationTailEvent>, DataFusionError> {
+        let id = self.invocation_id;
+        let status = self
+            .query::<InvocationTailStatus>(&format!(
+                "SELECT status, modified_at, retry_count, next_retry_at, last_failure, \
+                last_failure_error_code, completion_result, completion_failure \
+                FROM sys_invocation WHERE id = '{id}'"
+            ))
+            .await?
+            .pop();
+        if status.is_none() {
+            return Ok(self.state.update(Snapshot {
+                status,
+                entries: Vec::new(),
+                events: Vec::new(),
+            }));
+        }
+
+        let entries = self
+            .query::<JournalEntryRow>(&format!(
+                "SELECT index, entry_type, name, completed, appended_at, entry_lite_json \
+                FROM sys_journal WHERE id = '{id}' AND index >= {} ORDER BY index",
+                self.state.next_journal_index
+            ))
+            .await?;
+        let events = self
+            .query::<JournalEventRow>(&format!(
+                "SELECT after_journal_entry_index, appended_at, event_type, event_json \
+                FROM sys_journal_events WHERE id = '{id}' \
+                ORDER BY appended_at, after_journal_entry_index"
+            ))
+            .await?;
+
+        Ok(self.state.update(Snapshot {
+            status,
+            entries,
+            events,
+        }))
+    }
+
+    async fn query<T: DeserializeOwned>(&self, sql: &str) -> Result<Vec<T>, DataFusionError> {
+        query_rows(&self.query_context, sql).await
+    }
+}
+
+/// Identifies a journal event. Events have no index of their own, and an event can be replaced
+/// by a newer one of the same type, such as the last transient error.
+type JournalEventKey = (u32, Option<String>, String);
+
+/// Tracks what was already reported of the tailed invocation.
+#[derive(Default)]
+struct TailState {
+    last_status: Option<InvocationTailStatus>,
+    next_journal_index: u32,
+    reported_events: HashSet<JournalEventKey>,
+    done: bool,
+}
+
+impl TailState {
+    /// Returns the events of the snapshot that were not reported yet.
+    fn update(&mut self, snapshot: Snapshot) -> Vec<InvocationTailEvent> {
+        let Some(status) = snapshot.status else {
+            // The invocation was purged, or never existed
+            self.done = true;
+            return Vec::new();
+        };
+
+        let mut events = Vec::new();
+        for entry in snapshot.entries {
+            if entry.index < self.next_journal_index {
+                continue;
+            }
+            self.next_journal_index = entry.index + 1;
+            events.push(InvocationTailEvent::JournalEntry(
+                InvocationTailJournalEntry {
+                    index: entry.index,
+                    entry_type: entry.entry_type,
+                    name: entry.name.filter(|name| !name.is_empty()),
+                    completed: entry.completed,
+                    appended_at: entry.appended_at,
+                    entry: entry
+                        .entry_lite_json
+                        .and_then(|json| serde_json::from_str(&json).ok()),
+                },
+            ));
+        }
+
+        for event in snapshot.events {
+            let key = (
+                event.after_journal_entry_index,
+                event.appended_at.clone(),
+                event.event_type.clone(),
+            );
+            if !self.reported_events.insert(key) {
+                continue;
+            }
+            events.push(InvocationTailEvent::JournalEvent(
+                InvocationTailJournalEvent {
+                    event_type: event.event_type,
+                    after_journal_entry_index: event.after_journal_entry_index,
+                    appended_at: event.appended_at,
+                    event: event
+                        .event_json
+                        .and_then(|json| serde_json::from_str(&json).ok()),
+                },
+            ));
+        }
+
+        if self.last_status.as_ref() != Some(&status) {
+            self.done = status.is_completed();
+            self.last_status = Some(status.clone());
+            events.push(InvocationTailEvent::Status(status));
+        }
+
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(status: &str, modified_at: &str) -> InvocationTailStatus {
+        InvocationTailStatus {
+            status: status.to_owned(),
+            modified_at: Some(modified_at.to_owned()),
+            retry_count: None,
+            next_retry_at: None,
+            last_failure: None,
+            last_failure_error_code: None,
+            completion_result: None,
+            completion_failure: None,
+        }
+    }
+
+    fn entry(index: u32) -> JournalEntryRow {
+        JournalEntryRow {
+            index,
+            entry_type: "Command: Run".to_owned(),
+            name: Some(String::new()),
+            completed: Some(true),
+            appended_at: None,
+            entry_lite_json: None,
+        }
+    }
+
+    fn event(event_type: &str, appended_at: &str) -> JournalEventRow {
+        JournalEventRow {
+            after_journal_entry_index: 1,
+            appended_at: Some(appended_at.to_owned()),
+            event_type: event_type.to_owned(),
+            event_json: Some(r#"{"error_message":"boom"}"#.to_owned()),
+        }
+    }
+
+    fn snapshot(
+        status: InvocationTailStatus,
+        entries: Vec<JournalEntryRow>,
+        events: Vec<JournalEventRow>,
+    ) -> Snapshot {
+        Snapshot {
+            status: Some(status),
+            entries,
+            events,
+        }
+    }
+
+    fn journal_entry_indexes(events: &[InvocationTailEvent]) -> Vec<u32> {
+        events
+            .iter()
+            .filter_map(|event| match event {
+                InvocationTailEvent::JournalEntry(entry) => Some(entry.index),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn journal_entries_are_reported_once() {
+        let mut state = TailState::default();
+        let running = status("running", "t0");
+
+        let events = state.update(snapshot(running.clone(), vec![entry(0), entry(1)], vec![]));
+        assert_eq!(journal_entry_indexes(&events), vec![0, 1]);
+        assert_eq!(state.next_journal_index, 2);
+
+        // Entries read again are skipped
+        let events = state.update(snapshot(running, vec![entry(1), entry(2)], vec![]));
+        assert_eq!(journal_entry_indexes(&events), vec![2]);
+        // Empty names are omitted
+        let InvocationTailEvent::JournalEntry(entry) = &events[0] else {
+            panic!("expected a journal entry");
+        };
+        assert_eq!(entry.name, None);
+    }
+
+    #[test]
+    fn replaced_journal_events_are_reported_again() {
+        let mut state = TailState::default();
+        let running = status("backing-off", "t0");
+
+        let events = state.update(snapshot(
+            running.clone(),
+            vec![],
+            vec![event("TransientError", "t1")],
+        ));
+        assert_eq!(events.len(), 2);
+
+        // The same event read again is not reported
+        let events = state.update(snapshot(
+            running.clone(),
+            vec![],
+            vec![event("TransientError", "t1")],
+        ));
+        assert!(events.is_empty());
+
+        // The transient error was replaced by a newer one, which is reported even though the
+        // number of events didn't change
+        let events = state.update(snapshot(
+            running,
+            vec![],
+            vec![event("TransientError", "t2")],
+        ));
+        assert_eq!(events.len(), 1);
+        let InvocationTailEvent::JournalEvent(event) = &events[0] else {
+            panic!("expected a journal event");
+        };
+        assert_eq!(event.appended_at.as_deref(), Some("t2"));
+        assert_eq!(event.event.as_ref().unwrap()["error_message"], "boom");
+    }
+
+    #[test]
+    fn status_changes_are_reported_until_completion() {
+        let mut state = TailState::default();
+
+        let events = state.update(snapshot(status("running", "t0"), vec![], vec![]));
+        assert!(matches!(&events[..], [InvocationTailEvent::Status(_)]));
+
+        // An unchanged status is not reported again
+        let events = state.update(snapshot(status("running", "t0"), vec![], vec![]));
+        assert!(events.is_empty());
+
+        let events = state.update(snapshot(status("suspended", "t1"), vec![], vec![]));
+        assert!(matches!(&events[..], [InvocationTailEvent::Status(s)] if s.status == "suspended"));
+        assert!(!state.done);
+
+        let events = state.update(snapshot(status("completed", "t2"), vec![], vec![]));
+        assert!(matches!(&events[..], [InvocationTailEvent::Status(s)] if s.is_completed()));
+        assert!(state.done);
+    }
+
+    #[test]
+    fn missing_invocation_ends_the_tail() {
+        let mut state = TailState::default();
+        let events = state.update(Snapshot {
+            status: None,
+            entries: vec![entry(0)],
+            events: vec![],
+        });
+        assert!(events.is_empty());
+        assert!(state.done);
+        assert!(state.last_status.is_none());
+    }
+}
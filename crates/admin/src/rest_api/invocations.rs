@@ -11,7 +11,6 @@
 use axum::Json;
 use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
-use futures::future;
 use tracing::warn;
 
 use restate_admin_rest_model::invocations::{
@@ -32,6 +31,7 @@ use restate_wal_protocol::{Command, Envelope};
 use serde::Deserialize;
 
 use super::error::*;
+use super::invocation_operations::{InvocationOperation, into_batch_operation_result};
 use crate::generate_meta_api_error;
 use crate::rest_api::create_envelope_header;
 use crate::state::AdminServiceState;
@@ -612,46 +612,11 @@ where
         );
     }
 
-    let futures = request.invocation_ids.into_iter().map(|invocation_id| {
-        let client = state.invocation_client.clone();
-        async move {
-            let result = client
-                .kill_invocation(PartitionProcessorRpcRequestId::new(), invocation_id)
-                .await;
-            (invocation_id, result)
-        }
-    });
-
-    let results = future::join_all(futures).await;
-
-    let mut succeeded = Vec::new();
-    let mut failed = Vec::new();
-
-    for (invocation_id, result) in results {
-        match result {
-            Ok(KillInvocationResponse::Ok) => succeeded.push(invocation_id),
-            Ok(KillInvocationResponse::NotFound) => {
-                failed.push(FailedInvocationOperation {
-                    invocation_id,
-                    error: format!("Invocation '{}' not found", invocation_id),
-                });
-            }
-            Ok(KillInvocationResponse::AlreadyCompleted) => {
-                failed.push(FailedInvocationOperation {
-                    invocation_id,
-                    error: format!("Invocation '{}' was already completed", invocation_id),
-                });
-            }
-            Err(e) => {
-                failed.push(FailedInvocationOperation {
-                    invocation_id,
-                    error: e.to_string(),
-                });
-            }
-        }
-    }
+    let results = InvocationOperation::Kill
+        .apply_all(&state.invocation_client, request.invocation_ids)
+        .await;
 
-    Ok(Json(BatchOperationResult { succeeded, failed }))
+    Ok(Json(into_batch_operation_result(results)))
 }
 
 generate_meta_api_error!(BatchCancelInvocationsError: [BatchTooLargeError, InvocationClientError]);
@@ -670,48 +635,11 @@ where
         );
     }
 
-    let futures = request.invocation_ids.into_iter().map(|invocation_id| {
-        let client = state.invocation_client.clone();
-        async move {
-            let result = client
-                .cancel_invocation(PartitionProcessorRpcRequestId::new(), invocation_id)
-                .await;
-            (invocation_id, result)
-        }
-    });
-
-    let results = future::join_all(futures).await;
+    let results = InvocationOperation::Cancel
+        .apply_all(&state.invocation_client, request.invocation_ids)
+        .await;
 
-    let mut succeeded = Vec::new();
-    let mut failed = Vec::new();
-
-    for (invocation_id, result) in results {
-        match result {
-            Ok(CancelInvocationResponse::Done | CancelInvocationResponse::Appended) => {
-                succeeded.push(invocation_id)
-            }
-            Ok(CancelInvocationResponse::NotFound) => {
-                failed.push(FailedInvocationOperation {
-                    invocation_id,
-                    error: format!("Invocation '{}' not found", invocation_id),
-                });
-            }
-            Ok(CancelInvocationResponse::AlreadyCompleted) => {
-                failed.push(FailedInvocationOperation {
-                    invocation_id,
-                    error: format!("Invocation '{}' was already completed", invocation_id),
-                });
-            }
-            Err(e) => {
-                failed.push(FailedInvocationOperation {
-                    invocation_id,
-                    error: e.to_string(),
-                });
-            }
-        }
-    }
-
-    Ok(Json(BatchOperationResult { succeeded, failed }))
+    Ok(Json(into_batch_operation_result(results)))
 }
 
 generate_meta_api_error!(BatchPurgeInvocationsError: [BatchTooLargeError, InvocationClientError]);
@@ -730,46 +658,11 @@ where
         );
     }
 
-    let futures = request.invocation_ids.into_iter().map(|invocation_id| {
-        let client = state.invocation_client.clone();
-        async move {
-            let result = client
-                .purge_invocation(PartitionProcessorRpcRequestId::new(), invocation_id)
-                .await;
-            (invocation_id, result)
-        }
-    });
-
-    let results = future::join_all(futures).await;
+    let results = InvocationOperation::Purge
+        .apply_all(&state.invocation_client, request.invocation_ids)
+        .await;
 
-    let mut succeeded = Vec::new();
-    let mut failed = Vec::new();
-
-    for (invocation_id, result) in results {
-        match result {
-            Ok(PurgeInvocationResponse::Ok) => succeeded.push(invocation_id),
-            Ok(PurgeInvocationResponse::NotFound) => {
-                failed.push(FailedInvocationOperation {
-                    invocation_id,
-                    error: format!("Invocation '{}' not found", invocation_id),
-                });
-            }
-            Ok(PurgeInvocationResponse::NotCompleted) => {
-                failed.push(FailedInvocationOperation {
-                    invocation_id,
-                    error: format!("Invocation '{}' is not yet completed", invocation_id),
-                });
-            }
-            Err(e) => {
-                failed.push(FailedInvocationOperation {
-                    invocation_id,
-                    error: e.to_string(),
-                });
-            }
-        }
-    }
-
-    Ok(Json(BatchOperationResult { succeeded, failed }))
+    Ok(Json(into_batch_operation_result(results)))
 }
 
 generate_meta_api_error!(BatchPurgeJournalError: [BatchTooLargeError, InvocationClientError]);
@@ -788,46 +681,11 @@ where
         );
     }
 
-    let futures = request.invocation_ids.into_iter().map(|invocation_id| {
-        let client = state.invocation_client.clone();
-        async move {
-            let result = client
-                .purge_journal(PartitionProcessorRpcRequestId::new(), invocation_id)
-                .await;
-            (invocation_id, result)
-        }
-    });
-
-    let results = future::join_all(futures).await;
-
-    let mut succeeded = Vec::new();
-    let mut failed = Vec::new();
-
-    for (invocation_id, result) in results {
-        match result {
-            Ok(PurgeInvocationResponse::Ok) => succeeded.push(invocation_id),
-            Ok(PurgeInvocationResponse::NotFound) => {
-                failed.push(FailedInvocationOperation {
-                    invocation_id,
-                    error: format!("Invocation '{}' not found", invocation_id),
-                });
-            }
-            Ok(PurgeInvocationResponse::NotCompleted) => {
-                failed.push(FailedInvocationOperation {
-                    invocation_id,
-                    error: format!("Invocation '{}' is not yet completed", invocation_id),
-                });
-            }
-            Err(e) => {
-                failed.push(FailedInvocationOperation {
-                    invocation_id,
-                    error: e.to_string(),
-                });
-            }
-        }
-    }
+    let results = InvocationOperation::PurgeJournal
+        .apply_all(&state.invocation_client, request.invocation_ids)
+        .await;
 
-    Ok(Json(BatchOperationResult { succeeded, failed }))
+    Ok(Json(into_batch_operation_result(results)))
 }
 
 generate_meta_api_error!(BatchRestartAsNewError: [BatchTooLargeError, InvocationClientError, InvalidFieldError]);
@@ -859,126 +717,24 @@ where
         .into_client()
         .map_err(|e| InvalidFieldError("deployment", e))?;
 
-    let futures = request.invocation_ids.into_iter().map(|invocation_id| {
-        let client = state.invocation_client.clone();
-        let deployment = deployment.clone();
-        async move {
-            let result = client
-                .restart_as_new_invocation(
-                    PartitionProcessorRpcRequestId::new(),
-                    invocation_id,
-                    EntryIndex::default(), // Always restart from the beginning
-                    deployment,
-                )
-                .await;
-            (invocation_id, result)
-        }
-    });
-
-    let results = future::join_all(futures).await;
+    let results = InvocationOperation::RestartAsNew(deployment)
+        .apply_all(&state.invocation_client, request.invocation_ids)
+        .await;
 
     let mut succeeded = Vec::new();
     let mut failed = Vec::new();
 
     for (invocation_id, result) in results {
         match result {
-            Ok(client::RestartAsNewInvocationResponse::Ok { new_invocation_id }) => {
-                succeeded.push(RestartedInvocation {
-                    old_invocation_id: invocation_id,
-                    new_invocation_id,
-                });
-            }
-            Ok(client::RestartAsNewInvocationResponse::NotFound) => {
-                failed.push(FailedInvocationOperation {
-                    invocation_id,
-                    error: format!("Invocation '{}' not found", invocation_id),
-                });
-            }
-            Ok(client::RestartAsNewInvocationResponse::StillRunning) => {
-                failed.push(FailedInvocationOperation {
-                    invocation_id,
-                    error: format!("Invocation '{}' is still running", invocation_id),
-                });
-            }
-            Ok(client::RestartAsNewInvocationResponse::Unsupported) => {
-                failed.push(FailedInvocationOperation {
-                    invocation_id,
-                    error: format!("Restarting invocation '{}' is not supported", invocation_id),
-                });
-            }
-            Ok(client::RestartAsNewInvocationResponse::MissingInput) => {
-                failed.push(FailedInvocationOperation {
-                    invocation_id,
-                    error: format!(
-                        "Invocation '{}' cannot be restarted because the input is not available",
-                        invocation_id
-                    ),
-                });
-            }
-            Ok(client::RestartAsNewInvocationResponse::NotStarted) => {
-                failed.push(FailedInvocationOperation {
-                    invocation_id,
-                    error: format!(
-                        "Invocation '{}' cannot be restarted because it's not running yet",
-                        invocation_id
-                    ),
-                });
-            }
-            Ok(client::RestartAsNewInvocationResponse::JournalIndexOutOfRange) => {
-                failed.push(FailedInvocationOperation {
-                    invocation_id,
-                    error: format!(
-                        "Journal index out of range for invocation '{}'",
-                        invocation_id
-                    ),
-                });
-            }
-            Ok(client::RestartAsNewInvocationResponse::JournalCopyRangeInvalid) => {
-                failed.push(FailedInvocationOperation {
-                    invocation_id,
-                    error: format!(
-                        "Journal copy range invalid for invocation '{}'",
-                        invocation_id
-                    ),
-                });
-            }
-            Ok(client::RestartAsNewInvocationResponse::CannotPatchDeploymentId) => {
-                failed.push(FailedInvocationOperation {
-                    invocation_id,
-                    error: format!(
-                        "Cannot change deployment ID for invocation '{}'",
-                        invocation_id
-                    ),
-                });
-            }
-            Ok(client::RestartAsNewInvocationResponse::DeploymentNotFound) => {
-                failed.push(FailedInvocationOperation {
-                    invocation_id,
-                    error: format!(
-                        "Deployment not found when restarting invocation '{}'",
-                        invocation_id
-                    ),
-                });
-            }
-            Ok(client::RestartAsNewInvocationResponse::IncompatibleDeploymentId {
-                pinned_protocol_version,
-                deployment_id,
-                supported_protocol_versions,
-            }) => {
-                failed.push(FailedInvocationOperation {
-                    invocation_id,
-                    error: format!(
-                        "Invocation '{}' is running on protocol version '{}', while the chosen deployment '{}' supports the range {:?}",
-                        invocation_id, pinned_protocol_version, deployment_id, supported_protocol_versions
-                    ),
-                });
-            }
-            Err(e) => {
-                failed.push(FailedInvocationOperation {
-                    invocation_id,
-                    error: e.to_string(),
-                });
-            }
+            Ok(new_invocation_id) => succeeded.push(RestartedInvocation {
+                old_invocation_id: invocation_id,
+                new_invocation_id: new_invocation_id
+                    .expect("restarting as new returns the new invocation id"),
+            }),
+            Err(error) => failed.push(FailedInvocationOperation {
+                invocation_id,
+                error,
+            }),
         }
     }
 
@@ -1008,94 +764,11 @@ where
         .into_client()
         .map_err(|e| InvalidFieldError("deployment", e))?;
 
-    let futures = request.invocation_ids.into_iter().map(|invocation_id| {
-        let client = state.invocation_client.clone();
-        let deployment = deployment.clone();
-        async move {
-            let result = client
-                .resume_invocation(
-                    PartitionProcessorRpcRequestId::new(),
-                    invocation_id,
-                    deployment,
-                )
-                .await;
-            (invocation_id, result)
-        }
-    });
-
-    let results = future::join_all(futures).await;
-
-    let mut succeeded = Vec::new();
-    let mut failed = Vec::new();
+    let results = InvocationOperation::Resume(deployment)
+        .apply_all(&state.invocation_client, request.invocation_ids)
+        .await;
 
-    for (invocation_id, result) in results {
-        match result {
-            Ok(ResumeInvocationResponse::Ok) => succeeded.push(invocation_id),
-            Ok(ResumeInvocationResponse::NotFound) => {
-                failed.push(FailedInvocationOperation {
-                    invocation_id,
-                    error: format!("Invocation '{}' not found", invocation_id),
-                });
-            }
-            Ok(ResumeInvocationResponse::NotStarted) => {
-                failed.push(FailedInvocationOperation {
-                    invocation_id,
-                    error: format!(
-                        "Invocation '{}' is either inboxed or scheduled, cannot be resumed",
-                        invocation_id
-                    ),
-                });
-            }
-            Ok(ResumeInvocationResponse::Completed) => {
-                failed.push(FailedInvocationOperation {
-                    invocation_id,
-                    error: format!(
-                        "Invocation '{}' is completed, cannot be resumed",
-                        invocation_id
-                    ),
-                });
-            }
-            Ok(ResumeInvocationResponse::CannotChangeDeploymentId) => {
-                failed.push(FailedInvocationOperation {
-                    invocation_id,
-                    error: format!(
-                        "Cannot change deployment ID for invocation '{}'",
-                        invocation_id
-                    ),
-                });
-            }
-            Ok(ResumeInvocationResponse::DeploymentNotFound) => {
-                failed.push(FailedInvocationOperation {
-                    invocation_id,
-                    error: format!(
-                        "Deployment not found when resuming invocation '{}'",
-                        invocation_id
-                    ),
-                });
-            }
-            Ok(ResumeInvocationResponse::IncompatibleDeploymentId {
-                pinned_protocol_version,
-                deployment_id,
-                supported_protocol_versions,
-            }) => {
-                failed.push(FailedInvocationOperation {
-                    invocation_id,
-                    error: format!(
-                        "Invocation '{}' is running on protocol version '{}', while the chosen deployment '{}' supports the range {:?}",
-                        invocation_id, pinned_protocol_version, deployment_id, supported_protocol_versions
-                    ),
-                });
-            }
-            Err(e) => {
-                failed.push(FailedInvocationOperation {
-                    invocation_id,
-                    error: e.to_string(),
-                });
-            }
-        }
-    }
-
-    Ok(Json(BatchOperationResult { succeeded, failed }))
+    Ok(Json(into_batch_operation_result(results)))
 }
 
 generate_meta_api_error!(BatchPauseInvocationsError: [BatchTooLargeError, InvocationClientError]);
@@ -1114,49 +787,9 @@ where
         );
     }
 
-    let futures = request.invocation_ids.into_iter().map(|invocation_id| {
-        let client = state.invocation_client.clone();
-        async move {
-            let result = client
-                .pause_invocation(PartitionProcessorRpcRequestId::new(), invocation_id)
-                .await;
-            (invocation_id, result)
-        }
-    });
-
-    let results = future::join_all(futures).await;
-
-    let mut succeeded = Vec::new();
-    let mut failed = Vec::new();
-
-    for (invocation_id, result) in results {
-        match result {
-            Ok(PauseInvocationResponse::Accepted | PauseInvocationResponse::AlreadyPaused) => {
-                succeeded.push(invocation_id)
-            }
-            Ok(PauseInvocationResponse::NotFound) => {
-                failed.push(FailedInvocationOperation {
-                    invocation_id,
-                    error: format!("Invocation '{}' not found", invocation_id),
-                });
-            }
-            Ok(PauseInvocationResponse::NotRunning) => {
-                failed.push(FailedInvocationOperation {
-                    invocation_id,
-                    error: format!(
-                        "Invocation '{}' is not running, cannot be paused",
-                        invocation_id
-                    ),
-                });
-            }
-            Err(e) => {
-                failed.push(FailedInvocationOperation {
-                    invocation_id,
-                    error: e.to_string(),
-                });
-            }
-        }
-    }
+    let results = InvocationOperation::Pause
+        .apply_all(&state.invocation_client, request.invocation_ids)
+        .await;
 
-    Ok(Json(BatchOperationResult { succeeded, failed }))
+    Ok(Json(into_batch_operation_result(results)))
 }
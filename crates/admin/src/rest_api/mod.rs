@@ -10,18 +10,25 @@
 
 //! This module implements the Meta API endpoint.
 
+mod bulk_operations;
 mod cluster_health;
 mod deployments;
 mod error;
 mod handlers;
 mod health;
+mod invocation_operations;
+mod invocation_tail;
 mod invocations;
 mod kafka_clusters;
 mod query;
+mod query_operations;
 mod rules;
+mod schedules;
 mod serdes;
+mod service_state;
 mod services;
 mod subscriptions;
+mod time;
 mod version;
 
 use serde::Serialize;
@@ -36,6 +43,7 @@ use restate_wal_protocol::{Destination, Header, Source};
 
 use crate::state::AdminServiceState;
 
+pub(crate) use bulk_operations::BulkOperationJobs;
 pub use version::{MAX_ADMIN_API_VERSION, MIN_ADMIN_API_VERSION};
 
 #[derive(OpenApi)]
@@ -57,6 +65,7 @@ pub use version::{MAX_ADMIN_API_VERSION, MIN_ADMIN_API_VERSION};
         (name = "subscription", description = "Subscription management",
          external_docs(url = "https://docs.restate.dev/operate/invocation#managing-kafka-subscriptions", description = "Kafka subscriptions documentation")),
         (name = "kafka_cluster", description = "Kafka cluster management"),
+        (name = "schedule", description = "Cron schedule management"),
         (name = "service", description = "Service management"),
         (name = "service_handler", description = "Service handlers metadata"),
         (name = "cluster_health", description = "Cluster health"),
@@ -64,6 +73,7 @@ pub use version::{MAX_ADMIN_API_VERSION, MIN_ADMIN_API_VERSION};
         (name = "version", description = "API Version"),
         (name = "introspection", description = "System introspection"),
         (name = "rule", description = "Limiter rule book management"),
+        (name = "time", description = "Controllable time of test servers"),
     ),
     components(responses(
         error::meta_api_error::BadRequest,
@@ -103,6 +113,8 @@ where
             .routes(routes!(services::get_service_openapi))
             .routes(routes!(services::modify_service))
             .routes(routes!(services::modify_service_state))
+            .routes(routes!(service_state::export_service_state))
+            .routes(routes!(service_state::import_service_state))
             // Handler endpoints
             .routes(routes!(handlers::list_service_handlers))
             .routes(routes!(handlers::get_service_handler))
@@ -115,22 +127,38 @@ where
             .routes(routes!(invocations::restart_as_new_invocation))
             .routes(routes!(invocations::resume_invocation))
             .routes(routes!(invocations::pause_invocation))
+            .routes(routes!(invocation_tail::tail_invocation))
+            .routes(routes!(bulk_operations::submit_bulk_invocation_operation))
+            .routes(routes!(bulk_operations::get_bulk_invocation_operation))
+            .routes(routes!(time::fire_invocation_timers))
             // Subscription endpoints
             .routes(routes!(subscriptions::create_subscription))
             .routes(routes!(subscriptions::list_subscriptions))
             .routes(routes!(subscriptions::get_subscription))
             .routes(routes!(subscriptions::delete_subscription))
+            .routes(routes!(subscriptions::pause_subscription))
+            .routes(routes!(subscriptions::resume_subscription))
+            .routes(routes!(subscriptions::seek_subscription))
             // Kafka cluster endpoints
             .routes(routes!(kafka_clusters::create_kafka_cluster))
             .routes(routes!(kafka_clusters::list_kafka_clusters))
             .routes(routes!(kafka_clusters::get_kafka_cluster))
             .routes(routes!(kafka_clusters::update_kafka_cluster))
             .routes(routes!(kafka_clusters::delete_kafka_cluster))
+            // Schedule endpoints
+            .routes(routes!(schedules::create_schedule))
+            .routes(routes!(schedules::list_schedules))
+            .routes(routes!(schedules::get_schedule))
+            .routes(routes!(schedules::modify_schedule))
+            .routes(routes!(schedules::delete_schedule))
             // Rule book endpoints
             .routes(routes!(rules::upsert_rules))
             .routes(routes!(rules::delete_rules))
-            // Query endpoint
+            // Controllable time endpoints
+            .routes(routes!(time::advance_time))
+            // Query endpoints
             .routes(routes!(query::query))
+            .routes(routes!(query::query_operations))
     };
 
     let (router, api) = router.split_for_parts();
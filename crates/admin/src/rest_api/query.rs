@@ -9,10 +9,12 @@
 // by the Apache License, Version 2.0.
 
 use std::io::Write;
+use std::num::NonZeroU32;
 use std::pin::Pin;
 use std::sync::Arc;
 
-use crate::query_utils::{RecordBatchWriter, WriteRecordBatchStream};
+use super::query_operations::{InvocationOperationQuery, InvocationOperationQueryError};
+use crate::query_utils::{ParquetWriter, RecordBatchWriter, WriteRecordBatchStream};
 use crate::state::AdminServiceState;
 use axum::extract::State;
 use axum::http::StatusCode;
@@ -24,12 +26,14 @@ use datafusion::arrow::datatypes::Schema;
 use datafusion::arrow::ipc::writer::StreamWriter;
 use datafusion::arrow::json::writer::JsonArray;
 use datafusion::common::DataFusionError;
+use datafusion::execution::SendableRecordBatchStream;
+use futures::stream::BoxStream;
 use futures::{StreamExt, TryStreamExt};
 use http::{HeaderMap, HeaderValue};
 use http_body::Frame;
 use http_body_util::StreamBody;
 use parking_lot::Mutex;
-use restate_admin_rest_model::query::QueryRequest;
+use restate_admin_rest_model::query::{QueryFormat, QueryOperationsRequest, QueryRequest};
 use restate_core::network::TransportConnect;
 use restate_types::invocation::client::InvocationClient;
 use restate_types::schema::registry::{DiscoveryClient, MetadataService, TelemetryClient};
@@ -48,6 +52,14 @@ pub(crate) enum QueryError {
     Datafusion(#[from] datafusion::error::DataFusionError),
     #[error("Query service not available")]
     Unavailable,
+    #[error(transparent)]
+    InvocationOperation(#[from] InvocationOperationQueryError),
+    #[error("{0} applies an operation to invocations, use the /query/operations endpoint instead")]
+    OperationNotAllowed(String),
+    #[error(
+        "The query doesn't select any of the invocation functions, use the /query endpoint instead"
+    )]
+    NoOperation,
 }
 
 impl IntoResponse for QueryError {
@@ -55,6 +67,12 @@ impl IntoResponse for QueryError {
         let status_code = match &self {
             QueryError::Datafusion(_) => StatusCode::INTERNAL_SERVER_ERROR,
             QueryError::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+            QueryError::InvocationOperation(InvocationOperationQueryError::Datafusion(_)) => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+            QueryError::InvocationOperation(_)
+            | QueryError::OperationNotAllowed(_)
+            | QueryError::NoOperation => StatusCode::BAD_REQUEST,
         };
         (
             status_code,
@@ -67,6 +85,10 @@ impl IntoResponse for QueryError {
 }
 
 /// Query the system and service state by using SQL.
+///
+/// The results are streamed in the requested `format`: JSON, an Arrow IPC stream, a Parquet
+/// file or CSV. Queries selecting one of the invocation functions are rejected, they must use
+/// the `/query/operations` endpoint.
 #[utoipa::path(
     post,
     path = "/query",
@@ -76,8 +98,11 @@ impl IntoResponse for QueryError {
         (status = 200, description = "Query results",
             content (
                 ("application/vnd.apache.arrow.stream"),
-                ("application/json", example = json!({"rows": []}))
+                ("application/json", example = json!({"rows": []})),
+                ("application/vnd.apache.parquet"),
+                ("text/csv")
             )),
+        (status = 400, description = "The query applies an operation to invocations", body = QueryErrorBody),
         (status = 500, description = "Datafusion error", body = QueryErrorBody),
         (status = 503, description = "Query service not available", body = QueryErrorBody),
     )
@@ -98,24 +123,100 @@ where
         return Err(QueryError::Unavailable);
     };
 
-    let query_result = query_context.execute(&payload.query).await?;
+    if let Some(operation_query) = InvocationOperationQuery::parse(&payload.query)? {
+        return Err(QueryError::OperationNotAllowed(
+            operation_query.function().to_owned(),
+        ));
+    }
+    let record_batches = query_context.execute(&payload.query).await?.stream;
 
-    let (result_stream, content_type) = match headers.get(http::header::ACCEPT) {
-        Some(v) if v == HeaderValue::from_static("application/json") => (
-            WriteRecordBatchStream::<JsonWriter>::new(query_result.stream, payload.query)?
-                .map_ok(Frame::data)
-                .left_stream(),
-            "application/json",
-        ),
-        _ => (
-            WriteRecordBatchStream::<StreamWriter<Vec<u8>>>::new(
-                query_result.stream,
-                payload.query,
-            )?
-            .map_ok(Frame::data)
-            .right_stream(),
-            "application/vnd.apache.arrow.stream",
+    stream_results(&headers, payload.format, payload.query, record_batches).await
+}
+
+/// Apply an operation to the invocations selected by a SQL query.
+///
+/// The query must select exactly one of the functions `restate_kill(id)`, `restate_cancel(id)`,
+/// `restate_purge(id)`, `restate_purge_journal(id)`, `restate_pause(id)`, `restate_resume(id)` or
+/// `restate_restart_as_new(id)`, for example
+/// `SELECT restate_cancel(id) FROM sys_invocation WHERE target_service_name = 'Greeter'`.
+/// The result contains the outcome of the operation for every selected invocation.
+///
+/// Unless `dry_run` is set to `false`, the selected invocations are only returned, without
+/// applying the operation.
+#[utoipa::path(
+    post,
+    path = "/query/operations",
+    operation_id = "query_operations",
+    tag = "invocation",
+    responses(
+        (status = 200, description = "Outcome of the operation for every selected invocation",
+            content (
+                ("application/vnd.apache.arrow.stream"),
+                ("application/json", example = json!({"rows": []})),
+                ("application/vnd.apache.parquet"),
+                ("text/csv")
+            )),
+        (status = 400, description = "Invalid invocation operation", body = QueryErrorBody),
+        (status = 500, description = "Datafusion error", body = QueryErrorBody),
+        (status = 503, description = "Query service not available", body = QueryErrorBody),
+    )
+)]
+pub(crate) async fn query_operations<Metadata, Discovery, Telemetry, Invocations, Transport>(
+    State(state): State<AdminServiceState<Metadata, Discovery, Telemetry, Invocations, Transport>>,
+    headers: HeaderMap,
+    Json(payload): Json<QueryOperationsRequest>,
+) -> Result<Response, QueryError>
+where
+    Metadata: MetadataService + Send + Sync + Clone + 'static,
+    Discovery: DiscoveryClient + Send + Sync + Clone + 'static,
+    Telemetry: TelemetryClient + Send + Sync + Clone + 'static,
+    Invocations: InvocationClient + Send + Sync + Clone + 'static,
+    Transport: TransportConnect,
+{
+    let Some(query_context) = state.query_context.as_ref() else {
+        return Err(QueryError::Unavailable);
+    };
+
+    let Some(operation_query) = InvocationOperationQuery::parse(&payload.query)? else {
+        return Err(QueryError::NoOperation);
+    };
+    let operations_per_second = match payload.operations_per_second {
+        Some(operations_per_second) => Some(
+            NonZeroU32::new(operations_per_second)
+                .ok_or(InvocationOperationQueryError::ZeroOperationsPerSecond)?,
         ),
+        None => None,
+    };
+    let record_batches = operation_query
+        .execute(
+            query_context,
+            &state.invocation_client,
+            payload.dry_run,
+            operations_per_second,
+        )
+        .await?;
+
+    stream_results(&headers, payload.format, payload.query, record_batches).await
+}
+
+/// Streams the record batches in the requested format, falling back to the `Accept` header.
+async fn stream_results(
+    headers: &HeaderMap,
+    format: Option<QueryFormat>,
+    query: String,
+    record_batches: SendableRecordBatchStream,
+) -> Result<Response, QueryError> {
+    let format = format.unwrap_or_else(|| match headers.get(http::header::ACCEPT) {
+        Some(v) if v == HeaderValue::from_static("application/json") => QueryFormat::Json,
+        _ => QueryFormat::ArrowIpc,
+    });
+    let result_stream = match format {
+        QueryFormat::Json => write_record_batches::<JsonWriter>(record_batches, query)?,
+        QueryFormat::ArrowIpc => {
+            write_record_batches::<StreamWriter<Vec<u8>>>(record_batches, query)?
+        }
+        QueryFormat::Parquet => write_record_batches::<ParquetWriter>(record_batches, query)?,
+        QueryFormat::Csv => write_record_batches::<CsvWriter>(record_batches, query)?,
     };
 
     let mut result_stream = result_stream.peekable();
@@ -127,12 +228,21 @@ where
     }
 
     Ok(Response::builder()
-        .header(http::header::CONTENT_TYPE, content_type)
+        .header(http::header::CONTENT_TYPE, format.content_type())
         .body(StreamBody::new(result_stream))
         .expect("content-type header is correct")
         .into_response())
 }
 
+fn write_record_batches<W: RecordBatchWriter + Unpin + Send + 'static>(
+    record_batches: SendableRecordBatchStream,
+    query: String,
+) -> Result<BoxStream<'static, Result<Frame<Bytes>, DataFusionError>>, DataFusionError> {
+    Ok(WriteRecordBatchStream::<W>::new(record_batches, query)?
+        .map_ok(Frame::data)
+        .boxed())
+}
+
 #[derive(Clone)]
 // unfortunately the json writer doesnt give a way to get a mutable reference to the underlying writer, so we need another pointer in to its buffer
 // we use a lock here to help make the writer send/sync, despite it being totally uncontended :(
@@ -193,3 +303,54 @@ impl RecordBatchWriter for JsonWriter {
         Ok(Bytes::from(self.lock_writer.take()))
     }
 }
+
+/// Writes one JSON object per row, separated by newlines.
+pub(crate) struct NdjsonWriter {
+    json_writer: datafusion::arrow::json::LineDelimitedWriter<LockWriter>,
+    lock_writer: LockWriter,
+}
+
+impl RecordBatchWriter for NdjsonWriter {
+    fn new(_schema: &Schema) -> Result<Self, DataFusionError> {
+        let lock_writer = LockWriter::new();
+        Ok(Self {
+            json_writer: datafusion::arrow::json::LineDelimitedWriter::new(lock_writer.clone()),
+            lock_writer,
+        })
+    }
+
+    fn write(&mut self, batch: &RecordBatch) -> Result<Bytes, DataFusionError> {
+        self.json_writer.write(batch)?;
+        Ok(Bytes::from(self.lock_writer.take()))
+    }
+
+    fn finish(&mut self) -> Result<Bytes, DataFusionError> {
+        self.json_writer.finish()?;
+        Ok(Bytes::from(self.lock_writer.take()))
+    }
+}
+
+/// Writes CSV, with a header row.
+pub(crate) struct CsvWriter {
+    csv_writer: datafusion::arrow::csv::Writer<LockWriter>,
+    lock_writer: LockWriter,
+}
+
+impl RecordBatchWriter for CsvWriter {
+    fn new(_schema: &Schema) -> Result<Self, DataFusionError> {
+        let lock_writer = LockWriter::new();
+        Ok(Self {
+            csv_writer: datafusion::arrow::csv::Writer::new(lock_writer.clone()),
+            lock_writer,
+        })
+    }
+
+    fn write(&mut self, batch: &RecordBatch) -> Result<Bytes, DataFusionError> {
+        self.csv_writer.write(batch)?;
+        Ok(Bytes::from(self.lock_writer.take()))
+    }
+
+    fn finish(&mut self) -> Result<Bytes, DataFusionError> {
+        Ok(Bytes::from(self.lock_writer.take()))
+    }
+}
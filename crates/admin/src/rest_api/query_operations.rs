@@ -0,0 +1,296 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Invocation functions of the SQL query endpoint, for example
+//! `SELECT restate_cancel(id) FROM sys_invocation WHERE status = 'backing-off'`.
+//!
+//! The query is rewritten to select the invocation ids, and the operation is applied to each of
+//! them through the same paths as the batch endpoints. The result has one row per invocation.
+
+use std::num::NonZeroU32;
+use std::sync::Arc;
+
+use datafusion::arrow::array::{ArrayRef, StringArray};
+use datafusion::arrow::datatypes::{DataType, Field, Schema};
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::error::DataFusionError;
+use datafusion::execution::SendableRecordBatchStream;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::sql::sqlparser::ast::{
+    Expr, FunctionArg, FunctionArgExpr, FunctionArguments, Ident, SelectItem, SetExpr, Statement,
+};
+use datafusion::sql::sqlparser::dialect::PostgreSqlDialect;
+use datafusion::sql::sqlparser::parser::Parser;
+use futures::{StreamExt, stream};
+use serde::Deserialize;
+
+use restate_admin_rest_model::invocations::BATCH_OPERATION_MAX_SIZE;
+use restate_storage_query_datafusion::context::QueryContext;
+use restate_types::identifiers::InvocationId;
+use restate_types::invocation::client::{InvocationClient, PatchDeploymentId};
+
+use super::invocation_operations::{InvocationOperation, InvocationOperationResult};
+use crate::query_utils::query_rows;
+
+/// Maps the invocation functions to their operation.
+fn operation_of(function: &str) -> Option<InvocationOperation> {
+    Some(match function {
+        "restate_kill" => InvocationOperation::Kill,
+        "restate_cancel" => InvocationOperation::Cancel,
+        "restate_purge" => InvocationOperation::Purge,
+        "restate_purge_journal" => InvocationOperation::PurgeJournal,
+        "restate_pause" => InvocationOperation::Pause,
+        "restate_resume" => InvocationOperation::Resume(PatchDeploymentId::KeepPinned),
+        "restate_restart_as_new" => {
+            InvocationOperation::RestartAsNew(PatchDeploymentId::PinToLatest)
+        }
+        _ => return None,
+    })
+}
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum InvocationOperationQueryError {
+    #[error(
+        "{0} must be the only column of the query, and take the invocation id as single argument"
+    )]
+    InvalidUsage(String),
+    #[error(
+        "The query selected {0} invocations, which exceeds the maximum of {1}. Use LIMIT to operate on fewer invocations at once"
+    )]
+    TooManyInvocations(usize, usize),
+    #[error("operations_per_second must be greater than zero")]
+    ZeroOperationsPerSecond,
+    #[error("{0}")]
+    Datafusion(#[from] DataFusionError),
+}
+
+/// A query applying an [`InvocationOperation`] to the invocations it selects.
+#[derive(Debug)]
+pub(crate) struct InvocationOperationQuery {
+    operation: InvocationOperation,
+    function: String,
+    /// The query with the function replaced by its argument.
+    ids_query: String,
+}
+
+#[derive(Deserialize)]
+struct IdRow {
+    id: Option<String>,
+}
+
+impl InvocationOperationQuery {
+    /// The invocation function selected by the query.
+    pub(crate) fn function(&self) -> &str {
+        &self.function
+    }
+
+    /// Returns `None` unless the query uses one of the invocation functions. Queries that can't
+    /// be parsed are left to the query engine, to report the error.
+    pub(crate) fn parse(sql: &str) -> Result<Option<Self>, InvocationOperationQueryError> {
+        let Ok(mut statements) = Parser::parse_sql(&PostgreSqlDialect {}, sql) else {
+            return Ok(None);
+        };
+        let [Statement::Query(query)] = statements.as_mut_slice() else {
+            return Ok(None);
+        };
+        let SetExpr::Select(select) = query.body.as_mut() else {
+            return Ok(None);
+        };
+
+        let Some((name, operation, function)) =
+            select.projection.iter().find_map(|item| match item {
+                SelectItem::UnnamedExpr(Expr::Function(function))
+                | SelectItem::ExprWithAlias {
+                    expr: Expr::Function(function),
+                    ..
+                } => {
+                    let name = function.name.to_string().to_lowercase();
+                    operation_of(&name).map(|operation| (name, operation, function))
+                }
+                _ => None,
+            })
+        else {
+            return Ok(None);
+        };
+
+        let argument = match &function.args {
+            FunctionArguments::List(list) if select.projection.len() == 1 => {
+                match list.args.as_slice() {
+                    [FunctionArg::Unnamed(FunctionArgExpr::Expr(argument))] => argument.clone(),
+                    _ => return Err(InvocationOperationQueryError::InvalidUsage(name)),
+                }
+            }
+            _ => return Err(InvocationOperationQueryError::InvalidUsage(name)),
+        };
+
+        select.projection = vec![SelectItem::ExprWithAlias {
+            expr: argument,
+            alias: Ident::new("id"),
+        }];
+
+        Ok(Some(Self {
+            operation,
+            function: name,
+            ids_query: statements[0].to_string(),
+        }))
+    }
+
+    /// Applies the operation to the selected invocations, at most `operations_per_second` if
+    /// set. Dry runs only report the selected invocations.
+    pub(crate) async fn execute<Invocations: InvocationClient>(
+        self,
+        query_context: &QueryContext,
+        invocation_client: &Invocations,
+        dry_run: bool,
+        operations_per_second: Option<NonZeroU32>,
+    ) -> Result<SendableRecordBatchStream, InvocationOperationQueryError> {
+        let rows: Vec<IdRow> = query_rows(query_context, &self.ids_query).await?;
+        if rows.len() > BATCH_OPERATION_MAX_SIZE {
+            return Err(InvocationOperationQueryError::TooManyInvocations(
+                rows.len(),
+                BATCH_OPERATION_MAX_SIZE,
+            ));
+        }
+
+        let mut results = Vec::with_capacity(rows.len());
+        let mut invocation_ids = Vec::with_capacity(rows.len());
+        for row in rows {
+            let id = row.id.unwrap_or_default();
+            match id.parse::<InvocationId>() {
+                Ok(invocation_id) => invocation_ids.push(invocation_id),
+                Err(e) => results.push(OperationRow {
+                    id,
+                    result: Err(format!("Invalid invocation id: {e}")),
+                }),
+            }
+        }
+
+        if dry_run {
+            results.extend(
+                invocation_ids
+                    .into_iter()
+                    .map(|invocation_id| OperationRow {
+                        id: invocation_id.to_string(),
+                        result: Ok(None),
+                    }),
+            );
+        } else {
+            let applied: Vec<(InvocationId, InvocationOperationResult)> = self
+                .operation
+                .apply_throttled(invocation_client, invocation_ids, operations_per_second)
+                .collect()
+                .await;
+            results.extend(
+                applied
+                    .into_iter()
+                    .map(|(invocation_id, result)| OperationRow {
+                        id: invocation_id.to_string(),
+                        result,
+                    }),
+            );
+        }
+
+        let batch = operation_batch(&self.function, dry_run, results)?;
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            batch.schema(),
+            stream::once(async move { Ok(batch) }),
+        )))
+    }
+}
+
+struct OperationRow {
+    id: String,
+    result: InvocationOperationResult,
+}
+
+fn operation_batch(
+    function: &str,
+    dry_run: bool,
+    rows: Vec<OperationRow>,
+) -> Result<RecordBatch, DataFusionError> {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Utf8, false),
+        Field::new("operation", DataType::Utf8, false),
+        Field::new("status", DataType::Utf8, false),
+        Field::new("new_invocation_id", DataType::Utf8, true),
+        Field::new("error", DataType::Utf8, true),
+    ]));
+
+    let status = |result: &InvocationOperationResult| match result {
+        Ok(_) if dry_run => "dry-run",
+        Ok(_) => "succeeded",
+        Err(_) => "failed",
+    };
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(StringArray::from_iter_values(
+            rows.iter().map(|row| row.id.as_str()),
+        )),
+        Arc::new(StringArray::from_iter_values(rows.iter().map(|_| function))),
+        Arc::new(StringArray::from_iter_values(
+            rows.iter().map(|row| status(&row.result)),
+        )),
+        Arc::new(StringArray::from_iter(rows.iter().map(|row| {
+            row.result
+                .as_ref()
+                .ok()
+                .and_then(|new_invocation_id| new_invocation_id.map(|id| id.to_string()))
+        }))),
+        Arc::new(StringArray::from_iter(
+            rows.iter().map(|row| row.result.as_ref().err().cloned()),
+        )),
+    ];
+
+    Ok(RecordBatch::try_new(schema, columns)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_invocation_operation() {
+        let query = InvocationOperationQuery::parse(
+            "SELECT restate_cancel(id) FROM sys_invocation WHERE status = 'backing-off' LIMIT 10",
+        )
+        .unwrap()
+        .unwrap();
+        assert!(matches!(query.operation, InvocationOperation::Cancel));
+        assert_eq!(
+            query.ids_query,
+            "SELECT id AS id FROM sys_invocation WHERE status = 'backing-off' LIMIT 10"
+        );
+    }
+
+    #[test]
+    fn parse_regular_query() {
+        assert!(
+            InvocationOperationQuery::parse("SELECT id, status FROM sys_invocation")
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            InvocationOperationQuery::parse("SELECT id FROM")
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn parse_invalid_usage() {
+        assert!(matches!(
+            InvocationOperationQuery::parse("SELECT id, restate_kill(id) FROM sys_invocation"),
+            Err(InvocationOperationQueryError::InvalidUsage(_))
+        ));
+        assert!(matches!(
+            InvocationOperationQuery::parse("SELECT restate_kill() FROM sys_invocation"),
+            Err(InvocationOperationQueryError::InvalidUsage(_))
+        ));
+    }
+}
@@ -46,9 +46,9 @@ impl IntoResponse for RulesApiError {
             RulesApiError::RuleBook(RuleBookError::PreconditionFailed { .. }) => {
                 StatusCode::CONFLICT
             }
-            RulesApiError::RuleBook(RuleBookError::CapExceeded { .. }) => {
-                StatusCode::UNPROCESSABLE_ENTITY
-            }
+            RulesApiError::RuleBook(
+                RuleBookError::CapExceeded { .. } | RuleBookError::BurstWithoutRate { .. },
+            ) => StatusCode::UNPROCESSABLE_ENTITY,
             RulesApiError::MetadataStore(_) => StatusCode::INTERNAL_SERVER_ERROR,
         };
         (
@@ -0,0 +1,224 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use super::error::*;
+use crate::generate_meta_api_error;
+use crate::state::AdminServiceState;
+
+use restate_admin_rest_model::schedules::*;
+
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use axum::{Json, http};
+use bytes::Bytes;
+use restate_errors::warn_it;
+use restate_types::config::Configuration;
+use restate_types::identifiers::ScheduleId;
+use restate_types::schema::registry::{self, MetadataService};
+
+generate_meta_api_error!(CreateScheduleError: [
+    SchedulesDisabledError,
+    MetaApiError,
+]);
+
+/// Create schedule
+///
+/// Creates a new schedule, invoking the given handler every time the cron expression fires.
+#[utoipa::path(
+    post,
+    path = "/schedules",
+    operation_id = "create_schedule",
+    tag = "schedule",
+    request_body = CreateScheduleRequest,
+    responses(
+        (status = 201, description = "Schedule created successfully", body = ScheduleResponse, headers(
+            ("Location" = String, description = "URI of the created schedule")
+        )),
+        CreateScheduleError
+    )
+)]
+pub async fn create_schedule<Metadata, Discovery, Telemetry, Invocations, Transport>(
+    State(state): State<AdminServiceState<Metadata, Discovery, Telemetry, Invocations, Transport>>,
+    Json(payload): Json<CreateScheduleRequest>,
+) -> Result<impl axum::response::IntoResponse, CreateScheduleError>
+where
+    Metadata: MetadataService,
+{
+    if !Configuration::pinned()
+        .common
+        .experimental
+        .is_schedules_enabled()
+    {
+        return Err(SchedulesDisabledError.into());
+    }
+
+    let schedule = state
+        .schema_registry
+        .create_schedule(registry::AddScheduleRequest {
+            cron: payload.cron,
+            timezone: payload.timezone,
+            service: payload.service,
+            handler: payload.handler,
+            key: payload.key,
+            payload: payload.payload.map(encode_payload).unwrap_or_default(),
+            overlap_policy: payload.overlap_policy,
+        })
+        .await
+        .inspect_err(|e| warn_it!(e))
+        .map_err(MetaApiError::from)?;
+
+    Ok((
+        StatusCode::CREATED,
+        [(
+            http::header::LOCATION,
+            format!("schedules/{}", schedule.id()),
+        )],
+        Json(ScheduleResponse::from(schedule)),
+    ))
+}
+
+/// Get schedule
+///
+/// Returns the details of a specific schedule, including its target and the time of its next tick.
+#[utoipa::path(
+    get,
+    path = "/schedules/{schedule}",
+    operation_id = "get_schedule",
+    tag = "schedule",
+    params(
+        ("schedule" = String, Path, description = "Schedule identifier"),
+    ),
+    responses(
+        (status = 200, description = "Schedule details", body = ScheduleResponse),
+        MetaApiError
+    )
+)]
+pub async fn get_schedule<Metadata, Discovery, Telemetry, Invocations, Transport>(
+    State(state): State<AdminServiceState<Metadata, Discovery, Telemetry, Invocations, Transport>>,
+    Path(schedule_id): Path<ScheduleId>,
+) -> Result<Json<ScheduleResponse>, MetaApiError>
+where
+    Metadata: MetadataService,
+{
+    let schedule = state
+        .schema_registry
+        .get_schedule(schedule_id)
+        .ok_or_else(|| MetaApiError::ScheduleNotFound(schedule_id))?;
+
+    Ok(ScheduleResponse::from(schedule).into())
+}
+
+/// List schedules
+///
+/// Returns a list of all registered schedules.
+#[utoipa::path(
+    get,
+    path = "/schedules",
+    operation_id = "list_schedules",
+    tag = "schedule",
+    responses(
+        (status = 200, description = "List of schedules", body = ListSchedulesResponse)
+    )
+)]
+pub async fn list_schedules<Metadata, Discovery, Telemetry, Invocations, Transport>(
+    State(state): State<AdminServiceState<Metadata, Discovery, Telemetry, Invocations, Transport>>,
+) -> Json<ListSchedulesResponse>
+where
+    Metadata: MetadataService,
+{
+    let mut schedules = state.schema_registry.list_schedules();
+    schedules.sort_by_key(|schedule| schedule.id());
+
+    ListSchedulesResponse {
+        schedules: schedules.into_iter().map(ScheduleResponse::from).collect(),
+    }
+    .into()
+}
+
+/// Modify schedule
+///
+/// Modifies the cron expression, timezone, payload or overlap policy of a schedule.
+/// The next tick is recomputed, ticks already scheduled with the previous configuration are discarded.
+#[utoipa::path(
+    patch,
+    path = "/schedules/{schedule}",
+    operation_id = "modify_schedule",
+    tag = "schedule",
+    params(
+        ("schedule" = String, Path, description = "Schedule identifier"),
+    ),
+    request_body = ModifyScheduleRequest,
+    responses(
+        (status = 200, description = "Schedule modified successfully", body = ScheduleResponse),
+        MetaApiError
+    )
+)]
+pub async fn modify_schedule<Metadata, Discovery, Telemetry, Invocations, Transport>(
+    State(state): State<AdminServiceState<Metadata, Discovery, Telemetry, Invocations, Transport>>,
+    Path(schedule_id): Path<ScheduleId>,
+    Json(payload): Json<ModifyScheduleRequest>,
+) -> Result<Json<ScheduleResponse>, MetaApiError>
+where
+    Metadata: MetadataService,
+{
+    let schedule = state
+        .schema_registry
+        .modify_schedule(
+            schedule_id,
+            registry::ModifyScheduleRequest {
+                cron: payload.cron,
+                timezone: payload.timezone,
+                payload: payload.payload.map(encode_payload),
+                overlap_policy: payload.overlap_policy,
+            },
+        )
+        .await
+        .inspect_err(|e| warn_it!(e))?;
+
+    Ok(ScheduleResponse::from(schedule).into())
+}
+
+/// Delete schedule
+///
+/// Deletes a schedule. Invocations started by previous ticks are not affected.
+#[utoipa::path(
+    delete,
+    path = "/schedules/{schedule}",
+    operation_id = "delete_schedule",
+    tag = "schedule",
+    params(
+        ("schedule" = String, Path, description = "Schedule identifier"),
+    ),
+    responses(
+        (status = 202, description = "Schedule deletion accepted and will be processed asynchronously"),
+        MetaApiError
+    )
+)]
+pub async fn delete_schedule<Metadata, Discovery, Telemetry, Invocations, Transport>(
+    State(state): State<AdminServiceState<Metadata, Discovery, Telemetry, Invocations, Transport>>,
+    Path(schedule_id): Path<ScheduleId>,
+) -> Result<StatusCode, MetaApiError>
+where
+    Metadata: MetadataService,
+{
+    state
+        .schema_registry
+        .delete_schedule(schedule_id)
+        .await
+        .inspect_err(|e| warn_it!(e))?;
+    Ok(StatusCode::ACCEPTED)
+}
+
+fn encode_payload(payload: serde_json::Value) -> Bytes {
+    if payload.is_null() {
+        return Bytes::new();
+    }
+    Bytes::from(serde_json::to_vec(&payload).expect("serializing a json value cannot fail"))
+}
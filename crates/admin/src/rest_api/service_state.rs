@@ -0,0 +1,789 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::num::NonZeroU32;
+use std::sync::Arc;
+use std::time::Duration;
+
+use axum::Json;
+use axum::body::Body;
+use axum::extract::{Path, Query, State};
+use axum::http;
+use axum::response::{IntoResponse, Response};
+use bytes::{Bytes, BytesMut};
+use datafusion::arrow::array::{Array, AsArray, BinaryArray, StringArray};
+use datafusion::arrow::compute::cast;
+use datafusion::arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::error::DataFusionError;
+use datafusion::execution::SendableRecordBatchStream;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use futures::{Stream, StreamExt, TryStreamExt, future, stream};
+use http_body::Frame;
+use http_body_util::StreamBody;
+use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+use serde::Deserialize;
+use tokio::sync::mpsc;
+use tokio::time::MissedTickBehavior;
+
+use restate_admin_rest_model::services::{
+    FailedServiceStateImport, ImportServiceStateResponse, ImportVersionCheck, ServiceStateEntry,
+    ServiceStateFormat,
+};
+use restate_core::Metadata as CoreMetadata;
+use restate_core::network::TransportConnect;
+use restate_ingestion_client::IngestionClient;
+use restate_types::identifiers::ServiceId;
+use restate_types::schema::registry::MetadataService;
+use restate_types::sharding::KeyRange;
+use restate_types::state_mut::{ExternalStateMutation, StateMutationVersion};
+use restate_types::{Scope, schema};
+use restate_wal_protocol::Envelope;
+
+use super::error::*;
+use super::query::NdjsonWriter;
+use super::services::patch_state;
+use crate::generate_meta_api_error;
+use crate::query_utils::{ParquetWriter, WriteRecordBatchStream};
+use crate::state::AdminServiceState;
+
+/// Maximum size of a line of an NDJSON import.
+const IMPORT_MAX_LINE_SIZE: usize = 64 * 1024 * 1024;
+
+/// Maximum size of a Parquet import, which is written to a temporary file before reading it.
+const IMPORT_MAX_PARQUET_SIZE: usize = 4 * 1024 * 1024 * 1024;
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct ExportServiceStateParams {
+    /// Format of the export, defaults to `ndjson`.
+    pub format: Option<ServiceStateFormat>,
+}
+
+generate_meta_api_error!(ExportServiceStateError: [
+    MetaApiError,
+    QueryUnavailableError,
+    QueryFailedError,
+]);
+
+/// Export service state
+///
+/// Streams all the state entries of a Virtual Object or Workflow, for every key.
+/// The entries of a key are contiguous, and carry the version of its whole state.
+/// The export can be written back with the import endpoint, also to another environment.
+#[utoipa::path(
+    get,
+    path = "/services/{service}/state/export",
+    operation_id = "export_service_state",
+    tag = "service",
+    params(
+        ("service" = String, Path, description = "Fully qualified service name."),
+        ExportServiceStateParams
+    ),
+    responses(
+        (status = 200, description = "The state entries of the service",
+            content (
+                ("application/x-ndjson" = ServiceStateEntry),
+                ("application/vnd.apache.parquet")
+            )),
+        ExportServiceStateError
+    )
+)]
+pub async fn export_service_state<Metadata, Discovery, Telemetry, Invocations, Transport>(
+    State(state): State<AdminServiceState<Metadata, Discovery, Telemetry, Invocations, Transport>>,
+    Path(service_name): Path<String>,
+    Query(ExportServiceStateParams { format }): Query<ExportServiceStateParams>,
+) -> Result<Response, ExportServiceStateError>
+where
+    Metadata: MetadataService,
+{
+    check_has_state(
+        state.schema_registry.get_service(&service_name),
+        &service_name,
+    )?;
+    let query_context = state.query_context.clone().ok_or(QueryUnavailableError)?;
+
+    let query = format!(
+        "SELECT scope, service_key, key, value FROM state WHERE service_name = '{}'",
+        service_name.replace('\'', "''")
+    );
+    // The state of each partition is queried on its own and ordered by key, so that the version
+    // of a key can be computed once all its entries are read, without sorting the whole state.
+    let partition_queries = partition_key_ranges().into_iter().map({
+        let query = query.clone();
+        move |key_range| {
+            format!(
+                "{query} AND partition_key BETWEEN {} AND {} ORDER BY scope, service_key",
+                key_range.start(),
+                key_range.end()
+            )
+        }
+    });
+    let record_batches: SendableRecordBatchStream = Box::pin(RecordBatchStreamAdapter::new(
+        export_schema(),
+        stream::iter(partition_queries)
+            .then(move |partition_query| {
+                let query_context = query_context.clone();
+                async move {
+                    let result = query_context.execute(&partition_query).await?;
+                    Ok::<_, DataFusionError>(versioned_entries(result.stream))
+                }
+            })
+            .try_flatten(),
+    ));
+
+    let (body, content_type) = match format.unwrap_or_default() {
+        ServiceStateFormat::Ndjson => (
+            WriteRecordBatchStream::<NdjsonWriter>::new(record_batches, query)
+                .map_err(QueryFailedError)?
+                .map_ok(Frame::data)
+                .left_stream(),
+            "application/x-ndjson",
+        ),
+        ServiceStateFormat::Parquet => (
+            WriteRecordBatchStream::<ParquetWriter>::new(record_batches, query)
+                .map_err(QueryFailedError)?
+                .map_ok(Frame::data)
+                .right_stream(),
+            "application/vnd.apache.parquet",
+        ),
+    };
+
+    Ok(Response::builder()
+        .header(http::header::CONTENT_TYPE, content_type)
+        .body(StreamBody::new(body))
+        .expect("content-type header is correct")
+        .into_response())
+}
+
+#[derive(Debug, Deserialize, utoipa::IntoParams)]
+pub struct ImportServiceStateParams {
+    /// Format of the request body, defaults to `ndjson`.
+    pub format: Option<ServiceStateFormat>,
+    /// Condition the state of a key must meet to be replaced, defaults to `empty`.
+    pub version_check: Option<ImportVersionCheck>,
+    /// Maximum number of keys imported per second.
+    pub keys_per_second: Option<u32>,
+}
+
+/// Import service state
+///
+/// Writes back state entries in the format of the export endpoint, whose entries must be
+/// contiguous per key. The body is streamed, and the state of every key is submitted as soon as
+/// all its entries are read: it replaces the whole state of the key, if the key meets the
+/// `version_check`. The check is applied when the partition processes the mutation, keys failing
+/// it are left untouched.
+///
+/// The response reports the keys whose mutation couldn't be submitted. If the import stopped
+/// early, for example because of an invalid entry, the keys read before were still imported.
+#[utoipa::path(
+    post,
+    path = "/services/{service}/state/import",
+    operation_id = "import_service_state",
+    tag = "service",
+    params(
+        ("service" = String, Path, description = "Fully qualified service name."),
+        ImportServiceStateParams
+    ),
+    request_body(
+        content = Vec<u8>,
+        description = "The state entries, in the given format",
+        content_type = "application/octet-stream"
+    ),
+    responses(
+        (status = 200, description = "Outcome of the import", body = ImportServiceStateResponse),
+        MetaApiError
+    )
+)]
+pub async fn import_service_state<Metadata, Discovery, Telemetry, Invocations, Transport>(
+    State(mut state): State<
+        AdminServiceState<Metadata, Discovery, Telemetry, Invocations, Transport>,
+    >,
+    Path(service_name): Path<String>,
+    Query(ImportServiceStateParams {
+        format,
+        version_check,
+        keys_per_second,
+    }): Query<ImportServiceStateParams>,
+    body: Body,
+) -> Result<Json<ImportServiceStateResponse>, MetaApiError>
+where
+    Metadata: MetadataService,
+    Transport: TransportConnect,
+{
+    check_has_state(
+        state.schema_registry.get_service(&service_name),
+        &service_name,
+    )?;
+    let keys_per_second = keys_per_second
+        .map(|keys_per_second| {
+            NonZeroU32::new(keys_per_second).ok_or_else(|| {
+                MetaApiError::InvalidField("keys_per_second", "must be greater than zero".into())
+            })
+        })
+        .transpose()?;
+    let version_check = version_check.unwrap_or_default();
+
+    let mut entries = match format.unwrap_or_default() {
+        ServiceStateFormat::Ndjson => ndjson_entries(body).boxed(),
+        ServiceStateFormat::Parquet => parquet_entries(body).boxed(),
+    };
+
+    let mut interval = keys_per_second.map(|keys_per_second| {
+        let mut interval = tokio::time::interval(Duration::from_secs(1) / keys_per_second.get());
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        interval
+    });
+    let mut response = ImportServiceStateResponse::default();
+    let mut imported_objects = HashSet::new();
+    let mut object: Option<ImportedObject> = None;
+    loop {
+        let entry = match entries.next().await {
+            Some(Ok(entry)) => Some(entry),
+            Some(Err(err)) => {
+                // The entries read so far might not be the whole state of the pending key
+                response.error = Some(err);
+                break;
+            }
+            None => None,
+        };
+
+        // All the entries of the pending key were read
+        if let Some(complete) =
+            object.take_if(|object| entry.as_ref().is_none_or(|entry| !object.contains(entry)))
+        {
+            if let Some(interval) = interval.as_mut() {
+                interval.tick().await;
+            }
+            if let Err(err) = import_object(
+                &mut state.ingestion_client,
+                &service_name,
+                version_check,
+                complete,
+                &mut response,
+            )
+            .await
+            {
+                response.error = Some(err);
+                break;
+            }
+        }
+
+        let Some(entry) = entry else {
+            break;
+        };
+        match object.as_mut() {
+            Some(object) => object.push(entry),
+            None => {
+                if !imported_objects.insert((entry.scope.clone(), entry.service_key.clone())) {
+                    response.error = Some(format!(
+                        "the entries of key '{}' are not contiguous, they must be grouped by key as in the export",
+                        entry.service_key
+                    ));
+                    break;
+                }
+                object = Some(ImportedObject::new(entry));
+            }
+        }
+    }
+
+    if response.objects == 0
+        && response.failed.is_empty()
+        && let Some(error) = response.error
+    {
+        return Err(MetaApiError::InvalidField("body", error));
+    }
+    Ok(Json(response))
+}
+
+/// The imported state of a Virtual Object or Workflow.
+struct ImportedObject {
+    scope: Option<String>,
+    service_key: String,
+    version: Option<String>,
+    state: HashMap<Bytes, Bytes>,
+}
+
+impl ImportedObject {
+    fn new(entry: ServiceStateEntry) -> Self {
+        let mut object = Self {
+            scope: entry.scope.clone(),
+            service_key: entry.service_key.clone(),
+            version: entry.version.clone(),
+            state: HashMap::new(),
+        };
+        object.push(entry);
+        object
+    }
+
+    fn contains(&self, entry: &ServiceStateEntry) -> bool {
+        self.scope == entry.scope && self.service_key == entry.service_key
+    }
+
+    fn push(&mut self, entry: ServiceStateEntry) {
+        self.state
+            .insert(Bytes::from(entry.key), Bytes::from(entry.value));
+    }
+}
+
+/// Submits the state mutation of the object, recording invalid objects as failed.
+/// Returns an error if the mutation couldn't be submitted, in which case the import stops.
+async fn import_object<Transport: TransportConnect>(
+    ingestion_client: &mut IngestionClient<Transport, Envelope>,
+    service_name: &str,
+    version_check: ImportVersionCheck,
+    object: ImportedObject,
+    response: &mut ImportServiceStateResponse,
+) -> Result<(), String> {
+    let ImportedObject {
+        scope,
+        service_key,
+        version,
+        state,
+    } = object;
+    let fail = |response: &mut ImportServiceStateResponse, error: String| {
+        response.failed.push(FailedServiceStateImport {
+            scope: scope.clone(),
+            service_key: service_key.clone(),
+            error,
+        });
+    };
+
+    let version = match version_check {
+        ImportVersionCheck::Empty => Some(StateMutationVersion::from_user_state(&[]).into_inner()),
+        ImportVersionCheck::Unchanged => match version {
+            Some(version) => Some(version),
+            None => {
+                fail(response, "the entries have no version".to_owned());
+                return Ok(());
+            }
+        },
+        ImportVersionCheck::Overwrite => None,
+    };
+    let service_scope = match scope.as_deref().map(Scope::try_non_interned).transpose() {
+        Ok(service_scope) => service_scope,
+        Err(err) => {
+            fail(response, MetaApiError::BadScope(err).to_string());
+            return Ok(());
+        }
+    };
+
+    let entries = state.len();
+    if let Err(err) = patch_state(
+        ingestion_client,
+        ExternalStateMutation {
+            service_id: ServiceId::new(service_scope, service_name.to_owned(), service_key.clone()),
+            version,
+            state,
+        },
+    )
+    .await
+    {
+        fail(response, err.to_string());
+        return Err(format!(
+            "the import stopped after failing to submit the state of key '{service_key}': {err}"
+        ));
+    }
+    response.objects += 1;
+    response.entries += entries;
+    Ok(())
+}
+
+fn check_has_state(
+    service: Option<schema::service::ServiceMetadata>,
+    service_name: &str,
+) -> Result<(), MetaApiError> {
+    match service {
+        Some(service) if !service.ty.has_state() => Err(MetaApiError::UnsupportedOperation(
+            "export or import state",
+            service.ty,
+        )),
+        Some(_) => Ok(()),
+        None => Err(MetaApiError::ServiceNotFound(service_name.to_owned())),
+    }
+}
+
+/// Reads the entries of an NDJSON body, line by line.
+fn ndjson_entries(body: Body) -> impl Stream<Item = Result<ServiceStateEntry, String>> + Send {
+    stream::unfold(
+        Some((body.into_data_stream(), NdjsonEntries::default())),
+        |state| async move {
+            let (mut body, mut entries) = state?;
+            match body.next().await {
+                Some(Ok(chunk)) => Some((entries.push(&chunk), Some((body, entries)))),
+                Some(Err(err)) => {
+                    Some((vec![Err(format!("failed to read the body: {err}"))], None))
+                }
+                None => Some((entries.finish().into_iter().collect(), None)),
+            }
+        },
+    )
+    .flat_map(stream::iter)
+}
+
+/// Splits NDJSON chunks into entries, keeping the last incomplete line until the next chunk.
+#[derive(Default)]
+struct NdjsonEntries {
+    buffer: BytesMut,
+    /// Length of the buffer prefix known not to contain a newline.
+    scanned: usize,
+    line: usize,
+}
+
+impl NdjsonEntries {
+    fn push(&mut self, chunk: &[u8]) -> Vec<Result<ServiceStateEntry, String>> {
+        self.buffer.extend_from_slice(chunk);
+        let mut entries = Vec::new();
+        while let Some(end) = self.buffer[self.scanned..]
+            .iter()
+            .position(|b| *b == b'\n')
+            .map(|position| self.scanned + position)
+        {
+            let line = self.buffer.split_to(end + 1);
+            self.scanned = 0;
+            entries.extend(self.parse_line(&line));
+        }
+        self.scanned = self.buffer.len();
+        if self.buffer.len() > IMPORT_MAX_LINE_SIZE {
+            entries.push(Err(format!(
+                "line {} exceeds the maximum size of {IMPORT_MAX_LINE_SIZE} bytes",
+                self.line + 1
+            )));
+        }
+        entries
+    }
+
+    fn finish(&mut self) -> Option<Result<ServiceStateEntry, String>> {
+        let line = self.buffer.split();
+        self.parse_line(&line)
+    }
+
+    fn parse_line(&mut self, line: &[u8]) -> Option<Result<ServiceStateEntry, String>> {
+        self.line += 1;
+        let line = line.trim_ascii();
+        if line.is_empty() {
+            return None;
+        }
+        Some(
+            serde_json::from_slice(line)
+                .map_err(|e| format!("invalid entry on line {}: {e}", self.line)),
+        )
+    }
+}
+
+/// Reads the entries of a Parquet body. The footer of a Parquet file is at its end, so the body
+/// is first written to a temporary file, which is then read one record batch at a time.
+fn parquet_entries(body: Body) -> impl Stream<Item = Result<ServiceStateEntry, String>> + Send {
+    let (chunks_tx, chunks_rx) = mpsc::channel(16);
+    let (batches_tx, batches_rx) = mpsc::channel(1);
+    tokio::task::spawn_blocking(move || {
+        if let Err(err) = read_parquet(chunks_rx, &batches_tx) {
+            let _ = batches_tx.blocking_send(Err(err));
+        }
+    });
+
+    let upload = async move {
+        let mut body = body.into_data_stream();
+        let mut size = 0;
+        while let Some(chunk) = body.next().await {
+            let chunk = chunk.map_err(|err| format!("failed to read the body: {err}"))?;
+            size += chunk.len();
+            if size > IMPORT_MAX_PARQUET_SIZE {
+                return Err(format!(
+                    "the Parquet file exceeds the maximum size of {IMPORT_MAX_PARQUET_SIZE} bytes"
+                ));
+            }
+            if chunks_tx.send(chunk).await.is_err() {
+                // the reader failed, and reports why
+                break;
+            }
+        }
+        Ok(())
+    };
+    let batches = stream::unfold(batches_rx, |mut batches_rx| async move {
+        batches_rx.recv().await.map(|batch| (batch, batches_rx))
+    });
+
+    stream::once(upload)
+        .filter_map(|uploaded| future::ready(uploaded.err().map(Err)))
+        .chain(batches.flat_map(|batch| {
+            stream::iter(match batch {
+                Ok(entries) => entries.into_iter().map(Ok).collect(),
+                Err(err) => vec![Err(err)],
+            })
+        }))
+}
+
+fn read_parquet(
+    mut chunks: mpsc::Receiver<Bytes>,
+    batches: &mpsc::Sender<Result<Vec<ServiceStateEntry>, String>>,
+) -> Result<(), String> {
+    let mut file =
+        tempfile::tempfile().map_err(|e| format!("failed to create a temporary file: {e}"))?;
+    while let Some(chunk) = chunks.blocking_recv() {
+        file.write_all(&chunk)
+            .map_err(|e| format!("failed to write a temporary file: {e}"))?;
+    }
+
+    let reader = ParquetRecordBatchReaderBuilder::try_new(file)
+        .and_then(|builder| builder.build())
+        .map_err(|e| format!("invalid Parquet file: {e}"))?;
+    for batch in reader {
+        let batch = batch.map_err(|e| format!("invalid Parquet file: {e}"))?;
+        let mut entries = Vec::with_capacity(batch.num_rows());
+        append_entries(&batch, &mut entries)?;
+        if batches.blocking_send(Ok(entries)).is_err() {
+            // the import stopped
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Key ranges of the partitions, each of which holds the whole state of its keys.
+fn partition_key_ranges() -> Vec<KeyRange> {
+    CoreMetadata::with_current(|m| {
+        m.partition_table_ref()
+            .iter()
+            .map(|(_, partition)| partition.key_range)
+            .collect()
+    })
+}
+
+/// Adds the version of their key's state to the entries, which must be ordered by key. The
+/// entries of the last key of a record batch are held back until all of them are read.
+fn versioned_entries(record_batches: SendableRecordBatchStream) -> SendableRecordBatchStream {
+    let stream = stream::unfold(Some((record_batches, Vec::new())), |state| async move {
+        let (mut record_batches, mut pending) = state?;
+        loop {
+            match record_batches.next().await {
+                Some(Ok(batch)) => {
+                    if let Err(err) = append_entries(&batch, &mut pending) {
+                        return Some((Err(DataFusionError::Execution(err)), None));
+                    }
+                    let last_key_start = match pending.last() {
+                        Some(last) => pending
+                            .iter()
+                            .rposition(|entry| !same_key(entry, last))
+                            .map_or(0, |position| position + 1),
+                        None => 0,
+                    };
+                    let last_key = pending.split_off(last_key_start);
+                    let complete = std::mem::replace(&mut pending, last_key);
+                    if !complete.is_empty() {
+                        return Some((versioned_batch(complete), Some((record_batches, pending))));
+                    }
+                }
+                Some(Err(err)) => return Some((Err(err), None)),
+                None if pending.is_empty() => return None,
+                None => return Some((versioned_batch(pending), None)),
+            }
+        }
+    });
+    Box::pin(RecordBatchStreamAdapter::new(export_schema(), stream))
+}
+
+fn same_key(a: &ServiceStateEntry, b: &ServiceStateEntry) -> bool {
+    a.scope == b.scope && a.service_key == b.service_key
+}
+
+fn export_schema() -> SchemaRef {
+    Arc::new(Schema::new(vec![
+        Field::new("scope", DataType::Utf8, true),
+        Field::new("service_key", DataType::Utf8, false),
+        Field::new("key", DataType::Utf8, false),
+        Field::new("value", DataType::Binary, false),
+        Field::new("version", DataType::Utf8, false),
+    ]))
+}
+
+/// Builds the export record batch of entries containing the whole state of their keys.
+fn versioned_batch(mut entries: Vec<ServiceStateEntry>) -> Result<RecordBatch, DataFusionError> {
+    for key_entries in entries.chunk_by_mut(same_key) {
+        let state: Vec<_> = key_entries
+            .iter()
+            .map(|entry| {
+                (
+                    Bytes::copy_from_slice(entry.key.as_bytes()),
+                    Bytes::copy_from_slice(&entry.value),
+                )
+            })
+            .collect();
+        let version = StateMutationVersion::from_user_state(&state).into_inner();
+        for entry in key_entries {
+            entry.version = Some(version.clone());
+        }
+    }
+
+    RecordBatch::try_new(
+        export_schema(),
+        vec![
+            Arc::new(StringArray::from_iter(
+                entries.iter().map(|entry| entry.scope.as_deref()),
+            )),
+            Arc::new(StringArray::from_iter_values(
+                entries.iter().map(|entry| entry.service_key.as_str()),
+            )),
+            Arc::new(StringArray::from_iter_values(
+                entries.iter().map(|entry| entry.key.as_str()),
+            )),
+            Arc::new(BinaryArray::from_iter_values(
+                entries.iter().map(|entry| entry.value.as_slice()),
+            )),
+            Arc::new(StringArray::from_iter(
+                entries.iter().map(|entry| entry.version.as_deref()),
+            )),
+        ],
+    )
+    .map_err(DataFusionError::from)
+}
+
+fn append_entries(batch: &RecordBatch, entries: &mut Vec<ServiceStateEntry>) -> Result<(), String> {
+    let column = |name: &str, data_type: &DataType| {
+        let column = batch
+            .column_by_name(name)
+            .ok_or_else(|| format!("missing column '{name}'"))?;
+        cast(column, data_type).map_err(|e| format!("invalid column '{name}': {e}"))
+    };
+    let optional_column = |name: &str| {
+        batch
+            .column_by_name(name)
+            .map(|column| cast(column, &DataType::Utf8))
+            .transpose()
+            .map_err(|e| format!("invalid column '{name}': {e}"))
+    };
+    let scope = optional_column("scope")?;
+    let version = optional_column("version")?;
+    let service_key = column("service_key", &DataType::Utf8)?;
+    let key = column("key", &DataType::Utf8)?;
+    let value = column("value", &DataType::Binary)?;
+
+    let service_key = service_key.as_string::<i32>();
+    let key = key.as_string::<i32>();
+    let value = value.as_binary::<i32>();
+    for row in 0..batch.num_rows() {
+        if service_key.is_null(row) || key.is_null(row) || value.is_null(row) {
+            return Err(format!("unexpected null value in row {row}"));
+        }
+        entries.push(ServiceStateEntry {
+            scope: scope
+                .as_ref()
+                .map(|scope| scope.as_string::<i32>())
+                .filter(|scope| !scope.is_null(row))
+                .map(|scope| scope.value(row).to_owned()),
+            service_key: service_key.value(row).to_owned(),
+            key: key.value(row).to_owned(),
+            value: value.value(row).to_vec(),
+            version: version
+                .as_ref()
+                .map(|version| version.as_string::<i32>())
+                .filter(|version| !version.is_null(row))
+                .map(|version| version.value(row).to_owned()),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(service_key: &str, key: &str, value: &[u8]) -> ServiceStateEntry {
+        ServiceStateEntry {
+            scope: None,
+            service_key: service_key.to_owned(),
+            key: key.to_owned(),
+            value: value.to_vec(),
+            version: None,
+        }
+    }
+
+    #[test]
+    fn parse_ndjson_entries() {
+        let body = br#"{"service_key":"alice","key":"count","value":"3432"}
+
+{"scope":"tenant-1","service_key":"bob","key":"name","value":"22626f6222","version":"v1"}"#;
+
+        // Lines split across chunks are parsed once complete
+        let mut parser = NdjsonEntries::default();
+        let mut entries = Vec::new();
+        for chunk in body.chunks(7) {
+            entries.extend(parser.push(chunk));
+        }
+        entries.extend(parser.finish());
+
+        assert_eq!(
+            entries.into_iter().collect::<Result<Vec<_>, _>>().unwrap(),
+            vec![
+                entry("alice", "count", b"42"),
+                ServiceStateEntry {
+                    scope: Some("tenant-1".to_owned()),
+                    version: Some("v1".to_owned()),
+                    ..entry("bob", "name", b"\"bob\"")
+                },
+            ]
+        );
+
+        let mut parser = NdjsonEntries::default();
+        assert!(parser.push(b"{\"key\":\"count\"}\n")[0].is_err());
+    }
+
+    #[restate_core::test]
+    async fn export_versions_keys_spanning_batches() {
+        let batch = |entries: Vec<ServiceStateEntry>| {
+            let batch = versioned_batch(entries).unwrap();
+            // the export query has no version column
+            batch.project(&[0, 1, 2, 3])
+        };
+        let input = vec![
+            batch(vec![entry("alice", "a", b"1"), entry("bob", "a", b"2")]),
+            batch(vec![entry("bob", "b", b"3")]),
+        ];
+        let schema = input[0].as_ref().unwrap().schema();
+        let record_batches: SendableRecordBatchStream = Box::pin(RecordBatchStreamAdapter::new(
+            schema,
+            stream::iter(input.into_iter().map(|batch| batch.map_err(Into::into))),
+        ));
+
+        let mut entries = Vec::new();
+        let mut batches = versioned_entries(record_batches);
+        while let Some(batch) = batches.next().await {
+            append_entries(&batch.unwrap(), &mut entries).unwrap();
+        }
+
+        let version = |state: &[(&str, &[u8])]| {
+            let state: Vec<_> = state
+                .iter()
+                .map(|(key, value)| (Bytes::from(key.to_string()), Bytes::from(value.to_vec())))
+                .collect();
+            Some(StateMutationVersion::from_user_state(&state).into_inner())
+        };
+        let alice = version(&[("a", b"1")]);
+        let bob = version(&[("a", b"2"), ("b", b"3")]);
+        assert_eq!(
+            entries,
+            vec![
+                ServiceStateEntry {
+                    version: alice,
+                    ..entry("alice", "a", b"1")
+                },
+                ServiceStateEntry {
+                    version: bob.clone(),
+                    ..entry("bob", "a", b"2")
+                },
+                ServiceStateEntry {
+                    version: bob,
+                    ..entry("bob", "b", b"3")
+                },
+            ]
+        );
+    }
+}
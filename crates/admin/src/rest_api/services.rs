@@ -20,8 +20,10 @@ use restate_admin_rest_model::services::*;
 use restate_core::TaskCenter;
 use restate_core::network::TransportConnect;
 use restate_errors::warn_it;
+use restate_ingestion_client::IngestionClient;
 use restate_types::config::Configuration;
 use restate_types::identifiers::{ServiceId, WithPartitionKey};
+use restate_types::schema::invocation_target::OnMaxAttempts;
 use restate_types::schema::registry::MetadataService;
 use restate_types::schema::service::ServiceMetadata;
 use restate_types::state_mut::ExternalStateMutation;
@@ -125,7 +127,7 @@ where
 
 /// Modify service configuration
 ///
-/// Updates the configuration of a registered service, such as public visibility, retention policies, and timeout settings.
+/// Updates the configuration of a registered service, such as public visibility, retention policies, timeout settings, the behavior when the retry policy max attempts are reached, and the traffic split between deployments.
 /// Note: Service re-discovery will update these settings based on the service endpoint configuration.
 #[utoipa::path(
     patch,
@@ -150,6 +152,10 @@ pub async fn modify_service<Metadata, Discovery, Telemetry, Invocations, Transpo
         journal_retention,
         inactivity_timeout,
         abort_timeout,
+        retry_policy_on_max_attempts,
+        retry_policy_dead_letter,
+        traffic_split,
+        handlers,
     }): Json<ModifyServiceRequest>,
 ) -> Result<Json<ServiceMetadata>, MetaApiError>
 where
@@ -162,6 +168,27 @@ where
         workflow_completion_retention,
         inactivity_timeout,
         abort_timeout,
+        retry_policy_on_max_attempts,
+        retry_policy_dead_letter,
+        traffic_split: traffic_split.map(|traffic_split| {
+            schema::registry::ModifyTrafficSplitRequest {
+                stable_deployment_id: traffic_split.stable_deployment_id,
+                canary_weight: traffic_split.canary_weight,
+                rollback: traffic_split.rollback,
+            }
+        }),
+        handlers: handlers
+            .into_iter()
+            .map(|(name, handler)| {
+                (
+                    name,
+                    schema::registry::ModifyHandlerRequest {
+                        retry_policy_on_max_attempts: handler.retry_policy_on_max_attempts,
+                        retry_policy_dead_letter: handler.retry_policy_dead_letter,
+                    },
+                )
+            })
+            .collect(),
     };
 
     if modify_request.public.is_none()
@@ -170,11 +197,24 @@ where
         && modify_request.workflow_completion_retention.is_none()
         && modify_request.inactivity_timeout.is_none()
         && modify_request.abort_timeout.is_none()
+        && modify_request.retry_policy_on_max_attempts.is_none()
+        && modify_request.retry_policy_dead_letter.is_none()
+        && modify_request.traffic_split.is_none()
+        && modify_request.handlers.is_empty()
     {
         // No need to do anything
         return get_service(State(state), Path(service_name)).await;
     }
 
+    for on_max_attempts in modify_request.retry_policy_on_max_attempts.iter().chain(
+        modify_request
+            .handlers
+            .values()
+            .filter_map(|handler| handler.retry_policy_on_max_attempts.as_ref()),
+    ) {
+        check_cluster_version(on_max_attempts)?;
+    }
+
     let response = state
         .schema_registry
         .modify_service(service_name, modify_request)
@@ -184,6 +224,24 @@ where
     Ok(response.into())
 }
 
+/// Older nodes fail to decode a schema holding values they don't know.
+fn check_cluster_version(on_max_attempts: &OnMaxAttempts) -> Result<(), MetaApiError> {
+    let Some(min_version) = on_max_attempts.min_required_version() else {
+        return Ok(());
+    };
+    let nodes_config = restate_core::Metadata::with_current(|m| m.nodes_config_ref());
+    if nodes_config.all_nodes_at_least(min_version) {
+        Ok(())
+    } else {
+        Err(MetaApiError::InvalidField(
+            "retry_policy_on_max_attempts",
+            format!(
+                "{on_max_attempts:?} requires every node to run Restate {min_version} or newer"
+            ),
+        ))
+    }
+}
+
 /// Modify service state
 ///
 /// Modifies the K/V state of a Virtual Object. For a detailed description of this API and how to use it, see the [state documentation](https://docs.restate.dev/operate/invocation#modifying-service-state).
@@ -243,20 +301,31 @@ where
         .map(|(k, v)| (Bytes::from(k), v))
         .collect();
 
-    let partition_key = service_id.partition_key();
-    let patch_state = ExternalStateMutation {
-        service_id,
-        version,
-        state: new_state,
-    };
+    patch_state(
+        &mut state.ingestion_client,
+        ExternalStateMutation {
+            service_id,
+            version,
+            state: new_state,
+        },
+    )
+    .await?;
+
+    Ok(StatusCode::ACCEPTED)
+}
 
+/// Appends a [`Command::PatchState`] with the given mutation to the log of its partition.
+pub(super) async fn patch_state<Transport: TransportConnect>(
+    ingestion_client: &mut IngestionClient<Transport, Envelope>,
+    mutation: ExternalStateMutation,
+) -> Result<(), MetaApiError> {
+    let partition_key = mutation.service_id.partition_key();
     let envelope = Envelope::new(
         create_envelope_header(partition_key),
-        Command::PatchState(patch_state),
+        Command::PatchState(mutation),
     );
 
-    let result = state
-        .ingestion_client
+    let result = ingestion_client
         .ingest(partition_key, envelope)
         .await
         .map_err(|err| {
@@ -272,6 +341,6 @@ where
             "Failed sending state patching command to the cluster.".to_owned(),
         ))
     } else {
-        Ok(StatusCode::ACCEPTED)
+        Ok(())
     }
 }
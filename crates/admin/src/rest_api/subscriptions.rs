@@ -166,3 +166,104 @@ where
         .inspect_err(|e| warn_it!(e))?;
     Ok(StatusCode::ACCEPTED)
 }
+
+/// Pause subscription
+///
+/// Pauses the consumption of a Kafka subscription. The consumers stop and commit the offsets of
+/// the records already ingested, and restart from them when the subscription is resumed.
+#[utoipa::path(
+    patch,
+    path = "/subscriptions/{subscription}/pause",
+    operation_id = "pause_subscription",
+    tag = "subscription",
+    params(
+        ("subscription" = String, Path, description = "Subscription identifier"),
+    ),
+    responses(
+        (status = 200, description = "Subscription paused", body = SubscriptionResponse),
+        MetaApiError
+    )
+)]
+pub async fn pause_subscription<Metadata, Discovery, Telemetry, Invocations, Transport>(
+    State(state): State<AdminServiceState<Metadata, Discovery, Telemetry, Invocations, Transport>>,
+    Path(subscription_id): Path<SubscriptionId>,
+) -> Result<Json<SubscriptionResponse>, MetaApiError>
+where
+    Metadata: MetadataService,
+{
+    let subscription = state
+        .schema_registry
+        .set_subscription_paused(subscription_id, true)
+        .await
+        .inspect_err(|e| warn_it!(e))?;
+
+    Ok(SubscriptionResponse::from(subscription).into())
+}
+
+/// Resume subscription
+///
+/// Resumes the consumption of a paused Kafka subscription.
+#[utoipa::path(
+    patch,
+    path = "/subscriptions/{subscription}/resume",
+    operation_id = "resume_subscription",
+    tag = "subscription",
+    params(
+        ("subscription" = String, Path, description = "Subscription identifier"),
+    ),
+    responses(
+        (status = 200, description = "Subscription resumed", body = SubscriptionResponse),
+        MetaApiError
+    )
+)]
+pub async fn resume_subscription<Metadata, Discovery, Telemetry, Invocations, Transport>(
+    State(state): State<AdminServiceState<Metadata, Discovery, Telemetry, Invocations, Transport>>,
+    Path(subscription_id): Path<SubscriptionId>,
+) -> Result<Json<SubscriptionResponse>, MetaApiError>
+where
+    Metadata: MetadataService,
+{
+    let subscription = state
+        .schema_registry
+        .set_subscription_paused(subscription_id, false)
+        .await
+        .inspect_err(|e| warn_it!(e))?;
+
+    Ok(SubscriptionResponse::from(subscription).into())
+}
+
+/// Seek subscription
+///
+/// Moves the consumers of a Kafka subscription to an offset, a timestamp, or the beginning or end
+/// of the topic. Records consumed again after moving back are ingested again, creating new
+/// invocations. If the subscription is paused, the seek is applied when it is resumed.
+#[utoipa::path(
+    patch,
+    path = "/subscriptions/{subscription}/seek",
+    operation_id = "seek_subscription",
+    tag = "subscription",
+    params(
+        ("subscription" = String, Path, description = "Subscription identifier"),
+    ),
+    request_body = SeekSubscriptionRequest,
+    responses(
+        (status = 200, description = "Seek accepted and applied asynchronously by the consumers", body = SubscriptionResponse),
+        MetaApiError
+    )
+)]
+pub async fn seek_subscription<Metadata, Discovery, Telemetry, Invocations, Transport>(
+    State(state): State<AdminServiceState<Metadata, Discovery, Telemetry, Invocations, Transport>>,
+    Path(subscription_id): Path<SubscriptionId>,
+    Json(payload): Json<SeekSubscriptionRequest>,
+) -> Result<Json<SubscriptionResponse>, MetaApiError>
+where
+    Metadata: MetadataService,
+{
+    let subscription = state
+        .schema_registry
+        .seek_subscription(subscription_id, payload.position.into(), payload.partition)
+        .await
+        .inspect_err(|e| warn_it!(e))?;
+
+    Ok(SubscriptionResponse::from(subscription).into())
+}
@@ -0,0 +1,124 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use axum::Json;
+use axum::extract::{Path, State};
+use axum::http::StatusCode;
+use serde::de::IgnoredAny;
+
+use restate_admin_rest_model::time::{AdvanceTimeRequest, AdvanceTimeResponse};
+use restate_types::config::Configuration;
+use restate_types::identifiers::InvocationId;
+use restate_types::time::MillisSinceEpoch;
+use restate_types::timer::TimeControl;
+
+use super::error::*;
+use crate::generate_meta_api_error;
+use crate::query_utils::query_rows;
+use crate::state::AdminServiceState;
+
+generate_meta_api_error!(AdvanceTimeError: [
+    ControllableTimeDisabledError,
+    InvalidFieldError,
+]);
+
+generate_meta_api_error!(FireTimersError: [
+    ControllableTimeDisabledError,
+    InvalidFieldError,
+    InvocationNotFoundError,
+    QueryUnavailableError,
+    QueryFailedError,
+]);
+
+/// Advance the time of the timers
+///
+/// Fires the timers of the partition processors that are due until the given point in time. The
+/// time observed by the timers doesn't change: timers registered afterwards, like the next sleep
+/// of a resumed invocation, follow the wall clock again. Requires the `controllable_time`
+/// experimental feature.
+#[utoipa::path(
+    post,
+    path = "/time/advance",
+    operation_id = "advance_time",
+    tag = "time",
+    request_body = AdvanceTimeRequest,
+    responses(
+        (status = 202, description = "Firing the due timers", body = AdvanceTimeResponse),
+        AdvanceTimeError,
+    )
+)]
+pub async fn advance_time(
+    Json(request): Json<AdvanceTimeRequest>,
+) -> Result<(StatusCode, Json<AdvanceTimeResponse>), AdvanceTimeError> {
+    let time_control = time_control()?;
+    let until = match (request.by, request.to) {
+        (Some(by), None) => MillisSinceEpoch::now() + by,
+        (None, Some(to)) => to,
+        _ => {
+            return Err(
+                InvalidFieldError("by", "exactly one of 'by' and 'to' must be set".into()).into(),
+            );
+        }
+    };
+    time_control.fire_until(until);
+    Ok((StatusCode::ACCEPTED, Json(AdvanceTimeResponse { until })))
+}
+
+/// Fire the timers of an invocation
+///
+/// Fires the pending sleeps of the invocation, or its start if it's scheduled for later. Timers of
+/// other invocations aren't affected. Requires the `controllable_time` experimental feature.
+#[utoipa::path(
+    post,
+    path = "/invocations/{invocation_id}/fire-timers",
+    operation_id = "fire_invocation_timers",
+    tag = "time",
+    params(
+        ("invocation_id" = String, Path, description = "Invocation identifier."),
+    ),
+    responses(
+        (status = 202, description = "Firing the timers of the invocation"),
+        FireTimersError,
+    )
+)]
+pub async fn fire_invocation_timers<Metadata, Discovery, Telemetry, Invocations, Transport>(
+    State(state): State<AdminServiceState<Metadata, Discovery, Telemetry, Invocations, Transport>>,
+    Path(invocation_id): Path<String>,
+) -> Result<StatusCode, FireTimersError> {
+    let time_control = time_control()?;
+    let invocation_id = invocation_id
+        .parse::<InvocationId>()
+        .map_err(|e| InvalidFieldError("invocation_id", e.to_string()))?;
+    let query_context = state.query_context.as_ref().ok_or(QueryUnavailableError)?;
+
+    let invocations: Vec<IgnoredAny> = query_rows(
+        query_context,
+        &format!("SELECT id FROM sys_invocation WHERE id = '{invocation_id}'"),
+    )
+    .await?;
+    if invocations.is_empty() {
+        return Err(InvocationNotFoundError(invocation_id.to_string()).into());
+    }
+
+    time_control.fire_invocation(invocation_id.invocation_uuid());
+    Ok(StatusCode::ACCEPTED)
+}
+
+fn time_control() -> Result<&'static TimeControl, ControllableTimeDisabledError> {
+    if Configuration::pinned()
+        .common
+        .experimental
+        .is_controllable_time_enabled()
+    {
+        Ok(TimeControl::global())
+    } else {
+        Err(ControllableTimeDisabledError)
+    }
+}
@@ -22,6 +22,7 @@ use tower_http::trace::TraceLayer;
 use tracing::{Span, debug, info, info_span};
 
 use restate_admin_rest_model::version::AdminApiVersion;
+use restate_bifrost::Bifrost;
 use restate_core::network::{TransportConnect, net_util};
 use restate_core::{MetadataWriter, TaskCenter};
 use restate_limiter::rule_book::RuleBookObserver;
@@ -52,6 +53,7 @@ pub struct AdminService<Metadata, Discovery, Telemetry, Invocations, Transport>
     serdes_client: SerdesClient,
     invocation_client: Invocations,
     query_context: Option<restate_storage_query_datafusion::context::QueryContext>,
+    bifrost: Option<Bifrost>,
     metadata_client: MetadataStoreClient,
     rule_book_observer: Option<Arc<dyn RuleBookObserver>>,
 }
@@ -83,6 +85,7 @@ where
             serdes_client,
             invocation_client,
             query_context: None,
+            bifrost: None,
             metadata_client,
             rule_book_observer: None,
         }
@@ -98,6 +101,13 @@ where
         }
     }
 
+    pub fn with_bifrost(self, bifrost: Bifrost) -> Self {
+        Self {
+            bifrost: Some(bifrost),
+            ..self
+        }
+    }
+
     pub fn with_rule_book_observer(self, observer: Arc<dyn RuleBookObserver>) -> Self {
         Self {
             rule_book_observer: Some(observer),
@@ -118,6 +128,7 @@ where
             self.ingestion_client,
             self.metadata_client.clone(),
             self.query_context,
+            self.bifrost,
             self.rule_book_observer,
         );
 
@@ -223,9 +234,14 @@ where
             TaskCenter::with_current(|tc| opts.advertised_address(tc.address_book()))
         );
 
-        net_util::run_hyper_server(self.listeners, service, || ())
-            .await
-            .map_err(Into::into)
+        net_util::run_hyper_server(
+            self.listeners,
+            opts.admin_listener_options().tls(),
+            service,
+            || (),
+        )
+        .await
+        .map_err(Into::into)
     }
 }
 
@@ -8,6 +8,7 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use restate_bifrost::Bifrost;
 use restate_core::network::TransportConnect;
 use restate_ingestion_client::IngestionClient;
 use restate_limiter::rule_book::RuleBookObserver;
@@ -18,6 +19,8 @@ use restate_types::schema::registry::SchemaRegistry;
 use restate_wal_protocol::Envelope;
 use std::sync::Arc;
 
+use crate::rest_api::BulkOperationJobs;
+
 #[derive(Clone, derive_builder::Builder)]
 pub struct AdminServiceState<Metadata, Discovery, Telemetry, Invocations, Transport> {
     pub schema_registry: SchemaRegistry<Metadata, Discovery, Telemetry>,
@@ -29,7 +32,11 @@ pub struct AdminServiceState<Metadata, Discovery, Telemetry, Invocations, Transp
     pub metadata_store_client: MetadataStoreClient,
     // Some value if the query endpoint is activated
     pub query_context: Option<QueryContext>,
+    // Some value if the partition logs are readable, used to follow invocations
+    pub bifrost: Option<Bifrost>,
     pub rule_book_observer: Option<Arc<dyn RuleBookObserver>>,
+    #[builder(default)]
+    pub(crate) bulk_operations: BulkOperationJobs,
 }
 
 impl<Metadata, Discovery, Telemetry, Invocations, Transport>
@@ -44,6 +51,7 @@ where
         ingestion_client: IngestionClient<Transport, Envelope>,
         metadata_store_client: MetadataStoreClient,
         query_context: Option<QueryContext>,
+        bifrost: Option<Bifrost>,
         rule_book_observer: Option<Arc<dyn RuleBookObserver>>,
     ) -> Self {
         Self {
@@ -53,7 +61,9 @@ where
             ingestion_client,
             metadata_store_client,
             query_context,
+            bifrost,
             rule_book_observer,
+            bulk_operations: BulkOperationJobs::default(),
         }
     }
 }
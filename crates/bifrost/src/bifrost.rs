@@ -26,7 +26,7 @@ use restate_core::MetadataWriter;
 use restate_core::my_node_id;
 use restate_core::{Metadata, ShutdownError};
 use restate_types::config::Configuration;
-use restate_types::logs::metadata::SealMetadata;
+use restate_types::logs::metadata::{InternalKind, SealMetadata};
 use restate_types::logs::metadata::{LogletParams, Logs, SegmentIndex};
 use restate_types::logs::metadata::{MaybeSegment, ProviderKind, Segment};
 use restate_types::logs::{KeyFilter, LogId, Lsn, SequenceNumber, TailState};
@@ -37,6 +37,7 @@ use crate::background_appender::BackgroundAppender;
 use crate::log_chain_writer::LogChainCommand;
 use crate::loglet::{FindTailOptions, LogletProvider, OperationError};
 use crate::loglet_wrapper::LogletWrapper;
+use crate::providers::object_store_loglet::ObjectStoreTier;
 use crate::sealed_loglet::SealedLoglet;
 use crate::watchdog::{WatchdogCommand, WatchdogSender};
 use crate::{BifrostAdmin, Error, InputRecord, LogReadStream, Result};
@@ -292,6 +293,8 @@ pub struct BifrostInner {
     watchdog: WatchdogSender,
     // Initialized after BifrostService::start completes.
     pub(crate) providers: OnceLock<EnumMap<ProviderKind, Option<Arc<dyn LogletProvider>>>>,
+    // Initialized after BifrostService::start completes.
+    pub(crate) object_store_tier: OnceLock<Option<Arc<ObjectStoreTier>>>,
     shutting_down: AtomicBool,
     pub(crate) read_stream_registry: crate::read_stream_registry::ActiveReadStreamRegistry,
 }
@@ -301,6 +304,7 @@ impl BifrostInner {
         Self {
             watchdog,
             providers: Default::default(),
+            object_store_tier: Default::default(),
             shutting_down: AtomicBool::new(false),
             read_stream_registry: Default::default(),
         }
@@ -569,6 +573,48 @@ impl BifrostInner {
         response_rx.await.map_err(|_| ShutdownError)?
     }
 
+    /// Turns the permanent seal of the given log chain into a regular seal, allowing the chain
+    /// to be extended again. This is a no-op if the chain is not permanently sealed.
+    pub async fn lift_permanent_seal(&self, log_id: LogId) -> std::result::Result<(), Error> {
+        let (response_rx, cmd) = LogChainCommand::lift_permanent_seal(log_id);
+        let _ = self.watchdog.send(WatchdogCommand::ChainCommand(cmd));
+
+        response_rx.await.map_err(|_| ShutdownError)?
+    }
+
+    /// Copies the records of a sealed segment to the object-store tier, then replaces the
+    /// segment's loglet with the offloaded copy in the log chain. The segment must be backed by
+    /// a loglet provider.
+    pub(crate) async fn offload_segment(
+        &self,
+        log_id: LogId,
+        segment: Segment<'_>,
+    ) -> std::result::Result<(), Error> {
+        let tier = self
+            .object_store_tier()
+            .filter(|tier| tier.is_offloading())
+            .ok_or_else(|| Error::Disabled(InternalKind::ObjectStore.to_string()))?;
+        let source_kind =
+            ProviderKind::try_from(segment.config.kind).expect("offloading a provider segment");
+        let segment_index = segment.index();
+        let source_params = segment.config.params.clone();
+        let source = self.get_loglet(log_id, segment).await?;
+        let params = tier
+            .offload(
+                log_id,
+                segment_index,
+                source_kind,
+                source_params,
+                source.inner().clone(),
+            )
+            .await?;
+
+        let (response_rx, cmd) = LogChainCommand::archive_segment(log_id, segment_index, params);
+        let _ = self.watchdog.send(WatchdogCommand::ChainCommand(cmd));
+
+        response_rx.await.map_err(|_| ShutdownError)?
+    }
+
     // --- Helper functions --- //
     /// Get the provider for a given kind. A provider must be enabled and BifrostService **must**
     /// be started before calling this.
@@ -584,6 +630,11 @@ impl BifrostInner {
             .ok_or_else(|| Error::Disabled(kind.to_string()))
     }
 
+    /// The object-store tier, if it was enabled on the service.
+    pub(crate) fn object_store_tier(&self) -> Option<&Arc<ObjectStoreTier>> {
+        self.object_store_tier.get().and_then(Option::as_ref)
+    }
+
     /// Checks if the log_id exists and that the provider is not disabled (can be created).
     pub(crate) fn check_log_id(&self, log_id: LogId) -> Result<(), Error> {
         let logs = Metadata::with_current(|metadata| metadata.logs_ref());
@@ -639,6 +690,12 @@ impl BifrostInner {
     ) -> Result<LogletWrapper, Error> {
         let loglet = if segment.config.kind.is_seal_marker() {
             SealedLoglet::get()
+        } else if segment.config.kind == InternalKind::ObjectStore {
+            let tier = self
+                .object_store_tier()
+                .ok_or_else(|| Error::Disabled(InternalKind::ObjectStore.to_string()))?;
+            tier.get_loglet(log_id, segment.index(), &segment.config.params)
+                .await?
         } else {
             let provider = self.provider_for(
                 segment
@@ -266,6 +266,18 @@ impl<'a> BifrostAdmin<'a> {
         }
     }
 
+    /// Lifts the permanent seal of a log chain, leaving the chain sealed at the same tail.
+    ///
+    /// Use this to back out of a permanent seal (e.g. an aborted partition split). The chain can
+    /// then be extended again, for instance through [`Self::seal_and_extend_chain`].
+    #[instrument(level = "debug", skip(self))]
+    pub async fn lift_permanent_seal(&self, log_id: LogId) -> Result<()> {
+        self.inner.fail_if_shutting_down()?;
+        self.inner.lift_permanent_seal(log_id).await?;
+
+        Ok(())
+    }
+
     /// Adds a segment to the end of the chain
     ///
     /// The loglet must be sealed first. This operations assumes that the loglet with
@@ -13,10 +13,11 @@ use std::sync::Arc;
 
 use restate_core::{ShutdownError, SyncError};
 use restate_metadata_store::ReadWriteError;
+use restate_types::SemanticRestateVersion;
 use restate_types::clock;
 use restate_types::errors::MaybeRetryableError;
 use restate_types::logs::builder::BuilderError;
-use restate_types::logs::metadata::SegmentIndex;
+use restate_types::logs::metadata::{InternalKind, SegmentIndex};
 use restate_types::logs::{LogId, Lsn};
 
 use crate::loglet::OperationError;
@@ -94,6 +95,10 @@ pub enum AdminError {
         expected: SegmentIndex,
         found: SegmentIndex,
     },
+    #[error("segment {1} of log {0} does not exist or is not sealed")]
+    SegmentNotSealed(LogId, SegmentIndex),
+    #[error("segments of kind '{0}' require every node of the cluster to run at least v{1}")]
+    UnsupportedKind(InternalKind, &'static SemanticRestateVersion),
     #[error("loglet params could not be deserialized: {0}")]
     ParamsSerde(#[from] Arc<serde_json::Error>),
     #[error("logs HLC clock error: {0}")]
@@ -118,6 +123,9 @@ impl From<BuilderError> for AdminError {
             }
             BuilderError::ParamsSerde(error) => AdminError::ParamsSerde(Arc::new(error)),
             BuilderError::SegmentConflict(lsn) => AdminError::SegmentConflict(lsn),
+            BuilderError::SegmentNotSealed(log_id, index) => {
+                AdminError::SegmentNotSealed(log_id, index)
+            }
             BuilderError::HlcClock(err) => AdminError::LogsHlcClock(err),
         }
     }
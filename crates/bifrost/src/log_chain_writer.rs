@@ -17,12 +17,13 @@ use restate_core::{MetadataWriter, TaskCenter, TaskHandle, TaskKind, cancellatio
 use restate_metadata_store::ReadModifyWriteError;
 use restate_types::logs::builder::{BuilderError, LogsBuilder};
 use restate_types::logs::metadata::{
-    Chain, LogletParams, Logs, ProviderKind, SealMetadata, SegmentIndex,
+    Chain, InternalKind, LogletParams, Logs, ProviderKind, SealMetadata, SegmentIndex,
 };
 use restate_types::logs::{LogId, Lsn, SequenceNumber};
 
 use crate::Error;
 use crate::error::AdminError;
+use crate::providers::object_store_loglet;
 
 const MAX_BATCH_SIZE: usize = 1024;
 
@@ -123,6 +124,40 @@ impl LogChainCommand {
         (rx, cmd)
     }
 
+    pub fn lift_permanent_seal(log_id: LogId) -> (oneshot::Receiver<Result<(), Error>>, Self) {
+        let (tx, rx) = oneshot::channel();
+        let cmd = Self {
+            log_id,
+            op: ChainOp::LiftPermanentSeal {
+                response: OpOutput {
+                    tx,
+                    staged_result: None,
+                },
+            },
+        };
+        (rx, cmd)
+    }
+
+    pub fn archive_segment(
+        log_id: LogId,
+        segment_index: SegmentIndex,
+        params: LogletParams,
+    ) -> (oneshot::Receiver<Result<(), Error>>, Self) {
+        let (tx, rx) = oneshot::channel();
+        let cmd = Self {
+            log_id,
+            op: ChainOp::ArchiveSegment {
+                segment_index,
+                params,
+                response: OpOutput {
+                    tx,
+                    staged_result: None,
+                },
+            },
+        };
+        (rx, cmd)
+    }
+
     pub fn trim_prefix(log_id: LogId, trim_point: Lsn) -> Self {
         Self {
             log_id,
@@ -134,7 +169,9 @@ impl LogChainCommand {
         match self.op {
             ChainOp::Extend { response, .. } => response.fail(err),
             ChainOp::SealChain { response, .. } => response.fail(err),
+            ChainOp::LiftPermanentSeal { response } => response.fail(err),
             ChainOp::AddLog { response, .. } => response.fail(err),
+            ChainOp::ArchiveSegment { response, .. } => response.fail(err),
             ChainOp::TrimPrefix { .. } => { /* do nothing */ }
         }
     }
@@ -143,7 +180,9 @@ impl LogChainCommand {
         match self.op {
             ChainOp::Extend { response, .. } => response.complete(),
             ChainOp::SealChain { response, .. } => response.complete(),
+            ChainOp::LiftPermanentSeal { response } => response.complete(),
             ChainOp::AddLog { response, .. } => response.complete(),
+            ChainOp::ArchiveSegment { response, .. } => response.complete(),
             ChainOp::TrimPrefix { trim_point } => {
                 debug!(
                     "Log {} chain has been trimmed to trim-point {}",
@@ -172,6 +211,10 @@ enum ChainOp {
         #[debug(skip)]
         response: OpOutput<Lsn>,
     },
+    LiftPermanentSeal {
+        #[debug(skip)]
+        response: OpOutput<()>,
+    },
     AddLog {
         provider: ProviderKind,
         #[debug(skip)]
@@ -179,6 +222,13 @@ enum ChainOp {
         #[debug(skip)]
         response: OpOutput<()>,
     },
+    ArchiveSegment {
+        segment_index: SegmentIndex,
+        #[debug(skip)]
+        params: LogletParams,
+        #[debug(skip)]
+        response: OpOutput<()>,
+    },
     TrimPrefix {
         trim_point: Lsn,
     },
@@ -284,6 +334,24 @@ impl LogChainWriter {
                                     metadata,
                                 ));
                             }
+                            ChainOp::LiftPermanentSeal { ref mut response } => {
+                                response.stage_output(Self::lift_permanent_seal(
+                                    &mut builder,
+                                    cmd.log_id,
+                                ));
+                            }
+                            ChainOp::ArchiveSegment {
+                                segment_index,
+                                ref params,
+                                ref mut response,
+                            } => {
+                                response.stage_output(Self::archive_segment(
+                                    &mut builder,
+                                    cmd.log_id,
+                                    segment_index,
+                                    params,
+                                ));
+                            }
                             ChainOp::TrimPrefix { trim_point } => {
                                 // ignores the error if the log is unknown.
                                 let _ = Self::trim_prefix(&mut builder, cmd.log_id, trim_point);
@@ -376,6 +444,35 @@ impl LogChainWriter {
         Ok(lsn)
     }
 
+    fn lift_permanent_seal(builder: &mut LogsBuilder, log_id: LogId) -> Result<(), Error> {
+        let mut chain_builder = builder.chain(log_id).ok_or(Error::UnknownLogId(log_id))?;
+
+        // lifting the seal of a chain that is not permanently sealed is a no-op
+        let _ = chain_builder
+            .lift_permanent_seal()
+            .map_err(AdminError::from)?;
+
+        Ok(())
+    }
+
+    fn archive_segment(
+        builder: &mut LogsBuilder,
+        log_id: LogId,
+        segment_index: SegmentIndex,
+        params: &LogletParams,
+    ) -> Result<(), Error> {
+        if let Some(min_version) = object_store_loglet::missing_cluster_version() {
+            return Err(AdminError::UnsupportedKind(InternalKind::ObjectStore, min_version).into());
+        }
+        let mut chain_builder = builder.chain(log_id).ok_or(Error::UnknownLogId(log_id))?;
+
+        chain_builder
+            .archive_segment(segment_index, params.clone())
+            .map_err(AdminError::from)?;
+
+        Ok(())
+    }
+
     fn trim_prefix(builder: &mut LogsBuilder, log_id: LogId, trim_point: Lsn) -> Result<(), Error> {
         let mut chain_builder = builder.chain(log_id).ok_or(Error::UnknownLogId(log_id))?;
 
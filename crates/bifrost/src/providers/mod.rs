@@ -13,5 +13,6 @@ pub mod local_loglet;
 
 #[cfg(any(test, feature = "memory-loglet"))]
 pub mod memory_loglet;
+pub mod object_store_loglet;
 #[cfg(feature = "replicated-loglet")]
 pub mod replicated_loglet;
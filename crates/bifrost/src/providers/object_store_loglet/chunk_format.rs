@@ -0,0 +1,285 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::ops::Deref;
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+use restate_types::logs::{Keys, LogletOffset, Record};
+use restate_types::storage::{PolyBytes, StorageCodec};
+use restate_types::time::NanosSinceEpoch;
+
+const CHUNK_FORMAT_VERSION: u8 = 0x01;
+/// format version, first offset and number of records
+const CHUNK_HEADER_SIZE: usize = 1 + 4 + 4;
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum ChunkDecodeError {
+    #[error("unsupported chunk format version {0}")]
+    UnsupportedFormatVersion(u8),
+    #[error("unsupported key style {0}")]
+    UnsupportedKeyStyle(u8),
+    #[error("chunk is truncated")]
+    Truncated,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, derive_more::TryFrom)]
+#[try_from(repr)]
+#[repr(u8)]
+enum KeyStyle {
+    None = 0,
+    Single = 1,
+    Pair = 2,
+    RangeInclusive = 3,
+}
+
+/// A batch of consecutive records of an offloaded segment, stored as a single object.
+///
+/// Chunk layout, byte order is little-endian:
+///    [1 byte]        Format version (`CHUNK_FORMAT_VERSION`)
+///    [4 bytes]       Offset of the first record
+///    [4 bytes]       Number of records
+///    For every record:
+///    [4 bytes]       Length of the encoded record
+///    [1 byte]        KeyStyle (see `KeyStyle` enum)
+///      * [8 bytes]   First Key (if KeyStyle is != 0)
+///      * [8 bytes]   Second Key (if KeyStyle is > 1)
+///    [8 bytes]       `created_at` timestamp
+///    [remaining]     Serialized Payload
+#[derive(Debug)]
+pub(super) struct Chunk {
+    first_offset: LogletOffset,
+    records: Vec<Record>,
+}
+
+impl Chunk {
+    /// Returns the record at `offset` if this chunk contains it.
+    pub fn get(&self, offset: LogletOffset) -> Option<&Record> {
+        let index = offset.checked_sub(*self.first_offset)?;
+        self.records.get(usize::try_from(index).ok()?)
+    }
+
+    pub fn decode(mut buf: Bytes) -> Result<Self, ChunkDecodeError> {
+        ensure_remaining(&buf, CHUNK_HEADER_SIZE)?;
+        let format_version = buf.get_u8();
+        if format_version != CHUNK_FORMAT_VERSION {
+            return Err(ChunkDecodeError::UnsupportedFormatVersion(format_version));
+        }
+        let first_offset = LogletOffset::new(buf.get_u32_le());
+        let count = buf.get_u32_le() as usize;
+
+        let mut records = Vec::with_capacity(count);
+        for _ in 0..count {
+            ensure_remaining(&buf, 4)?;
+            let len = buf.get_u32_le() as usize;
+            ensure_remaining(&buf, len)?;
+            records.push(decode_record(buf.split_to(len))?);
+        }
+
+        Ok(Self {
+            first_offset,
+            records,
+        })
+    }
+}
+
+/// Accumulates consecutive records into an encoded chunk.
+pub(super) struct ChunkBuilder {
+    first_offset: LogletOffset,
+    count: u32,
+    buf: BytesMut,
+}
+
+impl ChunkBuilder {
+    pub fn new(first_offset: LogletOffset) -> Self {
+        let mut buf = BytesMut::new();
+        buf.put_u8(CHUNK_FORMAT_VERSION);
+        buf.put_u32_le(*first_offset);
+        // number of records, written on `finish()`
+        buf.put_u32_le(0);
+        Self {
+            first_offset,
+            count: 0,
+            buf,
+        }
+    }
+
+    pub fn first_offset(&self) -> LogletOffset {
+        self.first_offset
+    }
+
+    /// The offset of the next record to be pushed
+    pub fn next_offset(&self) -> LogletOffset {
+        self.first_offset + self.count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    pub fn size(&self) -> usize {
+        self.buf.len()
+    }
+
+    pub fn push(&mut self, record: &Record) {
+        let len_pos = self.buf.len();
+        // length of the record, patched after encoding
+        self.buf.put_u32_le(0);
+        let buf = &mut self.buf;
+        match record.keys() {
+            Keys::None => buf.put_u8(KeyStyle::None as u8),
+            Keys::Single(key) => {
+                buf.put_u8(KeyStyle::Single as u8);
+                buf.put_u64_le(*key);
+            }
+            Keys::Pair(key1, key2) => {
+                buf.put_u8(KeyStyle::Pair as u8);
+                buf.put_u64_le(*key1);
+                buf.put_u64_le(*key2);
+            }
+            Keys::RangeInclusive(range) => {
+                buf.put_u8(KeyStyle::RangeInclusive as u8);
+                buf.put_u64_le(*range.start());
+                buf.put_u64_le(*range.end());
+            }
+        }
+        buf.put_u64_le(record.created_at().as_u64());
+        match record.body() {
+            PolyBytes::Bytes(raw_bytes) => buf.put_slice(raw_bytes),
+            PolyBytes::Both(_, raw_bytes) => buf.put_slice(raw_bytes),
+            PolyBytes::Typed(encodeable) => {
+                StorageCodec::encode(encodeable.deref(), buf).expect("record serde is infallible")
+            }
+        }
+
+        let len = u32::try_from(self.buf.len() - len_pos - 4).expect("record fits in a chunk");
+        self.buf[len_pos..len_pos + 4].copy_from_slice(&len.to_le_bytes());
+        self.count += 1;
+    }
+
+    pub fn finish(mut self) -> Bytes {
+        self.buf[5..CHUNK_HEADER_SIZE].copy_from_slice(&self.count.to_le_bytes());
+        self.buf.freeze()
+    }
+}
+
+fn decode_record(mut buf: Bytes) -> Result<Record, ChunkDecodeError> {
+    ensure_remaining(&buf, 1)?;
+    let key_style = buf.get_u8();
+    let key_style = KeyStyle::try_from(key_style)
+        .map_err(|_| ChunkDecodeError::UnsupportedKeyStyle(key_style))?;
+    let keys = match key_style {
+        KeyStyle::None => Keys::None,
+        KeyStyle::Single => {
+            ensure_remaining(&buf, 8)?;
+            Keys::Single(buf.get_u64_le())
+        }
+        KeyStyle::Pair => {
+            ensure_remaining(&buf, 16)?;
+            Keys::Pair(buf.get_u64_le(), buf.get_u64_le())
+        }
+        KeyStyle::RangeInclusive => {
+            ensure_remaining(&buf, 16)?;
+            let start = buf.get_u64_le();
+            let end = buf.get_u64_le();
+            Keys::RangeInclusive(start..=end)
+        }
+    };
+    ensure_remaining(&buf, 8)?;
+    let created_at = NanosSinceEpoch::from(buf.get_u64_le());
+
+    Ok(Record::from_parts(created_at, keys, PolyBytes::Bytes(buf)))
+}
+
+fn ensure_remaining(buf: &Bytes, len: usize) -> Result<(), ChunkDecodeError> {
+    if buf.remaining() < len {
+        Err(ChunkDecodeError::Truncated)
+    } else {
+        Ok(())
+    }
+}
+
+/// The name of the object holding the trim point of a segment that was trimmed after it was
+/// offloaded.
+pub(super) const TRIM_POINT_OBJECT: &str = "trim-point";
+
+/// The name of the object holding the chunk that starts at `first_offset`. Offsets are
+/// zero-padded so that chunks sort in offset order.
+pub(super) fn chunk_name(first_offset: LogletOffset) -> String {
+    format!("{:010}", *first_offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn chunk_roundtrip() -> googletest::Result<()> {
+        let records = [
+            Record::from_parts(
+                NanosSinceEpoch::from(100),
+                Keys::None,
+                PolyBytes::Typed(Arc::new("first".to_owned())),
+            ),
+            Record::from_parts(
+                NanosSinceEpoch::from(200),
+                Keys::Single(14),
+                PolyBytes::Typed(Arc::new("second".to_owned())),
+            ),
+            Record::from_parts(
+                NanosSinceEpoch::from(300),
+                Keys::RangeInclusive(5..=10),
+                PolyBytes::Typed(Arc::new("third".to_owned())),
+            ),
+        ];
+
+        let mut builder = ChunkBuilder::new(LogletOffset::new(7));
+        for record in &records {
+            builder.push(record);
+        }
+        assert_that!(builder.next_offset(), eq(LogletOffset::new(10)));
+
+        let chunk = Chunk::decode(builder.finish())?;
+        assert_that!(chunk.get(LogletOffset::new(6)), none());
+        assert_that!(chunk.get(LogletOffset::new(10)), none());
+
+        for (offset, expected) in (7..10).zip(&records) {
+            let decoded = chunk.get(LogletOffset::new(offset)).unwrap();
+            assert_that!(decoded.keys(), eq(expected.keys()));
+            assert_that!(decoded.created_at(), eq(expected.created_at()));
+            assert_that!(
+                decoded.clone().decode::<String>()?,
+                eq(expected.clone().decode::<String>()?)
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn truncated_chunk() {
+        let mut builder = ChunkBuilder::new(LogletOffset::OLDEST);
+        builder.push(&Record::from_parts(
+            NanosSinceEpoch::from(100),
+            Keys::Single(1),
+            PolyBytes::Typed(Arc::new("hello".to_owned())),
+        ));
+        let encoded = builder.finish();
+
+        assert!(matches!(
+            Chunk::decode(encoded.slice(..encoded.len() - 1)),
+            Err(ChunkDecodeError::Truncated)
+        ));
+    }
+}
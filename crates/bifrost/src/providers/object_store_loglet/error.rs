@@ -0,0 +1,77 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::sync::Arc;
+
+use restate_core::ShutdownError;
+use restate_types::errors::MaybeRetryableError;
+use restate_types::logs::metadata::SegmentIndex;
+use restate_types::logs::{LogId, LogletOffset};
+
+use super::chunk_format::ChunkDecodeError;
+use crate::loglet::OperationError;
+
+#[derive(Debug, thiserror::Error)]
+pub(crate) enum ObjectStoreLogletError {
+    #[error("cannot parse loglet configuration for log_id={0} at segment_index={1}: {2}")]
+    LogletParamsParsingError(LogId, SegmentIndex, serde_json::Error),
+    #[error("object store error: {0}")]
+    ObjectStore(#[from] object_store::Error),
+    #[error("cannot decode offloaded records: {0}")]
+    ChunkDecode(#[from] ChunkDecodeError),
+    #[error("loglet of log_id={0} at segment_index={1} is not sealed")]
+    NotSealed(LogId, SegmentIndex),
+    #[error("loglet of log_id={0} at segment_index={1} has a gap at offset {2}")]
+    UnexpectedGap(LogId, SegmentIndex, LogletOffset),
+    #[error("loglet of log_id={0} at segment_index={1} has a corrupted trim point")]
+    CorruptedTrimPoint(LogId, SegmentIndex),
+    #[error("cannot create a client for the object store at '{0}': {1}")]
+    Client(String, String),
+    #[error(transparent)]
+    Loglet(#[from] OperationError),
+    #[error(transparent)]
+    Shutdown(#[from] ShutdownError),
+}
+
+impl MaybeRetryableError for ObjectStoreLogletError {
+    fn retryable(&self) -> bool {
+        match self {
+            Self::LogletParamsParsingError(..) => false,
+            Self::ObjectStore(_) => true,
+            Self::ChunkDecode(_) => false,
+            Self::NotSealed(..) => true,
+            Self::UnexpectedGap(..) => false,
+            Self::CorruptedTrimPoint(..) => false,
+            Self::Client(..) => true,
+            Self::Loglet(err) => err.retryable(),
+            Self::Shutdown(_) => false,
+        }
+    }
+}
+
+impl From<ObjectStoreLogletError> for OperationError {
+    fn from(value: ObjectStoreLogletError) -> Self {
+        match value {
+            ObjectStoreLogletError::Shutdown(e) => OperationError::Shutdown(e),
+            ObjectStoreLogletError::Loglet(e) => e,
+            e => OperationError::Other(Arc::new(e)),
+        }
+    }
+}
+
+impl From<ObjectStoreLogletError> for crate::Error {
+    fn from(value: ObjectStoreLogletError) -> Self {
+        match value {
+            ObjectStoreLogletError::Shutdown(e) => crate::Error::Shutdown(e),
+            ObjectStoreLogletError::Loglet(e) => e.into(),
+            e => crate::Error::Loglet(Arc::new(e)),
+        }
+    }
+}
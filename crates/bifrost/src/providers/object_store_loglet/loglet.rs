@@ -0,0 +1,307 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::borrow::Cow;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::task::{Poll, ready};
+
+use async_trait::async_trait;
+use futures::future::BoxFuture;
+use futures::stream::{self, BoxStream};
+use futures::{FutureExt, Stream, StreamExt};
+use object_store::path::Path as ObjectPath;
+use object_store::{ObjectStore, ObjectStoreExt, PutPayload};
+use tokio::sync::OnceCell;
+
+use restate_types::logs::metadata::SegmentIndex;
+use restate_types::logs::{
+    KeyFilter, LogId, LogletId, LogletOffset, MatchKeyQuery, Record, SequenceNumber, TailState,
+};
+
+use super::ObjectStoreLogletParams;
+use super::chunk_format::{Chunk, TRIM_POINT_OBJECT, chunk_name};
+use super::error::ObjectStoreLogletError;
+use crate::LogEntry;
+use crate::Result;
+use crate::loglet::{
+    FindTailOptions, Loglet, LogletCommit, LogletReadStream, OperationError,
+    SendableLogletReadStream,
+};
+
+/// A read-only loglet serving the records of a sealed segment from the object-store tier.
+#[derive(derive_more::Debug)]
+pub(crate) struct ObjectStoreLoglet {
+    log_id: LogId,
+    segment_index: SegmentIndex,
+    #[debug(skip)]
+    object_store: Arc<dyn ObjectStore>,
+    path: ObjectPath,
+    params: ObjectStoreLogletParams,
+    /// Trims of offloaded segments are persisted in the [`TRIM_POINT_OBJECT`] next to the
+    /// chunks. It's loaded once, before the first operation that depends on the trim point.
+    trim_point: AtomicU32,
+    trim_point_loaded: OnceCell<()>,
+}
+
+impl ObjectStoreLoglet {
+    pub fn new(
+        log_id: LogId,
+        segment_index: SegmentIndex,
+        object_store: Arc<dyn ObjectStore>,
+        params: ObjectStoreLogletParams,
+    ) -> Self {
+        Self {
+            log_id,
+            segment_index,
+            object_store,
+            path: ObjectPath::from(params.path.as_str()),
+            trim_point: AtomicU32::new(*params.trim_point),
+            trim_point_loaded: OnceCell::new(),
+            params,
+        }
+    }
+
+    fn trim_point(&self) -> LogletOffset {
+        LogletOffset::new(self.trim_point.load(Ordering::Relaxed))
+    }
+
+    /// Loads the persisted trim point of the segment, if the segment was trimmed after it was
+    /// offloaded.
+    async fn load_trim_point(&self) -> Result<LogletOffset, ObjectStoreLogletError> {
+        self.trim_point_loaded
+            .get_or_try_init(|| async {
+                let path = self.path.clone().join(TRIM_POINT_OBJECT);
+                let bytes = match self.object_store.get(&path).await {
+                    Ok(result) => result.bytes().await?,
+                    Err(object_store::Error::NotFound { .. }) => return Ok(()),
+                    Err(err) => return Err(err.into()),
+                };
+                let trim_point = std::str::from_utf8(&bytes)
+                    .ok()
+                    .and_then(|trim_point| trim_point.parse().ok())
+                    .ok_or(ObjectStoreLogletError::CorruptedTrimPoint(
+                        self.log_id,
+                        self.segment_index,
+                    ))?;
+                self.trim_point.fetch_max(trim_point, Ordering::Relaxed);
+                Ok(())
+            })
+            .await?;
+        Ok(self.trim_point())
+    }
+
+    /// Fetches the chunk that holds the record at `offset`
+    fn fetch_chunk(
+        &self,
+        offset: LogletOffset,
+    ) -> BoxFuture<'static, Result<Chunk, OperationError>> {
+        let first_offset = self
+            .params
+            .chunks
+            .partition_point(|first_offset| *first_offset <= offset)
+            .checked_sub(1)
+            .map(|index| self.params.chunks[index])
+            .expect("offsets after the trim point are covered by a chunk");
+        let path = self.path.clone().join(chunk_name(first_offset));
+        let object_store = Arc::clone(&self.object_store);
+
+        async move {
+            let bytes = object_store
+                .get(&path)
+                .await
+                .map_err(ObjectStoreLogletError::from)?
+                .bytes()
+                .await
+                .map_err(ObjectStoreLogletError::from)?;
+            Ok(Chunk::decode(bytes).map_err(ObjectStoreLogletError::from)?)
+        }
+        .boxed()
+    }
+}
+
+#[async_trait]
+impl Loglet for ObjectStoreLoglet {
+    fn id(&self) -> Option<LogletId> {
+        Some(LogletId::new(self.log_id, self.segment_index))
+    }
+
+    fn debug_str(&self) -> Cow<'static, str> {
+        Cow::from(format!("object-store/{}", self.path))
+    }
+
+    async fn create_read_stream(
+        self: Arc<Self>,
+        filter: KeyFilter,
+        from: LogletOffset,
+        to: Option<LogletOffset>,
+    ) -> Result<SendableLogletReadStream, OperationError> {
+        self.load_trim_point().await?;
+        Ok(Box::pin(ObjectStoreReadStream {
+            loglet: self,
+            filter,
+            read_pointer: from,
+            read_to: to,
+            chunk: None,
+            fetch: None,
+            terminated: false,
+        }))
+    }
+
+    fn watch_tail(&self) -> BoxStream<'static, TailState<LogletOffset>> {
+        let tail = self.params.tail;
+        Box::pin(
+            stream::once(async move { TailState::Sealed(tail) })
+                // The stream must continue to be pending. If the stream is terminated
+                // bifrost will consider the system to be shutting down.
+                .chain(stream::pending()),
+        )
+    }
+
+    async fn enqueue_batch(&self, _: Arc<[Record]>) -> Result<LogletCommit, OperationError> {
+        Ok(LogletCommit::sealed())
+    }
+
+    async fn find_tail(
+        &self,
+        _: FindTailOptions,
+    ) -> Result<TailState<LogletOffset>, OperationError> {
+        Ok(TailState::Sealed(self.params.tail))
+    }
+
+    async fn get_trim_point(&self) -> Result<Option<LogletOffset>, OperationError> {
+        let trim_point = self.load_trim_point().await?;
+        if trim_point == LogletOffset::INVALID {
+            Ok(None)
+        } else {
+            Ok(Some(trim_point))
+        }
+    }
+
+    async fn trim(&self, trim_point: LogletOffset) -> Result<(), OperationError> {
+        let trim_point = trim_point.min(self.params.tail.prev_unchecked());
+        let current_trim_point = self.load_trim_point().await?;
+        // The chunks are kept: readers on other nodes might not have observed the trim point
+        // yet, and must not find gaps that are not reported as trim gaps.
+        if trim_point > current_trim_point {
+            self.object_store
+                .put(
+                    &self.path.clone().join(TRIM_POINT_OBJECT),
+                    PutPayload::from(trim_point.to_string()),
+                )
+                .await
+                .map_err(ObjectStoreLogletError::from)?;
+            self.trim_point.fetch_max(*trim_point, Ordering::Relaxed);
+        }
+        Ok(())
+    }
+
+    async fn seal(&self) -> Result<(), OperationError> {
+        // offloaded segments are always sealed
+        Ok(())
+    }
+}
+
+struct ObjectStoreReadStream {
+    loglet: Arc<ObjectStoreLoglet>,
+    /// Chooses which records to read/return
+    filter: KeyFilter,
+    /// The next offset to read from
+    read_pointer: LogletOffset,
+    /// Last offset to read before terminating the stream. None means "tailing" reader.
+    read_to: Option<LogletOffset>,
+    /// The last fetched chunk
+    chunk: Option<Chunk>,
+    fetch: Option<BoxFuture<'static, Result<Chunk, OperationError>>>,
+    terminated: bool,
+}
+
+impl LogletReadStream for ObjectStoreReadStream {
+    /// Current read pointer. This points to the next offset to be read.
+    fn read_pointer(&self) -> LogletOffset {
+        self.read_pointer
+    }
+    /// Returns true if the stream is terminated.
+    fn is_terminated(&self) -> bool {
+        self.terminated
+    }
+}
+
+impl Stream for ObjectStoreReadStream {
+    type Item = Result<LogEntry<LogletOffset>, OperationError>;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        loop {
+            if self.terminated {
+                return Poll::Ready(None);
+            }
+
+            let next_offset = self.read_pointer;
+            // We have reached the limit we are allowed to read
+            if self.read_to.is_some_and(|read_to| next_offset > read_to) {
+                self.terminated = true;
+                return Poll::Ready(None);
+            }
+
+            // The segment is sealed, no records will ever be written at or after the tail.
+            if next_offset >= self.loglet.params.tail {
+                return Poll::Pending;
+            }
+
+            // Are we reading behind the loglet head? -> TrimGap
+            let trim_point = self.loglet.trim_point();
+            if next_offset <= trim_point {
+                let trim_gap = LogEntry::new_trim_gap(next_offset, trim_point);
+                self.read_pointer = trim_point.next();
+                return Poll::Ready(Some(Ok(trim_gap)));
+            }
+
+            if let Some(record) = self.chunk.as_ref().and_then(|chunk| chunk.get(next_offset)) {
+                let record = record.clone();
+                self.read_pointer = next_offset.next();
+                // If this is a filtered record, skip it.
+                if !record.matches_key_query(&self.filter) {
+                    continue;
+                }
+                return Poll::Ready(Some(Ok(LogEntry::new_data(next_offset, record))));
+            }
+
+            // Fetch the chunk holding the next record
+            if self.fetch.is_none() {
+                let fetch = self.loglet.fetch_chunk(next_offset);
+                self.fetch = Some(fetch);
+            }
+            let result = ready!(
+                self.fetch
+                    .as_mut()
+                    .expect("fetch is in flight")
+                    .poll_unpin(cx)
+            );
+            self.fetch = None;
+            match result {
+                Ok(chunk) if chunk.get(next_offset).is_none() => {
+                    self.terminated = true;
+                    let err = ObjectStoreLogletError::UnexpectedGap(
+                        self.loglet.log_id,
+                        self.loglet.segment_index,
+                        next_offset,
+                    );
+                    return Poll::Ready(Some(Err(err.into())));
+                }
+                Ok(chunk) => self.chunk = Some(chunk),
+                // The read pointer doesn't move on error, the next poll retries the fetch.
+                Err(err) => return Poll::Ready(Some(Err(err))),
+            }
+        }
+    }
+}
@@ -0,0 +1,70 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! The object-store tier of bifrost.
+//!
+//! Sealed segments that are no longer the tail of their log are offloaded by the bifrost
+//! watchdog: the records are copied into objects, then the segment is switched to the
+//! `object-store` kind in the log chain. Readers of the segment are served by a read-only
+//! [`ObjectStoreLoglet`](loglet::ObjectStoreLoglet), and the source loglet is trimmed after a
+//! grace period to reclaim its storage.
+
+mod chunk_format;
+mod error;
+mod loglet;
+mod tier;
+
+pub use tier::ObjectStoreTier;
+
+use serde::{Deserialize, Serialize};
+
+use restate_core::Metadata;
+use restate_types::SemanticRestateVersion;
+use restate_types::logs::LogletOffset;
+use restate_types::logs::metadata::{InternalKind, LogletParams, ProviderKind};
+use restate_types::time::MillisSinceEpoch;
+
+/// Older nodes fail to decode log chains holding offloaded segments. Returns the version every
+/// node must run before segments can be offloaded, if the cluster doesn't satisfy it yet.
+pub(crate) fn missing_cluster_version() -> Option<&'static SemanticRestateVersion> {
+    let min_version = InternalKind::ObjectStore.min_required_version()?;
+    let nodes_config = Metadata::with_current(|m| m.nodes_config_ref());
+    (!nodes_config.all_nodes_at_least(min_version)).then_some(min_version)
+}
+
+/// The params of an offloaded segment. These are stored as json in the log chain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct ObjectStoreLogletParams {
+    /// URL of the tier destination the segment was offloaded to. Every node reads the segment
+    /// from there, whether or not it has the same destination configured.
+    pub destination: String,
+    /// Path of the segment in the object store, chunk objects are stored under it
+    pub path: String,
+    /// Records up to and including this offset were trimmed before offloading
+    pub trim_point: LogletOffset,
+    /// The sealed tail of the segment
+    pub tail: LogletOffset,
+    /// The offsets of the first record of every chunk, in ascending order
+    pub chunks: Vec<LogletOffset>,
+    /// The loglet the records were copied from. It is trimmed once the grace period elapses.
+    pub source_kind: ProviderKind,
+    pub source_params: LogletParams,
+    pub archived_at: MillisSinceEpoch,
+}
+
+impl ObjectStoreLogletParams {
+    pub fn deserialize_from(slice: &[u8]) -> Result<Self, serde_json::Error> {
+        serde_json::from_slice(slice)
+    }
+
+    pub fn serialize(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+}
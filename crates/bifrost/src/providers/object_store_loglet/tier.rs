@@ -0,0 +1,472 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Context;
+use dashmap::DashMap;
+use futures::StreamExt;
+use object_store::path::Path as ObjectPath;
+use object_store::{ObjectStore, ObjectStoreExt, PutPayload};
+use tracing::{debug, info};
+use url::Url;
+
+use restate_object_store_util::create_object_store_client;
+use restate_types::config::{ObjectStoreOptions, ObjectStoreTierOptions};
+use restate_types::logs::metadata::{LogletParams, ProviderKind, Segment, SegmentIndex};
+use restate_types::logs::{KeyFilter, LogId, LogletOffset, SequenceNumber, TailState};
+use restate_types::retries::RetryPolicy;
+use restate_types::time::MillisSinceEpoch;
+
+use super::ObjectStoreLogletParams;
+use super::chunk_format::{ChunkBuilder, chunk_name};
+use super::error::ObjectStoreLogletError;
+use super::loglet::ObjectStoreLoglet;
+use crate::loglet::{FindTailOptions, Loglet};
+
+/// Stores the records of sealed segments in an object store, and serves the offloaded segments.
+///
+/// Every node reads offloaded segments from the destination recorded in their params, so that
+/// nodes without a destination, or with a different one, can read them too.
+pub struct ObjectStoreTier {
+    /// Destination URL sealed segments are offloaded to, if one is configured
+    destination: Option<String>,
+    prefix: ObjectPath,
+    /// Options of the clients of the destinations, including the ones of other nodes
+    object_store_options: ObjectStoreOptions,
+    object_store_retry_policy: RetryPolicy,
+    /// Clients by destination URL
+    object_stores: DashMap<String, Arc<dyn ObjectStore>>,
+    object_size: usize,
+    offload_delay: Duration,
+    offload_min_records: u64,
+    trim_grace_period: Duration,
+    loglets: DashMap<(LogId, SegmentIndex), Arc<ObjectStoreLoglet>>,
+}
+
+impl ObjectStoreTier {
+    /// Creates the tier. Sealed segments are only offloaded if a destination is configured.
+    pub async fn new_from_config(options: &ObjectStoreTierOptions) -> anyhow::Result<Self> {
+        let mut tier = ObjectStoreTier {
+            destination: None,
+            prefix: ObjectPath::default(),
+            object_store_options: options.object_store.clone(),
+            object_store_retry_policy: options.object_store_retry_policy.clone(),
+            object_stores: DashMap::default(),
+            object_size: options.object_size.as_usize(),
+            offload_delay: options.offload_delay.into(),
+            offload_min_records: options.offload_min_records,
+            trim_grace_period: options.trim_grace_period.into(),
+            loglets: DashMap::default(),
+        };
+        let Some(ref destination) = options.destination else {
+            return Ok(tier);
+        };
+
+        let mut destination =
+            Url::parse(destination).context("Failed parsing object-store tier URL")?;
+        // Prevent passing configuration options to object_store via the destination URL.
+        destination
+            .query()
+            .inspect(|params| info!("Object-store tier destination parameters ignored: {params}"));
+        destination.set_query(None);
+
+        tier.prefix = ObjectPath::from(destination.path());
+        let object_store = create_object_store_client(
+            destination.clone(),
+            &options.object_store,
+            &options.object_store_retry_policy,
+        )
+        .await?;
+        tier.object_stores
+            .insert(destination.to_string(), object_store);
+        tier.destination = Some(destination.to_string());
+        Ok(tier)
+    }
+
+    /// Whether sealed segments are offloaded by this node.
+    pub fn is_offloading(&self) -> bool {
+        self.destination.is_some()
+    }
+
+    /// How long source loglets are kept around after their segment was offloaded
+    pub fn trim_grace_period(&self) -> Duration {
+        self.trim_grace_period
+    }
+
+    /// Whether a segment that has been known to be sealed for `sealed_for` is old and large
+    /// enough to be offloaded.
+    pub(crate) fn should_offload(&self, segment: &Segment<'_>, sealed_for: Duration) -> bool {
+        let Some(tail_lsn) = segment.tail_lsn else {
+            return false;
+        };
+        let records = tail_lsn.as_u64().saturating_sub(segment.base_lsn.as_u64());
+        records >= self.offload_min_records && sealed_for >= self.offload_delay
+    }
+
+    pub(crate) async fn get_loglet(
+        &self,
+        log_id: LogId,
+        segment_index: SegmentIndex,
+        params: &LogletParams,
+    ) -> Result<Arc<dyn Loglet>, ObjectStoreLogletError> {
+        if let Some(loglet) = self.loglets.get(&(log_id, segment_index)) {
+            return Ok(Arc::clone(loglet.value()) as Arc<dyn Loglet>);
+        }
+
+        let params = ObjectStoreLogletParams::deserialize_from(params.as_bytes()).map_err(|e| {
+            ObjectStoreLogletError::LogletParamsParsingError(log_id, segment_index, e)
+        })?;
+        let object_store = self.object_store(&params.destination).await?;
+        let loglet = self
+            .loglets
+            .entry((log_id, segment_index))
+            .or_insert_with(|| {
+                Arc::new(ObjectStoreLoglet::new(
+                    log_id,
+                    segment_index,
+                    object_store,
+                    params,
+                ))
+            });
+        Ok(Arc::clone(loglet.value()) as Arc<dyn Loglet>)
+    }
+
+    /// Returns the client of the object store at `destination`, creating it with the object
+    /// store options of this node if needed.
+    async fn object_store(
+        &self,
+        destination: &str,
+    ) -> Result<Arc<dyn ObjectStore>, ObjectStoreLogletError> {
+        if let Some(object_store) = self.object_stores.get(destination) {
+            return Ok(Arc::clone(object_store.value()));
+        }
+
+        let url = Url::parse(destination)
+            .map_err(|e| ObjectStoreLogletError::Client(destination.to_owned(), e.to_string()))?;
+        let object_store = create_object_store_client(
+            url,
+            &self.object_store_options,
+            &self.object_store_retry_policy,
+        )
+        .await
+        .map_err(|e| ObjectStoreLogletError::Client(destination.to_owned(), format!("{e:#}")))?;
+        Ok(Arc::clone(
+            self.object_stores
+                .entry(destination.to_owned())
+                .or_insert(object_store)
+                .value(),
+        ))
+    }
+
+    /// Copies the records of a sealed loglet into the object store, and returns the params of
+    /// the offloaded segment.
+    pub(crate) async fn offload(
+        &self,
+        log_id: LogId,
+        segment_index: SegmentIndex,
+        source_kind: ProviderKind,
+        source_params: LogletParams,
+        source: Arc<dyn Loglet>,
+    ) -> Result<LogletParams, ObjectStoreLogletError> {
+        let destination = self
+            .destination
+            .clone()
+            .expect("segments are only offloaded by nodes with a destination");
+        let object_store = self.object_store(&destination).await?;
+        let TailState::Sealed(tail) = source.find_tail(FindTailOptions::ConsistentRead).await?
+        else {
+            return Err(ObjectStoreLogletError::NotSealed(log_id, segment_index));
+        };
+        let trim_point = source
+            .get_trim_point()
+            .await?
+            .unwrap_or(LogletOffset::INVALID);
+
+        let path = self
+            .prefix
+            .clone()
+            .join(log_id.to_string())
+            .join(segment_index.to_string());
+        let mut chunks = Vec::new();
+
+        if trim_point.next() < tail {
+            let mut records = source
+                .create_read_stream(KeyFilter::Any, trim_point.next(), Some(tail.prev()))
+                .await?;
+            let mut chunk = ChunkBuilder::new(trim_point.next());
+            while let Some(entry) = records.next().await {
+                let entry = entry?;
+                let offset = entry.sequence_number();
+                let Some(record) = entry
+                    .into_record()
+                    .filter(|_| offset == chunk.next_offset())
+                else {
+                    // Gaps can't be represented in the tier, we'll try again later if the
+                    // source was trimmed in the meantime.
+                    return Err(ObjectStoreLogletError::UnexpectedGap(
+                        log_id,
+                        segment_index,
+                        offset,
+                    ));
+                };
+                chunk.push(&record);
+
+                if chunk.size() >= self.object_size {
+                    let next_chunk = ChunkBuilder::new(chunk.next_offset());
+                    chunks.push(
+                        put_chunk(
+                            &object_store,
+                            &path,
+                            std::mem::replace(&mut chunk, next_chunk),
+                        )
+                        .await?,
+                    );
+                }
+            }
+            if !chunk.is_empty() {
+                chunks.push(put_chunk(&object_store, &path, chunk).await?);
+            }
+        }
+
+        debug!(
+            %log_id,
+            %segment_index,
+            %tail,
+            chunks = chunks.len(),
+            "Offloaded the records of a sealed segment to {path}"
+        );
+
+        let params = ObjectStoreLogletParams {
+            destination,
+            path: path.to_string(),
+            trim_point,
+            tail,
+            chunks,
+            source_kind,
+            source_params,
+            archived_at: MillisSinceEpoch::now(),
+        };
+        let params = params.serialize().map_err(|e| {
+            ObjectStoreLogletError::LogletParamsParsingError(log_id, segment_index, e)
+        })?;
+        Ok(LogletParams::from(params))
+    }
+}
+
+async fn put_chunk(
+    object_store: &Arc<dyn ObjectStore>,
+    path: &ObjectPath,
+    chunk: ChunkBuilder,
+) -> Result<LogletOffset, ObjectStoreLogletError> {
+    let first_offset = chunk.first_offset();
+    object_store
+        .put(
+            &path.clone().join(chunk_name(first_offset)),
+            PutPayload::from(chunk.finish()),
+        )
+        .await?;
+    Ok(first_offset)
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+    use object_store::memory::InMemory;
+
+    use restate_core::TestCoreEnvBuilder;
+    use restate_types::logs::metadata::{LogletConfig, SegmentIndex};
+    use restate_types::logs::{LogletId, Lsn};
+
+    use super::*;
+    use crate::loglet_wrapper::LogletWrapper;
+    use crate::providers::memory_loglet::MemoryLoglet;
+    use crate::providers::object_store_loglet::chunk_format::TRIM_POINT_OBJECT;
+
+    #[restate_core::test]
+    async fn offload_sealed_loglet() -> googletest::Result<()> {
+        let _node_env = TestCoreEnvBuilder::with_incoming_only_connector()
+            .set_provider_kind(ProviderKind::InMemory)
+            .build()
+            .await;
+        let log_id = LogId::new(1);
+        let segment_index = SegmentIndex::from(2);
+        let source = LogletWrapper::new(
+            segment_index,
+            Lsn::OLDEST,
+            None,
+            LogletConfig::for_testing(),
+            MemoryLoglet::new(LogletId::new(log_id, segment_index)),
+        );
+        for i in 1..=10 {
+            source.append(format!("record{i}").into()).await?;
+        }
+        source.trim(Lsn::new(3)).await?;
+        source.seal().await?;
+
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        // every chunk holds a couple of records
+        let tier = test_tier(Arc::clone(&object_store), Duration::ZERO, 0);
+        let params = tier
+            .offload(
+                log_id,
+                segment_index,
+                ProviderKind::InMemory,
+                LogletParams::from("source".to_owned()),
+                source.inner().clone(),
+            )
+            .await?;
+
+        let offloaded = ObjectStoreLogletParams::deserialize_from(params.as_bytes())?;
+        assert_that!(offloaded.trim_point, eq(LogletOffset::new(3)));
+        assert_that!(offloaded.tail, eq(LogletOffset::new(11)));
+        assert_that!(offloaded.chunks.len(), gt(1));
+        assert_that!(offloaded.destination, eq(TEST_DESTINATION));
+        assert_that!(offloaded.path, eq("logs/1/2"));
+
+        let loglet = tier.get_loglet(log_id, segment_index, &params).await?;
+        assert_that!(
+            loglet.find_tail(FindTailOptions::default()).await?,
+            eq(TailState::Sealed(LogletOffset::new(11)))
+        );
+        assert_that!(
+            loglet.get_trim_point().await?,
+            some(eq(LogletOffset::new(3)))
+        );
+
+        let mut read_stream = loglet
+            .clone()
+            .create_read_stream(
+                KeyFilter::Any,
+                LogletOffset::OLDEST,
+                Some(LogletOffset::new(10)),
+            )
+            .await?;
+        let entry = read_stream.next().await.unwrap()?;
+        assert!(entry.is_trim_gap());
+        assert_that!(
+            entry.trim_gap_to_sequence_number(),
+            some(eq(LogletOffset::new(3)))
+        );
+        for offset in 4..=10 {
+            let entry = read_stream.next().await.unwrap()?;
+            assert_that!(entry.sequence_number(), eq(LogletOffset::new(offset)));
+            assert_that!(
+                entry.decode_unchecked::<String>(),
+                eq(format!("record{offset}"))
+            );
+        }
+        assert!(read_stream.next().await.is_none());
+
+        // trimmed chunks are kept for readers that haven't observed the trim point yet
+        loglet.trim(LogletOffset::new(6)).await?;
+        let names = object_names(&object_store).await;
+        assert!(
+            offloaded
+                .chunks
+                .iter()
+                .all(|first_offset| names.contains(&chunk_name(*first_offset)))
+        );
+        let mut read_stream = loglet
+            .clone()
+            .create_read_stream(
+                KeyFilter::Any,
+                LogletOffset::OLDEST,
+                Some(LogletOffset::new(10)),
+            )
+            .await?;
+        let entry = read_stream.next().await.unwrap()?;
+        assert_that!(
+            entry.trim_gap_to_sequence_number(),
+            some(eq(LogletOffset::new(6)))
+        );
+        for offset in 7..=10 {
+            let entry = read_stream.next().await.unwrap()?;
+            assert_that!(entry.sequence_number(), eq(LogletOffset::new(offset)));
+        }
+
+        // trims of the offloaded segment never go beyond the last record
+        loglet.trim(LogletOffset::MAX).await?;
+        assert_that!(
+            loglet.get_trim_point().await?,
+            some(eq(LogletOffset::new(10)))
+        );
+        assert_that!(
+            object_names(&object_store).await.len(),
+            eq(offloaded.chunks.len() + 1)
+        );
+        assert_that!(
+            object_names(&object_store).await,
+            contains(eq(TRIM_POINT_OBJECT))
+        );
+
+        // the trim point survives a restart
+        let loglet = ObjectStoreLoglet::new(log_id, segment_index, object_store, offloaded);
+        assert_that!(
+            loglet.get_trim_point().await?,
+            some(eq(LogletOffset::new(10)))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn offload_old_and_large_segments() {
+        let tier = test_tier(Arc::new(InMemory::new()), Duration::from_secs(60), 100);
+        let config = LogletConfig::for_testing();
+        let segment = |tail_lsn: Option<u64>| Segment {
+            base_lsn: Lsn::new(1),
+            tail_lsn: tail_lsn.map(Lsn::new),
+            config: &config,
+        };
+
+        assert!(tier.should_offload(&segment(Some(101)), Duration::from_secs(60)));
+        // too recently sealed
+        assert!(!tier.should_offload(&segment(Some(101)), Duration::from_secs(59)));
+        // too small
+        assert!(!tier.should_offload(&segment(Some(100)), Duration::from_secs(60)));
+        // not sealed
+        assert!(!tier.should_offload(&segment(None), Duration::from_secs(60)));
+    }
+
+    const TEST_DESTINATION: &str = "memory:///logs";
+
+    /// A tier offloading to `object_store` in chunks of a couple of records
+    fn test_tier(
+        object_store: Arc<dyn ObjectStore>,
+        offload_delay: Duration,
+        offload_min_records: u64,
+    ) -> ObjectStoreTier {
+        let object_stores = DashMap::default();
+        object_stores.insert(TEST_DESTINATION.to_owned(), object_store);
+        ObjectStoreTier {
+            destination: Some(TEST_DESTINATION.to_owned()),
+            prefix: ObjectPath::from("logs"),
+            object_store_options: ObjectStoreOptions::default(),
+            object_store_retry_policy: RetryPolicy::None,
+            object_stores,
+            object_size: 64,
+            offload_delay,
+            offload_min_records,
+            trim_grace_period: Duration::ZERO,
+            loglets: DashMap::default(),
+        }
+    }
+
+    /// Names of the objects stored under the segment's path
+    async fn object_names(object_store: &Arc<dyn ObjectStore>) -> Vec<String> {
+        object_store
+            .list(Some(&ObjectPath::from("logs/1/2")))
+            .map(|meta| meta.unwrap().location.filename().unwrap().to_owned())
+            .collect()
+            .await
+    }
+}
@@ -758,7 +758,7 @@ mod tests {
     use restate_core::{TaskCenter, TaskKind, TestCoreEnvBuilder};
     use restate_rocksdb::RocksDbManager;
     use restate_types::Versioned;
-    use restate_types::config::LocalLogletOptions;
+    use restate_types::config::{LocalLogletOptions, ObjectStoreTierOptions};
     use restate_types::live::{Constant, LiveLoadExt};
     use restate_types::logs::metadata::{ProviderKind, new_single_node_loglet_params};
     use restate_types::logs::{KeyFilter, SequenceNumber};
@@ -1433,4 +1433,94 @@ mod tests {
 
         Ok(())
     }
+
+    #[restate_core::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn readstream_offloaded_segment() -> anyhow::Result<()> {
+        const LOG_ID: LogId = LogId::new(0);
+
+        let node_env = TestCoreEnvBuilder::with_incoming_only_connector()
+            .set_provider_kind(ProviderKind::InMemory)
+            .build()
+            .await;
+
+        let destination = tempfile::tempdir()?;
+        let svc = BifrostService::new(node_env.metadata_writer)
+            .enable_in_memory_loglet()
+            .enable_object_store_tier(ObjectStoreTierOptions {
+                destination: Some(format!("file://{}", destination.path().display())),
+                ..Default::default()
+            });
+        let bifrost = svc.handle();
+        svc.start().await.expect("loglet must start");
+
+        let mut appender = bifrost.create_appender(LOG_ID, ErrorRecoveryStrategy::Wait)?;
+        for i in 1..=10 {
+            appender.append(format!("segment-1-{i}")).await?;
+        }
+        bifrost.admin().trim(LOG_ID, Lsn::new(3)).await?;
+
+        bifrost
+            .admin()
+            .seal_and_extend_chain(
+                LOG_ID,
+                None,
+                Version::MIN,
+                ProviderKind::InMemory,
+                new_single_node_loglet_params(ProviderKind::InMemory),
+            )
+            .await?;
+        for i in 11..=15 {
+            appender.append(format!("segment-2-{i}")).await?;
+        }
+
+        // move the sealed segment to the object-store tier
+        let logs = node_env.metadata.logs_snapshot();
+        let sealed_segment = logs.chain(&LOG_ID).unwrap().iter().next().unwrap();
+        bifrost
+            .inner
+            .offload_segment(LOG_ID, sealed_segment)
+            .await?;
+        assert_that!(
+            node_env
+                .metadata
+                .logs_ref()
+                .chain(&LOG_ID)
+                .unwrap()
+                .head()
+                .config
+                .kind,
+            eq(InternalKind::ObjectStore)
+        );
+
+        // reads go across the offloaded and the writeable segment
+        let mut reader = bifrost.create_reader(LOG_ID, KeyFilter::Any, Lsn::OLDEST, Lsn::MAX)?;
+        let trim_gap = reader.next().await.expect("to stay alive")?;
+        assert_that!(
+            trim_gap.trim_gap_to_sequence_number(),
+            some(eq(Lsn::new(3)))
+        );
+        for i in 4..=15 {
+            let record = reader.next().await.expect("to stay alive")?;
+            assert_that!(record.sequence_number(), eq(Lsn::new(i)));
+            let segment = if i <= 10 { 1 } else { 2 };
+            assert_that!(
+                record.decode_unchecked::<String>(),
+                eq(format!("segment-{segment}-{i}"))
+            );
+        }
+
+        // trims of the log apply to the offloaded segment too
+        bifrost.admin().trim(LOG_ID, Lsn::new(7)).await?;
+        let mut reader = bifrost.create_reader(LOG_ID, KeyFilter::Any, Lsn::OLDEST, Lsn::new(8))?;
+        let trim_gap = reader.next().await.expect("to stay alive")?;
+        assert_that!(
+            trim_gap.trim_gap_to_sequence_number(),
+            some(eq(Lsn::new(7)))
+        );
+        let record = reader.next().await.expect("to stay alive")?;
+        assert_that!(record.sequence_number(), eq(Lsn::new(8)));
+        assert!(reader.next().await.is_none());
+
+        Ok(())
+    }
 }
@@ -18,6 +18,7 @@ use tracing::{debug, error, trace};
 use restate_core::{MetadataWriter, TaskCenterFutureExt, TaskKind, cancellation_watcher};
 #[cfg(feature = "local-loglet")]
 use restate_types::config::LocalLogletOptions;
+use restate_types::config::ObjectStoreTierOptions;
 #[cfg(feature = "local-loglet")]
 use restate_types::live::BoxLiveLoad;
 use restate_types::logs::metadata::ProviderKind;
@@ -25,6 +26,7 @@ use restate_types::logs::metadata::ProviderKind;
 use crate::bifrost::BifrostInner;
 #[cfg(any(test, feature = "memory-loglet"))]
 use crate::providers::memory_loglet;
+use crate::providers::object_store_loglet::ObjectStoreTier;
 use crate::watchdog::{Watchdog, WatchdogCommand};
 use crate::{Bifrost, loglet::LogletProviderFactory};
 
@@ -34,6 +36,7 @@ pub struct BifrostService {
     watchdog_rx: mpsc::UnboundedReceiver<WatchdogCommand>,
     metadata_writer: MetadataWriter,
     factories: HashMap<ProviderKind, Box<dyn LogletProviderFactory>>,
+    object_store_tier: Option<ObjectStoreTierOptions>,
 }
 
 impl BifrostService {
@@ -47,6 +50,7 @@ impl BifrostService {
             watchdog_rx,
             metadata_writer,
             factories: HashMap::with_capacity(ProviderKind::LENGTH),
+            object_store_tier: None,
         }
     }
 
@@ -69,6 +73,13 @@ impl BifrostService {
         self
     }
 
+    /// Reads offloaded segments, and offloads sealed segments to the object store if `options`
+    /// has a destination.
+    pub fn enable_object_store_tier(mut self, options: ObjectStoreTierOptions) -> Self {
+        self.object_store_tier = Some(options);
+        self
+    }
+
     pub fn handle(&self) -> Bifrost {
         Bifrost::new(self.inner.clone())
     }
@@ -149,6 +160,21 @@ impl BifrostService {
             .set(providers.clone())
             .map_err(|_| anyhow::anyhow!("bifrost must be initialized only once"))?;
 
+        let object_store_tier = match self.object_store_tier {
+            Some(ref options) => Some(ObjectStoreTier::new_from_config(options).await?),
+            None => None,
+        };
+        if object_store_tier
+            .as_ref()
+            .is_some_and(ObjectStoreTier::is_offloading)
+        {
+            debug!("Object-store tier enabled");
+        }
+        self.inner
+            .object_store_tier
+            .set(object_store_tier.map(Arc::new))
+            .map_err(|_| anyhow::anyhow!("bifrost must be initialized only once"))?;
+
         // We spawn the watchdog as a background long-running task
         Watchdog::start(self.inner, self.watchdog_rx, self.metadata_writer)?;
 
@@ -11,7 +11,7 @@
 use std::sync::Arc;
 use std::time::Duration;
 
-use ahash::{HashMap, HashMapExt};
+use ahash::{HashMap, HashMapExt, HashSet};
 use enum_map::Enum;
 use futures::StreamExt;
 use futures::future::OptionFuture;
@@ -26,7 +26,7 @@ use restate_core::{
     cancellation_watcher,
 };
 use restate_types::config::Configuration;
-use restate_types::logs::metadata::{Logs, ProviderKind, SegmentIndex};
+use restate_types::logs::metadata::{InternalKind, Logs, ProviderKind, Segment, SegmentIndex};
 use restate_types::logs::{LogId, Lsn, SequenceNumber};
 use restate_util_time::DurationExt;
 
@@ -34,6 +34,7 @@ use crate::BifrostAdmin;
 use crate::bifrost::BifrostInner;
 use crate::log_chain_writer::{LogChainCommand, LogChainWriter};
 use crate::loglet::{Improvement, LogletProvider};
+use crate::providers::object_store_loglet::{self, ObjectStoreLogletParams};
 
 pub type WatchdogSender = mpsc::UnboundedSender<WatchdogCommand>;
 type WatchdogReceiver = mpsc::UnboundedReceiver<WatchdogCommand>;
@@ -41,6 +42,7 @@ type WatchdogReceiver = mpsc::UnboundedReceiver<WatchdogCommand>;
 const IMPROVEMENT_ROUND_INTERVAL: Duration = Duration::from_secs(1);
 // this duration is jitter-ed with +/- 50% to splay updates
 const IMPROVEMENT_ACTION_AFTER: Duration = Duration::from_secs(5);
+const OFFLOAD_ROUND_INTERVAL: Duration = Duration::from_secs(10);
 
 pub enum WatchdogCommand {
     WatchProvider(Arc<dyn LogletProvider>),
@@ -71,6 +73,12 @@ pub struct Watchdog {
     in_flight_trim: Option<restate_core::task_center::TaskHandle<()>>,
     my_preferred_logs: HashMap<LogId, PreferredLog>,
     pending_trims: TrimRequests,
+    in_flight_offload: Option<TaskHandle<()>>,
+    /// Offloaded segments whose source loglet has been trimmed by this node
+    trimmed_sources: HashSet<(LogId, SegmentIndex)>,
+    /// When this node first saw sealed segments that were not offloaded yet. The offload delay
+    /// is measured from then, so it restarts with the node.
+    sealed_segments: HashMap<(LogId, SegmentIndex), Instant>,
 }
 
 struct PreferredLog {
@@ -110,6 +118,9 @@ impl Watchdog {
             in_flight_trim: None,
             pending_trims: HashMap::with_capacity(128),
             my_preferred_logs: HashMap::default(),
+            in_flight_offload: None,
+            trimmed_sources: HashSet::default(),
+            sealed_segments: HashMap::default(),
         };
 
         TaskCenter::spawn(
@@ -219,6 +230,7 @@ impl Watchdog {
         trace!("Bifrost watchdog started");
 
         let mut improvement_interval = tokio::time::interval(IMPROVEMENT_ROUND_INTERVAL);
+        let mut offload_interval = tokio::time::interval(OFFLOAD_ROUND_INTERVAL);
         let mut logs = Metadata::with_current(|m| m.updateable_logs_metadata());
         let mut config = Configuration::live();
 
@@ -260,9 +272,16 @@ impl Watchdog {
                     let logs = logs.live_load();
                     self.improve_logs(logs);
                 }
+                _tick = offload_interval.tick(), if !shutdown_requested => {
+                    let logs = logs.live_load();
+                    self.offload_segments(logs);
+                }
                 Some(_) = OptionFuture::from(self.in_flight_trim.as_mut()) => {
                     self.in_flight_trim = None;
                 }
+                Some(_) = OptionFuture::from(self.in_flight_offload.as_mut()) => {
+                    self.in_flight_offload = None;
+                }
             }
         }
         Ok(())
@@ -386,6 +405,157 @@ impl Watchdog {
         }
     }
 
+    /// Moves the sealed segments of the logs we are the preferred writer of to the object-store
+    /// tier once they are old and large enough, and trims the source loglets of offloaded
+    /// segments once their grace period elapsed.
+    ///
+    /// Segments are handled one at a time to keep the load on the object store low. Nothing is
+    /// offloaded until every node of the cluster can decode offloaded segments.
+    fn offload_segments(&mut self, logs: &Logs) {
+        let Some(tier) = self.inner.object_store_tier() else {
+            return;
+        };
+        if self.in_flight_offload.is_some() {
+            return;
+        }
+
+        // forget about segments that were removed from their chain
+        let in_chain = |(log_id, segment_index): &(LogId, SegmentIndex)| {
+            logs.chain(log_id)
+                .is_some_and(|chain| chain.iter().any(|s| s.index() == *segment_index))
+        };
+        self.trimmed_sources.retain(in_chain);
+        self.sealed_segments.retain(|segment, _| in_chain(segment));
+
+        let offload_allowed = match object_store_loglet::missing_cluster_version() {
+            None => true,
+            Some(min_version) => {
+                trace!(
+                    "Not offloading sealed segments until all nodes run at least v{min_version}"
+                );
+                false
+            }
+        };
+        let trim_grace_period = tier.trim_grace_period();
+        let now = Instant::now();
+        for log_id in self.my_preferred_logs.keys() {
+            let Some(chain) = logs.chain(log_id) else {
+                continue;
+            };
+            for segment in chain.iter() {
+                let task = match segment.config.kind {
+                    InternalKind::ObjectStore
+                        if !self.trimmed_sources.contains(&(*log_id, segment.index())) =>
+                    {
+                        let Ok(params) = ObjectStoreLogletParams::deserialize_from(
+                            segment.config.params.as_bytes(),
+                        ) else {
+                            continue;
+                        };
+                        if params.archived_at.elapsed() < trim_grace_period {
+                            continue;
+                        }
+                        self.trimmed_sources.insert((*log_id, segment.index()));
+                        self.spawn_trim_source(*log_id, segment.index(), params)
+                    }
+                    InternalKind::Local | InternalKind::Replicated
+                        if offload_allowed
+                            && tier.is_offloading()
+                            && segment.tail_lsn.is_some() =>
+                    {
+                        let sealed_since = *self
+                            .sealed_segments
+                            .entry((*log_id, segment.index()))
+                            .or_insert(now);
+                        if !tier.should_offload(&segment, now - sealed_since) {
+                            continue;
+                        }
+                        self.spawn_offload(*log_id, segment)
+                    }
+                    _ => continue,
+                };
+                // ignore if task-center is shutting down
+                self.in_flight_offload = task.ok();
+                return;
+            }
+        }
+    }
+
+    fn spawn_offload(
+        &self,
+        log_id: LogId,
+        segment: Segment<'_>,
+    ) -> Result<TaskHandle<()>, ShutdownError> {
+        let bifrost = self.inner.clone();
+        let segment_index = segment.index();
+        let (base_lsn, tail_lsn, config) =
+            (segment.base_lsn, segment.tail_lsn, segment.config.clone());
+        TaskCenter::spawn_unmanaged_child(
+            TaskKind::BifrostBackgroundLowPriority,
+            "offload-segment",
+            async move {
+                let segment = Segment {
+                    base_lsn,
+                    tail_lsn,
+                    config: &config,
+                };
+                let result = bifrost.offload_segment(log_id, segment).await;
+
+                match result {
+                    Ok(()) => info!(
+                        %log_id,
+                        %segment_index,
+                        "Sealed segment has been moved to the object-store tier"
+                    ),
+                    Err(err) => warn!(
+                        %log_id,
+                        %segment_index,
+                        %err,
+                        "Bifrost watchdog failed to offload a sealed segment to the object-store tier; will retry later"
+                    ),
+                }
+            },
+        )
+    }
+
+    fn spawn_trim_source(
+        &self,
+        log_id: LogId,
+        segment_index: SegmentIndex,
+        params: ObjectStoreLogletParams,
+    ) -> Result<TaskHandle<()>, ShutdownError> {
+        let bifrost = self.inner.clone();
+        TaskCenter::spawn_unmanaged_child(
+            TaskKind::BifrostBackgroundLowPriority,
+            "trim-offloaded-source",
+            async move {
+                let result = async {
+                    let source = bifrost
+                        .provider_for(params.source_kind)?
+                        .get_loglet(log_id, segment_index, &params.source_params)
+                        .await?;
+                    source.trim(params.tail.prev_unchecked()).await?;
+                    Ok::<_, crate::Error>(())
+                }
+                .await;
+
+                match result {
+                    Ok(()) => debug!(
+                        %log_id,
+                        %segment_index,
+                        "Trimmed the source loglet of an offloaded segment"
+                    ),
+                    Err(err) => warn!(
+                        %log_id,
+                        %segment_index,
+                        %err,
+                        "Bifrost watchdog failed to trim the source loglet of an offloaded segment"
+                    ),
+                }
+            },
+        )
+    }
+
     fn store_trim_request(&mut self, log_id: LogId, requested_trim_point: Lsn) {
         self.pending_trims
             .entry(log_id)
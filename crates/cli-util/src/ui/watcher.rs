@@ -35,6 +35,10 @@ pub struct Watch {
 }
 
 impl Watch {
+    pub fn is_enabled(&self) -> bool {
+        self.watch
+    }
+
     pub async fn run<F, O>(&self, mut what: F) -> Result<()>
     where
         F: FnMut() -> O,
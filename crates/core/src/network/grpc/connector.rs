@@ -26,6 +26,7 @@ use restate_types::net::connect_opts::GrpcConnectionOptions;
 use crate::network::grpc::DEFAULT_GRPC_COMPRESSION;
 use crate::network::protobuf::core_node_svc::core_node_svc_client::CoreNodeSvcClient;
 use crate::network::protobuf::network::Message;
+use crate::network::tls::with_client_tls;
 use crate::network::transport_connector::find_node;
 use crate::network::{ConnectError, Destination, Swimlane, TransportConnect};
 use crate::{Metadata, TaskCenter, TaskKind};
@@ -53,7 +54,7 @@ impl TransportConnect for GrpcConnector {
 
         debug!("Connecting to {} at {}", destination, address);
         let networking = &Configuration::pinned().networking;
-        let channel = create_channel(address, swimlane, networking);
+        let channel = create_channel(address, swimlane, networking)?;
 
         // Establish the connection
         let client = CoreNodeSvcClient::new(channel)
@@ -85,7 +86,7 @@ fn create_channel<P: ListenerPort + GrpcPort>(
     address: AdvertisedAddress<P>,
     _swimlane: Swimlane,
     options: &NetworkingOptions,
-) -> Channel {
+) -> Result<Channel, ConnectError> {
     let address = address.into_address().expect("valid address");
     let endpoint = match &address {
         PeerNetAddress::Uds(_) => {
@@ -111,7 +112,15 @@ fn create_channel<P: ListenerPort + GrpcPort>(
         // this true by default, but this is to guard against any change in defaults
         .tcp_nodelay(true);
 
-    match address {
+    let endpoint = match &address {
+        PeerNetAddress::Http(uri) if uri.scheme_str() == Some("https") => {
+            with_client_tls(endpoint, options.tls.as_ref())
+                .map_err(|e| ConnectError::Transport(e.to_string()))?
+        }
+        _ => endpoint,
+    };
+
+    let channel = match address {
         PeerNetAddress::Uds(uds_path) => {
             endpoint.connect_with_connector_lazy(tower::service_fn(move |_: Uri| {
                 let uds_path = uds_path.clone();
@@ -121,7 +130,8 @@ fn create_channel<P: ListenerPort + GrpcPort>(
             }))
         }
         PeerNetAddress::Http(_) => endpoint.connect_lazy()
-    }
+    };
+    Ok(channel)
 }
 
 #[derive(Clone, Default)]
@@ -26,13 +26,14 @@ use tokio_util::either::Either;
 use tonic::transport::{Channel, Endpoint};
 use tracing::{Instrument, Span, debug, error_span, info, instrument, trace};
 
-use restate_types::config::Configuration;
+use restate_types::config::{Configuration, ListenerTlsOptions};
 use restate_types::errors::GenericError;
 use restate_types::net::address::{AdvertisedAddress, GrpcPort};
 use restate_types::net::address::{ListenerPort, PeerNetAddress};
 use restate_types::net::connect_opts::CommonClientConnectionOptions;
 use restate_types::net::listener::Listeners;
 
+use super::tls::{TlsAcceptor, TlsConfigError, with_client_tls};
 use crate::{ShutdownError, TaskCenter, TaskKind, cancellation_watcher};
 
 pub enum DNSResolution {
@@ -61,6 +62,18 @@ pub fn create_tonic_channel<
 
     let endpoint = apply_options(endpoint, options);
 
+    // Peers advertising an `https` address serve TLS, like the fabric connector does
+    let endpoint = match &address {
+        PeerNetAddress::Http(uri) if uri.scheme_str() == Some("https") => {
+            let networking = &Configuration::pinned().networking;
+            match with_client_tls(endpoint, options.tls().or(networking.tls.as_ref())) {
+                Ok(endpoint) => endpoint,
+                Err(err) => return failing_channel(Channel::builder(uri.clone()), err),
+            }
+        }
+        _ => endpoint,
+    };
+
     match address {
         PeerNetAddress::Uds(uds_path) => {
             endpoint.connect_with_connector_lazy(tower::service_fn(move |_: Uri| {
@@ -92,6 +105,16 @@ pub fn create_tonic_channel<
     }
 }
 
+/// A channel failing every request with the TLS configuration error, so that callers see why
+/// they cannot connect.
+fn failing_channel(endpoint: Endpoint, err: TlsConfigError) -> Channel {
+    let err = err.to_string();
+    endpoint.connect_with_connector_lazy(tower::service_fn(move |_: Uri| {
+        let err = err.clone();
+        async move { Err::<TokioIo<UnixStream>, _>(io::Error::other(err)) }
+    }))
+}
+
 fn apply_options<T: CommonClientConnectionOptions + Send + Sync + ?Sized>(
     endpoint: Endpoint,
     options: &T,
@@ -115,6 +138,8 @@ pub enum Error {
     Io(#[from] io::Error),
     #[error("failed handling hyper connection: {0}")]
     HandlingConnection(#[from] GenericError),
+    #[error("cannot configure TLS: {0}")]
+    Tls(#[from] TlsConfigError),
     #[error(transparent)]
     Shutdown(#[from] ShutdownError),
 }
@@ -127,6 +152,7 @@ pub enum Error {
 )]
 pub async fn run_hyper_server<P: ListenerPort, S, B>(
     listeners: Listeners<P>,
+    tls_options: Option<&ListenerTlsOptions>,
     service: S,
     on_stop: impl Fn(),
 ) -> Result<(), Error>
@@ -150,8 +176,12 @@ where
         Span::current().record("server.port", socket_addr.port());
     }
 
+    let tls_acceptor = tls_options
+        .map(|tls_options| TlsAcceptor::start(tls_options, P::NAME))
+        .transpose()?;
+
     info!("Server listening");
-    run_listener_loop(listeners, service, P::NAME).await?;
+    run_listener_loop(listeners, tls_acceptor, service, P::NAME).await?;
     on_stop();
 
     info!("Stopped listening");
@@ -161,6 +191,7 @@ where
 
 async fn run_listener_loop<P: ListenerPort, S, B>(
     mut listeners: Listeners<P>,
+    tls_acceptor: Option<TlsAcceptor>,
     service: S,
     server_name: &'static str,
 ) -> Result<(), Error>
@@ -205,26 +236,35 @@ where
 
                 match stream {
                     Either::Left(tcp_stream) => {
-                        // TCP SOCKET
-                        let io = TokioIo::new(tcp_stream);
-                        let connection = graceful_shutdown.watch(builder
-                            .serve_connection(io, service.clone()).into_owned());
-                        TaskCenter::spawn(TaskKind::SocketHandler, task_name.clone(), async move {
-                            trace!("New tcp connection accepted");
-                            if let Err(e) = connection.await {
-                                if let Some(hyper_error) = e.downcast_ref::<hyper::Error>() {
-                                    if hyper_error.is_incomplete_message() {
-                                        debug!("Connection closed before request completed");
+                        if let Some(tls_acceptor) = tls_acceptor.clone() {
+                            // TLS over TCP SOCKET, the handshake happens off the accept loop
+                            let watcher = graceful_shutdown.watcher();
+                            let service = service.clone();
+                            TaskCenter::spawn(TaskKind::SocketHandler, task_name.clone(), async move {
+                                let tls_stream = match tls_acceptor.accept(tcp_stream).await {
+                                    Ok(tls_stream) => tls_stream,
+                                    Err(e) => {
+                                        debug!("TLS handshake failed: {e}");
+                                        return Ok(());
                                     }
-                                } else {
-                                    debug!("Connection terminated due to error: {e}");
-                                }
-                            } else {
-                                trace!("Connection completed cleanly");
-                            }
-                            Ok(())
-                        }.instrument(socket_span))?;
-
+                                };
+                                trace!("New tls connection accepted");
+                                let connection = watcher.watch(builder
+                                    .serve_connection(TokioIo::new(tls_stream), service).into_owned());
+                                on_connection_closed(connection.await);
+                                Ok(())
+                            }.instrument(socket_span))?;
+                        } else {
+                            // TCP SOCKET
+                            let io = TokioIo::new(tcp_stream);
+                            let connection = graceful_shutdown.watch(builder
+                                .serve_connection(io, service.clone()).into_owned());
+                            TaskCenter::spawn(TaskKind::SocketHandler, task_name.clone(), async move {
+                                trace!("New tcp connection accepted");
+                                on_connection_closed(connection.await);
+                                Ok(())
+                            }.instrument(socket_span))?;
+                        }
                     },
                     Either::Right(unix_stream) => {
                         // UNIX SOCKET
@@ -233,17 +273,7 @@ where
                             .serve_connection(io, service.clone()).into_owned());
                         TaskCenter::spawn(TaskKind::SocketHandler, task_name.clone(), async move {
                             trace!("New uds connection accepted");
-                            if let Err(e) = connection.await {
-                                if let Some(hyper_error) = e.downcast_ref::<hyper::Error>() {
-                                    if hyper_error.is_incomplete_message() {
-                                        debug!("Connection closed before request completed");
-                                    }
-                                } else {
-                                    debug!("Connection terminated due to error: {e}");
-                                }
-                            } else {
-                                trace!("Connection completed cleanly");
-                            }
+                            on_connection_closed(connection.await);
                             Ok(())
                         }.instrument(socket_span))?;
                     }
@@ -266,6 +296,20 @@ where
     Ok(())
 }
 
+fn on_connection_closed(result: Result<(), GenericError>) {
+    if let Err(e) = result {
+        if let Some(hyper_error) = e.downcast_ref::<hyper::Error>() {
+            if hyper_error.is_incomplete_message() {
+                debug!("Connection closed before request completed");
+            }
+        } else {
+            debug!("Connection terminated due to error: {e}");
+        }
+    } else {
+        trace!("Connection completed cleanly");
+    }
+}
+
 #[derive(Clone, Default)]
 struct TaskCenterExecutor;
 
@@ -345,3 +389,58 @@ impl<R> Future for RandomAddressResolverFuture<R> {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+    use std::net::SocketAddr;
+
+    use tower::ServiceExt;
+
+    use restate_types::config::NetworkingOptions;
+    use restate_types::net::address::FabricPort;
+
+    use super::*;
+    use crate::network::tls::test_util::TestCertificates;
+
+    #[restate_core::test]
+    async fn tonic_channel_connects_to_tls_only_node() -> anyhow::Result<()> {
+        let _ = rustls::crypto::aws_lc_rs::default_provider().install_default();
+        let certificates = TestCertificates::generate();
+        let dir = tempfile::tempdir()?;
+        let (listener_tls, client_tls) = certificates.write_to(dir.path());
+
+        let listeners =
+            Listeners::<FabricPort>::new_tcp_listener(SocketAddr::from(([127, 0, 0, 1], 0)))
+                .await?;
+        let port = listeners.tcp_address().expect("tcp listener").port();
+        let service = hyper::service::service_fn(|_| async {
+            Ok::<_, Infallible>(hyper::Response::new(String::new()))
+        });
+        TaskCenter::spawn(TaskKind::NodeRpcServer, "tls-server", async move {
+            run_hyper_server(listeners, Some(&listener_tls), service, || {}).await?;
+            Ok(())
+        })?;
+
+        let address: AdvertisedAddress<FabricPort> = format!("https://localhost:{port}").parse()?;
+        let request = || {
+            http::Request::builder()
+                .uri(format!("https://localhost:{port}/"))
+                .body(tonic::body::Body::empty())
+                .unwrap()
+        };
+
+        let mut options = NetworkingOptions::default();
+        options.tls = Some(client_tls);
+        let channel = create_tonic_channel(address.clone(), &options, DNSResolution::Gai);
+        let response = channel.oneshot(request()).await?;
+        assert_eq!(http::StatusCode::OK, response.status());
+
+        // the node's certificate is not trusted without the CA
+        let channel =
+            create_tonic_channel(address, &NetworkingOptions::default(), DNSResolution::Gai);
+        assert!(channel.oneshot(request()).await.is_err());
+
+        Ok(())
+    }
+}
@@ -16,6 +16,7 @@ use tonic::service::Routes;
 use tower_http::trace::{DefaultOnFailure, TraceLayer};
 use tracing::{Level, debug};
 
+use restate_types::config::Configuration;
 use restate_types::health::HealthStatus;
 use restate_types::net::address::FabricPort;
 use restate_types::net::listener::{AddressBook, Listeners};
@@ -115,7 +116,12 @@ impl NetworkServerBuilder {
 
         node_rpc_health.update(NodeRpcStatus::Ready);
 
-        run_hyper_server(self.listeners, service, || {
+        let tls_options = Configuration::pinned()
+            .common
+            .fabric_listener_options()
+            .tls()
+            .cloned();
+        run_hyper_server(self.listeners, tls_options.as_ref(), service, || {
             node_rpc_health.update(NodeRpcStatus::Stopping)
         })
         .await?;
@@ -0,0 +1,433 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use arc_swap::ArcSwap;
+use rustls::RootCertStore;
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use rustls::server::{ServerConfig, WebPkiClientVerifier};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_rustls::server::TlsStream;
+use tonic::transport::{Certificate, ClientTlsConfig, Endpoint, Identity};
+use tracing::{debug, info, warn};
+
+use restate_types::config::{FabricClientTlsOptions, ListenerTlsOptions};
+
+use crate::{ShutdownError, TaskCenter, TaskKind, cancellation_watcher};
+
+/// Upper bound for a client to complete the TLS handshake, so that idle sockets don't pile up.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Debug, thiserror::Error)]
+pub enum TlsConfigError {
+    #[error("cannot read '{0}': {1}")]
+    Pem(PathBuf, #[source] rustls::pki_types::pem::Error),
+    #[error("cannot read '{0}': {1}")]
+    Read(PathBuf, #[source] io::Error),
+    #[error("'{0}' contains no certificates")]
+    NoCertificates(PathBuf),
+    #[error("invalid client CA certificates: {0}")]
+    ClientCa(#[from] rustls::server::VerifierBuilderError),
+    #[error(transparent)]
+    Rustls(#[from] rustls::Error),
+    #[error(transparent)]
+    Client(#[from] tonic::transport::Error),
+    #[error(transparent)]
+    Shutdown(#[from] ShutdownError),
+}
+
+/// Terminates TLS on accepted connections.
+///
+/// The certificate, key and client CA files are polled for changes and the server configuration
+/// is swapped when they change, so that rotated certificates are used for new connections
+/// without restarting the listener. Established connections keep their certificate.
+#[derive(Clone)]
+pub struct TlsAcceptor {
+    config: Arc<ArcSwap<ServerConfig>>,
+}
+
+impl TlsAcceptor {
+    /// Loads the configured certificate and starts watching the files for changes. The watcher
+    /// is a child of the current task and stops together with the listener.
+    pub fn start(
+        options: &ListenerTlsOptions,
+        server_name: &'static str,
+    ) -> Result<Self, TlsConfigError> {
+        let config = Arc::new(ArcSwap::from_pointee(load_server_config(options)?));
+        info!(
+            cert_file = %options.cert_file.display(),
+            mtls = options.client_ca_file.is_some(),
+            "TLS enabled for {server_name}"
+        );
+
+        TaskCenter::spawn_child(
+            TaskKind::TlsCertificateReloader,
+            "tls-certificate-reloader",
+            reload_on_change(options.clone(), Arc::clone(&config), server_name),
+        )?;
+
+        Ok(Self { config })
+    }
+
+    /// Performs the server side of the TLS handshake.
+    pub async fn accept<IO>(&self, stream: IO) -> io::Result<TlsStream<IO>>
+    where
+        IO: AsyncRead + AsyncWrite + Unpin,
+    {
+        let acceptor = tokio_rustls::TlsAcceptor::from(self.config.load_full());
+        tokio::time::timeout(HANDSHAKE_TIMEOUT, acceptor.accept(stream))
+            .await
+            .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, "TLS handshake timed out"))?
+    }
+}
+
+async fn reload_on_change(
+    options: ListenerTlsOptions,
+    config: Arc<ArcSwap<ServerConfig>>,
+    server_name: &'static str,
+) -> anyhow::Result<()> {
+    let mut shutdown = std::pin::pin!(cancellation_watcher());
+    let mut interval = tokio::time::interval(options.reload_interval.into());
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let mut last_modified = modification_times(&options);
+
+    loop {
+        tokio::select! {
+            _ = &mut shutdown => return Ok(()),
+            _ = interval.tick() => {}
+        }
+
+        let modified = modification_times(&options);
+        if modified == last_modified {
+            continue;
+        }
+        debug!("TLS files of {server_name} changed, reloading");
+
+        match load_server_config(&options) {
+            Ok(new_config) => {
+                config.store(Arc::new(new_config));
+                last_modified = modified;
+                info!("Reloaded TLS certificate of {server_name}");
+            }
+            // Files are often not replaced atomically, we'll try again on the next tick
+            Err(err) => warn!("Cannot reload TLS certificate of {server_name}: {err}"),
+        }
+    }
+}
+
+fn modification_times(options: &ListenerTlsOptions) -> Vec<Option<SystemTime>> {
+    [
+        Some(options.cert_file.as_path()),
+        Some(options.key_file.as_path()),
+        options.client_ca_file.as_deref(),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|path| {
+        std::fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .ok()
+    })
+    .collect()
+}
+
+/// Configures the endpoint of a peer advertising an `https` address. Without explicit options,
+/// the peer is verified against the native root certificates.
+///
+/// Tonic uses the process-wide default crypto provider, which restate-server sets to aws_lc_rs,
+/// the provider of the TLS listeners.
+pub(crate) fn with_client_tls(
+    endpoint: Endpoint,
+    options: Option<&FabricClientTlsOptions>,
+) -> Result<Endpoint, TlsConfigError> {
+    Ok(endpoint.tls_config(client_tls_config(options)?)?)
+}
+
+fn client_tls_config(
+    options: Option<&FabricClientTlsOptions>,
+) -> Result<ClientTlsConfig, TlsConfigError> {
+    let Some(options) = options else {
+        return Ok(ClientTlsConfig::new()
+            .with_native_roots()
+            .assume_http2(true));
+    };
+
+    let read =
+        |path: &Path| std::fs::read(path).map_err(|e| TlsConfigError::Read(path.to_owned(), e));
+
+    let mut config = ClientTlsConfig::new().assume_http2(true);
+    config = match &options.ca_file {
+        Some(ca_file) => config.ca_certificate(Certificate::from_pem(read(ca_file)?)),
+        None => config.with_native_roots(),
+    };
+    if let (Some(cert_file), Some(key_file)) = (&options.cert_file, &options.key_file) {
+        config = config.identity(Identity::from_pem(read(cert_file)?, read(key_file)?));
+    }
+    Ok(config)
+}
+
+fn load_server_config(options: &ListenerTlsOptions) -> Result<ServerConfig, TlsConfigError> {
+    // Both ring and aws_lc_rs are enabled in the workspace, so the provider must be explicit
+    let provider = Arc::new(rustls::crypto::aws_lc_rs::default_provider());
+    let cert_chain = load_certificates(&options.cert_file)?;
+    let key = PrivateKeyDer::from_pem_file(&options.key_file)
+        .map_err(|e| TlsConfigError::Pem(options.key_file.clone(), e))?;
+
+    let builder = ServerConfig::builder_with_provider(Arc::clone(&provider))
+        .with_protocol_versions(rustls::DEFAULT_VERSIONS)?;
+    let mut config = match &options.client_ca_file {
+        Some(client_ca_file) => builder.with_client_cert_verifier(
+            WebPkiClientVerifier::builder_with_provider(
+                Arc::new(load_root_store(client_ca_file)?),
+                provider,
+            )
+            .build()?,
+        ),
+        None => builder.with_no_client_auth(),
+    }
+    .with_single_cert(cert_chain, key)?;
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+
+    Ok(config)
+}
+
+fn load_certificates(path: &Path) -> Result<Vec<CertificateDer<'static>>, TlsConfigError> {
+    let certificates = CertificateDer::pem_file_iter(path)
+        .and_then(|certificates| certificates.collect::<Result<Vec<_>, _>>())
+        .map_err(|e| TlsConfigError::Pem(path.to_path_buf(), e))?;
+    if certificates.is_empty() {
+        return Err(TlsConfigError::NoCertificates(path.to_path_buf()));
+    }
+    Ok(certificates)
+}
+
+fn load_root_store(path: &Path) -> Result<RootCertStore, TlsConfigError> {
+    let mut roots = RootCertStore::empty();
+    let (_, ignored) = roots.add_parsable_certificates(load_certificates(path)?);
+    if ignored > 0 {
+        warn!(
+            "Ignored {ignored} unparsable certificates in '{}'",
+            path.display()
+        );
+    }
+    Ok(roots)
+}
+
+#[cfg(feature = "test-util")]
+pub mod test_util {
+    use std::path::Path;
+    use std::sync::Arc;
+
+    use rcgen::{BasicConstraints, CertificateParams, CertifiedIssuer, IsCa, KeyPair};
+    use rustls::RootCertStore;
+    use rustls::client::ClientConfig;
+    use rustls::pki_types::pem::PemObject;
+    use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+
+    use restate_types::config::{FabricClientTlsOptions, ListenerTlsOptions};
+
+    /// A CA with a server certificate for `localhost` and a client certificate, for tests
+    /// exercising TLS and mutual TLS.
+    pub struct TestCertificates {
+        pub ca_pem: String,
+        pub server_cert_pem: String,
+        pub server_key_pem: String,
+        pub client_cert_pem: String,
+        pub client_key_pem: String,
+    }
+
+    impl TestCertificates {
+        pub fn generate() -> Self {
+            let mut ca_params = CertificateParams::new(Vec::<String>::new()).unwrap();
+            ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+            let ca = CertifiedIssuer::self_signed(ca_params, KeyPair::generate().unwrap()).unwrap();
+
+            let issue = |subject_alt_names: Vec<String>| {
+                let key = KeyPair::generate().unwrap();
+                let cert = CertificateParams::new(subject_alt_names)
+                    .unwrap()
+                    .signed_by(&key, &ca)
+                    .unwrap();
+                (cert.pem(), key.serialize_pem())
+            };
+            let (server_cert_pem, server_key_pem) = issue(vec!["localhost".to_owned()]);
+            let (client_cert_pem, client_key_pem) = issue(vec!["client".to_owned()]);
+
+            Self {
+                ca_pem: ca.pem(),
+                server_cert_pem,
+                server_key_pem,
+                client_cert_pem,
+                client_key_pem,
+            }
+        }
+
+        /// Client configuration trusting the CA, presenting the client certificate if
+        /// `with_identity` is set.
+        pub fn client_config(&self, with_identity: bool) -> ClientConfig {
+            let mut roots = RootCertStore::empty();
+            roots
+                .add(CertificateDer::from_pem_slice(self.ca_pem.as_bytes()).unwrap())
+                .unwrap();
+            let builder = ClientConfig::builder_with_provider(Arc::new(
+                rustls::crypto::aws_lc_rs::default_provider(),
+            ))
+            .with_protocol_versions(rustls::DEFAULT_VERSIONS)
+            .unwrap()
+            .with_root_certificates(roots);
+
+            let mut config = if with_identity {
+                builder
+                    .with_client_auth_cert(
+                        vec![
+                            CertificateDer::from_pem_slice(self.client_cert_pem.as_bytes())
+                                .unwrap(),
+                        ],
+                        PrivateKeyDer::from_pem_slice(self.client_key_pem.as_bytes()).unwrap(),
+                    )
+                    .unwrap()
+            } else {
+                builder.with_no_client_auth()
+            };
+            config.alpn_protocols = vec![b"h2".to_vec()];
+            config
+        }
+
+        /// Writes the PEM files to `dir` and returns the listener options requiring client
+        /// certificates, and the client options presenting one.
+        pub fn write_to(&self, dir: &Path) -> (ListenerTlsOptions, FabricClientTlsOptions) {
+            let write = |name: &str, pem: &str| {
+                let path = dir.join(name);
+                std::fs::write(&path, pem).unwrap();
+                path
+            };
+            let ca_file = write("ca.pem", &self.ca_pem);
+
+            let listener = ListenerTlsOptions::new(
+                write("server.pem", &self.server_cert_pem),
+                write("server.key", &self.server_key_pem),
+            )
+            .with_client_ca_file(ca_file.clone());
+            let client = FabricClientTlsOptions {
+                ca_file: Some(ca_file),
+                cert_file: Some(write("client.pem", &self.client_cert_pem)),
+                key_file: Some(write("client.key", &self.client_key_pem)),
+            };
+            (listener, client)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_util::TestCertificates;
+    use super::*;
+
+    use rustls::ClientConfig;
+    use rustls::pki_types::ServerName;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    fn acceptor(options: &ListenerTlsOptions) -> TlsAcceptor {
+        TlsAcceptor {
+            config: Arc::new(ArcSwap::from_pointee(load_server_config(options).unwrap())),
+        }
+    }
+
+    /// Runs the handshake over an in-memory stream and exchanges a message in both directions.
+    async fn handshake(acceptor: &TlsAcceptor, client_config: ClientConfig) -> io::Result<()> {
+        let (client_io, server_io) = tokio::io::duplex(16 * 1024);
+        let connector = tokio_rustls::TlsConnector::from(Arc::new(client_config));
+
+        let server = async {
+            let mut stream = acceptor.accept(server_io).await?;
+            let mut buf = [0; 4];
+            stream.read_exact(&mut buf).await?;
+            stream.write_all(b"pong").await?;
+            stream.flush().await?;
+            assert_eq!(&buf, b"ping");
+            io::Result::Ok(())
+        };
+        let client = async {
+            let mut stream = connector
+                .connect(ServerName::try_from("localhost").unwrap(), client_io)
+                .await?;
+            stream.write_all(b"ping").await?;
+            stream.flush().await?;
+            let mut buf = [0; 4];
+            stream.read_exact(&mut buf).await?;
+            assert_eq!(&buf, b"pong");
+            io::Result::Ok(())
+        };
+
+        let (server, client) = tokio::join!(server, client);
+        server.and(client)
+    }
+
+    #[tokio::test]
+    async fn tls_handshake() {
+        let certificates = TestCertificates::generate();
+        let dir = tempfile::tempdir().unwrap();
+        let (mut options, _) = certificates.write_to(dir.path());
+        options.client_ca_file = None;
+
+        handshake(&acceptor(&options), certificates.client_config(false))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn mtls_handshake_with_client_certificate() {
+        let certificates = TestCertificates::generate();
+        let dir = tempfile::tempdir().unwrap();
+        let (options, _) = certificates.write_to(dir.path());
+
+        handshake(&acceptor(&options), certificates.client_config(true))
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn mtls_rejects_client_without_certificate() {
+        let certificates = TestCertificates::generate();
+        let dir = tempfile::tempdir().unwrap();
+        let (options, _) = certificates.write_to(dir.path());
+
+        assert!(
+            handshake(&acceptor(&options), certificates.client_config(false))
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn mtls_rejects_client_certificate_of_other_ca() {
+        let certificates = TestCertificates::generate();
+        let dir = tempfile::tempdir().unwrap();
+        let (options, _) = certificates.write_to(dir.path());
+
+        // Same server CA, but the client certificate is issued by an unrelated CA
+        let other = TestCertificates::generate();
+        let client_certificates = TestCertificates {
+            ca_pem: certificates.ca_pem.clone(),
+            ..other
+        };
+
+        assert!(
+            handshake(&acceptor(&options), client_certificates.client_config(true))
+                .await
+                .is_err()
+        );
+    }
+}
@@ -81,6 +81,9 @@ pub enum TaskKind {
     LogServerRole,
     #[strum(props(OnError = "log", runtime = "default"))]
     SocketHandler,
+    /// Watches the certificate files of a TLS listener and reloads them on change.
+    #[strum(props(OnCancel = "abort", OnError = "log"))]
+    TlsCertificateReloader,
     /// An http2 stream handler created by the server-side of the connection.
     #[strum(props(OnError = "log", runtime = "default"))]
     H2ServerStream,
@@ -100,7 +103,7 @@ pub enum TaskKind {
     SystemService,
     #[strum(props(OnCancel = "abort"))]
     Ingress,
-    /// Kafka ingestion related task
+    /// Subscription ingestion (Kafka, NATS and webhooks) related task
     Kafka,
     PartitionProcessor,
     #[strum(props(runtime = "default"))]
@@ -83,7 +83,12 @@ impl<T, V> IngestionClient<T, V> {
         opts: SessionOptions,
     ) -> Self {
         Self {
-            manager: SessionManager::new(networking, partition_routing, opts),
+            manager: SessionManager::new(
+                networking,
+                partition_routing,
+                partition_table.clone(),
+                opts,
+            ),
             partition_table,
             permits: Arc::new(Semaphore::new(memory_budget.get())),
             memory_budget,
@@ -141,7 +146,7 @@ where
 
         let acquire = self.permits.clone().acquire_many_owned(budget as u32);
 
-        IngestFuture::awaiting_permits(record, handle, acquire)
+        IngestFuture::awaiting_permits(partition_key, record, handle, acquire)
     }
 
     /// Once closed, calls to ingest will return [`IngestionError::Closed`].
@@ -159,6 +164,7 @@ enum IngestFutureState {
         err: Option<IngestionError>,
     },
     AwaitingPermit {
+        partition_key: PartitionKey,
         record: Option<IngestRecord>,
         handle: SessionHandle,
         acquire: BoxFuture<'static, Result<OwnedSemaphorePermit, AcquireError>>,
@@ -182,12 +188,18 @@ impl IngestFuture {
 
     /// create a pending ingestion future that will eventually resolve to
     /// [`RecordCommit`] or error
-    fn awaiting_permits<F>(record: IngestRecord, handle: SessionHandle, acquire: F) -> Self
+    fn awaiting_permits<F>(
+        partition_key: PartitionKey,
+        record: IngestRecord,
+        handle: SessionHandle,
+        acquire: F,
+    ) -> Self
     where
         F: Future<Output = Result<OwnedSemaphorePermit, AcquireError>> + Send + 'static,
     {
         Self {
             state: IngestFutureState::AwaitingPermit {
+                partition_key,
                 record: Some(record),
                 handle,
                 acquire: acquire.boxed(),
@@ -207,6 +219,7 @@ impl Future for IngestFuture {
         let result = match this.state.as_mut().project() {
             IngestFutureStateProj::Error { err } => Poll::Ready(Err(err.take().unwrap())),
             IngestFutureStateProj::AwaitingPermit {
+                partition_key,
                 record,
                 handle,
                 acquire,
@@ -215,7 +228,7 @@ impl Future for IngestFuture {
                     Ok(permit) => {
                         let record = record.take().unwrap();
                         handle
-                            .ingest(permit, record)
+                            .ingest(permit, *partition_key, record)
                             .map_err(|_| IngestionError::Closed("partition session closed"))
                     }
                     Err(_) => Err(IngestionError::Closed("permits semaphore closed")),
@@ -273,7 +286,7 @@ impl InputRecord<String> {
 
 #[cfg(test)]
 mod test {
-    use std::{num::NonZeroUsize, time::Duration};
+    use std::{num::NonZeroUsize, sync::Arc, time::Duration};
 
     use bytes::BytesMut;
     use futures::{FutureExt, StreamExt};
@@ -281,7 +294,7 @@ mod test {
     use test_log::test;
 
     use restate_core::{
-        Metadata, TaskCenter, TestCoreEnvBuilder,
+        Metadata, MetadataWriter, TaskCenter, TestCoreEnvBuilder,
         network::{
             BackPressureMode, FailingConnector, Incoming, Rpc, ServiceMessage, ServiceStream,
         },
@@ -310,12 +323,14 @@ mod test {
         ServiceStream<PartitionLeaderService>,
         IngestionClient<FailingConnector, String>,
     ) {
-        let (incoming, client, _states, _my_node_id) = init_env_with_states(batch_size).await;
+        let (incoming, client, _states, _my_node_id, _metadata_writer) =
+            init_env_with_states(batch_size).await;
         (incoming, client)
     }
 
     /// Like [`init_env`] but also returns the partition replica-set states (so tests can simulate
-    /// leadership changes via `note_observed_leader`) and the node id acting as leader.
+    /// leadership changes via `note_observed_leader`), the node id acting as leader and the
+    /// metadata writer (so tests can update the partition table).
     async fn init_env_with_states(
         batch_size: usize,
     ) -> (
@@ -323,6 +338,7 @@ mod test {
         IngestionClient<FailingConnector, String>,
         PartitionReplicaSetStates,
         GenerationalNodeId,
+        MetadataWriter,
     ) {
         let mut builder = TestCoreEnvBuilder::with_incoming_only_connector()
             .add_mock_nodes_config()
@@ -363,7 +379,13 @@ mod test {
                 .unwrap(),
         );
 
-        (incoming, client, partition_replica_set_states, my_node_id)
+        (
+            incoming,
+            client,
+            partition_replica_set_states,
+            my_node_id,
+            env.metadata_writer,
+        )
     }
 
     async fn must_next(
@@ -595,7 +617,8 @@ mod test {
         let one_record = InputRecord::from_str("r0")
             .into_record(&mut buf)
             .estimate_size();
-        let (mut incoming, mut client, states, my_node_id) = init_env_with_states(one_record).await;
+        let (mut incoming, mut client, states, my_node_id, _metadata_writer) =
+            init_env_with_states(one_record).await;
 
         let c0 = client.ingest(0, InputRecord::from_str("r0")).await.unwrap();
         let c1 = client.ingest(0, InputRecord::from_str("r1")).await.unwrap();
@@ -658,4 +681,58 @@ mod test {
         c0.await.expect("r0 commits");
         c1.await.expect("r1 commits");
     }
+
+    // Records of a partition which was split are re-routed to the partition that owns their key
+    // now. The leader of the split partition rejects them because its log is permanently sealed.
+    #[test(restate_core::test(start_paused = true))]
+    async fn split_partition_reroutes_records() {
+        let mut buf = BytesMut::new();
+        let (mut incoming, mut client, states, my_node_id, metadata_writer) =
+            init_env_with_states(10).await;
+
+        let commit = client.ingest(0, InputRecord::from_str("r0")).await.unwrap();
+
+        let msg = must_next(&mut incoming).await;
+        assert_that!(msg.sort_code(), some(eq(0)));
+
+        let mut partition_table = Metadata::with_current(|m| m.partition_table_snapshot())
+            .as_ref()
+            .clone()
+            .into_builder();
+        let [left, right] = partition_table
+            .split_partition(PartitionId::from(0))
+            .unwrap();
+        for partition_id in [left, right] {
+            states.note_observed_leader(
+                partition_id,
+                LeadershipState {
+                    current_leader: my_node_id,
+                    current_leader_epoch: LeaderEpoch::INITIAL,
+                },
+            );
+        }
+        metadata_writer
+            .update(Arc::new(partition_table.build()))
+            .await
+            .unwrap();
+
+        let (rx, _) = msg.split();
+        rx.send(ResponseStatus::NotLeader { of: 0.into() }.into());
+        tokio::time::sleep(Duration::from_secs(1)).await;
+
+        // the record is replayed against the partition that owns key 0 now
+        let msg = must_next(&mut incoming).await;
+        assert_that!(msg.sort_code(), some(eq(u64::from(left))));
+        let (rx, body) = msg.split();
+        assert_that!(
+            body.records,
+            all!(
+                len(eq(1)),
+                contains(eq(InputRecord::from_str("r0").into_record(&mut buf)))
+            )
+        );
+        rx.send(ResponseStatus::Ack.into());
+
+        commit.await.expect("r0 commits");
+    }
 }
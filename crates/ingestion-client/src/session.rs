@@ -23,8 +23,10 @@ use restate_core::{
     partitions::PartitionRouting,
 };
 use restate_types::{
-    identifiers::PartitionId,
+    identifiers::{PartitionId, PartitionKey},
+    live::Live,
     net::ingest::{IngestRecord, IngestRequest, ResponseStatus},
+    partitions::{FindPartition, PartitionTable},
     retries::RetryPolicy,
 };
 
@@ -78,12 +80,16 @@ where
 }
 
 impl RecordCommit {
-    fn new(permit: OwnedSemaphorePermit) -> (Self, RecordCommitResolver) {
+    fn new(
+        permit: OwnedSemaphorePermit,
+        partition_key: PartitionKey,
+    ) -> (Self, RecordCommitResolver) {
         let (tx, rx) = oneshot::channel();
         (
             Self { v: Some(()), rx },
             RecordCommitResolver {
                 tx,
+                partition_key,
                 _permit: permit,
             },
         )
@@ -91,6 +97,14 @@ impl RecordCommit {
 }
 
 impl<V> RecordCommit<V> {
+    /// Create a [`RecordCommit`] that is already resolved to `v`. Useful for
+    /// records that were made durable without going through the [`IngestionClient`].
+    pub fn committed(v: V) -> Self {
+        let (tx, rx) = oneshot::channel();
+        let _ = tx.send(Ok(()));
+        Self { v: Some(v), rx }
+    }
+
     pub fn map<F, T>(self, f: F) -> RecordCommit<T>
     where
         F: FnOnce(V) -> T,
@@ -102,6 +116,8 @@ impl<V> RecordCommit<V> {
 
 struct RecordCommitResolver {
     tx: oneshot::Sender<Result<(), CancelledError>>,
+    // used to re-route the record if its partition is split
+    partition_key: PartitionKey,
     _permit: OwnedSemaphorePermit,
 }
 
@@ -114,7 +130,6 @@ impl RecordCommitResolver {
     /// explicitly cancel the RecordCommit
     /// If resolver is dropped, the RecordCommit
     /// will resolve to [`CancelledError`]
-    #[allow(dead_code)]
     pub fn cancelled(self) {
         let _ = self.tx.send(Err(CancelledError));
     }
@@ -139,6 +154,10 @@ impl IngestionBatch {
             resolver.committed();
         }
     }
+
+    fn into_records(self) -> impl Iterator<Item = (RecordCommitResolver, IngestRecord)> {
+        self.resolvers.into_iter().zip(self.records.to_vec())
+    }
 }
 
 /// Tunable parameters for batching and networking behaviour of partition sessions.
@@ -221,9 +240,10 @@ impl SessionHandle {
     pub fn ingest(
         &self,
         permit: OwnedSemaphorePermit,
+        partition_key: PartitionKey,
         record: IngestRecord,
     ) -> Result<RecordCommit, SessionClosed> {
-        let (commit, resolver) = RecordCommit::new(permit);
+        let (commit, resolver) = RecordCommit::new(permit, partition_key);
         self.tx
             .send((resolver, record))
             .map_err(|_| SessionClosed)?;
@@ -234,7 +254,11 @@ impl SessionHandle {
 
 enum SessionState {
     Connecting,
-    Connected { connection: Connection },
+    Connected {
+        connection: Connection,
+    },
+    /// The partition was split and replaced by new partitions
+    Split,
     Shutdown,
 }
 
@@ -356,6 +380,10 @@ where
                     }
                     SessionState::Connecting
                 }
+                SessionState::Split => {
+                    self.reroute();
+                    break;
+                }
                 SessionState::Shutdown => {
                     self.rx.close();
                     break;
@@ -365,6 +393,10 @@ where
     }
 
     async fn connect(&mut self) -> Option<SessionState> {
+        if self.was_split() {
+            return Some(SessionState::Split);
+        }
+
         // Resolve the node separately: routing returns the current leader if known, otherwise it
         // falls back to a live replica-set member.
         let node_id = self
@@ -389,6 +421,60 @@ where
         }
     }
 
+    /// Whether the partition of this session was replaced by the partitions split from it. Its
+    /// log is permanently sealed and will not accept any further records.
+    fn was_split(&self) -> bool {
+        let partition_table = self.manager.partition_table.pinned();
+        partition_table.get(&self.partition).is_none()
+            && partition_table
+                .iter()
+                .any(|(_, partition)| partition.split_from() == Some(self.partition))
+    }
+
+    /// Hands all records which have not been committed yet over to the sessions of the
+    /// partitions which now own their partition keys. The records keep their order.
+    ///
+    /// Records which cannot be routed are cancelled.
+    fn reroute(&mut self) {
+        // new records are routed to the new partitions from here on
+        self.manager.handles.remove(&self.partition);
+        self.rx.close();
+
+        let mut records: Vec<_> = self
+            .in_flight
+            .take()
+            .into_iter()
+            .flat_map(IngestionBatch::into_records)
+            .chain(mem::take(&mut self.carry_over))
+            .collect();
+        while let Ok(item) = self.rx.as_mut().try_recv() {
+            records.push(item);
+        }
+
+        debug!(
+            partition_id = %self.partition,
+            "Partition was split, re-routing {} records to the new partitions",
+            records.len()
+        );
+
+        let partition_table = self.manager.partition_table.pinned();
+        for (resolver, record) in records {
+            match partition_table.find_partition_id(resolver.partition_key) {
+                Ok(partition_id) => {
+                    // a dropped resolver cancels the record
+                    let _ = self.manager.get(partition_id).tx.send((resolver, record));
+                }
+                Err(err) => {
+                    warn!(
+                        partition_key = resolver.partition_key,
+                        "Cannot re-route record of split partition {}: {err}", self.partition
+                    );
+                    resolver.cancelled();
+                }
+            }
+        }
+    }
+
     /// Processes batches one at a time, keeping at most a single batch inflight.
     ///
     /// Each batch must be acknowledged (committed) before the next one is sent.
@@ -486,6 +572,7 @@ where
 struct SessionManagerInner<T> {
     networking: Networking<T>,
     partition_routing: PartitionRouting,
+    partition_table: Live<PartitionTable>,
     opts: SessionOptions,
     // Since ingestion sessions are started on demand
     // we make sure we decouple the session cancellation
@@ -550,11 +637,13 @@ impl<T> SessionManager<T> {
     pub fn new(
         networking: Networking<T>,
         partition_routing: PartitionRouting,
+        partition_table: Live<PartitionTable>,
         opts: SessionOptions,
     ) -> Self {
         let inner = SessionManagerInner {
             networking,
             partition_routing,
+            partition_table,
             opts,
             handles: Default::default(),
             cancellation: CancellationToken::new(),
@@ -0,0 +1,57 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use http::{HeaderMap, HeaderName};
+
+use super::{AuthenticationError, Authenticator, Principal};
+
+pub(crate) const API_KEY_HEADER: HeaderName = HeaderName::from_static("x-restate-api-key");
+
+/// Authenticates requests carrying one of the configured static keys in the
+/// `x-restate-api-key` header.
+pub struct ApiKeyAuthenticator {
+    keys: Vec<(Principal, String)>,
+}
+
+impl ApiKeyAuthenticator {
+    pub fn new(keys: impl IntoIterator<Item = (Principal, String)>) -> Self {
+        Self {
+            keys: keys.into_iter().collect(),
+        }
+    }
+}
+
+impl Authenticator for ApiKeyAuthenticator {
+    fn authenticate(&self, headers: &HeaderMap) -> Result<Option<Principal>, AuthenticationError> {
+        let Some(provided) = headers.get(API_KEY_HEADER) else {
+            return Ok(None);
+        };
+
+        // Compare against every key to not leak which key matched through timing.
+        let mut matched = None;
+        for (principal, key) in &self.keys {
+            if constant_time_eq(provided.as_bytes(), key.as_bytes()) && matched.is_none() {
+                matched = Some(principal);
+            }
+        }
+
+        matched
+            .cloned()
+            .map(Some)
+            .ok_or(AuthenticationError::InvalidApiKey)
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
@@ -0,0 +1,238 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::path::{Path, PathBuf};
+
+use http::{HeaderMap, header};
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{DecodingKey, Validation};
+use serde_json::{Map, Value};
+use tracing::info;
+
+use restate_types::config::IngressJwtOptions;
+
+use super::{AuthenticationError, Authenticator, Principal};
+
+const BEARER_PREFIX: &str = "Bearer ";
+
+#[derive(Debug, thiserror::Error)]
+pub enum JwksReadError {
+    #[error("cannot read JWKS file '{0}': {1}")]
+    Io(PathBuf, #[source] std::io::Error),
+    #[error("cannot parse JWKS file '{0}': {1}")]
+    Parse(PathBuf, #[source] serde_json::Error),
+    #[error("JWKS file '{0}' contains an unsupported key: {1}")]
+    UnsupportedKey(PathBuf, #[source] jsonwebtoken::errors::Error),
+    #[error("JWKS file '{0}' contains no keys")]
+    Empty(PathBuf),
+}
+
+/// Authenticates requests carrying a JWT as bearer token in the `authorization` header.
+///
+/// Tokens are verified against the keys of a JWKS file read at construction time.
+pub struct JwtAuthenticator {
+    keys: Vec<(Option<String>, DecodingKey)>,
+    issuer: Option<String>,
+    audience: Option<String>,
+    principal_claim: String,
+}
+
+impl JwtAuthenticator {
+    pub fn from_options(options: &IngressJwtOptions) -> Result<Self, JwksReadError> {
+        let jwks = read_jwks(&options.jwks_file)?;
+        let keys = jwks
+            .keys
+            .iter()
+            .map(|jwk| {
+                DecodingKey::from_jwk(jwk)
+                    .map(|key| (jwk.common.key_id.clone(), key))
+                    .map_err(|e| JwksReadError::UnsupportedKey(options.jwks_file.clone(), e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        if keys.is_empty() {
+            return Err(JwksReadError::Empty(options.jwks_file.clone()));
+        }
+
+        info!(
+            path = %options.jwks_file.display(),
+            keys = keys.len(),
+            "Loaded ingress JWKS"
+        );
+
+        Ok(Self {
+            keys,
+            issuer: options.issuer.clone(),
+            audience: options.audience.clone(),
+            principal_claim: options.principal_claim.clone(),
+        })
+    }
+
+    fn decoding_key(&self, kid: Option<&str>) -> Option<&DecodingKey> {
+        match kid {
+            Some(kid) => self
+                .keys
+                .iter()
+                .find(|(key_id, _)| key_id.as_deref() == Some(kid))
+                .map(|(_, key)| key),
+            // Without key id, we can only pick the key if there's no ambiguity
+            None if self.keys.len() == 1 => Some(&self.keys[0].1),
+            None => None,
+        }
+    }
+}
+
+impl Authenticator for JwtAuthenticator {
+    fn authenticate(&self, headers: &HeaderMap) -> Result<Option<Principal>, AuthenticationError> {
+        let Some(token) = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix(BEARER_PREFIX))
+        else {
+            return Ok(None);
+        };
+
+        let token_header = jsonwebtoken::decode_header(token)
+            .map_err(|e| AuthenticationError::InvalidToken(e.to_string()))?;
+        let key = self
+            .decoding_key(token_header.kid.as_deref())
+            .ok_or_else(|| AuthenticationError::InvalidToken("unknown signing key".to_owned()))?;
+        if !key.family().algorithms().contains(&token_header.alg) {
+            return Err(AuthenticationError::InvalidToken(format!(
+                "algorithm {:?} does not match the signing key",
+                token_header.alg
+            )));
+        }
+
+        let mut validation = Validation::new(token_header.alg);
+        if let Some(issuer) = &self.issuer {
+            validation.set_issuer(&[issuer]);
+        }
+        match &self.audience {
+            Some(audience) => validation.set_audience(&[audience]),
+            None => validation.validate_aud = false,
+        }
+
+        let claims = jsonwebtoken::decode::<Map<String, Value>>(token, key, &validation)
+            .map_err(|e| AuthenticationError::InvalidToken(e.to_string()))?
+            .claims;
+
+        match claims.get(&self.principal_claim) {
+            Some(Value::String(principal)) => Ok(Some(Principal::new(principal.as_str()))),
+            _ => Err(AuthenticationError::InvalidToken(format!(
+                "missing string claim '{}'",
+                self.principal_claim
+            ))),
+        }
+    }
+}
+
+fn read_jwks(path: &Path) -> Result<JwkSet, JwksReadError> {
+    let contents = std::fs::read(path).map_err(|e| JwksReadError::Io(path.to_path_buf(), e))?;
+    serde_json::from_slice(&contents).map_err(|e| JwksReadError::Parse(path.to_path_buf(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::SystemTime;
+
+    use http::HeaderValue;
+    use jsonwebtoken::{EncodingKey, Header};
+    use serde_json::json;
+
+    const SECRET: &[u8] = b"super-secret-signing-key";
+    // base64url encoding of SECRET
+    const SECRET_JWK: &str = "c3VwZXItc2VjcmV0LXNpZ25pbmcta2V5";
+
+    fn write_jwks() -> tempfile::NamedTempFile {
+        let jwks = json!({
+            "keys": [{
+                "kty": "oct",
+                "kid": "test-key",
+                "alg": "HS256",
+                "k": SECRET_JWK,
+            }]
+        });
+        let file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(file.path(), serde_json::to_vec(&jwks).unwrap()).unwrap();
+        file
+    }
+
+    fn token(kid: &str, claims: Value) -> HeaderMap {
+        let mut jwt_header = Header::new(jsonwebtoken::Algorithm::HS256);
+        jwt_header.kid = Some(kid.to_owned());
+        let token =
+            jsonwebtoken::encode(&jwt_header, &claims, &EncodingKey::from_secret(SECRET)).unwrap();
+
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::try_from(format!("Bearer {token}")).unwrap(),
+        );
+        headers
+    }
+
+    fn now() -> u64 {
+        SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+
+    #[test]
+    fn verify_token() {
+        let jwks = write_jwks();
+        let authenticator = JwtAuthenticator::from_options(&IngressJwtOptions {
+            jwks_file: jwks.path().to_path_buf(),
+            issuer: Some("https://issuer.example".to_owned()),
+            audience: None,
+            principal_claim: "sub".to_owned(),
+        })
+        .unwrap();
+
+        assert!(
+            authenticator
+                .authenticate(&HeaderMap::new())
+                .unwrap()
+                .is_none()
+        );
+
+        let headers = token(
+            "test-key",
+            json!({"sub": "ops", "iss": "https://issuer.example", "exp": now() + 60}),
+        );
+        assert_eq!(
+            authenticator.authenticate(&headers).unwrap(),
+            Some(Principal::new("ops"))
+        );
+
+        // Wrong issuer
+        let headers = token(
+            "test-key",
+            json!({"sub": "ops", "iss": "https://other.example", "exp": now() + 60}),
+        );
+        assert!(authenticator.authenticate(&headers).is_err());
+
+        // Expired
+        let headers = token(
+            "test-key",
+            json!({"sub": "ops", "iss": "https://issuer.example", "exp": now() - 3600}),
+        );
+        assert!(authenticator.authenticate(&headers).is_err());
+
+        // Unknown key id
+        let headers = token(
+            "other-key",
+            json!({"sub": "ops", "iss": "https://issuer.example", "exp": now() + 60}),
+        );
+        assert!(authenticator.authenticate(&headers).is_err());
+    }
+}
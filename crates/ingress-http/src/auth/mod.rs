@@ -0,0 +1,181 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+mod api_key;
+mod jwt;
+
+pub use api_key::ApiKeyAuthenticator;
+pub use jwt::{JwksReadError, JwtAuthenticator};
+
+use std::fmt;
+use std::sync::Arc;
+
+use http::HeaderMap;
+
+use restate_types::config::IngressAuthOptions;
+
+/// Service/handler metadata key holding the comma separated list of principals allowed to
+/// invoke the service/handler through the ingress. `*` allows every authenticated principal.
+pub const ALLOWED_PRINCIPALS_METADATA_KEY: &str = "restate.ingress.allowed-principals";
+
+/// Authenticated identity of the caller of an ingress request.
+///
+/// The authentication layer stores it in the request extensions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Principal(String);
+
+impl Principal {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self(name.into())
+    }
+
+    pub fn name(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Principal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AuthenticationError {
+    #[error("missing credentials")]
+    MissingCredentials,
+    #[error("invalid API key")]
+    InvalidApiKey,
+    #[error("invalid token: {0}")]
+    InvalidToken(String),
+}
+
+/// Authenticates the requests received by the ingress.
+pub trait Authenticator: Send + Sync + 'static {
+    /// Returns `Ok(None)` if the request carries no credentials this authenticator understands,
+    /// so that other authenticators can be tried.
+    fn authenticate(&self, headers: &HeaderMap) -> Result<Option<Principal>, AuthenticationError>;
+}
+
+/// Tries each authenticator in order, the first one recognizing the credentials decides.
+struct AuthenticatorChain(Vec<Box<dyn Authenticator>>);
+
+impl Authenticator for AuthenticatorChain {
+    fn authenticate(&self, headers: &HeaderMap) -> Result<Option<Principal>, AuthenticationError> {
+        for authenticator in &self.0 {
+            if let Some(principal) = authenticator.authenticate(headers)? {
+                return Ok(Some(principal));
+            }
+        }
+        Ok(None)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AuthenticatorBuildError {
+    #[error("ingress authentication is enabled, but neither api keys nor jwt are configured")]
+    NoMechanism,
+    #[error(transparent)]
+    Jwks(#[from] JwksReadError),
+}
+
+/// Builds the authenticator described by the given options.
+pub fn authenticator_from_options(
+    options: &IngressAuthOptions,
+) -> Result<Arc<dyn Authenticator>, AuthenticatorBuildError> {
+    let mut authenticators: Vec<Box<dyn Authenticator>> = Vec::with_capacity(2);
+    if !options.api_keys.is_empty() {
+        authenticators.push(Box::new(ApiKeyAuthenticator::new(
+            options
+                .api_keys
+                .iter()
+                .map(|api_key| (Principal::new(&api_key.principal), api_key.key.clone())),
+        )));
+    }
+    if let Some(jwt_options) = &options.jwt {
+        authenticators.push(Box::new(JwtAuthenticator::from_options(jwt_options)?));
+    }
+
+    if authenticators.is_empty() {
+        return Err(AuthenticatorBuildError::NoMechanism);
+    }
+    Ok(Arc::new(AuthenticatorChain(authenticators)))
+}
+
+/// Checks whether the principal is contained in the given allow list.
+pub(crate) fn is_allowed(allowed_principals: &str, principal: &Principal) -> bool {
+    allowed_principals
+        .split(',')
+        .map(str::trim)
+        .any(|allowed| allowed == "*" || allowed == principal.name())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use http::HeaderValue;
+
+    use restate_types::config::IngressApiKey;
+
+    #[test]
+    fn chain_uses_first_matching_authenticator() {
+        let authenticator = authenticator_from_options(&IngressAuthOptions {
+            api_keys: vec![
+                IngressApiKey {
+                    principal: "ops".to_owned(),
+                    key: "ops-secret".to_owned(),
+                },
+                IngressApiKey {
+                    principal: "billing".to_owned(),
+                    key: "billing-secret".to_owned(),
+                },
+            ],
+            jwt: None,
+        })
+        .unwrap();
+
+        let mut headers = HeaderMap::new();
+        assert!(authenticator.authenticate(&headers).unwrap().is_none());
+
+        headers.insert(
+            api_key::API_KEY_HEADER,
+            HeaderValue::from_static("billing-secret"),
+        );
+        assert_eq!(
+            authenticator.authenticate(&headers).unwrap(),
+            Some(Principal::new("billing"))
+        );
+
+        headers.insert(api_key::API_KEY_HEADER, HeaderValue::from_static("nope"));
+        assert!(matches!(
+            authenticator.authenticate(&headers),
+            Err(AuthenticationError::InvalidApiKey)
+        ));
+    }
+
+    #[test]
+    fn no_mechanism_is_rejected() {
+        assert!(matches!(
+            authenticator_from_options(&IngressAuthOptions::default()),
+            Err(AuthenticatorBuildError::NoMechanism)
+        ));
+    }
+
+    #[test]
+    fn allow_list() {
+        let ops = Principal::new("ops");
+        assert!(is_allowed("ops", &ops));
+        assert!(is_allowed("billing, ops", &ops));
+        assert!(is_allowed("*", &ops));
+        assert!(!is_allowed("billing", &ops));
+        assert!(!is_allowed("", &ops));
+    }
+}
@@ -0,0 +1,95 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use http::Request;
+
+use restate_types::schema::service::ServiceMetadataResolver;
+
+use super::{Handler, HandlerError};
+use crate::auth::{ALLOWED_PRINCIPALS_METADATA_KEY, Principal, is_allowed};
+
+impl<Schemas, Dispatcher> Handler<Schemas, Dispatcher>
+where
+    Schemas: ServiceMetadataResolver,
+{
+    /// Checks the authenticated principal against the allow list of the service/handler.
+    ///
+    /// The handler allow list takes precedence over the service one. When neither is set, or
+    /// when the ingress has no authentication configured, every request is allowed.
+    pub(crate) fn authorize<B>(
+        &self,
+        req: &Request<B>,
+        service_name: &str,
+        handler_name: Option<&str>,
+    ) -> Result<(), HandlerError> {
+        self.authorize_principal(
+            req.extensions().get::<Principal>(),
+            service_name,
+            handler_name,
+        )
+    }
+
+    /// Like [`Self::authorize`], for requests whose principal was taken out of the request.
+    pub(crate) fn authorize_principal(
+        &self,
+        principal: Option<&Principal>,
+        service_name: &str,
+        handler_name: Option<&str>,
+    ) -> Result<(), HandlerError> {
+        let Some(principal) = principal else {
+            return Ok(());
+        };
+        // Unknown services are reported by the request handlers themselves
+        let Some(service) = self.schemas.pinned().resolve_latest_service(service_name) else {
+            return Ok(());
+        };
+
+        let allowed_principals = handler_name
+            .and_then(|handler_name| service.handlers.get(handler_name))
+            .and_then(|handler| handler.metadata.get(ALLOWED_PRINCIPALS_METADATA_KEY))
+            .or_else(|| service.metadata.get(ALLOWED_PRINCIPALS_METADATA_KEY));
+
+        match allowed_principals {
+            Some(allowed_principals) if !is_allowed(allowed_principals, principal) => {
+                Err(HandlerError::Forbidden(principal.to_string()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks the principal for an invocation whose target is unknown, e.g. the failure of an
+    /// invocation queried by id. The principal must be allowed by every allow list, since the
+    /// invocation could target any service.
+    pub(crate) fn authorize_unknown_target(
+        &self,
+        principal: Option<&Principal>,
+    ) -> Result<(), HandlerError> {
+        let Some(principal) = principal else {
+            return Ok(());
+        };
+
+        let allowed =
+            self.schemas.pinned().list_services().iter().all(|service| {
+                service
+                    .metadata
+                    .get(ALLOWED_PRINCIPALS_METADATA_KEY)
+                    .into_iter()
+                    .chain(service.handlers.values().filter_map(|handler| {
+                        handler.metadata.get(ALLOWED_PRINCIPALS_METADATA_KEY)
+                    }))
+                    .all(|allowed_principals| is_allowed(allowed_principals, principal))
+            });
+        if allowed {
+            Ok(())
+        } else {
+            Err(HandlerError::Forbidden(principal.to_string()))
+        }
+    }
+}
@@ -21,6 +21,7 @@ use restate_util_string::RestrictedValueError;
 
 use super::APPLICATION_JSON;
 use crate::RequestDispatcherError;
+use crate::auth::AuthenticationError;
 
 #[derive(Debug, thiserror::Error)]
 pub(crate) enum HandlerError {
@@ -112,6 +113,10 @@ pub(crate) enum HandlerError {
     DispatcherError(#[from] RequestDispatcherError),
     #[error("bad scope value: {0}")]
     BadScopeValue(RestrictedValueError),
+    #[error("unauthenticated: {0}")]
+    Unauthenticated(AuthenticationError),
+    #[error("the principal '{0}' is not allowed to invoke this handler")]
+    Forbidden(String),
 }
 
 // IMPORTANT! If you touch this, please update crates/types/src/schema/openapi.rs too
@@ -169,6 +174,8 @@ impl HandlerError {
                 StatusCode::PAYLOAD_TOO_LARGE
             }
             HandlerError::Body(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            HandlerError::Unauthenticated(_) => StatusCode::UNAUTHORIZED,
+            HandlerError::Forbidden(_) => StatusCode::FORBIDDEN,
             HandlerError::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
             HandlerError::MethodNotAllowed => StatusCode::METHOD_NOT_ALLOWED,
             HandlerError::NotImplemented => StatusCode::NOT_IMPLEMENTED,
@@ -0,0 +1,1047 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! gRPC and gRPC-Web support of the ingress.
+//!
+//! gRPC requests to `/{service}/{method}` are translated to requests to the `/{service}/{handler}`
+//! path of the ingress API, and their responses back to gRPC responses. Handler methods are
+//! unary, since a Restate handler takes a single input and returns a single output.
+//!
+//! The reserved `dev.restate.Invocations` service addresses the invocation named by the
+//! `x-restate-invocation-id` metadata: the server streaming `Attach` method sends its output once
+//! the invocation completes, and the unary `GetOutput` method returns the output of a completed
+//! invocation.
+//!
+//! Protobuf messages are converted from and to the JSON input and output of the handlers with the
+//! descriptor set registered in the service metadata, or else with the configured
+//! `ingress.grpc.descriptor_set`.
+
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::fmt;
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, ready};
+
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use futures::FutureExt;
+use futures::future::BoxFuture;
+use http::{
+    HeaderMap, HeaderName, HeaderValue, Method, Request, Response, StatusCode, Uri, header,
+};
+use http_body::Frame;
+use http_body_util::{BodyExt, Full};
+use prost::Message;
+use prost_reflect::{DescriptorPool, DynamicMessage, MethodDescriptor};
+use tracing::{debug, trace, warn};
+
+use restate_types::ServiceName;
+use restate_types::config::IngressGrpcOptions;
+use restate_types::errors::GenericError;
+use restate_types::identifiers::{InvocationId, ServiceRevision};
+use restate_types::invocation::InvocationQuery;
+use restate_types::invocation::client::{InvocationOutput, InvocationOutputResponse};
+use restate_types::schema::invocation_target::InvocationTargetResolver;
+use restate_types::schema::service::{ServiceMetadata, ServiceMetadataResolver};
+
+use super::path_parsing::{InvokeType, ServiceRequestType, TargetType};
+use super::responses::{IDEMPOTENCY_EXPIRES, X_RESTATE_ID};
+use super::{APPLICATION_JSON, Handler, HandlerError};
+use crate::RequestDispatcher;
+use crate::auth::Principal;
+
+/// Service metadata holding the base64 encoded Protobuf file descriptor set describing the gRPC
+/// service of a Restate service, as generated by `protoc --include_imports --descriptor_set_out`.
+pub const GRPC_DESCRIPTOR_SET_METADATA_KEY: &str = "restate.grpc.descriptor-set";
+
+/// gRPC service attaching to invocations and reading their output.
+const INVOCATIONS_SERVICE: &str = "dev.restate.Invocations";
+/// gRPC metadata holding the key of the Virtual Object or Workflow to invoke.
+const X_RESTATE_KEY: HeaderName = HeaderName::from_static("x-restate-key");
+/// gRPC metadata holding the id of the invocation addressed by the `dev.restate.Invocations`
+/// methods.
+const X_RESTATE_INVOCATION_ID: HeaderName = HeaderName::from_static("x-restate-invocation-id");
+/// gRPC metadata turning the request into a send, executed after the given delay.
+const DELAY: HeaderName = HeaderName::from_static("delay");
+const GRPC_STATUS: HeaderName = HeaderName::from_static("grpc-status");
+const GRPC_MESSAGE: HeaderName = HeaderName::from_static("grpc-message");
+
+/// Length of the prefix of each gRPC message: the compression flag and the message length.
+const MESSAGE_PREFIX_LEN: usize = 5;
+/// Flag of the gRPC-Web frame carrying the trailers.
+const GRPC_WEB_TRAILERS_FLAG: u8 = 0x80;
+
+#[derive(Debug, thiserror::Error)]
+pub enum GrpcDescriptorSetError {
+    #[error("cannot read descriptor set file '{0}': {1}")]
+    Read(String, #[source] std::io::Error),
+    #[error("'{0}' is not a Protobuf file descriptor set: {1}")]
+    Decode(String, #[source] prost_reflect::DescriptorError),
+    #[error("the registered descriptor set is not base64 encoded: {0}")]
+    RegisteredEncoding(#[source] base64::DecodeError),
+    #[error("the registered descriptor set is not a Protobuf file descriptor set: {0}")]
+    RegisteredDecode(#[source] prost_reflect::DescriptorError),
+}
+
+/// gRPC configuration of the ingress.
+#[derive(Clone, Default)]
+pub struct GrpcIngress {
+    descriptors: Option<DescriptorPool>,
+    /// Descriptor sets registered in the service metadata, decoded once per service revision.
+    registered: Arc<Mutex<HashMap<String, (ServiceRevision, Option<DescriptorPool>)>>>,
+}
+
+impl GrpcIngress {
+    pub fn from_options(options: &IngressGrpcOptions) -> Result<Self, GrpcDescriptorSetError> {
+        let descriptors = options
+            .descriptor_set
+            .as_deref()
+            .map(read_descriptor_set)
+            .transpose()?;
+        Ok(Self {
+            descriptors,
+            registered: Default::default(),
+        })
+    }
+
+    /// Returns the descriptor of the method of the handler. The descriptor set registered in the
+    /// service metadata takes precedence over the configured one.
+    fn method(&self, service: &ServiceMetadata, handler: &str) -> Option<MethodDescriptor> {
+        self.registered_descriptors(service)
+            .and_then(|descriptors| find_method(&descriptors, &service.name, handler))
+            .or_else(|| find_method(self.descriptors.as_ref()?, &service.name, handler))
+    }
+
+    fn registered_descriptors(&self, service: &ServiceMetadata) -> Option<DescriptorPool> {
+        let encoded = service.metadata.get(GRPC_DESCRIPTOR_SET_METADATA_KEY)?;
+        let mut registered = self.registered.lock().expect("lock is not poisoned");
+        if let Some((revision, descriptors)) = registered.get(&service.name)
+            && *revision == service.revision
+        {
+            return descriptors.clone();
+        }
+
+        let descriptors = decode_registered_descriptor_set(encoded)
+            .inspect_err(|err| {
+                warn!(
+                    "Ignoring the gRPC descriptor set of service '{}': {err}",
+                    service.name
+                )
+            })
+            .ok();
+        registered.insert(
+            service.name.clone(),
+            (service.revision, descriptors.clone()),
+        );
+        descriptors
+    }
+}
+
+/// Finds the method of the handler in the gRPC service named after the Restate service, with
+/// either its fully qualified name or its simple name.
+fn find_method(
+    descriptors: &DescriptorPool,
+    service_name: &str,
+    handler_name: &str,
+) -> Option<MethodDescriptor> {
+    descriptors
+        .services()
+        .find(|service| service.full_name() == service_name)
+        .or_else(|| {
+            descriptors
+                .services()
+                .find(|service| service.name() == service_name)
+        })?
+        .methods()
+        .find(|method| {
+            method.name() == handler_name || lower_first_char(method.name()) == handler_name
+        })
+}
+
+fn decode_registered_descriptor_set(
+    encoded: &str,
+) -> Result<DescriptorPool, GrpcDescriptorSetError> {
+    let descriptor_set = BASE64_STANDARD
+        .decode(encoded)
+        .map_err(GrpcDescriptorSetError::RegisteredEncoding)?;
+    DescriptorPool::decode(descriptor_set.as_slice())
+        .map_err(GrpcDescriptorSetError::RegisteredDecode)
+}
+
+fn read_descriptor_set(path: &Path) -> Result<DescriptorPool, GrpcDescriptorSetError> {
+    let file = path.display().to_string();
+    let descriptor_set =
+        std::fs::read(path).map_err(|e| GrpcDescriptorSetError::Read(file.clone(), e))?;
+    DescriptorPool::decode(descriptor_set.as_slice())
+        .map_err(|e| GrpcDescriptorSetError::Decode(file, e))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Protocol {
+    Grpc,
+    GrpcWeb,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Encoding {
+    Proto,
+    Json,
+}
+
+/// Content type of a gRPC request, echoed in the response.
+#[derive(Debug, Clone)]
+pub(crate) struct GrpcContentType {
+    protocol: Protocol,
+    encoding: Encoding,
+    value: HeaderValue,
+}
+
+impl GrpcContentType {
+    /// Returns `None` if the request is not a gRPC request.
+    pub(crate) fn from_headers(headers: &HeaderMap) -> Option<Result<Self, GrpcStatus>> {
+        let value = headers.get(header::CONTENT_TYPE)?;
+        let content_type = value.to_str().ok()?;
+        let essence = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+
+        let (protocol, subtype) =
+            if let Some(subtype) = essence.strip_prefix("application/grpc-web") {
+                (Protocol::GrpcWeb, subtype)
+            } else if let Some(subtype) = essence.strip_prefix("application/grpc") {
+                (Protocol::Grpc, subtype)
+            } else {
+                return None;
+            };
+
+        let encoding = match subtype {
+            "" | "+proto" => Encoding::Proto,
+            "+json" => Encoding::Json,
+            _ => {
+                return Some(Err(GrpcStatus::new(
+                    GrpcCode::Unimplemented,
+                    format!("unsupported content type '{content_type}'"),
+                )));
+            }
+        };
+
+        Some(Ok(Self {
+            protocol,
+            encoding,
+            value: value.clone(),
+        }))
+    }
+}
+
+/// gRPC status codes, see <https://grpc.github.io/grpc/core/md_doc_statuscodes.html>.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum GrpcCode {
+    Ok = 0,
+    Cancelled = 1,
+    Unknown = 2,
+    InvalidArgument = 3,
+    DeadlineExceeded = 4,
+    NotFound = 5,
+    PermissionDenied = 7,
+    ResourceExhausted = 8,
+    FailedPrecondition = 9,
+    Aborted = 10,
+    Unimplemented = 12,
+    Internal = 13,
+    Unavailable = 14,
+    Unauthenticated = 16,
+}
+
+impl GrpcCode {
+    fn from_http_status(status: StatusCode) -> Self {
+        match status.as_u16() {
+            200..=299 => GrpcCode::Ok,
+            400 => GrpcCode::InvalidArgument,
+            401 => GrpcCode::Unauthenticated,
+            403 => GrpcCode::PermissionDenied,
+            404 => GrpcCode::NotFound,
+            405 | 501 => GrpcCode::Unimplemented,
+            409 => GrpcCode::Aborted,
+            413 | 429 => GrpcCode::ResourceExhausted,
+            // The invocation exists but has not completed yet
+            470 => GrpcCode::FailedPrecondition,
+            499 => GrpcCode::Cancelled,
+            503 => GrpcCode::Unavailable,
+            504 => GrpcCode::DeadlineExceeded,
+            _ => GrpcCode::Unknown,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct GrpcStatus {
+    code: GrpcCode,
+    message: String,
+}
+
+impl GrpcStatus {
+    fn new(code: GrpcCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    fn ok() -> Self {
+        Self::new(GrpcCode::Ok, "")
+    }
+
+    fn from_handler_error(error: HandlerError) -> Self {
+        let response = error.into_response::<Full<Bytes>>();
+        let status = response.status();
+        let body = response
+            .into_body()
+            .collect()
+            .now_or_never()
+            .map(|collected| {
+                collected
+                    .unwrap_or_else(|e: Infallible| match e {})
+                    .to_bytes()
+            })
+            .unwrap_or_default();
+        Self::from_error_response(status, &body)
+    }
+
+    /// Extracts the message of the JSON error responses of the ingress.
+    fn from_error_response(status: StatusCode, body: &[u8]) -> Self {
+        let message = serde_json::from_slice::<serde_json::Value>(body)
+            .ok()
+            .and_then(|value| value.get("message")?.as_str().map(ToOwned::to_owned))
+            .unwrap_or_else(|| String::from_utf8_lossy(body).into_owned());
+        Self::new(GrpcCode::from_http_status(status), message)
+    }
+
+    /// Returns a Trailers-Only response reporting this status.
+    pub(crate) fn into_response(self) -> Response<GrpcBody> {
+        let content_type = GrpcContentType {
+            protocol: Protocol::Grpc,
+            encoding: Encoding::Proto,
+            value: HeaderValue::from_static("application/grpc"),
+        };
+        let mut headers = HeaderMap::new();
+        self.append_to(&mut headers);
+        grpc_response(&content_type, headers, GrpcBody::default())
+    }
+
+    fn log_failure(&self) {
+        debug!(
+            grpc.status = self.code as u16,
+            "Complete gRPC request with a failure: {}", self.message
+        );
+    }
+
+    fn append_to(&self, headers: &mut HeaderMap) {
+        headers.insert(GRPC_STATUS, HeaderValue::from(self.code as u16));
+        if !self.message.is_empty() {
+            // The grpc-message value is percent-encoded
+            if let Ok(message) = HeaderValue::from_str(&urlencoding::encode(&self.message)) {
+                headers.insert(GRPC_MESSAGE, message);
+            }
+        }
+    }
+}
+
+/// Body of the gRPC responses: a single message followed by the trailers.
+#[derive(Default)]
+pub(crate) struct GrpcBody {
+    message: Option<Bytes>,
+    trailers: Option<HeaderMap>,
+    /// Resolves to the body of server streaming responses, whose headers are sent before the
+    /// message is available.
+    pending: Option<BoxFuture<'static, GrpcBody>>,
+}
+
+impl GrpcBody {
+    fn pending(body: impl Future<Output = GrpcBody> + Send + 'static) -> Self {
+        Self {
+            pending: Some(body.boxed()),
+            ..Default::default()
+        }
+    }
+}
+
+impl fmt::Debug for GrpcBody {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GrpcBody")
+            .field("message", &self.message)
+            .field("trailers", &self.trailers)
+            .field("pending", &self.pending.is_some())
+            .finish()
+    }
+}
+
+impl http_body::Body for GrpcBody {
+    type Data = Bytes;
+    type Error = Infallible;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let this = self.get_mut();
+        if let Some(pending) = &mut this.pending {
+            *this = ready!(pending.poll_unpin(cx));
+        }
+        if let Some(message) = this.message.take() {
+            return Poll::Ready(Some(Ok(Frame::data(message))));
+        }
+        Poll::Ready(this.trailers.take().map(|t| Ok(Frame::trailers(t))))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.pending.is_none() && self.message.is_none() && self.trailers.is_none()
+    }
+}
+
+/// Reply of a gRPC method.
+enum GrpcReply {
+    /// Message of a unary method.
+    Unary(Bytes),
+    /// Message of a server streaming method, sent once available.
+    Streaming(BoxFuture<'static, Result<Bytes, GrpcStatus>>),
+}
+
+impl<Schemas, Dispatcher> Handler<Schemas, Dispatcher>
+where
+    Schemas: ServiceMetadataResolver + InvocationTargetResolver + Clone + Send + Sync + 'static,
+    Dispatcher: RequestDispatcher + Clone + Send + Sync + 'static,
+{
+    pub(crate) async fn handle_grpc<B>(
+        self,
+        req: Request<B>,
+        content_type: GrpcContentType,
+    ) -> Response<GrpcBody>
+    where
+        B: http_body::Body,
+        <B as http_body::Body>::Error: Into<GenericError>,
+    {
+        let mut response_headers = HeaderMap::new();
+        let invocations_method = match parse_grpc_path(req.uri()) {
+            Ok((INVOCATIONS_SERVICE, method)) => Some(method.to_owned()),
+            _ => None,
+        };
+        let reply = match invocations_method {
+            Some(method) => {
+                self.handle_grpc_invocation(req, &content_type, &method)
+                    .await
+            }
+            None => self
+                .handle_grpc_call(req, &content_type, &mut response_headers)
+                .await
+                .map(GrpcReply::Unary),
+        };
+
+        match reply {
+            Ok(GrpcReply::Unary(message)) => {
+                let body = grpc_body(content_type.protocol, Ok(message));
+                grpc_response(&content_type, response_headers, body)
+            }
+            Ok(GrpcReply::Streaming(message)) => {
+                let protocol = content_type.protocol;
+                let body = GrpcBody::pending(message.map(move |res| grpc_body(protocol, res)));
+                grpc_response(&content_type, response_headers, body)
+            }
+            Err(status) => {
+                status.log_failure();
+                // Trailers-Only response
+                status.append_to(&mut response_headers);
+                grpc_response(&content_type, response_headers, GrpcBody::default())
+            }
+        }
+    }
+
+    /// Returns the response message.
+    async fn handle_grpc_call<B>(
+        self,
+        req: Request<B>,
+        content_type: &GrpcContentType,
+        response_headers: &mut HeaderMap,
+    ) -> Result<Bytes, GrpcStatus>
+    where
+        B: http_body::Body,
+        <B as http_body::Body>::Error: Into<GenericError>,
+    {
+        let grpc = self.grpc_ingress()?;
+        if req.method() != Method::POST {
+            return Err(GrpcStatus::from_handler_error(
+                HandlerError::MethodNotAllowed,
+            ));
+        }
+
+        let (grpc_service, grpc_method) = parse_grpc_path(req.uri())?;
+        let (service_name, handler_name) = self
+            .resolve_grpc_target(grpc_service, grpc_method)
+            .ok_or_else(|| {
+                GrpcStatus::new(
+                    GrpcCode::Unimplemented,
+                    format!("unknown method '{grpc_method}' of service '{grpc_service}'"),
+                )
+            })?;
+        trace!(
+            "Resolved gRPC method /{grpc_service}/{grpc_method} to {service_name}/{handler_name}"
+        );
+
+        self.authorize(&req, &service_name, Some(&handler_name))
+            .map_err(GrpcStatus::from_handler_error)?;
+
+        let service = self
+            .schemas
+            .pinned()
+            .resolve_latest_service(&service_name)
+            .ok_or_else(|| {
+                GrpcStatus::from_handler_error(HandlerError::ServiceNotFound(service_name.clone()))
+            })?;
+        let method_descriptor = match content_type.encoding {
+            Encoding::Proto => grpc.method(&service, &handler_name),
+            Encoding::Json => None,
+        };
+        let target = if service.ty.is_keyed() {
+            let key = req
+                .headers()
+                .get(X_RESTATE_KEY)
+                .ok_or_else(|| {
+                    GrpcStatus::new(
+                        GrpcCode::InvalidArgument,
+                        format!("missing '{X_RESTATE_KEY}' metadata, required to invoke '{service_name}'"),
+                    )
+                })?
+                .to_str()
+                .map_err(|e| GrpcStatus::from_handler_error(HandlerError::BadHeader(X_RESTATE_KEY, e)))?
+                .to_owned();
+            TargetType::Keyed { key }
+        } else {
+            TargetType::Unkeyed
+        };
+
+        let (mut parts, body) = req.into_parts();
+        let body = body
+            .collect()
+            .await
+            .map_err(|e| GrpcStatus::from_handler_error(HandlerError::Body(e.into())))?
+            .to_bytes();
+        let message = decode_message(body)?;
+
+        // Translate the gRPC request to a request of the ingress API
+        let (input, input_content_type) = match (content_type.encoding, &method_descriptor) {
+            (Encoding::Json, _) => (message, Some(APPLICATION_JSON)),
+            (Encoding::Proto, Some(method)) => transcode_input(method, message)?,
+            // Empty messages are passed as an empty input
+            (Encoding::Proto, None) if message.is_empty() => (message, None),
+            (Encoding::Proto, None) => {
+                return Err(GrpcStatus::new(
+                    GrpcCode::FailedPrecondition,
+                    format!(
+                        "cannot convert the request message of '{service_name}/{handler_name}', \
+                        no Protobuf descriptor set describes it: register the descriptor set of \
+                        the service in the '{GRPC_DESCRIPTOR_SET_METADATA_KEY}' service metadata, \
+                        or use the application/grpc+json content type"
+                    ),
+                ));
+            }
+        };
+
+        let delay = parts
+            .headers
+            .get(DELAY)
+            .map(|delay| {
+                delay
+                    .to_str()
+                    .map_err(|e| GrpcStatus::from_handler_error(HandlerError::BadHeader(DELAY, e)))
+            })
+            .transpose()?
+            .map(ToOwned::to_owned);
+        let invoke_ty = if delay.is_some() {
+            InvokeType::Send
+        } else {
+            InvokeType::Call
+        };
+        parts.uri = ingress_uri(&parts.uri, delay.as_deref());
+        let grpc_metadata: Vec<_> = parts
+            .headers
+            .keys()
+            .filter(|name| is_grpc_metadata(name))
+            .cloned()
+            .collect();
+        for name in grpc_metadata {
+            parts.headers.remove(name);
+        }
+        match input_content_type {
+            Some(input_content_type) => {
+                parts
+                    .headers
+                    .insert(header::CONTENT_TYPE, input_content_type);
+            }
+            None => {
+                parts.headers.remove(header::CONTENT_TYPE);
+            }
+        }
+
+        let response = self
+            .handle_service_request(
+                Request::from_parts(parts, Full::new(input)),
+                ServiceRequestType {
+                    name: ServiceName::new(&service_name),
+                    handler: handler_name,
+                    target,
+                    invoke_ty,
+                    scope: None,
+                },
+            )
+            .await
+            .unwrap_or_else(|e| e.into_response());
+
+        grpc_output(
+            response,
+            content_type.encoding,
+            method_descriptor.as_ref(),
+            response_headers,
+        )
+        .await
+    }
+
+    /// Handles the methods of the `dev.restate.Invocations` service. Their request message is
+    /// `google.protobuf.Empty`, and their response message is the output of the invocation.
+    async fn handle_grpc_invocation<B>(
+        self,
+        req: Request<B>,
+        content_type: &GrpcContentType,
+        method: &str,
+    ) -> Result<GrpcReply, GrpcStatus> {
+        let grpc = self.grpc_ingress()?;
+        if req.method() != Method::POST {
+            return Err(GrpcStatus::from_handler_error(
+                HandlerError::MethodNotAllowed,
+            ));
+        }
+
+        let invocation_id = req
+            .headers()
+            .get(X_RESTATE_INVOCATION_ID)
+            .ok_or_else(|| {
+                GrpcStatus::new(
+                    GrpcCode::InvalidArgument,
+                    format!("missing '{X_RESTATE_INVOCATION_ID}' metadata"),
+                )
+            })?
+            .to_str()
+            .map_err(|e| {
+                GrpcStatus::from_handler_error(HandlerError::BadHeader(X_RESTATE_INVOCATION_ID, e))
+            })?;
+        let invocation_query =
+            InvocationQuery::Invocation(invocation_id.parse::<InvocationId>().map_err(|e| {
+                GrpcStatus::from_handler_error(HandlerError::BadInvocationId(
+                    invocation_id.to_owned(),
+                    e,
+                ))
+            })?);
+        let principal = req.extensions().get::<Principal>().cloned();
+        let encoding = content_type.encoding;
+
+        match method {
+            "Attach" => Ok(GrpcReply::Streaming(
+                async move {
+                    let output = self
+                        .attach_invocation_output(principal, invocation_query)
+                        .await;
+                    self.grpc_invocation_output(&grpc, encoding, output).await
+                }
+                .boxed(),
+            )),
+            "GetOutput" => {
+                let output = self
+                    .read_invocation_output(principal, invocation_query)
+                    .await;
+                self.grpc_invocation_output(&grpc, encoding, output)
+                    .await
+                    .map(GrpcReply::Unary)
+            }
+            _ => Err(GrpcStatus::new(
+                GrpcCode::Unimplemented,
+                format!("unknown method '{method}' of service '{INVOCATIONS_SERVICE}'"),
+            )),
+        }
+    }
+
+    /// Converts the output of an invocation to the response message, with the descriptor of the
+    /// method of the invoked handler.
+    async fn grpc_invocation_output(
+        &self,
+        grpc: &GrpcIngress,
+        encoding: Encoding,
+        output: Result<InvocationOutput, HandlerError>,
+    ) -> Result<Bytes, GrpcStatus> {
+        let output = output.map_err(GrpcStatus::from_handler_error)?;
+        let method_descriptor = match (&output.response, encoding) {
+            (InvocationOutputResponse::Success(invocation_target, _), Encoding::Proto) => self
+                .schemas
+                .pinned()
+                .resolve_latest_service(invocation_target.service_name())
+                .and_then(|service| grpc.method(&service, invocation_target.handler_name())),
+            _ => None,
+        };
+        let response = self
+            .reply_with_invocation_output(output)
+            .unwrap_or_else(|e| e.into_response());
+
+        grpc_output(
+            response,
+            encoding,
+            method_descriptor.as_ref(),
+            &mut HeaderMap::new(),
+        )
+        .await
+    }
+
+    fn grpc_ingress(&self) -> Result<GrpcIngress, GrpcStatus> {
+        self.grpc.clone().ok_or_else(|| {
+            GrpcStatus::new(
+                GrpcCode::Unimplemented,
+                "gRPC is not enabled on this ingress, configure ingress.grpc to enable it",
+            )
+        })
+    }
+
+    /// Resolves the service and handler invoked by a gRPC method. The service can be named with
+    /// its fully qualified name or its simple name, and the handler with the name of the method
+    /// or the name of the method starting with a lowercase letter.
+    fn resolve_grpc_target(
+        &self,
+        grpc_service: &str,
+        grpc_method: &str,
+    ) -> Option<(String, String)> {
+        let schemas = self.schemas.pinned();
+        let simple_service = grpc_service.rsplit('.').next().unwrap_or(grpc_service);
+        let lower_camel_method = lower_first_char(grpc_method);
+
+        [grpc_service, simple_service]
+            .into_iter()
+            .flat_map(|service| {
+                [grpc_method, lower_camel_method.as_str()]
+                    .into_iter()
+                    .map(move |handler| (service, handler))
+            })
+            .find(|(service, handler)| {
+                schemas
+                    .resolve_latest_invocation_target(service, handler)
+                    .is_some()
+            })
+            .map(|(service, handler)| (service.to_owned(), handler.to_owned()))
+    }
+}
+
+/// Translates the response of the ingress API to the response message, and copies its
+/// invocation metadata to the gRPC response headers.
+async fn grpc_output(
+    response: Response<Full<Bytes>>,
+    encoding: Encoding,
+    method_descriptor: Option<&MethodDescriptor>,
+    response_headers: &mut HeaderMap,
+) -> Result<Bytes, GrpcStatus> {
+    let (parts, body) = response.into_parts();
+    for name in [X_RESTATE_ID, IDEMPOTENCY_EXPIRES] {
+        if let Some(value) = parts.headers.get(&name) {
+            response_headers.insert(name, value.clone());
+        }
+    }
+    let output = body
+        .collect()
+        .await
+        .unwrap_or_else(|e: Infallible| match e {})
+        .to_bytes();
+    match parts.status {
+        StatusCode::OK => match (encoding, method_descriptor) {
+            (Encoding::Json, _) => Ok(output),
+            (Encoding::Proto, Some(method)) => transcode_output(method, &output),
+            (Encoding::Proto, None) if output.is_empty() => Ok(output),
+            (Encoding::Proto, None) => Err(GrpcStatus::new(
+                GrpcCode::FailedPrecondition,
+                format!(
+                    "cannot convert the handler output, no Protobuf descriptor set describes it: \
+                    register the descriptor set of the service in the \
+                    '{GRPC_DESCRIPTOR_SET_METADATA_KEY}' service metadata, or use the \
+                    application/grpc+json content type"
+                ),
+            )),
+        },
+        // Sends reply with an empty message, the invocation id is in the x-restate-id header
+        StatusCode::ACCEPTED => match encoding {
+            Encoding::Proto => Ok(Bytes::new()),
+            Encoding::Json => Ok(output),
+        },
+        status => Err(GrpcStatus::from_error_response(status, &output)),
+    }
+}
+
+/// Parses the `/{service}/{method}` path of gRPC requests.
+fn parse_grpc_path(uri: &Uri) -> Result<(&str, &str), GrpcStatus> {
+    let mut segments = uri.path().strip_prefix('/').unwrap_or_default().split('/');
+    match (segments.next(), segments.next(), segments.next()) {
+        (Some(service), Some(method), None) if !service.is_empty() && !method.is_empty() => {
+            Ok((service, method))
+        }
+        _ => Err(GrpcStatus::new(
+            GrpcCode::Unimplemented,
+            format!("bad gRPC path '{}', expected /:service/:method", uri.path()),
+        )),
+    }
+}
+
+fn lower_first_char(s: &str) -> String {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Keeps the path of the gRPC request, which is recorded in the invocation headers, and passes
+/// the delay with the query parameter understood by the ingress API.
+fn ingress_uri(uri: &Uri, delay: Option<&str>) -> Uri {
+    let path_and_query = match delay {
+        Some(delay) => format!("{}?delay={}", uri.path(), urlencoding::encode(delay)),
+        None => uri.path().to_owned(),
+    };
+    path_and_query.parse().unwrap_or_else(|_| uri.clone())
+}
+
+/// Metadata consumed by the gRPC translation, which is not propagated to the invocation.
+fn is_grpc_metadata(name: &HeaderName) -> bool {
+    name == header::TE
+        || name == header::CONTENT_LENGTH
+        || name == X_RESTATE_KEY
+        || name == DELAY
+        || name.as_str().starts_with("grpc-")
+}
+
+/// Extracts the single message of the body of a unary gRPC request.
+fn decode_message(mut body: Bytes) -> Result<Bytes, GrpcStatus> {
+    // Some clients send an empty body for empty messages
+    if body.is_empty() {
+        return Ok(body);
+    }
+    if body.len() < MESSAGE_PREFIX_LEN {
+        return Err(GrpcStatus::new(
+            GrpcCode::InvalidArgument,
+            "truncated gRPC message",
+        ));
+    }
+    let compressed = body.get_u8();
+    if compressed != 0 {
+        return Err(GrpcStatus::new(
+            GrpcCode::Unimplemented,
+            "compressed gRPC messages are not supported",
+        ));
+    }
+    let len = body.get_u32() as usize;
+    if body.len() < len {
+        return Err(GrpcStatus::new(
+            GrpcCode::InvalidArgument,
+            "truncated gRPC message",
+        ));
+    }
+    let message = body.split_to(len);
+    if !body.is_empty() {
+        return Err(GrpcStatus::new(
+            GrpcCode::Unimplemented,
+            "only unary methods are supported, the request must contain a single message",
+        ));
+    }
+    Ok(message)
+}
+
+fn encode_message(flag: u8, message: &[u8]) -> BytesMut {
+    let mut buf = BytesMut::with_capacity(MESSAGE_PREFIX_LEN + message.len());
+    buf.put_u8(flag);
+    buf.put_u32(message.len() as u32);
+    buf.put_slice(message);
+    buf
+}
+
+/// Converts the protobuf request message to the JSON input of the handler. Messages without
+/// fields, such as `google.protobuf.Empty`, are passed as an empty input.
+fn transcode_input(
+    method: &MethodDescriptor,
+    message: Bytes,
+) -> Result<(Bytes, Option<HeaderValue>), GrpcStatus> {
+    let input = method.input();
+    if input.fields().next().is_none() {
+        return Ok((Bytes::new(), None));
+    }
+    let message = DynamicMessage::decode(input, message).map_err(|e| {
+        GrpcStatus::new(
+            GrpcCode::InvalidArgument,
+            format!("cannot decode the request message: {e}"),
+        )
+    })?;
+    let json = serde_json::to_vec(&message).map_err(|e| {
+        GrpcStatus::new(
+            GrpcCode::InvalidArgument,
+            format!("cannot convert the request message to JSON: {e}"),
+        )
+    })?;
+    Ok((json.into(), Some(APPLICATION_JSON)))
+}
+
+/// Converts the JSON output of the handler to the protobuf response message.
+fn transcode_output(method: &MethodDescriptor, output: &[u8]) -> Result<Bytes, GrpcStatus> {
+    if output.is_empty() {
+        return Ok(Bytes::new());
+    }
+    let mut deserializer = serde_json::Deserializer::from_slice(output);
+    let message = DynamicMessage::deserialize(method.output(), &mut deserializer)
+        .and_then(|message| deserializer.end().map(|_| message))
+        .map_err(|e| {
+            GrpcStatus::new(
+                GrpcCode::Internal,
+                format!(
+                    "cannot convert the handler output to '{}': {e}",
+                    method.output().full_name()
+                ),
+            )
+        })?;
+    Ok(message.encode_to_vec().into())
+}
+
+fn grpc_response(
+    content_type: &GrpcContentType,
+    mut headers: HeaderMap,
+    body: GrpcBody,
+) -> Response<GrpcBody> {
+    headers.insert(header::CONTENT_TYPE, content_type.value.clone());
+    let mut response = Response::new(body);
+    *response.headers_mut() = headers;
+    response
+}
+
+/// Returns the body reporting the result of the call: the response message followed by the
+/// trailers holding the status.
+fn grpc_body(protocol: Protocol, result: Result<Bytes, GrpcStatus>) -> GrpcBody {
+    let mut trailers = HeaderMap::new();
+    let message = match result {
+        Ok(message) => {
+            GrpcStatus::ok().append_to(&mut trailers);
+            Some(message)
+        }
+        Err(status) => {
+            status.log_failure();
+            status.append_to(&mut trailers);
+            None
+        }
+    };
+    encode_body(protocol, message, trailers)
+}
+
+fn encode_body(protocol: Protocol, message: Option<Bytes>, trailers: HeaderMap) -> GrpcBody {
+    match protocol {
+        Protocol::Grpc => GrpcBody {
+            message: message.map(|message| encode_message(0, &message).freeze()),
+            trailers: (!trailers.is_empty()).then_some(trailers),
+            pending: None,
+        },
+        // gRPC-Web sends the trailers in the body
+        Protocol::GrpcWeb => {
+            let mut body = message
+                .map(|message| encode_message(0, &message))
+                .unwrap_or_default();
+            if !trailers.is_empty() {
+                let mut encoded_trailers = Vec::new();
+                for (name, value) in &trailers {
+                    encoded_trailers.extend_from_slice(name.as_str().as_bytes());
+                    encoded_trailers.extend_from_slice(b": ");
+                    encoded_trailers.extend_from_slice(value.as_bytes());
+                    encoded_trailers.extend_from_slice(b"\r\n");
+                }
+                body.extend_from_slice(&encode_message(GRPC_WEB_TRAILERS_FLAG, &encoded_trailers));
+            }
+            GrpcBody {
+                message: (!body.is_empty()).then(|| body.freeze()),
+                trailers: None,
+                pending: None,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_content_type() {
+        let content_type = |value: &'static str| {
+            let mut headers = HeaderMap::new();
+            headers.insert(header::CONTENT_TYPE, HeaderValue::from_static(value));
+            GrpcContentType::from_headers(&headers)
+                .map(|res| res.map(|ct| (ct.protocol, ct.encoding)))
+        };
+
+        assert!(content_type("application/json").is_none());
+        assert_eq!(
+            content_type("application/grpc").unwrap().unwrap(),
+            (Protocol::Grpc, Encoding::Proto)
+        );
+        assert_eq!(
+            content_type("application/grpc+json; charset=utf-8")
+                .unwrap()
+                .unwrap(),
+            (Protocol::Grpc, Encoding::Json)
+        );
+        assert_eq!(
+            content_type("application/grpc-web+proto").unwrap().unwrap(),
+            (Protocol::GrpcWeb, Encoding::Proto)
+        );
+        assert!(content_type("application/grpc-web-text").unwrap().is_err());
+    }
+
+    #[test]
+    fn message_framing() {
+        let framed = encode_message(0, b"hello").freeze();
+        assert_eq!(
+            decode_message(framed.clone()).unwrap(),
+            Bytes::from_static(b"hello")
+        );
+        assert!(decode_message(Bytes::new()).unwrap().is_empty());
+        assert!(decode_message(framed.slice(..7)).is_err());
+
+        let mut two_messages = BytesMut::from(&framed[..]);
+        two_messages.extend_from_slice(&framed);
+        assert!(decode_message(two_messages.freeze()).is_err());
+
+        let compressed = encode_message(1, b"hello").freeze();
+        assert_eq!(
+            decode_message(compressed).unwrap_err().code,
+            GrpcCode::Unimplemented
+        );
+    }
+
+    #[test]
+    fn grpc_web_trailers_in_body() {
+        let body = grpc_body(Protocol::GrpcWeb, Ok(Bytes::from_static(b"hi")));
+        assert!(body.trailers.is_none());
+
+        let mut expected = encode_message(0, b"hi");
+        expected.extend_from_slice(&encode_message(
+            GRPC_WEB_TRAILERS_FLAG,
+            b"grpc-status: 0\r\n",
+        ));
+        assert_eq!(body.message.unwrap(), expected.freeze());
+    }
+}
@@ -16,17 +16,22 @@ use tracing::warn;
 use restate_types::errors::GenericError;
 use restate_types::identifiers::IdempotencyId;
 use restate_types::invocation::InvocationQuery;
-use restate_types::invocation::client::{AttachInvocationResponse, GetInvocationOutputResponse};
+use restate_types::invocation::client::{
+    AttachInvocationResponse, GetInvocationOutputResponse, InvocationOutput,
+    InvocationOutputResponse,
+};
 use restate_types::schema::invocation_target::InvocationTargetResolver;
+use restate_types::schema::service::ServiceMetadataResolver;
 
 use super::HandlerError;
 use super::path_parsing::{InvocationRequestType, InvocationTargetType, TargetType};
 use super::{Handler, InvocationTargetRequest};
 use crate::RequestDispatcher;
+use crate::auth::Principal;
 
 impl<Schemas, Dispatcher> Handler<Schemas, Dispatcher>
 where
-    Schemas: InvocationTargetResolver + Clone + Send + Sync + 'static,
+    Schemas: ServiceMetadataResolver + InvocationTargetResolver + Clone + Send + Sync + 'static,
     Dispatcher: RequestDispatcher + Clone + Send + Sync + 'static,
 {
     pub(crate) async fn handle_invocation<B: http_body::Body>(
@@ -92,7 +97,9 @@ where
         if req.method() != Method::GET {
             return Err(HandlerError::MethodNotAllowed);
         }
-        self.attach_invocation_query(invocation_query).await
+        let principal = req.extensions().get::<Principal>().cloned();
+        self.attach_invocation_query(principal, invocation_query)
+            .await
     }
 
     pub(crate) async fn handle_invocation_get_output<B: http_body::Body>(
@@ -106,7 +113,9 @@ where
         if req.method() != Method::GET {
             return Err(HandlerError::MethodNotAllowed);
         }
-        self.get_invocation_output_query(invocation_query).await
+        let principal = req.extensions().get::<Principal>().cloned();
+        self.get_invocation_output_query(principal, invocation_query)
+            .await
     }
 
     pub(crate) async fn handle_attach_by_target<B: http_body::Body>(
@@ -116,8 +125,10 @@ where
     where
         <B as http_body::Body>::Error: Into<GenericError>,
     {
+        let principal = req.extensions().get::<Principal>().cloned();
         let invocation_query = Self::parse_invocation_target_body(req).await?;
-        self.attach_invocation_query(invocation_query).await
+        self.attach_invocation_query(principal, invocation_query)
+            .await
     }
 
     pub(crate) async fn handle_output_by_target<B: http_body::Body>(
@@ -127,8 +138,10 @@ where
     where
         <B as http_body::Body>::Error: Into<GenericError>,
     {
+        let principal = req.extensions().get::<Principal>().cloned();
         let invocation_query = Self::parse_invocation_target_body(req).await?;
-        self.get_invocation_output_query(invocation_query).await
+        self.get_invocation_output_query(principal, invocation_query)
+            .await
     }
 
     async fn parse_invocation_target_body<B: http_body::Body>(
@@ -156,8 +169,33 @@ where
 
     async fn attach_invocation_query(
         self,
+        principal: Option<Principal>,
+        invocation_query: InvocationQuery,
+    ) -> Result<Response<Full<Bytes>>, HandlerError> {
+        let output = self
+            .attach_invocation_output(principal, invocation_query)
+            .await?;
+        self.reply_with_invocation_output(output)
+    }
+
+    async fn get_invocation_output_query(
+        self,
+        principal: Option<Principal>,
         invocation_query: InvocationQuery,
     ) -> Result<Response<Full<Bytes>>, HandlerError> {
+        let output = self
+            .read_invocation_output(principal, invocation_query)
+            .await?;
+        self.reply_with_invocation_output(output)
+    }
+
+    /// Waits for the invocation to complete, and returns its output once authorized.
+    pub(super) async fn attach_invocation_output(
+        &self,
+        principal: Option<Principal>,
+        invocation_query: InvocationQuery,
+    ) -> Result<InvocationOutput, HandlerError> {
+        self.authorize_invocation_query(principal.as_ref(), &invocation_query)?;
         let response = match self
             .dispatcher
             .attach_invocation(invocation_query.clone())
@@ -171,22 +209,17 @@ where
             }
             AttachInvocationResponse::Ready(response) => response,
         };
-
-        Self::reply_with_invocation_response(response, move |invocation_target| {
-            self.schemas
-                .pinned()
-                .resolve_latest_invocation_target(
-                    invocation_target.service_name(),
-                    invocation_target.handler_name(),
-                )
-                .ok_or(HandlerError::NotFound)
-        })
+        self.authorize_invocation_output(principal.as_ref(), &invocation_query, &response)?;
+        Ok(response)
     }
 
-    async fn get_invocation_output_query(
-        self,
+    /// Returns the output of the completed invocation once authorized.
+    pub(super) async fn read_invocation_output(
+        &self,
+        principal: Option<Principal>,
         invocation_query: InvocationQuery,
-    ) -> Result<Response<Full<Bytes>>, HandlerError> {
+    ) -> Result<InvocationOutput, HandlerError> {
+        self.authorize_invocation_query(principal.as_ref(), &invocation_query)?;
         let response = match self
             .dispatcher
             .get_invocation_output(invocation_query.clone())
@@ -209,8 +242,15 @@ where
                 return Err(HandlerError::Unavailable);
             }
         };
+        self.authorize_invocation_output(principal.as_ref(), &invocation_query, &response)?;
+        Ok(response)
+    }
 
-        Self::reply_with_invocation_response(response, move |invocation_target| {
+    pub(super) fn reply_with_invocation_output(
+        &self,
+        output: InvocationOutput,
+    ) -> Result<Response<Full<Bytes>>, HandlerError> {
+        Self::reply_with_invocation_response(output, |invocation_target| {
             self.schemas
                 .pinned()
                 .resolve_latest_invocation_target(
@@ -220,4 +260,44 @@ where
                 .ok_or(HandlerError::NotFound)
         })
     }
+
+    /// Authorizes the queries naming their target service before dispatching them.
+    fn authorize_invocation_query(
+        &self,
+        principal: Option<&Principal>,
+        invocation_query: &InvocationQuery,
+    ) -> Result<(), HandlerError> {
+        match invocation_query {
+            InvocationQuery::Invocation(_) => Ok(()),
+            InvocationQuery::Workflow(service_id) => {
+                self.authorize_principal(principal, &service_id.service_name, None)
+            }
+            InvocationQuery::IdempotencyId(idempotency_id) => self.authorize_principal(
+                principal,
+                &idempotency_id.service_name,
+                Some(&idempotency_id.service_handler),
+            ),
+        }
+    }
+
+    /// Authorizes the queries by invocation id, whose target is known only once the output
+    /// is read.
+    fn authorize_invocation_output(
+        &self,
+        principal: Option<&Principal>,
+        invocation_query: &InvocationQuery,
+        output: &InvocationOutput,
+    ) -> Result<(), HandlerError> {
+        if !matches!(invocation_query, InvocationQuery::Invocation(_)) {
+            return Ok(());
+        }
+        match &output.response {
+            InvocationOutputResponse::Success(invocation_target, _) => self.authorize_principal(
+                principal,
+                invocation_target.service_name(),
+                Some(invocation_target.handler_name()),
+            ),
+            InvocationOutputResponse::Failure(_) => self.authorize_unknown_target(principal),
+        }
+    }
 }
@@ -8,8 +8,10 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+mod authorization;
 mod awakeables;
 mod error;
+mod grpc;
 mod health;
 mod invocation;
 mod lookup;
@@ -22,13 +24,16 @@ mod tracing;
 mod workflow;
 
 use std::convert::Infallible;
+use std::pin::Pin;
 use std::task::{Context, Poll};
 
 use bytestring::ByteString;
 use enumset::EnumSet;
-use error::HandlerError;
+pub(crate) use error::HandlerError;
 use futures::FutureExt;
 use futures::future::BoxFuture;
+pub use grpc::{GRPC_DESCRIPTOR_SET_METADATA_KEY, GrpcDescriptorSetError, GrpcIngress};
+use http_body::Frame;
 use http_body_util::Full;
 use hyper::http::HeaderValue;
 use hyper::{Request, Response};
@@ -46,6 +51,7 @@ use restate_types::schema::service::ServiceMetadataResolver;
 use restate_util_string::{ReString, RestrictedValue};
 
 use super::*;
+use crate::handler::grpc::{GrpcBody, GrpcContentType};
 use crate::handler::path_parsing::{
     AwakeableRequestType, InvocationRequestType, ServiceRequestType, WorkflowRequestType,
 };
@@ -76,6 +82,7 @@ pub(crate) struct Handler<Schemas, Dispatcher> {
     schemas: Live<Schemas>,
     dispatcher: Dispatcher,
     cluster_features: EnumSet<ClusterFeature>,
+    grpc: Option<GrpcIngress>,
 }
 
 impl<Schemas, Dispatcher> Handler<Schemas, Dispatcher> {
@@ -86,8 +93,15 @@ impl<Schemas, Dispatcher> Handler<Schemas, Dispatcher> {
             schemas,
             dispatcher,
             cluster_features,
+            grpc: None,
         }
     }
+
+    /// Accepts gRPC requests if set.
+    pub(crate) fn with_grpc(mut self, grpc: Option<GrpcIngress>) -> Self {
+        self.grpc = grpc;
+        self
+    }
 }
 
 impl<Schemas, Dispatcher, Body> tower::Service<Request<Body>> for Handler<Schemas, Dispatcher>
@@ -98,7 +112,7 @@ where
     <Body as http_body::Body>::Data: Send + 'static,
     <Body as http_body::Body>::Error: Into<GenericError>,
 {
-    type Response = Response<Full<Bytes>>;
+    type Response = Response<ResponseBody>;
     type Error = Infallible;
     type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
 
@@ -107,6 +121,19 @@ where
     }
 
     fn call(&mut self, req: Request<Body>) -> Self::Future {
+        // gRPC method paths are not ingress API paths, they're parsed by the gRPC handler
+        if let Some(content_type) = GrpcContentType::from_headers(req.headers()) {
+            let this = self.clone();
+            return async move {
+                let response = match content_type {
+                    Ok(content_type) => this.handle_grpc(req, content_type).await,
+                    Err(status) => status.into_response(),
+                };
+                Ok(response.map(ResponseBody::Grpc))
+            }
+            .boxed();
+        }
+
         let res = self.parse_path(req.uri());
 
         let mut this = self.clone();
@@ -121,12 +148,18 @@ where
                     this.handle_awakeable(req, awakeable_request).await
                 }
                 RequestType::Service(service_request) => {
+                    this.authorize(
+                        &req,
+                        service_request.name.as_str(),
+                        Some(service_request.handler.as_str()),
+                    )?;
                     this.handle_service_request(req, service_request).await
                 }
                 RequestType::Invocation(invocation_request) => {
                     this.handle_invocation(req, invocation_request).await
                 }
                 RequestType::Workflow(workflow_request) => {
+                    this.authorize(&req, workflow_request.workflow_name(), None)?;
                     this.handle_workflow(req, workflow_request).await
                 }
                 RequestType::Attach(invocation_id) => {
@@ -145,11 +178,64 @@ where
                 RequestType::Lookup => this.handle_lookup(req).await,
             }
         }
-        .map(|r| Ok::<_, Infallible>(r.unwrap_or_else(|e| e.into_response())))
+        .map(|r| {
+            Ok::<_, Infallible>(
+                r.unwrap_or_else(|e| e.into_response())
+                    .map(ResponseBody::Full),
+            )
+        })
         .boxed()
     }
 }
 
+/// Body of the ingress responses.
+#[derive(Debug)]
+pub(crate) enum ResponseBody {
+    Full(Full<Bytes>),
+    Grpc(GrpcBody),
+}
+
+impl Default for ResponseBody {
+    fn default() -> Self {
+        ResponseBody::Full(Full::default())
+    }
+}
+
+impl From<Bytes> for ResponseBody {
+    fn from(value: Bytes) -> Self {
+        ResponseBody::Full(Full::new(value))
+    }
+}
+
+impl http_body::Body for ResponseBody {
+    type Data = Bytes;
+    type Error = Infallible;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        match self.get_mut() {
+            ResponseBody::Full(body) => http_body::Body::poll_frame(Pin::new(body), cx),
+            ResponseBody::Grpc(body) => http_body::Body::poll_frame(Pin::new(body), cx),
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        match self {
+            ResponseBody::Full(body) => http_body::Body::is_end_stream(body),
+            ResponseBody::Grpc(body) => http_body::Body::is_end_stream(body),
+        }
+    }
+
+    fn size_hint(&self) -> http_body::SizeHint {
+        match self {
+            ResponseBody::Full(body) => http_body::Body::size_hint(body),
+            ResponseBody::Grpc(body) => http_body::Body::size_hint(body),
+        }
+    }
+}
+
 #[derive(Debug, Deserialize)]
 #[serde(
     tag = "target",
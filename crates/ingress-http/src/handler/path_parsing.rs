@@ -24,6 +24,14 @@ pub(crate) enum WorkflowRequestType {
 }
 
 impl WorkflowRequestType {
+    pub(crate) fn workflow_name(&self) -> &str {
+        match self {
+            WorkflowRequestType::Attach(name, _) | WorkflowRequestType::GetOutput(name, _) => {
+                name.as_str()
+            }
+        }
+    }
+
     /// Parse workflow request from unversioned path: `/restate/workflow/{name}/{key}/attach|output`
     /// (old ingress API)
     fn from_path_chunks<'a>(
@@ -13,6 +13,8 @@ use std::future::ready;
 use std::sync::Arc;
 use std::time::Duration;
 
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
 use bytes::Bytes;
 use bytestring::ByteString;
 use futures::{FutureExt, stream};
@@ -20,6 +22,8 @@ use http::StatusCode;
 use http::{HeaderValue, Method, Request, Response};
 use http_body::Frame;
 use http_body_util::{BodyExt, Empty, Full, StreamBody};
+use prost::Message;
+use tokio::sync::oneshot;
 use tower::{ServiceBuilder, ServiceExt};
 use tower_http::limit::{RequestBodyLimitLayer, ResponseBody as LimitResponseBody};
 use tracing_test::traced_test;
@@ -30,11 +34,14 @@ use super::health::HealthResponse;
 use super::lookup::LookupResponse;
 use super::mocks::*;
 use super::service_handler::*;
+use super::{GRPC_DESCRIPTOR_SET_METADATA_KEY, GrpcIngress, ResponseBody};
 use crate::MockRequestDispatcher;
+use crate::auth::{ALLOWED_PRINCIPALS_METADATA_KEY, Principal};
 use crate::handler::responses::X_RESTATE_ID;
 use restate_core::TestCoreEnv;
 use restate_test_util::{assert, assert_eq};
 use restate_types::config::{Configuration, set_current_config};
+use restate_types::errors::InvocationError;
 use restate_types::identifiers::{IdempotencyId, InvocationId, ServiceId, WithInvocationId};
 use restate_types::invocation::client::{
     AttachInvocationResponse, GetInvocationOutputResponse, InvocationOutput,
@@ -50,6 +57,7 @@ use restate_types::schema::invocation_target::{
     InputContentType, InputRules, InputValidationRule, InvocationTargetMetadata,
     OutputContentTypeRule, OutputRules,
 };
+use restate_types::schema::service::ServiceMetadataResolver;
 
 #[restate_core::test]
 #[traced_test]
@@ -734,9 +742,7 @@ async fn get_output_with_invocation_id() {
     );
 
     let req = hyper::Request::builder()
-        .uri(format!(
-            "http://localhost/restate/invocation/{invocation_id}/output"
-        ))
+        .uri(format!("http://localhost/restate/output/{invocation_id}"))
         .method(Method::GET)
         .header("content-type", "application/json")
         .body(Empty::<Bytes>::new())
@@ -1145,6 +1151,55 @@ async fn health() {
     let _: HealthResponse = serde_json::from_slice(&response_bytes).unwrap();
 }
 
+fn schemas_with_allowed_principals(allowed_principals: &str) -> MockSchemas {
+    let mut schemas = MockSchemas::default().with_service_and_target(
+        "greeter.Greeter",
+        "greet",
+        InvocationTargetMetadata::mock(InvocationTargetType::Service),
+    );
+    let mut service = schemas.0.resolve_latest_service("greeter.Greeter").unwrap();
+    service.handlers.get_mut("greet").unwrap().metadata.insert(
+        ALLOWED_PRINCIPALS_METADATA_KEY.to_owned(),
+        allowed_principals.to_owned(),
+    );
+    schemas.0.add(service);
+    schemas
+}
+
+#[restate_core::test]
+#[traced_test]
+async fn principal_not_in_allow_list_is_forbidden() {
+    let mut req = hyper::Request::get("http://localhost/greeter.Greeter/greet")
+        .body(Empty::<Bytes>::default())
+        .unwrap();
+    req.extensions_mut().insert(Principal::new("ops"));
+
+    let response = handle_with_schemas_and_dispatcher(
+        req,
+        schemas_with_allowed_principals("billing"),
+        MockRequestDispatcher::default(),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
+#[restate_core::test]
+#[traced_test]
+async fn principal_in_allow_list_is_accepted() {
+    let mut req = hyper::Request::get("http://localhost/greeter.Greeter/greet")
+        .body(Empty::<Bytes>::default())
+        .unwrap();
+    req.extensions_mut().insert(Principal::new("ops"));
+
+    let response = handle_with_schemas_and_dispatcher(
+        req,
+        schemas_with_allowed_principals("billing, ops"),
+        expect_invocation_and_reply_with_empty(),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
 fn expect_invocation_and_reply_with_empty() -> MockRequestDispatcher {
     let mut mock_dispatcher = MockRequestDispatcher::new();
     mock_dispatcher
@@ -1189,7 +1244,7 @@ pub async fn handle_with_schemas_and_dispatcher<B: http_body::Body + Send + 'sta
     mut req: Request<B>,
     schemas: MockSchemas,
     dispatcher: MockRequestDispatcher,
-) -> Response<Full<Bytes>>
+) -> Response<ResponseBody>
 where
     <B as http_body::Body>::Error: std::error::Error + Send + Sync + 'static,
     <B as http_body::Body>::Data: Send + Sync + 'static,
@@ -1208,7 +1263,7 @@ where
 pub async fn handle<B: http_body::Body + Send + 'static>(
     req: Request<B>,
     mock_request_dispatcher: MockRequestDispatcher,
-) -> Response<Full<Bytes>>
+) -> Response<ResponseBody>
 where
     <B as http_body::Body>::Error: std::error::Error + Send + Sync + 'static,
     <B as http_body::Body>::Data: Send + Sync + 'static,
@@ -1220,7 +1275,7 @@ async fn handle_with_size_limit<B>(
     mut req: Request<B>,
     size_limit: usize,
     dispatcher: MockRequestDispatcher,
-) -> Response<LimitResponseBody<Full<Bytes>>>
+) -> Response<LimitResponseBody<ResponseBody>>
 where
     B: http_body::Body + Send + 'static,
     <B as http_body::Body>::Data: Send + Sync + 'static,
@@ -1324,6 +1379,91 @@ async fn output_with_id_path() {
     assert_eq!(response.status(), StatusCode::OK);
 }
 
+fn expect_attach_and_reply_with_greeter_output(
+    invocation_id: InvocationId,
+) -> MockRequestDispatcher {
+    let mut mock_dispatcher = MockRequestDispatcher::default();
+    mock_dispatcher
+        .expect_attach_invocation()
+        .return_once(move |_| {
+            ready(Ok(AttachInvocationResponse::Ready(InvocationOutput {
+                request_id: Default::default(),
+                invocation_id: Some(invocation_id),
+                completion_expiry_time: None,
+                response: InvocationOutputResponse::Success(
+                    InvocationTarget::service("greeter.Greeter", "greet"),
+                    Bytes::from_static(b"123"),
+                ),
+            })))
+            .boxed()
+        });
+    mock_dispatcher
+}
+
+#[restate_core::test]
+#[traced_test]
+async fn attach_with_id_path_checks_target_allow_list() {
+    let invocation_id = InvocationId::mock_random();
+
+    let mut req = hyper::Request::get(format!("http://localhost/restate/attach/{invocation_id}"))
+        .body(Empty::<Bytes>::new())
+        .unwrap();
+    req.extensions_mut().insert(Principal::new("ops"));
+
+    let response = handle_with_schemas_and_dispatcher(
+        req,
+        schemas_with_allowed_principals("billing"),
+        expect_attach_and_reply_with_greeter_output(invocation_id),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let mut req = hyper::Request::get(format!("http://localhost/restate/attach/{invocation_id}"))
+        .body(Empty::<Bytes>::new())
+        .unwrap();
+    req.extensions_mut().insert(Principal::new("billing"));
+
+    let response = handle_with_schemas_and_dispatcher(
+        req,
+        schemas_with_allowed_principals("billing"),
+        expect_attach_and_reply_with_greeter_output(invocation_id),
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[restate_core::test]
+#[traced_test]
+async fn output_with_id_path_failure_requires_every_allow_list() {
+    let invocation_id = InvocationId::mock_random();
+
+    let mut req = hyper::Request::get(format!("http://localhost/restate/output/{invocation_id}"))
+        .body(Empty::<Bytes>::new())
+        .unwrap();
+    req.extensions_mut().insert(Principal::new("ops"));
+
+    let mut mock_dispatcher = MockRequestDispatcher::default();
+    mock_dispatcher
+        .expect_get_invocation_output()
+        .return_once(move |_| {
+            ready(Ok(GetInvocationOutputResponse::Ready(InvocationOutput {
+                request_id: Default::default(),
+                invocation_id: Some(invocation_id),
+                completion_expiry_time: None,
+                response: InvocationOutputResponse::Failure(InvocationError::internal("boom")),
+            })))
+            .boxed()
+        });
+
+    let response = handle_with_schemas_and_dispatcher(
+        req,
+        schemas_with_allowed_principals("billing"),
+        mock_dispatcher,
+    )
+    .await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+}
+
 #[restate_core::test]
 #[traced_test]
 async fn lookup_idempotency_unkeyed_returns_deterministic_id() {
@@ -1589,3 +1729,456 @@ async fn scoped_virtual_object_allowed_when_flag_on() {
     .await;
     assert_eq!(response.status(), StatusCode::OK);
 }
+
+// -- gRPC -----------------------------------------------------------------
+
+fn grpc_frame(message: &[u8]) -> Bytes {
+    let mut frame = vec![0];
+    frame.extend_from_slice(&(message.len() as u32).to_be_bytes());
+    frame.extend_from_slice(message);
+    frame.into()
+}
+
+async fn handle_grpc(
+    req: Request<Full<Bytes>>,
+    grpc: Option<GrpcIngress>,
+    dispatcher: MockRequestDispatcher,
+) -> Response<ResponseBody> {
+    handle_grpc_with_schemas(req, mock_schemas(), grpc, dispatcher).await
+}
+
+async fn handle_grpc_with_schemas(
+    mut req: Request<Full<Bytes>>,
+    schemas: MockSchemas,
+    grpc: Option<GrpcIngress>,
+    dispatcher: MockRequestDispatcher,
+) -> Response<ResponseBody> {
+    let _env = TestCoreEnv::create_with_single_node(1, 1).await;
+
+    req.extensions_mut()
+        .insert(ConnectInfo::new(SocketAddress::Anonymous));
+    req.extensions_mut().insert(opentelemetry::Context::new());
+
+    Handler::new(Live::from_value(schemas), Arc::new(dispatcher))
+        .with_grpc(grpc)
+        .oneshot(req)
+        .await
+        .unwrap()
+}
+
+/// Registers in the metadata of `greeter.Greeter` the descriptor set of its gRPC service, whose
+/// `Greet` method takes a `GreetingRequest` and returns a `GreetingResponse`.
+fn schemas_with_greeter_descriptor_set() -> MockSchemas {
+    use prost_types::field_descriptor_proto::{Label, Type};
+    use prost_types::{
+        DescriptorProto, FieldDescriptorProto, FileDescriptorProto, FileDescriptorSet,
+        MethodDescriptorProto, ServiceDescriptorProto,
+    };
+
+    let message = |name: &str, field: &str| DescriptorProto {
+        name: Some(name.to_owned()),
+        field: vec![FieldDescriptorProto {
+            name: Some(field.to_owned()),
+            json_name: Some(field.to_owned()),
+            number: Some(1),
+            label: Some(Label::Optional as i32),
+            r#type: Some(Type::String as i32),
+            ..Default::default()
+        }],
+        ..Default::default()
+    };
+    let file = FileDescriptorProto {
+        name: Some("greeter.proto".to_owned()),
+        package: Some("greeter".to_owned()),
+        message_type: vec![
+            message("GreetingRequest", "person"),
+            message("GreetingResponse", "greeting"),
+        ],
+        service: vec![ServiceDescriptorProto {
+            name: Some("Greeter".to_owned()),
+            method: vec![MethodDescriptorProto {
+                name: Some("Greet".to_owned()),
+                input_type: Some(".greeter.GreetingRequest".to_owned()),
+                output_type: Some(".greeter.GreetingResponse".to_owned()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }],
+        syntax: Some("proto3".to_owned()),
+        ..Default::default()
+    };
+    let descriptor_set = FileDescriptorSet { file: vec![file] }.encode_to_vec();
+
+    let mut schemas = mock_schemas();
+    let mut service = schemas.0.resolve_latest_service("greeter.Greeter").unwrap();
+    service.metadata.insert(
+        GRPC_DESCRIPTOR_SET_METADATA_KEY.to_owned(),
+        BASE64_STANDARD.encode(descriptor_set),
+    );
+    schemas.0.add(service);
+    schemas
+}
+
+/// Protobuf encoding of a message whose single field is the string `value`, numbered 1.
+fn proto_string_message(value: &str) -> Vec<u8> {
+    let mut message = vec![0x0a, value.len() as u8];
+    message.extend_from_slice(value.as_bytes());
+    message
+}
+
+fn greeting_output(invocation_id: InvocationId) -> InvocationOutput {
+    InvocationOutput {
+        request_id: Default::default(),
+        invocation_id: Some(invocation_id),
+        completion_expiry_time: None,
+        response: InvocationOutputResponse::Success(
+            InvocationTarget::service("greeter.Greeter", "greet"),
+            serde_json::to_vec(&GreetingResponse {
+                greeting: "Igal".to_string(),
+            })
+            .unwrap()
+            .into(),
+        ),
+    }
+}
+
+#[restate_core::test]
+#[traced_test]
+async fn grpc_call_service() {
+    let greeting_req = serde_json::to_vec(&GreetingRequest {
+        person: "Francesco".to_string(),
+    })
+    .unwrap();
+
+    // Method names are resolved to the handler starting with a lowercase letter
+    let req = hyper::Request::builder()
+        .uri("http://localhost/greeter.Greeter/Greet")
+        .method(Method::POST)
+        .header("content-type", "application/grpc+json")
+        .header("te", "trailers")
+        .header("grpc-timeout", "10S")
+        .header("my-header", "my-value")
+        .body(Full::new(grpc_frame(&greeting_req)))
+        .unwrap();
+
+    let mut mock_dispatcher = MockRequestDispatcher::default();
+    mock_dispatcher
+        .expect_call()
+        .return_once(|invocation_request| {
+            assert_eq!(invocation_request.header.target.handler_name(), "greet");
+            assert!(
+                invocation_request
+                    .header
+                    .headers
+                    .iter()
+                    .any(|h| h.name == "my-header")
+            );
+            assert!(
+                !invocation_request
+                    .header
+                    .headers
+                    .iter()
+                    .any(|h| h.name.starts_with("grpc-") || h.name == "te")
+            );
+            let greeting_req: GreetingRequest =
+                serde_json::from_slice(&invocation_request.body).unwrap();
+            assert_eq!(&greeting_req.person, "Francesco");
+
+            ready(Ok(InvocationOutput {
+                request_id: Default::default(),
+                invocation_id: Some(invocation_request.invocation_id()),
+                completion_expiry_time: None,
+                response: InvocationOutputResponse::Success(
+                    invocation_request.header.target.clone(),
+                    serde_json::to_vec(&GreetingResponse {
+                        greeting: "Igal".to_string(),
+                    })
+                    .unwrap()
+                    .into(),
+                ),
+            }))
+            .boxed()
+        });
+
+    let response = handle_grpc(req, Some(GrpcIngress::default()), mock_dispatcher).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers().get("content-type").unwrap(),
+        "application/grpc+json"
+    );
+    assert!(response.headers().contains_key(X_RESTATE_ID));
+    let collected = response.into_body().collect().await.unwrap();
+    assert_eq!(
+        collected.trailers().unwrap().get("grpc-status").unwrap(),
+        "0"
+    );
+    let body = collected.to_bytes();
+    let response_value: GreetingResponse = serde_json::from_slice(&body[5..]).unwrap();
+    assert_eq!(response_value.greeting, "Igal");
+}
+
+#[restate_core::test]
+#[traced_test]
+async fn grpc_send_with_delay() {
+    let req = hyper::Request::builder()
+        .uri("http://localhost/greeter.GreeterObject/greet")
+        .method(Method::POST)
+        .header("content-type", "application/grpc")
+        .header("x-restate-key", "my-key")
+        .header("delay", "10s")
+        .body(Full::new(grpc_frame(b"")))
+        .unwrap();
+
+    let mut mock_dispatcher = MockRequestDispatcher::default();
+    mock_dispatcher
+        .expect_send()
+        .return_once(|invocation_request| {
+            assert_eq!(&**invocation_request.header.target.key().unwrap(), "my-key");
+            assert!(invocation_request.header.execution_time.is_some());
+            assert!(invocation_request.body.is_empty());
+
+            ready(Ok(SubmittedInvocationNotification {
+                request_id: Default::default(),
+                execution_time: None,
+                is_new_invocation: true,
+            }))
+            .boxed()
+        });
+
+    let response = handle_grpc(req, Some(GrpcIngress::default()), mock_dispatcher).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers().contains_key(X_RESTATE_ID));
+    let collected = response.into_body().collect().await.unwrap();
+    assert_eq!(
+        collected.trailers().unwrap().get("grpc-status").unwrap(),
+        "0"
+    );
+    assert_eq!(collected.to_bytes(), grpc_frame(b""));
+}
+
+#[restate_core::test]
+#[traced_test]
+async fn grpc_call_with_registered_descriptor_set() {
+    let req = hyper::Request::builder()
+        .uri("http://localhost/greeter.Greeter/Greet")
+        .method(Method::POST)
+        .header("content-type", "application/grpc")
+        .body(Full::new(grpc_frame(&proto_string_message("Francesco"))))
+        .unwrap();
+
+    let mut mock_dispatcher = MockRequestDispatcher::default();
+    mock_dispatcher
+        .expect_call()
+        .return_once(|invocation_request| {
+            let greeting_req: GreetingRequest =
+                serde_json::from_slice(&invocation_request.body).unwrap();
+            assert_eq!(&greeting_req.person, "Francesco");
+
+            ready(Ok(greeting_output(invocation_request.invocation_id()))).boxed()
+        });
+
+    let response = handle_grpc_with_schemas(
+        req,
+        schemas_with_greeter_descriptor_set(),
+        Some(GrpcIngress::default()),
+        mock_dispatcher,
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let collected = response.into_body().collect().await.unwrap();
+    assert_eq!(
+        collected.trailers().unwrap().get("grpc-status").unwrap(),
+        "0"
+    );
+    assert_eq!(
+        collected.to_bytes(),
+        grpc_frame(&proto_string_message("Igal"))
+    );
+}
+
+#[restate_core::test]
+#[traced_test]
+async fn grpc_proto_without_descriptor_set_is_rejected() {
+    let req = hyper::Request::builder()
+        .uri("http://localhost/greeter.Greeter/Greet")
+        .method(Method::POST)
+        .header("content-type", "application/grpc")
+        .body(Full::new(grpc_frame(&proto_string_message("Francesco"))))
+        .unwrap();
+
+    let response = handle_grpc(
+        req,
+        Some(GrpcIngress::default()),
+        MockRequestDispatcher::default(),
+    )
+    .await;
+
+    assert_eq!(response.headers().get("grpc-status").unwrap(), "9");
+}
+
+#[restate_core::test]
+#[traced_test]
+async fn grpc_attach_streams_output() {
+    let invocation_id = InvocationId::mock_random();
+    let req = hyper::Request::builder()
+        .uri("http://localhost/dev.restate.Invocations/Attach")
+        .method(Method::POST)
+        .header("content-type", "application/grpc+json")
+        .header("x-restate-invocation-id", invocation_id.to_string())
+        .body(Full::new(grpc_frame(b"")))
+        .unwrap();
+
+    let (output_tx, output_rx) = oneshot::channel();
+    let mut mock_dispatcher = MockRequestDispatcher::default();
+    mock_dispatcher
+        .expect_attach_invocation()
+        .return_once(move |actual_invocation_query| {
+            assert_eq!(
+                InvocationQuery::Invocation(invocation_id),
+                actual_invocation_query
+            );
+            async move { Ok(AttachInvocationResponse::Ready(output_rx.await.unwrap())) }.boxed()
+        });
+
+    // The headers are sent before the invocation completes
+    let response = handle_grpc(req, Some(GrpcIngress::default()), mock_dispatcher).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(!response.headers().contains_key("grpc-status"));
+
+    output_tx.send(greeting_output(invocation_id)).unwrap();
+    let collected = response.into_body().collect().await.unwrap();
+    assert_eq!(
+        collected.trailers().unwrap().get("grpc-status").unwrap(),
+        "0"
+    );
+    let body = collected.to_bytes();
+    let response_value: GreetingResponse = serde_json::from_slice(&body[5..]).unwrap();
+    assert_eq!(response_value.greeting, "Igal");
+}
+
+#[restate_core::test]
+#[traced_test]
+async fn grpc_get_output_with_registered_descriptor_set() {
+    let invocation_id = InvocationId::mock_random();
+    let req = hyper::Request::builder()
+        .uri("http://localhost/dev.restate.Invocations/GetOutput")
+        .method(Method::POST)
+        .header("content-type", "application/grpc")
+        .header("x-restate-invocation-id", invocation_id.to_string())
+        .body(Full::new(grpc_frame(b"")))
+        .unwrap();
+
+    let mut mock_dispatcher = MockRequestDispatcher::default();
+    mock_dispatcher
+        .expect_get_invocation_output()
+        .return_once(move |actual_invocation_query| {
+            assert_eq!(
+                InvocationQuery::Invocation(invocation_id),
+                actual_invocation_query
+            );
+            ready(Ok(GetInvocationOutputResponse::Ready(greeting_output(
+                invocation_id,
+            ))))
+            .boxed()
+        });
+
+    let response = handle_grpc_with_schemas(
+        req,
+        schemas_with_greeter_descriptor_set(),
+        Some(GrpcIngress::default()),
+        mock_dispatcher,
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let collected = response.into_body().collect().await.unwrap();
+    assert_eq!(
+        collected.trailers().unwrap().get("grpc-status").unwrap(),
+        "0"
+    );
+    assert_eq!(
+        collected.to_bytes(),
+        grpc_frame(&proto_string_message("Igal"))
+    );
+}
+
+#[restate_core::test]
+#[traced_test]
+async fn grpc_attach_without_invocation_id() {
+    let req = hyper::Request::builder()
+        .uri("http://localhost/dev.restate.Invocations/Attach")
+        .method(Method::POST)
+        .header("content-type", "application/grpc")
+        .body(Full::new(grpc_frame(b"")))
+        .unwrap();
+
+    let response = handle_grpc(
+        req,
+        Some(GrpcIngress::default()),
+        MockRequestDispatcher::default(),
+    )
+    .await;
+
+    // Trailers-Only response
+    assert_eq!(response.headers().get("grpc-status").unwrap(), "3");
+}
+
+#[restate_core::test]
+#[traced_test]
+async fn grpc_keyed_service_without_key() {
+    let req = hyper::Request::builder()
+        .uri("http://localhost/greeter.GreeterObject/greet")
+        .method(Method::POST)
+        .header("content-type", "application/grpc")
+        .body(Full::new(grpc_frame(b"")))
+        .unwrap();
+
+    let response = handle_grpc(
+        req,
+        Some(GrpcIngress::default()),
+        MockRequestDispatcher::default(),
+    )
+    .await;
+
+    // Trailers-Only response
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers().get("grpc-status").unwrap(), "3");
+}
+
+#[restate_core::test]
+#[traced_test]
+async fn grpc_unknown_method() {
+    let req = hyper::Request::builder()
+        .uri("http://localhost/greeter.Greeter/Unknown")
+        .method(Method::POST)
+        .header("content-type", "application/grpc")
+        .body(Full::new(grpc_frame(b"")))
+        .unwrap();
+
+    let response = handle_grpc(
+        req,
+        Some(GrpcIngress::default()),
+        MockRequestDispatcher::default(),
+    )
+    .await;
+
+    assert_eq!(response.headers().get("grpc-status").unwrap(), "12");
+}
+
+#[restate_core::test]
+#[traced_test]
+async fn grpc_disabled() {
+    let req = hyper::Request::builder()
+        .uri("http://localhost/greeter.Greeter/greet")
+        .method(Method::POST)
+        .header("content-type", "application/grpc")
+        .body(Full::new(grpc_frame(b"")))
+        .unwrap();
+
+    let response = handle_grpc(req, None, MockRequestDispatcher::default()).await;
+
+    assert_eq!(response.headers().get("grpc-status").unwrap(), "12");
+}
@@ -0,0 +1,94 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::future::{Ready, ready};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use futures::future::Either;
+use http::{Request, Response};
+use metrics::counter;
+use tower::{Layer, Service};
+use tracing::debug;
+
+use crate::auth::{AuthenticationError, Authenticator};
+use crate::handler::HandlerError;
+use crate::metric_definitions::{INGRESS_REQUESTS, REQUEST_UNAUTHENTICATED};
+
+const HEALTH_PATH: &str = "/restate/health";
+
+/// Authenticates the incoming requests, storing the resulting [`crate::auth::Principal`] in the
+/// request extensions. If no authenticator is configured, every request is let through.
+pub struct AuthenticationLayer {
+    authenticator: Option<Arc<dyn Authenticator>>,
+}
+
+impl AuthenticationLayer {
+    pub fn new(authenticator: Option<Arc<dyn Authenticator>>) -> Self {
+        Self { authenticator }
+    }
+}
+
+impl<S> Layer<S> for AuthenticationLayer {
+    type Service = Authentication<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        Authentication {
+            inner,
+            authenticator: self.authenticator.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Authentication<S> {
+    inner: S,
+    authenticator: Option<Arc<dyn Authenticator>>,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for Authentication<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+    ResBody: http_body::Body + Default + From<Bytes>,
+{
+    type Response = Response<ResBody>;
+    type Error = S::Error;
+    type Future = Either<S::Future, Ready<Result<Response<ResBody>, S::Error>>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<ReqBody>) -> Self::Future {
+        let Some(authenticator) = &self.authenticator else {
+            return Either::Left(self.inner.call(req));
+        };
+        // Health checks are used by probes, which don't carry credentials
+        if req.uri().path() == HEALTH_PATH {
+            return Either::Left(self.inner.call(req));
+        }
+
+        let error = match authenticator.authenticate(req.headers()) {
+            Ok(Some(principal)) => {
+                req.extensions_mut().insert(principal);
+                return Either::Left(self.inner.call(req));
+            }
+            Ok(None) => AuthenticationError::MissingCredentials,
+            Err(err) => err,
+        };
+
+        debug!("Rejecting unauthenticated request: {error}");
+        counter!(INGRESS_REQUESTS, "status" => REQUEST_UNAUTHENTICATED).increment(1);
+        Either::Right(ready(Ok(
+            HandlerError::Unauthenticated(error).into_response()
+        )))
+    }
+}
@@ -8,12 +8,14 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+pub mod auth;
 mod handler;
 mod layers;
 mod metric_definitions;
 mod rpc_request_dispatcher;
 mod server;
 
+pub use handler::{GRPC_DESCRIPTOR_SET_METADATA_KEY, GrpcDescriptorSetError, GrpcIngress};
 pub use rpc_request_dispatcher::InvocationClientRequestDispatcher;
 pub use server::{HyperServerIngress, IngressServerError};
 
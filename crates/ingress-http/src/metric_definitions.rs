@@ -20,6 +20,7 @@ pub const INGRESS_REQUESTS: &str = "restate.ingress.requests.total";
 pub const REQUEST_ADMITTED: &str = "admitted";
 pub const REQUEST_COMPLETED: &str = "completed";
 pub const REQUEST_RATE_LIMITED: &str = "rate-limited";
+pub const REQUEST_UNAUTHENTICATED: &str = "unauthenticated";
 
 pub const INGRESS_REQUEST_DURATION: &str = "restate.ingress.request_duration.seconds";
 
@@ -11,6 +11,7 @@
 use std::convert::Infallible;
 use std::future::Future;
 use std::num::NonZeroU32;
+use std::sync::Arc;
 use std::time::Duration;
 
 use codederror::CodedError;
@@ -30,8 +31,9 @@ use tower_http::trace::TraceLayer;
 use tracing::{Span, debug, info, info_span, instrument};
 
 use restate_core::network::hyper_error_status;
+use restate_core::network::tls::{TlsAcceptor, TlsConfigError};
 use restate_core::{TaskCenter, TaskKind, cancellation_watcher};
-use restate_types::config::IngressOptions;
+use restate_types::config::{IngressOptions, ListenerTlsOptions};
 use restate_types::errors::GenericError;
 use restate_types::health::HealthStatus;
 use restate_types::live::Live;
@@ -43,7 +45,8 @@ use restate_types::schema::service::ServiceMetadataResolver;
 use restate_util_time::DurationExt;
 
 use super::*;
-use crate::handler::Handler;
+use crate::auth::{Authenticator, AuthenticatorBuildError, authenticator_from_options};
+use crate::handler::{GrpcDescriptorSetError, GrpcIngress, Handler};
 use crate::metric_definitions::{HTTP_CONNECTION_CREATED, HTTP_CONNECTION_DROPPED};
 
 #[derive(Debug, thiserror::Error, CodedError)]
@@ -51,6 +54,15 @@ pub enum IngressServerError {
     #[error("error while running ingress http server: {0}")]
     #[code(unknown)]
     Running(#[from] hyper::Error),
+    #[error("cannot configure ingress authentication: {0}")]
+    #[code(unknown)]
+    Authentication(#[from] AuthenticatorBuildError),
+    #[error("cannot configure ingress TLS: {0}")]
+    #[code(unknown)]
+    Tls(#[from] TlsConfigError),
+    #[error("cannot configure ingress gRPC: {0}")]
+    #[code(unknown)]
+    Grpc(#[from] GrpcDescriptorSetError),
 }
 
 pub struct HyperServerIngress<Schemas, Dispatcher> {
@@ -58,6 +70,9 @@ pub struct HyperServerIngress<Schemas, Dispatcher> {
     concurrency_limit: usize,
     request_size_limit: usize,
     http2_max_concurrent_streams: Option<NonZeroU32>,
+    authenticator: Option<Arc<dyn Authenticator>>,
+    tls_options: Option<ListenerTlsOptions>,
+    grpc: Option<GrpcIngress>,
 
     // Parameters to build the layers
     schemas: Live<Schemas>,
@@ -77,9 +92,18 @@ where
         dispatcher: Dispatcher,
         schemas: Live<Schemas>,
         health: HealthStatus<IngressStatus>,
-    ) -> HyperServerIngress<Schemas, Dispatcher> {
+    ) -> Result<HyperServerIngress<Schemas, Dispatcher>, IngressServerError> {
         crate::metric_definitions::describe_metrics();
-        HyperServerIngress::new(
+        let authenticator = ingress_options
+            .auth()
+            .map(authenticator_from_options)
+            .transpose()?;
+        let grpc = ingress_options
+            .grpc()
+            .map(GrpcIngress::from_options)
+            .transpose()?;
+
+        Ok(HyperServerIngress::new(
             listeners,
             ingress_options.concurrent_api_requests_limit(),
             ingress_options.request_size_limit().get(),
@@ -88,6 +112,9 @@ where
             dispatcher,
             health,
         )
+        .with_authenticator(authenticator)
+        .with_tls_options(ingress_options.ingress_listener_options().tls().cloned())
+        .with_grpc(grpc))
     }
 }
 
@@ -112,12 +139,34 @@ where
             concurrency_limit,
             request_size_limit,
             http2_max_concurrent_streams,
+            authenticator: None,
+            tls_options: None,
+            grpc: None,
             schemas,
             dispatcher,
             health,
         }
     }
 
+    /// Sets the authenticator used to authenticate the ingress requests. Requests are not
+    /// authenticated if `None`.
+    pub fn with_authenticator(mut self, authenticator: Option<Arc<dyn Authenticator>>) -> Self {
+        self.authenticator = authenticator;
+        self
+    }
+
+    /// Serves the TCP listener over TLS if set.
+    pub fn with_tls_options(mut self, tls_options: Option<ListenerTlsOptions>) -> Self {
+        self.tls_options = tls_options;
+        self
+    }
+
+    /// Accepts gRPC requests if set.
+    pub fn with_grpc(mut self, grpc: Option<GrpcIngress>) -> Self {
+        self.grpc = grpc;
+        self
+    }
+
     #[instrument(
         level = "error",
         name = "server",
@@ -130,6 +179,9 @@ where
             concurrency_limit,
             request_size_limit,
             http2_max_concurrent_streams,
+            authenticator,
+            tls_options,
+            grpc,
             schemas,
             dispatcher,
             health,
@@ -187,9 +239,10 @@ where
             .layer(NormalizePathLayer::trim_trailing_slash())
             .layer(RequestBodyLimitLayer::new(request_size_limit))
             .layer(CorsLayer::very_permissive())
+            .layer(layers::auth::AuthenticationLayer::new(authenticator))
             .layer(layers::load_shed::LoadShedLayer::new(concurrency_limit))
             .layer(layers::tracing_context_extractor::HttpTraceContextExtractorLayer)
-            .service(Handler::new(schemas, dispatcher));
+            .service(Handler::new(schemas, dispatcher).with_grpc(grpc));
 
         // todo(azmy): `CorsLayer` should sit above `RequestBodyLimitLayer` so CORS is applied
         // as early as possible. This is currently blocked because `CorsLayer` requires the
@@ -197,6 +250,12 @@ where
         // Tracked upstream in https://github.com/tower-rs/tower-http/pull/679  once merged,
         // move `CorsLayer` above `RequestBodyLimitLayer`.
 
+        let tls_acceptor = tls_options
+            .as_ref()
+            .map(|tls_options| TlsAcceptor::start(tls_options, HttpIngressPort::NAME))
+            .transpose()
+            .map_err(IngressServerError::from)?;
+
         let mut shutdown = std::pin::pin!(cancellation_watcher());
 
         if let Some(uds_path) = listeners.uds_address() {
@@ -216,12 +275,31 @@ where
                     let (stream, peer_addr) = res?;
                     match stream {
                         Either::Left(tcp_stream) => {
-                            Self::handle_connection(
-                                tcp_stream,
-                                peer_addr,
-                                service.clone(),
-                                http2_max_concurrent_streams,
-                            )?;
+                            if let Some(tls_acceptor) = tls_acceptor.clone() {
+                                // Don't block the accept loop on the handshake
+                                let service = service.clone();
+                                TaskCenter::spawn(TaskKind::Ingress, "ingress-tls-handshake", async move {
+                                    match tls_acceptor.accept(tcp_stream).await {
+                                        Ok(tls_stream) => Self::handle_connection(
+                                            tls_stream,
+                                            peer_addr,
+                                            service,
+                                            http2_max_concurrent_streams,
+                                        ),
+                                        Err(err) => {
+                                            debug!("TLS handshake with {peer_addr} failed: {err}");
+                                            Ok(())
+                                        }
+                                    }
+                                })?;
+                            } else {
+                                Self::handle_connection(
+                                    tcp_stream,
+                                    peer_addr,
+                                    service.clone(),
+                                    http2_max_concurrent_streams,
+                                )?;
+                            }
                         }
                         Either::Right(unix_stream) => {
                             Self::handle_connection(
@@ -339,6 +417,7 @@ mod tests {
     use hyper_util::client::legacy::Client;
     use hyper_util::rt::TokioExecutor;
     use restate_core::TestCoreEnv;
+    use restate_core::network::tls::test_util::TestCertificates;
     use restate_core::{TaskCenter, TaskKind};
     use restate_hyper_uds::UnixSocketConnector;
     use restate_test_util::assert_eq;
@@ -399,6 +478,7 @@ mod tests {
         let socket_path = socket_dir.path().join("ingress.sock");
         bootstrap_test(
             Listeners::new_unix_listener(socket_path.clone()).unwrap(),
+            None,
             mock_dispatcher,
         )
         .await;
@@ -432,8 +512,102 @@ mod tests {
         restate_test_util::assert_eq!(response_value.greeting, "Igal");
     }
 
+    fn expect_greeting_call() -> MockRequestDispatcher {
+        let mut mock_dispatcher = MockRequestDispatcher::default();
+        mock_dispatcher
+            .expect_call()
+            .return_once(|invocation_request| {
+                Box::pin(ready(Ok(InvocationOutput {
+                    request_id: Default::default(),
+                    invocation_id: Some(invocation_request.invocation_id()),
+                    completion_expiry_time: None,
+                    response: InvocationOutputResponse::Success(
+                        InvocationTarget::service("greeter.Greeter", "greet"),
+                        serde_json::to_vec(&GreetingResponse {
+                            greeting: "Igal".to_string(),
+                        })
+                        .unwrap()
+                        .into(),
+                    ),
+                })))
+            });
+        mock_dispatcher
+    }
+
+    /// Sends a greeting over an HTTP/2 connection to the TLS listener at `address`.
+    async fn tls_post(
+        address: std::net::SocketAddr,
+        client_config: rustls::ClientConfig,
+    ) -> anyhow::Result<http::StatusCode> {
+        let tcp_stream = tokio::net::TcpStream::connect(address).await?;
+        let tls_stream = tokio_rustls::TlsConnector::from(Arc::new(client_config))
+            .connect(
+                rustls::pki_types::ServerName::try_from("localhost")?,
+                tcp_stream,
+            )
+            .await?;
+        let (mut sender, connection) =
+            hyper::client::conn::http2::handshake(TokioExecutor::new(), TokioIo::new(tls_stream))
+                .await?;
+        tokio::spawn(connection);
+
+        let response = sender
+            .send_request(
+                http::Request::post("https://localhost/greeter.Greeter/greet")
+                    .header(http::header::CONTENT_TYPE, "application/json")
+                    .body(Full::new(Bytes::from_static(br#"{"person":"Francesco"}"#)))?,
+            )
+            .await?;
+        Ok(response.status())
+    }
+
+    #[restate_core::test]
+    #[traced_test]
+    async fn https_post_with_client_certificate() {
+        let certificates = TestCertificates::generate();
+        let tls_dir = tempfile::tempdir().unwrap();
+        let (tls_options, _) = certificates.write_to(tls_dir.path());
+
+        let listeners = Listeners::new_tcp_listener("127.0.0.1:0".parse().unwrap())
+            .await
+            .unwrap();
+        let address = listeners.tcp_address().unwrap();
+        bootstrap_test(listeners, Some(tls_options), expect_greeting_call()).await;
+
+        let status = tls_post(address, certificates.client_config(true))
+            .await
+            .unwrap();
+        assert_eq!(status, http::StatusCode::OK);
+    }
+
+    #[restate_core::test]
+    #[traced_test]
+    async fn https_post_without_client_certificate_is_rejected() {
+        let certificates = TestCertificates::generate();
+        let tls_dir = tempfile::tempdir().unwrap();
+        let (tls_options, _) = certificates.write_to(tls_dir.path());
+
+        let listeners = Listeners::new_tcp_listener("127.0.0.1:0".parse().unwrap())
+            .await
+            .unwrap();
+        let address = listeners.tcp_address().unwrap();
+        bootstrap_test(
+            listeners,
+            Some(tls_options),
+            MockRequestDispatcher::default(),
+        )
+        .await;
+
+        assert!(
+            tls_post(address, certificates.client_config(false))
+                .await
+                .is_err()
+        );
+    }
+
     async fn bootstrap_test(
         listeners: Listeners<HttpIngressPort>,
+        tls_options: Option<ListenerTlsOptions>,
         mock_request_dispatcher: MockRequestDispatcher,
     ) {
         let _env = TestCoreEnv::create_with_single_node(1, 1).await;
@@ -448,7 +622,8 @@ mod tests {
             Live::from_value(mock_schemas()),
             Arc::new(mock_request_dispatcher),
             health.ingress_status(),
-        );
+        )
+        .with_tls_options(tls_options);
         TaskCenter::spawn(TaskKind::SystemService, "ingress", ingress.run()).unwrap();
     }
 }
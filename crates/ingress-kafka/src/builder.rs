@@ -9,16 +9,20 @@
 // by the Apache License, Version 2.0.
 
 use std::borrow::Borrow;
+use std::fmt;
+use std::sync::Arc;
 
 use anyhow::bail;
 use base64::Engine;
 use bytes::Bytes;
+use bytestring::ByteString;
+use metrics::counter;
 use opentelemetry::propagation::{Extractor, TextMapPropagator};
 use opentelemetry::trace::{Span, SpanContext, TraceContextExt};
 use opentelemetry_sdk::propagation::TraceContextPropagator;
 use rdkafka::Message;
 use rdkafka::message::BorrowedMessage;
-use tracing::{info_span, trace};
+use tracing::{info_span, trace, warn};
 
 use rdkafka::message::Headers;
 
@@ -35,6 +39,8 @@ use restate_util_string::{ReString, RestateString, RestrictedValueError};
 use restate_wal_protocol::{Command, Destination, Envelope, Source};
 
 use crate::Error;
+use crate::metric_definitions::KAFKA_INGRESS_SKIPPED_RECORDS;
+use crate::transform::EventTransform;
 
 #[derive(Clone)]
 pub struct EnvelopeBuilder {
@@ -42,6 +48,7 @@ pub struct EnvelopeBuilder {
     schema: Live<Schema>,
     // avoids creating a new string for each invocation
     subscription_id: String,
+    transform: Arc<EventTransform>,
 }
 
 impl EnvelopeBuilder {
@@ -50,33 +57,87 @@ impl EnvelopeBuilder {
             subscription_id: subscription.id().to_string(),
             subscription,
             schema,
+            transform: Arc::default(),
         }
     }
 
+    /// Filters and transforms the Kafka records with `transform` before building the envelopes.
+    pub fn with_transform(mut self, transform: EventTransform) -> Self {
+        self.transform = Arc::new(transform);
+        self
+    }
+
     pub fn subscription(&self) -> &Subscription {
         &self.subscription
     }
 
+    pub fn subscription_id(&self) -> &str {
+        &self.subscription_id
+    }
+
+    /// Builds the envelope for an event received from any source.
+    ///
+    /// `producer_id` identifies the producer of the event for deduplication purposes, while
+    /// `consumer` is the name of the consumer group/durable consumer that received it.
     pub fn build(
         &mut self,
         producer_id: u128,
-        consumer_group_id: &str,
-        msg: BorrowedMessage<'_>,
+        consumer: &str,
+        event: IngressEvent,
     ) -> Result<Envelope, Error> {
+        let system = event.origin.system();
+
         // Prepare ingress span
         let ingress_span = info_span!(
-            "kafka_ingress_consume",
-            otel.name = "kafka_ingress_consume",
-            messaging.system = "kafka",
+            "subscription_ingress_consume",
+            otel.name = %format_args!("{system}_ingress_consume"),
+            messaging.system = system,
             messaging.operation = "receive",
-            messaging.source.name = msg.topic(),
-            messaging.source.partition = msg.partition(),
+            messaging.source.name = event.origin.source_name(),
             messaging.destination.name = %self.subscription.sink(),
             restate.subscription.id = %self.subscription.id(),
-            messaging.consumer.group.name = consumer_group_id
+            messaging.consumer.group.name = consumer
         );
 
-        trace!(parent: &ingress_span, "Building Kafka ingress request");
+        trace!(parent: &ingress_span, "Building {system} ingress request");
+
+        let dedup = event
+            .origin
+            .sequence_number()
+            .map(|sequence_number| DedupInformation::producer(producer_id, sequence_number));
+
+        let origin = event.origin.clone();
+        let invocation = InvocationBuilder::create(
+            &self.subscription,
+            producer_id,
+            self.schema.live_load(),
+            event,
+            consumer,
+        )
+        .map_err(|cause| Error::Event {
+            subscription: self.subscription_id.clone(),
+            origin,
+            cause,
+        })?;
+
+        Ok(self.wrap_service_invocation_in_envelope(invocation, dedup))
+    }
+
+    /// Builds the envelope for a Kafka record, or returns `None` if the record is filtered out by
+    /// the transformation options of the subscription. Records which cannot be transformed, e.g.
+    /// because their payload cannot be decoded, are skipped as well, since retrying them would
+    /// block the partition forever.
+    pub fn build_kafka(
+        &mut self,
+        producer_id: u128,
+        consumer_group_id: &str,
+        msg: BorrowedMessage<'_>,
+    ) -> Result<Option<Envelope>, Error> {
+        let origin = EventOrigin::Kafka {
+            topic: msg.topic().to_string(),
+            partition: msg.partition(),
+            offset: msg.offset(),
+        };
 
         let key = if let Some(k) = msg.key() {
             Bytes::copy_from_slice(k)
@@ -89,60 +150,81 @@ impl EnvelopeBuilder {
             Bytes::default()
         };
 
-        let headers = Self::generate_events_attributes(&msg, &self.subscription_id);
-        let (scope, limit_key) = if restate_types::config::Configuration::pinned()
-            .common
-            .experimental
-            .is_kafka_scope_enabled()
-        {
-            extract_scope_limit_key(&msg).map_err(|err| Error::Event {
-                subscription: self.subscription_id.clone(),
-                topic: msg.topic().to_string(),
-                partition: msg.partition(),
-                offset: msg.offset(),
-                cause: anyhow::anyhow!("invalid scope value in x-restate-scope header: {err}"),
-            })?
-        } else {
-            (None, LimitKey::None)
+        let record_headers: Vec<_> = kafka_headers(&msg).collect();
+        let (key, payload) = match self.transform.apply(key, payload, &record_headers) {
+            Ok(Some(transformed)) => transformed,
+            Ok(None) => {
+                trace!(
+                    restate.subscription.id = %self.subscription_id,
+                    "Skipping Kafka record {origin}, which is filtered out"
+                );
+                return Ok(None);
+            }
+            Err(err) => {
+                warn!(
+                    restate.subscription.id = %self.subscription_id,
+                    "Skipping Kafka record {origin}, which cannot be transformed: {err:#}"
+                );
+                counter!(
+                    KAFKA_INGRESS_SKIPPED_RECORDS,
+                    "subscription" => self.subscription_id.clone(),
+                )
+                .increment(1);
+                return Ok(None);
+            }
         };
 
-        let dedup = DedupInformation::producer(producer_id, msg.offset() as u64);
+        let headers = Self::generate_events_attributes(&msg, &self.subscription_id);
+        let (scope, limit_key) = self.extract_scope_limit_key(&origin, record_headers)?;
 
-        let invocation = InvocationBuilder::create(
-            &self.subscription,
+        self.build(
             producer_id,
-            self.schema.live_load(),
-            key,
-            payload,
-            headers,
-            scope,
-            limit_key,
             consumer_group_id,
-            msg.topic(),
-            msg.partition(),
-            msg.offset(),
+            IngressEvent {
+                key,
+                payload,
+                headers,
+                scope,
+                limit_key,
+                idempotency_key: None,
+                origin,
+            },
         )
-        .map_err(|cause| Error::Event {
-            subscription: self.subscription_id.clone(),
-            topic: msg.topic().to_string(),
-            partition: msg.partition(),
-            offset: msg.offset(),
-            cause,
-        })?;
+        .map(Some)
+    }
 
-        Ok(self.wrap_service_invocation_in_envelope(invocation, dedup))
+    /// Reads the scope and limit key from the `x-restate-scope` and `x-restate-limit-key`
+    /// headers of an event, if the experimental support for them is enabled.
+    pub fn extract_scope_limit_key<'a>(
+        &self,
+        origin: &EventOrigin,
+        headers: impl IntoIterator<Item = (&'a str, &'a [u8])>,
+    ) -> Result<(Option<Scope>, LimitKey<ReString>), Error> {
+        if !restate_types::config::Configuration::pinned()
+            .common
+            .experimental
+            .is_kafka_scope_enabled()
+        {
+            return Ok((None, LimitKey::None));
+        }
+
+        extract_scope_limit_key(headers).map_err(|err| Error::Event {
+            subscription: self.subscription_id.clone(),
+            origin: origin.clone(),
+            cause: anyhow::anyhow!("invalid scope value in x-restate-scope header: {err}"),
+        })
     }
 
     fn wrap_service_invocation_in_envelope(
         &self,
         service_invocation: Box<ServiceInvocation>,
-        dedup_information: DedupInformation,
+        dedup_information: Option<DedupInformation>,
     ) -> Envelope {
         let header = restate_wal_protocol::Header {
             source: Source::Ingress {},
             dest: Destination::Processor {
                 partition_key: service_invocation.partition_key(),
-                dedup: Some(dedup_information),
+                dedup: dedup_information,
             },
         };
 
@@ -170,22 +252,114 @@ impl EnvelopeBuilder {
     }
 }
 
-pub(crate) fn extract_scope_limit_key(
-    msg: &impl rdkafka::Message,
-) -> Result<(Option<Scope>, LimitKey<ReString>), RestrictedValueError> {
-    let Some(kafka_headers) = msg.headers() else {
-        return Ok((None, LimitKey::None));
-    };
+/// Header of an event holding the key of the Virtual Object or Workflow target, for the sources
+/// without a native notion of record key.
+pub(crate) const KEY_HEADER: &str = "x-restate-key";
+
+/// A single event received from a subscription source, independent of the source protocol.
+#[derive(Debug)]
+pub struct IngressEvent {
+    /// Key of the event, used as key of the Virtual Object and Workflow targets.
+    pub key: Bytes,
+    pub payload: Bytes,
+    /// Attributes of the event, passed to the handler as headers.
+    pub headers: Vec<Header>,
+    pub scope: Option<Scope>,
+    pub limit_key: LimitKey<ReString>,
+    /// Idempotency key of the invocation, for the sources which cannot provide a sequence number
+    /// for deduplication.
+    pub idempotency_key: Option<ByteString>,
+    pub origin: EventOrigin,
+}
 
+/// Position of an event in its source.
+#[derive(Debug, Clone)]
+pub enum EventOrigin {
+    Kafka {
+        topic: String,
+        partition: i32,
+        offset: i64,
+    },
+    Nats {
+        stream: String,
+        stream_sequence: u64,
+        /// Whether JetStream delivered the message before, possibly to another node.
+        redelivered: bool,
+    },
+    Webhook {
+        name: String,
+    },
+}
+
+impl EventOrigin {
+    pub fn system(&self) -> &'static str {
+        match self {
+            EventOrigin::Kafka { .. } => "kafka",
+            EventOrigin::Nats { .. } => "nats",
+            EventOrigin::Webhook { .. } => "webhook",
+        }
+    }
+
+    pub fn source_name(&self) -> &str {
+        match self {
+            EventOrigin::Kafka { topic, .. } => topic,
+            EventOrigin::Nats { stream, .. } => stream,
+            EventOrigin::Webhook { name } => name,
+        }
+    }
+
+    /// Sequence number used to deduplicate the event, which must be increasing for each
+    /// producer. `None` if the source doesn't provide one.
+    pub fn sequence_number(&self) -> Option<u64> {
+        match self {
+            EventOrigin::Kafka { offset, .. } => Some(*offset as u64),
+            // Every node consuming the stream deduplicates with its own producer id, so the first
+            // delivery of a redelivered message may have been ingested by nobody.
+            EventOrigin::Nats {
+                stream_sequence,
+                redelivered,
+                ..
+            } => (!redelivered).then_some(*stream_sequence),
+            EventOrigin::Webhook { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for EventOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EventOrigin::Kafka {
+                topic,
+                partition,
+                offset,
+            } => write!(f, "topic {topic} partition {partition} offset {offset}"),
+            EventOrigin::Nats {
+                stream,
+                stream_sequence,
+                ..
+            } => write!(f, "stream {stream} sequence {stream_sequence}"),
+            EventOrigin::Webhook { name } => write!(f, "webhook {name}"),
+        }
+    }
+}
+
+fn kafka_headers<'a>(msg: &'a impl rdkafka::Message) -> impl Iterator<Item = (&'a str, &'a [u8])> {
+    msg.headers().into_iter().flat_map(|headers| {
+        (0..headers.count()).filter_map(move |idx| {
+            let header = headers.get(idx);
+            header.value.map(|value| (header.key, value))
+        })
+    })
+}
+
+pub(crate) fn extract_scope_limit_key<'a>(
+    headers: impl IntoIterator<Item = (&'a str, &'a [u8])>,
+) -> Result<(Option<Scope>, LimitKey<ReString>), RestrictedValueError> {
     let mut scope = None;
     let mut limit_key = LimitKey::None;
 
-    for idx in 0..kafka_headers.count() {
-        let header = kafka_headers.get(idx);
-        let Some(value) = header.value else {
-            continue;
-        };
-        match header.key {
+    for (key, value) in headers {
+        match key {
             "x-restate-scope" => {
                 if let Ok(s) = std::str::from_utf8(value)
                     && !s.is_empty()
@@ -211,24 +385,32 @@ pub(crate) fn extract_scope_limit_key(
 pub struct InvocationBuilder;
 
 impl InvocationBuilder {
-    #[allow(clippy::too_many_arguments)]
     pub fn create(
         subscription: &Subscription,
         producer_id: u128,
         schema: &Schema,
-        key: Bytes,
-        payload: Bytes,
-        headers: Vec<restate_types::invocation::Header>,
-        scope: Option<Scope>,
-        limit_key: LimitKey<ReString>,
-        consumer_group_id: &str,
-        topic: &str,
-        partition: i32,
-        offset: i64,
+        event: IngressEvent,
+        consumer: &str,
     ) -> Result<Box<ServiceInvocation>, anyhow::Error> {
+        let IngressEvent {
+            key,
+            payload,
+            headers,
+            scope,
+            limit_key,
+            idempotency_key,
+            origin,
+        } = event;
+
         let Sink::Invocation {
             event_invocation_target_template,
-        } = subscription.sink();
+        } = subscription.sink()
+        else {
+            bail!(
+                "subscription {} does not invoke a handler",
+                subscription.id()
+            );
+        };
 
         let invocation_target = match event_invocation_target_template {
             EventInvocationTargetTemplate::Service { name, handler } => {
@@ -241,7 +423,7 @@ impl InvocationBuilder {
             } => InvocationTarget::virtual_object(
                 name.clone(),
                 std::str::from_utf8(&key)
-                    .map_err(|e| anyhow::anyhow!("The Kafka record key must be valid UTF-8: {e}"))?
+                    .map_err(|e| anyhow::anyhow!("The event key must be valid UTF-8: {e}"))?
                     .to_owned(),
                 handler.clone(),
                 *handler_ty,
@@ -253,7 +435,7 @@ impl InvocationBuilder {
             } => InvocationTarget::workflow(
                 name.clone(),
                 std::str::from_utf8(&key)
-                    .map_err(|e| anyhow::anyhow!("The Kafka record key must be valid UTF-8: {e}"))?
+                    .map_err(|e| anyhow::anyhow!("The event key must be valid UTF-8: {e}"))?
                     .to_owned(),
                 handler.clone(),
                 *handler_ty,
@@ -302,26 +484,30 @@ impl InvocationBuilder {
             )
         }
 
-        let invocation_retention = target.compute_retention(false);
-
-        let seed = KafkaPartitionKeySeed {
-            producer: &producer_id,
-            offset: &offset,
+        let invocation_retention = target.compute_retention(idempotency_key.is_some());
+
+        let invocation_id = match origin.sequence_number() {
+            Some(sequence_number) => {
+                let seed = KafkaPartitionKeySeed {
+                    producer: &producer_id,
+                    offset: &(sequence_number as i64),
+                };
+                InvocationId::generate_or_else(
+                    &invocation_target,
+                    idempotency_key.as_deref(),
+                    || partitioner::HashPartitioner::compute_partition_key(seed),
+                )
+            }
+            None => InvocationId::generate(&invocation_target, idempotency_key.as_deref()),
         };
 
-        let invocation_id = InvocationId::generate_or_else(&invocation_target, None, || {
-            partitioner::HashPartitioner::compute_partition_key(seed)
-        });
-
         // Figure out tracing span
         let ingress_span_context = prepare_tracing_span(
             &invocation_id,
             &invocation_target,
             &headers,
-            consumer_group_id,
-            topic,
-            partition as i64,
-            offset,
+            consumer,
+            &origin,
         );
 
         // Finally generate service invocation
@@ -334,6 +520,7 @@ impl InvocationBuilder {
         service_invocation.argument = payload;
         service_invocation.headers = headers;
         service_invocation.limit_key = limit_key;
+        service_invocation.idempotency_key = idempotency_key;
         service_invocation.with_retention(invocation_retention);
 
         Ok(service_invocation)
@@ -342,21 +529,19 @@ impl InvocationBuilder {
 
 #[derive(Hash)]
 /// Hashable seed that yields a deterministic partition key for service invocations, keeping
-/// identical invocations on the same partition for deduplication.
+/// identical invocations on the same partition for deduplication. Used for every source
+/// providing a sequence number, not only Kafka.
 struct KafkaPartitionKeySeed<'a> {
     producer: &'a u128,
     offset: &'a i64,
 }
 
-#[allow(clippy::too_many_arguments)]
 pub(crate) fn prepare_tracing_span(
     invocation_id: &InvocationId,
     invocation_target: &InvocationTarget,
     headers: &[restate_types::invocation::Header],
-    consumer_group_name: &str,
-    topic: &str,
-    partition: i64,
-    offset: i64,
+    consumer: &str,
+    origin: &EventOrigin,
 ) -> SpanContext {
     let tracing_context = TraceContextPropagator::new().extract(&HeaderExtractor(headers));
     let inbound_span = tracing_context.span();
@@ -367,20 +552,38 @@ pub(crate) fn prepare_tracing_span(
         SpanRelation::None
     };
 
-    let span = restate_tracing_instrumentation::info_invocation_span!(
-        relation = relation,
-        prefix = "ingress_kafka",
-        id = invocation_id,
-        target = invocation_target,
-        tags = (
-            messaging.system = "kafka",
-            messaging.consumer.group.name = consumer_group_name.to_owned(),
-            messaging.operation.type = "process",
-            messaging.kafka.offset = offset,
-            messaging.source.partition.id = partition,
-            messaging.source.name = topic.to_owned()
-        )
-    );
+    let span = match origin {
+        EventOrigin::Kafka {
+            topic,
+            partition,
+            offset,
+        } => restate_tracing_instrumentation::info_invocation_span!(
+            relation = relation,
+            prefix = "ingress_kafka",
+            id = invocation_id,
+            target = invocation_target,
+            tags = (
+                messaging.system = "kafka",
+                messaging.consumer.group.name = consumer.to_owned(),
+                messaging.operation.type = "process",
+                messaging.kafka.offset = *offset,
+                messaging.source.partition.id = *partition as i64,
+                messaging.source.name = topic.to_owned()
+            )
+        ),
+        origin => restate_tracing_instrumentation::info_invocation_span!(
+            relation = relation,
+            prefix = format!("ingress_{}", origin.system()),
+            id = invocation_id,
+            target = invocation_target,
+            tags = (
+                messaging.system = origin.system(),
+                messaging.consumer.group.name = consumer.to_owned(),
+                messaging.operation.type = "process",
+                messaging.source.name = origin.source_name().to_owned()
+            )
+        ),
+    };
 
     span.span_context().clone()
 }
@@ -0,0 +1,85 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use parking_lot::RwLock;
+
+use restate_types::identifiers::SubscriptionId;
+
+/// Consumption progress of a Kafka partition assigned to this node.
+#[derive(Debug, Clone)]
+pub struct PartitionConsumerLag {
+    pub subscription_id: SubscriptionId,
+    pub consumer_group: String,
+    pub topic: String,
+    pub partition: i32,
+    /// Offset of the next record to ingest, known once a record has been ingested.
+    pub next_offset: Option<i64>,
+    /// High watermark of the partition, as last reported by the broker.
+    pub high_watermark: Option<i64>,
+}
+
+impl PartitionConsumerLag {
+    /// Number of records produced to the partition which haven't been ingested yet.
+    pub fn lag(&self) -> Option<i64> {
+        Some((self.high_watermark? - self.next_offset?).max(0))
+    }
+}
+
+/// Registry of the Kafka partitions consumed by the subscriptions running on this node.
+#[derive(Debug, Clone, Default)]
+pub struct ConsumerLagRegistry {
+    next_id: Arc<AtomicU64>,
+    partitions: Arc<RwLock<HashMap<u64, PartitionConsumerLag>>>,
+}
+
+impl ConsumerLagRegistry {
+    pub fn snapshot(&self) -> Vec<PartitionConsumerLag> {
+        self.partitions.read().values().cloned().collect()
+    }
+
+    /// Registers a partition, until the returned guard is dropped.
+    pub(crate) fn register(&self, partition: PartitionConsumerLag) -> PartitionLagGuard {
+        // Partitions are identified by a unique id rather than by subscription and partition,
+        // since the consumer of a revoked partition might be dropped after the consumer of the
+        // same partition assigned again.
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.partitions.write().insert(id, partition);
+        PartitionLagGuard {
+            id,
+            registry: self.clone(),
+        }
+    }
+}
+
+pub(crate) struct PartitionLagGuard {
+    id: u64,
+    registry: ConsumerLagRegistry,
+}
+
+impl PartitionLagGuard {
+    pub(crate) fn update(&self, next_offset: i64, high_watermark: Option<i64>) {
+        if let Some(partition) = self.registry.partitions.write().get_mut(&self.id) {
+            partition.next_offset = Some(next_offset);
+            if high_watermark.is_some() {
+                partition.high_watermark = high_watermark;
+            }
+        }
+    }
+}
+
+impl Drop for PartitionLagGuard {
+    fn drop(&mut self) {
+        self.registry.partitions.write().remove(&self.id);
+    }
+}
@@ -25,7 +25,7 @@ use rdkafka::consumer::{
 use rdkafka::error::KafkaError;
 use rdkafka::topic_partition_list::TopicPartitionListElem;
 use rdkafka::types::RDKafkaErrorCode;
-use rdkafka::{ClientConfig, ClientContext, Message, Statistics};
+use rdkafka::{ClientConfig, ClientContext, Message, Offset, Statistics, TopicPartitionList};
 
 use restate_core::network::{NetworkSender, Swimlane, TransportConnect};
 use restate_core::{Metadata, TaskCenter, TaskHandle, TaskKind, task_center};
@@ -35,23 +35,34 @@ use restate_types::identifiers::{SubscriptionId, WithPartitionKey};
 use restate_types::net::ingest::{DedupSequenceNrQueryRequest, ProducerId, ResponseStatus};
 use restate_types::partitions::FindPartition;
 use restate_types::retries::RetryPolicy;
-use restate_types::schema::subscriptions::{EventInvocationTargetTemplate, Sink};
+use restate_types::schema::subscriptions::{
+    EventInvocationTargetTemplate, SeekPosition, Sink, SubscriptionSeek,
+};
 use restate_wal_protocol::Envelope;
 use tokio::sync::{mpsc, oneshot};
-use tracing::{debug, instrument, trace, warn};
+use tracing::{debug, info, instrument, trace, warn};
 
 use crate::Error;
 use crate::builder::EnvelopeBuilder;
+use crate::consumer_lag::{ConsumerLagRegistry, PartitionConsumerLag};
 use crate::metric_definitions::{KAFKA_INGRESS_CONSUMER_LAG, KAFKA_INGRESS_REQUESTS};
+use crate::transform::EventTransform;
 
 type MessageConsumer<T> = StreamConsumer<RebalanceContext<T>>;
 
+/// Timeout of the blocking operations of a seek.
+const SEEK_TIMEOUT: Duration = Duration::from_secs(10);
+/// Prefix of the metadata committed with the offsets of the partitions moved by a seek, followed
+/// by the generation of the last seek applied to the partition.
+const SEEK_GENERATION_METADATA_PREFIX: &str = "restate.seek-generation=";
+
 #[derive(Clone)]
 pub struct ConsumerTask<T> {
     client_config: ClientConfig,
     topics: Vec<String>,
     ingestion: IngestionClient<T, Envelope>,
     builder: EnvelopeBuilder,
+    consumer_lag: ConsumerLagRegistry,
 }
 
 impl<T> ConsumerTask<T>
@@ -63,12 +74,14 @@ where
         topics: Vec<String>,
         ingestion: IngestionClient<T, Envelope>,
         builder: EnvelopeBuilder,
+        consumer_lag: ConsumerLagRegistry,
     ) -> Self {
         Self {
             client_config,
             topics,
             ingestion,
             builder,
+            consumer_lag,
         }
     }
 
@@ -86,6 +99,9 @@ where
             self.topics, self.client_config
         );
 
+        let transform = EventTransform::from_metadata(self.builder.subscription().metadata())
+            .map_err(Error::InvalidTransform)?;
+
         let (failures_tx, failures_rx) = mpsc::unbounded_channel();
 
         let rebalance_context = RebalanceContext {
@@ -94,8 +110,9 @@ where
             topic_partition_tasks: parking_lot::Mutex::new(HashMap::new()),
             failures_tx,
             ingestion: self.ingestion.clone(),
-            builder: self.builder.clone(),
+            builder: self.builder.clone().with_transform(transform),
             consumer_group_id,
+            consumer_lag: self.consumer_lag.clone(),
         };
         let consumer: Arc<MessageConsumer<T>> =
             Arc::new(self.client_config.create_with_context(rebalance_context)?);
@@ -170,6 +187,7 @@ struct RebalanceContext<T: TransportConnect> {
     ingestion: IngestionClient<T, Envelope>,
     builder: EnvelopeBuilder,
     consumer_group_id: String,
+    consumer_lag: ConsumerLagRegistry,
 }
 
 impl<T> ClientContext for RebalanceContext<T>
@@ -249,6 +267,7 @@ where
                                 Arc::clone(&consumer),
                                 self.consumer_group_id.clone(),
                                 self.failures_tx.clone(),
+                                self.consumer_lag.clone(),
                             );
 
                             if let Ok(task_handle) = self.task_center_handle.spawn_unmanaged(
@@ -331,6 +350,9 @@ where
     consumer: Arc<MessageConsumer<T>>,
     consumer_group_id: String,
     failed: mpsc::UnboundedSender<Error>,
+    consumer_lag: ConsumerLagRegistry,
+    /// Generation of the last seek applied to the partition, which is part of the producer id.
+    seek_generation: u32,
 }
 
 impl<T, C> TopicPartitionConsumptionTask<T, C>
@@ -346,6 +368,7 @@ where
         consumer: Arc<MessageConsumer<T>>,
         consumer_group_id: String,
         failed: mpsc::UnboundedSender<Error>,
+        consumer_lag: ConsumerLagRegistry,
     ) -> Self {
         Self {
             ingestion,
@@ -355,6 +378,8 @@ where
             consumer,
             consumer_group_id,
             failed,
+            consumer_lag,
+            seek_generation: 0,
         }
     }
 
@@ -374,6 +399,10 @@ where
 
     /// query the legacy dedup information for this consumption task.
     async fn legacy_dedup_offset(&self) -> Option<u64> {
+        if self.seek_generation > 0 {
+            // the records consumed after a seek are deduplicated with a new producer id
+            return None;
+        }
         if !matches!(
             self.builder.subscription().sink(),
             Sink::Invocation {
@@ -468,6 +497,141 @@ where
         .expect("tries forever")
     }
 
+    /// Reads the generation of the last seek applied to this partition from the metadata of its
+    /// committed offset.
+    async fn committed_seek_generation(&self) -> Result<u32, Error> {
+        let committed_once = || {
+            let consumer = Arc::clone(&self.consumer);
+            let TopicPartition(topic, partition) = self.topic_partition.clone();
+            async move {
+                tokio::task::spawn_blocking(move || {
+                    let mut committed = TopicPartitionList::new();
+                    committed.add_partition(&topic, partition);
+                    let committed = consumer.committed_offsets(committed, SEEK_TIMEOUT)?;
+                    Ok::<_, KafkaError>(
+                        committed
+                            .find_partition(&topic, partition)
+                            .and_then(|elem| {
+                                elem.metadata()
+                                    .strip_prefix(SEEK_GENERATION_METADATA_PREFIX)?
+                                    .parse()
+                                    .ok()
+                            })
+                            .unwrap_or_default(),
+                    )
+                })
+                .await
+                .expect("reading the committed offsets does not panic")
+            }
+        };
+
+        Ok(RetryPolicy::exponential(
+            Duration::from_millis(50),
+            2.0,
+            Some(10),
+            Some(Duration::from_secs(1)),
+        )
+        .retry(committed_once)
+        .await?)
+    }
+
+    /// Returns the last seek of the subscription if it moves this partition, and it was not
+    /// applied to it yet.
+    fn pending_seek(&self) -> Option<SubscriptionSeek> {
+        let consumption = self.builder.subscription().consumption();
+        let generation = consumption.seek_generation(self.topic_partition.1);
+        if generation <= self.seek_generation {
+            return None;
+        }
+        match consumption.last_seek() {
+            Some(seek) if seek.generation == generation => Some(seek.clone()),
+            _ => {
+                // Only the last seek is known, keep consuming from the committed offset
+                warn!(
+                    "Seek {generation} was superseded by a seek of another partition before being applied to this one, ignoring it"
+                );
+                None
+            }
+        }
+    }
+
+    /// Moves the consumer of this partition to the position of `seek`, and commits the new offset
+    /// together with the generation of the seek, so that both survive restarts even if no record
+    /// is ingested in the meantime.
+    async fn seek(&self, seek: &SubscriptionSeek) -> Result<(), Error> {
+        let position = seek.position;
+        let generation = seek.generation;
+        let seek_once = || {
+            let consumer = Arc::clone(&self.consumer);
+            let TopicPartition(topic, partition) = self.topic_partition.clone();
+            async move {
+                tokio::task::spawn_blocking(move || {
+                    let (low, high) = consumer.fetch_watermarks(&topic, partition, SEEK_TIMEOUT)?;
+                    let offset = match position {
+                        SeekPosition::Earliest => low,
+                        SeekPosition::Latest => high,
+                        SeekPosition::Offset(offset) => offset.clamp(low, high),
+                        SeekPosition::Timestamp(timestamp) => {
+                            let mut timestamps = TopicPartitionList::new();
+                            timestamps.add_partition_offset(
+                                &topic,
+                                partition,
+                                Offset::Offset(timestamp.as_u64() as i64),
+                            )?;
+                            match consumer
+                                .offsets_for_times(timestamps, SEEK_TIMEOUT)?
+                                .find_partition(&topic, partition)
+                                .map(|elem| elem.offset())
+                            {
+                                Some(Offset::Offset(offset)) => offset,
+                                // no record at or after the timestamp
+                                _ => high,
+                            }
+                        }
+                    };
+
+                    consumer.seek(&topic, partition, Offset::Offset(offset), SEEK_TIMEOUT)?;
+                    let mut committed = TopicPartitionList::new();
+                    let mut elem = committed.add_partition(&topic, partition);
+                    elem.set_offset(Offset::Offset(offset))?;
+                    elem.set_metadata(format!("{SEEK_GENERATION_METADATA_PREFIX}{generation}"));
+                    consumer.commit(&committed, CommitMode::Sync)?;
+                    Ok::<_, KafkaError>(offset)
+                })
+                .await
+                .expect("seeking does not panic")
+            }
+        };
+
+        // The partition might not be assigned yet when the consumption task starts
+        let offset = RetryPolicy::exponential(
+            Duration::from_millis(50),
+            2.0,
+            Some(10),
+            Some(Duration::from_secs(1)),
+        )
+        .retry(seek_once)
+        .await?;
+
+        info!("Moved to offset {offset} after seeking to {position}");
+        Ok(())
+    }
+
+    /// Stores the offset following `offset` for the next commit, together with the generation of
+    /// the last seek applied to this partition.
+    fn store_offset(&self, offset: i64) -> Result<(), KafkaError> {
+        let mut offsets = TopicPartitionList::new();
+        let mut elem = offsets.add_partition(&self.topic_partition.0, self.topic_partition.1);
+        elem.set_offset(Offset::Offset(offset + 1))?;
+        if self.seek_generation > 0 {
+            elem.set_metadata(format!(
+                "{SEEK_GENERATION_METADATA_PREFIX}{}",
+                self.seek_generation
+            ));
+        }
+        self.consumer.store_offsets(&offsets)
+    }
+
     #[instrument(skip(self), fields(
         restate.subscription.id = %self.builder.subscription().id(),
         topic=%self.topic_partition.0,
@@ -477,6 +641,14 @@ where
     async fn run_inner(&mut self) -> Result<(), Error> {
         debug!("Starting topic consumption loop");
 
+        // The producer id changes only once the seek has been applied, since the records consumed
+        // before are deduplicated with the producer id of the previous generation
+        self.seek_generation = self.committed_seek_generation().await?;
+        if let Some(seek) = self.pending_seek() {
+            self.seek(&seek).await?;
+            self.seek_generation = seek.generation;
+        }
+
         let legacy_dedup_offset = self.legacy_dedup_offset().await;
         debug!("Legacy dedup offset: {legacy_dedup_offset:?}",);
 
@@ -485,8 +657,18 @@ where
             &self.consumer_group_id,
             &self.topic_partition.0,
             self.topic_partition.1,
+            self.seek_generation,
         );
 
+        let lag = self.consumer_lag.register(PartitionConsumerLag {
+            subscription_id: self.builder.subscription().id(),
+            consumer_group: self.consumer_group_id.clone(),
+            topic: self.topic_partition.0.clone(),
+            partition: self.topic_partition.1,
+            next_offset: None,
+            high_watermark: None,
+        });
+
         let ingress_request_counter = counter!(
             KAFKA_INGRESS_REQUESTS,
             "subscription" => self.builder.subscription().id().to_string(),
@@ -510,7 +692,14 @@ where
                         "Store kafka offset",
                     );
 
-                    self.consumer.store_offset(&self.topic_partition.0, self.topic_partition.1, offset)?;
+                    self.store_offset(offset)?;
+                    // cached by librdkafka from the fetch responses, doesn't query the broker
+                    let high_watermark = self
+                        .consumer
+                        .get_watermark_offsets(&self.topic_partition.0, self.topic_partition.1)
+                        .ok()
+                        .map(|(_, high)| high);
+                    lag.update(offset + 1, high_watermark);
                 },
                 Some(received) = consumer_stream.next() => {
                     let msg = received?;
@@ -521,7 +710,7 @@ where
                             offset=%offset,
                             "Skipping kafka message (dedup)"
                         );
-                        self.consumer.store_offset(&self.topic_partition.0, self.topic_partition.1, offset)?;
+                        self.store_offset(offset)?;
                         continue;
                     }
 
@@ -530,13 +719,15 @@ where
                         "Ingesting kafka message"
                     );
 
-                    let envelope = self.builder.build(producer_id, &self.consumer_group_id, msg)?;
-
-                    let commit_token = self
-                        .ingestion
-                        .ingest(envelope.partition_key(), envelope)
-                        .await?
-                        .map(|_| offset);
+                    let commit_token = match self.builder.build_kafka(producer_id, &self.consumer_group_id, msg)? {
+                        Some(envelope) => self
+                            .ingestion
+                            .ingest(envelope.partition_key(), envelope)
+                            .await?
+                            .map(|_| offset),
+                        // Filtered and skipped records are committed in order with the ingested ones
+                        None => RecordCommit::committed(offset),
+                    };
 
                     inflight.push_back(commit_token);
                 }
@@ -559,6 +750,7 @@ fn dedup_producer_id(
     consumer_group: &str,
     topic: &str,
     partition: i32,
+    seek_generation: u32,
 ) -> u128 {
     let mut hasher = xxhash_rust::xxh3::Xxh3::new();
 
@@ -569,6 +761,11 @@ fn dedup_producer_id(
     topic.hash(&mut hasher);
     '\0'.hash(&mut hasher);
     partition.hash(&mut hasher);
+    // Partitions never sought keep the producer id they had before seeks were introduced
+    if seek_generation > 0 {
+        '\0'.hash(&mut hasher);
+        seek_generation.hash(&mut hasher);
+    }
 
     hasher.digest128()
 }
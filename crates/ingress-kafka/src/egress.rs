@@ -0,0 +1,341 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Kafka egress.
+//!
+//! Handlers publish to a virtual topic with one-way calls to [`VIRTUAL_TOPIC_SERVICE_NAME`].
+//! These calls are written to the outbox of the calling partition like any other call, and the
+//! shuffle of the partition hands them to [`KafkaEgress`] instead of ingesting them. The outputs
+//! of the handlers with a [`Source::Output`] subscription are written to the outbox as calls to
+//! the virtual topic [`Source::virtual_topic`] when their invocations complete.
+//!
+//! Every egress subscription gets a transactional producer per partition. Each record is
+//! published in its own transaction, which also commits the outbox sequence number as offset of
+//! the consumer group `<group.id>-<partition id>`. When a new leader takes over, initializing the
+//! transactions fences the producer of the previous leader, and the committed offset tells which
+//! outbox messages have already been published. This gives exactly-once publishing even though
+//! the outbox is truncated only after the records have been published.
+//!
+//! Records are never dropped: a record which cannot be published is retried until it succeeds,
+//! holding back the following outbox messages of the partition.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use metrics::counter;
+use rdkafka::consumer::{BaseConsumer, Consumer, ConsumerGroupMetadata};
+use rdkafka::message::{Header, OwnedHeaders};
+use rdkafka::producer::{FutureProducer, FutureRecord, Producer};
+use rdkafka::util::Timeout;
+use rdkafka::{ClientConfig, Offset, TopicPartitionList};
+use tracing::{debug, warn};
+
+use restate_types::identifiers::{PartitionId, SubscriptionId};
+use restate_types::invocation::{InvocationTarget, ServiceInvocation};
+use restate_types::live::Live;
+use restate_types::message::MessageIndex;
+use restate_types::retries::RetryPolicy;
+use restate_types::schema::Redaction;
+use restate_types::schema::Schema;
+use restate_types::schema::kafka::KafkaClusterResolver;
+use restate_types::schema::subscriptions::{
+    ListSubscriptionFilter, RESTATE_PROPERTY_PREFIX, Sink, Source, Subscription,
+    SubscriptionResolver, VIRTUAL_TOPIC_SERVICE_NAME,
+};
+
+use crate::Error;
+use crate::metric_definitions::{KAFKA_EGRESS_PUBLISH_FAILURES, KAFKA_EGRESS_RECORDS};
+
+/// Timeout of the blocking transactional operations of the producers.
+const TRANSACTION_TIMEOUT: Duration = Duration::from_secs(30);
+/// Partition of the egress topic the publishing progress is committed for.
+const PROGRESS_PARTITION: i32 = 0;
+
+/// Publishes the records of the virtual topics called by a partition to their Kafka sinks.
+pub struct KafkaEgress {
+    partition_id: PartitionId,
+    schema: Live<Schema>,
+    producers: HashMap<SubscriptionId, EgressProducer>,
+}
+
+impl KafkaEgress {
+    pub fn new(partition_id: PartitionId, schema: Live<Schema>) -> Self {
+        Self {
+            partition_id,
+            schema,
+            producers: HashMap::new(),
+        }
+    }
+
+    /// Returns true if the invocation target is a virtual topic, whose invocations must be
+    /// published with [`KafkaEgress::publish`].
+    pub fn is_virtual_topic(target: &InvocationTarget) -> bool {
+        target.service_name() == VIRTUAL_TOPIC_SERVICE_NAME
+    }
+
+    /// Publishes like [`KafkaEgress::publish`], retrying until the record is published. The delays
+    /// between the attempts follow `retry_policy`, whose last delay is repeated once the policy is
+    /// exhausted.
+    pub async fn publish_with_retry(
+        &mut self,
+        sequence_number: MessageIndex,
+        invocation: &ServiceInvocation,
+        retry_policy: &RetryPolicy,
+    ) {
+        let topic_name = invocation.invocation_target.handler_name();
+        let mut retry_iter = retry_policy.iter();
+        let mut delay = Duration::ZERO;
+        while let Err(err) = self.publish(sequence_number, invocation).await {
+            counter!(KAFKA_EGRESS_PUBLISH_FAILURES, "topic" => topic_name.to_string()).increment(1);
+            delay = retry_iter.next().unwrap_or(delay);
+            warn!(
+                %err,
+                restate.invocation.id = %invocation.invocation_id,
+                "Failed publishing outbox message {sequence_number} for virtual topic '{topic_name}' to Kafka, retrying in {delay:?}"
+            );
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// Publishes the invocation written to the outbox with `sequence_number` to the Kafka sinks of
+    /// its virtual topic. Records already published by a previous leader are skipped.
+    ///
+    /// On error, the producers are dropped and the call can be retried.
+    pub async fn publish(
+        &mut self,
+        sequence_number: MessageIndex,
+        invocation: &ServiceInvocation,
+    ) -> Result<(), Error> {
+        let topic_name = invocation.invocation_target.handler_name();
+        let subscriptions = self.schema.live_load().list_subscriptions(
+            &[ListSubscriptionFilter::ExactMatchSource(
+                Source::from_virtual_topic(topic_name).to_string(),
+            )],
+            Redaction::No,
+        );
+
+        if subscriptions.is_empty() {
+            warn!(
+                restate.invocation.id = %invocation.invocation_id,
+                "Dropping record for virtual topic '{topic_name}', which has no subscriptions anymore"
+            );
+        }
+
+        for subscription in subscriptions {
+            if let Err(err) = self
+                .publish_to(&subscription, sequence_number, invocation)
+                .await
+            {
+                // Recreate the producer, since the transaction might be in an unknown state
+                self.producers.remove(&subscription.id());
+                return Err(err);
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn publish_to(
+        &mut self,
+        subscription: &Subscription,
+        sequence_number: MessageIndex,
+        invocation: &ServiceInvocation,
+    ) -> Result<(), Error> {
+        let subscription_id = subscription.id();
+        if self
+            .producers
+            .get(&subscription_id)
+            .is_none_or(|producer| producer.subscription != *subscription)
+        {
+            let producer = self.create_producer(subscription.clone()).await?;
+            self.producers.insert(subscription_id, producer);
+        }
+        let producer = self
+            .producers
+            .get_mut(&subscription_id)
+            .expect("producer to exist");
+
+        if sequence_number < producer.next_sequence_number {
+            debug!(
+                restate.subscription.id = %subscription_id,
+                "Skipping outbox message {sequence_number}, which has already been published"
+            );
+            return Ok(());
+        }
+
+        producer.publish(sequence_number, invocation).await?;
+        producer.next_sequence_number = sequence_number + 1;
+
+        counter!(
+            KAFKA_EGRESS_RECORDS,
+            "subscription" => subscription_id.to_string(),
+            "topic" => producer.topic.clone(),
+        )
+        .increment(1);
+
+        Ok(())
+    }
+
+    async fn create_producer(
+        &mut self,
+        subscription: Subscription,
+    ) -> Result<EgressProducer, Error> {
+        let Sink::Kafka { cluster, topic } = subscription.sink().clone() else {
+            return Err(Error::NotEgressSubscription(subscription.id()));
+        };
+        let cluster_properties = self
+            .schema
+            .live_load()
+            .get_kafka_cluster(&cluster, Redaction::No)
+            .map(|cluster| cluster.properties)
+            .unwrap_or_default();
+
+        let mut client_config = ClientConfig::new();
+        // enabling probing for the ca certificates if the user does not specify anything else
+        client_config.set("https.ca.location", "probe");
+
+        // Subscription metadata takes precedence over cluster properties
+        for (k, v) in cluster_properties.iter().chain(subscription.metadata()) {
+            if !k.starts_with(RESTATE_PROPERTY_PREFIX) {
+                client_config.set(k, v);
+            }
+        }
+
+        let base_group_id = subscription
+            .metadata()
+            .get("group.id")
+            .cloned()
+            .unwrap_or_else(|| subscription.id().to_string());
+        let group_id = format!("{base_group_id}-{}", self.partition_id);
+        client_config.set("group.id", &group_id);
+        client_config.set("transactional.id", &group_id);
+        client_config.set("enable.idempotence", "true");
+        client_config.set("enable.auto.commit", "false");
+        client_config.set("isolation.level", "read_committed");
+
+        debug!(
+            restate.subscription.id = %subscription.id(),
+            "Creating transactional producer {group_id} for Kafka topic {topic}"
+        );
+
+        tokio::task::spawn_blocking(move || {
+            let producer: FutureProducer = client_config.create()?;
+            // Fences the producer of the previous leader, and aborts its pending transaction
+            producer.init_transactions(TRANSACTION_TIMEOUT)?;
+
+            let consumer: BaseConsumer = client_config.create()?;
+            let mut progress = TopicPartitionList::new();
+            progress.add_partition(&topic, PROGRESS_PARTITION);
+            let next_sequence_number = match consumer
+                .committed_offsets(progress, TRANSACTION_TIMEOUT)?
+                .find_partition(&topic, PROGRESS_PARTITION)
+                .map(|elem| elem.offset())
+            {
+                Some(Offset::Offset(offset)) => offset as MessageIndex,
+                _ => 0,
+            };
+            let group_metadata = consumer
+                .group_metadata()
+                .expect("consumers with a group.id have group metadata");
+
+            Ok(EgressProducer {
+                subscription,
+                topic,
+                producer,
+                group_metadata: Arc::new(group_metadata),
+                next_sequence_number,
+            })
+        })
+        .await
+        .expect("creating the producer does not panic")
+    }
+}
+
+struct EgressProducer {
+    subscription: Subscription,
+    topic: String,
+    producer: FutureProducer,
+    group_metadata: Arc<ConsumerGroupMetadata>,
+    /// First outbox sequence number which has not been published yet.
+    next_sequence_number: MessageIndex,
+}
+
+impl EgressProducer {
+    async fn publish(
+        &self,
+        sequence_number: MessageIndex,
+        invocation: &ServiceInvocation,
+    ) -> Result<(), Error> {
+        self.producer.begin_transaction()?;
+
+        let invocation_id = invocation.invocation_id.to_string();
+        let mut headers = OwnedHeaders::new().insert(Header {
+            key: "restate.invocation.id",
+            value: Some(invocation_id.as_bytes()),
+        });
+        for header in &invocation.headers {
+            headers = headers.insert(Header {
+                key: &header.name,
+                value: Some(header.value.as_bytes().as_ref()),
+            });
+        }
+
+        let record = FutureRecord {
+            topic: &self.topic,
+            partition: None,
+            payload: Some(invocation.argument.as_ref()),
+            // Calls without key are spread across the Kafka partitions
+            key: invocation
+                .invocation_target
+                .key()
+                .filter(|key| !key.is_empty())
+                .map(|key| key.as_bytes().as_ref()),
+            timestamp: None,
+            headers: Some(headers),
+        };
+
+        let result = self
+            .producer
+            .send(record, Timeout::Never)
+            .await
+            .map_err(|(err, _)| err);
+        if let Err(err) = result {
+            let producer = self.producer.clone();
+            let _ = tokio::task::spawn_blocking(move || {
+                producer.abort_transaction(TRANSACTION_TIMEOUT)
+            })
+            .await;
+            return Err(err.into());
+        }
+
+        let mut progress = TopicPartitionList::new();
+        progress.add_partition_offset(
+            &self.topic,
+            PROGRESS_PARTITION,
+            Offset::Offset(sequence_number as i64 + 1),
+        )?;
+
+        let producer = self.producer.clone();
+        let group_metadata = Arc::clone(&self.group_metadata);
+        tokio::task::spawn_blocking(move || {
+            producer.send_offsets_to_transaction(
+                &progress,
+                &group_metadata,
+                TRANSACTION_TIMEOUT,
+            )?;
+            producer.commit_transaction(TRANSACTION_TIMEOUT)
+        })
+        .await
+        .expect("committing the transaction does not panic")?;
+
+        Ok(())
+    }
+}
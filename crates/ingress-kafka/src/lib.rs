@@ -9,14 +9,23 @@
 // by the Apache License, Version 2.0.
 
 mod builder;
+mod consumer_lag;
 mod consumer_task;
+mod egress;
 mod metric_definitions;
+mod nats;
+mod source;
 mod subscription_controller;
+mod transform;
+mod webhook;
+
+use std::net::SocketAddr;
 
 use rdkafka::error::KafkaError;
 use tokio::sync::mpsc;
 
 use restate_ingestion_client::IngestionError;
+use restate_types::identifiers::SubscriptionId;
 use restate_types::schema::kafka::KafkaCluster;
 use restate_types::schema::subscriptions::Subscription;
 
@@ -32,19 +41,25 @@ pub type SubscriptionCommandReceiver = mpsc::Receiver<Command>;
 pub enum Error {
     #[error(transparent)]
     Kafka(#[from] KafkaError),
-    #[error(
-        "Error processing message subscription {subscription} topic {topic} partition {partition} offset {offset}: {cause}"
-    )]
+    #[error("NATS error: {0:#}")]
+    Nats(#[source] anyhow::Error),
+    #[error("cannot bind the webhook listener to {0}: {1}")]
+    WebhookBind(SocketAddr, #[source] anyhow::Error),
+    #[error("Error processing message subscription {subscription} {origin}: {cause}")]
     Event {
         subscription: String,
-        topic: String,
-        partition: i32,
-        offset: i64,
+        origin: builder::EventOrigin,
         #[source]
         cause: anyhow::Error,
     },
+    #[error("invalid transformation options: {0:#}")]
+    InvalidTransform(#[source] anyhow::Error),
     #[error("Ingress error: {0}")]
     IngestionError(#[from] IngestionError),
+    #[error("subscription {0} is not an egress subscription publishing to Kafka")]
+    NotEgressSubscription(SubscriptionId),
+    #[error("subscription {0} is an egress subscription, which is not consumed by the ingress")]
+    NotIngressSubscription(SubscriptionId),
     #[error(
         "Received a message on the main partition queue for topic {0} partition {1} despite partitioned queues"
     )]
@@ -59,4 +74,6 @@ pub enum Error {
     },
 }
 
+pub use consumer_lag::{ConsumerLagRegistry, PartitionConsumerLag};
+pub use egress::KafkaEgress;
 pub use subscription_controller::Service;
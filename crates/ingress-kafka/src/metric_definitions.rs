@@ -11,7 +11,12 @@
 use metrics::{Unit, describe_counter, describe_gauge};
 
 pub const KAFKA_INGRESS_REQUESTS: &str = "restate.kafka_ingress.requests.total";
+pub const KAFKA_INGRESS_SKIPPED_RECORDS: &str = "restate.kafka_ingress.skipped_records.total";
 pub const KAFKA_INGRESS_CONSUMER_LAG: &str = "restate.kafka_ingress.consumer.lag";
+pub const NATS_INGRESS_REQUESTS: &str = "restate.nats_ingress.requests.total";
+pub const WEBHOOK_INGRESS_REQUESTS: &str = "restate.webhook_ingress.requests.total";
+pub const KAFKA_EGRESS_RECORDS: &str = "restate.kafka_egress.records.total";
+pub const KAFKA_EGRESS_PUBLISH_FAILURES: &str = "restate.kafka_egress.publish_failures.total";
 
 pub(crate) fn describe_metrics() {
     describe_counter!(
@@ -19,9 +24,34 @@ pub(crate) fn describe_metrics() {
         Unit::Count,
         "Number of Kafka ingress requests"
     );
+    describe_counter!(
+        KAFKA_INGRESS_SKIPPED_RECORDS,
+        Unit::Count,
+        "Number of Kafka records skipped because they cannot be transformed"
+    );
     describe_gauge!(
         KAFKA_INGRESS_CONSUMER_LAG,
         Unit::Count,
         "Kafka Consumer Lag per partition"
     );
+    describe_counter!(
+        NATS_INGRESS_REQUESTS,
+        Unit::Count,
+        "Number of NATS ingress requests"
+    );
+    describe_counter!(
+        WEBHOOK_INGRESS_REQUESTS,
+        Unit::Count,
+        "Number of webhook ingress requests"
+    );
+    describe_counter!(
+        KAFKA_EGRESS_RECORDS,
+        Unit::Count,
+        "Number of records published to Kafka egress topics"
+    );
+    describe_counter!(
+        KAFKA_EGRESS_PUBLISH_FAILURES,
+        Unit::Count,
+        "Number of failed attempts to publish records to Kafka egress topics"
+    );
 }
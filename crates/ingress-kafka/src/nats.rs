@@ -0,0 +1,317 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! NATS JetStream source.
+//!
+//! Each subscription consumes its stream through a durable pull consumer with explicit acks,
+//! shared by all the nodes running the subscription. Messages are acked in order once their
+//! envelope has been committed. The stream sequence number deduplicates the messages ingested by
+//! the same node, whose producer id includes the node id since the sequence numbers received by
+//! different nodes interleave. Redelivered messages may have been delivered to another node
+//! first, so they are ingested again without deduplication.
+
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use async_nats::jetstream;
+use async_nats::jetstream::consumer::{AckPolicy, pull};
+use bytes::Bytes;
+use futures::StreamExt;
+use futures::future::OptionFuture;
+use metrics::counter;
+use tokio::sync::oneshot;
+use tracing::{debug, instrument, trace};
+
+use restate_core::Metadata;
+use restate_core::network::TransportConnect;
+use restate_ingestion_client::{IngestionClient, IngestionError, RecordCommit};
+use restate_types::PlainNodeId;
+use restate_types::identifiers::{SubscriptionId, WithPartitionKey};
+use restate_types::invocation::Header;
+use restate_types::schema::subscriptions::{
+    NATS_CONSUMER_PROPERTY, NATS_CREDENTIALS_FILE_PROPERTY, NATS_FILTER_SUBJECT_PROPERTY,
+    NATS_PASSWORD_PROPERTY, NATS_TOKEN_PROPERTY, NATS_USER_PROPERTY,
+};
+use restate_wal_protocol::Envelope;
+
+use crate::Error;
+use crate::builder::{EnvelopeBuilder, EventOrigin, IngressEvent, KEY_HEADER};
+use crate::metric_definitions::NATS_INGRESS_REQUESTS;
+
+/// Connection and consumer options of a NATS subscription, read from the subscription metadata.
+#[derive(Debug, Clone)]
+struct NatsOptions {
+    consumer: String,
+    filter_subject: Option<String>,
+    user: Option<String>,
+    password: Option<String>,
+    token: Option<String>,
+    credentials_file: Option<PathBuf>,
+}
+
+impl NatsOptions {
+    fn from_metadata(subscription_id: SubscriptionId, metadata: &HashMap<String, String>) -> Self {
+        Self {
+            consumer: metadata
+                .get(NATS_CONSUMER_PROPERTY)
+                .cloned()
+                .unwrap_or_else(|| subscription_id.to_string()),
+            filter_subject: metadata.get(NATS_FILTER_SUBJECT_PROPERTY).cloned(),
+            user: metadata.get(NATS_USER_PROPERTY).cloned(),
+            password: metadata.get(NATS_PASSWORD_PROPERTY).cloned(),
+            token: metadata.get(NATS_TOKEN_PROPERTY).cloned(),
+            credentials_file: metadata
+                .get(NATS_CREDENTIALS_FILE_PROPERTY)
+                .map(PathBuf::from),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct NatsConsumerTask<T> {
+    server: String,
+    stream: String,
+    options: NatsOptions,
+    ingestion: IngestionClient<T, Envelope>,
+    builder: EnvelopeBuilder,
+}
+
+impl<T> NatsConsumerTask<T>
+where
+    T: TransportConnect,
+{
+    pub fn new(
+        server: String,
+        stream: String,
+        ingestion: IngestionClient<T, Envelope>,
+        builder: EnvelopeBuilder,
+    ) -> Self {
+        let options = NatsOptions::from_metadata(
+            builder.subscription().id(),
+            builder.subscription().metadata(),
+        );
+        Self {
+            server,
+            stream,
+            options,
+            ingestion,
+            builder,
+        }
+    }
+
+    pub async fn run(mut self, mut rx: oneshot::Receiver<()>) -> Result<(), Error> {
+        tokio::select! {
+            res = self.run_inner() => res,
+            _ = &mut rx => Ok(()),
+        }
+    }
+
+    async fn connect(&self) -> Result<async_nats::Client, Error> {
+        let mut connect_options = async_nats::ConnectOptions::new()
+            .name(format!("restate-{}", self.builder.subscription_id()));
+        if let (Some(user), Some(password)) = (&self.options.user, &self.options.password) {
+            connect_options = connect_options.user_and_password(user.clone(), password.clone());
+        }
+        if let Some(token) = &self.options.token {
+            connect_options = connect_options.token(token.clone());
+        }
+        if let Some(credentials_file) = &self.options.credentials_file {
+            connect_options = connect_options
+                .credentials_file(credentials_file)
+                .await
+                .with_context(|| {
+                    format!(
+                        "cannot read NATS credentials file {}",
+                        credentials_file.display()
+                    )
+                })
+                .map_err(Error::Nats)?;
+        }
+
+        connect_options
+            .connect(self.server.as_str())
+            .await
+            .with_context(|| format!("cannot connect to NATS server {}", self.server))
+            .map_err(Error::Nats)
+    }
+
+    #[instrument(skip(self), fields(
+        restate.subscription.id = %self.builder.subscription().id(),
+        nats.stream = %self.stream,
+        nats.consumer = %self.options.consumer)
+    )]
+    async fn run_inner(&mut self) -> Result<(), Error> {
+        debug!(
+            "Starting consumer for NATS server {} with filter subject {:?}",
+            self.server, self.options.filter_subject
+        );
+
+        let client = self.connect().await?;
+        let stream = jetstream::new(client)
+            .get_stream(&self.stream)
+            .await
+            .with_context(|| format!("cannot get JetStream stream {}", self.stream))
+            .map_err(Error::Nats)?;
+        let consumer = stream
+            .get_or_create_consumer(
+                &self.options.consumer,
+                pull::Config {
+                    durable_name: Some(self.options.consumer.clone()),
+                    filter_subject: self.options.filter_subject.clone().unwrap_or_default(),
+                    ack_policy: AckPolicy::Explicit,
+                    ..Default::default()
+                },
+            )
+            .await
+            .with_context(|| {
+                format!(
+                    "cannot create JetStream consumer {} on stream {}",
+                    self.options.consumer, self.stream
+                )
+            })
+            .map_err(Error::Nats)?;
+        let mut messages = consumer
+            .messages()
+            .await
+            .context("cannot pull messages from JetStream consumer")
+            .map_err(Error::Nats)?;
+
+        let producer_id = dedup_producer_id(
+            &self.builder.subscription().id(),
+            &self.options.consumer,
+            &self.stream,
+            Metadata::with_current(|m| m.my_node_id()).as_plain(),
+        );
+
+        let ingress_request_counter = counter!(
+            NATS_INGRESS_REQUESTS,
+            "subscription" => self.builder.subscription().id().to_string(),
+            "stream" => self.stream.clone(),
+        );
+
+        let mut inflight = VecDeque::new();
+
+        loop {
+            tokio::select! {
+                biased;
+                Some(committed) = head_committed(&mut inflight) => {
+                    _ = inflight.pop_front().expect("to exist");
+                    let msg: jetstream::Message = committed.map_err(|_| Error::IngestionError(IngestionError::Closed("commit cancelled")))?;
+
+                    ingress_request_counter.increment(1);
+                    trace!("Ack NATS message");
+
+                    msg.ack().await.map_err(|err| Error::Nats(anyhow::anyhow!(err).context("cannot ack NATS message")))?;
+                },
+                Some(received) = messages.next() => {
+                    let msg = received.context("cannot receive NATS message").map_err(Error::Nats)?;
+                    let event = self.event(&msg)?;
+
+                    trace!(
+                        stream_sequence = ?event.origin.sequence_number(),
+                        "Ingesting NATS message"
+                    );
+
+                    let envelope = self.builder.build(producer_id, &self.options.consumer, event)?;
+
+                    let commit_token = self
+                        .ingestion
+                        .ingest(envelope.partition_key(), envelope)
+                        .await?
+                        .map(|_| msg);
+
+                    inflight.push_back(commit_token);
+                }
+            }
+        }
+    }
+
+    fn event(&self, msg: &jetstream::Message) -> Result<IngressEvent, Error> {
+        let info = msg.info().map_err(|err| {
+            Error::Nats(anyhow::anyhow!(err).context("invalid JetStream message"))
+        })?;
+        let origin = EventOrigin::Nats {
+            stream: self.stream.clone(),
+            stream_sequence: info.stream_sequence,
+            redelivered: info.delivered > 1,
+        };
+
+        let nats_headers = || {
+            msg.message.headers.iter().flat_map(|headers| {
+                headers.iter().flat_map(|(name, values)| {
+                    values
+                        .iter()
+                        .map(move |value| (name.as_ref(), value.as_str().as_bytes()))
+                })
+            })
+        };
+
+        let key = nats_headers()
+            .find(|(name, _)| *name == KEY_HEADER)
+            .map(|(_, value)| Bytes::copy_from_slice(value))
+            .unwrap_or_default();
+        let (scope, limit_key) = self
+            .builder
+            .extract_scope_limit_key(&origin, nats_headers())?;
+
+        let headers = vec![
+            Header::new("nats.subject", msg.message.subject.as_str()),
+            Header::new("nats.stream", self.stream.as_str()),
+            Header::new("nats.sequence", info.stream_sequence.to_string()),
+            Header::new(
+                "nats.timestamp",
+                (info.published.unix_timestamp_nanos() / 1_000_000).to_string(),
+            ),
+            Header::new("restate.subscription.id", self.builder.subscription_id()),
+        ];
+
+        Ok(IngressEvent {
+            key,
+            payload: msg.message.payload.clone(),
+            headers,
+            scope,
+            limit_key,
+            idempotency_key: None,
+            origin,
+        })
+    }
+}
+
+#[inline]
+fn head_committed(
+    inflight: &mut VecDeque<RecordCommit<jetstream::Message>>,
+) -> OptionFuture<&mut RecordCommit<jetstream::Message>> {
+    OptionFuture::from(inflight.front_mut())
+}
+
+// Do not change. Changing this hasher will create new producer-id which can
+// cause duplicates
+fn dedup_producer_id(
+    subscription: &SubscriptionId,
+    consumer: &str,
+    stream: &str,
+    node_id: PlainNodeId,
+) -> u128 {
+    let mut hasher = xxhash_rust::xxh3::Xxh3::new();
+
+    "nats".hash(&mut hasher);
+    '\0'.hash(&mut hasher);
+    subscription.hash(&mut hasher);
+    '\0'.hash(&mut hasher);
+    consumer.hash(&mut hasher);
+    '\0'.hash(&mut hasher);
+    stream.hash(&mut hasher);
+    '\0'.hash(&mut hasher);
+    node_id.hash(&mut hasher);
+
+    hasher.digest128()
+}
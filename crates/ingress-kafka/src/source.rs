@@ -0,0 +1,50 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use tokio::sync::oneshot;
+
+use restate_core::network::TransportConnect;
+
+use crate::Error;
+use crate::consumer_task::ConsumerTask;
+use crate::nats::NatsConsumerTask;
+use crate::webhook::WebhookConsumerTask;
+
+/// Consumer task of a subscription, for each of the supported sources.
+///
+/// Every task builds its envelopes with the [`crate::builder::EnvelopeBuilder`] of the
+/// subscription, and runs until it fails or the close channel is dropped/completed.
+#[derive(Clone)]
+pub enum SourceTask<T> {
+    Kafka(ConsumerTask<T>),
+    Nats(NatsConsumerTask<T>),
+    Webhook(WebhookConsumerTask<T>),
+}
+
+impl<T> SourceTask<T>
+where
+    T: TransportConnect,
+{
+    pub fn name(&self) -> &'static str {
+        match self {
+            SourceTask::Kafka(_) => "kafka-consumer",
+            SourceTask::Nats(_) => "nats-consumer",
+            SourceTask::Webhook(_) => "webhook-consumer",
+        }
+    }
+
+    pub async fn run(self, rx: oneshot::Receiver<()>) -> Result<(), Error> {
+        match self {
+            SourceTask::Kafka(task) => task.run(rx).await,
+            SourceTask::Nats(task) => task.run(rx).await,
+            SourceTask::Webhook(task) => task.run(rx).await,
+        }
+    }
+}
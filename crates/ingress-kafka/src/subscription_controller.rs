@@ -13,27 +13,34 @@ use std::time::Duration;
 
 use restate_wal_protocol::Envelope;
 use tokio::sync::mpsc;
-use tracing::{error, warn};
+use tracing::{debug, error, warn};
 
-use restate_core::cancellation_watcher;
 use restate_core::network::TransportConnect;
+use restate_core::{TaskCenter, TaskKind, cancellation_watcher};
 use restate_ingestion_client::IngestionClient;
+use restate_types::config::Configuration;
 use restate_types::identifiers::SubscriptionId;
 use restate_types::live::Live;
 use restate_types::retries::RetryPolicy;
 use restate_types::schema::Schema;
 use restate_types::schema::kafka::KafkaCluster;
-use restate_types::schema::subscriptions::{Source, Subscription};
+use restate_types::schema::subscriptions::{RESTATE_PROPERTY_PREFIX, Source, Subscription};
 
 use super::*;
 use crate::builder::EnvelopeBuilder;
+use crate::consumer_lag::ConsumerLagRegistry;
+use crate::nats::NatsConsumerTask;
+use crate::source::SourceTask;
 use crate::subscription_controller::task_orchestrator::TaskOrchestrator;
+use crate::webhook::{WebhookConsumerTask, WebhookRegistry};
 
 // For simplicity of the current implementation, this currently lives in this module
 // In future versions, we should either pull this out in a separate process, or generify it and move it to the worker, or an ad-hoc module
 pub struct Service<T> {
     ingestion: IngestionClient<T, Envelope>,
     schema: Live<Schema>,
+    webhooks: WebhookRegistry,
+    consumer_lag: ConsumerLagRegistry,
 
     commands_tx: SubscriptionCommandSender,
     commands_rx: SubscriptionCommandReceiver,
@@ -50,6 +57,8 @@ where
         Service {
             ingestion,
             schema,
+            webhooks: WebhookRegistry::default(),
+            consumer_lag: ConsumerLagRegistry::default(),
             commands_tx,
             commands_rx,
         }
@@ -59,10 +68,32 @@ where
         self.commands_tx.clone()
     }
 
+    /// Returns the registry of the Kafka partitions consumed by this node.
+    pub fn consumer_lag(&self) -> ConsumerLagRegistry {
+        self.consumer_lag.clone()
+    }
+
     pub async fn run(mut self) -> anyhow::Result<()> {
         let shutdown = cancellation_watcher();
         tokio::pin!(shutdown);
 
+        let webhook_listener = {
+            let ingress_options = &Configuration::pinned().ingress;
+            ingress_options.webhook_bind_address().map(|address| {
+                (
+                    address,
+                    ingress_options.ingress_listener_options().tls().cloned(),
+                )
+            })
+        };
+        if let Some((address, tls_options)) = webhook_listener {
+            TaskCenter::spawn_child(
+                TaskKind::Ingress,
+                "webhook-listener",
+                webhook::run_listener(address, tls_options, self.webhooks.clone()),
+            )?;
+        }
+
         let mut task_orchestrator = TaskOrchestrator::new(RetryPolicy::exponential(
             Duration::from_millis(200),
             2.0,
@@ -94,24 +125,67 @@ where
 
     fn handle_start_subscription(
         &mut self,
-        kafka_cluster: KafkaCluster,
+        kafka_cluster: Option<KafkaCluster>,
         subscription: Subscription,
         task_orchestrator: &mut TaskOrchestrator<T>,
-    ) {
+    ) -> Result<(), Error> {
+        let subscription_id = subscription.id();
+        let builder = EnvelopeBuilder::new(subscription.clone(), self.schema.clone());
+
+        // Create the consumer task
+        let source_task = match subscription.source() {
+            Source::Kafka { topic, .. } => {
+                let kafka_cluster = kafka_cluster
+                    .as_ref()
+                    .expect("Kafka subscriptions must have a cluster");
+                SourceTask::Kafka(self.kafka_consumer_task(
+                    kafka_cluster,
+                    &subscription,
+                    topic,
+                    builder,
+                ))
+            }
+            Source::Nats { server, stream } => SourceTask::Nats(NatsConsumerTask::new(
+                server.clone(),
+                stream.clone(),
+                self.ingestion.clone(),
+                builder,
+            )),
+            Source::Webhook { name } => SourceTask::Webhook(WebhookConsumerTask::new(
+                name.clone(),
+                self.ingestion.clone(),
+                builder,
+                self.webhooks.clone(),
+            )),
+            Source::Topic { .. } | Source::Output { .. } => {
+                return Err(Error::NotIngressSubscription(subscription_id));
+            }
+        };
+
+        task_orchestrator.start(subscription_id, source_task, kafka_cluster, subscription);
+        Ok(())
+    }
+
+    fn kafka_consumer_task(
+        &self,
+        kafka_cluster: &KafkaCluster,
+        subscription: &Subscription,
+        topic: &str,
+        builder: EnvelopeBuilder,
+    ) -> consumer_task::ConsumerTask<T> {
         let mut client_config = rdkafka::ClientConfig::new();
         // enabling probing for the ca certificates if the user does not specify anything else
         client_config.set("https.ca.location", "probe");
 
-        let Source::Kafka { topic, .. } = subscription.source();
-
         // Subscription metadata takes precedence over cluster properties
-        let cluster_properties = kafka_cluster.properties.clone();
-
-        for (k, v) in &cluster_properties {
+        for (k, v) in &kafka_cluster.properties {
             client_config.set(k, v);
         }
         for (k, v) in subscription.metadata() {
-            client_config.set(k, v);
+            // Restate options are handled by the consumer, and unknown to librdkafka
+            if !k.starts_with(RESTATE_PROPERTY_PREFIX) {
+                client_config.set(k, v);
+            }
         }
 
         // Options required by the business logic of our consumer,
@@ -119,17 +193,13 @@ where
         client_config.set("enable.auto.commit", "true");
         client_config.set("enable.auto.offset.store", "false");
 
-        let subscription_id = subscription.id();
-
-        // Create the consumer task
-        let consumer_task = consumer_task::ConsumerTask::new(
+        consumer_task::ConsumerTask::new(
             client_config,
             vec![topic.to_string()],
             self.ingestion.clone(),
-            EnvelopeBuilder::new(subscription.clone(), self.schema.clone()),
-        );
-
-        task_orchestrator.start(subscription_id, consumer_task, kafka_cluster, subscription);
+            builder,
+            self.consumer_lag.clone(),
+        )
     }
 
     fn handle_stop_subscription(
@@ -157,18 +227,33 @@ where
             task_orchestrator.running_subscriptions().cloned().collect();
 
         for subscription in subscriptions {
+            // Egress subscriptions are published by the partition processors
+            if subscription.is_egress() {
+                continue;
+            }
             let subscription_id = subscription.id();
 
-            // Find the KafkaCluster for this subscription
-            let Source::Kafka { cluster, .. } = subscription.source();
-            let Some(kafka_cluster) = cluster_map.get(cluster.as_str()).cloned() else {
-                error!(
-                    "KafkaCluster '{}' not found for subscription {}. This might happen if you registered a subscription with a cluster name, but this cluster is not available anymore in the configuration. Configured Kafka clusters: {:?}",
-                    cluster,
-                    subscription_id,
-                    cluster_map.keys().collect::<Vec<_>>()
-                );
+            if subscription.consumption().is_paused() {
+                // Left in running_subscriptions, so that it gets stopped below
+                debug!("Subscription {subscription_id} is paused");
                 continue;
+            }
+
+            // Find the KafkaCluster for Kafka subscriptions
+            let kafka_cluster = match subscription.source().kafka_cluster() {
+                Some(cluster) => {
+                    let Some(kafka_cluster) = cluster_map.get(cluster).cloned() else {
+                        error!(
+                            "KafkaCluster '{}' not found for subscription {}. This might happen if you registered a subscription with a cluster name, but this cluster is not available anymore in the configuration. Configured Kafka clusters: {:?}",
+                            cluster,
+                            subscription_id,
+                            cluster_map.keys().collect::<Vec<_>>()
+                        );
+                        continue;
+                    };
+                    Some(kafka_cluster.clone())
+                }
+                None => None,
             };
 
             if let Some((running_cluster, running_subscription)) =
@@ -176,30 +261,34 @@ where
             {
                 // Subscription is already running - check if configuration changed
                 let config_changed = running_subscription != &subscription
-                    || running_cluster.properties != kafka_cluster.properties;
+                    || running_cluster.map(|cluster| &cluster.properties)
+                        != kafka_cluster.as_ref().map(|cluster| &cluster.properties);
 
+                // Seeks change the subscription too, and are applied by the restarted consumers
                 if config_changed {
                     // Configuration changed -> restart the subscription
                     self.handle_stop_subscription(subscription_id, task_orchestrator);
-                    self.handle_start_subscription(
-                        kafka_cluster.clone(),
+                    if let Err(err) = self.handle_start_subscription(
+                        kafka_cluster,
                         subscription,
                         task_orchestrator,
-                    );
+                    ) {
+                        error!(%err, "Cannot restart subscription {subscription_id}");
+                    }
                 }
                 // We're good with this subscription
                 running_subscriptions.remove(&subscription_id);
             } else {
                 // New subscription -> start it
-                self.handle_start_subscription(
-                    kafka_cluster.clone(),
-                    subscription,
-                    task_orchestrator,
-                );
+                if let Err(err) =
+                    self.handle_start_subscription(kafka_cluster, subscription, task_orchestrator)
+                {
+                    error!(%err, "Cannot start subscription {subscription_id}");
+                }
             }
         }
 
-        // Stop any subscriptions that are no longer in the configuration
+        // Stop any subscriptions that are no longer in the configuration, or paused
         for subscription_id in running_subscriptions {
             self.handle_stop_subscription(subscription_id, task_orchestrator);
         }
@@ -209,7 +298,7 @@ where
 }
 
 mod task_orchestrator {
-    use crate::consumer_task;
+    use crate::source::SourceTask;
     use restate_core::network::TransportConnect;
     use restate_core::{TaskCenterFutureExt, TaskKind};
     use restate_timer_queue::TimerQueue;
@@ -226,11 +315,11 @@ mod task_orchestrator {
 
     struct TaskState<T> {
         // We use this to restart the consumer task in case of a failure
-        consumer_task_clone: consumer_task::ConsumerTask<T>,
+        consumer_task_clone: SourceTask<T>,
         task_state_inner: TaskStateInner,
         retry_iter: RetryIter<'static>,
-        // Store the KafkaCluster and Subscription to detect configuration changes
-        kafka_cluster: KafkaCluster,
+        // Store the KafkaCluster (for Kafka sources) and Subscription to detect configuration changes
+        kafka_cluster: Option<KafkaCluster>,
         subscription: Subscription,
     }
 
@@ -376,8 +465,8 @@ mod task_orchestrator {
         pub(super) fn start(
             &mut self,
             subscription_id: SubscriptionId,
-            consumer_task_clone: consumer_task::ConsumerTask<T>,
-            kafka_cluster: KafkaCluster,
+            consumer_task_clone: SourceTask<T>,
+            kafka_cluster: Option<KafkaCluster>,
             subscription: Subscription,
         ) {
             // Shutdown old task, if any
@@ -395,17 +484,18 @@ mod task_orchestrator {
                 "Spawning the consumer task for subscription id {}",
                 subscription_id
             );
+            let task_name = consumer_task_clone.name();
             let task_id = self
                 .tasks
                 .build_task()
-                .name("kafka-consumer")
+                .name(task_name)
                 .spawn({
                     let consumer_task_clone = consumer_task_clone.clone();
                     consumer_task_clone
                         .run(rx)
-                        .in_current_tc_as_task(TaskKind::Kafka, "kafka-consumer-task")
+                        .in_current_tc_as_task(TaskKind::Kafka, task_name)
                 })
-                .expect("to spawn kafka consumer task")
+                .expect("to spawn consumer task")
                 .id();
 
             self.running_tasks_to_subscriptions
@@ -449,10 +539,10 @@ mod task_orchestrator {
         pub(super) fn get_running_config(
             &self,
             subscription_id: &SubscriptionId,
-        ) -> Option<(&KafkaCluster, &Subscription)> {
+        ) -> Option<(Option<&KafkaCluster>, &Subscription)> {
             self.subscription_id_to_task_state
                 .get(subscription_id)
-                .map(|state| (&state.kafka_cluster, &state.subscription))
+                .map(|state| (state.kafka_cluster.as_ref(), &state.subscription))
         }
     }
 }
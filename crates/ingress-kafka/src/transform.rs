@@ -0,0 +1,420 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Filtering and transformation of the records of Kafka subscriptions, configured through the
+//! `restate.*` subscription options.
+//!
+//! Records are processed in the following order:
+//! 1. header filters, see [`KAFKA_FILTER_HEADER_PROPERTY_PREFIX`]
+//! 2. payload decoding from Avro or Protobuf to JSON, see [`KAFKA_DECODE_PROPERTY`]
+//! 3. JSON path filter on the (decoded) payload, see [`KAFKA_FILTER_JSON_PATH_PROPERTY`]
+//! 4. key template, see [`KAFKA_KEY_TEMPLATE_PROPERTY`]
+//!
+//! Records which cannot be decoded, parsed as JSON or rendered with the key template are skipped
+//! by the consumers, see [`crate::builder::EnvelopeBuilder::build_kafka`].
+
+use std::collections::HashMap;
+use std::fmt;
+
+use anyhow::{Context, anyhow, bail};
+use bytes::Bytes;
+use prost_reflect::{DescriptorPool, DynamicMessage, MessageDescriptor};
+use serde_json::Value;
+
+use restate_types::schema::event_transform::{JsonPredicate, KeyTemplate};
+use restate_types::schema::subscriptions::{
+    KAFKA_DECODE_MESSAGE_PROPERTY, KAFKA_DECODE_PROPERTY, KAFKA_DECODE_SCHEMA_FILE_PROPERTY,
+    KAFKA_DECODE_WIRE_FORMAT_PROPERTY, KAFKA_FILTER_HEADER_PROPERTY_PREFIX,
+    KAFKA_FILTER_JSON_PATH_PROPERTY, KAFKA_KEY_TEMPLATE_PROPERTY,
+};
+
+/// Filters and transformations applied to the records of a subscription before invoking.
+#[derive(Debug, Default)]
+pub struct EventTransform {
+    header_filters: Vec<(String, String)>,
+    json_filter: Option<JsonPredicate>,
+    key_template: Option<KeyTemplate>,
+    decoder: Option<PayloadDecoder>,
+}
+
+impl EventTransform {
+    /// Parses the transformation options of the subscription metadata, and loads the schema file
+    /// of the decoder.
+    pub fn from_metadata(metadata: &HashMap<String, String>) -> anyhow::Result<Self> {
+        let mut header_filters: Vec<_> = metadata
+            .iter()
+            .filter_map(|(k, v)| {
+                k.strip_prefix(KAFKA_FILTER_HEADER_PROPERTY_PREFIX)
+                    .map(|name| (name.to_owned(), v.clone()))
+            })
+            .collect();
+        header_filters.sort();
+
+        let json_filter = metadata
+            .get(KAFKA_FILTER_JSON_PATH_PROPERTY)
+            .map(|predicate| predicate.parse())
+            .transpose()
+            .with_context(|| format!("invalid '{KAFKA_FILTER_JSON_PATH_PROPERTY}'"))?;
+        let key_template = metadata
+            .get(KAFKA_KEY_TEMPLATE_PROPERTY)
+            .map(|template| template.parse())
+            .transpose()
+            .with_context(|| format!("invalid '{KAFKA_KEY_TEMPLATE_PROPERTY}'"))?;
+        let decoder = PayloadDecoder::from_metadata(metadata)
+            .with_context(|| format!("invalid '{KAFKA_DECODE_PROPERTY}' options"))?;
+
+        Ok(Self {
+            header_filters,
+            json_filter,
+            key_template,
+            decoder,
+        })
+    }
+
+    /// Applies the filters and transformations to a record, returning its new key and payload,
+    /// or `None` if the record is filtered out.
+    pub fn apply(
+        &self,
+        key: Bytes,
+        payload: Bytes,
+        headers: &[(&str, &[u8])],
+    ) -> anyhow::Result<Option<(Bytes, Bytes)>> {
+        for (name, value) in &self.header_filters {
+            if !headers
+                .iter()
+                .any(|(k, v)| k == name && *v == value.as_bytes())
+            {
+                return Ok(None);
+            }
+        }
+
+        let mut json = None;
+        let payload = if let Some(decoder) = &self.decoder {
+            let value = decoder.decode(&payload)?;
+            let payload = Bytes::from(serde_json::to_vec(&value)?);
+            json = Some(value);
+            payload
+        } else {
+            payload
+        };
+
+        let needs_json = self.json_filter.is_some()
+            || self
+                .key_template
+                .as_ref()
+                .is_some_and(KeyTemplate::reads_payload);
+        if needs_json && json.is_none() {
+            json = Some(
+                serde_json::from_slice(&payload).context("the record payload is not valid JSON")?,
+            );
+        }
+
+        if let Some(filter) = &self.json_filter
+            && !filter.matches(json.as_ref().expect("payload was parsed"))
+        {
+            return Ok(None);
+        }
+
+        let key = match &self.key_template {
+            Some(template) => Bytes::from(template.render(&key, headers, json.as_ref())?),
+            None => key,
+        };
+
+        Ok(Some((key, payload)))
+    }
+}
+
+/// Decodes the payloads to JSON, using a schema read from a local file.
+enum PayloadDecoder {
+    Avro {
+        schema: apache_avro::Schema,
+        confluent: bool,
+    },
+    Protobuf {
+        message: MessageDescriptor,
+        /// Message indexes of `message` in the Confluent wire format.
+        message_indexes: Vec<i64>,
+        confluent: bool,
+    },
+}
+
+impl fmt::Debug for PayloadDecoder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PayloadDecoder::Avro { confluent, .. } => f
+                .debug_struct("Avro")
+                .field("confluent", confluent)
+                .finish_non_exhaustive(),
+            PayloadDecoder::Protobuf {
+                message, confluent, ..
+            } => f
+                .debug_struct("Protobuf")
+                .field("message", &message.full_name())
+                .field("confluent", confluent)
+                .finish_non_exhaustive(),
+        }
+    }
+}
+
+impl PayloadDecoder {
+    fn from_metadata(metadata: &HashMap<String, String>) -> anyhow::Result<Option<Self>> {
+        let Some(format) = metadata.get(KAFKA_DECODE_PROPERTY) else {
+            return Ok(None);
+        };
+        let schema_file = metadata
+            .get(KAFKA_DECODE_SCHEMA_FILE_PROPERTY)
+            .ok_or_else(|| anyhow!("missing '{KAFKA_DECODE_SCHEMA_FILE_PROPERTY}'"))?;
+        let schema = std::fs::read(schema_file)
+            .with_context(|| format!("cannot read schema file '{schema_file}'"))?;
+        let confluent = match metadata
+            .get(KAFKA_DECODE_WIRE_FORMAT_PROPERTY)
+            .map(String::as_str)
+        {
+            None | Some("raw") => false,
+            Some("confluent") => true,
+            Some(other) => bail!("unsupported wire format '{other}'"),
+        };
+
+        match format.as_str() {
+            "avro" => {
+                let schema = apache_avro::Schema::parse_str(
+                    std::str::from_utf8(&schema).context("Avro schema must be valid UTF-8")?,
+                )
+                .with_context(|| format!("invalid Avro schema in '{schema_file}'"))?;
+                Ok(Some(PayloadDecoder::Avro { schema, confluent }))
+            }
+            "protobuf" => {
+                let message_name = metadata
+                    .get(KAFKA_DECODE_MESSAGE_PROPERTY)
+                    .ok_or_else(|| anyhow!("missing '{KAFKA_DECODE_MESSAGE_PROPERTY}'"))?;
+                let pool = DescriptorPool::decode(schema.as_slice()).with_context(|| {
+                    format!("'{schema_file}' is not a Protobuf file descriptor set")
+                })?;
+                let message = pool.get_message_by_name(message_name).ok_or_else(|| {
+                    anyhow!("message '{message_name}' not found in '{schema_file}'")
+                })?;
+                // The path of a message in its file alternates between the field numbers of the
+                // (nested) message types and the index of the message among them
+                let message_indexes = message
+                    .path()
+                    .chunks(2)
+                    .map(|step| i64::from(step[1]))
+                    .collect();
+                Ok(Some(PayloadDecoder::Protobuf {
+                    message,
+                    message_indexes,
+                    confluent,
+                }))
+            }
+            other => bail!("unsupported format '{other}'"),
+        }
+    }
+
+    fn decode(&self, payload: &[u8]) -> anyhow::Result<Value> {
+        match self {
+            PayloadDecoder::Avro { schema, confluent } => {
+                let mut datum = if *confluent {
+                    strip_confluent_header(payload)?
+                } else {
+                    payload
+                };
+                let value = apache_avro::from_avro_datum(schema, &mut datum, None)
+                    .context("cannot decode Avro payload")?;
+                Value::try_from(value).context("cannot convert Avro payload to JSON")
+            }
+            PayloadDecoder::Protobuf {
+                message,
+                message_indexes,
+                confluent,
+            } => {
+                let payload = if *confluent {
+                    strip_message_indexes(strip_confluent_header(payload)?, message_indexes)?
+                } else {
+                    payload
+                };
+                let message = DynamicMessage::decode(message.clone(), payload)
+                    .context("cannot decode Protobuf payload")?;
+                serde_json::to_value(&message).context("cannot convert Protobuf payload to JSON")
+            }
+        }
+    }
+}
+
+/// Strips the magic byte and the schema id prepended by the Confluent serializers. The schema id
+/// is ignored, since the schema is read from the configured file.
+fn strip_confluent_header(payload: &[u8]) -> anyhow::Result<&[u8]> {
+    match payload {
+        [0, _, _, _, _, rest @ ..] => Ok(rest),
+        _ => bail!("the payload doesn't start with the Confluent wire format header"),
+    }
+}
+
+/// Strips the array of message indexes the Confluent Protobuf serializer writes after the schema
+/// id, failing if they don't point to the configured message. The indexes are encoded as zig-zag
+/// varints prefixed by their count, and an empty array stands for the first message of the file.
+fn strip_message_indexes<'a>(
+    mut payload: &'a [u8],
+    expected_indexes: &[i64],
+) -> anyhow::Result<&'a [u8]> {
+    let mut read_varint = || -> anyhow::Result<i64> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let (&byte, rest) = payload
+                .split_first()
+                .ok_or_else(|| anyhow!("truncated Confluent message indexes"))?;
+            payload = rest;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok((value >> 1) as i64 ^ -((value & 1) as i64));
+            }
+        }
+        bail!("invalid Confluent message indexes")
+    };
+
+    let indexes: Vec<i64> = match read_varint()? {
+        0 => vec![0],
+        count => (0..count)
+            .map(|_| read_varint())
+            .collect::<anyhow::Result<_>>()?,
+    };
+    if indexes != expected_indexes {
+        bail!(
+            "the record was written with the message indexes {indexes:?}, but the configured message has the indexes {expected_indexes:?}"
+        );
+    }
+    Ok(payload)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transform(options: &[(&str, &str)]) -> EventTransform {
+        EventTransform::from_metadata(
+            &options
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn filter_by_header() {
+        let transform = transform(&[("restate.filter.header.event-type", "created")]);
+        let payload = Bytes::from_static(b"{}");
+
+        assert!(
+            transform
+                .apply(
+                    Bytes::new(),
+                    payload.clone(),
+                    &[("event-type", b"created".as_slice())]
+                )
+                .unwrap()
+                .is_some()
+        );
+        assert!(
+            transform
+                .apply(
+                    Bytes::new(),
+                    payload.clone(),
+                    &[("event-type", b"deleted".as_slice())]
+                )
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            transform
+                .apply(Bytes::new(), payload, &[])
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn filter_by_json_path() {
+        let transform = transform(&[(
+            "restate.filter.json-path",
+            r#"$.order.items[0].type == "book""#,
+        )]);
+
+        assert!(
+            transform
+                .apply(
+                    Bytes::new(),
+                    Bytes::from_static(br#"{"order": {"items": [{"type": "book"}]}}"#),
+                    &[],
+                )
+                .unwrap()
+                .is_some()
+        );
+        assert!(
+            transform
+                .apply(
+                    Bytes::new(),
+                    Bytes::from_static(br#"{"order": {"items": [{"type": "pen"}]}}"#),
+                    &[],
+                )
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            transform
+                .apply(Bytes::new(), Bytes::from_static(b"not json"), &[])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn key_template() {
+        let transform = transform(&[(
+            "restate.key-template",
+            "{header.tenant}/{$.customer.id}-{key}",
+        )]);
+
+        let (key, _) = transform
+            .apply(
+                Bytes::from_static(b"order-1"),
+                Bytes::from_static(br#"{"customer": {"id": 42}}"#),
+                &[("tenant", b"acme".as_slice())],
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(key, Bytes::from_static(b"acme/42-order-1"));
+
+        assert!(
+            transform
+                .apply(
+                    Bytes::from_static(b"order-1"),
+                    Bytes::from_static(br#"{"customer": {"id": 42}}"#),
+                    &[],
+                )
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn confluent_protobuf_header() {
+        // magic byte, schema id 1, message indexes [0] (encoded as a single 0), payload
+        let payload = [0, 0, 0, 0, 1, 0, 8, 1];
+        let message = strip_confluent_header(&payload).unwrap();
+        assert_eq!(strip_message_indexes(message, &[0]).unwrap(), &[8, 1]);
+        assert!(strip_message_indexes(message, &[1]).is_err());
+
+        // message indexes [1, 0]
+        let payload = [0, 0, 0, 0, 1, 4, 2, 0, 8, 1];
+        let message = strip_confluent_header(&payload).unwrap();
+        assert_eq!(strip_message_indexes(message, &[1, 0]).unwrap(), &[8, 1]);
+        assert!(strip_message_indexes(message, &[0]).is_err());
+
+        assert!(strip_confluent_header(&[1, 2]).is_err());
+    }
+}
@@ -0,0 +1,432 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Webhook source.
+//!
+//! Events are pushed with `POST /webhooks/<name>` to the webhook listener, which forwards them
+//! to the consumer tasks of the subscriptions with source `webhook://<name>`. The listener replies
+//! `202 Accepted` once the invocations of all the subscriptions have been committed, so that
+//! the senders can retry otherwise. Webhooks don't provide sequence numbers, hence requests are
+//! deduplicated only if they carry an `idempotency-key` header.
+
+use std::collections::HashMap;
+use std::convert::Infallible;
+use std::hash::Hash;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+use bytes::Bytes;
+use bytestring::ByteString;
+use constant_time_eq::constant_time_eq;
+use futures::StreamExt;
+use futures::stream::FuturesUnordered;
+use http::{HeaderMap, Method, Request, Response, StatusCode, header};
+use http_body_util::{BodyExt, Full, Limited};
+use hyper::body::Incoming;
+use metrics::counter;
+use tokio::sync::{mpsc, oneshot};
+use tracing::{debug, instrument, trace, warn};
+
+use restate_core::network::TransportConnect;
+use restate_core::network::net_util::run_hyper_server;
+use restate_ingestion_client::IngestionClient;
+use restate_types::config::{Configuration, ListenerTlsOptions};
+use restate_types::identifiers::{SubscriptionId, WithPartitionKey};
+use restate_types::invocation::Header;
+use restate_types::net::address::WebhookPort;
+use restate_types::net::listener::Listeners;
+use restate_types::schema::subscriptions::{
+    WEBHOOK_ALLOW_UNAUTHENTICATED_PROPERTY, WEBHOOK_SECRET_PROPERTY,
+};
+use restate_wal_protocol::Envelope;
+
+use crate::Error;
+use crate::builder::{EnvelopeBuilder, EventOrigin, IngressEvent, KEY_HEADER};
+use crate::metric_definitions::WEBHOOK_INGRESS_REQUESTS;
+
+const WEBHOOKS_PATH_PREFIX: &str = "/webhooks/";
+const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+/// Number of requests buffered for each subscription before the listener waits.
+const WEBHOOK_REQUESTS_BUFFER: usize = 128;
+
+/// Request received by the webhook listener, forwarded to a subscription.
+struct WebhookRequest {
+    headers: HeaderMap,
+    body: Bytes,
+    respond_to: oneshot::Sender<StatusCode>,
+}
+
+/// Consumer tasks of the webhook subscriptions, by webhook name.
+#[derive(Clone, Default)]
+pub struct WebhookRegistry(
+    Arc<
+        parking_lot::RwLock<HashMap<String, HashMap<SubscriptionId, mpsc::Sender<WebhookRequest>>>>,
+    >,
+);
+
+impl WebhookRegistry {
+    fn register(
+        &self,
+        name: &str,
+        subscription_id: SubscriptionId,
+        tx: mpsc::Sender<WebhookRequest>,
+    ) -> Registration {
+        self.0
+            .write()
+            .entry(name.to_owned())
+            .or_default()
+            .insert(subscription_id, tx);
+        Registration {
+            registry: self.clone(),
+            name: name.to_owned(),
+            subscription_id,
+        }
+    }
+
+    fn senders(&self, name: &str) -> Vec<mpsc::Sender<WebhookRequest>> {
+        self.0
+            .read()
+            .get(name)
+            .map(|subscriptions| subscriptions.values().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Removes the subscription from the registry when dropped.
+struct Registration {
+    registry: WebhookRegistry,
+    name: String,
+    subscription_id: SubscriptionId,
+}
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        let mut webhooks = self.registry.0.write();
+        if let Some(subscriptions) = webhooks.get_mut(&self.name) {
+            subscriptions.remove(&self.subscription_id);
+            if subscriptions.is_empty() {
+                webhooks.remove(&self.name);
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct WebhookConsumerTask<T> {
+    name: String,
+    secret: Option<String>,
+    allow_unauthenticated: bool,
+    ingestion: IngestionClient<T, Envelope>,
+    builder: EnvelopeBuilder,
+    registry: WebhookRegistry,
+}
+
+impl<T> WebhookConsumerTask<T>
+where
+    T: TransportConnect,
+{
+    pub fn new(
+        name: String,
+        ingestion: IngestionClient<T, Envelope>,
+        builder: EnvelopeBuilder,
+        registry: WebhookRegistry,
+    ) -> Self {
+        let metadata = builder.subscription().metadata();
+        let secret = metadata
+            .get(WEBHOOK_SECRET_PROPERTY)
+            .filter(|secret| !secret.is_empty())
+            .cloned();
+        let allow_unauthenticated = metadata
+            .get(WEBHOOK_ALLOW_UNAUTHENTICATED_PROPERTY)
+            .is_some_and(|value| value.parse().unwrap_or(false));
+        if secret.is_none() && !allow_unauthenticated {
+            warn!(
+                restate.subscription.id = %builder.subscription().id(),
+                "Webhook subscription has neither '{WEBHOOK_SECRET_PROPERTY}' nor '{WEBHOOK_ALLOW_UNAUTHENTICATED_PROPERTY}=true', all its requests will be rejected"
+            );
+        }
+        Self {
+            name,
+            secret,
+            allow_unauthenticated,
+            ingestion,
+            builder,
+            registry,
+        }
+    }
+
+    #[instrument(skip_all, fields(
+        restate.subscription.id = %self.builder.subscription().id(),
+        webhook = %self.name)
+    )]
+    pub async fn run(mut self, mut rx: oneshot::Receiver<()>) -> Result<(), Error> {
+        debug!("Starting webhook consumer");
+
+        let (tx, mut requests) = mpsc::channel(WEBHOOK_REQUESTS_BUFFER);
+        let _registration =
+            self.registry
+                .register(&self.name, self.builder.subscription().id(), tx);
+
+        let producer_id = producer_id(&self.builder.subscription().id(), &self.name);
+        let ingress_request_counter = counter!(
+            WEBHOOK_INGRESS_REQUESTS,
+            "subscription" => self.builder.subscription().id().to_string(),
+            "webhook" => self.name.clone(),
+        );
+
+        let mut inflight = FuturesUnordered::new();
+
+        loop {
+            tokio::select! {
+                Some(()) = inflight.next(), if !inflight.is_empty() => {},
+                Some(request) = requests.recv() => {
+                    let WebhookRequest { headers, body, respond_to } = request;
+                    if !self.is_authorized(&headers) {
+                        _ = respond_to.send(StatusCode::UNAUTHORIZED);
+                        continue;
+                    }
+
+                    let envelope = match self.event(headers, body).and_then(|event| self.builder.build(producer_id, "webhook", event)) {
+                        Ok(envelope) => envelope,
+                        Err(err) => {
+                            debug!("Rejecting webhook request: {err}");
+                            _ = respond_to.send(StatusCode::BAD_REQUEST);
+                            continue;
+                        }
+                    };
+
+                    trace!("Ingesting webhook request");
+                    let commit = self
+                        .ingestion
+                        .ingest(envelope.partition_key(), envelope)
+                        .await?;
+
+                    let ingress_request_counter = ingress_request_counter.clone();
+                    inflight.push(async move {
+                        let status = match commit.await {
+                            Ok(()) => {
+                                ingress_request_counter.increment(1);
+                                StatusCode::ACCEPTED
+                            }
+                            Err(_) => StatusCode::SERVICE_UNAVAILABLE,
+                        };
+                        _ = respond_to.send(status);
+                    });
+                },
+                _ = &mut rx => {
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    fn is_authorized(&self, headers: &HeaderMap) -> bool {
+        is_authorized(self.secret.as_deref(), self.allow_unauthenticated, headers)
+    }
+
+    fn event(&self, headers: HeaderMap, body: Bytes) -> Result<IngressEvent, Error> {
+        let origin = EventOrigin::Webhook {
+            name: self.name.clone(),
+        };
+
+        let key = headers
+            .get(KEY_HEADER)
+            .map(|value| Bytes::copy_from_slice(value.as_bytes()))
+            .unwrap_or_default();
+        let idempotency_key = headers
+            .get(IDEMPOTENCY_KEY_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .filter(|value| !value.is_empty())
+            .map(ByteString::from);
+        let (scope, limit_key) = self.builder.extract_scope_limit_key(
+            &origin,
+            headers
+                .iter()
+                .map(|(name, value)| (name.as_str(), value.as_bytes())),
+        )?;
+
+        // Forward the request headers, so handlers can read e.g. the event type
+        let mut event_headers = Vec::with_capacity(headers.len() + 2);
+        event_headers.push(Header::new("webhook.name", self.name.as_str()));
+        event_headers.push(Header::new(
+            "restate.subscription.id",
+            self.builder.subscription_id(),
+        ));
+        event_headers.extend(
+            headers
+                .iter()
+                .filter(|(name, _)| *name != header::AUTHORIZATION)
+                .filter_map(|(name, value)| Some(Header::new(name.as_str(), value.to_str().ok()?))),
+        );
+
+        Ok(IngressEvent {
+            key,
+            payload: body,
+            headers: event_headers,
+            scope,
+            limit_key,
+            idempotency_key,
+            origin,
+        })
+    }
+}
+
+/// Checks the bearer token of a webhook request. Requests to webhooks without secret are accepted
+/// only if the subscription opted out of the authentication explicitly.
+fn is_authorized(secret: Option<&str>, allow_unauthenticated: bool, headers: &HeaderMap) -> bool {
+    let Some(secret) = secret else {
+        return allow_unauthenticated;
+    };
+
+    headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|token| constant_time_eq(token.as_bytes(), secret.as_bytes()))
+}
+
+/// Serves the webhook listener until shutdown, with the TLS settings of the ingress listener.
+pub async fn run_listener(
+    address: SocketAddr,
+    tls_options: Option<ListenerTlsOptions>,
+    registry: WebhookRegistry,
+) -> anyhow::Result<()> {
+    let listeners = Listeners::<WebhookPort>::new_tcp_listener(address)
+        .await
+        .map_err(|err| Error::WebhookBind(address, err))?;
+
+    let service =
+        hyper::service::service_fn(move |request| handle_request(registry.clone(), request));
+    run_hyper_server(listeners, tls_options.as_ref(), service, || {
+        debug!("Webhook listener stopped")
+    })
+    .await?;
+    Ok(())
+}
+
+async fn handle_request(
+    registry: WebhookRegistry,
+    request: Request<Incoming>,
+) -> Result<Response<Full<Bytes>>, Infallible> {
+    let Some(name) = request
+        .uri()
+        .path()
+        .strip_prefix(WEBHOOKS_PATH_PREFIX)
+        .map(|name| name.trim_end_matches('/'))
+        .filter(|name| !name.is_empty() && !name.contains('/'))
+    else {
+        return Ok(response(StatusCode::NOT_FOUND));
+    };
+    if request.method() != Method::POST {
+        return Ok(response(StatusCode::METHOD_NOT_ALLOWED));
+    }
+
+    let senders = registry.senders(name);
+    if senders.is_empty() {
+        return Ok(response(StatusCode::NOT_FOUND));
+    }
+
+    let (parts, body) = request.into_parts();
+    let request_size_limit = Configuration::pinned().ingress.request_size_limit().get();
+    let body = match Limited::new(body, request_size_limit).collect().await {
+        Ok(collected) => collected.to_bytes(),
+        Err(_) => return Ok(response(StatusCode::PAYLOAD_TOO_LARGE)),
+    };
+
+    // Every subscription of the webhook must accept the request. Reply with the worst status,
+    // so that the sender retries if one of them failed.
+    let mut status = StatusCode::ACCEPTED;
+    let mut responses = Vec::with_capacity(senders.len());
+    for sender in senders {
+        let (tx, rx) = oneshot::channel();
+        let request = WebhookRequest {
+            headers: parts.headers.clone(),
+            body: body.clone(),
+            respond_to: tx,
+        };
+        if sender.send(request).await.is_err() {
+            status = status.max(StatusCode::SERVICE_UNAVAILABLE);
+            continue;
+        }
+        responses.push(rx);
+    }
+    for rx in responses {
+        status = status.max(rx.await.unwrap_or(StatusCode::SERVICE_UNAVAILABLE));
+    }
+
+    Ok(response(status))
+}
+
+fn response(status: StatusCode) -> Response<Full<Bytes>> {
+    Response::builder()
+        .status(status)
+        .body(Full::default())
+        .expect("response must be valid")
+}
+
+/// Producer id of the envelopes of a webhook subscription. Webhook envelopes are not
+/// deduplicated by sequence number, so this is only used to identify the producer.
+fn producer_id(subscription: &SubscriptionId, name: &str) -> u128 {
+    let mut hasher = xxhash_rust::xxh3::Xxh3::new();
+
+    "webhook".hash(&mut hasher);
+    '\0'.hash(&mut hasher);
+    subscription.hash(&mut hasher);
+    '\0'.hash(&mut hasher);
+    name.hash(&mut hasher);
+
+    hasher.digest128()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registration_is_removed_on_drop() {
+        let registry = WebhookRegistry::default();
+        let subscription_a = SubscriptionId::default();
+        let subscription_b = SubscriptionId::default();
+
+        let (tx, _rx) = mpsc::channel(1);
+        let registration_a = registry.register("github", subscription_a, tx.clone());
+        let registration_b = registry.register("github", subscription_b, tx);
+        assert_eq!(registry.senders("github").len(), 2);
+        assert!(registry.senders("stripe").is_empty());
+
+        drop(registration_a);
+        assert_eq!(registry.senders("github").len(), 1);
+
+        drop(registration_b);
+        assert!(registry.senders("github").is_empty());
+        assert!(registry.0.read().is_empty());
+    }
+
+    #[test]
+    fn authorization() {
+        let with_token = |token: &str| {
+            HeaderMap::from_iter([(
+                header::AUTHORIZATION,
+                format!("Bearer {token}").parse().unwrap(),
+            )])
+        };
+
+        assert!(is_authorized(Some("secret"), false, &with_token("secret")));
+        assert!(!is_authorized(Some("secret"), false, &with_token("other")));
+        assert!(!is_authorized(Some("secret"), false, &with_token("secre")));
+        assert!(!is_authorized(Some("secret"), false, &HeaderMap::new()));
+        // The secret is checked even when unauthenticated requests are allowed
+        assert!(!is_authorized(Some("secret"), true, &HeaderMap::new()));
+
+        assert!(is_authorized(None, true, &HeaderMap::new()));
+        assert!(!is_authorized(None, false, &HeaderMap::new()));
+        assert!(!is_authorized(None, false, &with_token("secret")));
+    }
+}
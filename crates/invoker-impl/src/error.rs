@@ -252,6 +252,21 @@ impl InvokerError {
         }
     }
 
+    /// Whether the error shows that the deployment is unavailable, that is it could not be
+    /// connected to or it replied with a 5xx status code. These errors trip the circuit breaker
+    /// of the deployment.
+    pub(crate) fn is_deployment_unavailable(&self) -> bool {
+        match self {
+            InvokerError::Client(err) => err.is_connect(),
+            InvokerError::ServiceUnavailable(_) => true,
+            InvokerError::UnexpectedResponse(status_code)
+            | InvokerError::RateLimited {
+                code: status_code, ..
+            } => status_code.is_server_error(),
+            _ => false,
+        }
+    }
+
     pub(crate) fn should_bump_start_message_retry_count_since_last_stored_entry(&self) -> bool {
         !matches!(
             self,
@@ -405,6 +420,8 @@ pub(crate) enum CommandPreconditionError {
     InvalidLimitKey(String, restate_types::limit_key::ParseError),
     #[error("invalid invocation id {0}: {1}")]
     InvalidInvocationId(String, IdDecodeError),
+    #[error("delayed calls to the virtual topic '{0}' are not supported")]
+    DelayedVirtualTopicCall(String),
 }
 
 #[derive(Debug)]
@@ -24,7 +24,7 @@ use restate_types::vqueues::VQueueId;
 use restate_worker_api::resources::ReservedResources;
 
 use crate::error::RequestedErrorBehavior;
-use crate::quota::ConcurrencySlot;
+use crate::quota::{ConcurrencySlot, DeploymentSlot};
 
 use super::*;
 
@@ -55,6 +55,8 @@ pub(super) struct InvocationStateMachine<K: TimerKey = tokio_util::time::delay_q
     pub(super) limit_key: LimitKey<ReString>,
     pub(super) idempotency_key: Option<ReString>,
     pub(super) last_transient_error_event: Option<TransientErrorEvent>,
+    /// The deployment the invocation was pinned to when it was invoked, if any.
+    pub(super) pinned_deployment_id: Option<DeploymentId>,
     invocation_state: AttemptState<K>,
     retry_policy_state: RetryPolicyState,
     /// This retry count is passed in the StartMessage.
@@ -169,6 +171,11 @@ enum AttemptState<K: TimerKey> {
         using_deployment: Option<PinnedDeployment>,
         // If true, we need to notify the deployment id to the partition processor
         should_notify_pinned_deployment: bool,
+        // If true, the deployment answered during this attempt, and we have reported it
+        // to its circuit breaker
+        deployment_responded: bool,
+        // Counts this attempt against the limits of its deployment
+        deployment_slot: DeploymentSlot,
     },
 
     WaitingRetry {
@@ -245,6 +252,7 @@ impl<K: TimerKey> InvocationStateMachine<K> {
             limit_key,
             idempotency_key,
             last_transient_error_event: None,
+            pinned_deployment_id: None,
             invocation_state: AttemptState::New,
             retry_policy_state: RetryPolicyState {
                 selected_from_deployment_id: None,
@@ -262,6 +270,7 @@ impl<K: TimerKey> InvocationStateMachine<K> {
         &mut self,
         abort_handle: AbortHandle,
         notifications_tx: mpsc::UnboundedSender<Notification>,
+        deployment_slot: DeploymentSlot,
     ) {
         debug_assert!(matches!(
             &self.invocation_state,
@@ -276,6 +285,33 @@ impl<K: TimerKey> InvocationStateMachine<K> {
             run_completion_proposals_to_ack: Default::default(),
             using_deployment: None,
             should_notify_pinned_deployment: false,
+            deployment_responded: false,
+            deployment_slot,
+        };
+    }
+
+    /// Parks the invocation until its deployment lets the next attempt through. Unlike a retry,
+    /// this does not count towards the retry policy. An in-flight attempt is aborted, which is
+    /// only safe as long as it didn't talk to the deployment yet.
+    pub(super) fn hold(&mut self, register_timer: impl FnOnce() -> K) {
+        let journal_tracker = match &self.invocation_state {
+            AttemptState::New => Default::default(),
+            AttemptState::InFlight {
+                abort_handle,
+                journal_tracker,
+                ..
+            } => {
+                abort_handle.abort();
+                journal_tracker.clone()
+            }
+            AttemptState::WaitingRetry {
+                journal_tracker, ..
+            } => journal_tracker.clone(),
+        };
+        self.invocation_state = AttemptState::WaitingRetry {
+            timer_fired: false,
+            journal_tracker,
+            retry_timer_key: register_timer(),
         };
     }
 
@@ -345,6 +381,26 @@ impl<K: TimerKey> InvocationStateMachine<K> {
         }
     }
 
+    /// The deployment the slot of the in-flight attempt was acquired for.
+    pub(super) fn deployment_slot_id(&self) -> Option<DeploymentId> {
+        match &self.invocation_state {
+            AttemptState::InFlight {
+                deployment_slot, ..
+            } => deployment_slot.deployment_id(),
+            _ => None,
+        }
+    }
+
+    /// Replaces the slot of the in-flight attempt, releasing the previous one.
+    pub(super) fn replace_deployment_slot(&mut self, slot: DeploymentSlot) {
+        if let AttemptState::InFlight {
+            deployment_slot, ..
+        } = &mut self.invocation_state
+        {
+            *deployment_slot = slot;
+        }
+    }
+
     pub(super) fn pinned_deployment_to_notify(&mut self) -> Option<PinnedDeployment> {
         debug_assert!(matches!(
             &self.invocation_state,
@@ -628,7 +684,8 @@ impl<K: TimerKey> InvocationStateMachine<K> {
         } else {
             match self.retry_policy_state.on_max_attempts {
                 OnMaxAttempts::Pause => OnTaskError::Pause,
-                OnMaxAttempts::Kill => OnTaskError::Fail,
+                // The partition processor takes care of forwarding to the dead-letter handler
+                OnMaxAttempts::Kill | OnMaxAttempts::DeadLetter => OnTaskError::Fail,
             }
         }
     }
@@ -644,6 +701,30 @@ impl<K: TimerKey> InvocationStateMachine<K> {
         }
     }
 
+    /// The deployment the next attempt is expected to use: the one of the last attempt, if any
+    /// attempt got as far as choosing one, or otherwise the one the invocation was pinned to.
+    pub(super) fn next_deployment_id(&self) -> Option<DeploymentId> {
+        self.retry_policy_state
+            .selected_from_deployment_id
+            .or(self.pinned_deployment_id)
+    }
+
+    /// Marks that the deployment answered the in-flight attempt. Returns the deployment the
+    /// first time this is called for the attempt.
+    pub(super) fn notify_deployment_responded(&mut self) -> Option<DeploymentId> {
+        if let AttemptState::InFlight {
+            using_deployment: Some(using_deployment),
+            deployment_responded,
+            ..
+        } = &mut self.invocation_state
+            && !*deployment_responded
+        {
+            *deployment_responded = true;
+            return Some(using_deployment.deployment_id);
+        }
+        None
+    }
+
     pub(super) fn attempt_deployment_id(&self) -> AttemptDeploymentId {
         AttemptDeploymentId(match &self.invocation_state {
             AttemptState::InFlight {
@@ -719,6 +800,12 @@ pub(super) enum OnTaskError {
 
 pub(super) struct AttemptDeploymentId(Option<DeploymentId>);
 
+impl AttemptDeploymentId {
+    pub(super) fn get(&self) -> Option<DeploymentId> {
+        self.0
+    }
+}
+
 impl fmt::Display for AttemptDeploymentId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.0 {
@@ -833,7 +920,7 @@ mod tests {
         let abort_handle = tokio::spawn(async {}).abort_handle();
         let (tx, mut rx) = mpsc::unbounded_channel();
 
-        invocation_state_machine.start(abort_handle, tx);
+        invocation_state_machine.start(abort_handle, tx, DeploymentSlot::empty());
         invocation_state_machine.notify_new_command(1, true);
         invocation_state_machine.notify_new_command(2, false);
         invocation_state_machine.notify_new_command(3, true);
@@ -859,7 +946,7 @@ mod tests {
     ) -> mpsc::UnboundedReceiver<Notification> {
         let abort_handle = tokio::spawn(async {}).abort_handle();
         let (tx, rx) = mpsc::unbounded_channel();
-        ism.start(abort_handle, tx);
+        ism.start(abort_handle, tx, DeploymentSlot::empty());
         ism.notify_pinned_deployment(
             PinnedDeployment::new(DeploymentId::default(), version),
             true,
@@ -952,6 +1039,7 @@ mod tests {
         invocation_state_machine.start(
             tokio::spawn(async {}).abort_handle(),
             mpsc::unbounded_channel().0,
+            DeploymentSlot::empty(),
         );
 
         // Notify error
@@ -972,6 +1060,7 @@ mod tests {
         invocation_state_machine.start(
             tokio::spawn(async {}).abort_handle(),
             mpsc::unbounded_channel().0,
+            DeploymentSlot::empty(),
         );
 
         // Get error again
@@ -992,6 +1081,7 @@ mod tests {
         invocation_state_machine.start(
             tokio::spawn(async {}).abort_handle(),
             mpsc::unbounded_channel().0,
+            DeploymentSlot::empty(),
         );
         assert_eq!(
             invocation_state_machine.start_message_retry_count_since_last_stored_command,
@@ -1013,7 +1103,7 @@ mod tests {
         let abort_handle = tokio::spawn(async {}).abort_handle();
         let (tx, _rx) = mpsc::unbounded_channel();
 
-        invocation_state_machine.start(abort_handle, tx);
+        invocation_state_machine.start(abort_handle, tx, DeploymentSlot::empty());
 
         // Invoker generates entry 1
         invocation_state_machine.notify_new_command(1, false);
@@ -1045,7 +1135,7 @@ mod tests {
         let abort_handle = tokio::spawn(async {}).abort_handle();
         let (tx, _rx) = mpsc::unbounded_channel();
 
-        invocation_state_machine.start(abort_handle, tx);
+        invocation_state_machine.start(abort_handle, tx, DeploymentSlot::empty());
         // Only RunCompletion notifications are valid proposals today; the ISM asserts this.
         // requested_ack=false because this test is about journal-tracker accounting for
         // retry safety, not about the v7 ack swap.
@@ -208,6 +208,18 @@ pub(super) enum InvocationTaskOutputInner {
     },
 }
 
+impl InvocationTaskOutputInner {
+    /// Whether this output can only be produced once the deployment answered the request.
+    pub(super) fn is_deployment_response(&self) -> bool {
+        !matches!(
+            self,
+            InvocationTaskOutputInner::PinnedDeployment(..)
+                | InvocationTaskOutputInner::Failed(..)
+                | InvocationTaskOutputInner::ShouldYield { .. }
+        )
+    }
+}
+
 /// Sender half of the invoker body channel.
 ///
 /// Unbounded because backpressure is provided by the memory budget rather than
@@ -499,11 +511,12 @@ where
                 )
             } else {
                 // We can choose the freshest deployment for the latest revision
-                // of the registered service.
+                // of the registered service, unless a traffic split routes us elsewhere.
                 let deployment = shortcircuit!(
                     schemas
-                        .resolve_latest_deployment_for_service(
-                            self.invocation_target.service_name()
+                        .resolve_deployment_for_new_invocation(
+                            &self.invocation_id,
+                            &self.invocation_target
                         )
                         .ok_or(InvokerError::NoDeploymentForService)
                 );
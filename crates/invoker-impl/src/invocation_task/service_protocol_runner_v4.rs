@@ -57,6 +57,7 @@ use restate_types::journal_v2::{
 use restate_types::limit_key::LimitKey;
 use restate_types::schema::deployment::{Deployment, DeploymentType, ProtocolType};
 use restate_types::schema::invocation_target::{DeploymentStatus, InvocationTargetResolver};
+use restate_types::schema::subscriptions::VIRTUAL_TOPIC_SERVICE_NAME;
 use restate_types::service_protocol::ServiceProtocolVersion;
 use restate_util_string::{ReString, RestateString, RestrictedValue, ToReString};
 use restate_worker_api::invoker::JournalMetadata;
@@ -1094,6 +1095,14 @@ where
                 TerminalLoopState::Continue(())
             }
             Message::OneWayCallCommand(cmd) => {
+                if cmd.invoke_time != 0 && cmd.service_name == VIRTUAL_TOPIC_SERVICE_NAME {
+                    // Virtual topics are published as soon as the call leaves the outbox
+                    return TerminalLoopState::Failed(InvokerError::CommandPrecondition(
+                        self.command_index,
+                        EntryType::Command(CommandType::OneWayCall),
+                        CommandPreconditionError::DelayedVirtualTopicCall(cmd.handler_name),
+                    ));
+                }
                 let name = cmd.name;
                 let entry: Entry = OneWayCallCommand {
                     request: shortcircuit!(
@@ -24,18 +24,18 @@ use std::path::PathBuf;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::time::{Duration, Instant, SystemTime};
-use std::{cmp, panic};
+use std::{cmp, fmt, panic};
 
 use futures::StreamExt;
 use gardal::futures::ThrottledStream;
 use gardal::{PaddedAtomicSharedStorage, StreamExt as GardalStreamExt, TokioClock};
-use metrics::counter;
+use metrics::{counter, gauge};
 use tokio::sync::mpsc;
 use tokio::task::{AbortHandle, JoinSet};
 use tokio_util::time::DelayQueue;
 use tokio_util::time::delay_queue::Key as RetryTimerKey;
 use tracing::instrument;
-use tracing::{debug, trace, warn};
+use tracing::{debug, info, trace, warn};
 
 use restate_core::cancellation_token;
 use restate_errors::warn_it;
@@ -62,8 +62,12 @@ use restate_types::schema::deployment::DeploymentResolver;
 use restate_types::schema::invocation_target::InvocationTargetResolver;
 use restate_types::sharding::KeyRange;
 use restate_util_time::DurationExt;
+use restate_worker_api::invoker::canary::CanaryOutcomes;
 use restate_worker_api::invoker::capacity::TokenBucket;
-use restate_worker_api::invoker::invocation_reader::InvocationReader;
+use restate_worker_api::invoker::circuit_breaker::{Admission, DeploymentCircuitBreakers};
+use restate_worker_api::invoker::invocation_reader::{
+    InvocationReader, InvocationReaderTransaction,
+};
 use restate_worker_api::invoker::{
     Effect, EffectKind, EntryEnricher, FencedEffect, InvocationStatusReport, YieldReason,
 };
@@ -78,9 +82,12 @@ use crate::invocation_state_machine::OnTaskError;
 use crate::invocation_task::InvocationTask;
 use crate::invocation_task::{InvocationTaskOutput, InvocationTaskOutputInner};
 use crate::metric_definitions::{
-    INVOKER_ENQUEUE, INVOKER_INVOCATION_TASKS, TASK_OP_COMPLETED, TASK_OP_FAILED, TASK_OP_STARTED,
+    INVOKER_CIRCUIT_BREAKER_HELD_ATTEMPTS, INVOKER_CIRCUIT_BREAKER_OPEN,
+    INVOKER_CIRCUIT_BREAKER_TRIPS, INVOKER_DEPLOYMENT_LIMITS_HELD_ATTEMPTS, INVOKER_ENQUEUE,
+    INVOKER_INVOCATION_TASKS, TASK_OP_COMPLETED, TASK_OP_FAILED, TASK_OP_STARTED,
     TASK_OP_SUSPENDED,
 };
+use crate::quota::{DeploymentQuotaExceeded, DeploymentSlot};
 use crate::status_store::InvocationStatusStore;
 
 use self::input_command::VQueueInvokeCommand;
@@ -101,6 +108,44 @@ fn fence(token: FencingToken, effect: Effect) -> FencedEffect {
     }
 }
 
+/// How long an attempt held by the concurrency limit of its deployment waits at most. The
+/// attempt is woken up earlier when another attempt against the deployment ends.
+const DEPLOYMENT_CONCURRENCY_HOLD: Duration = Duration::from_secs(5);
+
+/// Whether the next attempt of an invocation can start.
+enum AttemptAdmission {
+    /// Start the attempt, counting it against the limits of its deployment with the slot.
+    Start(DeploymentSlot),
+    Held(AttemptHold),
+}
+
+struct AttemptHold {
+    deployment_id: DeploymentId,
+    duration: Duration,
+    reason: HoldReason,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HoldReason {
+    CircuitBreaker,
+    DeploymentConcurrency,
+    DeploymentRate,
+}
+
+impl fmt::Display for HoldReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HoldReason::CircuitBreaker => {
+                f.write_str("the circuit breaker of its deployment is open")
+            }
+            HoldReason::DeploymentConcurrency => {
+                f.write_str("its deployment reached its concurrency limit")
+            }
+            HoldReason::DeploymentRate => f.write_str("its deployment reached its rate limit"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) enum Notification {
     /// V1 completion signal: just the entry index (data read from RocksDB on demand).
@@ -255,6 +300,8 @@ impl<StorageReader, TEntryEnricher, Schemas> Service<StorageReader, TEntryEnrich
         invocation_token_bucket: Option<TokenBucket>,
         action_token_bucket: Option<TokenBucket>,
         memory_pool: MemoryPool,
+        circuit_breakers: DeploymentCircuitBreakers,
+        canary_outcomes: CanaryOutcomes,
     ) -> Service<StorageReader, TEntryEnricher, Schemas>
     where
         StorageReader: InvocationReader + Clone + Send + Sync + 'static,
@@ -304,6 +351,9 @@ impl<StorageReader, TEntryEnricher, Schemas> Service<StorageReader, TEntryEnrich
                 ),
                 memory_pool,
                 pending_memory_lease: None,
+                circuit_breakers,
+                canary_outcomes,
+                deployment_quotas: Default::default(),
             },
             invocation_token_bucket,
         }
@@ -322,6 +372,8 @@ impl<StorageReader, TEntryEnricher, Schemas> Service<StorageReader, TEntryEnrich
         invocation_token_bucket: Option<TokenBucket>,
         action_token_bucket: Option<TokenBucket>,
         memory_pool: MemoryPool,
+        circuit_breakers: DeploymentCircuitBreakers,
+        canary_outcomes: CanaryOutcomes,
     ) -> Result<Service<StorageReader, TEntryEnricher, Schemas>, BuildError>
     where
         StorageReader: InvocationReader + Clone + Send + Sync + 'static,
@@ -344,6 +396,8 @@ impl<StorageReader, TEntryEnricher, Schemas> Service<StorageReader, TEntryEnrich
             invocation_token_bucket,
             action_token_bucket,
             memory_pool,
+            circuit_breakers,
+            canary_outcomes,
         ))
     }
 }
@@ -460,13 +514,20 @@ struct ServiceInner<InvocationTaskRunner, Schemas, StorageReader> {
     /// Acquired at the top of `step()` when the queue is non-empty, and consumed
     /// when the segment queue arm fires.
     pending_memory_lease: Option<MemoryLease>,
+
+    // Circuit breakers of the deployments, shared across all invokers on this node.
+    circuit_breakers: DeploymentCircuitBreakers,
+    // Outcomes of the attempts against canary deployments, shared across all invokers on this node.
+    canary_outcomes: CanaryOutcomes,
+    // Attempts running against each deployment, to enforce the deployment limits.
+    deployment_quotas: quota::DeploymentQuotas<RetryTimerKey>,
 }
 
 impl<ITR, Schemas, IR> ServiceInner<ITR, Schemas, IR>
 where
     ITR: InvocationTaskRunner<IR>,
     IR: InvocationReader + Clone + Send + Sync + 'static,
-    Schemas: InvocationTargetResolver,
+    Schemas: DeploymentResolver + InvocationTargetResolver,
 {
     // Returns true if we should execute another step, false if we should stop executing steps
     async fn step(
@@ -516,7 +577,7 @@ where
                             "partition_id" => self.invoker_id_label.clone()
                         )
                         .increment(1);
-                        self.handle_vqueue_invoke(options, *command);
+                        self.handle_vqueue_invoke(options, *command).await;
                     },
                     // --- Other commands (they don't go through the segment queue)
                     InputCommand::Abort { ref invocation_id } => {
@@ -545,7 +606,7 @@ where
             Some(invoke_input_command) = segmented_input_queue.next(), if !segmented_input_queue.inner().is_empty() && self.quota.is_slot_available() && self.pending_memory_lease.is_some() => {
                 let initial_memory_lease = self.pending_memory_lease.take().unwrap();
                 let budget = self.create_outbound_budget(options, initial_memory_lease);
-                self.handle_invoke(options, invoke_input_command.invocation_id, invoke_input_command.fencing_token, invoke_input_command.invocation_target, budget);
+                self.handle_invoke(options, invoke_input_command.invocation_id, invoke_input_command.fencing_token, invoke_input_command.invocation_target, budget).await;
             },
             memory_lease = self.memory_pool.reserve(initial_invocation_memory), if !segmented_input_queue.inner().is_empty() && self.pending_memory_lease.is_none() => {
                 self.pending_memory_lease = Some(memory_lease);
@@ -563,6 +624,9 @@ where
                 if self.invocation_state_machine_manager.is_stale_fencing_token(&invocation_id, fencing_token) {
                     trace!(restate.invocation.id = %invocation_id, "Dropping stale invoker task output from a previous attempt");
                 } else {
+                if inner.is_deployment_response() {
+                    self.handle_deployment_responded(&invocation_id);
+                }
                 match inner {
                     InvocationTaskOutputInner::PinnedDeployment(deployment_metadata, has_changed) => {
                         self.handle_pinned_deployment(
@@ -632,6 +696,9 @@ where
                 let invocation_id = expired.into_inner();
                 self.handle_retry_timer_fired(options, invocation_id, timer_key);
             },
+            deployment_id = self.deployment_quotas.next_released() => {
+                self.handle_deployment_slot_released(options, deployment_id);
+            },
             Some(invocation_task_result) = self.invocation_tasks.join_next() => {
                 if let Err(err) = invocation_task_result {
                     // Propagate panics coming from invocation tasks.
@@ -663,7 +730,53 @@ where
             restate.invocation.target = %command.invocation_target,
         )
     )]
-    fn handle_vqueue_invoke(&mut self, options: &InvokerOptions, mut command: VQueueInvokeCommand) {
+    async fn handle_vqueue_invoke(
+        &mut self,
+        options: &InvokerOptions,
+        mut command: VQueueInvokeCommand,
+    ) {
+        // Invocations are pinned to a deployment once they have run on it
+        let pinned_deployment_id = command
+            .permit
+            .metadata
+            .deployment
+            .as_deref()
+            .and_then(|deployment_id| deployment_id.parse().ok());
+        let deployment_slot = match self.attempt_admission(
+            options,
+            &command.invocation_id,
+            &command.invocation_target,
+            pinned_deployment_id,
+            false,
+        ) {
+            AttemptAdmission::Start(deployment_slot) => deployment_slot,
+            AttemptAdmission::Held(hold) => {
+                // Keep the invocation in its vqueue while the attempt is held, rather than
+                // holding on to a concurrency slot of the invoker.
+                trace!(
+                    "Yielding invocation for {} because {}",
+                    hold.duration.friendly(),
+                    hold.reason
+                );
+                let _ = self
+                    .invocation_state_machine_manager
+                    .partition_sender()
+                    .send(fence(
+                        command.fencing_token,
+                        Effect {
+                            invocation_id: command.invocation_id,
+                            kind: EffectKind::Yield {
+                                reason: YieldReason::InvokerLoadShedding,
+                                error_event: None,
+                                resume_at: Some(RoughTimestamp::now() + hold.duration),
+                            },
+                        },
+                    ))
+                    .await;
+                return;
+            }
+        };
+
         let (mut retry_iter, on_max_attempts) =
             self.schemas.live_load().resolve_invocation_retry_policy(
                 None,
@@ -701,6 +814,7 @@ where
                 concurrency_slot,
             ),
             budget,
+            deployment_slot,
         )
     }
 
@@ -714,7 +828,7 @@ where
             restate.invocation.target = %invocation_target,
         )
     )]
-    fn handle_invoke(
+    async fn handle_invoke(
         &mut self,
         options: &InvokerOptions,
         invocation_id: InvocationId,
@@ -729,26 +843,46 @@ where
                 invocation_target.handler_name(),
             );
 
-        let storage_reader = self.invocation_state_machine_manager.storage_reader();
+        let storage_reader = self
+            .invocation_state_machine_manager
+            .storage_reader()
+            .clone();
         let concurrency_slot = self.quota.acquire_slot();
         let fake_permit = ReservedResources::new_empty();
-        self.start_invocation_task(
+        let mut ism = InvocationStateMachine::create(
+            None,
+            fake_permit,
+            fencing_token,
+            invocation_target,
+            LimitKey::None,
+            None,
+            retry_iter,
+            on_max_attempts,
+            concurrency_slot,
+        );
+
+        ism.pinned_deployment_id = self.read_pinned_deployment_id(&invocation_id).await;
+        match self.attempt_admission(
             options,
-            storage_reader.clone(),
-            invocation_id,
-            InvocationStateMachine::create(
-                None,
-                fake_permit,
-                fencing_token,
-                invocation_target,
-                LimitKey::None,
-                None,
-                retry_iter,
-                on_max_attempts,
-                concurrency_slot,
+            &invocation_id,
+            &ism.invocation_target,
+            ism.pinned_deployment_id,
+            true,
+        ) {
+            AttemptAdmission::Held(hold) => {
+                self.abort_previous_attempt(&invocation_id);
+                ism.budget = Some(budget);
+                self.hold_invocation(invocation_id, ism, hold);
+            }
+            AttemptAdmission::Start(deployment_slot) => self.start_invocation_task(
+                options,
+                storage_reader,
+                invocation_id,
+                ism,
+                budget,
+                deployment_slot,
             ),
-            budget,
-        )
+        }
     }
 
     #[instrument(
@@ -804,7 +938,8 @@ where
         pinned_deployment: PinnedDeployment,
         has_changed: bool,
     ) {
-        self.invocation_state_machine_manager
+        let exceeded = self
+            .invocation_state_machine_manager
             .handle_for_invocation(&invocation_id, |_, ism| {
                 trace!(
                     restate.invocation.target = %ism.invocation_target,
@@ -824,8 +959,45 @@ where
                     self.schemas.live_load(),
                 );
 
+                // The attempt counts against the limits of the deployment it actually uses.
+                // Invocations coming from a vqueue are limited by the scheduler instead.
+                if ism.qid.is_none()
+                    && ism.deployment_slot_id() != Some(pinned_deployment.deployment_id)
+                {
+                    let limits = self
+                        .schemas
+                        .live_load()
+                        .get_deployment(&pinned_deployment.deployment_id)
+                        .map(|deployment| deployment.limits)
+                        .unwrap_or_default();
+                    if let Err(exceeded) = self
+                        .deployment_quotas
+                        .check(pinned_deployment.deployment_id, &limits)
+                    {
+                        return Some(exceeded);
+                    }
+                    ism.replace_deployment_slot(
+                        self.deployment_quotas
+                            .acquire(pinned_deployment.deployment_id),
+                    );
+                }
+
                 ism.notify_pinned_deployment(pinned_deployment, has_changed);
-            });
+                None
+            })
+            .flatten();
+
+        // The attempt was admitted against another deployment, e.g. because the invocation was
+        // pinned to an older deployment before. It didn't talk to the deployment yet, so it can
+        // be held until the deployment it's pinned to has capacity.
+        if let Some(exceeded) = exceeded
+            && let Some((_, _, ism)) = self
+                .invocation_state_machine_manager
+                .remove_invocation(&invocation_id)
+        {
+            let hold = Self::deployment_limits_hold(pinned_deployment.deployment_id, exceeded);
+            self.hold_invocation(invocation_id, ism, hold);
+        }
     }
 
     #[instrument(
@@ -1109,6 +1281,7 @@ where
                 "Invocation task closed correctly");
 
             self.status_store.on_end(&invocation_id);
+            Self::record_canary_outcome(&self.schemas, &self.canary_outcomes, &ism, false);
             let _ = sender
                 .send(fence(
                     ism.fencing_token,
@@ -1147,6 +1320,7 @@ where
             )
             .increment(1);
             self.status_store.on_end(&invocation_id);
+            Self::record_canary_outcome(&self.schemas, &self.canary_outcomes, &ism, false);
 
             if ism.requested_pause {
                 // We should send pause instead
@@ -1215,6 +1389,7 @@ where
             )
             .increment(1);
             self.status_store.on_end(&invocation_id);
+            Self::record_canary_outcome(&self.schemas, &self.canary_outcomes, &ism, false);
 
             if ism.requested_pause {
                 // We should send pause instead
@@ -1283,6 +1458,7 @@ where
             )
             .increment(1);
             self.status_store.on_end(&invocation_id);
+            Self::record_canary_outcome(&self.schemas, &self.canary_outcomes, &ism, false);
 
             if ism.requested_pause {
                 // We should send pause instead
@@ -1347,6 +1523,7 @@ where
         {
             // Stash the budget on the ISM so it can be reused if we retry.
             ism.budget = Some(returned_budget);
+            Self::record_canary_outcome(&self.schemas, &self.canary_outcomes, &ism, true);
             self.handle_error_event(invocation_id, error, ism).await;
         } else {
             // If no state machine, this might be a result for an aborted invocation.
@@ -1484,6 +1661,38 @@ where
         }
     }
 
+    #[instrument(
+        level = "trace",
+        skip_all,
+        fields(
+            restate.deployment.id = %deployment_id,
+        )
+    )]
+    fn handle_deployment_slot_released(
+        &mut self,
+        options: &InvokerOptions,
+        deployment_id: DeploymentId,
+    ) {
+        let mut waiters = self.deployment_quotas.release(deployment_id);
+        while let Some((invocation_id, hold_timer_key)) = waiters.pop_front() {
+            // Skip the waiters that were woken up, or went away, in the meantime
+            let still_held = self
+                .invocation_state_machine_manager
+                .resolve_invocation(&invocation_id)
+                .is_some_and(|(_, ism)| ism.take_retry_timer_key() == Some(hold_timer_key));
+            if still_held {
+                trace!(
+                    restate.invocation.id = %invocation_id,
+                    "Waking up invocation held by the concurrency limit of its deployment"
+                );
+                self.handle_retry_now_invocation(options, invocation_id);
+                break;
+            }
+        }
+        self.deployment_quotas
+            .restore_waiters(deployment_id, waiters);
+    }
+
     #[instrument(
         level = "trace",
         skip_all,
@@ -1561,6 +1770,11 @@ where
         mut ism: InvocationStateMachine,
     ) {
         let attempt_deployment_id = ism.attempt_deployment_id();
+        if error.is_deployment_unavailable()
+            && let Some(deployment_id) = attempt_deployment_id.get()
+        {
+            self.handle_deployment_unavailable(deployment_id);
+        }
 
         // Call handle_task_error with a closure that registers the timer.
         // We need to capture the duration for logging and status updates.
@@ -1858,6 +2072,7 @@ where
         invocation_id: InvocationId,
         mut ism: InvocationStateMachine,
         budget: LocalMemoryPool,
+        deployment_slot: DeploymentSlot,
     ) {
         // If an in-flight state machine for this invocation already exists, abort it before
         // starting the new task. This happens when a fresh Invoke/VQInvoke races a previous
@@ -1865,19 +2080,7 @@ where
         // `register_invocation` would silently overwrite the old ISM, leaking its task — which
         // would keep running and could still emit now-stale effects. (No-op on the retry path,
         // which removes the ISM before re-starting it.)
-        if let Some((_, _, mut old_ism)) = self
-            .invocation_state_machine_manager
-            .remove_invocation(&invocation_id)
-        {
-            if let Some(timer_key) = old_ism.take_retry_timer_key() {
-                self.retry_timers.try_remove(&timer_key);
-            }
-            trace!(
-                restate.invocation.id = %invocation_id,
-                "Aborting previous in-flight state machine before starting a new attempt"
-            );
-            old_ism.abort();
-        }
+        self.abort_previous_attempt(&invocation_id);
 
         // Start the InvocationTask
         let (completions_tx, completions_rx) = mpsc::unbounded_channel();
@@ -1898,7 +2101,7 @@ where
 
         // Transition the state machine, and store it
         self.status_store.on_start(invocation_id);
-        ism.start(abort_handle, completions_tx);
+        ism.start(abort_handle, completions_tx, deployment_slot);
         trace!(
             restate.invocation.target = %ism.invocation_target,
             "Invocation task started state. Invocation state: {:?}",
@@ -1914,6 +2117,241 @@ where
             .register_invocation(invocation_id, ism);
     }
 
+    fn abort_previous_attempt(&mut self, invocation_id: &InvocationId) {
+        if let Some((_, _, mut old_ism)) = self
+            .invocation_state_machine_manager
+            .remove_invocation(invocation_id)
+        {
+            if let Some(timer_key) = old_ism.take_retry_timer_key() {
+                self.retry_timers.try_remove(&timer_key);
+            }
+            trace!(
+                restate.invocation.id = %invocation_id,
+                "Aborting previous in-flight state machine before starting a new attempt"
+            );
+            old_ism.abort();
+        }
+    }
+
+    /// Parks the invocation until its deployment lets the next attempt through. The memory
+    /// budget of the invocation should be stashed on the state machine, unless the held attempt
+    /// was in flight.
+    fn hold_invocation(
+        &mut self,
+        invocation_id: InvocationId,
+        mut ism: InvocationStateMachine,
+        hold: AttemptHold,
+    ) {
+        trace!(
+            restate.invocation.target = %ism.invocation_target,
+            "Holding invocation for {} because {}",
+            hold.duration.friendly(),
+            hold.reason
+        );
+        ism.hold(|| self.retry_timers.insert(invocation_id, hold.duration));
+        if hold.reason == HoldReason::DeploymentConcurrency
+            && let Some(hold_timer_key) = ism.take_retry_timer_key()
+        {
+            self.deployment_quotas
+                .add_waiter(hold.deployment_id, invocation_id, hold_timer_key);
+        }
+        self.status_store
+            .on_held(invocation_id, SystemTime::now() + hold.duration);
+        self.invocation_state_machine_manager
+            .register_invocation(invocation_id, ism);
+    }
+
+    /// Reads the deployment the invocation is pinned to, if any. The invocation task reads it
+    /// again when it starts, so failed reads are only logged.
+    async fn read_pinned_deployment_id(
+        &self,
+        invocation_id: &InvocationId,
+    ) -> Option<DeploymentId> {
+        let mut storage_reader = self
+            .invocation_state_machine_manager
+            .storage_reader()
+            .clone();
+        let journal_metadata = storage_reader
+            .transaction()
+            .read_journal_metadata(invocation_id)
+            .await;
+        match journal_metadata {
+            Ok(journal_metadata) => journal_metadata?
+                .pinned_deployment
+                .map(|pinned_deployment| pinned_deployment.deployment_id),
+            Err(err) => {
+                debug!(
+                    restate.invocation.id = %invocation_id,
+                    "Failed to read the pinned deployment of the invocation: {err}"
+                );
+                None
+            }
+        }
+    }
+
+    /// Decides whether the next attempt of the invocation can start, based on the limits and the
+    /// circuit breaker of the deployment the attempt is expected to use: `deployment_id`, which is
+    /// the deployment of the last attempt or the one the invocation is pinned to, or otherwise
+    /// the deployment a new invocation is routed to.
+    ///
+    /// The limits are only enforced if `enforce_limits` is set. Invocations coming from a vqueue
+    /// are dispatched by the scheduler only once their deployment has capacity.
+    fn attempt_admission(
+        &mut self,
+        options: &InvokerOptions,
+        invocation_id: &InvocationId,
+        invocation_target: &InvocationTarget,
+        deployment_id: Option<DeploymentId>,
+        enforce_limits: bool,
+    ) -> AttemptAdmission {
+        let deployment = {
+            let schemas = self.schemas.live_load();
+            match deployment_id {
+                Some(deployment_id) => schemas.get_deployment(&deployment_id),
+                None => {
+                    schemas.resolve_deployment_for_new_invocation(invocation_id, invocation_target)
+                }
+            }
+        };
+        let Some(deployment) = deployment else {
+            // The slot is acquired once the invocation task picks the deployment
+            return AttemptAdmission::Start(DeploymentSlot::empty());
+        };
+
+        if enforce_limits
+            && let Err(exceeded) = self
+                .deployment_quotas
+                .check(deployment.id, &deployment.limits)
+        {
+            return AttemptAdmission::Held(Self::deployment_limits_hold(deployment.id, exceeded));
+        }
+
+        if let Admission::Held(duration) = self.circuit_breaker_admission(options, deployment.id) {
+            return AttemptAdmission::Held(AttemptHold {
+                deployment_id: deployment.id,
+                duration,
+                reason: HoldReason::CircuitBreaker,
+            });
+        }
+
+        if !enforce_limits {
+            return AttemptAdmission::Start(DeploymentSlot::empty());
+        }
+        AttemptAdmission::Start(self.deployment_quotas.acquire(deployment.id))
+    }
+
+    /// Holds an attempt that exceeds the limits of its deployment.
+    fn deployment_limits_hold(
+        deployment_id: DeploymentId,
+        exceeded: DeploymentQuotaExceeded,
+    ) -> AttemptHold {
+        let (reason, duration, limit) = match exceeded {
+            DeploymentQuotaExceeded::Concurrency => (
+                HoldReason::DeploymentConcurrency,
+                DEPLOYMENT_CONCURRENCY_HOLD,
+                "concurrency",
+            ),
+            DeploymentQuotaExceeded::Rate(wait) => (HoldReason::DeploymentRate, wait, "rate"),
+        };
+        counter!(INVOKER_DEPLOYMENT_LIMITS_HELD_ATTEMPTS, "limit" => limit).increment(1);
+        AttemptHold {
+            deployment_id,
+            duration,
+            reason,
+        }
+    }
+
+    /// Asks the circuit breaker of the deployment whether the next attempt can start.
+    fn circuit_breaker_admission(
+        &mut self,
+        options: &InvokerOptions,
+        deployment_id: DeploymentId,
+    ) -> Admission {
+        if self.circuit_breakers.is_empty() {
+            return Admission::Allowed;
+        }
+
+        let admission = self.circuit_breakers.admit(
+            &deployment_id,
+            &options.deployment_circuit_breaker,
+            tokio::time::Instant::now().into_std(),
+        );
+        match admission {
+            Admission::Allowed => {}
+            Admission::Probe => {
+                debug!(
+                    restate.deployment.id = %deployment_id,
+                    "Probing deployment whose circuit breaker is open"
+                );
+            }
+            Admission::Held(_) => {
+                counter!(INVOKER_CIRCUIT_BREAKER_HELD_ATTEMPTS).increment(1);
+            }
+        }
+        admission
+    }
+
+    /// Counts the outcome of an attempt against the canary deployment of a traffic split with a
+    /// rollback policy. The rollback itself is decided outside the invoker.
+    fn record_canary_outcome(
+        schemas: &Live<Schemas>,
+        canary_outcomes: &CanaryOutcomes,
+        ism: &InvocationStateMachine,
+        failed: bool,
+    ) {
+        let Some(deployment_id) = ism.attempt_deployment_id().get() else {
+            return;
+        };
+        let service_name = ism.invocation_target.service_name();
+        let is_monitored_canary = schemas
+            .pinned()
+            .resolve_traffic_split(service_name)
+            .is_some_and(|split| {
+                split.canary_deployment_id == deployment_id
+                    && split.rollback.is_some()
+                    && !split.rolled_back
+            });
+        if is_monitored_canary {
+            canary_outcomes.record(service_name, deployment_id, failed);
+        }
+    }
+
+    fn handle_deployment_responded(&mut self, invocation_id: &InvocationId) {
+        let Some(deployment_id) = self
+            .invocation_state_machine_manager
+            .resolve_invocation(invocation_id)
+            .and_then(|(_, ism)| ism.notify_deployment_responded())
+        else {
+            return;
+        };
+
+        if self.circuit_breakers.record_success(&deployment_id) {
+            info!(
+                restate.deployment.id = %deployment_id,
+                "Deployment is reachable again, closing its circuit breaker"
+            );
+            gauge!(INVOKER_CIRCUIT_BREAKER_OPEN).set(self.circuit_breakers.num_tripped() as f64);
+        }
+    }
+
+    fn handle_deployment_unavailable(&self, deployment_id: DeploymentId) {
+        let config = Configuration::pinned();
+        let options = &config.worker.invoker.deployment_circuit_breaker;
+        if self.circuit_breakers.record_failure(
+            &deployment_id,
+            options,
+            tokio::time::Instant::now().into_std(),
+        ) {
+            warn!(
+                restate.deployment.id = %deployment_id,
+                "Deployment is unavailable, opening its circuit breaker for {}",
+                options.open_duration
+            );
+            counter!(INVOKER_CIRCUIT_BREAKER_TRIPS).increment(1);
+            gauge!(INVOKER_CIRCUIT_BREAKER_OPEN).set(self.circuit_breakers.num_tripped() as f64);
+        }
+    }
+
     fn handle_retry_event<FN>(
         &mut self,
         options: &InvokerOptions,
@@ -1926,18 +2364,39 @@ where
             .invocation_state_machine_manager
             .remove_invocation(&invocation_id)
         {
+            let storage_reader = storage_reader.clone();
             f(&mut ism);
             if ism.is_ready_to_retry() {
-                trace!(
-                    restate.invocation.target = %ism.invocation_target,
-                    "Going to retry now");
-                let storage_reader = storage_reader.clone();
-                // Reuse the budget stashed on the ISM from the previous attempt
-                let budget = ism
-                    .budget
-                    .take()
-                    .expect("Invocation budget must be present when retrying");
-                self.start_invocation_task(options, storage_reader, invocation_id, ism, budget);
+                let enforce_limits = ism.qid.is_none();
+                match self.attempt_admission(
+                    options,
+                    &invocation_id,
+                    &ism.invocation_target,
+                    ism.next_deployment_id(),
+                    enforce_limits,
+                ) {
+                    AttemptAdmission::Held(hold) => {
+                        self.hold_invocation(invocation_id, ism, hold);
+                    }
+                    AttemptAdmission::Start(deployment_slot) => {
+                        trace!(
+                            restate.invocation.target = %ism.invocation_target,
+                            "Going to retry now");
+                        // Reuse the budget stashed on the ISM from the previous attempt. An
+                        // attempt held while in flight gave its budget up with its task.
+                        let budget = ism.budget.take().unwrap_or_else(|| {
+                            self.create_outbound_budget(options, self.memory_pool.empty_lease())
+                        });
+                        self.start_invocation_task(
+                            options,
+                            storage_reader,
+                            invocation_id,
+                            ism,
+                            budget,
+                            deployment_slot,
+                        );
+                    }
+                }
             } else {
                 trace!(
                     restate.invocation.target = %ism.invocation_target,
@@ -1964,7 +2423,7 @@ mod tests {
     use super::*;
 
     use std::future::{pending, ready};
-    use std::num::NonZeroUsize;
+    use std::num::{NonZeroU32, NonZeroUsize};
     use std::sync::Arc;
     use std::sync::atomic::{AtomicUsize, Ordering};
     use std::time::Duration;
@@ -2005,7 +2464,7 @@ mod tests {
     use crate::error::{
         InvocationMemoryExhausted, InvokerError, RequestedErrorBehavior, SdkInvocationErrorV2,
     };
-    use crate::quota::{ConcurrencySlot, InvokerConcurrencyQuota};
+    use crate::quota::{ConcurrencySlot, DeploymentSlot, InvokerConcurrencyQuota};
     use crate::test_util::EmptyStorageReader;
 
     // -- Mocks
@@ -2054,6 +2513,9 @@ mod tests {
                 ),
                 memory_pool: MemoryPool::unlimited(),
                 pending_memory_lease: None,
+                circuit_breakers: DeploymentCircuitBreakers::default(),
+                canary_outcomes: CanaryOutcomes::default(),
+                deployment_quotas: Default::default(),
             };
             (input_tx, status_tx, output_rx, service_inner)
         }
@@ -2254,6 +2716,77 @@ mod tests {
         }
     }
 
+    /// Schemas in which every service is exposed by a single deployment.
+    #[derive(Debug, Clone)]
+    struct SingleDeploymentSchemas(MockSchemas, Deployment);
+
+    impl DeploymentResolver for SingleDeploymentSchemas {
+        fn resolve_latest_deployment_for_service(&self, _: impl AsRef<str>) -> Option<Deployment> {
+            Some(self.1.clone())
+        }
+
+        fn find_deployment(
+            &self,
+            _: &DeploymentAddress,
+            _: &Headers,
+        ) -> Option<(Deployment, Vec<ServiceMetadata>)> {
+            None
+        }
+
+        fn get_deployment(&self, deployment_id: &DeploymentId) -> Option<Deployment> {
+            (self.1.id == *deployment_id).then(|| self.1.clone())
+        }
+
+        fn get_deployment_and_services(
+            &self,
+            _: &DeploymentId,
+        ) -> Option<(Deployment, Vec<ServiceMetadata>)> {
+            None
+        }
+
+        fn get_deployments(&self) -> Vec<(Deployment, Vec<(String, ServiceRevision)>)> {
+            vec![]
+        }
+    }
+
+    impl InvocationTargetResolver for SingleDeploymentSchemas {
+        fn resolve_latest_invocation_target(
+            &self,
+            service_name: impl AsRef<str>,
+            handler_name: impl AsRef<str>,
+        ) -> Option<InvocationTargetMetadata> {
+            self.0
+                .resolve_latest_invocation_target(service_name, handler_name)
+        }
+
+        fn resolve_invocation_attempt_options(
+            &self,
+            deployment_id: &DeploymentId,
+            service_name: impl AsRef<str>,
+            handler_name: impl AsRef<str>,
+        ) -> Option<InvocationAttemptOptions> {
+            self.0
+                .resolve_invocation_attempt_options(deployment_id, service_name, handler_name)
+        }
+
+        fn resolve_latest_service_type(
+            &self,
+            service_name: impl AsRef<str>,
+        ) -> Option<ServiceType> {
+            self.0.resolve_latest_service_type(service_name)
+        }
+
+        fn resolve_invocation_retry_policy(
+            &self,
+            deployment_id: Option<&DeploymentId>,
+            service_name: impl AsRef<str>,
+            handler_name: impl AsRef<str>,
+        ) -> (RetryIter<'static>, OnMaxAttempts) {
+            self.0
+                .resolve_invocation_retry_policy(deployment_id, service_name, handler_name)
+        }
+    }
+
     #[test(restate_core::test)]
     async fn input_order_is_maintained() {
         let invoker_options = InvokerOptionsBuilder::default()
@@ -2288,6 +2821,8 @@ mod tests {
             None,
             None,
             MemoryPool::unlimited(),
+            DeploymentCircuitBreakers::default(),
+            CanaryOutcomes::default(),
         );
 
         let mut handle = service.handle();
@@ -2457,13 +2992,15 @@ mod tests {
 
         // Invoke the service
         let budget = service_inner.test_budget();
-        service_inner.handle_invoke(
-            &invoker_options,
-            invocation_id,
-            0,
-            InvocationTarget::mock_virtual_object(),
-            budget,
-        );
+        service_inner
+            .handle_invoke(
+                &invoker_options,
+                invocation_id,
+                0,
+                InvocationTarget::mock_virtual_object(),
+                budget,
+            )
+            .await;
 
         // We should receive the new entry here
         let invoker_effect = service_inner.invocation_tasks_rx.recv().await.unwrap();
@@ -2545,7 +3082,11 @@ mod tests {
             ConcurrencySlot::empty(),
         );
         let (tx, _rx) = mpsc::unbounded_channel();
-        ism.start(tokio::spawn(async {}).abort_handle(), tx);
+        ism.start(
+            tokio::spawn(async {}).abort_handle(),
+            tx,
+            DeploymentSlot::empty(),
+        );
 
         // Add a notification proposal
         ism.notify_new_notification_proposal(
@@ -2592,13 +3133,15 @@ mod tests {
 
         // Start an invocation with epoch 0
         let budget = service_inner.test_budget();
-        service_inner.handle_invoke(
-            &InvokerOptions::default(),
-            invocation_id,
-            0,
-            InvocationTarget::mock_virtual_object(),
-            budget,
-        );
+        service_inner
+            .handle_invoke(
+                &InvokerOptions::default(),
+                invocation_id,
+                0,
+                InvocationTarget::mock_virtual_object(),
+                budget,
+            )
+            .await;
 
         // Simulate a transient failure to populate last_retry_attempt_failure
         service_inner
@@ -2677,13 +3220,15 @@ mod tests {
 
         // Start invocation epoch 0
         let budget = service_inner.test_budget();
-        service_inner.handle_invoke(
-            &invoker_options,
-            invocation_id,
-            0,
-            InvocationTarget::mock_virtual_object(),
-            budget,
-        );
+        service_inner
+            .handle_invoke(
+                &invoker_options,
+                invocation_id,
+                0,
+                InvocationTarget::mock_virtual_object(),
+                budget,
+            )
+            .await;
 
         // Select protocol V4 to allow proposing events
         service_inner.handle_pinned_deployment(
@@ -2789,13 +3334,15 @@ mod tests {
 
         // Start invocation epoch 0
         let budget = service_inner.test_budget();
-        service_inner.handle_invoke(
-            &invoker_options,
-            invocation_id,
-            0,
-            InvocationTarget::mock_virtual_object(),
-            budget,
-        );
+        service_inner
+            .handle_invoke(
+                &invoker_options,
+                invocation_id,
+                0,
+                InvocationTarget::mock_virtual_object(),
+                budget,
+            )
+            .await;
 
         // Select protocol V4 to allow proposing events
         service_inner.handle_pinned_deployment(
@@ -2853,13 +3400,15 @@ mod tests {
 
         // Start invocation epoch 0
         let budget = service_inner.test_budget();
-        service_inner.handle_invoke(
-            &invoker_options,
-            invocation_id,
-            FencingToken::default(),
-            InvocationTarget::mock_virtual_object(),
-            budget,
-        );
+        service_inner
+            .handle_invoke(
+                &invoker_options,
+                invocation_id,
+                FencingToken::default(),
+                InvocationTarget::mock_virtual_object(),
+                budget,
+            )
+            .await;
 
         // Select protocol V4 to allow proposing events
         service_inner.handle_pinned_deployment(
@@ -2901,13 +3450,15 @@ mod tests {
 
         // Start invocation epoch 0
         let budget = service_inner.test_budget();
-        service_inner.handle_invoke(
-            &InvokerOptions::default(),
-            invocation_id,
-            0,
-            InvocationTarget::mock_virtual_object(),
-            budget,
-        );
+        service_inner
+            .handle_invoke(
+                &InvokerOptions::default(),
+                invocation_id,
+                0,
+                InvocationTarget::mock_virtual_object(),
+                budget,
+            )
+            .await;
 
         // Abort error
         service_inner
@@ -2954,13 +3505,15 @@ mod tests {
 
         // Start invocation
         let budget = service_inner.test_budget();
-        service_inner.handle_invoke(
-            &invoker_options,
-            invocation_id,
-            0,
-            InvocationTarget::mock_virtual_object(),
-            budget,
-        );
+        service_inner
+            .handle_invoke(
+                &invoker_options,
+                invocation_id,
+                0,
+                InvocationTarget::mock_virtual_object(),
+                budget,
+            )
+            .await;
 
         // First transient error -> schedules retry (because 1 attempt available)
         let error_a = InvokerError::SdkV2(SdkInvocationErrorV2::unknown());
@@ -3000,6 +3553,33 @@ mod tests {
         // Create custom resolver that switches behavior based on presence of deployment id
         #[derive(Clone)]
         struct SwitchingResolver;
+        impl DeploymentResolver for SwitchingResolver {
+            fn resolve_latest_deployment_for_service(
+                &self,
+                _: impl AsRef<str>,
+            ) -> Option<Deployment> {
+                None
+            }
+            fn find_deployment(
+                &self,
+                _: &DeploymentAddress,
+                _: &Headers,
+            ) -> Option<(Deployment, Vec<ServiceMetadata>)> {
+                None
+            }
+            fn get_deployment(&self, _: &DeploymentId) -> Option<Deployment> {
+                None
+            }
+            fn get_deployment_and_services(
+                &self,
+                _: &DeploymentId,
+            ) -> Option<(Deployment, Vec<ServiceMetadata>)> {
+                None
+            }
+            fn get_deployments(&self) -> Vec<(Deployment, Vec<(String, ServiceRevision)>)> {
+                vec![]
+            }
+        }
         impl InvocationTargetResolver for SwitchingResolver {
             fn resolve_latest_invocation_target(
                 &self,
@@ -3050,13 +3630,15 @@ mod tests {
 
         // Start invocation
         let budget = service_inner.test_budget();
-        service_inner.handle_invoke(
-            &invoker_options,
-            invocation_id,
-            0,
-            InvocationTarget::mock_virtual_object(),
-            budget,
-        );
+        service_inner
+            .handle_invoke(
+                &invoker_options,
+                invocation_id,
+                0,
+                InvocationTarget::mock_virtual_object(),
+                budget,
+            )
+            .await;
 
         // Pin deployment (switches policy to Kill and resets attempts)
         let dp = PinnedDeployment::new(DeploymentId::new(), ServiceProtocolVersion::V4);
@@ -3121,13 +3703,15 @@ mod tests {
 
         // Start invocation
         let budget = service_inner.test_budget();
-        service_inner.handle_invoke(
-            &invoker_options,
-            invocation_id,
-            0,
-            InvocationTarget::mock_virtual_object(),
-            budget,
-        );
+        service_inner
+            .handle_invoke(
+                &invoker_options,
+                invocation_id,
+                0,
+                InvocationTarget::mock_virtual_object(),
+                budget,
+            )
+            .await;
 
         // Simulate a transient error to put invocation in WaitingRetry state
         let error = InvokerError::SdkV2(SdkInvocationErrorV2::unknown());
@@ -3185,13 +3769,15 @@ mod tests {
 
         // Start invocation (goes to InFlight state with pending task)
         let budget = service_inner.test_budget();
-        service_inner.handle_invoke(
-            &invoker_options,
-            invocation_id,
-            0,
-            InvocationTarget::mock_virtual_object(),
-            budget,
-        );
+        service_inner
+            .handle_invoke(
+                &invoker_options,
+                invocation_id,
+                0,
+                InvocationTarget::mock_virtual_object(),
+                budget,
+            )
+            .await;
 
         // Call manual pause while in flight
         service_inner.handle_pause_invocation(invocation_id).await;
@@ -3246,13 +3832,15 @@ mod tests {
 
         // Start invocation (goes to InFlight state with pending task)
         let budget = service_inner.test_budget();
-        service_inner.handle_invoke(
-            &invoker_options,
-            invocation_id,
-            0,
-            InvocationTarget::mock_virtual_object(),
-            budget,
-        );
+        service_inner
+            .handle_invoke(
+                &invoker_options,
+                invocation_id,
+                0,
+                InvocationTarget::mock_virtual_object(),
+                budget,
+            )
+            .await;
 
         // Call manual pause while in flight
         service_inner.handle_pause_invocation(invocation_id).await;
@@ -3309,13 +3897,15 @@ mod tests {
 
         // Start invocation
         let budget = service_inner.test_budget();
-        service_inner.handle_invoke(
-            &invoker_options,
-            invocation_id,
-            0,
-            InvocationTarget::mock_virtual_object(),
-            budget,
-        );
+        service_inner
+            .handle_invoke(
+                &invoker_options,
+                invocation_id,
+                0,
+                InvocationTarget::mock_virtual_object(),
+                budget,
+            )
+            .await;
 
         // Simulate a transient error to put invocation in WaitingRetry state
         let error = InvokerError::SdkV2(SdkInvocationErrorV2::unknown());
@@ -3375,13 +3965,15 @@ mod tests {
         );
 
         let budget = service_inner.test_budget();
-        service_inner.handle_invoke(
-            &invoker_options,
-            invocation_id,
-            0,
-            InvocationTarget::mock_virtual_object(),
-            budget,
-        );
+        service_inner
+            .handle_invoke(
+                &invoker_options,
+                invocation_id,
+                0,
+                InvocationTarget::mock_virtual_object(),
+                budget,
+            )
+            .await;
 
         // Simulate yield from invocation task (flag disabled by default)
         service_inner
@@ -3445,13 +4037,15 @@ mod tests {
         );
 
         let budget = service_inner.test_budget();
-        service_inner.handle_invoke(
-            &invoker_options,
-            invocation_id,
-            0,
-            InvocationTarget::mock_virtual_object(),
-            budget,
-        );
+        service_inner
+            .handle_invoke(
+                &invoker_options,
+                invocation_id,
+                0,
+                InvocationTarget::mock_virtual_object(),
+                budget,
+            )
+            .await;
 
         // Simulate yield from invocation task
         service_inner
@@ -3485,4 +4079,210 @@ mod tests {
         // The invocation should no longer be tracked (slot released)
         assert!(service_inner.quota.is_slot_available());
     }
+
+    #[test(restate_core::test(start_paused = true))]
+    async fn circuit_breaker_holds_attempts_against_unavailable_deployment() {
+        use restate_worker_api::invoker::circuit_breaker::CircuitState;
+
+        let invoker_options = InvokerOptionsBuilder::default()
+            .inactivity_timeout(FriendlyDuration::ZERO)
+            .abort_timeout(FriendlyDuration::ZERO)
+            .build()
+            .unwrap();
+        let failure_threshold = invoker_options
+            .deployment_circuit_breaker
+            .failure_threshold
+            .get() as usize;
+
+        let invocation_id = InvocationId::mock_random();
+        let deployment_id = DeploymentId::new();
+        let started_tasks = Arc::new(AtomicUsize::new(0));
+        let (_, _status_tx, _effects_rx, mut service_inner) = ServiceInner::mock(
+            Arc::clone(&started_tasks),
+            MockSchemas(
+                Some(RetryPolicy::fixed_delay(Duration::from_millis(100), None)),
+                Some(OnMaxAttempts::Kill),
+            ),
+            None,
+            EmptyStorageReader,
+        );
+
+        let budget = service_inner.test_budget();
+        service_inner
+            .handle_invoke(
+                &invoker_options,
+                invocation_id,
+                0,
+                InvocationTarget::mock_virtual_object(),
+                budget,
+            )
+            .await;
+
+        // Every attempt fails with a gateway error
+        for attempt in 1..=failure_threshold {
+            assert_eq!(started_tasks.load(Ordering::SeqCst), attempt);
+            service_inner.handle_pinned_deployment(
+                invocation_id,
+                PinnedDeployment::new(deployment_id, ServiceProtocolVersion::V4),
+                attempt == 1,
+            );
+            service_inner
+                .handle_invocation_task_failed(
+                    invocation_id,
+                    InvokerError::ServiceUnavailable(http::StatusCode::BAD_GATEWAY),
+                    service_inner.test_budget(),
+                )
+                .await;
+
+            let expired = service_inner.retry_timers.next().await.unwrap();
+            let timer_key = expired.key();
+            service_inner.handle_retry_timer_fired(
+                &invoker_options,
+                expired.into_inner(),
+                timer_key,
+            );
+        }
+
+        // The circuit breaker opened, the retry is held instead of started
+        assert_eq!(started_tasks.load(Ordering::SeqCst), failure_threshold);
+        assert_eq!(
+            service_inner.circuit_breakers.state(&deployment_id),
+            CircuitState::Open
+        );
+        assert!(service_inner.is_invocation_waiting_retry(&invocation_id));
+
+        // Once the open duration elapsed, the held attempt starts as probe
+        let expired = service_inner.retry_timers.next().await.unwrap();
+        let timer_key = expired.key();
+        service_inner.handle_retry_timer_fired(&invoker_options, expired.into_inner(), timer_key);
+        assert_eq!(started_tasks.load(Ordering::SeqCst), failure_threshold + 1);
+        assert_eq!(
+            service_inner.circuit_breakers.state(&deployment_id),
+            CircuitState::HalfOpen
+        );
+
+        // The deployment answers the probe, which closes the circuit breaker
+        service_inner.handle_pinned_deployment(
+            invocation_id,
+            PinnedDeployment::new(deployment_id, ServiceProtocolVersion::V4),
+            false,
+        );
+        service_inner.handle_deployment_responded(&invocation_id);
+        assert_eq!(
+            service_inner.circuit_breakers.state(&deployment_id),
+            CircuitState::Closed
+        );
+    }
+
+    #[test(restate_core::test(start_paused = true))]
+    async fn deployment_concurrency_limit_holds_attempts() {
+        let invoker_options = InvokerOptionsBuilder::default()
+            .inactivity_timeout(FriendlyDuration::ZERO)
+            .abort_timeout(FriendlyDuration::ZERO)
+            .build()
+            .unwrap();
+
+        let mut deployment = Deployment::mock();
+        deployment.limits.concurrency = NonZeroU32::new(1);
+        let deployment_id = deployment.id;
+
+        let invocation_id_1 = InvocationId::mock_random();
+        let invocation_id_2 = InvocationId::mock_random();
+        let started_tasks = Arc::new(AtomicUsize::new(0));
+        let (_, _status_tx, _effects_rx, mut service_inner) = ServiceInner::mock(
+            Arc::clone(&started_tasks),
+            SingleDeploymentSchemas(MockSchemas::default(), deployment),
+            None,
+            EmptyStorageReader,
+        );
+
+        let budget = service_inner.test_budget();
+        service_inner
+            .handle_invoke(
+                &invoker_options,
+                invocation_id_1,
+                0,
+                InvocationTarget::mock_virtual_object(),
+                budget,
+            )
+            .await;
+        assert_eq!(started_tasks.load(Ordering::SeqCst), 1);
+        assert_eq!(service_inner.deployment_quotas.in_flight(&deployment_id), 1);
+
+        // The deployment reached its concurrency limit, the second invocation is held
+        let budget = service_inner.test_budget();
+        service_inner
+            .handle_invoke(
+                &invoker_options,
+                invocation_id_2,
+                0,
+                InvocationTarget::mock_virtual_object(),
+                budget,
+            )
+            .await;
+        assert_eq!(started_tasks.load(Ordering::SeqCst), 1);
+        assert!(service_inner.is_invocation_waiting_retry(&invocation_id_2));
+
+        // Once the first invocation ends, the held one starts right away
+        service_inner
+            .handle_invocation_task_closed(invocation_id_1)
+            .await;
+        let released = service_inner.deployment_quotas.next_released().await;
+        assert_eq!(released, deployment_id);
+        service_inner.handle_deployment_slot_released(&invoker_options, released);
+        assert_eq!(started_tasks.load(Ordering::SeqCst), 2);
+        assert!(!service_inner.is_invocation_waiting_retry(&invocation_id_2));
+        assert_eq!(service_inner.deployment_quotas.in_flight(&deployment_id), 1);
+    }
+
+    #[test(restate_core::test)]
+    async fn pinned_deployment_checks_limits_before_acquiring() {
+        let invoker_options = InvokerOptionsBuilder::default()
+            .inactivity_timeout(FriendlyDuration::ZERO)
+            .abort_timeout(FriendlyDuration::ZERO)
+            .build()
+            .unwrap();
+
+        let mut deployment = Deployment::mock();
+        deployment.limits.concurrency = NonZeroU32::new(1);
+        let deployment_id = deployment.id;
+
+        let invocation_id = InvocationId::mock_random();
+        let started_tasks = Arc::new(AtomicUsize::new(0));
+        let (_, _status_tx, _effects_rx, mut service_inner) = ServiceInner::mock(
+            Arc::clone(&started_tasks),
+            SingleDeploymentSchemas(MockSchemas::default(), deployment),
+            None,
+            EmptyStorageReader,
+        );
+
+        let budget = service_inner.test_budget();
+        service_inner
+            .handle_invoke(
+                &invoker_options,
+                invocation_id,
+                0,
+                InvocationTarget::mock_virtual_object(),
+                budget,
+            )
+            .await;
+        assert_eq!(started_tasks.load(Ordering::SeqCst), 1);
+
+        // Simulate an attempt that was admitted against another deployment, while the
+        // deployment it gets pinned to has no capacity left
+        service_inner
+            .invocation_state_machine_manager
+            .handle_for_invocation(&invocation_id, |_, ism| {
+                ism.replace_deployment_slot(DeploymentSlot::empty())
+            });
+        assert_eq!(service_inner.deployment_quotas.in_flight(&deployment_id), 1);
+
+        service_inner.handle_pinned_deployment(
+            invocation_id,
+            PinnedDeployment::new(deployment_id, ServiceProtocolVersion::V4),
+            true,
+        );
+        assert!(service_inner.is_invocation_waiting_retry(&invocation_id));
+        assert_eq!(service_inner.deployment_quotas.in_flight(&deployment_id), 1);
+    }
 }
@@ -22,6 +22,14 @@ pub const INVOKER_SENT_BYTES: &str = "restate.invoker.sent.bytes.total";
 pub const INVOKER_RECEIVED_BYTES: &str = "restate.invoker.received.bytes.total";
 pub const INVOKER_CLIENT_REQUESTS: &str = "restate.invoker.client_requests.total";
 
+pub const INVOKER_CIRCUIT_BREAKER_OPEN: &str = "restate.invoker.deployment_circuit_breaker.open";
+pub const INVOKER_CIRCUIT_BREAKER_TRIPS: &str =
+    "restate.invoker.deployment_circuit_breaker.trips.total";
+pub const INVOKER_CIRCUIT_BREAKER_HELD_ATTEMPTS: &str =
+    "restate.invoker.deployment_circuit_breaker.held_attempts.total";
+pub const INVOKER_DEPLOYMENT_LIMITS_HELD_ATTEMPTS: &str =
+    "restate.invoker.deployment_limits.held_attempts.total";
+
 pub const TASK_OP_STARTED: &str = "started";
 pub const TASK_OP_SUSPENDED: &str = "suspended";
 pub const TASK_OP_FAILED: &str = "failed";
@@ -87,4 +95,28 @@ pub(crate) fn describe_metrics() {
         Unit::Count,
         "Requests sent to deployments and their status codes"
     );
+
+    describe_gauge!(
+        INVOKER_CIRCUIT_BREAKER_OPEN,
+        Unit::Count,
+        "Number of deployments whose circuit breaker is open or half-open"
+    );
+
+    describe_counter!(
+        INVOKER_CIRCUIT_BREAKER_TRIPS,
+        Unit::Count,
+        "Number of times the circuit breaker of any deployment opened"
+    );
+
+    describe_counter!(
+        INVOKER_CIRCUIT_BREAKER_HELD_ATTEMPTS,
+        Unit::Count,
+        "Number of invocation attempts held because the circuit breaker of their deployment is open"
+    );
+
+    describe_counter!(
+        INVOKER_DEPLOYMENT_LIMITS_HELD_ATTEMPTS,
+        Unit::Count,
+        "Number of invocation attempts held because their deployment reached its concurrency or rate limit"
+    );
 }
@@ -9,16 +9,23 @@
 // by the Apache License, Version 2.0.
 
 use std::{
-    num::NonZeroUsize,
+    collections::{HashMap, VecDeque},
+    num::{NonZeroU32, NonZeroUsize},
     sync::{
         Arc,
         atomic::{AtomicUsize, Ordering},
     },
+    time::Duration,
 };
 
 use metrics::{Counter, counter, gauge};
+use tokio::sync::mpsc;
 
 use restate_types::config::Configuration;
+use restate_types::identifiers::{DeploymentId, InvocationId};
+use restate_types::rate::Rate;
+use restate_types::schema::deployment::DeploymentLimits;
+use restate_worker_api::invoker::capacity::TokenBucket;
 
 use crate::{
     InvokerId,
@@ -174,3 +181,324 @@ impl Drop for ConcurrencySlot {
         }
     }
 }
+
+/// Why an attempt against a deployment can't start yet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum DeploymentQuotaExceeded {
+    /// The deployment reached its concurrency limit. The attempt can start once another attempt
+    /// against the deployment ends.
+    Concurrency,
+    /// The deployment reached its rate limit. The attempt can start after the given duration.
+    Rate(Duration),
+}
+
+/// Tracks the attempts this invoker runs against each deployment, and enforces the
+/// [`DeploymentLimits`] of the deployments on them.
+///
+/// Attempts hold a [`DeploymentSlot`] while running. Dropping the slot notifies the invoker
+/// through [`Self::next_released`], so invocations waiting for the deployment can be woken up.
+#[derive(Debug)]
+pub(super) struct DeploymentQuotas<K> {
+    deployments: HashMap<DeploymentId, DeploymentQuota<K>>,
+    released_tx: mpsc::UnboundedSender<DeploymentId>,
+    released_rx: mpsc::UnboundedReceiver<DeploymentId>,
+}
+
+#[derive(Debug)]
+struct DeploymentQuota<K> {
+    in_flight: u32,
+    rate_bucket: Option<RateBucket>,
+    /// Invocations held because of the concurrency limit, with the key of their hold timer.
+    waiters: VecDeque<(InvocationId, K)>,
+}
+
+impl<K> Default for DeploymentQuota<K> {
+    fn default() -> Self {
+        Self {
+            in_flight: 0,
+            rate_bucket: None,
+            waiters: VecDeque::new(),
+        }
+    }
+}
+
+impl<K> DeploymentQuota<K> {
+    fn is_idle(&self) -> bool {
+        self.in_flight == 0 && self.rate_bucket.is_none() && self.waiters.is_empty()
+    }
+}
+
+impl<K> Default for DeploymentQuotas<K> {
+    fn default() -> Self {
+        let (released_tx, released_rx) = mpsc::unbounded_channel();
+        Self {
+            deployments: HashMap::new(),
+            released_tx,
+            released_rx,
+        }
+    }
+}
+
+impl<K> DeploymentQuotas<K> {
+    /// Checks whether an attempt against the deployment can start under the given limits. This
+    /// doesn't take any quota, use [`Self::acquire`] once the attempt is going to start.
+    pub(super) fn check(
+        &mut self,
+        deployment_id: DeploymentId,
+        limits: &DeploymentLimits,
+    ) -> Result<(), DeploymentQuotaExceeded> {
+        if limits.is_unlimited() && !self.deployments.contains_key(&deployment_id) {
+            return Ok(());
+        }
+        let quota = self.deployments.entry(deployment_id).or_default();
+
+        if limits
+            .concurrency
+            .is_some_and(|concurrency| quota.in_flight >= concurrency.get())
+        {
+            return Err(DeploymentQuotaExceeded::Concurrency);
+        }
+
+        // Limits can change at any time, rebuild the bucket when they do
+        let Some(rate_bucket) = RateBucket::sync(&mut quota.rate_bucket, limits) else {
+            return Ok(());
+        };
+        rate_bucket
+            .wait_time()
+            .map_or(Ok(()), |wait| Err(DeploymentQuotaExceeded::Rate(wait)))
+    }
+
+    /// Takes the quota of an attempt against the deployment. Callers should
+    /// [`check`](Self::check) first, this never fails.
+    pub(super) fn acquire(&mut self, deployment_id: DeploymentId) -> DeploymentSlot {
+        let quota = self.deployments.entry(deployment_id).or_default();
+        quota.in_flight += 1;
+        if let Some(rate_bucket) = &quota.rate_bucket {
+            rate_bucket.consume();
+        }
+        DeploymentSlot {
+            inner: Some((deployment_id, self.released_tx.clone())),
+        }
+    }
+
+    /// Registers an invocation held because of the concurrency limit of the deployment.
+    pub(super) fn add_waiter(
+        &mut self,
+        deployment_id: DeploymentId,
+        invocation_id: InvocationId,
+        hold_timer_key: K,
+    ) {
+        self.deployments
+            .entry(deployment_id)
+            .or_default()
+            .waiters
+            .push_back((invocation_id, hold_timer_key));
+    }
+
+    /// Waits for the next released [`DeploymentSlot`].
+    pub(super) async fn next_released(&mut self) -> DeploymentId {
+        self.released_rx
+            .recv()
+            .await
+            .expect("sender is owned by DeploymentQuotas")
+    }
+
+    /// Accounts for a released [`DeploymentSlot`], and returns the invocations waiting for the
+    /// deployment, oldest first. Waiters that are not woken up must be registered again.
+    pub(super) fn release(&mut self, deployment_id: DeploymentId) -> VecDeque<(InvocationId, K)> {
+        let Some(quota) = self.deployments.get_mut(&deployment_id) else {
+            return VecDeque::new();
+        };
+        quota.in_flight = quota.in_flight.saturating_sub(1);
+        let waiters = std::mem::take(&mut quota.waiters);
+        if quota.in_flight == 0 && quota.rate_bucket.as_ref().is_none_or(RateBucket::is_full) {
+            quota.rate_bucket = None;
+        }
+        if quota.is_idle() {
+            self.deployments.remove(&deployment_id);
+        }
+        waiters
+    }
+
+    /// Puts back the waiters not woken up by [`Self::release`], ahead of the ones registered since.
+    pub(super) fn restore_waiters(
+        &mut self,
+        deployment_id: DeploymentId,
+        waiters: VecDeque<(InvocationId, K)>,
+    ) {
+        if waiters.is_empty() {
+            return;
+        }
+        let quota = self.deployments.entry(deployment_id).or_default();
+        let newer = std::mem::replace(&mut quota.waiters, waiters);
+        quota.waiters.extend(newer);
+    }
+
+    #[cfg(test)]
+    pub(super) fn in_flight(&self, deployment_id: &DeploymentId) -> u32 {
+        self.deployments
+            .get(deployment_id)
+            .map_or(0, |quota| quota.in_flight)
+    }
+}
+
+/// An attempt running against a deployment, see [`DeploymentQuotas`].
+#[derive(derive_more::Debug)]
+#[debug("DeploymentSlot({:?})", inner.as_ref().map(|(deployment_id, _)| deployment_id))]
+pub struct DeploymentSlot {
+    inner: Option<(DeploymentId, mpsc::UnboundedSender<DeploymentId>)>,
+}
+
+impl DeploymentSlot {
+    /// A slot for an attempt whose deployment is not known yet.
+    pub fn empty() -> Self {
+        Self { inner: None }
+    }
+
+    pub fn deployment_id(&self) -> Option<DeploymentId> {
+        self.inner.as_ref().map(|(deployment_id, _)| *deployment_id)
+    }
+}
+
+impl Drop for DeploymentSlot {
+    fn drop(&mut self) {
+        if let Some((deployment_id, released_tx)) = self.inner.take() {
+            // The receiver is gone only when the invoker shuts down
+            let _ = released_tx.send(deployment_id);
+        }
+    }
+}
+
+/// The token bucket backing the rate limit of a deployment, with the limits it was built for.
+#[derive(Debug)]
+struct RateBucket {
+    rate: Rate,
+    burst: NonZeroU32,
+    bucket: TokenBucket,
+}
+
+impl RateBucket {
+    /// Makes `rate_bucket` match the rate limit of `limits`, and returns it. Returns `None` if
+    /// the rate is unlimited.
+    fn sync<'a>(
+        rate_bucket: &'a mut Option<RateBucket>,
+        limits: &DeploymentLimits,
+    ) -> Option<&'a RateBucket> {
+        let (Some(rate), Some(burst), Some(limit)) =
+            (limits.rate, limits.effective_burst(), limits.rate_limit())
+        else {
+            *rate_bucket = None;
+            return None;
+        };
+        if rate_bucket
+            .as_ref()
+            .is_none_or(|rate_bucket| rate_bucket.rate != rate || rate_bucket.burst != burst)
+        {
+            *rate_bucket = Some(RateBucket {
+                rate,
+                burst,
+                bucket: TokenBucket::new(limit, gardal::TokioClock),
+            });
+        }
+        rate_bucket.as_ref()
+    }
+
+    /// Returns how long to wait until a token is available, or `None` if one is available now.
+    fn wait_time(&self) -> Option<Duration> {
+        let missing = 1.0 - self.bucket.balance();
+        (missing > 0.0).then(|| {
+            self.rate
+                .period()
+                .mul_f64(missing / f64::from(self.rate.get().get()))
+        })
+    }
+
+    fn consume(&self) {
+        self.bucket
+            .consume_with_borrow(NonZeroU32::MIN)
+            .expect("consuming one token with borrow must fit burst");
+    }
+
+    /// Whether the bucket is full, in which case it can be rebuilt from scratch.
+    fn is_full(&self) -> bool {
+        self.bucket.balance() >= f64::from(self.burst.get())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use restate_test_util::{assert, let_assert};
+
+    fn limits(
+        concurrency: Option<u32>,
+        rate: Option<&str>,
+        burst: Option<u32>,
+    ) -> DeploymentLimits {
+        DeploymentLimits {
+            concurrency: concurrency.and_then(NonZeroU32::new),
+            rate: rate.map(|rate| rate.parse().unwrap()),
+            burst: burst.and_then(NonZeroU32::new),
+        }
+    }
+
+    #[test]
+    fn concurrency_limit() {
+        let mut quotas = DeploymentQuotas::<u64>::default();
+        let deployment_id = DeploymentId::new();
+        let limits = limits(Some(1), None, None);
+
+        assert_eq!(quotas.check(deployment_id, &limits), Ok(()));
+        let slot = quotas.acquire(deployment_id);
+        assert_eq!(
+            quotas.check(deployment_id, &limits),
+            Err(DeploymentQuotaExceeded::Concurrency)
+        );
+
+        let invocation_id = InvocationId::mock_random();
+        quotas.add_waiter(deployment_id, invocation_id, 1);
+
+        drop(slot);
+        let released = quotas.released_rx.try_recv().unwrap();
+        assert_eq!(released, deployment_id);
+        assert_eq!(
+            quotas.release(released),
+            VecDeque::from([(invocation_id, 1)])
+        );
+        assert_eq!(quotas.in_flight(&deployment_id), 0);
+        assert_eq!(quotas.check(deployment_id, &limits), Ok(()));
+    }
+
+    #[restate_core::test(start_paused = true)]
+    async fn rate_limit() {
+        let mut quotas = DeploymentQuotas::<u64>::default();
+        let deployment_id = DeploymentId::new();
+        let limits = limits(None, Some("10/s"), Some(2));
+
+        // The burst is available right away
+        for _ in 0..2 {
+            assert_eq!(quotas.check(deployment_id, &limits), Ok(()));
+            let _slot = quotas.acquire(deployment_id);
+        }
+        let_assert!(
+            Err(DeploymentQuotaExceeded::Rate(wait)) = quotas.check(deployment_id, &limits)
+        );
+        assert!(wait > Duration::from_millis(99) && wait <= Duration::from_millis(100));
+
+        // Then one token every 100 milliseconds
+        tokio::time::advance(Duration::from_millis(100)).await;
+        assert_eq!(quotas.check(deployment_id, &limits), Ok(()));
+        let _slot = quotas.acquire(deployment_id);
+        assert!(matches!(
+            quotas.check(deployment_id, &limits),
+            Err(DeploymentQuotaExceeded::Rate(_))
+        ));
+
+        // Removing the limits lets attempts through right away
+        assert_eq!(
+            quotas.check(deployment_id, &DeploymentLimits::default()),
+            Ok(())
+        );
+    }
+}
@@ -83,6 +83,12 @@ impl InvocationStatusStore {
         self.0.remove(invocation_id);
     }
 
+    pub(super) fn on_held(&mut self, invocation_id: InvocationId, next_retry_at: SystemTime) {
+        let report = self.0.entry(invocation_id).or_default();
+        report.in_flight = false;
+        report.next_retry_at = Some(next_retry_at);
+    }
+
     pub(super) fn on_failure(
         &mut self,
         invocation_id: InvocationId,
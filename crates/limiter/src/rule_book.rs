@@ -149,6 +149,9 @@ impl RuleBook {
 
             match change {
                 RuleChange::Upsert(upsert) => {
+                    if upsert.limits.burst.is_some() && upsert.limits.rate.is_none() {
+                        return Err(RuleBookError::BurstWithoutRate { pattern });
+                    }
                     if !check_precondition(upsert.precondition, actual_version) {
                         return Err(RuleBookError::PreconditionFailed {
                             pattern,
@@ -414,6 +417,9 @@ pub enum RuleBookError {
         precondition: Precondition,
         actual: Option<Version>,
     },
+    /// The rule sets a burst without a rate to apply it to.
+    #[error("rule for pattern {pattern} sets a burst without a rate")]
+    BurstWithoutRate { pattern: RulePattern<ReString> },
 }
 
 impl Default for RuleBook {
@@ -473,6 +479,8 @@ mod tests {
     use bilrost::{Message, OwnedMessage};
     use std::num::NonZeroU32;
 
+    use restate_types::rate::Rate;
+
     use super::*;
 
     fn pat(s: &str) -> RulePattern<ReString> {
@@ -481,9 +489,7 @@ mod tests {
 
     fn upsert(concurrency: u32) -> RuleUpsert {
         RuleUpsert {
-            limits: UserLimits {
-                concurrency: NonZeroU32::new(concurrency),
-            },
+            limits: UserLimits::new(NonZeroU32::new(concurrency)),
             description: None,
             disabled: false,
             precondition: Precondition::None,
@@ -514,9 +520,8 @@ mod tests {
         rules.insert(
             pat("*"),
             PersistedRule {
-                limits: UserLimits {
-                    concurrency: NonZeroU32::new(1000),
-                },
+                limits: UserLimits::new(NonZeroU32::new(1000))
+                    .with_rate(Some(Rate::Second(NonZeroU32::new(100).unwrap())), None),
                 description: Some("global default".to_owned()),
                 disabled: false,
                 last_modified: MillisSinceEpoch::new(42),
@@ -526,9 +531,8 @@ mod tests {
         rules.insert(
             pat("scope1/*/tenant1"),
             PersistedRule {
-                limits: UserLimits {
-                    concurrency: NonZeroU32::new(10),
-                },
+                limits: UserLimits::new(NonZeroU32::new(10))
+                    .with_rate(Some(Rate::Minute(NonZeroU32::MIN)), NonZeroU32::new(5)),
                 description: None,
                 disabled: true,
                 last_modified: MillisSinceEpoch::new(43),
@@ -572,6 +576,36 @@ mod tests {
         assert_eq!(book.version(), v_before_book.next());
     }
 
+    #[test]
+    fn upsert_rate_change_bumps_per_rule_version() {
+        let mut book = RuleBook::empty();
+        book.apply_change(pat("*"), RuleChange::Upsert(upsert(1000)))
+            .unwrap();
+        let v_before_rule = book.get(&pat("*")).unwrap().version;
+
+        let rate = Rate::Second(NonZeroU32::new(100).unwrap());
+        let mut change = upsert(1000);
+        change.limits = change.limits.with_rate(Some(rate), NonZeroU32::new(10));
+        book.apply_change(pat("*"), RuleChange::Upsert(change))
+            .unwrap();
+        let r = book.get(&pat("*")).unwrap();
+        assert_eq!(r.limits.rate, Some(rate));
+        assert_eq!(r.limits.burst, NonZeroU32::new(10));
+        assert_eq!(r.version, v_before_rule.next());
+    }
+
+    #[test]
+    fn upsert_rejects_burst_without_rate() {
+        let mut book = RuleBook::empty();
+        let mut change = upsert(1000);
+        change.limits.burst = NonZeroU32::new(10);
+        let err = book
+            .apply_change(pat("*"), RuleChange::Upsert(change))
+            .unwrap_err();
+        assert!(matches!(err, RuleBookError::BurstWithoutRate { .. }));
+        assert_eq!(book.version(), Version::INVALID);
+    }
+
     #[test]
     fn upsert_reason_only_change_bumps_book_but_not_rule_version() {
         let mut book = RuleBook::empty();
@@ -20,6 +20,7 @@
 
 use std::num::NonZeroU32;
 
+use restate_types::rate::Rate;
 use restate_util_string::ReString;
 
 use crate::RulePattern;
@@ -45,11 +46,46 @@ pub struct UserLimits {
     )]
     #[cfg_attr(feature = "schema", schema(value_type = Option<u32>, minimum = 1))]
     pub concurrency: Option<NonZeroU32>,
+    /// Rate at which invocations are allowed to start (e.g. `100/s`), enforced with a
+    /// token bucket. `None` means unlimited.
+    #[cfg_attr(feature = "bilrost", bilrost(tag(2)))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[cfg_attr(feature = "schema", schema(value_type = Option<String>, example = "100/s"))]
+    pub rate: Option<Rate>,
+    /// Maximum number of invocations that can start in a burst, i.e. the capacity of
+    /// the token bucket. Only meaningful together with `rate`; `None` defaults to the
+    /// number of invocations allowed per rate period.
+    #[cfg_attr(feature = "bilrost", bilrost(tag(3)))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[cfg_attr(feature = "schema", schema(value_type = Option<u32>, minimum = 1))]
+    pub burst: Option<NonZeroU32>,
 }
 
 impl UserLimits {
     pub fn new(concurrency: Option<NonZeroU32>) -> Self {
-        Self { concurrency }
+        Self {
+            concurrency,
+            rate: None,
+            burst: None,
+        }
+    }
+
+    pub fn with_rate(mut self, rate: Option<Rate>, burst: Option<NonZeroU32>) -> Self {
+        self.rate = rate;
+        self.burst = burst;
+        self
+    }
+
+    /// The capacity of the token bucket backing [`Self::rate`], or `None` if the
+    /// rate is unlimited.
+    pub fn effective_burst(&self) -> Option<NonZeroU32> {
+        self.rate.map(|rate| self.burst.unwrap_or(rate.get()))
     }
 }
 
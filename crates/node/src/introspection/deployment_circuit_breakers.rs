@@ -0,0 +1,98 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Local scanner implementation for the `deployment_circuit_breakers` DataFusion table.
+//!
+//! This scanner reads the circuit breakers of the deployments tracked by the
+//! invoker of the local worker and produces Arrow record batches for fan-out
+//! SQL queries.
+
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use datafusion::arrow::datatypes::SchemaRef;
+use datafusion::execution::SendableRecordBatchStream;
+use datafusion::logical_expr::Expr;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use futures::stream;
+
+use restate_core::Metadata;
+use restate_storage_query_datafusion::Scan;
+use restate_storage_query_datafusion::deployment_circuit_breakers::DeploymentCircuitBreakersBuilder;
+use restate_storage_query_datafusion::table_util::Builder;
+use restate_types::GenerationalNodeId;
+use restate_types::identifiers::DeploymentId;
+use restate_worker::{CircuitState, DeploymentCircuitBreakers};
+
+/// Creates a local scanner for `deployment_circuit_breakers` from the circuit
+/// breakers of the worker.
+pub(crate) fn create_local_scanner(
+    circuit_breakers: DeploymentCircuitBreakers,
+    metadata: Metadata,
+) -> Arc<dyn Scan> {
+    Arc::new(DeploymentCircuitBreakersScanner {
+        circuit_breakers,
+        metadata,
+    })
+}
+
+struct DeploymentCircuitBreakersScanner {
+    circuit_breakers: DeploymentCircuitBreakers,
+    metadata: Metadata,
+}
+
+impl Debug for DeploymentCircuitBreakersScanner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("DeploymentCircuitBreakersScanner")
+    }
+}
+
+impl Scan for DeploymentCircuitBreakersScanner {
+    fn scan(
+        &self,
+        projection: SchemaRef,
+        _filters: &[Expr],
+        _batch_size: usize,
+        limit: Option<usize>,
+    ) -> SendableRecordBatchStream {
+        let snapshot = self.circuit_breakers.snapshot();
+        let my_node_id = self.metadata.my_node_id();
+        let schema = projection.clone();
+
+        let fut = async move {
+            let mut builder = DeploymentCircuitBreakersBuilder::new(schema.clone());
+
+            for (count, (deployment_id, state)) in snapshot.into_iter().enumerate() {
+                if limit.is_some_and(|l| count >= l) {
+                    break;
+                }
+                append_row(&mut builder, my_node_id, deployment_id, state);
+            }
+
+            builder.finish()
+        };
+
+        Box::pin(RecordBatchStreamAdapter::new(projection, stream::once(fut)))
+    }
+}
+
+fn append_row(
+    builder: &mut DeploymentCircuitBreakersBuilder,
+    node_id: GenerationalNodeId,
+    deployment_id: DeploymentId,
+    state: CircuitState,
+) {
+    let mut row = builder.row();
+
+    row.fmt_plain_node_id(node_id.as_plain());
+    row.fmt_gen_node_id(node_id);
+    row.fmt_deployment_id(deployment_id);
+    row.fmt_state(state);
+}
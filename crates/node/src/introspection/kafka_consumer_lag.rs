@@ -0,0 +1,105 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Local scanner implementation for the `kafka_consumer_lag` DataFusion table.
+//!
+//! This scanner reads the [`ConsumerLagRegistry`] snapshot from the Kafka
+//! subscriptions running on the local worker and produces Arrow record batches
+//! for fan-out SQL queries.
+
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use datafusion::arrow::datatypes::SchemaRef;
+use datafusion::execution::SendableRecordBatchStream;
+use datafusion::logical_expr::Expr;
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use futures::stream;
+
+use restate_core::Metadata;
+use restate_storage_query_datafusion::Scan;
+use restate_storage_query_datafusion::kafka_consumer_lag::KafkaConsumerLagBuilder;
+use restate_storage_query_datafusion::table_util::Builder;
+use restate_types::GenerationalNodeId;
+use restate_worker::{ConsumerLagRegistry, PartitionConsumerLag};
+
+/// Creates a local scanner for `kafka_consumer_lag` from the consumer lag
+/// registry of the worker.
+pub(crate) fn create_local_scanner(
+    registry: ConsumerLagRegistry,
+    metadata: Metadata,
+) -> Arc<dyn Scan> {
+    Arc::new(KafkaConsumerLagScanner { registry, metadata })
+}
+
+struct KafkaConsumerLagScanner {
+    registry: ConsumerLagRegistry,
+    metadata: Metadata,
+}
+
+impl Debug for KafkaConsumerLagScanner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("KafkaConsumerLagScanner")
+    }
+}
+
+impl Scan for KafkaConsumerLagScanner {
+    fn scan(
+        &self,
+        projection: SchemaRef,
+        _filters: &[Expr],
+        _batch_size: usize,
+        limit: Option<usize>,
+    ) -> SendableRecordBatchStream {
+        let snapshot = self.registry.snapshot();
+        let my_node_id = self.metadata.my_node_id();
+        let schema = projection.clone();
+
+        let fut = async move {
+            let mut builder = KafkaConsumerLagBuilder::new(schema.clone());
+
+            for (count, partition) in snapshot.iter().enumerate() {
+                if limit.is_some_and(|l| count >= l) {
+                    break;
+                }
+                append_row(&mut builder, my_node_id, partition);
+            }
+
+            builder.finish()
+        };
+
+        Box::pin(RecordBatchStreamAdapter::new(projection, stream::once(fut)))
+    }
+}
+
+fn append_row(
+    builder: &mut KafkaConsumerLagBuilder,
+    node_id: GenerationalNodeId,
+    partition: &PartitionConsumerLag,
+) {
+    let mut row = builder.row();
+
+    row.fmt_plain_node_id(node_id.as_plain());
+    row.fmt_gen_node_id(node_id);
+    row.fmt_subscription_id(partition.subscription_id);
+    row.consumer_group(&partition.consumer_group);
+    row.topic(&partition.topic);
+    row.partition(partition.partition);
+
+    if let Some(next_offset) = partition.next_offset {
+        row.next_offset(next_offset.max(0) as u64);
+    }
+    if let Some(high_watermark) = partition.high_watermark {
+        row.high_watermark(high_watermark.max(0) as u64);
+    }
+    if let Some(lag) = partition.lag() {
+        row.lag(lag as u64);
+    }
+}
@@ -15,4 +15,6 @@
 //! batches for fan-out SQL queries.
 
 pub(crate) mod bifrost_read_streams;
+pub(crate) mod deployment_circuit_breakers;
+pub(crate) mod kafka_consumer_lag;
 pub(crate) mod loglet_workers;
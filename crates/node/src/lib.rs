@@ -113,6 +113,12 @@ pub enum BuildError {
         #[code]
         roles::AdminRoleBuildError,
     ),
+    #[error("building ingress failed: {0}")]
+    Ingress(
+        #[from]
+        #[code]
+        restate_ingress_http::IngressServerError,
+    ),
     #[error("building log-server failed: {0}")]
     LogServer(
         #[from]
@@ -257,6 +263,9 @@ impl Node {
 
         let bifrost_svc = bifrost_svc.with_factory(replicated_loglet_factory);
 
+        let bifrost_svc =
+            bifrost_svc.enable_object_store_tier(config.bifrost.object_store_tier.clone());
+
         #[cfg(feature = "memory-loglet")]
         let bifrost_svc = bifrost_svc.enable_in_memory_loglet();
 
@@ -347,6 +356,25 @@ impl Node {
             remote_scanner_manager.register_node_scanner("loglet_workers", local_scanner);
         }
 
+        // Register kafka_consumer_lag local scanner if the worker role is present.
+        if let Some(worker_role) = &worker_role {
+            let local_scanner = introspection::kafka_consumer_lag::create_local_scanner(
+                worker_role.kafka_consumer_lag(),
+                metadata.clone(),
+            );
+            remote_scanner_manager.register_node_scanner("kafka_consumer_lag", local_scanner);
+        }
+
+        // Register deployment_circuit_breakers local scanner if the worker role is present.
+        if let Some(worker_role) = &worker_role {
+            let local_scanner = introspection::deployment_circuit_breakers::create_local_scanner(
+                worker_role.deployment_circuit_breakers(),
+                metadata.clone(),
+            );
+            remote_scanner_manager
+                .register_node_scanner("deployment_circuit_breakers", local_scanner);
+        }
+
         // Register bifrost_read_streams scanner — available on every node since
         // any node with bifrost can have active read streams.
         {
@@ -390,7 +418,7 @@ impl Node {
                 metadata.updateable_schema(),
                 metadata.updateable_partition_table(),
                 PartitionRouting::new(replica_set_states.clone(), tc.clone()),
-            ))
+            )?)
         } else {
             None
         };
@@ -153,7 +153,8 @@ impl<T: TransportConnect> AdminRole<T> {
             service_discovery,
             telemetry_http_client,
         )
-        .with_query_context(query_context.clone());
+        .with_query_context(query_context.clone())
+        .with_bifrost(bifrost.clone());
 
         if let Some(observer) = local_rule_book_observer {
             admin = admin.with_rule_book_observer(observer);
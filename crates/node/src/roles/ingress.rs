@@ -11,7 +11,9 @@
 use restate_core::network::{Networking, TransportConnect};
 use restate_core::partitions::PartitionRouting;
 use restate_core::{TaskCenter, TaskKind};
-use restate_ingress_http::{HyperServerIngress, InvocationClientRequestDispatcher};
+use restate_ingress_http::{
+    HyperServerIngress, IngressServerError, InvocationClientRequestDispatcher,
+};
 use restate_types::config::IngressOptions;
 use restate_types::health::HealthStatus;
 use restate_types::live::{BoxLiveLoad, Live};
@@ -39,7 +41,7 @@ impl<T: TransportConnect> IngressRole<T> {
         schema: Live<Schema>,
         partition_table: Live<PartitionTable>,
         partition_routing: PartitionRouting,
-    ) -> Self {
+    ) -> Result<Self, IngressServerError> {
         let dispatcher = InvocationClientRequestDispatcher::new(
             PartitionProcessorInvocationClient::new(networking, partition_table, partition_routing),
         );
@@ -49,9 +51,9 @@ impl<T: TransportConnect> IngressRole<T> {
             dispatcher,
             schema,
             health,
-        );
+        )?;
 
-        Self { ingress_http }
+        Ok(Self { ingress_http })
     }
 
     pub fn start(self) -> Result<(), anyhow::Error> {
@@ -25,7 +25,7 @@ use restate_types::health::HealthStatus;
 use restate_types::partitions::state::PartitionReplicaSetStates;
 use restate_types::protobuf::common::WorkerStatus;
 use restate_wal_protocol::Envelope;
-use restate_worker::{RuleBookCacheHandle, Worker};
+use restate_worker::{ConsumerLagRegistry, DeploymentCircuitBreakers, RuleBookCacheHandle, Worker};
 use restate_worker_api::ProcessorsManagerHandle;
 
 #[derive(Debug, thiserror::Error, CodedError)]
@@ -86,6 +86,14 @@ where
         self.worker.rule_book_cache_handle()
     }
 
+    pub fn kafka_consumer_lag(&self) -> ConsumerLagRegistry {
+        self.worker.kafka_consumer_lag()
+    }
+
+    pub fn deployment_circuit_breakers(&self) -> DeploymentCircuitBreakers {
+        self.worker.deployment_circuit_breakers()
+    }
+
     pub fn start(self) -> anyhow::Result<()> {
         TaskCenter::spawn(TaskKind::WorkerRole, "worker-service", async {
             self.worker.run().await
@@ -15,9 +15,14 @@ use restate_storage_api::deduplication_table::{
 };
 use restate_types::identifiers::PartitionId;
 
+use restate_storage_api::protobuf_types::PartitionStoreProtobufValue;
+
 use crate::TableKind::Deduplication;
-use crate::keys::{KeyKind, define_table_key};
-use crate::{PaddedPartitionId, PartitionStore, PartitionStoreTransaction, StorageAccess};
+use crate::TableScanIterationDecision::Emit;
+use crate::keys::{DecodeTableKey, KeyKind, define_table_key};
+use crate::{
+    PaddedPartitionId, PartitionStore, PartitionStoreTransaction, StorageAccess, TableScan,
+};
 
 define_table_key!(
     Deduplication,
@@ -47,6 +52,36 @@ fn get_dedup_sequence_number<S: StorageAccess>(
     storage.get_value_proto(key)
 }
 
+/// Copies the deduplication sequence numbers of the partition `from` for all producers but the
+/// partition itself, whose sequence numbers are bound to the leader epochs of `from`.
+pub(crate) fn copy_dedup_sequence_numbers<S: StorageAccess>(
+    storage: &mut S,
+    from: PartitionId,
+    to: PartitionId,
+) -> Result<usize> {
+    let self_producer = ProducerId::self_producer();
+    let entries = storage.for_each_key_value_in_place(
+        TableScan::<DeduplicationKeyBuilder>::SinglePartition(from),
+        |mut k, mut v| {
+            let entry = DeduplicationKey::deserialize_from(&mut k).and_then(|key| {
+                DedupSequenceNumber::decode(&mut v).map(|sequence_number| (key, sequence_number))
+            });
+            Emit(entry)
+        },
+    )?;
+
+    let mut copied = 0;
+    for entry in entries {
+        let (key, dedup_sequence_number) = entry?;
+        if key.producer_id == self_producer {
+            continue;
+        }
+        storage.put_kv_proto(create_key(to, key.producer_id), &dedup_sequence_number)?;
+        copied += 1;
+    }
+    Ok(copied)
+}
+
 impl ReadDeduplicationTable for PartitionStore {
     async fn get_dedup_sequence_number(
         &mut self,
@@ -15,7 +15,8 @@ use crate::{
 };
 use restate_limiter::RuleBook;
 use restate_storage_api::fsm_table::{
-    CachedEpochMetadata, PartitionDurability, ReadFsmTable, SequenceNumber, WriteFsmTable,
+    AdoptedOutbox, CachedEpochMetadata, PartitionDurability, ReadFsmTable, SequenceNumber,
+    WriteFsmTable,
 };
 use restate_storage_api::protobuf_types::{PartitionStoreProtobufValue, ProtobufStorageWrapper};
 use restate_storage_api::{Result, StorageError};
@@ -83,6 +84,11 @@ pub(crate) mod fsm_variable {
     /// `VersionBarrierCommand` entries carrying feature changes.
     /// *Since v1.7.0*
     pub(crate) const STATE_MACHINE_FEATURES: u64 = 10;
+
+    /// Producers of the outbox messages taken over from the partition this partition was split
+    /// from. Written once when the partition is bootstrapped from the split partition.
+    /// *Since v1.7.1*
+    pub(crate) const ADOPTED_OUTBOX: u64 = 11;
 }
 
 fn get<T: PartitionStoreProtobufValue, S: StorageAccess>(
@@ -217,6 +223,61 @@ pub(crate) fn append_storage_version_to_wb(
     Ok(())
 }
 
+/// Copies the state machine variables of the partition `from` that are not bound to its log.
+/// Used when bootstrapping a partition from the state of the partition it was split from.
+pub(crate) fn copy_split_variables<S: StorageAccess>(
+    storage: &mut S,
+    from: PartitionId,
+    to: PartitionId,
+) -> Result<()> {
+    // The applied LSN, durability and partition configuration refer to the log and the epochs
+    // of the split partition, the new partition starts over on its own log.
+    for state_id in [
+        fsm_variable::INBOX_SEQ_NUMBER,
+        fsm_variable::OUTBOX_SEQ_NUMBER,
+        fsm_variable::RESTATE_VERSION_BARRIER,
+        fsm_variable::STORAGE_VERSION,
+        fsm_variable::SERVICES_SCHEMA_METADATA,
+        fsm_variable::JC_ORPHAN_CLEANUP_DONE,
+        fsm_variable::RULE_BOOK,
+        fsm_variable::STATE_MACHINE_FEATURES,
+    ] {
+        let value = storage.get_kv_raw(create_key(from, state_id), |_, value| {
+            Ok(value.map(bytes::Bytes::copy_from_slice))
+        })?;
+        if let Some(value) = value {
+            storage.put_kv_raw(create_key(to, state_id), value)?;
+        }
+    }
+    Ok(())
+}
+
+/// Records which producers the outbox messages of the partition `from` belong to, when the
+/// partition `to` takes them over. `head` is the index of the first message of `from` which has
+/// not been delivered yet.
+pub(crate) fn adopt_outbox_producers<S: StorageAccess>(
+    storage: &mut S,
+    from: PartitionId,
+    to: PartitionId,
+    head: Option<MessageIndex>,
+) -> Result<AdoptedOutbox> {
+    let end = get::<SequenceNumber, _>(storage, from, fsm_variable::OUTBOX_SEQ_NUMBER)?
+        .map(Into::into)
+        .unwrap_or_default();
+    let adopted_outbox = storage
+        .get_value_storage_codec::<_, AdoptedOutbox>(create_key(
+            from,
+            fsm_variable::ADOPTED_OUTBOX,
+        ))?
+        .unwrap_or_default()
+        .adopt(head.unwrap_or(end), from, end);
+    storage.put_kv_storage_codec(
+        create_key(to, fsm_variable::ADOPTED_OUTBOX),
+        &adopted_outbox,
+    )?;
+    Ok(adopted_outbox)
+}
+
 pub(crate) fn is_jc_orphan_cleanup_done<S: StorageAccess>(
     storage: &mut S,
     partition_id: PartitionId,
@@ -290,6 +351,12 @@ impl ReadFsmTable for PartitionStore {
         self.get_value_storage_codec(key)
             .map(|opt| opt.unwrap_or_default())
     }
+
+    async fn get_adopted_outbox(&mut self) -> Result<AdoptedOutbox> {
+        let key = create_key(self.partition_id(), fsm_variable::ADOPTED_OUTBOX);
+        self.get_value_storage_codec(key)
+            .map(|opt| opt.unwrap_or_default())
+    }
 }
 
 impl WriteFsmTable for PartitionStoreTransaction<'_> {
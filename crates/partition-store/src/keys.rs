@@ -193,6 +193,15 @@ impl KeyKind {
         }
     }
 
+    /// Whether keys of this kind start with the (padded) partition id instead of the partition
+    /// key.
+    pub const fn is_keyed_by_partition_id(&self) -> bool {
+        matches!(
+            self,
+            KeyKind::Deduplication | KeyKind::Fsm | KeyKind::Outbox | KeyKind::Timers
+        )
+    }
+
     pub fn serialize<B: BufMut>(&self, buf: &mut B) {
         let bytes = self.as_bytes();
         buf.put_slice(bytes);
@@ -899,6 +908,10 @@ impl KeyEncode for TimerKeyKind {
                 target.put_u8(3);
                 invocation_uuid.encode(target);
             }
+            TimerKeyKind::ScheduleTick { invocation_uuid } => {
+                target.put_u8(4);
+                invocation_uuid.encode(target);
+            }
         }
     }
 
@@ -920,6 +933,9 @@ impl KeyEncode for TimerKeyKind {
             TimerKeyKind::CleanInvocationStatus { invocation_uuid } => {
                 KeyEncode::serialized_length(invocation_uuid)
             }
+            TimerKeyKind::ScheduleTick { invocation_uuid } => {
+                KeyEncode::serialized_length(invocation_uuid)
+            }
         }
     }
 }
@@ -953,6 +969,10 @@ impl KeyDecode for TimerKeyKind {
                 let invocation_uuid = InvocationUuid::decode(source)?;
                 TimerKeyKind::NeoInvoke { invocation_uuid }
             }
+            4 => {
+                let invocation_uuid = InvocationUuid::decode(source)?;
+                TimerKeyKind::ScheduleTick { invocation_uuid }
+            }
             i => {
                 return Err(StorageError::Generic(anyhow!(
                     "Unknown discriminator for TimerKind: '{}'",
@@ -42,7 +42,7 @@ fn add_message<S: StorageAccess>(
     storage.put_kv_proto(key, outbox_message)
 }
 
-fn get_outbox_head_seq_number<S: StorageAccess>(
+pub(crate) fn get_outbox_head_seq_number<S: StorageAccess>(
     storage: &mut S,
     partition_id: PartitionId,
 ) -> Result<Option<u64>> {
@@ -124,6 +124,24 @@ fn truncate_outbox<S: StorageAccess>(
     Ok(())
 }
 
+/// Copies the outbox of the partition `from`, keeping the message indexes.
+pub(crate) fn copy_outbox<S: StorageAccess>(
+    storage: &mut S,
+    from: PartitionId,
+    to: PartitionId,
+) -> Result<usize> {
+    let mut copied = 0;
+    let mut next_sequence_number = 0;
+    while let Some((sequence_number, outbox_message)) =
+        get_next_outbox_message(storage, from, next_sequence_number)?
+    {
+        add_message(storage, to, sequence_number, &outbox_message)?;
+        next_sequence_number = sequence_number + 1;
+        copied += 1;
+    }
+    Ok(copied)
+}
+
 impl ReadOutboxTable for PartitionStore {
     async fn get_outbox_head_seq_number(&mut self) -> Result<Option<u64>> {
         get_outbox_head_seq_number(self, self.partition_id())
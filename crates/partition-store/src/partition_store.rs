@@ -19,7 +19,7 @@ use bytes::BytesMut;
 use enum_map::Enum;
 use rocksdb::{
     BoundColumnFamily, DBPinnableSlice, DBRawIteratorWithThreadMode, PrefixRange, ReadOptions,
-    SnapshotWithThreadMode,
+    SnapshotWithThreadMode, WriteBatch,
 };
 use static_assertions::const_assert_eq;
 use tokio::sync::mpsc;
@@ -44,16 +44,20 @@ use restate_types::storage::StorageEncode;
 
 use restate_types::partitions::StorageVersion;
 
+use crate::deduplication_table::copy_dedup_sequence_numbers;
 use crate::fsm_table::{
-    get_locally_durable_lsn, get_storage_version, get_storage_version_from_partition_db,
-    is_jc_orphan_cleanup_done, put_jc_orphan_cleanup_done, put_storage_version,
+    adopt_outbox_producers, copy_split_variables, get_locally_durable_lsn, get_storage_version,
+    get_storage_version_from_partition_db, is_jc_orphan_cleanup_done, put_jc_orphan_cleanup_done,
+    put_storage_version,
 };
 use crate::keys::{EncodeTableKey, EncodeTableKeyPrefix, KeyKind};
 use crate::migrations::run_migrations_up_to;
+use crate::outbox_table::{copy_outbox, get_outbox_head_seq_number};
 use crate::partition_db::PartitionDb;
 use crate::scan::PhysicalScan;
 use crate::scan::TableScan;
 use crate::snapshots::{LocalPartitionSnapshot, SnapshotDir};
+use crate::timer_table::copy_timers_in_range;
 
 pub type DB = rocksdb::DB;
 
@@ -662,6 +666,100 @@ impl PartitionStore {
         put_jc_orphan_cleanup_done(self, self.partition_id())
     }
 
+    /// Takes over the state of the partition `split_from` that is keyed by its partition id,
+    /// after a snapshot of that partition has been imported into this partition store. Data
+    /// keyed by partition key outside of the key range of this partition and the data keyed by
+    /// the partition id of `split_from` are deleted afterwards.
+    ///
+    /// The outbox must only be adopted by one of the partitions resulting from a split, so that
+    /// pending messages are not sent twice. The adopted messages are sent under the producer id
+    /// of the partition which created them, see [`ReadFsmTable::get_adopted_outbox`].
+    pub(crate) async fn adopt_split_partition_state(
+        &mut self,
+        split_from: PartitionId,
+        adopt_outbox: bool,
+    ) -> Result<()> {
+        let partition_id = self.partition_id();
+        let key_range = self.partition_key_range();
+
+        let mut txn = self.transaction();
+        copy_split_variables(&mut txn, split_from, partition_id)?;
+        let timers = copy_timers_in_range(&mut txn, split_from, partition_id, key_range)?;
+        let dedup_sequence_numbers =
+            copy_dedup_sequence_numbers(&mut txn, split_from, partition_id)?;
+        let outbox_messages = if adopt_outbox {
+            let head = get_outbox_head_seq_number(&mut txn, split_from)?;
+            adopt_outbox_producers(&mut txn, split_from, partition_id, head)?;
+            copy_outbox(&mut txn, split_from, partition_id)?
+        } else {
+            0
+        };
+        txn.commit().await?;
+        self.delete_data_not_owned_after_split(split_from).await?;
+
+        debug!(
+            %split_from,
+            timers,
+            dedup_sequence_numbers,
+            outbox_messages,
+            "Adopted the state of the split partition"
+        );
+        Ok(())
+    }
+
+    /// Deletes the data imported from the snapshot of the partition `split_from` which this
+    /// partition does not own: data keyed by partition key outside of its key range and data
+    /// keyed by the partition id of `split_from`.
+    async fn delete_data_not_owned_after_split(&self, split_from: PartitionId) -> Result<()> {
+        const PREFIX_LEN: usize = KeyKind::SERIALIZED_LENGTH + size_of::<u64>();
+        let prefix = |key_kind: KeyKind, value: u64| {
+            let mut prefix = [0u8; PREFIX_LEN];
+            prefix[..KeyKind::SERIALIZED_LENGTH].copy_from_slice(key_kind.as_bytes());
+            prefix[KeyKind::SERIALIZED_LENGTH..].copy_from_slice(&value.to_be_bytes());
+            prefix
+        };
+
+        let key_range = self.partition_key_range();
+        let cf_handle = self.partition_db().cf_handle();
+        let mut wb = WriteBatch::default();
+        for key_kind in <KeyKind as strum::VariantArray>::VARIANTS {
+            if key_kind.is_keyed_by_partition_id() {
+                let split_from = u64::from(split_from);
+                wb.delete_range_cf(
+                    cf_handle,
+                    prefix(*key_kind, split_from),
+                    prefix(*key_kind, split_from + 1),
+                );
+            } else {
+                wb.delete_range_cf(
+                    cf_handle,
+                    prefix(*key_kind, 0),
+                    prefix(*key_kind, key_range.start()),
+                );
+                if let Some(end) = key_range.end().checked_add(1) {
+                    wb.delete_range_cf(
+                        cf_handle,
+                        &prefix(*key_kind, end)[..],
+                        &key_kind.exclusive_upper_bound()[..],
+                    );
+                }
+            }
+        }
+
+        self.partition_db()
+            .rocksdb()
+            .write_batch(
+                "delete-data-not-owned-after-split",
+                Priority::High,
+                IoMode::Default,
+                rocksdb::WriteOptions::default(),
+                wb,
+            )
+            .await
+            .map(|_| ())
+            .map_err(|err| StorageError::Generic(err.into()))
+    }
+
     pub async fn verify_and_run_migrations(&mut self) -> Result<()> {
         // The target schema version is gated by the operator opt-in. Without
         // the flag we leave the partition at `V1_5` so a downgrade to a
@@ -680,8 +778,10 @@ impl PartitionStore {
 
         // We assume the partition store to be empty if it does not contain any applied lsn. The
         // reason is that we always commit changes to the partition store via a transaction which
-        // also updates the applied lsn field.
-        let is_empty = self.get_applied_lsn().await?.is_none();
+        // also updates the applied lsn field. The exception are partitions bootstrapped from the
+        // partition they were split from, which also adopt the storage version of its data.
+        let is_empty = self.get_applied_lsn().await?.is_none()
+            && get_storage_version(self, self.partition_id()).await? == StorageVersion::None;
         if is_empty {
             put_storage_version(self, self.partition_id(), target as u16).await?;
             // A fresh partition store cannot have orphaned jc index entries, so mark the
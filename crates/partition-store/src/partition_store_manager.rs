@@ -240,7 +240,16 @@ impl PartitionStoreManager {
             // we have a database, but perhaps it doesn't meet the min_applied_lsn requirement?
             let mut partition_store = PartitionStore::from(db);
             match target_lsn {
-                None => return Ok(partition_store),
+                None if partition.split_from().is_none() => return Ok(partition_store),
+                None => {
+                    // Bootstrapping a split partition is only durable once the partition has
+                    // applied its first log record, before that it is bootstrapped again.
+                    if partition_store.get_applied_lsn().await?.is_some() {
+                        return Ok(partition_store);
+                    }
+                    drop(partition_store);
+                    cell.drop_cf(&mut state_guard).await?;
+                }
                 Some(min_applied_lsn) => {
                     let my_applied_lsn = partition_store.get_applied_lsn().await?;
                     if my_applied_lsn.unwrap_or(Lsn::INVALID) >= min_applied_lsn {
@@ -263,6 +272,39 @@ impl PartitionStoreManager {
             .await
             .map_err(OpenError::Snapshot)?;
 
+        // A partition that was split from another partition starts from the state of that
+        // partition, until it has a snapshot of its own.
+        if let (None, None, Some(split_from)) = (&snapshot, target_lsn, partition.split_from()) {
+            let Some(snapshot) = self
+                .snapshots
+                .download_latest_snapshot(split_from)
+                .await
+                .map_err(OpenError::Snapshot)?
+            else {
+                error!(
+                    %split_from,
+                    "No snapshot of the partition this partition was split from is available"
+                );
+                return Err(OpenError::SnapshotRequired);
+            };
+
+            info!(
+                %split_from,
+                split_lsn = %snapshot.min_applied_lsn,
+                "Bootstrapping partition store from the partition it was split from"
+            );
+            // The partition keeping the start of the split key range adopts the outbox
+            let adopt_outbox = snapshot.key_range.start() == partition.key_range.start();
+            let db = cell
+                .import_cf(&mut state_guard, snapshot, rocksdb.clone())
+                .await?;
+            let mut partition_store = PartitionStore::from(db);
+            partition_store
+                .adopt_split_partition_state(split_from, adopt_outbox)
+                .await?;
+            return Ok(partition_store);
+        }
+
         match (snapshot, target_lsn) {
             (None, None) => {
                 debug!("No snapshot found for partition, creating new partition store");
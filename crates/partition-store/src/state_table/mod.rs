@@ -242,7 +242,7 @@ fn delete_all_user_state<S: StorageAccess>(
     Ok(())
 }
 
-fn get_user_state<S: StorageAccess>(
+pub(crate) fn get_user_state<S: StorageAccess>(
     storage: &mut S,
     storage_version: StorageVersion,
     service_id: &ServiceId,
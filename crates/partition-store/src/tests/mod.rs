@@ -39,6 +39,7 @@ mod locks_table_test;
 mod outbox_table_test;
 mod promise_table_test;
 mod snapshots_test;
+mod split_test;
 mod state_table_test;
 mod timer_table_test;
 mod virtual_object_status_table_test;
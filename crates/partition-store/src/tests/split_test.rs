@@ -0,0 +1,145 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use bytes::Bytes;
+use googletest::prelude::*;
+use tempfile::tempdir;
+
+use restate_rocksdb::RocksDbManager;
+use restate_storage_api::Transaction;
+use restate_storage_api::fsm_table::{
+    AdoptedOutbox, AdoptedOutboxRange, ReadFsmTable, WriteFsmTable,
+};
+use restate_storage_api::outbox_table::{OutboxMessage, WriteOutboxTable};
+use restate_storage_api::state_table::WriteStateTable;
+use restate_types::identifiers::{PartitionId, ServiceId, SnapshotId};
+use restate_types::logs::Lsn;
+use restate_types::partitions::Partition;
+use restate_types::sharding::KeyRange;
+
+use super::mock_random_service_invocation;
+use crate::outbox_table::get_outbox_head_seq_number;
+use crate::snapshots::LocalPartitionSnapshot;
+use crate::state_table::get_user_state;
+use crate::{PartitionStore, PartitionStoreManager};
+
+const SPLIT_FROM: Partition = Partition::new(PartitionId::new_unchecked(1), KeyRange::new(0, 199));
+const LEFT: Partition = Partition::new(PartitionId::new_unchecked(2), KeyRange::new(0, 99));
+const RIGHT: Partition = Partition::new(PartitionId::new_unchecked(3), KeyRange::new(100, 199));
+
+fn state_key() -> Bytes {
+    Bytes::from_static(b"key")
+}
+
+fn service_id(partition_key: u64) -> ServiceId {
+    ServiceId::with_partition_key(partition_key, "svc", format!("key-{partition_key}"))
+}
+
+async fn get_state(partition_store: &mut PartitionStore, partition_key: u64) -> Option<Bytes> {
+    let storage_version = partition_store.storage_version();
+    get_user_state(
+        partition_store,
+        storage_version,
+        &service_id(partition_key),
+        &state_key(),
+    )
+    .unwrap()
+}
+
+#[restate_core::test]
+async fn adopt_split_partition_state() -> googletest::Result<()> {
+    RocksDbManager::init();
+    let manager = PartitionStoreManager::create(true).await?;
+
+    let mut partition_store = manager.open(&SPLIT_FROM, None).await?;
+    let mut txn = partition_store.transaction();
+    txn.put_applied_lsn(Lsn::new(100))?;
+    for partition_key in [50, 150] {
+        txn.put_user_state(&service_id(partition_key), &state_key(), b"value")?;
+    }
+    // messages [0, 3) were delivered, [3, 5) are pending
+    for message_index in 3..5 {
+        txn.put_outbox_message(
+            message_index,
+            &OutboxMessage::ServiceInvocation(mock_random_service_invocation()),
+        )?;
+    }
+    txn.put_outbox_seq_number(5)?;
+    txn.commit().await?;
+
+    // importing a snapshot consumes its files, each of the new partitions imports its own copy
+    let snapshots_dir = tempdir()?;
+    let mut snapshots = Vec::new();
+    for partition in [&LEFT, &RIGHT] {
+        let snapshot_dir = snapshots_dir
+            .path()
+            .join(partition.partition_id.to_string());
+        snapshots.push(
+            partition_store
+                .create_local_snapshot(&snapshot_dir, None, SnapshotId::new())
+                .await?,
+        );
+    }
+    drop(partition_store);
+    let [left_snapshot, right_snapshot]: [LocalPartitionSnapshot; 2] =
+        snapshots.try_into().ok().unwrap();
+
+    let open_split_partition =
+        async |partition: &Partition, snapshot: LocalPartitionSnapshot, adopt_outbox: bool| {
+            let mut partition_store = manager.open_from_snapshot(partition, snapshot).await?;
+            partition_store
+                .adopt_split_partition_state(SPLIT_FROM.partition_id, adopt_outbox)
+                .await?;
+            googletest::Result::Ok(partition_store)
+        };
+
+    // the left partition adopts the outbox and sends it under the producer id of the split
+    // partition
+    let mut left = open_split_partition(&LEFT, left_snapshot, true).await?;
+    assert_that!(left.get_outbox_seq_number().await?, eq(5));
+    assert_that!(
+        get_outbox_head_seq_number(&mut left, LEFT.partition_id)?,
+        some(eq(3))
+    );
+    assert_that!(
+        left.get_adopted_outbox().await?,
+        eq(&AdoptedOutbox {
+            ranges: vec![AdoptedOutboxRange {
+                producer: SPLIT_FROM.partition_id,
+                end: 5,
+            }],
+        })
+    );
+    // the new partition starts over on its own log
+    assert_that!(left.get_applied_lsn().await?, none());
+
+    // data which belongs to the other partition or to the split partition is pruned
+    assert_that!(get_state(&mut left, 50).await, some(anything()));
+    assert_that!(get_state(&mut left, 150).await, none());
+    assert_that!(
+        get_outbox_head_seq_number(&mut left, SPLIT_FROM.partition_id)?,
+        none()
+    );
+
+    // the right partition does not adopt the outbox
+    let mut right = open_split_partition(&RIGHT, right_snapshot, false).await?;
+    assert_that!(
+        get_outbox_head_seq_number(&mut right, RIGHT.partition_id)?,
+        none()
+    );
+    assert_that!(
+        right.get_adopted_outbox().await?,
+        eq(&AdoptedOutbox::default())
+    );
+    assert_that!(get_state(&mut right, 50).await, none());
+    assert_that!(get_state(&mut right, 150).await, some(anything()));
+
+    Ok(())
+}
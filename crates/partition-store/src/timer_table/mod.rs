@@ -8,6 +8,8 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::ops::RangeBounds;
+
 use futures::Stream;
 use futures_util::stream;
 
@@ -17,7 +19,8 @@ use restate_storage_api::protobuf_types::PartitionStoreProtobufValue;
 use restate_storage_api::timer_table::{
     ReadTimerTable, Timer, TimerKey, TimerKeyKind, WriteTimerTable,
 };
-use restate_types::identifiers::{InvocationUuid, PartitionId};
+use restate_types::identifiers::{InvocationUuid, PartitionId, WithPartitionKey};
+use restate_types::sharding::KeyRange;
 
 use crate::TableKind::Timers;
 use crate::TableScanIterationDecision::Emit;
@@ -108,6 +111,15 @@ fn exclusive_start_key_range(
                     },
                 }
             }
+            TimerKeyKind::ScheduleTick { invocation_uuid } => {
+                let incremented_invocation_uuid = increment_invocation_uuid(invocation_uuid);
+                TimerKey {
+                    timestamp: timer_key.timestamp,
+                    kind: TimerKeyKind::ScheduleTick {
+                        invocation_uuid: incremented_invocation_uuid,
+                    },
+                }
+            }
         };
 
         let lower_bound = write_timer_key(partition_id, &next_timer_key);
@@ -174,6 +186,24 @@ fn next_timers_greater_than<S: StorageAccess>(
     })
 }
 
+/// Copies the timers of the partition `from` that belong to the given key range.
+pub(crate) fn copy_timers_in_range<S: StorageAccess>(
+    storage: &mut S,
+    from: PartitionId,
+    to: PartitionId,
+    key_range: KeyRange,
+) -> Result<usize> {
+    let mut copied = 0;
+    for timer in next_timers_greater_than(storage, from, None, usize::MAX)? {
+        let (timer_key, timer) = timer?;
+        if key_range.contains(&timer.partition_key()) {
+            add_timer(storage, to, &timer_key, &timer)?;
+            copied += 1;
+        }
+    }
+    Ok(copied)
+}
+
 impl ReadTimerTable for PartitionStore {
     fn next_timers_greater_than(
         &mut self,
@@ -516,6 +546,9 @@ mod tests {
                         invocation_uuid: InvocationUuid::mock_random(),
                     }
                 }
+                TimerKeyKindDiscriminants::ScheduleTick => TimerKeyKind::ScheduleTick {
+                    invocation_uuid: InvocationUuid::mock_random(),
+                },
             }
         };
 
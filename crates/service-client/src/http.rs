@@ -23,23 +23,29 @@ use http::Version;
 use http_body_util::{BodyExt, Either as EitherBody};
 use hyper::body::{Body, Incoming};
 use hyper::http::HeaderValue;
-use hyper::http::uri::PathAndQuery;
+use hyper::http::uri::{PathAndQuery, Scheme};
 use hyper::{HeaderMap, Method, Request, Response, Uri};
 use hyper_rustls::{ConfigBuilderExt, HttpsConnector};
 use hyper_util::client::legacy::connect::HttpConnector;
+use hyper_util::rt::TokioIo;
 use rustls::{ClientConfig, KeyLogFile};
-use tower::Layer;
+use tokio::net::UnixStream;
+use tower::util::MapResponse;
+use tower::{Layer, ServiceExt};
 
 use restate_types::config::HttpOptions;
+use restate_types::deployment::unix_socket_path;
 
 use crate::pool::conn::PermittedRecvStream;
 use crate::pool::tls::TlsConnector;
+use crate::pool::uds::{self, UnixConnector};
 use crate::pool::{self, Pool, TcpConnector};
 use crate::utils::ErrorExt;
 
 use super::proxy::ProxyConnector;
 
 type ProxiedHttpsConnector = ProxyConnector<HttpsConnector<HttpConnector>>;
+type UnixHttpConnector = MapResponse<UnixConnector, fn(UnixStream) -> TokioIo<UnixStream>>;
 
 static TLS_CLIENT_CONFIG: LazyLock<ClientConfig> = LazyLock::new(|| {
     // We need to explicitly configure the crypto provider since we activate the ring as well as
@@ -75,6 +81,13 @@ pub struct HttpClient {
 
     /// Client when HTTP2 was specifically requested. Uses the custom [`pool::Pool`]
     h2_pool: Pool<ProxyConnector<TlsConnector<TcpConnector>>>,
+
+    /// Client for deployments listening on a unix domain socket, when HTTP1.1 was specifically
+    /// requested.
+    uds_h1_client: hyper_util::client::legacy::Client<UnixHttpConnector, BoxBody>,
+
+    /// Client for deployments listening on a unix domain socket. Uses the custom [`pool::Pool`]
+    uds_h2_pool: Pool<UnixConnector>,
 }
 
 impl HttpClient {
@@ -126,20 +139,9 @@ impl HttpClient {
             .enable_http1()
             .wrap_connector(http_connector.clone());
 
-        let h2_pool = {
-            // Use the connect_timeout as tls handshake timeout should be okay
-            let connector = pool::tls::TlsConnectorLayer::new(
-                TLS_CLIENT_CONFIG.clone(),
-                options.connect_timeout.into(),
-            )
-            .layer(pool::TcpConnector::new(options.connect_timeout.into()));
-
-            let connector = ProxyConnector::new(
-                options.http_proxy.clone(),
-                options.no_proxy.clone(),
-                connector,
-            );
+        let unix_connector = UnixConnector::new(options.connect_timeout.into());
 
+        let pool_builder = {
             let builder = pool::PoolBuilder::default()
                 .keep_alive_interval(keep_alive_interval)
                 .keep_alive_timeout(
@@ -159,12 +161,27 @@ impl HttpClient {
                     Some(options.http2_idle_connection_timeout.into())
                 });
 
-            let builder = match options.http2_initial_max_send_streams {
+            match options.http2_initial_max_send_streams {
                 Some(value) => builder.initial_max_send_streams(value),
                 None => builder,
-            };
+            }
+        };
 
-            builder.build(connector)
+        let h2_pool = {
+            // Use the connect_timeout as tls handshake timeout should be okay
+            let connector = pool::tls::TlsConnectorLayer::new(
+                TLS_CLIENT_CONFIG.clone(),
+                options.connect_timeout.into(),
+            )
+            .layer(pool::TcpConnector::new(options.connect_timeout.into()));
+
+            let connector = ProxyConnector::new(
+                options.http_proxy.clone(),
+                options.no_proxy.clone(),
+                connector,
+            );
+
+            pool_builder.clone().build(connector)
         };
 
         HttpClient {
@@ -179,6 +196,10 @@ impl HttpClient {
                 https_h1_connector,
             )),
             h2_pool,
+            uds_h1_client: builder.clone().build::<_, BoxBody>(
+                unix_connector.map_response(TokioIo::new as fn(UnixStream) -> TokioIo<UnixStream>),
+            ),
+            uds_h2_pool: pool_builder.build(unix_connector),
         }
     }
 
@@ -244,11 +265,48 @@ impl HttpClient {
         B: Body<Data = Bytes> + Send + Sized + 'static,
         B::Error: std::error::Error + Send + Sync + 'static,
     {
+        // Requests to unix domain sockets carry the socket path in the authority, so that
+        // connections are pooled per socket. See [`uds`].
+        let (uri, headers, is_unix_socket) = match unix_socket_path(&uri) {
+            Some(socket_path) => {
+                let uri = Uri::builder()
+                    .scheme(Scheme::HTTP)
+                    .authority(uds::socket_authority(&socket_path.to_string_lossy()))
+                    .path_and_query("/")
+                    .build();
+                let uri = match uri {
+                    Ok(uri) => uri,
+                    Err(err) => return future::ready(Err(err.into())).right_future(),
+                };
+                let mut headers = headers;
+                headers
+                    .entry(http::header::HOST)
+                    .or_insert(HeaderValue::from_static("localhost"));
+                (uri, headers, true)
+            }
+            None => (uri, headers, false),
+        };
+
         let request = match Self::build_request(uri, version, body, method, path, headers) {
             Ok(request) => request,
             Err(err) => return future::ready(Err(err.into())).right_future(),
         };
 
+        if is_unix_socket {
+            let fut = match version {
+                Some(Version::HTTP_11) => ResponseMapper {
+                    fut: self.uds_h1_client.request(request),
+                }
+                .left_future(),
+                // There's no ALPN without TLS, so we use h2c as for cleartext urls
+                _ => ResponseMapper {
+                    fut: self.uds_h2_pool.request(request),
+                }
+                .right_future(),
+            };
+            return Either::Left(Either::Right(fut));
+        }
+
         let fut = match version {
             // version is set to http1.1 when use_http1.1 is set
             Some(Version::HTTP_11) => ResponseMapper {
@@ -275,7 +333,7 @@ impl HttpClient {
             .left_future(),
         };
 
-        Either::Left(fut)
+        Either::Left(Either::Left(fut))
     }
 }
 
@@ -380,6 +438,14 @@ impl HttpError {
         }
     }
 
+    /// Connect errors are those where no connection to the remote endpoint could be established.
+    pub fn is_connect(&self) -> bool {
+        matches!(
+            self,
+            HttpError::Connect(_) | HttpError::PoolError(pool::Error::IO(_))
+        )
+    }
+
     fn is_possible_h11_only_error(err: &hyper_util::client::legacy::Error) -> bool {
         // this is the error we see from the h2 lib when the server sends back an http1.1 response
         // to an http2 request. http2 is designed to start requests with what looks like an invalid
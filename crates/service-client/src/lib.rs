@@ -275,6 +275,16 @@ impl ServiceClientError {
             ServiceClientError::IdentityV1(_) => false, // this really should never happen
         }
     }
+
+    /// Connect errors are those where the deployment could not be reached at all.
+    pub fn is_connect(&self) -> bool {
+        match self {
+            ServiceClientError::Http(_, http_error) => http_error.is_connect(),
+            ServiceClientError::Lambda(_, _)
+            | ServiceClientError::GcpAuth(_, _)
+            | ServiceClientError::IdentityV1(_) => false,
+        }
+    }
 }
 
 pub struct Request<B> {
@@ -14,6 +14,7 @@ mod metric_definitions;
 #[cfg(any(test, feature = "test_util"))]
 pub mod test_util;
 pub mod tls;
+pub mod uds;
 
 use std::{
     fmt::Display,
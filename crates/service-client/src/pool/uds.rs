@@ -0,0 +1,132 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Unix domain socket connections.
+//!
+//! Both the hyper client and the [`Pool`](super::Pool) key their connections by the authority of
+//! the request URI. To keep separate connections per socket, requests to unix domain sockets carry
+//! the hex encoded path of the socket as host of the URI, see [`socket_authority`].
+
+use std::{
+    io::{self, ErrorKind},
+    path::PathBuf,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures::future::BoxFuture;
+use http::Uri;
+use tokio::net::UnixStream;
+use tower::Service;
+use tracing::trace;
+
+/// Returns the authority of the requests to the unix domain socket at the given path.
+pub fn socket_authority(socket_path: &str) -> String {
+    socket_path
+        .as_bytes()
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+fn decode_socket_path(uri: &Uri) -> Option<PathBuf> {
+    let host = uri.host()?.as_bytes();
+    if host.is_empty() || host.len() % 2 != 0 {
+        return None;
+    }
+
+    let bytes = host
+        .chunks(2)
+        .map(|digits| {
+            std::str::from_utf8(digits)
+                .ok()
+                .and_then(|digits| u8::from_str_radix(digits, 16).ok())
+        })
+        .collect::<Option<Vec<_>>>()?;
+    String::from_utf8(bytes).ok().map(PathBuf::from)
+}
+
+/// A Tower [`Service`] that establishes connections to the unix domain socket encoded in the
+/// host of the URI.
+#[derive(Debug, Clone, Copy)]
+pub struct UnixConnector {
+    connect_timeout: Duration,
+}
+
+impl UnixConnector {
+    pub fn new(connect_timeout: Duration) -> Self {
+        Self { connect_timeout }
+    }
+}
+
+impl Service<Uri> for UnixConnector {
+    type Response = UnixStream;
+    type Error = io::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: Uri) -> Self::Future {
+        let connect_timeout = self.connect_timeout;
+        let fut = async move {
+            let socket_path = decode_socket_path(&req).ok_or_else(|| {
+                io::Error::new(ErrorKind::InvalidInput, "unknown unix domain socket path")
+            })?;
+            trace!("connecting to unix:{}", socket_path.display());
+
+            tokio::time::timeout(connect_timeout, UnixStream::connect(socket_path))
+                .await
+                .map_err(|_| io::Error::new(ErrorKind::TimedOut, "connect timeout"))?
+        };
+
+        Box::pin(fut)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn socket_authority_roundtrip() {
+        let socket_path = "/run/restate/greeter.sock";
+        let uri: Uri = format!("unix://{}/discover", socket_authority(socket_path))
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            decode_socket_path(&uri),
+            Some(PathBuf::from("/run/restate/greeter.sock"))
+        );
+    }
+
+    #[tokio::test]
+    async fn connect_to_socket() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket_path = dir.path().join("service.sock");
+        let listener = tokio::net::UnixListener::bind(&socket_path).unwrap();
+
+        let uri: Uri = format!(
+            "unix://{}/",
+            socket_authority(socket_path.to_str().unwrap())
+        )
+        .parse()
+        .unwrap();
+        let (connected, accepted) = tokio::join!(
+            UnixConnector::new(Duration::from_secs(1)).call(uri),
+            listener.accept()
+        );
+
+        connected.unwrap();
+        accepted.unwrap();
+    }
+}
@@ -207,7 +207,7 @@ impl DiscoveryClient for ServiceDiscovery {
                     // ALPN will sort this out
                     None
                 } else {
-                    // By default, we use h2c on HTTP
+                    // By default, we use h2c on HTTP and unix domain sockets
                     Some(http::Version::HTTP_2)
                 };
                 // Use the same auth for discovery as the regular invocation path uses
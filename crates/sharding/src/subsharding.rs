@@ -105,6 +105,12 @@ impl ShardPlan {
         Some(self.find_shard_unchecked(idx))
     }
 
+    /// The key range covered by the plan.
+    #[inline]
+    pub const fn key_range(&self) -> &KeyRange {
+        &self.range
+    }
+
     /// Number of shards actually used.
     pub const fn shard_count(&self) -> u16 {
         self.num_shards
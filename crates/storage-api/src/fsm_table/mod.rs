@@ -13,7 +13,7 @@ use std::future::Future;
 use bytes::BytesMut;
 
 use restate_limiter::RuleBook;
-use restate_types::identifiers::LeaderEpoch;
+use restate_types::identifiers::{LeaderEpoch, PartitionId};
 use restate_types::logs::Lsn;
 use restate_types::message::MessageIndex;
 use restate_types::partitions::features::PersistedStateMachineFeatures;
@@ -65,6 +65,11 @@ pub trait ReadFsmTable {
     fn get_state_machine_features(
         &mut self,
     ) -> impl Future<Output = Result<PersistedStateMachineFeatures>> + Send + '_;
+
+    /// The outbox messages this partition took over from the partition it was split from.
+    /// Defaults to [`AdoptedOutbox::default`] (nothing adopted).
+    /// *Since v1.7.1*
+    fn get_adopted_outbox(&mut self) -> impl Future<Output = Result<AdoptedOutbox>> + Send + '_;
 }
 
 pub trait WriteFsmTable {
@@ -196,3 +201,110 @@ impl StorageDecode for CachedEpochMetadata {
         decode::decode_bilrost(buf)
     }
 }
+
+/// Outbox messages which a partition took over from the partition it was split from. These
+/// messages keep their index and must be sent under the producer id of the partition which
+/// created them, so that receivers deduplicate messages which have already been delivered.
+/// *Since v1.7.1*
+#[derive(Debug, Clone, Default, Eq, PartialEq, bilrost::Message)]
+pub struct AdoptedOutbox {
+    /// Adopted message index ranges, ordered by their end. A range starts at the end of the
+    /// previous range.
+    #[bilrost(tag(1))]
+    pub ranges: Vec<AdoptedOutboxRange>,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, bilrost::Message)]
+pub struct AdoptedOutboxRange {
+    /// The partition which created the messages of this range.
+    #[bilrost(tag(1))]
+    pub producer: PartitionId,
+    /// The exclusive end of the message indexes of this range.
+    #[bilrost(tag(2))]
+    pub end: MessageIndex,
+}
+
+impl AdoptedOutbox {
+    /// The partition which created the adopted outbox message with the given index, if it was
+    /// adopted.
+    pub fn producer_of(&self, index: MessageIndex) -> Option<PartitionId> {
+        self.ranges
+            .iter()
+            .find(|range| index < range.end)
+            .map(|range| range.producer)
+    }
+
+    /// The adopted outbox of a partition split from a partition with the given adopted outbox,
+    /// which takes over the messages of `producer` up to `end`. Ranges which only contain
+    /// already delivered messages (below `head`) are dropped.
+    pub fn adopt(&self, head: MessageIndex, producer: PartitionId, end: MessageIndex) -> Self {
+        let mut ranges: Vec<_> = self
+            .ranges
+            .iter()
+            .filter(|range| range.end > head)
+            .copied()
+            .collect();
+        if ranges.last().is_none_or(|range| range.end < end) {
+            ranges.push(AdoptedOutboxRange { producer, end });
+        }
+        Self { ranges }
+    }
+}
+
+impl StorageEncode for AdoptedOutbox {
+    fn default_codec(&self) -> StorageCodecKind {
+        StorageCodecKind::Bilrost
+    }
+
+    fn encode(&self, buf: &mut BytesMut) -> Result<(), StorageEncodeError> {
+        encode::encode_bilrost(self, buf)
+    }
+}
+
+impl StorageDecode for AdoptedOutbox {
+    fn decode<B: bytes::Buf>(
+        buf: &mut B,
+        kind: StorageCodecKind,
+    ) -> Result<Self, StorageDecodeError>
+    where
+        Self: Sized,
+    {
+        assert_eq!(kind, StorageCodecKind::Bilrost);
+
+        decode::decode_bilrost(buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(producer: u16, end: MessageIndex) -> AdoptedOutboxRange {
+        AdoptedOutboxRange {
+            producer: PartitionId::from(producer),
+            end,
+        }
+    }
+
+    #[test]
+    fn adopt_outbox_of_split_partition() {
+        // partition 1 adopts the outbox of partition 0 with the messages [3, 5)
+        let adopted = AdoptedOutbox::default().adopt(3, PartitionId::from(0), 5);
+        assert_eq!(adopted.ranges, vec![range(0, 5)]);
+
+        // partition 1 is split again after creating the messages [5, 10)
+        let nested = adopted.adopt(4, PartitionId::from(1), 10);
+        assert_eq!(nested.ranges, vec![range(0, 5), range(1, 10)]);
+        assert_eq!(nested.producer_of(4), Some(PartitionId::from(0)));
+        assert_eq!(nested.producer_of(5), Some(PartitionId::from(1)));
+        assert_eq!(nested.producer_of(10), None);
+
+        // the messages of partition 0 were delivered before the split
+        let nested = adopted.adopt(5, PartitionId::from(1), 10);
+        assert_eq!(nested.ranges, vec![range(1, 10)]);
+
+        // partition 1 did not create any messages itself
+        let nested = adopted.adopt(3, PartitionId::from(1), 5);
+        assert_eq!(nested.ranges, vec![range(0, 5)]);
+    }
+}
@@ -1529,6 +1529,14 @@ pub mod v1 {
                         )
                     }
                     source::Source::Internal(_) => restate_types::invocation::Source::Internal,
+                    source::Source::Schedule(schedule) => {
+                        restate_types::invocation::Source::Schedule(
+                            restate_types::identifiers::ScheduleId::from_slice(
+                                &schedule.schedule_id,
+                            )
+                            .map_err(ConversionError::invalid_data)?,
+                        )
+                    }
                 };
 
                 Ok(source)
@@ -1561,6 +1569,11 @@ pub mod v1 {
                         })
                     }
                     restate_types::invocation::Source::Internal => source::Source::Internal(()),
+                    restate_types::invocation::Source::Schedule(schedule_id) => {
+                        source::Source::Schedule(source::Schedule {
+                            schedule_id: schedule_id.to_bytes().to_vec().into(),
+                        })
+                    }
                 };
 
                 Source {
@@ -1595,6 +1608,11 @@ pub mod v1 {
                         })
                     }
                     restate_types::invocation::Source::Internal => source::Source::Internal(()),
+                    restate_types::invocation::Source::Schedule(schedule_id) => {
+                        source::Source::Schedule(source::Schedule {
+                            schedule_id: schedule_id.to_bytes().to_vec().into(),
+                        })
+                    }
                 };
 
                 Source {
@@ -3764,6 +3782,24 @@ pub mod v1 {
                                 )?,
                             )
                         }
+                        timer::Value::ScheduleTick(schedule_tick) => {
+                            crate::timer_table::Timer::ScheduleTick {
+                                schedule_id: restate_types::identifiers::ScheduleId::from_slice(
+                                    &schedule_tick.schedule_id,
+                                )
+                                .map_err(ConversionError::invalid_data)?,
+                                revision: schedule_tick.revision,
+                                invocation_id: restate_types::identifiers::InvocationId::try_from(
+                                    schedule_tick.invocation_id.ok_or_else(|| {
+                                        ConversionError::missing_field("invocation_id")
+                                    })?,
+                                )?,
+                                previous_invocation_id: schedule_tick
+                                    .previous_invocation_id
+                                    .map(restate_types::identifiers::InvocationId::try_from)
+                                    .transpose()?,
+                            }
+                        }
                     },
                 )
             }
@@ -3791,6 +3827,17 @@ pub mod v1 {
                                 invocation_id: Some(InvocationId::from(invocation_id)),
                             })
                         }
+                        crate::timer_table::Timer::ScheduleTick {
+                            schedule_id,
+                            revision,
+                            invocation_id,
+                            previous_invocation_id,
+                        } => timer::Value::ScheduleTick(timer::ScheduleTick {
+                            schedule_id: schedule_id.to_bytes().to_vec().into(),
+                            revision,
+                            invocation_id: Some(InvocationId::from(invocation_id)),
+                            previous_invocation_id: previous_invocation_id.map(InvocationId::from),
+                        }),
                     }),
                 }
             }
@@ -4152,7 +4199,7 @@ pub mod v1 {
         use restate_types::vqueues::VQueueId;
         use restate_types::{
             errors::ConversionError,
-            identifiers::{DeploymentId, InvocationId, SubscriptionId},
+            identifiers::{DeploymentId, InvocationId, ScheduleId, SubscriptionId},
             invocation::ServiceType,
             service_protocol::ServiceProtocolVersion,
         };
@@ -4547,6 +4594,13 @@ pub mod v1 {
             }
         }
 
+        impl super::source::Schedule {
+            pub fn schedule_id(&self) -> std::result::Result<ScheduleId, ConversionError> {
+                ScheduleId::from_slice(self.schedule_id.as_ref())
+                    .map_err(|_| ConversionError::invalid_data_static("schedule_id"))
+            }
+        }
+
         impl super::source::RestartAsNew {
             pub fn invocation_id(&self) -> std::result::Result<InvocationId, ConversionError> {
                 InvocationId::try_from(
@@ -12,7 +12,9 @@ use std::cmp::Ordering;
 
 use futures::Stream;
 
-use restate_types::identifiers::{InvocationId, InvocationUuid, PartitionKey, WithPartitionKey};
+use restate_types::identifiers::{
+    InvocationId, InvocationUuid, PartitionKey, ScheduleId, WithPartitionKey,
+};
 use restate_types::invocation::ServiceInvocation;
 use restate_types::time::MillisSinceEpoch;
 
@@ -65,6 +67,13 @@ impl TimerKey {
             kind: TimerKeyKind::CleanInvocationStatus { invocation_uuid },
         }
     }
+
+    fn schedule_tick(timestamp: u64, invocation_uuid: InvocationUuid) -> Self {
+        TimerKey {
+            timestamp,
+            kind: TimerKeyKind::ScheduleTick { invocation_uuid },
+        }
+    }
 }
 
 impl PartialOrd for TimerKey {
@@ -104,6 +113,8 @@ pub enum TimerKeyKind {
     },
     /// Cleaning of invocation status
     CleanInvocationStatus { invocation_uuid: InvocationUuid },
+    /// Tick of a cron schedule, keyed by the invocation the tick will start
+    ScheduleTick { invocation_uuid: InvocationUuid },
 }
 
 impl TimerKeyKind {
@@ -115,6 +126,7 @@ impl TimerKeyKind {
             } => invocation_uuid,
             TimerKeyKind::CleanInvocationStatus { invocation_uuid } => invocation_uuid,
             TimerKeyKind::NeoInvoke { invocation_uuid } => invocation_uuid,
+            TimerKeyKind::ScheduleTick { invocation_uuid } => invocation_uuid,
         }
     }
 }
@@ -134,7 +146,8 @@ impl Ord for TimerKeyKind {
                 } => invocation_uuid.cmp(other_invocation_uuid),
                 TimerKeyKind::CompleteJournalEntry { .. }
                 | TimerKeyKind::CleanInvocationStatus { .. }
-                | TimerKeyKind::NeoInvoke { .. } => Ordering::Less,
+                | TimerKeyKind::NeoInvoke { .. }
+                | TimerKeyKind::ScheduleTick { .. } => Ordering::Less,
             },
             TimerKeyKind::CompleteJournalEntry {
                 invocation_uuid,
@@ -147,9 +160,9 @@ impl Ord for TimerKeyKind {
                 } => invocation_uuid
                     .cmp(other_invocation_uuid)
                     .then_with(|| journal_index.cmp(other_journal_index)),
-                TimerKeyKind::CleanInvocationStatus { .. } | TimerKeyKind::NeoInvoke { .. } => {
-                    Ordering::Less
-                }
+                TimerKeyKind::CleanInvocationStatus { .. }
+                | TimerKeyKind::NeoInvoke { .. }
+                | TimerKeyKind::ScheduleTick { .. } => Ordering::Less,
             },
             TimerKeyKind::CleanInvocationStatus { invocation_uuid } => match other {
                 TimerKeyKind::Invoke { .. } | TimerKeyKind::CompleteJournalEntry { .. } => {
@@ -158,7 +171,9 @@ impl Ord for TimerKeyKind {
                 TimerKeyKind::CleanInvocationStatus {
                     invocation_uuid: other_invocation_uuid,
                 } => invocation_uuid.cmp(other_invocation_uuid),
-                TimerKeyKind::NeoInvoke { .. } => Ordering::Less,
+                TimerKeyKind::NeoInvoke { .. } | TimerKeyKind::ScheduleTick { .. } => {
+                    Ordering::Less
+                }
             },
             TimerKeyKind::NeoInvoke { invocation_uuid } => match other {
                 TimerKeyKind::Invoke { .. }
@@ -167,6 +182,16 @@ impl Ord for TimerKeyKind {
                 TimerKeyKind::NeoInvoke {
                     invocation_uuid: other_invocation_uuid,
                 } => invocation_uuid.cmp(other_invocation_uuid),
+                TimerKeyKind::ScheduleTick { .. } => Ordering::Less,
+            },
+            TimerKeyKind::ScheduleTick { invocation_uuid } => match other {
+                TimerKeyKind::Invoke { .. }
+                | TimerKeyKind::CompleteJournalEntry { .. }
+                | TimerKeyKind::CleanInvocationStatus { .. }
+                | TimerKeyKind::NeoInvoke { .. } => Ordering::Greater,
+                TimerKeyKind::ScheduleTick {
+                    invocation_uuid: other_invocation_uuid,
+                } => invocation_uuid.cmp(other_invocation_uuid),
             },
         }
     }
@@ -191,6 +216,15 @@ pub enum Timer {
     // TODO remove this variant when removing the old invocation status table
     CleanInvocationStatus(InvocationId),
     NeoInvoke(InvocationId),
+    ScheduleTick {
+        schedule_id: ScheduleId,
+        /// Revision of the schedule this tick was registered for
+        revision: u32,
+        /// Invocation started by this tick
+        invocation_id: InvocationId,
+        /// Invocation started by the last executed tick, used to enforce the overlap policy
+        previous_invocation_id: Option<InvocationId>,
+    },
 }
 
 impl Timer {
@@ -236,12 +270,31 @@ impl Timer {
         )
     }
 
+    pub fn schedule_tick(
+        timestamp: u64,
+        schedule_id: ScheduleId,
+        revision: u32,
+        invocation_id: InvocationId,
+        previous_invocation_id: Option<InvocationId>,
+    ) -> (TimerKey, Self) {
+        (
+            TimerKey::schedule_tick(timestamp, invocation_id.invocation_uuid()),
+            Timer::ScheduleTick {
+                schedule_id,
+                revision,
+                invocation_id,
+                previous_invocation_id,
+            },
+        )
+    }
+
     pub fn invocation_id(&self) -> InvocationId {
         match self {
             Timer::Invoke(service_invocation) => service_invocation.invocation_id,
             Timer::CompleteJournalEntry(invocation_id, _) => *invocation_id,
             Timer::CleanInvocationStatus(invocation_id) => *invocation_id,
             Timer::NeoInvoke(invocation_id) => *invocation_id,
+            Timer::ScheduleTick { invocation_id, .. } => *invocation_id,
         }
     }
 }
@@ -257,6 +310,7 @@ impl WithPartitionKey for Timer {
             Timer::Invoke(service_invocation) => service_invocation.partition_key(),
             Timer::CleanInvocationStatus(invocation_id) => invocation_id.partition_key(),
             Timer::NeoInvoke(invocation_id) => invocation_id.partition_key(),
+            Timer::ScheduleTick { invocation_id, .. } => invocation_id.partition_key(),
         }
     }
 }
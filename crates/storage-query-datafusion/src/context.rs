@@ -64,6 +64,7 @@ const SYS_INVOCATION_VIEW: &str = "CREATE VIEW sys_invocation as SELECT
             ss.invoked_by_service_name,
             ss.invoked_by_id,
             ss.invoked_by_subscription_id,
+            ss.invoked_by_schedule_id,
             ss.invoked_by_target,
             ss.restarted_from,
             ss.pinned_deployment_id,
@@ -376,6 +377,18 @@ impl RegisterTable for ClusterTables {
             self.remote_scanner_manager.clone(),
             None, // local scanner is registered separately by the node
         )?;
+        crate::kafka_consumer_lag::register_self(
+            ctx,
+            metadata.clone(),
+            self.remote_scanner_manager.clone(),
+            None, // local scanner is registered separately if this node is also a worker
+        )?;
+        crate::deployment_circuit_breakers::register_self(
+            ctx,
+            metadata.clone(),
+            self.remote_scanner_manager.clone(),
+            None, // local scanner is registered separately if this node is also a worker
+        )?;
 
         if !Configuration::pinned().common.disable_config_sql_table {
             crate::config::register_self(
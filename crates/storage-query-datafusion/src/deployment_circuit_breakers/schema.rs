@@ -0,0 +1,27 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use datafusion::arrow::datatypes::DataType;
+
+use crate::table_macro::*;
+
+define_table!(
+    /// Circuit breakers of the deployments which recently failed, per worker node in the cluster.
+    deployment_circuit_breakers(
+        /// The PlainNodeId of the node tracking the circuit breaker.
+        plain_node_id: DataType::Utf8,
+        /// Current known generation ID of the node.
+        gen_node_id: DataType::Utf8,
+        /// The ID of the deployment.
+        deployment_id: DataType::Utf8,
+        /// The state of the circuit breaker: `closed`, `open` or `half-open`.
+        state: DataType::Utf8,
+    )
+);
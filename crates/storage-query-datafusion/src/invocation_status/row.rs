@@ -303,6 +303,7 @@ fn needs_invoked_by(row: &SysInvocationStatusRowBuilder) -> bool {
         || row.is_invoked_by_id_defined()
         || row.is_invoked_by_target_defined()
         || row.is_invoked_by_subscription_id_defined()
+        || row.is_invoked_by_schedule_id_defined()
         || row.is_restarted_from_defined()
 }
 
@@ -347,6 +348,12 @@ fn fill_invoked_by(
                 row.fmt_restarted_from(restart_as_new.invocation_id()?)
             }
         }
+        Source::Schedule(schedule) => {
+            row.invoked_by("schedule");
+            if row.is_invoked_by_schedule_id_defined() {
+                row.fmt_invoked_by_schedule_id(schedule.schedule_id()?)
+            }
+        }
     }
 
     Ok(())
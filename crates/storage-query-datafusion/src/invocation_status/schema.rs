@@ -67,6 +67,7 @@ define_table!(sys_invocation_status(
     /// * `service` if the invocation was created by another Restate service.
     /// * `subscription` if the invocation was created by a subscription (e.g. Kafka).
     /// * `restart_as_new` if the invocation was created by restarting an old invocation as new.
+    /// * `schedule` if the invocation was created by a tick of a cron schedule.
     invoked_by: DataType::LargeUtf8,
 
     /// The caller [Invocation ID](/operate/invocation#invocation-identifier) if `invoked_by = 'service'`.
@@ -75,6 +76,9 @@ define_table!(sys_invocation_status(
     /// The subscription id if `invoked_by = 'subscription'`.
     invoked_by_subscription_id: DataType::LargeUtf8,
 
+    /// The schedule id if `invoked_by = 'schedule'`.
+    invoked_by_schedule_id: DataType::LargeUtf8,
+
     /// The name of caller service if `invoked_by = 'service'`.
     invoked_by_service_name: DataType::LargeUtf8,
 
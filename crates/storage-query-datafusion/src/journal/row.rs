@@ -189,7 +189,7 @@ pub(crate) fn append_journal_row_v2(
         row.entry_lite_json(entry_lite_json);
     }
 
-    if row.is_entry_json_defined() || row.is_name_defined() {
+    if row.is_entry_json_defined() || row.is_name_defined() || row.is_sleep_wakeup_at_defined() {
         // We need to parse the entry
         let Ok(entry) = raw_entry.decode::<ServiceProtocolV4Codec, journal_v2::Entry>() else {
             log_data_corruption_error!(
@@ -206,6 +206,11 @@ pub(crate) fn append_journal_row_v2(
         {
             row.entry_json(json);
         }
+        if row.is_sleep_wakeup_at_defined()
+            && let journal_v2::Entry::Command(journal_v2::Command::Sleep(sleep)) = &entry
+        {
+            row.sleep_wakeup_at(sleep.wake_up_time.as_u64() as i64);
+        }
         if row.is_name_defined()
             && let journal_v2::Entry::Command(cmd) = entry
         {
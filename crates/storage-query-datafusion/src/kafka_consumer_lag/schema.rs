@@ -0,0 +1,37 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use datafusion::arrow::datatypes::DataType;
+
+use crate::table_macro::*;
+
+define_table!(
+    /// Consumption progress of the Kafka partitions assigned to each worker node in the cluster.
+    kafka_consumer_lag(
+        /// The PlainNodeId of the node consuming the partition.
+        plain_node_id: DataType::Utf8,
+        /// Current known generation ID of the node.
+        gen_node_id: DataType::Utf8,
+        /// The ID of the subscription consuming the partition.
+        subscription_id: DataType::Utf8,
+        /// The Kafka consumer group of the subscription.
+        consumer_group: DataType::Utf8,
+        /// The Kafka topic.
+        topic: DataType::Utf8,
+        /// The partition of the topic.
+        partition: DataType::Int32,
+        /// Offset of the next record to ingest. Only set once a record has been ingested.
+        next_offset: DataType::UInt64,
+        /// High watermark of the partition, as last reported by the broker.
+        high_watermark: DataType::UInt64,
+        /// Number of records produced to the partition which haven't been ingested yet.
+        lag: DataType::UInt64,
+    )
+);
@@ -15,11 +15,13 @@ pub mod remote_query_scanner_server;
 pub mod bifrost_read_stream;
 pub mod config;
 mod deployment;
+pub mod deployment_circuit_breakers;
 mod inbox;
 mod invocation_state;
 mod invocation_status;
 mod journal;
 mod journal_events;
+pub mod kafka_consumer_lag;
 mod keyed_service_status;
 mod locks;
 mod log;
@@ -23,6 +23,12 @@ pub(crate) fn append_rule_row(
     if let Some(concurrency) = rule.limits.concurrency {
         row.concurrency(concurrency.get());
     }
+    if let Some(rate) = &rule.limits.rate {
+        row.fmt_rate(rate);
+    }
+    if let Some(burst) = rule.limits.burst {
+        row.burst(burst.get());
+    }
     if let Some(description) = rule.description.as_deref() {
         row.description(description);
     }
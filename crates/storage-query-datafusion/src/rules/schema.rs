@@ -20,6 +20,14 @@ define_table!(sys_rules(
     /// rule does not constrain concurrency.
     concurrency: DataType::UInt32,
 
+    /// Rate limit imposed by this rule, e.g. `100/s`. Null means the
+    /// rule does not constrain the rate.
+    rate: DataType::Utf8,
+
+    /// Token bucket capacity of the rate limit. Null means the burst
+    /// defaults to the number of invocations allowed per rate period.
+    burst: DataType::UInt32,
+
     /// Free-form description set by the operator.
     description: DataType::Utf8,
 
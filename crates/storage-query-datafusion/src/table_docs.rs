@@ -147,6 +147,9 @@ pub fn sys_invocation_table_docs() -> OwnedTableDocs {
         sys_invocation_status
             .remove("invoked_by_subscription_id")
             .expect("invoked_by_subscription_id should exist"),
+        sys_invocation_status
+            .remove("invoked_by_schedule_id")
+            .expect("invoked_by_schedule_id should exist"),
         sys_invocation_status
             .remove("invoked_by_target")
             .expect("invoked_by_target should exist"),
@@ -46,6 +46,21 @@ pub(super) fn append_user_limit_row(
     {
         row.concurrency_limit(limit);
     }
+    if row.is_rate_limit_defined()
+        && let Some(rate) = &entry.rate_limit
+    {
+        row.fmt_rate_limit(rate);
+    }
+    if row.is_rate_burst_defined()
+        && let Some(burst) = entry.rate_burst
+    {
+        row.rate_burst(burst);
+    }
+    if row.is_available_tokens_defined()
+        && let Some(tokens) = entry.available_tokens
+    {
+        row.available_tokens(tokens);
+    }
     if row.is_rule_pattern_defined()
         && let Some(pattern) = &entry.rule_pattern
     {
@@ -59,6 +74,9 @@ pub(super) fn append_user_limit_row(
     if row.is_num_waiters_defined() {
         row.num_waiters(entry.num_waiters);
     }
+    if row.is_num_rate_waiters_defined() {
+        row.num_rate_waiters(entry.num_rate_waiters);
+    }
 }
 
 fn level_name(level: Level) -> &'static str {
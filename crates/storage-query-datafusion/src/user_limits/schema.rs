@@ -36,6 +36,17 @@ define_table!(sys_user_limits(
     /// The configured concurrency limit (null if unlimited).
     concurrency_limit: DataType::UInt32,
 
+    /// The configured rate limit, e.g. `100/s` (null if unlimited).
+    rate_limit: DataType::Utf8,
+
+    /// The capacity of the token bucket enforcing the rate limit (null if
+    /// the rate is unlimited).
+    rate_burst: DataType::UInt32,
+
+    /// Whole tokens currently available in the token bucket (null if the
+    /// rate is unlimited).
+    available_tokens: DataType::UInt32,
+
     /// The rule pattern that defines the limit (null if unlimited).
     /// Resolved from the rule handle; shows "[removed]" if the rule was
     /// deleted since the counter was created.
@@ -44,6 +55,9 @@ define_table!(sys_user_limits(
     /// Available capacity (limit - usage). Null if unlimited.
     available: DataType::UInt32,
 
-    /// Number of vqueues currently waiting behind this counter.
+    /// Number of vqueues currently waiting behind this counter for concurrency.
     num_waiters: DataType::UInt64,
+
+    /// Number of vqueues currently waiting behind this counter for rate tokens.
+    num_rate_waiters: DataType::UInt64,
 ));
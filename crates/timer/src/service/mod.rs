@@ -315,6 +315,12 @@ where
         }
     }
 
+    /// The timers kept in memory, in no particular order. Without a memory limit, these are all
+    /// the timers once they were loaded from storage.
+    pub fn timers(&self) -> impl Iterator<Item = &Timer> {
+        self.timer_queue.iter().map(|(timer, _)| timer)
+    }
+
     fn adjust_timer_batch_end(
         timer_queue: &mut DoublePriorityQueue<Timer>,
         max_fired_timer: &mut Option<Timer::TimerKey>,
@@ -472,3 +472,33 @@ async fn delete_loading_timer() {
         TimerValue::new(2, MillisSinceEpoch::from(2))
     );
 }
+
+#[test(tokio::test)]
+async fn list_and_remove_pending_timers() {
+    let mut clock = ManualClock::new(MillisSinceEpoch::UNIX_EPOCH);
+    let timer_reader = MockTimerReader::<TimerValue>::new();
+    timer_reader.add_timer(TimerValue::new(0, MillisSinceEpoch::from(1)));
+
+    let service = TimerService::new(clock.clone(), None, timer_reader.clone());
+    tokio::pin!(service);
+    assert!(service.as_mut().next_timer().now_or_never().is_none());
+
+    let later_timer = TimerValue::new(1, MillisSinceEpoch::from(10));
+    service.as_mut().add_timer(later_timer);
+    let mut timers: Vec<_> = service.timers().copied().collect();
+    timers.sort();
+    assert_eq!(
+        timers,
+        vec![TimerValue::new(0, MillisSinceEpoch::from(1)), later_timer]
+    );
+
+    // a timer taken out of the service to fire it ahead of time doesn't fire again
+    service.as_mut().remove_timer(later_timer);
+    clock.advance_time_to(MillisSinceEpoch::from(10));
+    assert_eq!(
+        service.as_mut().next_timer().await,
+        TimerValue::new(0, MillisSinceEpoch::from(1))
+    );
+    assert!(service.as_mut().next_timer().now_or_never().is_none());
+    assert_eq!(service.timers().count(), 0);
+}
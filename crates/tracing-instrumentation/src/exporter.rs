@@ -163,4 +163,40 @@ impl ExporterBuilder {
                 .map_err(|e| super::bad_endpoint(format!("build HTTP exporter: {e}")))?),
         }
     }
+
+    #[cfg(feature = "prometheus")]
+    pub fn build_metric_exporter(
+        &self,
+        temporality: opentelemetry_sdk::metrics::Temporality,
+    ) -> Result<opentelemetry_otlp::MetricExporter, super::Error> {
+        match self {
+            ExporterBuilder::Tonic {
+                metadata,
+                channel,
+                protocol,
+            } => Ok(opentelemetry_otlp::MetricExporter::builder()
+                .with_temporality(temporality)
+                .with_tonic()
+                .with_channel(channel.clone())
+                .with_metadata(metadata.clone())
+                .with_protocol(*protocol)
+                .build()
+                .map_err(|e| super::bad_endpoint(format!("build gRPC metric exporter: {e}")))?),
+
+            ExporterBuilder::Http {
+                client,
+                headers,
+                protocol,
+                endpoint,
+            } => Ok(opentelemetry_otlp::MetricExporter::builder()
+                .with_temporality(temporality)
+                .with_http()
+                .with_http_client(client.clone())
+                .with_protocol(*protocol)
+                .with_headers(headers.clone())
+                .with_endpoint(endpoint.to_string())
+                .build()
+                .map_err(|e| super::bad_endpoint(format!("build HTTP metric exporter: {e}")))?),
+        }
+    }
 }
@@ -9,6 +9,8 @@
 // by the Apache License, Version 2.0.
 
 mod exporter;
+#[cfg(feature = "prometheus")]
+mod otlp_metrics;
 mod pretty;
 #[cfg(feature = "prometheus")]
 pub mod prometheus_metrics;
@@ -0,0 +1,541 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use metrics::{Counter, Gauge, Histogram, Key, KeyName, Metadata, Recorder, SharedString, Unit};
+use metrics_util::registry::{AtomicStorage, Registry};
+use opentelemetry::metrics::{Meter, MeterProvider as _};
+use opentelemetry::{InstrumentationScope, KeyValue};
+use opentelemetry_sdk::metrics::{SdkMeterProvider, Temporality};
+use opentelemetry_sdk::runtime;
+use parking_lot::{Mutex, RwLock};
+
+use restate_types::config::{CommonOptions, MetricsTemporality, OtlpMetricsOptions};
+
+use crate::{Error, ExporterBuilder, semconv};
+
+/// Bucket boundaries of histograms measured in seconds. The OpenTelemetry defaults are meant
+/// for milliseconds.
+const SECONDS_BOUNDARIES: &[f64] = &[
+    0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0,
+];
+
+/// Recorder keeping the metric values to push over OTLP.
+///
+/// The values are pushed into the OpenTelemetry SDK by the upkeep task. This allows installing
+/// the recorder before the tokio runtime, which the OTLP exporters need, is running.
+#[derive(Clone, Default)]
+pub(crate) struct OtlpRecorder {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    registry: Registry<Key, AtomicStorage>,
+    descriptions: RwLock<HashMap<String, Description>>,
+}
+
+impl Default for Inner {
+    fn default() -> Self {
+        Self {
+            registry: Registry::atomic(),
+            descriptions: RwLock::default(),
+        }
+    }
+}
+
+struct Description {
+    unit: Option<Unit>,
+    description: SharedString,
+}
+
+impl OtlpRecorder {
+    #[cfg(test)]
+    pub(crate) fn counter_value(&self, name: &str) -> Option<u64> {
+        let mut value = None;
+        self.inner.registry.visit_counters(|key, counter| {
+            if key.name() == name {
+                value = Some(counter.load(Ordering::Acquire));
+            }
+        });
+        value
+    }
+}
+
+impl Inner {
+    fn describe(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+        self.descriptions
+            .write()
+            .insert(key.as_str().to_owned(), Description { unit, description });
+    }
+}
+
+impl Recorder for OtlpRecorder {
+    fn describe_counter(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+        self.inner.describe(key, unit, description)
+    }
+
+    fn describe_gauge(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+        self.inner.describe(key, unit, description)
+    }
+
+    fn describe_histogram(&self, key: KeyName, unit: Option<Unit>, description: SharedString) {
+        self.inner.describe(key, unit, description)
+    }
+
+    fn register_counter(&self, key: &Key, _metadata: &Metadata<'_>) -> Counter {
+        self.inner
+            .registry
+            .get_or_create_counter(key, |counter| Counter::from_arc(counter.clone()))
+    }
+
+    fn register_gauge(&self, key: &Key, _metadata: &Metadata<'_>) -> Gauge {
+        self.inner
+            .registry
+            .get_or_create_gauge(key, |gauge| Gauge::from_arc(gauge.clone()))
+    }
+
+    fn register_histogram(&self, key: &Key, _metadata: &Metadata<'_>) -> Histogram {
+        self.inner
+            .registry
+            .get_or_create_histogram(key, |histogram| Histogram::from_arc(histogram.clone()))
+    }
+}
+
+/// Pushes the metrics recorded by [`OtlpRecorder`] to an OTLP endpoint.
+pub(crate) struct OtlpMetrics {
+    recorder: OtlpRecorder,
+    options: OtlpMetricsOptions,
+    global_labels: Vec<KeyValue>,
+    service_instance_id: String,
+    upkeep: Option<Arc<Mutex<OtlpUpkeep>>>,
+}
+
+impl OtlpMetrics {
+    /// Returns `None` unless an OTLP metrics endpoint is configured.
+    pub(crate) fn new(opts: &CommonOptions) -> Option<(Self, OtlpRecorder)> {
+        opts.otlp_metrics.metrics_otlp_endpoint.as_ref()?;
+
+        let recorder = OtlpRecorder::default();
+        let this = Self {
+            recorder: recorder.clone(),
+            options: opts.otlp_metrics.clone(),
+            global_labels: vec![
+                KeyValue::new("cluster_name", opts.cluster_name().to_owned()),
+                KeyValue::new("node_name", opts.node_name().to_owned()),
+            ],
+            service_instance_id: format!("{}/{}", opts.cluster_name(), opts.node_name()),
+            upkeep: None,
+        };
+        Some((this, recorder))
+    }
+
+    /// How often the metrics are pushed. The upkeep task copies the recorded values at the same
+    /// pace.
+    pub(crate) fn export_interval(&self) -> Duration {
+        self.options.metrics_otlp_export_interval.into()
+    }
+
+    /// Starts pushing metrics if not started yet, and returns the state of the upkeep task.
+    ///
+    /// Must be called from within a tokio runtime.
+    pub(crate) fn start(&mut self) -> Result<Arc<Mutex<OtlpUpkeep>>, Error> {
+        if let Some(upkeep) = &self.upkeep {
+            return Ok(Arc::clone(upkeep));
+        }
+
+        let endpoint = self
+            .options
+            .metrics_otlp_endpoint
+            .as_ref()
+            .expect("OTLP metrics endpoint is set");
+        let exporter = ExporterBuilder::new(endpoint, self.options.metrics_otlp_headers.clone())?
+            .build_metric_exporter(temporality(self.options.metrics_otlp_temporality))?;
+
+        let resource = opentelemetry_sdk::Resource::builder_empty()
+            .with_attributes(crate::otel_resource_attributes_from_env())
+            .with_attributes(vec![
+                KeyValue::new(semconv::resource::SERVICE_NAME, "Restate"),
+                KeyValue::new(semconv::resource::SERVICE_NAMESPACE, "Restate"),
+                KeyValue::new(
+                    semconv::resource::SERVICE_INSTANCE_ID,
+                    self.service_instance_id.clone(),
+                ),
+                KeyValue::new(
+                    semconv::resource::SERVICE_VERSION,
+                    env!("CARGO_PKG_VERSION"),
+                ),
+            ])
+            .build();
+
+        // Like the span processor, the reader runs on the tokio runtime because the exporters
+        // use async clients.
+        let reader =
+            opentelemetry_sdk::metrics::periodic_reader_with_async_runtime::PeriodicReader::builder(
+                exporter,
+                runtime::Tokio,
+            )
+            .with_interval(self.export_interval())
+            .build();
+
+        let provider = SdkMeterProvider::builder()
+            .with_resource(resource)
+            .with_reader(reader)
+            .build();
+
+        let upkeep = Arc::new(Mutex::new(OtlpUpkeep::new(
+            self.recorder.clone(),
+            self.global_labels.clone(),
+            provider,
+        )));
+        self.upkeep = Some(Arc::clone(&upkeep));
+        Ok(upkeep)
+    }
+
+    /// Pushes the values recorded since the last upkeep and stops pushing metrics.
+    pub(crate) fn shutdown(&self) {
+        if let Some(upkeep) = &self.upkeep {
+            upkeep.lock().shutdown();
+        }
+    }
+}
+
+/// Copies the recorded values into the OpenTelemetry instruments.
+pub(crate) struct OtlpUpkeep {
+    recorder: OtlpRecorder,
+    global_labels: Vec<KeyValue>,
+    provider: SdkMeterProvider,
+    meter: Meter,
+    counters: HashMap<Key, ExportedCounter>,
+    gauges: HashMap<Key, Exported<opentelemetry::metrics::Gauge<f64>>>,
+    histograms: HashMap<Key, Exported<opentelemetry::metrics::Histogram<f64>>>,
+}
+
+struct Exported<I> {
+    instrument: I,
+    attributes: Vec<KeyValue>,
+}
+
+struct ExportedCounter {
+    exported: Exported<opentelemetry::metrics::Counter<u64>>,
+    /// Value of the counter at the previous upkeep, the instrument is incremented by the difference.
+    last_value: u64,
+}
+
+impl OtlpUpkeep {
+    fn new(
+        recorder: OtlpRecorder,
+        global_labels: Vec<KeyValue>,
+        provider: SdkMeterProvider,
+    ) -> Self {
+        let meter = provider.meter_with_scope(
+            InstrumentationScope::builder("restate")
+                .with_version(env!("CARGO_PKG_VERSION"))
+                .build(),
+        );
+        Self {
+            recorder,
+            global_labels,
+            provider,
+            meter,
+            counters: HashMap::default(),
+            gauges: HashMap::default(),
+            histograms: HashMap::default(),
+        }
+    }
+
+    pub(crate) fn run_upkeep(&mut self) {
+        let inner = Arc::clone(&self.recorder.inner);
+        let descriptions = inner.descriptions.read();
+
+        inner.registry.visit_counters(|key, counter| {
+            let value = counter.load(Ordering::Acquire);
+            if !self.counters.contains_key(key) {
+                let mut builder = self.meter.u64_counter(key.name().to_owned());
+                if let Some(description) = descriptions.get(key.name()) {
+                    builder = builder.with_description(description.description.to_string());
+                    if let Some(unit) = description.unit {
+                        builder = builder.with_unit(otel_unit(unit));
+                    }
+                }
+                let exported = ExportedCounter {
+                    exported: Exported {
+                        instrument: builder.build(),
+                        attributes: attributes(key, &self.global_labels),
+                    },
+                    last_value: 0,
+                };
+                self.counters.insert(key.clone(), exported);
+            }
+            let counter = self.counters.get_mut(key).expect("counter was inserted");
+
+            let delta = value.saturating_sub(counter.last_value);
+            counter.last_value = value;
+            counter
+                .exported
+                .instrument
+                .add(delta, &counter.exported.attributes);
+        });
+
+        inner.registry.visit_gauges(|key, gauge| {
+            let value = f64::from_bits(gauge.load(Ordering::Acquire));
+            if !self.gauges.contains_key(key) {
+                let mut builder = self.meter.f64_gauge(key.name().to_owned());
+                if let Some(description) = descriptions.get(key.name()) {
+                    builder = builder.with_description(description.description.to_string());
+                    if let Some(unit) = description.unit {
+                        builder = builder.with_unit(otel_unit(unit));
+                    }
+                }
+                let exported = Exported {
+                    instrument: builder.build(),
+                    attributes: attributes(key, &self.global_labels),
+                };
+                self.gauges.insert(key.clone(), exported);
+            }
+            let gauge = &self.gauges[key];
+            gauge.instrument.record(value, &gauge.attributes);
+        });
+
+        inner.registry.visit_histograms(|key, bucket| {
+            if !self.histograms.contains_key(key) {
+                let mut builder = self.meter.f64_histogram(key.name().to_owned());
+                if let Some(description) = descriptions.get(key.name()) {
+                    builder = builder.with_description(description.description.to_string());
+                    if let Some(unit) = description.unit {
+                        builder = builder.with_unit(otel_unit(unit));
+                        if unit == Unit::Seconds {
+                            builder = builder.with_boundaries(SECONDS_BOUNDARIES.to_vec());
+                        }
+                    }
+                }
+                let exported = Exported {
+                    instrument: builder.build(),
+                    attributes: attributes(key, &self.global_labels),
+                };
+                self.histograms.insert(key.clone(), exported);
+            }
+            let histogram = &self.histograms[key];
+            bucket.clear_with(|samples| {
+                for sample in samples {
+                    histogram.instrument.record(*sample, &histogram.attributes);
+                }
+            });
+        });
+    }
+
+    fn shutdown(&mut self) {
+        self.run_upkeep();
+        if let Err(err) = self.provider.shutdown() {
+            tracing::debug!("Failed to shut down the OTLP metrics exporter: {err}");
+        }
+    }
+}
+
+fn temporality(temporality: MetricsTemporality) -> Temporality {
+    match temporality {
+        MetricsTemporality::Cumulative => Temporality::Cumulative,
+        MetricsTemporality::Delta => Temporality::Delta,
+    }
+}
+
+fn attributes(key: &Key, global_labels: &[KeyValue]) -> Vec<KeyValue> {
+    global_labels
+        .iter()
+        .cloned()
+        .chain(
+            key.labels()
+                .map(|label| KeyValue::new(label.key().to_owned(), label.value().to_owned())),
+        )
+        .collect()
+}
+
+/// Maps units to the [UCUM](https://ucum.org/) codes used by OpenTelemetry.
+fn otel_unit(unit: Unit) -> &'static str {
+    match unit {
+        Unit::Count => "1",
+        Unit::Percent => "%",
+        Unit::Seconds => "s",
+        Unit::Milliseconds => "ms",
+        Unit::Microseconds => "us",
+        Unit::Nanoseconds => "ns",
+        Unit::Tebibytes => "TiBy",
+        Unit::Gibibytes => "GiBy",
+        Unit::Mebibytes => "MiBy",
+        Unit::Kibibytes => "KiBy",
+        Unit::Bytes => "By",
+        Unit::TerabitsPerSecond => "Tbit/s",
+        Unit::GigabitsPerSecond => "Gbit/s",
+        Unit::MegabitsPerSecond => "Mbit/s",
+        Unit::KilobitsPerSecond => "kbit/s",
+        Unit::BitsPerSecond => "bit/s",
+        Unit::CountPerSecond => "1/s",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use opentelemetry_sdk::metrics::data::{
+        AggregatedMetrics, HistogramDataPoint, MetricData, ResourceMetrics, SumDataPoint,
+    };
+    use opentelemetry_sdk::metrics::{
+        InMemoryMetricExporter, InMemoryMetricExporterBuilder, PeriodicReader,
+    };
+
+    use super::*;
+
+    struct TestUpkeep {
+        recorder: OtlpRecorder,
+        exporter: InMemoryMetricExporter,
+        upkeep: OtlpUpkeep,
+    }
+
+    impl TestUpkeep {
+        fn new(metrics_temporality: MetricsTemporality) -> Self {
+            let exporter = InMemoryMetricExporterBuilder::new()
+                .with_temporality(temporality(metrics_temporality))
+                .build();
+            let provider = SdkMeterProvider::builder()
+                .with_reader(PeriodicReader::builder(exporter.clone()).build())
+                .build();
+            let recorder = OtlpRecorder::default();
+            let upkeep = OtlpUpkeep::new(
+                recorder.clone(),
+                vec![
+                    KeyValue::new("cluster_name", "test-cluster"),
+                    KeyValue::new("node_name", "n1"),
+                ],
+                provider,
+            );
+            Self {
+                recorder,
+                exporter,
+                upkeep,
+            }
+        }
+
+        fn record(&self, f: impl FnOnce()) {
+            metrics::with_local_recorder(&self.recorder, f)
+        }
+
+        /// Runs the upkeep and returns what the exporter pushes afterwards.
+        fn export(&mut self) -> ResourceMetrics {
+            self.upkeep.run_upkeep();
+            self.exporter.reset();
+            self.upkeep.provider.force_flush().unwrap();
+            self.exporter
+                .get_finished_metrics()
+                .unwrap()
+                .pop()
+                .expect("metrics were exported")
+        }
+    }
+
+    fn find<'a>(metrics: &'a ResourceMetrics, name: &str) -> &'a AggregatedMetrics {
+        metrics
+            .scope_metrics()
+            .flat_map(|scope| scope.metrics())
+            .find(|metric| metric.name() == name)
+            .unwrap_or_else(|| panic!("metric {name} was exported"))
+            .data()
+    }
+
+    fn sum_point<'a>(metrics: &'a ResourceMetrics, name: &str) -> &'a SumDataPoint<u64> {
+        let AggregatedMetrics::U64(MetricData::Sum(sum)) = find(metrics, name) else {
+            panic!("{name} is not a u64 sum");
+        };
+        sum.data_points().next().expect("one data point")
+    }
+
+    fn histogram_point<'a>(
+        metrics: &'a ResourceMetrics,
+        name: &str,
+    ) -> &'a HistogramDataPoint<f64> {
+        let AggregatedMetrics::F64(MetricData::Histogram(histogram)) = find(metrics, name) else {
+            panic!("{name} is not a f64 histogram");
+        };
+        histogram.data_points().next().expect("one data point")
+    }
+
+    #[test]
+    fn counter_is_incremented_by_the_difference_since_last_upkeep() {
+        let mut test = TestUpkeep::new(MetricsTemporality::Cumulative);
+        let counter = test.record(|| metrics::counter!("restate_test_total"));
+
+        counter.increment(5);
+        assert_eq!(5, sum_point(&test.export(), "restate_test_total").value());
+
+        // nothing recorded in between, the counter must not be incremented again
+        assert_eq!(5, sum_point(&test.export(), "restate_test_total").value());
+
+        counter.increment(3);
+        assert_eq!(8, sum_point(&test.export(), "restate_test_total").value());
+    }
+
+    #[test]
+    fn delta_temporality_exports_the_increments() {
+        let mut test = TestUpkeep::new(MetricsTemporality::Delta);
+        let counter = test.record(|| metrics::counter!("restate_test_total"));
+
+        counter.increment(5);
+        assert_eq!(5, sum_point(&test.export(), "restate_test_total").value());
+
+        counter.increment(3);
+        assert_eq!(3, sum_point(&test.export(), "restate_test_total").value());
+    }
+
+    #[test]
+    fn histogram_samples_are_drained() {
+        let mut test = TestUpkeep::new(MetricsTemporality::Cumulative);
+        let histogram = test.record(|| {
+            metrics::describe_histogram!("restate_test_seconds", Unit::Seconds, "test latency");
+            metrics::histogram!("restate_test_seconds")
+        });
+
+        histogram.record(0.1);
+        histogram.record(0.2);
+        let metrics = test.export();
+        let point = histogram_point(&metrics, "restate_test_seconds");
+        assert_eq!(2, point.count());
+        assert!((point.sum() - 0.3).abs() < f64::EPSILON);
+        assert_eq!(SECONDS_BOUNDARIES, point.bounds().collect::<Vec<_>>());
+
+        // the samples were recorded once, the next upkeep must not record them again
+        let metrics = test.export();
+        assert_eq!(2, histogram_point(&metrics, "restate_test_seconds").count());
+
+        histogram.record(0.3);
+        let metrics = test.export();
+        assert_eq!(3, histogram_point(&metrics, "restate_test_seconds").count());
+    }
+
+    #[test]
+    fn global_labels_are_added_to_the_attributes() {
+        let mut test = TestUpkeep::new(MetricsTemporality::Cumulative);
+        test.record(|| metrics::counter!("restate_test_total", "partition" => "7").increment(1));
+
+        let metrics = test.export();
+        let attributes: Vec<_> = sum_point(&metrics, "restate_test_total")
+            .attributes()
+            .map(|kv| (kv.key.as_str().to_owned(), kv.value.as_str().into_owned()))
+            .collect();
+        assert_eq!(
+            vec![
+                ("cluster_name".to_owned(), "test-cluster".to_owned()),
+                ("node_name".to_owned(), "n1".to_owned()),
+                ("partition".to_owned(), "7".to_owned()),
+            ],
+            attributes
+        );
+    }
+}
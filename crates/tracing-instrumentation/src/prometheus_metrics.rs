@@ -8,32 +8,49 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::time::Duration;
+
 use indexmap::IndexMap;
 use metrics_exporter_prometheus::formatting;
-use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
+use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle, PrometheusRecorder};
+use metrics_util::layers::{Fanout, FanoutBuilder};
 use tokio::task::AbortHandle;
 use tokio::time::MissedTickBehavior;
-use tracing::{debug, trace};
+use tracing::{debug, error, trace};
 
 use restate_types::config::CommonOptions;
 
+use crate::otlp_metrics::{OtlpMetrics, OtlpRecorder};
+
+/// How often the Prometheus histograms are drained into their summaries.
+const PROMETHEUS_UPKEEP_INTERVAL: Duration = Duration::from_secs(5);
+
 #[derive(Default)]
 pub struct Prometheus {
     handle: Option<PrometheusHandle>,
+    otlp: Option<OtlpMetrics>,
     upkeep_task: Option<AbortHandle>,
     global_labels: IndexMap<String, String>,
 }
 
 impl Prometheus {
     /// Creates and installs a global records unless prometheus is explicitly disabled in
-    /// configuration.
+    /// configuration. If an OTLP metrics endpoint is configured, the metrics are recorded for
+    /// pushing them over OTLP as well.
     ///
     /// Note that this *does not* start the upkeep task, the caller should call
     /// `start_upkeep_task()` from within a tokio runtime.
     pub fn install(opts: &CommonOptions) -> Self {
+        let (otlp, otlp_recorder) = OtlpMetrics::new(opts).unzip();
+
         if opts.disable_prometheus {
+            if let Some(otlp_recorder) = otlp_recorder {
+                metrics::set_global_recorder(otlp_recorder)
+                    .expect("no global metrics recorder should be installed");
+            }
             return Self {
                 handle: None,
+                otlp,
                 upkeep_task: None,
                 global_labels: IndexMap::default(),
             };
@@ -49,10 +66,14 @@ impl Prometheus {
 
         // We do not expect this to fail except due to atomic CAS failure
         // which should never happen in practice.
-        metrics::set_global_recorder(recorder)
-            .expect("no global metrics recorder should be installed");
+        match otlp_recorder {
+            Some(otlp_recorder) => metrics::set_global_recorder(fanout(recorder, otlp_recorder)),
+            None => metrics::set_global_recorder(recorder),
+        }
+        .expect("no global metrics recorder should be installed");
         Self {
             handle: Some(prometheus_handle),
+            otlp,
             upkeep_task: None,
             global_labels: IndexMap::from([
                 (
@@ -77,30 +98,58 @@ impl Prometheus {
 
     /// Starts the upkeep task. Should typically be run once, but it'll abort
     /// current task if it's already running.
+    ///
+    /// The upkeep task also copies the metrics to push to the OTLP metrics endpoint, if
+    /// configured, at the OTLP export interval.
     pub fn start_upkeep_task(&mut self) {
         // aborts current task if any
         self.stop_upkeep_task();
-        if let Some(prometheus_handle) = self.handle.clone() {
-            self.upkeep_task = Some(
-                tokio::task::Builder::new()
-                    .name("prometheus-upkeep")
-                    .spawn(async move {
-                        debug!("Prometheus metrics upkeep loop started");
-
-                        let mut update_interval =
-                            tokio::time::interval(std::time::Duration::from_secs(5));
-                        update_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
-
-                        loop {
-                            update_interval.tick().await;
-                            trace!("Performing Prometheus metrics upkeep...");
-                            prometheus_handle.run_upkeep();
-                        }
-                    })
-                    .expect("No tokio runtime")
-                    .abort_handle(),
-            );
+        let prometheus_handle = self.handle.clone();
+        let otlp_upkeep = self.otlp.as_mut().and_then(|otlp| match otlp.start() {
+            Ok(upkeep) => Some((upkeep, otlp.export_interval())),
+            Err(err) => {
+                error!("Failed to start pushing metrics over OTLP: {err}");
+                None
+            }
+        });
+        if prometheus_handle.is_none() && otlp_upkeep.is_none() {
+            return;
         }
+        self.upkeep_task = Some(
+            tokio::task::Builder::new()
+                .name("metrics-upkeep")
+                .spawn(async move {
+                    debug!("Metrics upkeep loop started");
+
+                    let mut prometheus_interval = tokio::time::interval(PROMETHEUS_UPKEEP_INTERVAL);
+                    prometheus_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+                    let mut otlp_interval = tokio::time::interval(
+                        otlp_upkeep
+                            .as_ref()
+                            .map_or(PROMETHEUS_UPKEEP_INTERVAL, |(_, interval)| *interval),
+                    );
+                    otlp_interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+                    loop {
+                        tokio::select! {
+                            _ = prometheus_interval.tick(), if prometheus_handle.is_some() => {
+                                trace!("Performing Prometheus metrics upkeep...");
+                                if let Some(prometheus_handle) = &prometheus_handle {
+                                    prometheus_handle.run_upkeep();
+                                }
+                            }
+                            _ = otlp_interval.tick(), if otlp_upkeep.is_some() => {
+                                trace!("Performing OTLP metrics upkeep...");
+                                if let Some((otlp_upkeep, _)) = &otlp_upkeep {
+                                    otlp_upkeep.lock().run_upkeep();
+                                }
+                            }
+                        }
+                    }
+                })
+                .expect("No tokio runtime")
+                .abort_handle(),
+        );
     }
 
     /// Stops the upkeep task if it's running.
@@ -111,8 +160,39 @@ impl Prometheus {
     }
 }
 
+/// Records every metric with both the Prometheus and the OTLP recorder.
+fn fanout(prometheus: PrometheusRecorder, otlp: OtlpRecorder) -> Fanout {
+    FanoutBuilder::default()
+        .add_recorder(prometheus)
+        .add_recorder(otlp)
+        .build()
+}
+
 impl Drop for Prometheus {
     fn drop(&mut self) {
         self.stop_upkeep_task();
+        if let Some(otlp) = &self.otlp {
+            otlp.shutdown();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fanout_records_with_both_recorders() {
+        let prometheus = PrometheusBuilder::default().build_recorder();
+        let handle = prometheus.handle();
+        let otlp = OtlpRecorder::default();
+
+        let recorder = fanout(prometheus, otlp.clone());
+        metrics::with_local_recorder(&recorder, || {
+            metrics::counter!("restate_test_total").increment(2)
+        });
+
+        assert!(handle.render().contains("restate_test_total 2"));
+        assert_eq!(Some(2), otlp.counter_value("restate_test_total"));
     }
 }
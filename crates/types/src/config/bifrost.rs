@@ -25,7 +25,9 @@ use crate::net::connect_opts::MESSAGE_SIZE_OVERHEAD;
 use crate::retries::RetryPolicy;
 
 use super::networking::DEFAULT_MESSAGE_SIZE_LIMIT;
-use super::{BackgroundWorkBudget, CommonOptions, NetworkingOptions, RocksDbOptions};
+use super::{
+    BackgroundWorkBudget, CommonOptions, NetworkingOptions, ObjectStoreOptions, RocksDbOptions,
+};
 
 /// # Bifrost options
 #[serde_as]
@@ -44,6 +46,8 @@ pub struct BifrostOptions {
     pub local: LocalLogletOptions,
     /// Configuration of replicated loglet provider
     pub replicated_loglet: ReplicatedLogletOptions,
+    /// Configuration of the object-store tier for sealed log segments
+    pub object_store_tier: ObjectStoreTierOptions,
 
     /// # Read retry policy
     ///
@@ -152,6 +156,7 @@ impl Default for BifrostOptions {
             default_provider: ProviderKind::Replicated,
             replicated_loglet: ReplicatedLogletOptions::default(),
             local: LocalLogletOptions::default(),
+            object_store_tier: ObjectStoreTierOptions::default(),
             read_retry_policy: RetryPolicy::exponential(
                 Duration::from_millis(50),
                 2.0,
@@ -312,6 +317,94 @@ impl Default for LocalLogletOptions {
     }
 }
 
+/// # Object-store tier options
+///
+/// Offloads the records of sealed log segments to an object store, freeing up the disk space
+/// they occupy on the local or log-server nodes. Set `destination` to enable the tier. Only
+/// segments that were sealed by a reconfiguration and are no longer the tail of their log are
+/// offloaded; reads of offloaded segments are served from the object store transparently.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize, derive_builder::Builder)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "schemars",
+    schemars(rename = "ObjectStoreTierOptions", default)
+)]
+#[serde(rename_all = "kebab-case")]
+#[builder(default)]
+pub struct ObjectStoreTierOptions {
+    /// # Destination URL
+    ///
+    /// Base URL under which the records of sealed log segments are stored. Supports the
+    /// `s3://`, `gs://`, `az://` and `file://` protocol schemes.
+    ///
+    /// Default: `None` - the tier is disabled
+    pub destination: Option<String>,
+
+    #[serde(flatten)]
+    pub object_store: ObjectStoreOptions,
+
+    /// # Error retry policy
+    ///
+    /// A retry policy for dealing with retryable object store errors.
+    pub object_store_retry_policy: RetryPolicy,
+
+    /// # Object size
+    ///
+    /// Records of a segment are uploaded in objects of (roughly) this size. Readers fetch
+    /// whole objects, so this also bounds the memory used by a single reader of an offloaded
+    /// segment.
+    ///
+    /// Default: 64MiB
+    pub object_size: NonZeroByteCount,
+
+    /// # Offload delay
+    ///
+    /// How long a segment must have been sealed before it's offloaded. Recently sealed segments
+    /// are likely still read by lagging readers, and many of them are trimmed before the delay
+    /// elapses.
+    ///
+    /// Default: 1h
+    pub offload_delay: FriendlyDuration,
+
+    /// # Minimum records
+    ///
+    /// Segments holding fewer records are not offloaded, they stay in their loglet until the log
+    /// is trimmed.
+    ///
+    /// Default: 10000
+    pub offload_min_records: u64,
+
+    /// # Trim grace period
+    ///
+    /// How long to keep the records of an offloaded segment in the original loglet, so that
+    /// in-flight readers of that loglet can finish before its storage is reclaimed.
+    ///
+    /// Default: 10m
+    pub trim_grace_period: FriendlyDuration,
+}
+
+impl Default for ObjectStoreTierOptions {
+    fn default() -> Self {
+        Self {
+            destination: None,
+            object_store: ObjectStoreOptions::default(),
+            object_store_retry_policy: RetryPolicy::exponential(
+                Duration::from_millis(100),
+                2.,
+                Some(10),
+                Some(Duration::from_secs(10)),
+            ),
+            object_size: NonZeroByteCount::new(
+                NonZeroUsize::new(64 * 1024 * 1024).expect("Non zero number"),
+            ),
+            offload_delay: FriendlyDuration::from_secs(3600),
+            offload_min_records: 10_000,
+            trim_grace_period: FriendlyDuration::from_secs(600),
+        }
+    }
+}
+
 #[serde_as]
 #[derive(Debug, Clone, Serialize, Deserialize, derive_builder::Builder)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
@@ -129,6 +129,18 @@ pub struct ListenerOptions<P: ListenerPort + 'static> {
     /// or it'll use the value supplied in `advertised-host` if set.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     advertised_address: Option<AdvertisedAddress<P>>,
+
+    /// # TLS
+    ///
+    /// Serve this listener over TLS. TCP connections will only accept TLS handshakes, unix
+    /// sockets are not affected. When set, the inferred advertised address uses the `https`
+    /// scheme.
+    ///
+    /// This is not inherited from the fabric listener.
+    ///
+    /// Since v1.7.1
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tls: Option<ListenerTlsOptions>,
 }
 
 impl<P: ListenerPort + 'static> ListenerOptions<P> {
@@ -171,9 +183,14 @@ impl<P: ListenerPort + 'static> ListenerOptions<P> {
 
     pub fn advertised_address(&self, address_book: &AddressBook) -> AdvertisedAddress<P> {
         self.advertised_address.clone().unwrap_or_else(|| {
-            address_book.guess_advertised_address(self.advertised_host.as_deref())
+            address_book
+                .guess_advertised_address(self.advertised_host.as_deref(), self.tls.is_some())
         })
     }
+
+    pub fn tls(&self) -> Option<&ListenerTlsOptions> {
+        self.tls.as_ref()
+    }
 }
 
 impl<P: ListenerPort> Default for ListenerOptions<P> {
@@ -186,10 +203,64 @@ impl<P: ListenerPort> Default for ListenerOptions<P> {
             bind_port: None,
             bind_address: None,
             advertised_address: None,
+            tls: None,
         }
     }
 }
 
+/// # Listener TLS options
+///
+/// Certificate and key used to terminate TLS on a listener. The files are PEM encoded and are
+/// re-read when they change on disk, so rotated certificates are picked up without a restart.
+#[serde_as]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct ListenerTlsOptions {
+    /// # Certificate file
+    ///
+    /// Path to the PEM encoded certificate chain presented to clients.
+    pub cert_file: PathBuf,
+
+    /// # Private key file
+    ///
+    /// Path to the PEM encoded private key of the certificate.
+    pub key_file: PathBuf,
+
+    /// # Client CA file
+    ///
+    /// Path to a PEM bundle of CA certificates. When set, clients must present a certificate
+    /// signed by one of these CAs (mutual TLS).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_ca_file: Option<PathBuf>,
+
+    /// # Reload interval
+    ///
+    /// How often the certificate, key and client CA files are checked for changes.
+    #[serde(default = "default_tls_reload_interval")]
+    pub reload_interval: NonZeroFriendlyDuration,
+}
+
+impl ListenerTlsOptions {
+    pub fn new(cert_file: PathBuf, key_file: PathBuf) -> Self {
+        Self {
+            cert_file,
+            key_file,
+            client_ca_file: None,
+            reload_interval: default_tls_reload_interval(),
+        }
+    }
+
+    pub fn with_client_ca_file(mut self, client_ca_file: PathBuf) -> Self {
+        self.client_ca_file = Some(client_ca_file);
+        self
+    }
+}
+
+fn default_tls_reload_interval() -> NonZeroFriendlyDuration {
+    NonZeroFriendlyDuration::from_secs_unchecked(60)
+}
+
 #[serde_as]
 #[derive(Debug, Clone, Serialize, Deserialize, derive_builder::Builder)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
@@ -312,6 +383,9 @@ pub struct CommonOptions {
     #[serde(flatten)]
     pub tracing: TracingOptions,
 
+    #[serde(flatten)]
+    pub otlp_metrics: OtlpMetricsOptions,
+
     /// # Logging Filter
     ///
     /// Log filter configuration. Can be overridden by the `RUST_LOG` environment variable.
@@ -658,8 +732,30 @@ experimental! {
     /// `x-restate-limit-key` record headers to drive vqueue scope and
     /// hierarchical limit-key routing. Requires `vqueues` to also be enabled.
     ///
+    /// NATS and webhook subscriptions read the same headers from the message
+    /// and request headers.
+    ///
     /// Since v1.7.0
     kafka_scope,
+
+    /// # Enables the controllable time of partition processor timers
+    ///
+    /// When enabled, the admin API can fire sleeps and delayed invocations ahead of the wall
+    /// clock, either all timers due until a point in time or the timers of one invocation. Only
+    /// applies to the partitions led by the node serving the request, meant for single-node test
+    /// servers. Never enable this in production.
+    ///
+    /// Since v1.7.1
+    controllable_time,
+
+    /// # Enables cron schedules
+    ///
+    /// When enabled, partitions fire the ticks of the schedules registered through the admin
+    /// API. Requires all nodes in the cluster to be running v1.7.1 or later because schedule
+    /// ticks are persisted as new timer and invocation source variants.
+    ///
+    /// Since v1.7.1
+    schedules,
 }
 
 serde_with::with_prefix!(pub prefix_tokio_console "tokio_console_");
@@ -851,6 +947,7 @@ impl Default for CommonOptions {
             service_client: Default::default(),
             shutdown_timeout: NonZeroFriendlyDuration::from_secs_unchecked(60),
             tracing: TracingOptions::default(),
+            otlp_metrics: OtlpMetricsOptions::default(),
             log_filter: "warn,restate=info".to_string(),
             log_format: Default::default(),
             log_disable_ansi_codes: false,
@@ -1236,6 +1333,76 @@ impl Default for TracingOptions {
     }
 }
 
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "schemars",
+    schemars(
+        title = "OTLP Metrics",
+        description = "Options for pushing metrics over OTLP"
+    )
+)]
+pub struct OtlpMetricsOptions {
+    /// # OTLP Metrics Endpoint
+    ///
+    /// Specify the endpoint to push metrics to, using the
+    /// [OpenTelemetry protocol](https://opentelemetry.io/docs/specs/otlp/).
+    /// The scheme selects the transport in the same way as for [`TracingOptions::tracing_endpoint`]:
+    /// `http[s]://` pushes over gRPC, `otlp+http[s]://` pushes protobuf over HTTP and
+    /// `otlp+json+http[s]://` pushes JSON over HTTP. HTTP endpoints must include the path, for
+    /// example `otlp+http://localhost:4318/v1/metrics`.
+    ///
+    /// Metrics are pushed in addition to the Prometheus endpoint, which keeps working as before.
+    /// If unset, metrics are not pushed.
+    pub metrics_otlp_endpoint: Option<String>,
+
+    /// # Additional OTLP metrics headers
+    ///
+    /// Specify additional headers you want the system to send to the OTLP metrics endpoint (e.g.
+    /// authentication headers).
+    #[serde(skip_serializing_if = "SerdeableHeaderHashMap::is_empty")]
+    #[serde(default)]
+    pub metrics_otlp_headers: SerdeableHeaderHashMap,
+
+    /// # OTLP Metrics Export Interval
+    ///
+    /// How often metrics are pushed to the OTLP metrics endpoint.
+    pub metrics_otlp_export_interval: NonZeroFriendlyDuration,
+
+    /// # OTLP Metrics Temporality
+    ///
+    /// Temporality of the pushed counters and histograms.
+    pub metrics_otlp_temporality: MetricsTemporality,
+}
+
+impl Default for OtlpMetricsOptions {
+    fn default() -> Self {
+        Self {
+            metrics_otlp_endpoint: None,
+            metrics_otlp_headers: SerdeableHeaderHashMap::default(),
+            metrics_otlp_export_interval: NonZeroFriendlyDuration::from_secs_unchecked(60),
+            metrics_otlp_temporality: MetricsTemporality::default(),
+        }
+    }
+}
+
+/// # Metrics temporality
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub enum MetricsTemporality {
+    /// # Cumulative
+    ///
+    /// Every export reports the values accumulated since the process started.
+    #[default]
+    Cumulative,
+    /// # Delta
+    ///
+    /// Every export reports only the change since the previous export.
+    Delta,
+}
+
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "kebab-case")]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
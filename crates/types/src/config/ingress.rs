@@ -8,7 +8,10 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::fmt;
+use std::net::SocketAddr;
 use std::num::{NonZeroU32, NonZeroUsize};
+use std::path::PathBuf;
 
 use restate_memory::NonZeroByteCount;
 use serde::{Deserialize, Serialize};
@@ -79,6 +82,35 @@ pub struct IngressOptions {
     /// Settings for the ingestion client
     /// Currently only used by the Kafka ingress and the admin API.
     pub ingestion: IngestionOptions,
+
+    /// # Authentication
+    ///
+    /// Authentication of the requests received by the HTTP ingress. If unset, the ingress
+    /// accepts every request that reaches it.
+    ///
+    /// Since v1.7.1
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth: Option<IngressAuthOptions>,
+
+    /// # Webhook bind address
+    ///
+    /// Address on which the subscriptions with a `webhook://<name>` source receive their events,
+    /// e.g. `0.0.0.0:9090`. Events are sent with `POST /webhooks/<name>`. The listener uses the
+    /// TLS settings of the ingress listener. If unset, webhook subscriptions don't receive any
+    /// events.
+    ///
+    /// Since v1.7.1
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    webhook_bind_address: Option<SocketAddr>,
+
+    /// # gRPC
+    ///
+    /// Accept gRPC and gRPC-Web requests on the `/{service}/{handler}` paths of the HTTP
+    /// ingress. If unset, gRPC requests are rejected.
+    ///
+    /// Since v1.7.1
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub grpc: Option<IngressGrpcOptions>,
 }
 
 impl IngressOptions {
@@ -131,6 +163,18 @@ impl IngressOptions {
         self.http2_max_concurrent_streams
     }
 
+    pub fn auth(&self) -> Option<&IngressAuthOptions> {
+        self.auth.as_ref()
+    }
+
+    pub fn grpc(&self) -> Option<&IngressGrpcOptions> {
+        self.grpc.as_ref()
+    }
+
+    pub fn webhook_bind_address(&self) -> Option<SocketAddr> {
+        self.webhook_bind_address
+    }
+
     /// set derived values if they are not configured to reduce verbose configurations
     pub fn set_derived_values(&mut self, common: &CommonOptions, networking: &NetworkingOptions) {
         self.ingress_listener_options
@@ -147,3 +191,133 @@ impl IngressOptions {
         );
     }
 }
+
+/// # Ingress gRPC options
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "schemars", schemars(default))]
+#[serde(rename_all = "kebab-case")]
+pub struct IngressGrpcOptions {
+    /// # Descriptor set
+    ///
+    /// A path to a file, such as "/etc/restate/services.binpb", which contains a Protobuf
+    /// `FileDescriptorSet` describing the gRPC services, as generated by
+    /// `protoc --include_imports --descriptor_set_out`. The protobuf messages of the methods it
+    /// describes are transcoded to and from the JSON expected by the handlers.
+    ///
+    /// The descriptor set registered in the metadata of a service, under the
+    /// `restate.grpc.descriptor-set` key, takes precedence over this file. Protobuf requests to
+    /// methods described by neither are rejected with `FAILED_PRECONDITION`.
+    ///
+    /// This file is currently only read when the ingress starts.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub descriptor_set: Option<PathBuf>,
+}
+
+/// # Ingress authentication options
+///
+/// At least one authentication mechanism must be configured. A request is accepted if it
+/// carries valid credentials for any of the configured mechanisms.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(feature = "schemars", schemars(default))]
+#[serde(rename_all = "kebab-case")]
+pub struct IngressAuthOptions {
+    /// # API keys
+    ///
+    /// Static API keys accepted by the ingress. Clients send the key in the
+    /// `x-restate-api-key` header.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub api_keys: Vec<IngressApiKey>,
+
+    /// # JWT
+    ///
+    /// Verify bearer tokens sent in the `authorization` header as JWTs signed by one of the
+    /// keys of a local JWKS file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jwt: Option<IngressJwtOptions>,
+}
+
+/// # Ingress API key
+#[derive(Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct IngressApiKey {
+    /// # Principal
+    ///
+    /// Name of the principal authenticated by this key. This is the name to use in the
+    /// `restate.ingress.allowed-principals` service and handler metadata.
+    pub principal: String,
+
+    /// # Key
+    ///
+    /// The secret value of the API key. It is redacted from debug output.
+    pub key: String,
+}
+
+impl fmt::Debug for IngressApiKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IngressApiKey")
+            .field("principal", &self.principal)
+            .field("key", &REDACTED)
+            .finish()
+    }
+}
+
+const REDACTED: &str = "<redacted>";
+
+/// # Ingress JWT options
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct IngressJwtOptions {
+    /// # JWKS file
+    ///
+    /// A path to a file, such as "/var/secrets/jwks.json", which contains the JSON Web Key Set
+    /// used to verify the token signatures. Tokens must carry a `kid` header matching one of the
+    /// keys, unless the set contains a single key.
+    ///
+    /// This file is currently only read when the ingress starts.
+    pub jwks_file: PathBuf,
+
+    /// # Issuer
+    ///
+    /// If set, tokens must carry a matching `iss` claim.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issuer: Option<String>,
+
+    /// # Audience
+    ///
+    /// If set, tokens must carry a matching `aud` claim.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub audience: Option<String>,
+
+    /// # Principal claim
+    ///
+    /// Name of the claim used as principal of the request.
+    #[serde(default = "default_principal_claim")]
+    pub principal_claim: String,
+}
+
+fn default_principal_claim() -> String {
+    "sub".to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn api_key_is_redacted_only_from_debug_output() {
+        let api_key = IngressApiKey {
+            principal: "billing".to_owned(),
+            key: "secret".to_owned(),
+        };
+        assert!(!format!("{api_key:?}").contains("secret"));
+
+        // the configuration dump must be reloadable, so serialization keeps the key
+        let serialized = toml::to_string(&api_key).unwrap();
+        let deserialized: IngressApiKey = toml::from_str(&serialized).unwrap();
+        assert_eq!("secret", deserialized.key);
+    }
+}
@@ -442,6 +442,12 @@ impl Configuration {
             return Err(InvalidConfigurationError::ForceNodeIdZero);
         }
 
+        if let Some(tls) = &self.networking.tls
+            && tls.cert_file.is_some() != tls.key_file.is_some()
+        {
+            return Err(InvalidConfigurationError::IncompleteClientIdentity);
+        }
+
         if self.common.node_name.is_none() {
             // If the node name is not set, we will fallback to use hostname as the node name.
             // So to avoid changing hostname to make data loss, we must validate the directory's entry.
@@ -485,6 +491,8 @@ pub enum InvalidConfigurationError {
     DeriveBindAddress(String),
     #[error("node-name is required: {0}")]
     RequiredNodeName(String),
+    #[error("networking.tls.cert-file and networking.tls.key-file must be set together")]
+    IncompleteClientIdentity,
 }
 
 /// Migrates a single field from a deprecated config location to its new one.
@@ -642,4 +650,20 @@ mod tests {
             _ => panic!("Shoule be RequiredNodeName error"),
         }
     }
+
+    #[test]
+    fn configuration_validate_fabric_client_identity() {
+        let mut config = Configuration::default();
+        config.networking.tls = Some(FabricClientTlsOptions {
+            cert_file: Some("node.pem".into()),
+            ..Default::default()
+        });
+        assert_eq!(
+            config.validate(),
+            Err(InvalidConfigurationError::IncompleteClientIdentity)
+        );
+
+        config.networking.tls.as_mut().unwrap().key_file = Some("node.key".into());
+        assert!(config.validate().is_ok());
+    }
 }
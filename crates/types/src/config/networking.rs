@@ -9,6 +9,7 @@
 // by the Apache License, Version 2.0.
 
 use std::num::NonZeroUsize;
+use std::path::PathBuf;
 use std::time::Duration;
 
 use restate_util_bytecount::NonZeroByteCount;
@@ -103,6 +104,44 @@ pub struct NetworkingOptions {
         skip_serializing_if = "is_default_fabric_memory_limit"
     )]
     fabric_memory_limit: NonZeroByteCount,
+
+    /// # Fabric TLS
+    ///
+    /// TLS settings used when connecting to other nodes whose fabric listener serves TLS
+    /// (advertised with the `https` scheme). Set `cert-file` and `key-file` to authenticate
+    /// this node to peers requiring mutual TLS.
+    ///
+    /// Since v1.7.1
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls: Option<FabricClientTlsOptions>,
+}
+
+/// # Fabric client TLS options
+///
+/// The files are PEM encoded and read on every new connection, so rotated files are picked up
+/// without a restart.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[serde(rename_all = "kebab-case")]
+pub struct FabricClientTlsOptions {
+    /// # CA file
+    ///
+    /// PEM bundle of the CA certificates used to verify the peers. If unset, the system's
+    /// native root certificates are used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ca_file: Option<PathBuf>,
+
+    /// # Certificate file
+    ///
+    /// Client certificate chain presented to peers requiring mutual TLS.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cert_file: Option<PathBuf>,
+
+    /// # Private key file
+    ///
+    /// Private key of the client certificate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_file: Option<PathBuf>,
 }
 
 const fn default_message_size_limit() -> NonZeroByteCount {
@@ -159,6 +198,7 @@ impl Default for NetworkingOptions {
             ),
             message_size_limit: default_message_size_limit(),
             fabric_memory_limit: default_fabric_memory_limit(),
+            tls: None,
         }
     }
 }
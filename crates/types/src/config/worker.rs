@@ -457,6 +457,18 @@ pub struct InvokerOptions {
     /// Since v1.7.0
     #[serde(flatten)]
     pub service_client: ServiceClientOptions,
+
+    /// # Deployment circuit breaker
+    ///
+    /// Protects deployments that are down from being flooded with retries. After a number of
+    /// consecutive connection errors or 5xx responses from a deployment, the invoker holds new
+    /// attempts against that deployment, and then probes it with a single attempt before
+    /// resuming.
+    ///
+    /// The circuit breaker state is shared by all partitions running on this node.
+    ///
+    /// Since v1.7.1
+    pub deployment_circuit_breaker: DeploymentCircuitBreakerOptions,
 }
 
 impl InvokerOptions {
@@ -591,6 +603,46 @@ impl Default for InvokerOptions {
             per_invocation_memory_limit: None,
             per_invocation_initial_memory: DEFAULT_PER_INVOCATION_INITIAL_MEMORY,
             service_client: ServiceClientOptions::default(),
+            deployment_circuit_breaker: DeploymentCircuitBreakerOptions::default(),
+        }
+    }
+}
+
+/// # Deployment circuit breaker options
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[cfg_attr(
+    feature = "schemars",
+    schemars(rename = "DeploymentCircuitBreakerOptions", default)
+)]
+#[serde(rename_all = "kebab-case", default)]
+pub struct DeploymentCircuitBreakerOptions {
+    /// # Enabled
+    ///
+    /// If disabled, every invocation retries against its deployment according to its own
+    /// retry policy only.
+    pub enabled: bool,
+
+    /// # Failure threshold
+    ///
+    /// Number of consecutive connection errors or 5xx responses from a deployment after
+    /// which the circuit breaker opens.
+    pub failure_threshold: NonZeroU32,
+
+    /// # Open duration
+    ///
+    /// How long the circuit breaker holds new attempts against a deployment once it opened.
+    /// After this time, a single probe attempt is let through. If the probe fails as well,
+    /// the circuit breaker opens again for the same duration.
+    pub open_duration: NonZeroFriendlyDuration,
+}
+
+impl Default for DeploymentCircuitBreakerOptions {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            failure_threshold: NonZeroU32::new(5).expect("is non zero"),
+            open_duration: NonZeroFriendlyDuration::from_secs_unchecked(10),
         }
     }
 }
@@ -15,9 +15,13 @@ pub use crate::schema::deployment::{GoogleIdTokenAuth, HttpAuth, derive_audience
 
 use crate::identifiers::{DeploymentId, LambdaARN};
 use crate::service_protocol::ServiceProtocolVersion;
+use http::uri::InvalidUri;
 use http::{HeaderName, HeaderValue, Uri};
+use serde::{Deserialize, Deserializer, Serializer};
+use serde_with::{DeserializeAs, SerializeAs};
 use std::collections::HashMap;
 use std::fmt;
+use std::path::Path;
 
 #[non_exhaustive]
 #[derive(Debug, Clone, PartialEq)]
@@ -81,6 +85,43 @@ impl fmt::Display for DeploymentAddress {
     }
 }
 
+/// Scheme of the uris of deployments listening on a unix domain socket.
+pub const UNIX_SCHEME: &str = "unix";
+
+/// Parses the uri of a deployment.
+///
+/// On top of the uris accepted by [`Uri`], this accepts the addresses of deployments listening on
+/// a unix domain socket, as `unix:///path/to/socket`. Since [`Uri`] requires an authority when
+/// the scheme is present, they're normalized to the equivalent `unix://localhost/path/to/socket`.
+pub fn parse_deployment_uri(s: &str) -> Result<Uri, InvalidUri> {
+    match s.strip_prefix("unix:///") {
+        Some(socket_path) => format!("{UNIX_SCHEME}://localhost/{socket_path}").parse(),
+        None => s.parse(),
+    }
+}
+
+/// Returns the path of the unix domain socket, if the uri is the address of a deployment
+/// listening on one.
+pub fn unix_socket_path(uri: &Uri) -> Option<&Path> {
+    (uri.scheme_str() == Some(UNIX_SCHEME)).then(|| Path::new(uri.path()))
+}
+
+/// [`serde_with`] adapter for deployment uris, accepting the forms of [`parse_deployment_uri`].
+pub struct DeploymentUriAsString;
+
+impl SerializeAs<Uri> for DeploymentUriAsString {
+    fn serialize_as<S: Serializer>(source: &Uri, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(source)
+    }
+}
+
+impl<'de> DeserializeAs<'de, Uri> for DeploymentUriAsString {
+    fn deserialize_as<D: Deserializer<'de>>(deserializer: D) -> Result<Uri, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        parse_deployment_uri(&s).map_err(serde::de::Error::custom)
+    }
+}
+
 pub type Headers = HashMap<HeaderName, HeaderValue>;
 
 pub type Metadata = HashMap<String, String>;
@@ -188,6 +229,26 @@ mod tests {
         assert_eq!(26, a_str.len());
     }
 
+    #[test]
+    fn parse_unix_socket_uri() {
+        let uri = parse_deployment_uri("unix:///run/restate/greeter.sock").unwrap();
+        assert_eq!(uri.to_string(), "unix://localhost/run/restate/greeter.sock");
+        assert_eq!(
+            unix_socket_path(&uri),
+            Some(Path::new("/run/restate/greeter.sock"))
+        );
+
+        // The normalized form is accepted as well
+        assert_eq!(parse_deployment_uri(&uri.to_string()).unwrap(), uri);
+    }
+
+    #[test]
+    fn parse_http_uri() {
+        let uri = parse_deployment_uri("http://localhost:9080/").unwrap();
+        assert_eq!(uri, Uri::from_static("http://localhost:9080/"));
+        assert_eq!(unix_socket_path(&uri), None);
+    }
+
     #[test]
     fn deployment_roundtrip() {
         let a = DeploymentId::new();
@@ -0,0 +1,205 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Payload delivered to the dead-letter handler of the invocations which exhausted their retries.
+//!
+//! See [`crate::schema::invocation_target::OnMaxAttempts::DeadLetter`].
+
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+use serde_with::{DisplayFromStr, serde_as};
+
+use crate::errors::InvocationError;
+use crate::identifiers::{InvocationId, InvocationUuid, WithPartitionKey};
+use crate::invocation::{Header, InvocationTarget, Source};
+
+pub const DEAD_LETTER_CONTENT_TYPE: &str = "application/json";
+
+/// Request body sent to the dead-letter handler, encoded as JSON.
+#[serde_as]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeadLetterRequest {
+    /// Id of the failed invocation.
+    #[serde_as(as = "DisplayFromStr")]
+    pub invocation_id: InvocationId,
+    /// Target of the failed invocation, formatted as `service[/key]/handler`.
+    pub target: String,
+    /// Base64 encoded input of the failed invocation.
+    ///
+    /// Not available for invocations running with service protocol versions older than V4.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub input: Option<String>,
+    /// Headers of the failed invocation.
+    #[serde(default)]
+    pub headers: Vec<DeadLetterHeader>,
+    /// Last failure of the invocation.
+    pub failure: DeadLetterFailure,
+    /// Summary of the journal at the time of the failure.
+    pub journal: DeadLetterJournalSummary,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeadLetterHeader {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeadLetterFailure {
+    pub code: u16,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeadLetterJournalSummary {
+    /// Number of entries in the journal.
+    pub length: u32,
+    /// Number of commands in the journal.
+    pub commands: u32,
+}
+
+impl DeadLetterRequest {
+    pub fn new(
+        invocation_id: InvocationId,
+        invocation_target: &InvocationTarget,
+        input: Option<(&Bytes, &[Header])>,
+        failure: &InvocationError,
+        journal: DeadLetterJournalSummary,
+    ) -> Self {
+        let (input, headers) = match input {
+            Some((payload, headers)) => (
+                Some(BASE64_STANDARD.encode(payload)),
+                headers
+                    .iter()
+                    .map(|h| DeadLetterHeader {
+                        name: h.name.to_string(),
+                        value: h.value.to_string(),
+                    })
+                    .collect(),
+            ),
+            None => (None, vec![]),
+        };
+
+        Self {
+            invocation_id,
+            target: invocation_target.to_string(),
+            input,
+            headers,
+            failure: DeadLetterFailure {
+                code: failure.code().into(),
+                message: failure.message().to_owned(),
+            },
+            journal,
+        }
+    }
+
+    pub fn encode(&self) -> Bytes {
+        Bytes::from(serde_json::to_vec(self).expect("dead-letter request must be serializable"))
+    }
+}
+
+/// Id of the invocation forwarding `failed_invocation_id` to `dead_letter_target`.
+///
+/// The id is deterministic, so the same failure is forwarded at most once, and it shares the
+/// partition key of the failed invocation.
+pub fn dead_letter_invocation_id(
+    failed_invocation_id: InvocationId,
+    dead_letter_target: &InvocationTarget,
+) -> InvocationId {
+    InvocationId::from_parts(
+        failed_invocation_id.partition_key(),
+        InvocationUuid::generate(
+            dead_letter_target,
+            Some(&format!("dead-letter/{failed_invocation_id}")),
+        ),
+    )
+}
+
+/// Returns true if the invocation was started by forwarding another invocation to its
+/// dead-letter handler.
+///
+/// Such invocations are never forwarded again when they fail, otherwise two services using each
+/// other as dead-letter handler would forward a failure back and forth forever.
+pub fn is_dead_letter_invocation(
+    invocation_id: InvocationId,
+    invocation_target: &InvocationTarget,
+    source: &Source,
+) -> bool {
+    matches!(
+        source,
+        Source::Service(failed_invocation_id, _)
+            if dead_letter_invocation_id(*failed_invocation_id, invocation_target) == invocation_id
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::errors::codes;
+
+    #[test]
+    fn roundtrip() {
+        let invocation_id = InvocationId::mock_random();
+        let request = DeadLetterRequest::new(
+            invocation_id,
+            &InvocationTarget::mock_service(),
+            Some((
+                &Bytes::from_static(b"{\"name\":\"Francesco\"}"),
+                &[Header::new("x-my-header", "my-value")],
+            )),
+            &InvocationError::new(codes::INTERNAL, "boom"),
+            DeadLetterJournalSummary {
+                length: 3,
+                commands: 2,
+            },
+        );
+
+        let decoded: DeadLetterRequest = serde_json::from_slice(&request.encode()).unwrap();
+        assert_eq!(decoded, request);
+        assert_eq!(decoded.invocation_id, invocation_id);
+        assert_eq!(
+            BASE64_STANDARD.decode(decoded.input.unwrap()).unwrap(),
+            b"{\"name\":\"Francesco\"}"
+        );
+        assert_eq!(decoded.failure.code, 500);
+    }
+
+    #[test]
+    fn detect_dead_letter_invocation() {
+        let failed_invocation_id = InvocationId::mock_random();
+        let failed_target = InvocationTarget::mock_service();
+        let dead_letter_target = InvocationTarget::service("DeadLetter", "handle");
+        let dead_letter_id = dead_letter_invocation_id(failed_invocation_id, &dead_letter_target);
+
+        assert_eq!(
+            dead_letter_id.partition_key(),
+            failed_invocation_id.partition_key()
+        );
+        assert!(is_dead_letter_invocation(
+            dead_letter_id,
+            &dead_letter_target,
+            &Source::Service(failed_invocation_id, failed_target.clone())
+        ));
+        // A regular call from the failed invocation to the same handler
+        assert!(!is_dead_letter_invocation(
+            InvocationId::generate(&dead_letter_target, None),
+            &dead_letter_target,
+            &Source::Service(failed_invocation_id, failed_target)
+        ));
+        assert!(!is_dead_letter_invocation(
+            dead_letter_id,
+            &dead_letter_target,
+            &Source::Internal
+        ));
+    }
+}
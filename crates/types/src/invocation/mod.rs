@@ -11,6 +11,7 @@
 //! This module contains all the core types representing a service invocation.
 
 pub mod client;
+pub mod dead_letter;
 
 use std::borrow::Cow;
 use std::hash::Hash;
@@ -34,7 +35,8 @@ use crate::Scope;
 use crate::errors::InvocationError;
 use crate::identifiers::{
     DeploymentId, EntryIndex, IdempotencyId, InvocationId, PartitionKey,
-    PartitionProcessorRpcRequestId, ServiceId, SubscriptionId, WithInvocationId, WithPartitionKey,
+    PartitionProcessorRpcRequestId, ScheduleId, ServiceId, SubscriptionId, WithInvocationId,
+    WithPartitionKey,
 };
 use crate::invocation::client::PatchDeploymentId;
 use crate::journal_v2::{CompletionId, GetInvocationOutputResult, Signal};
@@ -806,6 +808,8 @@ pub enum Source {
     RestartAsNew(InvocationId),
     /// Internal calls for the non-deterministic built-in services
     Internal,
+    /// Tick of a cron schedule
+    Schedule(ScheduleId),
 }
 
 impl Source {
@@ -1559,6 +1563,7 @@ mod serde_hacks {
         RestartAsNew(InvocationId),
         /// Internal calls for the non-deterministic built-in services
         Internal,
+        Schedule(ScheduleId),
     }
 
     impl From<ServiceInvocation> for super::ServiceInvocation {
@@ -1602,6 +1607,7 @@ mod serde_hacks {
                     Source::Service(id, target) => super::Source::Service(id, target),
                     Source::RestartAsNew(id) => super::Source::RestartAsNew(id),
                     Source::Internal => super::Source::Internal,
+                    Source::Schedule(id) => super::Source::Schedule(id),
                 },
                 restate_version,
             }
@@ -1654,6 +1660,7 @@ mod serde_hacks {
                     super::Source::Service(id, target) => Source::Service(id, target),
                     super::Source::Internal => Source::Internal,
                     super::Source::RestartAsNew(id) => Source::RestartAsNew(id),
+                    super::Source::Schedule(id) => Source::Schedule(id),
                 },
             }
         }
@@ -14,8 +14,8 @@ use std::ops::Deref;
 use restate_clock::{Clock, Error as ClockError, HlcClock, LocalStorage, WallClock};
 
 use super::metadata::{
-    Chain, LogletConfig, LogletParams, Logs, LogsConfiguration, LookupIndex, MaybeSegment,
-    ProviderKind, SealMetadata, SegmentIndex,
+    Chain, InternalKind, LogletConfig, LogletParams, Logs, LogsConfiguration, LookupIndex,
+    MaybeSegment, ProviderKind, SealMetadata, SegmentIndex,
 };
 use super::{LogId, Lsn};
 use crate::Version;
@@ -48,6 +48,8 @@ pub enum BuilderError {
     ParamsSerde(#[from] serde_json::Error),
     #[error("Segment conflicts with existing (base_lsn={0})")]
     SegmentConflict(Lsn),
+    #[error("segment {1} of log {0} does not exist or is not sealed")]
+    SegmentNotSealed(LogId, SegmentIndex),
     #[error(transparent)]
     HlcClock(#[from] ClockError),
 }
@@ -347,6 +349,79 @@ impl<C: Clock> ChainBuilder<'_, C> {
             }
         }
     }
+
+    /// Turns a permanent seal into a regular seal so that the chain can be extended again.
+    ///
+    /// Returns `false` (and leaves the chain untouched) if the chain is not permanently sealed.
+    pub fn lift_permanent_seal(&mut self) -> Result<bool, BuilderError> {
+        let mut last_entry = self
+            .inner
+            .chain
+            .last_entry()
+            .expect("chain have at least one segment");
+
+        if !last_entry.get().kind.is_seal_marker() {
+            return Ok(false);
+        }
+
+        let mut seal_metadata = SealMetadata::deserialize_from(last_entry.get().params.as_bytes())?;
+        if !seal_metadata.permanent_seal {
+            return Ok(false);
+        }
+
+        seal_metadata.permanent_seal = false;
+        let index = last_entry.get().index();
+        last_entry.insert(LogletConfig::new_sealed(index, &seal_metadata)?);
+        *self.modified = true;
+        Ok(true)
+    }
+
+    /// Replaces the loglet of a sealed segment with its copy in the object-store tier.
+    ///
+    /// The segment keeps its index and base lsn. Only sealed segments (segments followed by
+    /// another segment or a seal marker) can be archived. Archiving an already archived segment
+    /// is a no-op.
+    pub fn archive_segment(
+        &mut self,
+        index: SegmentIndex,
+        params: LogletParams,
+    ) -> Result<(), BuilderError> {
+        let Some((&base_lsn, _)) = self
+            .inner
+            .chain
+            .iter()
+            .rfind(|(_, config)| config.index() == index && !config.kind.is_seal_marker())
+        else {
+            return Err(BuilderError::SegmentNotSealed(self.log_id, index));
+        };
+
+        if self
+            .inner
+            .chain
+            .last_key_value()
+            .is_some_and(|(&tail_base_lsn, _)| tail_base_lsn == base_lsn)
+        {
+            // the tail segment is still open
+            return Err(BuilderError::SegmentNotSealed(self.log_id, index));
+        }
+
+        let config = self.inner.chain.get_mut(&base_lsn).expect("segment exists");
+        if config.kind == InternalKind::ObjectStore {
+            return Ok(());
+        }
+
+        if ProviderKind::Replicated == config.kind {
+            let old_params = ReplicatedLogletParams::deserialize_from(config.params.as_bytes())?;
+            self.lookup_index.rm_replicated_loglet_reference(
+                self.log_id,
+                config.index(),
+                old_params.loglet_id,
+            );
+        }
+        *config = LogletConfig::new_object_store(index, params);
+        *self.modified = true;
+        Ok(())
+    }
 }
 
 impl<C: Clock> Deref for ChainBuilder<'_, C> {
@@ -976,4 +1051,114 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn archive_segment() -> googletest::Result<()> {
+        use crate::GenerationalNodeId;
+        use crate::logs::LogletId;
+        use crate::replicated_loglet::ReplicatedLogletParams;
+        use crate::replication::{NodeSet, ReplicationProperty};
+
+        let mut builder = LogsBuilder::new(MockClock::new());
+        let log_id = LogId::new(1);
+
+        let loglet1 = ReplicatedLogletParams {
+            loglet_id: LogletId::from(1),
+            sequencer: GenerationalNodeId::new(1, 1),
+            replication: ReplicationProperty::new(NonZeroU8::new(2).unwrap()),
+            nodeset: NodeSet::new(),
+        };
+        let archived_params = LogletParams::from("archived");
+
+        // log-1 -> [replicated-loglet-1]
+        builder.add_log(
+            log_id,
+            Chain::new(
+                ProviderKind::Replicated,
+                LogletParams::from(loglet1.serialize()?),
+            ),
+        )?;
+
+        // the tail segment is open and cannot be archived
+        let mut chain = builder.chain(log_id).unwrap();
+        assert_that!(
+            chain.archive_segment(SegmentIndex::OLDEST, archived_params.clone()),
+            err(pat!(BuilderError::SegmentNotSealed(anything(), anything())))
+        );
+
+        // log-1 -> [replicated-loglet-1, sealed-loglet]
+        chain.seal(Lsn::from(10), &SealMetadata::default())?;
+        chain.archive_segment(SegmentIndex::OLDEST, archived_params.clone())?;
+
+        // the segment keeps its index and base lsn
+        let head = chain.head();
+        assert_that!(head.base_lsn, eq(Lsn::OLDEST));
+        assert_that!(head.tail_lsn, some(eq(Lsn::from(10))));
+        assert_that!(head.index(), eq(SegmentIndex::OLDEST));
+        assert_that!(head.config.kind, eq(InternalKind::ObjectStore));
+        assert_that!(head.config.params, eq(&archived_params));
+        assert!(chain.is_sealed());
+
+        // archiving again is a no-op, unknown segments are rejected
+        chain.archive_segment(SegmentIndex::OLDEST, archived_params.clone())?;
+        assert_that!(
+            chain.archive_segment(SegmentIndex::from(5), archived_params),
+            err(pat!(BuilderError::SegmentNotSealed(anything(), anything())))
+        );
+
+        // the replicated loglet is not referenced anymore
+        assert_that!(
+            builder
+                .inner
+                .lookup_index
+                .get_replicated_loglet(&LogletId::from(1)),
+            none()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn lift_permanent_seal() -> googletest::Result<()> {
+        let mut builder = LogsBuilder::new(MockClock::new());
+        let log_id = LogId::new(1);
+
+        builder.add_log(
+            log_id,
+            Chain::new(ProviderKind::InMemory, LogletParams::from("test1")),
+        )?;
+
+        let mut chain = builder.chain(log_id).unwrap();
+        // nothing to lift on an open chain
+        assert_that!(chain.lift_permanent_seal(), ok(eq(false)));
+
+        let permanent = SealMetadata::with_context(true, Default::default());
+        chain.seal(Lsn::from(10), &permanent)?;
+        assert!(chain.is_permanently_sealed());
+        assert_that!(
+            chain.append_segment(
+                Lsn::from(10),
+                ProviderKind::InMemory,
+                LogletParams::from("test2")
+            ),
+            err(pat!(BuilderError::ChainPermanentlySealed(anything())))
+        );
+
+        // the chain stays sealed at the same tail but can be extended again
+        assert_that!(chain.lift_permanent_seal(), ok(eq(true)));
+        assert!(chain.is_sealed());
+        assert!(!chain.is_permanently_sealed());
+        assert_that!(chain.sealed_tail(), some(eq(Lsn::from(10))));
+        assert_that!(chain.lift_permanent_seal(), ok(eq(false)));
+
+        let index = chain.append_segment(
+            Lsn::from(10),
+            ProviderKind::InMemory,
+            LogletParams::from("test2"),
+        )?;
+        assert_that!(index, eq(SegmentIndex::from(1)));
+        assert!(!chain.is_sealed());
+
+        Ok(())
+    }
 }
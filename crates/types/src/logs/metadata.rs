@@ -35,7 +35,10 @@ use crate::net::metadata::{MetadataContainer, MetadataKind};
 use crate::replicated_loglet::ReplicatedLogletParams;
 use crate::replication::ReplicationProperty;
 use crate::time::MillisSinceEpoch;
-use crate::{GenerationalNodeId, Version, Versioned, flexbuffers_storage_encode_decode};
+use crate::{
+    GenerationalNodeId, RESTATE_VERSION_1_7_1, SemanticRestateVersion, Version, Versioned,
+    flexbuffers_storage_encode_decode,
+};
 
 // Starts with 0 being the oldest loglet in the chain.
 #[derive(
@@ -606,12 +609,26 @@ pub enum InternalKind {
 
     /// A loglet that's always sealed and has no records. Used as a seal marker for a sealed chain.
     Sealed,
+    /// A sealed segment whose records were offloaded to the object-store tier. Read-only.
+    ///
+    /// *Since v1.7.1*
+    ObjectStore,
 }
 
 impl InternalKind {
     pub fn is_seal_marker(&self) -> bool {
         matches!(self, Self::Sealed)
     }
+
+    /// The minimum Restate-server version required to decode a log chain holding a segment of
+    /// this kind. Segments of such kinds must only be written once every node of the cluster
+    /// runs at least this version.
+    pub fn min_required_version(&self) -> Option<&'static SemanticRestateVersion> {
+        match self {
+            Self::ObjectStore => Some(&RESTATE_VERSION_1_7_1),
+            Self::Local | Self::InMemory | Self::Replicated | Self::Sealed => None,
+        }
+    }
 }
 
 impl From<ProviderKind> for InternalKind {
@@ -631,7 +648,7 @@ impl TryFrom<InternalKind> for ProviderKind {
             InternalKind::Local => Ok(Self::Local),
             InternalKind::InMemory => Ok(Self::InMemory),
             InternalKind::Replicated => Ok(Self::Replicated),
-            InternalKind::Sealed => Err(anyhow::anyhow!(
+            InternalKind::Sealed | InternalKind::ObjectStore => Err(anyhow::anyhow!(
                 "a special loglet kind that cannot be converted into user-facing kind"
             )),
         }
@@ -722,6 +739,15 @@ impl LogletConfig {
         })
     }
 
+    pub(crate) fn new_object_store(index: SegmentIndex, params: LogletParams) -> Self {
+        Self {
+            kind: InternalKind::ObjectStore,
+            params,
+            index,
+            created_at: None,
+        }
+    }
+
     pub fn index(&self) -> SegmentIndex {
         self.index
     }
@@ -838,6 +864,15 @@ impl Chain {
         self.sealed_tail().is_some()
     }
 
+    /// Is the chain sealed with a permanent seal? Such a chain is not allowed to grow.
+    pub fn is_permanently_sealed(&self) -> bool {
+        self.chain.last_key_value().is_some_and(|(_, config)| {
+            config.kind.is_seal_marker()
+                && SealMetadata::deserialize_from(config.params.as_bytes())
+                    .is_ok_and(|metadata| metadata.permanent_seal)
+        })
+    }
+
     /// Finds the last non-special segment in the chain if it exists.
     pub fn non_special_tail(&self) -> Option<Segment<'_>> {
         let (&base_lsn, config) = self
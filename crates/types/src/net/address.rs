@@ -99,6 +99,20 @@ impl ListenerPort for FabricPort {
 }
 impl GrpcPort for FabricPort {}
 
+/// Webhook subscriptions HTTP listener
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct WebhookPort;
+impl ListenerPort for WebhookPort {
+    const NAME: &'static str = "webhook-server";
+    const DEFAULT_PORT: u16 = 9090;
+    const UDS_NAME: &'static str = "webhook.sock";
+    const IS_ANONYMOUS_UDS_ALLOWED: bool = false;
+    fn default_port_str() -> &'static str {
+        "9090"
+    }
+}
+
 #[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct TokioConsolePort;
@@ -359,7 +373,13 @@ impl<P: ListenerPort> Default for AdvertisedAddress<P> {
 }
 
 impl<P: ListenerPort> AdvertisedAddress<P> {
-    pub fn derive_from_bind_address(address: SocketAddress, advertised_host: Option<&str>) -> Self {
+    /// Derives the address to advertise from the bound address. `secure` selects the `https`
+    /// scheme for TCP addresses, it has no effect on unix sockets.
+    pub fn derive_from_bind_address(
+        address: SocketAddress,
+        advertised_host: Option<&str>,
+        secure: bool,
+    ) -> Self {
         let inner = match address {
             SocketAddress::Socket(address) => {
                 let routable_ip = || {
@@ -379,8 +399,9 @@ impl<P: ListenerPort> AdvertisedAddress<P> {
                 };
                 // do we have an input hostname?
                 let hostname = advertised_host.unwrap_or_else(|| routable_ip());
+                let scheme = if secure { "https" } else { "http" };
                 PeerNetAddress::Http(
-                    format!("http://{hostname}:{}", address.port())
+                    format!("{scheme}://{hostname}:{}", address.port())
                         .parse()
                         .expect("valid uri"),
                 )
@@ -743,4 +764,20 @@ mod tests {
         let result = input.parse::<AdvertisedAddress<FabricPort>>();
         assert!(result.is_err(), "Expected an error for empty input");
     }
+
+    #[test]
+    fn derive_advertised_address_scheme() {
+        let bind = SocketAddress::Socket("127.0.0.1:5122".parse().unwrap());
+
+        let address = AdvertisedAddress::<FabricPort>::derive_from_bind_address(
+            bind.clone(),
+            Some("my-host"),
+            false,
+        );
+        assert_eq!(address.to_string(), "http://my-host:5122/");
+
+        let address =
+            AdvertisedAddress::<FabricPort>::derive_from_bind_address(bind, Some("my-host"), true);
+        assert_eq!(address.to_string(), "https://my-host:5122/");
+    }
 }
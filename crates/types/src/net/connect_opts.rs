@@ -12,7 +12,7 @@ use std::num::NonZeroUsize;
 use std::sync::Arc;
 use std::time::Duration;
 
-use crate::config::{MetadataClientOptions, NetworkingOptions};
+use crate::config::{FabricClientTlsOptions, MetadataClientOptions, NetworkingOptions};
 
 /// Overhead added to user-facing max_message_size.
 ///
@@ -33,6 +33,11 @@ pub trait CommonClientConnectionOptions: GrpcConnectionOptions {
     fn keep_alive_interval(&self) -> Duration;
     fn keep_alive_timeout(&self) -> Duration;
     fn http2_adaptive_window(&self) -> bool;
+    /// TLS settings for peers advertising an `https` address. If unset, the fabric TLS settings
+    /// of the node are used.
+    fn tls(&self) -> Option<&FabricClientTlsOptions> {
+        None
+    }
 }
 
 impl<T: GrpcConnectionOptions> GrpcConnectionOptions for &T {
@@ -64,6 +69,10 @@ where
     fn http2_adaptive_window(&self) -> bool {
         (*self).http2_adaptive_window()
     }
+
+    fn tls(&self) -> Option<&FabricClientTlsOptions> {
+        (*self).tls()
+    }
 }
 
 impl<T> GrpcConnectionOptions for Arc<T>
@@ -98,6 +107,10 @@ where
     fn http2_adaptive_window(&self) -> bool {
         (**self).http2_adaptive_window()
     }
+
+    fn tls(&self) -> Option<&FabricClientTlsOptions> {
+        (**self).tls()
+    }
 }
 
 impl GrpcConnectionOptions for NetworkingOptions {
@@ -129,6 +142,10 @@ impl CommonClientConnectionOptions for NetworkingOptions {
     fn http2_adaptive_window(&self) -> bool {
         self.http2_adaptive_window
     }
+
+    fn tls(&self) -> Option<&FabricClientTlsOptions> {
+        self.tls.as_ref()
+    }
 }
 
 impl GrpcConnectionOptions for MetadataClientOptions {
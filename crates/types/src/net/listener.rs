@@ -271,6 +271,7 @@ impl AddressBook {
     pub fn guess_advertised_address<P: ListenerPort + 'static>(
         &self,
         advertised_host: Option<&str>,
+        secure: bool,
     ) -> AdvertisedAddress<P> {
         let Some(addresses) = self.bound_addr.get(&std::any::TypeId::of::<P>()) else {
             // If we don't bind this address, we return a reasonable default.
@@ -281,9 +282,14 @@ impl AddressBook {
             AdvertisedAddress::derive_from_bind_address(
                 SocketAddress::Socket(tcp_address),
                 advertised_host,
+                secure,
             )
         } else if let Some(uds_path) = &addresses.uds_path {
-            AdvertisedAddress::derive_from_bind_address(SocketAddress::Uds(uds_path.clone()), None)
+            AdvertisedAddress::derive_from_bind_address(
+                SocketAddress::Uds(uds_path.clone()),
+                None,
+                false,
+            )
         } else {
             // We can't guess, so we'll return a reasonable default.
             AdvertisedAddress::default()
@@ -415,6 +421,15 @@ impl<P: ListenerPort> Listeners<P> {
             _phantom: std::marker::PhantomData,
         })
     }
+
+    pub async fn new_tcp_listener(bind_address: SocketAddr) -> Result<Self, anyhow::Error> {
+        let tcp_listener = TcpListener::bind(bind_address).await?;
+        Ok(Self {
+            tcp_listener: Some(tcp_listener),
+            unix_listener: None,
+            _phantom: std::marker::PhantomData,
+        })
+    }
 }
 
 impl<P: ListenerPort> Drop for Listeners<P> {
@@ -24,7 +24,7 @@ use crate::metadata::GlobalMetadata;
 use crate::net::address::{AdvertisedAddress, ControlPort, FabricPort};
 use crate::net::metadata::{MetadataContainer, MetadataKind};
 use crate::{
-    GenerationalNodeId, NodeId, PlainNodeId, RestateVersion, base62_util,
+    GenerationalNodeId, NodeId, PlainNodeId, RestateVersion, SemanticRestateVersion, base62_util,
     flexbuffers_storage_encode_decode,
 };
 use crate::{Version, Versioned};
@@ -526,6 +526,17 @@ impl NodesConfiguration {
         })
     }
 
+    /// True if every node reported a binary version equal or newer than `version`. Nodes that
+    /// haven't reported their version are considered older.
+    pub fn all_nodes_at_least(&self, version: &SemanticRestateVersion) -> bool {
+        self.iter().all(|(_, node)| {
+            node.binary_version
+                .as_ref()
+                .and_then(|binary_version| SemanticRestateVersion::try_from(binary_version).ok())
+                .is_some_and(|binary_version| binary_version.is_equal_or_newer_than(version))
+        })
+    }
+
     pub fn iter_mut(&mut self) -> impl Iterator<Item = (PlainNodeId, &'_ mut NodeConfig)> {
         self.nodes.iter_mut().filter_map(|(k, v)| {
             if let MaybeNode::Node(node) = v {
@@ -864,6 +875,37 @@ mod tests {
         assert_eq!(&node, found);
     }
 
+    #[test]
+    fn all_nodes_at_least() {
+        let mut config = NodesConfiguration::new_for_testing();
+        let address: AdvertisedAddress<_> = "unix:/tmp/my_socket".parse().unwrap();
+        let node = |id: u32, binary_version: Option<&str>| {
+            let mut node = NodeConfig::builder()
+                .name(format!("node{id}"))
+                .current_generation(GenerationalNodeId::new(id, 1))
+                .address(address.clone())
+                .roles(Role::Worker.into())
+                .binary_version(RestateVersion::unknown())
+                .build();
+            node.binary_version = binary_version.map(|v| RestateVersion::new(v.to_owned()));
+            node
+        };
+        let version = SemanticRestateVersion::parse("1.7.1-dev").unwrap();
+
+        config.upsert_node(node(1, Some("1.7.1")));
+        assert!(config.all_nodes_at_least(&version));
+
+        config.upsert_node(node(2, Some("1.7.0")));
+        assert!(!config.all_nodes_at_least(&version));
+
+        config.upsert_node(node(2, Some("1.8.0-dev")));
+        assert!(config.all_nodes_at_least(&version));
+
+        // nodes that haven't reported their version are older
+        config.upsert_node(node(3, None));
+        assert!(!config.all_nodes_at_least(&version));
+    }
+
     #[test]
     fn remove_node() {
         let mut config = NodesConfiguration::new_for_testing();
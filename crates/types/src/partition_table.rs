@@ -10,6 +10,7 @@
 
 use std::collections::BTreeMap;
 use std::hash::Hash;
+use std::num::NonZeroU16;
 use std::sync::Arc;
 
 use serde_with::serde_as;
@@ -22,7 +23,11 @@ use crate::nodes_config::NodesConfiguration;
 use crate::partitions::worker_candidate_filter;
 use crate::protobuf::common::DatabaseKind;
 use crate::replication::ReplicationProperty;
-use crate::{Version, Versioned, flexbuffers_storage_encode_decode};
+use crate::sharding::subsharding::ShardPlan;
+use crate::{
+    RESTATE_VERSION_1_7_1, SemanticRestateVersion, Version, Versioned,
+    flexbuffers_storage_encode_decode,
+};
 
 const PARTITION_CF_PREFIX: &str = "data-";
 
@@ -284,6 +289,8 @@ pub struct Partition {
     pub key_range: crate::sharding::KeyRange,
     log_id: Option<LogId>,
     cf_name: Option<CfName>,
+    #[serde(default)]
+    split_from: Option<PartitionId>,
 }
 
 impl Partition {
@@ -293,6 +300,7 @@ impl Partition {
             key_range,
             log_id: None,
             cf_name: None,
+            split_from: None,
         }
     }
 
@@ -319,6 +327,18 @@ impl Partition {
             .clone()
             .unwrap_or_else(|| CfName::for_partition(self.partition_id))
     }
+
+    /// The partition this partition was split from. Its state is bootstrapped from the latest
+    /// snapshot of that partition.
+    pub fn split_from(&self) -> Option<PartitionId> {
+        self.split_from
+    }
+
+    /// The minimum Restate-server version required to split partitions. Older nodes don't know
+    /// which partition a new partition was split from, and can't bootstrap its state.
+    pub fn split_min_required_version() -> &'static SemanticRestateVersion {
+        &RESTATE_VERSION_1_7_1
+    }
 }
 
 /// Errors when building a [`PartitionTable`] via the [`PartitionTableBuilder`].
@@ -330,6 +350,10 @@ pub enum BuilderError {
     Duplicate(PartitionId),
     #[error("partition table has reached its limits")]
     LimitReached,
+    #[error("partition '{0}' does not exist")]
+    NotFound(PartitionId),
+    #[error("partition '{0}' covers a single partition key and cannot be split")]
+    Unsplittable(PartitionId),
 }
 
 #[derive(Debug, Default)]
@@ -431,6 +455,52 @@ impl PartitionTableBuilder {
         }
     }
 
+    /// Replaces the given partition by two new partitions covering the lower and the upper half
+    /// of its key range. The new partitions get ids above all existing partition ids, and
+    /// therefore their own logs, and remember the partition they were split from.
+    ///
+    /// Returns the ids of the new partitions, ordered by their key ranges.
+    pub fn split_partition(
+        &mut self,
+        partition_id: PartitionId,
+    ) -> Result<[PartitionId; 2], BuilderError> {
+        let partition = self
+            .inner
+            .partitions
+            .get(&partition_id)
+            .ok_or(BuilderError::NotFound(partition_id))?;
+
+        let plan = ShardPlan::new(partition.key_range, NonZeroU16::MIN);
+        let split = plan.split(NonZeroU16::MIN);
+        let (Some(left), Some(right)) = (split.left(), split.right()) else {
+            return Err(BuilderError::Unsplittable(partition_id));
+        };
+        let key_ranges = [*left.key_range(), *right.key_range()];
+
+        let max_partition_id = *self
+            .inner
+            .partitions
+            .keys()
+            .next_back()
+            .expect("partition table contains the split partition");
+        if usize::from(*max_partition_id) + key_ranges.len() > usize::from(*PartitionId::MAX) {
+            return Err(BuilderError::LimitReached);
+        }
+
+        self.remove_partition(&partition_id);
+        let mut new_partition_id = max_partition_id;
+        let new_partition_ids = key_ranges.map(|key_range| {
+            new_partition_id = new_partition_id.next();
+            let mut partition = Partition::new(new_partition_id, key_range);
+            partition.split_from = Some(partition_id);
+            self.add_partition(partition)
+                .expect("split partitions cover the key range of the removed partition");
+            new_partition_id
+        });
+
+        Ok(new_partition_ids)
+    }
+
     /// Builds the new [`PartitionTable`] with an incremented version.
     pub fn build(mut self) -> PartitionTable {
         self.inner.version = Version::MIN.max(self.inner.version.next());
@@ -467,6 +537,8 @@ pub struct PartitionShadow {
     #[serde(default)]
     pub db_name: Option<DbName>,
     pub cf_name: Option<CfName>,
+    #[serde(default)]
+    pub split_from: Option<PartitionId>,
 }
 
 /// Serialization helper which handles the deserialization of the current and older
@@ -502,6 +574,7 @@ impl From<PartitionTable> for PartitionTableShadow {
                             key_range: partition.key_range,
                             cf_name: partition.cf_name,
                             db_name: None,
+                            split_from: partition.split_from,
                         };
 
                         (partition_id, partition_shadow)
@@ -533,6 +606,7 @@ impl TryFrom<PartitionTableShadow> for PartitionTable {
                         log_id: partition_shadow.log_id,
                         key_range: partition_shadow.key_range,
                         cf_name: partition_shadow.cf_name,
+                        split_from: partition_shadow.split_from,
                     };
 
                     builder.add_partition(partition)?;
@@ -555,6 +629,7 @@ mod tests {
     use test_log::test;
 
     use crate::identifiers::PartitionId;
+    use crate::logs::LogId;
     use crate::partition_table::{FindPartition, Partition, PartitionTable, PartitionTableBuilder};
     use crate::sharding::KeyRange;
     use crate::storage::StorageCodec;
@@ -639,4 +714,49 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn split_partition() -> anyhow::Result<()> {
+        let mut builder =
+            PartitionTable::with_equally_sized_partitions(Version::MIN, 2).into_builder();
+        let parent = builder.inner.get(&PartitionId::from(0)).unwrap().clone();
+
+        let [left, right] = builder.split_partition(PartitionId::from(0))?;
+        assert_eq!(left, PartitionId::from(2));
+        assert_eq!(right, PartitionId::from(3));
+
+        let partition_table = builder.build();
+        assert_eq!(partition_table.version(), Version::from(2));
+        assert!(!partition_table.contains(&PartitionId::from(0)));
+
+        let left = partition_table.get(&left).unwrap();
+        let right = partition_table.get(&right).unwrap();
+        assert_eq!(left.split_from(), Some(PartitionId::from(0)));
+        assert_eq!(
+            left.log_id(),
+            LogId::default_for_partition(left.partition_id)
+        );
+        assert_eq!(left.key_range.start(), parent.key_range.start());
+        assert_eq!(left.key_range.end() + 1, right.key_range.start());
+        assert_eq!(right.key_range.end(), parent.key_range.end());
+        assert_eq!(
+            partition_table.find_partition_id(parent.key_range.end())?,
+            right.partition_id
+        );
+
+        // the split origin survives serialization
+        let mut buf = BytesMut::default();
+        StorageCodec::encode(&partition_table, &mut buf)?;
+        assert_eq!(
+            StorageCodec::decode::<PartitionTable, _>(&mut buf)?,
+            partition_table
+        );
+
+        let mut builder = PartitionTableBuilder::new(Version::INVALID);
+        builder.add_partition(Partition::new(PartitionId::from(0), KeyRange::new(7, 7)))?;
+        assert!(builder.split_partition(PartitionId::from(0)).is_err());
+        assert!(builder.split_partition(PartitionId::from(1)).is_err());
+
+        Ok(())
+    }
 }
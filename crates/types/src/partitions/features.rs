@@ -16,7 +16,9 @@ use crate::storage::{
     StorageCodecKind, StorageDecode, StorageDecodeError, StorageEncode, StorageEncodeError, decode,
     encode,
 };
-use crate::{RESTATE_VERSION_1_6_0, RESTATE_VERSION_1_7_0, SemanticRestateVersion};
+use crate::{
+    RESTATE_VERSION_1_6_0, RESTATE_VERSION_1_7_0, RESTATE_VERSION_1_7_1, SemanticRestateVersion,
+};
 
 /// A change to the set of state-machine features enabled on a partition.
 ///
@@ -49,6 +51,11 @@ pub enum PartitionFeatureChange {
     ///
     /// *Since v1.7.0*
     EnableUniqueRandomSeeds = 3,
+    /// Fire the ticks of cron schedules, which persist schedule tick timers and invocations
+    /// sourced from a schedule.
+    ///
+    /// *Since v1.7.1*
+    EnableSchedules = 4,
 }
 
 impl PartitionFeatureChange {
@@ -65,6 +72,7 @@ impl PartitionFeatureChange {
             Self::EnableJournalV2 => &RESTATE_VERSION_1_6_0,
             Self::EnableVqueues => &RESTATE_VERSION_1_7_0,
             Self::EnableUniqueRandomSeeds => &RESTATE_VERSION_1_7_0,
+            Self::EnableSchedules => &RESTATE_VERSION_1_7_1,
         }
     }
 
@@ -77,6 +85,7 @@ impl PartitionFeatureChange {
             Self::EnableUniqueRandomSeeds => {
                 !std::mem::replace(&mut features.unique_random_seeds, true)
             }
+            Self::EnableSchedules => !std::mem::replace(&mut features.schedules, true),
         }
     }
 }
@@ -121,6 +130,11 @@ pub struct PersistedStateMachineFeatures {
     /// *Since v1.7.0*
     #[bilrost(tag(3))]
     pub unique_random_seeds: bool,
+    /// Ticks of cron schedules are fired.
+    ///
+    /// *Since v1.7.1*
+    #[bilrost(tag(4))]
+    pub schedules: bool,
 }
 
 impl PersistedStateMachineFeatures {
@@ -132,6 +146,7 @@ impl PersistedStateMachineFeatures {
             self.journal_v2.then_some("journal_v2"),
             self.vqueues.then_some("vqueues"),
             self.unique_random_seeds.then_some("unique_random_seeds"),
+            self.schedules.then_some("schedules"),
         ]
         .into_iter()
         .flatten()
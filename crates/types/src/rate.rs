@@ -12,6 +12,7 @@ use std::{
     fmt::{self, Display},
     num::{NonZeroU32, ParseIntError},
     str::FromStr,
+    time::Duration,
 };
 
 use serde::{Deserialize, Serialize, de::Visitor};
@@ -66,6 +67,27 @@ impl Rate {
             Rate::Hour(rate) => *rate,
         }
     }
+
+    /// Returns the time unit of the rate, i.e. the period in which [`Rate::get`] operations
+    /// are allowed.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use restate_types::rate::Rate;
+    /// use std::num::NonZeroU32;
+    /// use std::time::Duration;
+    ///
+    /// let rate = Rate::Minute(NonZeroU32::new(60).unwrap());
+    /// assert_eq!(rate.period(), Duration::from_secs(60));
+    /// ```
+    pub fn period(&self) -> Duration {
+        match self {
+            Rate::Second(_) => Duration::from_secs(1),
+            Rate::Minute(_) => Duration::from_secs(60),
+            Rate::Hour(_) => Duration::from_secs(3600),
+        }
+    }
 }
 
 impl Display for Rate {
@@ -214,6 +236,58 @@ impl<'de> Deserialize<'de> for Rate {
     }
 }
 
+// -- Bilrost support --
+
+/// Rates are encoded in their display form (e.g. `100/s`).
+mod bilrost_encoding {
+    use std::num::NonZeroU32;
+
+    use bilrost::encoding::{DistinguishedProxiable, ForOverwrite, General, Proxiable};
+    use bilrost::{Canonicity, DecodeErrorKind};
+
+    use super::Rate;
+
+    impl ForOverwrite<(), Rate> for () {
+        fn for_overwrite() -> Rate {
+            Rate::Second(NonZeroU32::MIN)
+        }
+    }
+
+    impl Proxiable for Rate {
+        type Proxy = String;
+
+        fn encode_proxy(&self) -> Self::Proxy {
+            self.to_string()
+        }
+
+        fn decode_proxy(&mut self, proxy: Self::Proxy) -> Result<(), DecodeErrorKind> {
+            *self = proxy.parse().map_err(|_| DecodeErrorKind::InvalidValue)?;
+            Ok(())
+        }
+    }
+
+    impl DistinguishedProxiable for Rate {
+        fn decode_proxy_distinguished(
+            &mut self,
+            proxy: Self::Proxy,
+        ) -> Result<Canonicity, DecodeErrorKind> {
+            *self = proxy.parse().map_err(|_| DecodeErrorKind::InvalidValue)?;
+            // Only the display form (e.g. `100/s`) is canonical
+            Ok(if self.to_string() == proxy {
+                Canonicity::Canonical
+            } else {
+                Canonicity::NotCanonical
+            })
+        }
+    }
+
+    bilrost::delegate_proxied_encoding!(
+        use encoding (General)
+        to encode proxied type (Rate)
+        with general encodings including distinguished
+    );
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -351,4 +425,26 @@ mod tests {
             assert_eq!(original, deserialized);
         }
     }
+
+    #[test]
+    fn bilrost_round_trip() {
+        use bilrost::{Message, OwnedMessage};
+
+        #[derive(Debug, PartialEq, bilrost::Message)]
+        struct EncodedRate {
+            #[bilrost(tag(1))]
+            rate: Option<Rate>,
+        }
+
+        for rate in [
+            None,
+            Some(Rate::Second(NonZeroU32::new(100).unwrap())),
+            Some(Rate::Minute(NonZeroU32::new(60).unwrap())),
+            Some(Rate::Hour(NonZeroU32::new(3600).unwrap())),
+        ] {
+            let value = EncodedRate { rate };
+            let encoded = value.encode_to_bytes();
+            assert_eq!(EncodedRate::decode(encoded).unwrap(), value);
+        }
+    }
 }
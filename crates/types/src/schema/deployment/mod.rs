@@ -15,15 +15,18 @@ pub use http_auth::{GoogleIdTokenAuth, HttpAuth, derive_audience};
 use std::collections::HashMap;
 use std::fmt;
 use std::fmt::{Display, Formatter};
+use std::num::NonZeroU32;
 use std::ops::RangeInclusive;
 
 use crate::config::Configuration;
 use crate::deployment::{
     DeploymentAddress, Headers, HttpDeploymentAddress, LambdaDeploymentAddress,
 };
-use crate::identifiers::{DeploymentId, LambdaARN, ServiceRevision};
+use crate::identifiers::{DeploymentId, InvocationId, LambdaARN, ServiceRevision};
+use crate::invocation::InvocationTarget;
+use crate::rate::Rate;
 use crate::schema::info::SchemaInfo;
-use crate::schema::service::ServiceMetadata;
+use crate::schema::service::{ServiceMetadata, TrafficSplit};
 use crate::time::MillisSinceEpoch;
 use bytestring::ByteString;
 use http::Uri;
@@ -58,6 +61,8 @@ pub struct Deployment {
     ///
     /// List of configuration/deprecation information related to this deployment.
     pub info: Vec<SchemaInfo>,
+    /// Caps on the invocations of this deployment, enforced by the invoker.
+    pub limits: DeploymentLimits,
 }
 
 impl Deployment {
@@ -127,6 +132,78 @@ impl Deployment {
     }
 }
 
+/// Caps on the invocations of a deployment.
+///
+/// Each partition enforces these caps on its own, so the deployment can see up to the number of
+/// partitions times these values across the cluster. `None` on a field means unlimited.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa-schema", derive(utoipa::ToSchema))]
+pub struct DeploymentLimits {
+    /// # Concurrency
+    ///
+    /// Maximum number of concurrently running invocation attempts against this deployment, per partition.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "utoipa-schema", schema(value_type = Option<u32>, minimum = 1))]
+    pub concurrency: Option<NonZeroU32>,
+
+    /// # Rate
+    ///
+    /// Rate at which invocation attempts against this deployment are allowed to start, per partition (e.g. `100/s`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "utoipa-schema", schema(value_type = Option<String>, example = "100/s"))]
+    pub rate: Option<Rate>,
+
+    /// # Burst
+    ///
+    /// Maximum number of invocation attempts that can start in a burst. Only meaningful together
+    /// with `rate`, defaults to the number of attempts allowed per rate period.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "utoipa-schema", schema(value_type = Option<u32>, minimum = 1))]
+    pub burst: Option<NonZeroU32>,
+}
+
+impl DeploymentLimits {
+    pub fn is_unlimited(&self) -> bool {
+        self.concurrency.is_none() && self.rate.is_none()
+    }
+
+    /// The capacity of the token bucket backing [`Self::rate`], or `None` if the rate is
+    /// unlimited.
+    pub fn effective_burst(&self) -> Option<NonZeroU32> {
+        self.rate.map(|rate| self.burst.unwrap_or(rate.get()))
+    }
+
+    /// The limit of the token bucket backing [`Self::rate`], or `None` if the rate is unlimited.
+    pub fn rate_limit(&self) -> Option<gardal::Limit> {
+        let limit = match self.rate? {
+            Rate::Second(rate) => gardal::Limit::per_second(rate),
+            Rate::Minute(rate) => gardal::Limit::per_minute(rate),
+            Rate::Hour(rate) => gardal::Limit::per_hour(rate),
+        };
+        Some(limit.with_burst(self.effective_burst()?))
+    }
+}
+
+impl Display for DeploymentLimits {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.is_unlimited() {
+            return f.write_str("unlimited");
+        }
+        let mut separator = "";
+        if let Some(concurrency) = self.concurrency {
+            write!(f, "concurrency={concurrency}")?;
+            separator = ", ";
+        }
+        if let Some(rate) = self.rate {
+            write!(f, "{separator}rate={rate}")?;
+            if let Some(burst) = self.burst {
+                write!(f, ", burst={burst}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Lambda compression
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(feature = "utoipa-schema", derive(utoipa::ToSchema))]
@@ -232,6 +309,20 @@ pub trait DeploymentResolver {
         service_name: impl AsRef<str>,
     ) -> Option<Deployment>;
 
+    /// Resolves the deployment a new invocation starts on. This is the latest deployment of the
+    /// target service, unless its [`TrafficSplit`] routes the invocation elsewhere.
+    fn resolve_deployment_for_new_invocation(
+        &self,
+        _invocation_id: &InvocationId,
+        invocation_target: &InvocationTarget,
+    ) -> Option<Deployment> {
+        self.resolve_latest_deployment_for_service(invocation_target.service_name())
+    }
+
+    fn resolve_traffic_split(&self, _service_name: impl AsRef<str>) -> Option<TrafficSplit> {
+        None
+    }
+
     fn find_deployment(
         &self,
         deployment_address: &DeploymentAddress,
@@ -466,6 +557,7 @@ pub mod test_util {
                 metadata: Default::default(),
                 additional_headers: Default::default(),
                 info: vec![],
+                limits: Default::default(),
             }
         }
 
@@ -487,6 +579,7 @@ pub mod test_util {
                 metadata: Default::default(),
                 additional_headers: Default::default(),
                 info: vec![],
+                limits: Default::default(),
             }
         }
     }
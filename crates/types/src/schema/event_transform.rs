@@ -0,0 +1,292 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! JSON path predicates and key templates of the [`KAFKA_FILTER_JSON_PATH_PROPERTY`] and
+//! [`KAFKA_KEY_TEMPLATE_PROPERTY`] subscription options. They are parsed when the subscription is
+//! created, to reject invalid options, and by the consumers applying them to the records.
+//!
+//! [`KAFKA_FILTER_JSON_PATH_PROPERTY`]: crate::schema::subscriptions::KAFKA_FILTER_JSON_PATH_PROPERTY
+//! [`KAFKA_KEY_TEMPLATE_PROPERTY`]: crate::schema::subscriptions::KAFKA_KEY_TEMPLATE_PROPERTY
+
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{Context, anyhow, bail};
+use serde_json::Value;
+
+/// Path to a value of a JSON document, in the `$.field[index].field` syntax.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonPath(Vec<PathSegment>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PathSegment {
+    Field(String),
+    Index(usize),
+}
+
+impl JsonPath {
+    /// Returns the value at the path, if any.
+    pub fn select<'v>(&self, mut value: &'v Value) -> Option<&'v Value> {
+        for segment in &self.0 {
+            value = match segment {
+                PathSegment::Field(field) => value.get(field)?,
+                PathSegment::Index(index) => value.get(index)?,
+            };
+        }
+        Some(value)
+    }
+}
+
+impl FromStr for JsonPath {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some(mut rest) = s.trim().strip_prefix('$') else {
+            bail!("JSON path '{s}' must start with '$'");
+        };
+
+        let mut segments = vec![];
+        while !rest.is_empty() {
+            if let Some(after_dot) = rest.strip_prefix('.') {
+                let end = after_dot.find(['.', '[']).unwrap_or(after_dot.len());
+                if end == 0 {
+                    bail!("JSON path '{s}' has an empty field name");
+                }
+                segments.push(PathSegment::Field(after_dot[..end].to_owned()));
+                rest = &after_dot[end..];
+            } else if let Some(after_bracket) = rest.strip_prefix('[') {
+                let end = after_bracket
+                    .find(']')
+                    .ok_or_else(|| anyhow!("JSON path '{s}' has an unclosed '['"))?;
+                let index = after_bracket[..end]
+                    .parse()
+                    .with_context(|| format!("JSON path '{s}' has an invalid array index"))?;
+                segments.push(PathSegment::Index(index));
+                rest = &after_bracket[end + 1..];
+            } else {
+                bail!("JSON path '{s}' has an unexpected character at '{rest}'");
+            }
+        }
+
+        Ok(Self(segments))
+    }
+}
+
+impl fmt::Display for JsonPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("$")?;
+        for segment in &self.0 {
+            match segment {
+                PathSegment::Field(field) => write!(f, ".{field}")?,
+                PathSegment::Index(index) => write!(f, "[{index}]")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Predicate on the JSON payload: `<path>` matches if the value exists and is not null,
+/// `<path> == <json>` and `<path> != <json>` compare the value with a JSON literal.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonPredicate {
+    Exists(JsonPath),
+    Equals(JsonPath, Value),
+    NotEquals(JsonPath, Value),
+}
+
+impl JsonPredicate {
+    pub fn matches(&self, payload: &Value) -> bool {
+        match self {
+            JsonPredicate::Exists(path) => path.select(payload).is_some_and(|v| !v.is_null()),
+            JsonPredicate::Equals(path, expected) => path.select(payload) == Some(expected),
+            JsonPredicate::NotEquals(path, expected) => path.select(payload) != Some(expected),
+        }
+    }
+}
+
+impl FromStr for JsonPredicate {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_literal = |literal: &str| {
+            serde_json::from_str(literal.trim()).with_context(|| {
+                format!(
+                    "'{}' is not a JSON literal, strings must be quoted",
+                    literal.trim()
+                )
+            })
+        };
+
+        // The operator is the first one in the predicate, the literal might contain the other one
+        let operator = ["==", "!="]
+            .into_iter()
+            .filter_map(|operator| s.find(operator).map(|index| (index, operator)))
+            .min();
+        match operator {
+            Some((index, "==")) => Ok(JsonPredicate::Equals(
+                s[..index].parse()?,
+                parse_literal(&s[index + 2..])?,
+            )),
+            Some((index, _)) => Ok(JsonPredicate::NotEquals(
+                s[..index].parse()?,
+                parse_literal(&s[index + 2..])?,
+            )),
+            None => Ok(JsonPredicate::Exists(s.parse()?)),
+        }
+    }
+}
+
+/// Template of the Virtual Object/Workflow key, made of literal text and of the placeholders
+/// `{key}` (the record key), `{header.<name>}` and `{$.<json path>}` (a value of the payload).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyTemplate(Vec<TemplatePart>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TemplatePart {
+    Literal(String),
+    Key,
+    Header(String),
+    Payload(JsonPath),
+}
+
+impl KeyTemplate {
+    /// Returns true if the template has placeholders reading the JSON payload.
+    pub fn reads_payload(&self) -> bool {
+        self.0
+            .iter()
+            .any(|part| matches!(part, TemplatePart::Payload(_)))
+    }
+
+    /// Renders the key of a record, given its key, headers and JSON payload.
+    pub fn render(
+        &self,
+        key: &[u8],
+        headers: &[(&str, &[u8])],
+        payload: Option<&Value>,
+    ) -> anyhow::Result<String> {
+        let mut rendered = String::new();
+        for part in &self.0 {
+            match part {
+                TemplatePart::Literal(literal) => rendered.push_str(literal),
+                TemplatePart::Key => rendered.push_str(
+                    std::str::from_utf8(key).context("the record key must be valid UTF-8")?,
+                ),
+                TemplatePart::Header(name) => {
+                    let (_, value) = headers
+                        .iter()
+                        .find(|(k, _)| k == name)
+                        .ok_or_else(|| anyhow!("the record has no '{name}' header"))?;
+                    rendered.push_str(std::str::from_utf8(value).with_context(|| {
+                        format!("the record header '{name}' must be valid UTF-8")
+                    })?);
+                }
+                TemplatePart::Payload(path) => {
+                    match payload
+                        .and_then(|payload| path.select(payload))
+                        .filter(|v| !v.is_null())
+                    {
+                        Some(Value::String(s)) => rendered.push_str(s),
+                        Some(value) => rendered.push_str(&value.to_string()),
+                        None => bail!("the record payload has no value at '{path}'"),
+                    }
+                }
+            }
+        }
+        Ok(rendered)
+    }
+}
+
+impl FromStr for KeyTemplate {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = vec![];
+        let mut rest = s;
+        while let Some(start) = rest.find('{') {
+            if start > 0 {
+                parts.push(TemplatePart::Literal(rest[..start].to_owned()));
+            }
+            let end = rest[start..]
+                .find('}')
+                .ok_or_else(|| anyhow!("key template '{s}' has an unclosed '{{'"))?
+                + start;
+            let placeholder = &rest[start + 1..end];
+            parts.push(if placeholder == "key" {
+                TemplatePart::Key
+            } else if let Some(name) = placeholder.strip_prefix("header.") {
+                TemplatePart::Header(name.to_owned())
+            } else if placeholder.starts_with('$') {
+                TemplatePart::Payload(placeholder.parse()?)
+            } else {
+                bail!(
+                    "key template '{s}' has an unknown placeholder '{{{placeholder}}}', supported placeholders: {{key}}, {{header.<name>}}, {{$.<json path>}}"
+                );
+            });
+            rest = &rest[end + 1..];
+        }
+        if !rest.is_empty() {
+            parts.push(TemplatePart::Literal(rest.to_owned()));
+        }
+        Ok(Self(parts))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_predicates() {
+        let payload = serde_json::json!({"a": {"b": 1, "c": null}});
+
+        let predicate: JsonPredicate = "$.a.b".parse().unwrap();
+        assert!(predicate.matches(&payload));
+        let predicate: JsonPredicate = "$.a.c".parse().unwrap();
+        assert!(!predicate.matches(&payload));
+        let predicate: JsonPredicate = "$.a.b != 2".parse().unwrap();
+        assert!(predicate.matches(&payload));
+
+        assert!("a.b".parse::<JsonPredicate>().is_err());
+        assert!("$.a == unquoted".parse::<JsonPredicate>().is_err());
+        assert!("$.a[x]".parse::<JsonPredicate>().is_err());
+    }
+
+    #[test]
+    fn json_predicate_operators() {
+        let payload = serde_json::json!({"a": "x==y", "b": "x!=y"});
+
+        let predicate: JsonPredicate = r#"$.a != "x==y""#.parse().unwrap();
+        assert!(!predicate.matches(&payload));
+        let predicate: JsonPredicate = r#"$.b == "x!=y""#.parse().unwrap();
+        assert!(predicate.matches(&payload));
+        let predicate: JsonPredicate = r#"$.b != "x==y""#.parse().unwrap();
+        assert!(predicate.matches(&payload));
+    }
+
+    #[test]
+    fn key_template_placeholders() {
+        let template: KeyTemplate = "{header.tenant}/{$.customer.id}-{key}".parse().unwrap();
+        assert!(template.reads_payload());
+        assert_eq!(
+            template
+                .render(
+                    b"order-1",
+                    &[("tenant", b"acme".as_slice())],
+                    Some(&serde_json::json!({"customer": {"id": 42}})),
+                )
+                .unwrap(),
+            "acme/42-order-1"
+        );
+
+        assert!("{unknown}".parse::<KeyTemplate>().is_err());
+        assert!("{key".parse::<KeyTemplate>().is_err());
+        assert!("{$.a[x]}".parse::<KeyTemplate>().is_err());
+    }
+}
@@ -24,6 +24,7 @@ use crate::invocation::{
     InvocationRetention, InvocationTargetType, ServiceType, WorkflowHandlerType,
 };
 use crate::retries::RetryIter;
+use crate::{RESTATE_VERSION_1_7_1, SemanticRestateVersion};
 
 pub const DEFAULT_IDEMPOTENCY_RETENTION: Duration = Duration::from_secs(60 * 60 * 24);
 pub const DEFAULT_WORKFLOW_COMPLETION_RETENTION: Duration = Duration::from_secs(60 * 60 * 24);
@@ -65,6 +66,20 @@ pub trait InvocationTargetResolver {
     ) -> (RetryIter<'static>, OnMaxAttempts);
 }
 
+/// This API resolves the dead-letter handler of invocations which exhausted their retries.
+pub trait DeadLetterResolver {
+    /// Returns the dead-letter target if the resolved retry policy of the given service handler
+    /// is [`OnMaxAttempts::DeadLetter`] and a target is configured, None otherwise.
+    ///
+    /// If deployment id is not provided, the last service/handler configuration will be applied instead.
+    fn resolve_dead_letter_target(
+        &self,
+        deployment_id: Option<&DeploymentId>,
+        service_name: impl AsRef<str>,
+        handler_name: impl AsRef<str>,
+    ) -> Option<DeadLetterTarget>;
+}
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(feature = "utoipa-schema", derive(utoipa::ToSchema))]
 pub enum OnMaxAttempts {
@@ -73,6 +88,42 @@ pub enum OnMaxAttempts {
     Pause,
     /// Kill the invocation when max attempts are reached.
     Kill,
+    /// Kill the invocation when max attempts are reached, and send its input, headers and last
+    /// failure to the configured [`DeadLetterTarget`].
+    ///
+    /// If no dead-letter target is configured, this behaves like [`OnMaxAttempts::Kill`].
+    DeadLetter,
+}
+
+impl OnMaxAttempts {
+    /// The minimum Restate-server version required to decode a schema holding this value. It
+    /// must only be set once every node of the cluster runs at least this version.
+    pub fn min_required_version(&self) -> Option<&'static SemanticRestateVersion> {
+        match self {
+            Self::DeadLetter => Some(&RESTATE_VERSION_1_7_1),
+            Self::Pause | Self::Kill => None,
+        }
+    }
+}
+
+/// Service handler receiving the invocations that exhausted their retries.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa-schema", derive(utoipa::ToSchema))]
+pub struct DeadLetterTarget {
+    /// # Service
+    ///
+    /// Name of the dead-letter service. Must be a service of type `Service`.
+    pub service: String,
+    /// # Handler
+    ///
+    /// Name of the dead-letter handler.
+    pub handler: String,
+}
+
+impl fmt::Display for DeadLetterTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.service, self.handler)
+    }
 }
 
 #[derive(Debug, Clone, Default)]
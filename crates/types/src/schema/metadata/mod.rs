@@ -13,7 +13,7 @@ mod serde_hacks;
 pub mod updater;
 
 use std::collections::HashMap;
-use std::num::NonZeroUsize;
+use std::num::{NonZeroU32, NonZeroUsize};
 use std::ops::RangeInclusive;
 use std::sync::Arc;
 use std::time::Duration;
@@ -32,28 +32,35 @@ use crate::config::{Configuration, InvocationRetryPolicyOptions};
 use crate::deployment::{
     DeploymentAddress, Headers, HttpDeploymentAddress, LambdaDeploymentAddress,
 };
-use crate::identifiers::{DeploymentId, SubscriptionId};
-use crate::invocation::{InvocationTargetType, ServiceType, WorkflowHandlerType};
+use crate::identifiers::{DeploymentId, InvocationId, ScheduleId, SubscriptionId};
+use crate::invocation::{
+    InvocationTarget, InvocationTargetType, ServiceType, VirtualObjectHandlerType,
+    WorkflowHandlerType,
+};
 use crate::live::Pinned;
 use crate::metadata::GlobalMetadata;
 use crate::net::address::{AdvertisedAddress, HttpIngressPort};
 use crate::net::metadata::{MetadataContainer, MetadataKind};
+use crate::rate::Rate;
 use crate::retries::{RetryIter, RetryPolicy};
 use crate::schema::deployment::{DeploymentResolver, DeploymentType, ProtocolType};
 use crate::schema::info::SchemaInfo;
 use crate::schema::invocation_target::{
-    DeploymentStatus, InputRules, InvocationAttemptOptions, InvocationTargetMetadata,
-    InvocationTargetResolver, OnMaxAttempts, OutputRules,
+    DeadLetterResolver, DeadLetterTarget, DeploymentStatus, InputRules, InvocationAttemptOptions,
+    InvocationTargetMetadata, InvocationTargetResolver, OnMaxAttempts, OutputRules,
 };
 use crate::schema::kafka::{
     DUPLICATED_KAFKA_CLUSTER_INFO_MESSAGE, KafkaCluster, KafkaClusterResolver,
 };
 use crate::schema::metadata::openapi::ServiceOpenAPI;
+use crate::schema::schedules::{Schedule, ScheduleResolver};
 use crate::schema::service::{
-    HandlerRetryPolicyMetadata, ServiceMetadataResolver, ServiceRetryPolicyMetadata,
+    CanaryRollbackPolicy, HandlerRetryPolicyMetadata, ServiceMetadataResolver,
+    ServiceRetryPolicyMetadata,
 };
 use crate::schema::subscriptions::{
-    ListSubscriptionFilter, Source, Subscription, SubscriptionResolver,
+    ListSubscriptionFilter, NATS_TOKEN_PROPERTY, Source, Subscription, SubscriptionResolver,
+    VIRTUAL_TOPIC_SERVICE_NAME,
 };
 use crate::schema::{Redaction, deployment, service};
 use crate::service_protocol::ServiceProtocolVersion;
@@ -74,6 +81,7 @@ pub struct Schema {
     active_service_revisions: HashMap<String, ActiveServiceRevision>,
     subscriptions: HashMap<SubscriptionId, Subscription>,
     kafka_clusters: HashMap<String, KafkaCluster>,
+    schedules: HashMap<ScheduleId, Schedule>,
 
     // If legacy is true, it means the schema raw data is
     // still using v1 schema model. Schema should be migrated.
@@ -88,6 +96,7 @@ impl Default for Schema {
             deployments: HashMap::default(),
             subscriptions: HashMap::default(),
             kafka_clusters: HashMap::default(),
+            schedules: HashMap::default(),
             legacy_v1: false,
         }
     }
@@ -105,6 +114,51 @@ impl Schema {
     pub fn touch(&mut self) {
         self.version = self.version.next();
     }
+
+    /// Returns a new schema where the traffic split of the service towards the given canary
+    /// deployment is rolled back, or `None` if there is no such split left to roll back.
+    pub fn roll_back_traffic_split(
+        &self,
+        service_name: &str,
+        canary_deployment_id: DeploymentId,
+    ) -> Option<Schema> {
+        let (rolled_back, schema) =
+            updater::SchemaUpdater::update_and_return(self.clone(), |updater| {
+                updater.roll_back_traffic_split(service_name, canary_deployment_id)
+            })
+            .ok()?;
+        rolled_back.then_some(schema)
+    }
+
+    /// Returns the limits of the deployment, or `None` if there is no such deployment.
+    pub fn deployment_limits(
+        &self,
+        deployment_id: &DeploymentId,
+    ) -> Option<deployment::DeploymentLimits> {
+        self.deployments.get(deployment_id).map(|dp| {
+            dp.limits
+                .map(DeploymentLimits::to_deployment_limits)
+                .unwrap_or_default()
+        })
+    }
+
+    /// Returns the deployment a new invocation of the service is routed to. Unlike
+    /// [`DeploymentResolver::resolve_deployment_for_new_invocation`], this doesn't check whether
+    /// the deployment the traffic split routes to exposes the invoked handler.
+    pub fn route_new_invocation(
+        &self,
+        service_name: impl AsRef<str>,
+        invocation_id: &InvocationId,
+    ) -> Option<DeploymentId> {
+        let active_service_revision = self.active_service_revisions.get(service_name.as_ref())?;
+        Some(
+            active_service_revision
+                .traffic_split()
+                .map_or(active_service_revision.deployment_id, |split| {
+                    split.route(invocation_id)
+                }),
+        )
+    }
 }
 
 impl GlobalMetadata for Schema {
@@ -140,6 +194,13 @@ struct ActiveServiceRevision {
 }
 
 impl ActiveServiceRevision {
+    fn traffic_split(&self) -> Option<service::TrafficSplit> {
+        self.service_revision
+            .traffic_split
+            .as_ref()
+            .map(|split| split.to_traffic_split(self.deployment_id))
+    }
+
     fn as_service_metadata(
         &self,
         served_using_protocol_type: Option<ProtocolType>,
@@ -192,10 +253,36 @@ impl DeliveryOptions {
 /// Since v1.7.0
 #[derive(Debug, Clone, Copy, serde::Serialize, serde::Deserialize)]
 pub struct DeploymentLimits {
-    /// Maximum number of concurrent invocations per node for this deployment.
+    /// Maximum number of concurrent invocations per partition for this deployment.
     /// A value of 0 means unlimited.
     #[serde(default)]
     pub invocations: u64,
+    /// Since v1.7.1
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate: Option<Rate>,
+    /// Since v1.7.1
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub burst: Option<NonZeroU32>,
+}
+
+impl DeploymentLimits {
+    fn from_deployment_limits(limits: deployment::DeploymentLimits) -> Option<Self> {
+        (!limits.is_unlimited()).then(|| DeploymentLimits {
+            invocations: limits.concurrency.map_or(0, |c| u64::from(c.get())),
+            rate: limits.rate,
+            burst: limits.burst,
+        })
+    }
+
+    fn to_deployment_limits(self) -> deployment::DeploymentLimits {
+        deployment::DeploymentLimits {
+            concurrency: u32::try_from(self.invocations)
+                .ok()
+                .and_then(NonZeroU32::new),
+            rate: self.rate,
+            burst: self.burst,
+        }
+    }
 }
 
 #[serde_as]
@@ -239,6 +326,10 @@ impl Deployment {
             metadata: self.metadata.clone(),
             additional_headers: self.delivery_options.additional_headers.clone(),
             info: vec![],
+            limits: self
+                .limits
+                .map(DeploymentLimits::to_deployment_limits)
+                .unwrap_or_default(),
         }
     }
     /// This returns true if the two deployments are to be considered the "same".
@@ -273,6 +364,31 @@ impl Deployment {
     }
 }
 
+/// Since v1.7.1
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct TrafficSplit {
+    /// Deployment receiving the new invocations not routed to the deployment of this revision.
+    stable_deployment_id: DeploymentId,
+    /// Percentage of new invocations routed to the deployment of this revision.
+    canary_weight: u8,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rollback: Option<CanaryRollbackPolicy>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    rolled_back: bool,
+}
+
+impl TrafficSplit {
+    fn to_traffic_split(&self, canary_deployment_id: DeploymentId) -> service::TrafficSplit {
+        service::TrafficSplit {
+            canary_deployment_id,
+            stable_deployment_id: self.stable_deployment_id,
+            canary_weight: self.canary_weight,
+            rollback: self.rollback,
+            rolled_back: self.rolled_back,
+        }
+    }
+}
+
 #[serde_as]
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 struct ServiceRevision {
@@ -378,6 +494,12 @@ struct ServiceRevision {
     retry_policy_max_interval: Option<Duration>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     retry_policy_on_max_attempts: Option<OnMaxAttempts>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    retry_policy_dead_letter: Option<DeadLetterTarget>,
+
+    /// Split of new invocations with a deployment exposing a previous revision.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    traffic_split: Option<TrafficSplit>,
 
     /// This is a cache for the computed value of ServiceOpenAPI
     #[serde(skip)]
@@ -494,6 +616,10 @@ impl ServiceRevision {
             metadata: self.metadata.clone(),
             deployment_id,
             revision: self.revision,
+            traffic_split: self
+                .traffic_split
+                .as_ref()
+                .map(|split| split.to_traffic_split(deployment_id)),
             public: self.public,
             idempotency_retention: idempotency_retention.unwrap_or(Duration::ZERO),
             workflow_completion_retention,
@@ -588,6 +714,8 @@ struct Handler {
     retry_policy_max_interval: Option<Duration>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     retry_policy_on_max_attempts: Option<OnMaxAttempts>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    retry_policy_dead_letter: Option<DeadLetterTarget>,
 }
 
 impl MapAsVecItem for Handler {
@@ -663,6 +791,7 @@ impl Handler {
                 max_attempts: self.retry_policy_max_attempts,
                 max_interval: self.retry_policy_max_interval,
                 on_max_attempts: self.retry_policy_on_max_attempts,
+                dead_letter: self.retry_policy_dead_letter.clone(),
             },
             info,
         }
@@ -683,6 +812,40 @@ impl DeploymentResolver for Schema {
             .map(|dp| dp.to_deployment())
     }
 
+    fn resolve_deployment_for_new_invocation(
+        &self,
+        invocation_id: &InvocationId,
+        invocation_target: &InvocationTarget,
+    ) -> Option<deployment::Deployment> {
+        let service_name: &str = invocation_target.service_name();
+        let handler_name: &str = invocation_target.handler_name();
+        let active_service_revision = self.active_service_revisions.get(service_name)?;
+        let deployment_id = active_service_revision
+            .traffic_split()
+            .map(|split| split.route(invocation_id))
+            // The stable deployment might have been removed in the meantime, or might not
+            // expose the invoked handler.
+            .filter(|deployment_id| {
+                self.deployments
+                    .get(deployment_id)
+                    .and_then(|dp| dp.services.get(service_name))
+                    .is_some_and(|svc| svc.handlers.contains_key(handler_name))
+            })
+            .unwrap_or(active_service_revision.deployment_id);
+        self.deployments
+            .get(&deployment_id)
+            .map(|dp| dp.to_deployment())
+    }
+
+    fn resolve_traffic_split(
+        &self,
+        service_name: impl AsRef<str>,
+    ) -> Option<service::TrafficSplit> {
+        self.active_service_revisions
+            .get(service_name.as_ref())?
+            .traffic_split()
+    }
+
     fn find_deployment(
         &self,
         deployment_address: &DeploymentAddress,
@@ -757,6 +920,10 @@ impl InvocationTargetResolver for Schema {
         let service_name = service_name.as_ref();
         let handler_name = handler_name.as_ref();
 
+        if service_name == VIRTUAL_TOPIC_SERVICE_NAME {
+            return self.resolve_virtual_topic(handler_name);
+        }
+
         let ActiveServiceRevision {
             service_revision,
             deployment_id,
@@ -882,6 +1049,68 @@ impl InvocationTargetResolver for Schema {
         service_name: impl AsRef<str>,
         handler_name: impl AsRef<str>,
     ) -> (RetryIter<'static>, OnMaxAttempts) {
+        let retry_policy = self.compute_retry_policy(deployment_id, service_name, handler_name);
+        (
+            retry_policy.as_retry_policy().into_iter(),
+            retry_policy.on_max_attempts,
+        )
+    }
+}
+
+impl DeadLetterResolver for Schema {
+    fn resolve_dead_letter_target(
+        &self,
+        deployment_id: Option<&DeploymentId>,
+        service_name: impl AsRef<str>,
+        handler_name: impl AsRef<str>,
+    ) -> Option<DeadLetterTarget> {
+        let retry_policy = self.compute_retry_policy(deployment_id, service_name, handler_name);
+        if retry_policy.on_max_attempts == OnMaxAttempts::DeadLetter {
+            retry_policy.dead_letter
+        } else {
+            None
+        }
+    }
+}
+
+impl Schema {
+    /// Resolves a one-way call to the virtual topic `name`, which exists as long as at least one
+    /// subscription publishes it. The call is never executed: the shuffle of the calling partition
+    /// publishes it to the Kafka sinks of the topic instead.
+    fn resolve_virtual_topic(&self, name: &str) -> Option<InvocationTargetMetadata> {
+        self.subscriptions
+            .values()
+            .any(|sub| matches!(sub.source(), Source::Topic { name: topic } if topic == name))
+            .then(|| InvocationTargetMetadata {
+                // Virtual topics can be published to only from handlers
+                public: false,
+                completion_retention: Duration::ZERO,
+                journal_retention: Duration::ZERO,
+                // The key of the call is the key of the published record, and it's optional
+                target_ty: InvocationTargetType::VirtualObject(VirtualObjectHandlerType::Shared),
+                input_rules: InputRules::default(),
+                output_rules: OutputRules::default(),
+                deployment_status: DeploymentStatus::Enabled,
+            })
+    }
+
+    /// Returns true if an egress subscription publishes the outputs of the given handler.
+    pub fn publishes_output_of(&self, service_name: &str, handler_name: &str) -> bool {
+        self.subscriptions.values().any(|sub| {
+            matches!(
+                sub.source(),
+                Source::Output { service, handler }
+                    if service == service_name && handler == handler_name
+            )
+        })
+    }
+
+    fn compute_retry_policy(
+        &self,
+        deployment_id: Option<&DeploymentId>,
+        service_name: impl AsRef<str>,
+        handler_name: impl AsRef<str>,
+    ) -> ComputedRetryPolicy {
         let configuration = Configuration::pinned();
         let mut retry_policy = configuration.resolve_default_retry_policy();
 
@@ -894,29 +1123,20 @@ impl InvocationTargetResolver for Schema {
                 .get(service_name.as_ref())
                 .map(|a| &a.service_revision)
         }) else {
-            return (
-                retry_policy.as_retry_policy().into_iter(),
-                retry_policy.on_max_attempts,
-            );
+            return retry_policy;
         };
 
         retry_policy.merge_with_service_revision_overrides(service_revision);
 
         let Some(handler) = service_revision.handlers.get(handler_name.as_ref()) else {
-            return (
-                retry_policy.as_retry_policy().into_iter(),
-                retry_policy.on_max_attempts,
-            );
+            return retry_policy;
         };
 
         retry_policy.merge_with_handler_overrides(handler);
 
         retry_policy.max_attempts = configuration.clamp_max_attempts(retry_policy.max_attempts);
 
-        (
-            retry_policy.as_retry_policy().into_iter(),
-            retry_policy.on_max_attempts,
-        )
+        retry_policy
     }
 }
 
@@ -1037,10 +1257,7 @@ impl KafkaClusterResolver for Schema {
         let subscriptions = self
             .subscriptions
             .values()
-            .filter(|sub| {
-                let Source::Kafka { cluster, .. } = sub.source();
-                cluster == cluster_name
-            })
+            .filter(|sub| sub.kafka_cluster() == Some(cluster_name))
             .map(|sub| sub.clone().redact(redact_secrets))
             .collect();
         Some((cluster, subscriptions))
@@ -1077,6 +1294,16 @@ impl KafkaClusterResolver for Schema {
     }
 }
 
+impl ScheduleResolver for Schema {
+    fn get_schedule(&self, id: ScheduleId) -> Option<Schedule> {
+        self.schedules.get(&id).cloned()
+    }
+
+    fn list_schedules(&self) -> Vec<Schedule> {
+        self.schedules.values().cloned().collect()
+    }
+}
+
 const REDACTION_VALUE: &str = "***";
 
 impl KafkaCluster {
@@ -1102,7 +1329,7 @@ impl Subscription {
             Redaction::Yes => {
                 let redacted_properties = self.metadata_mut();
                 for (key, value) in redacted_properties.iter_mut() {
-                    if is_sensitive_kafka_property(key) {
+                    if is_sensitive_kafka_property(key) || key == NATS_TOKEN_PROPERTY {
                         *value = REDACTION_VALUE.to_string();
                     }
                 }
@@ -1238,6 +1465,9 @@ impl ComputedRetryPolicy {
         if let Some(on_max_attempts) = service_revision.retry_policy_on_max_attempts {
             self.on_max_attempts = on_max_attempts;
         }
+        if let Some(dead_letter) = &service_revision.retry_policy_dead_letter {
+            self.dead_letter = Some(dead_letter.clone());
+        }
     }
 
     fn merge_with_handler_overrides(&mut self, handler: &Handler) {
@@ -1256,6 +1486,9 @@ impl ComputedRetryPolicy {
         if let Some(on_max_attempts) = handler.retry_policy_on_max_attempts {
             self.on_max_attempts = on_max_attempts;
         }
+        if let Some(dead_letter) = &handler.retry_policy_dead_letter {
+            self.dead_letter = Some(dead_letter.clone());
+        }
     }
 
     pub(super) fn as_retry_policy(&self) -> RetryPolicy {
@@ -1293,6 +1526,7 @@ impl Configuration {
                 crate::config::OnMaxAttempts::Pause => OnMaxAttempts::Pause,
                 crate::config::OnMaxAttempts::Kill => OnMaxAttempts::Kill,
             },
+            dead_letter: None,
         }
     }
 }
@@ -1306,11 +1540,125 @@ mod test_util {
 
     use super::service::ServiceMetadata;
     use super::service::ServiceMetadataResolver;
+    use super::updater::{
+        AddDeploymentRequest, AllowBreakingChanges, ModifyServiceRequest, Overwrite, SchemaUpdater,
+    };
+    use crate::deployment::DeploymentAddress;
+    use crate::endpoint_manifest;
     use crate::identifiers::ServiceRevision;
+    use crate::schema::deployment::ProtocolType;
+    use crate::schema::invocation_target::{DeadLetterTarget, OnMaxAttempts};
+    use crate::schema::registry::{DeploymentConnectionParameters, DiscoveryResponse};
     use crate::schema::service::HandlerMetadata;
+    use crate::service_protocol::{
+        MAX_INFLIGHT_SERVICE_PROTOCOL_VERSION, MIN_INFLIGHT_SERVICE_PROTOCOL_VERSION,
+    };
     use restate_test_util::assert_eq;
 
+    fn mock_service(name: &str, handler: &str) -> endpoint_manifest::Service {
+        endpoint_manifest::Service {
+            abort_timeout: None,
+            documentation: None,
+            ingress_private: None,
+            ty: endpoint_manifest::ServiceType::Service,
+            name: name.parse().unwrap(),
+            retry_policy_exponentiation_factor: None,
+            retry_policy_initial_interval: None,
+            retry_policy_max_attempts: None,
+            retry_policy_max_interval: None,
+            handlers: vec![endpoint_manifest::Handler {
+                abort_timeout: None,
+                documentation: None,
+                idempotency_retention: None,
+                name: handler.parse().unwrap(),
+                ty: None,
+                input: None,
+                output: None,
+                retry_policy_exponentiation_factor: None,
+                retry_policy_initial_interval: None,
+                retry_policy_max_attempts: None,
+                retry_policy_max_interval: None,
+                metadata: Default::default(),
+                inactivity_timeout: None,
+                journal_retention: None,
+                workflow_completion_retention: None,
+                enable_lazy_state: None,
+                ingress_private: None,
+                retry_policy_on_max_attempts: None,
+            }],
+            idempotency_retention: None,
+            inactivity_timeout: None,
+            journal_retention: None,
+            metadata: Default::default(),
+            enable_lazy_state: None,
+            retry_policy_on_max_attempts: None,
+        }
+    }
+
     impl Schema {
+        /// Registers a deployment with a service of type `Service` for each `(service, handler)`.
+        pub fn with_mock_services(self, services: &[(&str, &str)]) -> Self {
+            SchemaUpdater::update(self, |updater| {
+                updater
+                    .add_deployment(AddDeploymentRequest {
+                        deployment_address: DeploymentAddress::mock(),
+                        additional_headers: Default::default(),
+                        metadata: Default::default(),
+                        discovery_response: DiscoveryResponse {
+                            deployment_type_parameters: DeploymentConnectionParameters::Http {
+                                protocol_type: ProtocolType::BidiStream,
+                                http_version: http::Version::HTTP_2,
+                            },
+                            supported_protocol_versions: (MIN_INFLIGHT_SERVICE_PROTOCOL_VERSION
+                                as i32)
+                                ..=(MAX_INFLIGHT_SERVICE_PROTOCOL_VERSION as i32),
+                            sdk_version: None,
+                            services: services
+                                .iter()
+                                .map(|(name, handler)| mock_service(name, handler))
+                                .collect(),
+                        },
+                        allow_breaking_changes: AllowBreakingChanges::No,
+                        overwrite: Overwrite::No,
+                    })
+                    .map(|_| ())
+            })
+            .expect("mock services must be valid")
+        }
+
+        /// Forwards the invocations of `service_name` exhausting their retries to `dead_letter`.
+        pub fn with_mock_dead_letter(
+            self,
+            service_name: &str,
+            dead_letter: DeadLetterTarget,
+        ) -> Self {
+            SchemaUpdater::update(self, |updater| {
+                updater.modify_service(
+                    service_name,
+                    ModifyServiceRequest {
+                        retry_policy_on_max_attempts: Some(OnMaxAttempts::DeadLetter),
+                        retry_policy_dead_letter: Some(dead_letter),
+                        ..ModifyServiceRequest::default()
+                    },
+                )
+            })
+            .expect("mock dead-letter target must be valid")
+        }
+
+        /// Adds or replaces the subscription, bumping the schema version like a schema update would.
+        pub fn with_mock_subscription(mut self, subscription: Subscription) -> Self {
+            self.subscriptions.insert(subscription.id(), subscription);
+            self.version = self.version.next();
+            self
+        }
+
+        /// Adds or replaces the schedule, bumping the schema version like a schema update would.
+        pub fn with_mock_schedule(mut self, schedule: Schedule) -> Self {
+            self.schedules.insert(schedule.id(), schedule);
+            self.version = self.version.next();
+            self
+        }
+
         #[track_caller]
         pub fn assert_invocation_target(
             &self,
@@ -15,7 +15,7 @@ use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 use serde_with::serde_as;
 
-use crate::identifiers::DeploymentId;
+use crate::identifiers::{DeploymentId, ScheduleId};
 use crate::invocation::{VirtualObjectHandlerType, WorkflowHandlerType};
 
 mod v1_data_model {
@@ -390,6 +390,11 @@ pub struct Schema {
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     #[serde_as(as = "restate_serde_util::MapAsVec")]
     kafka_clusters: HashMap<String, KafkaCluster>,
+
+    // Schedules
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    #[serde_as(as = "restate_serde_util::MapAsVec")]
+    schedules: HashMap<ScheduleId, Schedule>,
 }
 
 impl restate_serde_util::MapAsVecItem for KafkaCluster {
@@ -400,6 +405,14 @@ impl restate_serde_util::MapAsVecItem for KafkaCluster {
     }
 }
 
+impl restate_serde_util::MapAsVecItem for Schedule {
+    type Key = ScheduleId;
+
+    fn key(&self) -> Self::Key {
+        self.id()
+    }
+}
+
 impl From<super::Schema> for Schema {
     fn from(
         super::Schema {
@@ -407,6 +420,7 @@ impl From<super::Schema> for Schema {
             deployments,
             subscriptions,
             kafka_clusters,
+            schedules,
             ..
         }: super::Schema,
     ) -> Self {
@@ -417,6 +431,7 @@ impl From<super::Schema> for Schema {
             version,
             subscriptions,
             kafka_clusters,
+            schedules,
         }
     }
 }
@@ -430,6 +445,7 @@ impl From<Schema> for super::Schema {
             version,
             subscriptions,
             kafka_clusters,
+            schedules,
         }: Schema,
     ) -> Self {
         if let Some(deployments_v2) = deployments_v2 {
@@ -442,6 +458,7 @@ impl From<Schema> for super::Schema {
                     .collect(),
                 subscriptions,
                 kafka_clusters,
+                schedules,
                 legacy_v1: false,
             }
         } else if let (Some(services), Some(deployments)) = (services, deployments) {
@@ -460,6 +477,7 @@ impl From<Schema> for super::Schema {
                     .collect(),
                 subscriptions,
                 kafka_clusters,
+                schedules,
                 legacy_v1: true,
             }
         } else {
@@ -532,6 +550,7 @@ mod conversions {
                             retry_policy_max_attempts: None,
                             retry_policy_max_interval: None,
                             retry_policy_on_max_attempts: None,
+                            retry_policy_dead_letter: None,
                         };
                         v2_handlers.insert(handler_name, handler);
                     }
@@ -556,6 +575,8 @@ mod conversions {
                         retry_policy_max_attempts: None,
                         retry_policy_max_interval: None,
                         retry_policy_on_max_attempts: None,
+                        retry_policy_dead_letter: None,
+                        traffic_split: None,
                         service_openapi_cache: Arc::new(Default::default()),
                     };
 
@@ -872,6 +893,8 @@ mod conversions {
                                     retry_policy_max_attempts: None,
                                     retry_policy_max_interval: None,
                                     retry_policy_on_max_attempts: None,
+                                    retry_policy_dead_letter: None,
+                                    traffic_split: None,
                                     service_openapi_cache: Arc::new(Default::default()),
                                     handlers: HashMap::from([(
                                         "greet".to_owned(),
@@ -894,6 +917,7 @@ mod conversions {
                                             retry_policy_max_attempts: None,
                                             retry_policy_max_interval: None,
                                             retry_policy_on_max_attempts: None,
+                                            retry_policy_dead_letter: None,
                                         },
                                     )]),
                                 }),
@@ -918,6 +942,8 @@ mod conversions {
                                     retry_policy_max_attempts: None,
                                     retry_policy_max_interval: None,
                                     retry_policy_on_max_attempts: None,
+                                    retry_policy_dead_letter: None,
+                                    traffic_split: None,
                                     service_openapi_cache: Arc::new(Default::default()),
                                     handlers: HashMap::from([
                                         (
@@ -943,6 +969,7 @@ mod conversions {
                                                 retry_policy_max_attempts: None,
                                                 retry_policy_max_interval: None,
                                                 retry_policy_on_max_attempts: None,
+                                                retry_policy_dead_letter: None,
                                             },
                                         ),
                                         (
@@ -968,6 +995,7 @@ mod conversions {
                                                 retry_policy_max_attempts: None,
                                                 retry_policy_max_interval: None,
                                                 retry_policy_on_max_attempts: None,
+                                                retry_policy_dead_letter: None,
                                             },
                                         ),
                                     ]),
@@ -1009,6 +1037,8 @@ mod conversions {
                                 retry_policy_max_attempts: None,
                                 retry_policy_max_interval: None,
                                 retry_policy_on_max_attempts: None,
+                                retry_policy_dead_letter: None,
+                                traffic_split: None,
                                 service_openapi_cache: Arc::new(Default::default()),
                                 handlers: HashMap::from([(
                                     "greet".to_owned(),
@@ -1031,6 +1061,7 @@ mod conversions {
                                         retry_policy_max_attempts: None,
                                         retry_policy_max_interval: None,
                                         retry_policy_on_max_attempts: None,
+                                        retry_policy_dead_letter: None,
                                     },
                                 )]),
                             }),
@@ -9,30 +9,43 @@
 // by the Apache License, Version 2.0.
 
 use super::{
-    ActiveServiceRevision, DeliveryOptions, Deployment, Handler, KafkaCluster, Schema,
-    ServiceRevision,
+    ActiveServiceRevision, DeliveryOptions, Deployment, DeploymentLimits, Handler, KafkaCluster,
+    Schema, ServiceRevision, TrafficSplit,
 };
 
 use crate::config::Configuration;
 use crate::deployment::{DeploymentAddress, Headers};
 use crate::endpoint_manifest::HandlerType;
 use crate::errors::GenericError;
-use crate::identifiers::{DeploymentId, SubscriptionId};
+use crate::identifiers::{DeploymentId, ScheduleId, SubscriptionId};
 use crate::invocation::{
     InvocationTargetType, ServiceType, VirtualObjectHandlerType, WorkflowHandlerType,
 };
 use crate::schema::Redaction;
 use crate::schema::deployment::DeploymentType;
+use crate::schema::event_transform::{JsonPredicate, KeyTemplate};
 use crate::schema::invocation_target::{
-    BadInputContentType, InputRules, InputValidationRule, OnMaxAttempts, OutputContentTypeRule,
-    OutputRules,
+    BadInputContentType, DeadLetterTarget, InputRules, InputValidationRule, OnMaxAttempts,
+    OutputContentTypeRule, OutputRules,
 };
 use crate::schema::kafka::{KafkaClusterName, KafkaClusterResolver};
 use crate::schema::registry::{DeploymentConnectionParameters, DiscoveryResponse};
-use crate::schema::subscriptions::{EventInvocationTargetTemplate, Sink, Source, Subscription};
+use crate::schema::schedules::{
+    CronSchedule, DEFAULT_SCHEDULE_TIMEZONE, OverlapPolicy, Schedule, ScheduleExpressionError,
+    ScheduleTarget,
+};
+use crate::schema::service::CanaryRollbackPolicy;
+use crate::schema::subscriptions::{
+    ConsumptionState, EventInvocationTargetTemplate, KAFKA_DECODE_MESSAGE_PROPERTY,
+    KAFKA_DECODE_PROPERTY, KAFKA_DECODE_SCHEMA_FILE_PROPERTY, KAFKA_DECODE_WIRE_FORMAT_PROPERTY,
+    KAFKA_FILTER_JSON_PATH_PROPERTY, KAFKA_KEY_TEMPLATE_PROPERTY, NATS_CONSUMER_PROPERTY,
+    SeekPosition, Sink, Source, Subscription, WEBHOOK_ALLOW_UNAUTHENTICATED_PROPERTY,
+    WEBHOOK_SECRET_PROPERTY,
+};
 use crate::time::MillisSinceEpoch;
 use crate::{deployment, endpoint_manifest, identifiers};
 use bilrost::encoding::Collection;
+use bytes::Bytes;
 use http::{HeaderValue, Uri};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -97,6 +110,12 @@ pub(in crate::schema) enum SchemaError {
         #[code]
         KafkaClusterError,
     ),
+    #[error(transparent)]
+    Schedule(
+        #[from]
+        #[code]
+        ScheduleError,
+    ),
 }
 
 #[derive(Debug, thiserror::Error, codederror::CodedError)]
@@ -147,6 +166,26 @@ pub(in crate::schema) enum ServiceError {
     #[error("modifying retention time for service type {0} is unsupported")]
     #[code(unknown)]
     CannotModifyRetentionTime(ServiceType),
+    #[error("cannot find the dead-letter handler '{0}'")]
+    #[code(unknown)]
+    DeadLetterTargetNotFound(DeadLetterTarget),
+    #[error("the dead-letter handler '{0}' must belong to a service of type {t}, but it is {1}", t = ServiceType::Service)]
+    #[code(unknown)]
+    BadDeadLetterTargetType(DeadLetterTarget, ServiceType),
+    #[error("the service '{0}' cannot use one of its own handlers as dead-letter handler")]
+    #[code(unknown)]
+    SelfReferencingDeadLetterTarget(String),
+    #[error("the traffic split canary weight must be between 0 and 100, got {0}")]
+    #[code(unknown)]
+    BadCanaryWeight(u8),
+    #[error("the canary rollback max failure rate must be between 0 and 100, got {0}")]
+    #[code(unknown)]
+    BadCanaryMaxFailureRate(u8),
+    #[error(
+        "the stable deployment of the traffic split for service '{0}' must be a deployment exposing a previous revision of the service, but it is {1}"
+    )]
+    #[code(unknown)]
+    BadStableDeployment(String, DeploymentId),
 }
 
 #[derive(Debug, thiserror::Error, codederror::CodedError)]
@@ -156,18 +195,56 @@ pub(in crate::schema) enum SubscriptionError {
     Override(SubscriptionId),
 
     #[error(
-        "invalid source URI '{0}': must have a scheme segment, with supported schemes: [kafka]."
+        "invalid source URI '{0}': must have a scheme segment, with supported schemes: [kafka, nats, webhook, topic, output]."
     )]
     InvalidSourceScheme(Uri),
     #[error(
         "invalid source URI '{0}': source URI of Kafka type must have a authority segment containing the cluster name."
     )]
     InvalidKafkaSourceAuthority(Uri),
+    #[error(
+        "invalid source URI '{0}': source URI of NATS type must have a authority segment containing the server address."
+    )]
+    InvalidNatsSourceAuthority(Uri),
+    #[error(
+        "invalid source URI '{0}': source URI of NATS type must have a path segment containing a valid JetStream stream name."
+    )]
+    InvalidNatsSourceStream(Uri),
+    #[error(
+        "invalid source URI '{0}': source URI of webhook type must have a authority segment containing the webhook name."
+    )]
+    InvalidWebhookSourceAuthority(Uri),
+    #[error(
+        "invalid source URI '{0}': source URI of topic type must have a authority segment containing the topic name."
+    )]
+    InvalidTopicSourceAuthority(Uri),
+    #[error(
+        "invalid source URI '{0}': source URI of output type must have a authority segment containing the service name, and a path segment containing the handler name."
+    )]
+    InvalidOutputSourceUri(Uri),
+    #[error("invalid source URI '{0}': cannot find service/handler specified in the source URI.")]
+    OutputSourceServiceNotFound(Uri),
+    #[error(
+        "webhook subscriptions require the '{WEBHOOK_SECRET_PROPERTY}' option, or '{WEBHOOK_ALLOW_UNAUTHENTICATED_PROPERTY}=true' to accept requests without bearer token."
+    )]
+    MissingWebhookSecret,
+    #[error(
+        "invalid '{WEBHOOK_ALLOW_UNAUTHENTICATED_PROPERTY}' option '{0}': must be either 'true' or 'false'."
+    )]
+    InvalidWebhookAllowUnauthenticated(String),
 
     #[error(
-        "invalid sink URI '{0}': must have a scheme segment, with supported schemes: [service]."
+        "invalid sink URI '{0}': must have a scheme segment, with supported schemes: [service, kafka]."
     )]
     InvalidSinkScheme(Uri),
+    #[error(
+        "invalid sink URI '{0}': sink URI of Kafka type must have a authority segment containing the cluster name, and a path segment containing the topic name."
+    )]
+    InvalidKafkaSinkUri(Uri),
+    #[error(
+        "invalid subscription from '{0}' to '{1}': only topic and output sources can be published to Kafka sinks, and they can only be published to Kafka sinks."
+    )]
+    IncompatibleSourceAndSink(Source, Sink),
     #[error(
         "invalid sink URI '{0}': sink URI of service type must have a authority segment containing the service name."
     )]
@@ -175,6 +252,12 @@ pub(in crate::schema) enum SubscriptionError {
     #[error("invalid sink URI '{0}': cannot find service/handler specified in the sink URI.")]
     SinkServiceNotFound(Uri),
 
+    #[error(
+        "subscription {0} does not consume from Kafka: only Kafka subscriptions can be paused, resumed and sought."
+    )]
+    #[code(unknown)]
+    NotKafkaSource(SubscriptionId),
+
     #[error(transparent)]
     #[code(unknown)]
     Validation(GenericError),
@@ -207,6 +290,25 @@ pub(in crate::schema) enum KafkaClusterError {
     MissingBrokerConfiguration(String),
 }
 
+#[derive(Debug, thiserror::Error, codederror::CodedError)]
+#[code(unknown)]
+pub(in crate::schema) enum ScheduleError {
+    #[error(transparent)]
+    InvalidExpression(#[from] ScheduleExpressionError),
+    #[error("cron expression '{0}' has no future occurrences")]
+    NoOccurrences(String),
+    #[error("cannot find the schedule target handler '{0}/{1}'")]
+    TargetNotFound(String, String),
+    #[error(
+        "the schedule target '{0}/{1}' is a workflow handler, only service and virtual object handlers can be scheduled"
+    )]
+    WorkflowTarget(String, String),
+    #[error("the schedule target '{0}/{1}' is a virtual object handler, a key must be provided")]
+    MissingKey(String, String),
+    #[error("the schedule target '{0}/{1}' is a service handler, it cannot have a key")]
+    UnexpectedKey(String, String),
+}
+
 #[derive(Debug, thiserror::Error, codederror::CodedError)]
 pub(in crate::schema) enum DeploymentError {
     #[error(
@@ -214,6 +316,9 @@ pub(in crate::schema) enum DeploymentError {
     )]
     #[code(restate_errors::META0016)]
     DifferentSupportedProtocolVersions(RangeInclusive<i32>, RangeInclusive<i32>),
+    #[error("the deployment limits burst can be set only together with a rate")]
+    #[code(unknown)]
+    BurstWithoutRate,
 }
 
 /// Behavior when service type changes during update
@@ -274,6 +379,44 @@ pub struct ModifyServiceRequest {
     pub workflow_completion_retention: Option<Duration>,
     pub inactivity_timeout: Option<Duration>,
     pub abort_timeout: Option<Duration>,
+    pub retry_policy_on_max_attempts: Option<OnMaxAttempts>,
+    pub retry_policy_dead_letter: Option<DeadLetterTarget>,
+    pub traffic_split: Option<ModifyTrafficSplitRequest>,
+    pub handlers: HashMap<String, ModifyHandlerRequest>,
+}
+
+/// Splits new invocations between the latest deployment of the service and `stable_deployment_id`.
+/// A `canary_weight` of 100 removes the split.
+#[derive(Debug, Clone)]
+pub struct ModifyTrafficSplitRequest {
+    pub stable_deployment_id: DeploymentId,
+    pub canary_weight: u8,
+    pub rollback: Option<CanaryRollbackPolicy>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ModifyHandlerRequest {
+    pub retry_policy_on_max_attempts: Option<OnMaxAttempts>,
+    pub retry_policy_dead_letter: Option<DeadLetterTarget>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AddScheduleRequest {
+    pub cron: String,
+    pub timezone: Option<String>,
+    pub service: String,
+    pub handler: String,
+    pub key: Option<String>,
+    pub payload: Bytes,
+    pub overlap_policy: OverlapPolicy,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ModifyScheduleRequest {
+    pub cron: Option<String>,
+    pub timezone: Option<String>,
+    pub payload: Option<Bytes>,
+    pub overlap_policy: Option<OverlapPolicy>,
 }
 
 /// Responsible for updating the provided [`Schema`] with new
@@ -322,6 +465,18 @@ impl SchemaUpdater {
     fn mark_updated(&mut self) {
         self.schema.active_service_revisions =
             ActiveServiceRevision::create_index(self.schema.deployments.values());
+        // Schedules whose target handler went away are removed, so that partitions stop ticking
+        let active_service_revisions = &self.schema.active_service_revisions;
+        self.schema.schedules.retain(|_, schedule| {
+            active_service_revisions
+                .get(schedule.target().service_name())
+                .is_some_and(|revision| {
+                    revision
+                        .service_revision
+                        .handlers
+                        .contains_key(schedule.target().handler_name())
+                })
+        });
         self.modified = true;
     }
 
@@ -603,6 +758,13 @@ impl SchemaUpdater {
             retry_policy_on_max_attempts
         );
 
+        // The dead-letter target cannot be set through the endpoint manifest
+        let retry_policy_dead_letter = if service_level_settings_behavior.preserve() {
+            previous_service_revision.and_then(|old_svc| old_svc.retry_policy_dead_letter.clone())
+        } else {
+            None
+        };
+
         let handlers = service
             .handlers
             .into_iter()
@@ -633,6 +795,8 @@ impl SchemaUpdater {
             retry_policy_max_attempts,
             retry_policy_max_interval,
             retry_policy_on_max_attempts,
+            retry_policy_dead_letter,
+            traffic_split: None,
             service_openapi_cache: Default::default(),
         })
     }
@@ -807,6 +971,26 @@ impl SchemaUpdater {
         }
     }
 
+    pub(in crate::schema) fn set_deployment_limits(
+        &mut self,
+        deployment_id: DeploymentId,
+        limits: crate::schema::deployment::DeploymentLimits,
+    ) -> Result<(), SchemaError> {
+        if limits.burst.is_some() && limits.rate.is_none() {
+            return Err(SchemaError::Deployment(DeploymentError::BurstWithoutRate));
+        }
+        let Some(existing_deployment) = self.schema.deployments.get_mut(&deployment_id) else {
+            return Err(SchemaError::NotFound(format!(
+                "deployment with id '{deployment_id}'"
+            )));
+        };
+
+        existing_deployment.limits = DeploymentLimits::from_deployment_limits(limits);
+        self.mark_updated();
+
+        Ok(())
+    }
+
     /// Returns true if it was removed
     pub fn remove_deployment(&mut self, deployment_id: DeploymentId) -> bool {
         if let Some(deployment) = self.schema.deployments.remove(&deployment_id) {
@@ -861,13 +1045,83 @@ impl SchemaUpdater {
                     topic: topic_name.to_string(),
                 }
             }
+            Some("nats") => {
+                let server = source
+                    .authority()
+                    .ok_or_else(|| {
+                        SchemaError::Subscription(SubscriptionError::InvalidNatsSourceAuthority(
+                            source.clone(),
+                        ))
+                    })?
+                    .as_str();
+                let stream_name = &source.path()[1..];
+                if stream_name.is_empty() || stream_name.contains(['/', '.', '*', '>']) {
+                    return Err(SchemaError::Subscription(
+                        SubscriptionError::InvalidNatsSourceStream(source),
+                    ));
+                }
+                Source::Nats {
+                    server: server.to_string(),
+                    stream: stream_name.to_string(),
+                }
+            }
+            Some("webhook") => {
+                let name = source
+                    .authority()
+                    .ok_or_else(|| {
+                        SchemaError::Subscription(SubscriptionError::InvalidWebhookSourceAuthority(
+                            source.clone(),
+                        ))
+                    })?
+                    .as_str();
+                Source::Webhook {
+                    name: name.to_string(),
+                }
+            }
+            Some("topic") => {
+                let name = source
+                    .authority()
+                    .ok_or_else(|| {
+                        SchemaError::Subscription(SubscriptionError::InvalidTopicSourceAuthority(
+                            source.clone(),
+                        ))
+                    })?
+                    .as_str();
+                Source::Topic {
+                    name: name.to_string(),
+                }
+            }
+            Some("output") => {
+                let service_name = source
+                    .authority()
+                    .ok_or_else(|| {
+                        SchemaError::Subscription(SubscriptionError::InvalidOutputSourceUri(
+                            source.clone(),
+                        ))
+                    })?
+                    .as_str();
+                let handler_name = &source.path()[1..];
+                if !self
+                    .schema
+                    .active_service_revisions
+                    .get(service_name)
+                    .is_some_and(|svc| svc.service_revision.handlers.contains_key(handler_name))
+                {
+                    return Err(SchemaError::Subscription(
+                        SubscriptionError::OutputSourceServiceNotFound(source),
+                    ));
+                }
+                Source::Output {
+                    service: service_name.to_owned(),
+                    handler: handler_name.to_owned(),
+                }
+            }
             _ => {
                 return Err(SchemaError::Subscription(
                     SubscriptionError::InvalidSourceScheme(source),
                 ));
             }
         };
-        let Source::Kafka { cluster, .. } = &source;
 
         // Parse sink
         let sink = match sink.scheme_str() {
@@ -925,6 +1179,19 @@ impl SchemaUpdater {
                     },
                 }
             }
+            Some("kafka") => {
+                let cluster_name = sink.authority().map(|authority| authority.as_str());
+                let topic_name = &sink.path()[1..];
+                let Some(cluster_name) = cluster_name.filter(|_| !topic_name.is_empty()) else {
+                    return Err(SchemaError::Subscription(
+                        SubscriptionError::InvalidKafkaSinkUri(sink),
+                    ));
+                };
+                Sink::Kafka {
+                    cluster: cluster_name.to_string(),
+                    topic: topic_name.to_string(),
+                }
+            }
             _ => {
                 return Err(SchemaError::Subscription(
                     SubscriptionError::InvalidSinkScheme(sink),
@@ -932,10 +1199,56 @@ impl SchemaUpdater {
             }
         };
 
+        if source.virtual_topic().is_some() != matches!(sink, Sink::Kafka { .. }) {
+            return Err(SchemaError::Subscription(
+                SubscriptionError::IncompatibleSourceAndSink(source, sink),
+            ));
+        }
+
         let mut metadata = metadata.unwrap_or_default();
-        check_ignored_kafka_properties(&metadata);
 
-        // Validate and merge cluster properties for Kafka sources
+        match &source {
+            Source::Kafka { cluster, .. } => {
+                check_ignored_kafka_properties(&metadata);
+                validate_kafka_transform_properties(&metadata).map_err(|err| {
+                    SchemaError::Subscription(SubscriptionError::Validation(GenericError::from(
+                        err,
+                    )))
+                })?;
+                self.merge_kafka_cluster_properties(id, cluster, &mut metadata)?;
+            }
+            Source::Topic { .. } | Source::Output { .. } => {
+                let cluster = sink
+                    .kafka_cluster()
+                    .expect("egress sources have a Kafka sink");
+                // group.id is the prefix of the consumer groups storing the publishing progress
+                self.merge_kafka_cluster_properties(id, cluster, &mut metadata)?;
+            }
+            Source::Nats { .. } => {
+                // Set the durable consumer name if unset, so restarts resume where they left off
+                metadata
+                    .entry(NATS_CONSUMER_PROPERTY.to_owned())
+                    .or_insert_with(|| id.to_string());
+            }
+            Source::Webhook { .. } => validate_webhook_properties(&metadata)?,
+        }
+
+        let subscription = Subscription::new(id, source, sink, metadata);
+
+        self.schema.subscriptions.insert(id, subscription);
+        self.mark_updated();
+
+        Ok(id)
+    }
+
+    /// Validates that the Kafka cluster of a subscription exists, and merges its properties in
+    /// the subscription metadata.
+    fn merge_kafka_cluster_properties(
+        &self,
+        id: SubscriptionId,
+        cluster: &str,
+        metadata: &mut HashMap<String, String>,
+    ) -> Result<(), SchemaError> {
         {
             let cluster_properties = self
                 .schema
@@ -970,17 +1283,170 @@ impl SchemaUpdater {
             }
         }
 
-        let subscription = Subscription::new(id, source, sink, metadata);
+        Ok(())
+    }
 
-        self.schema.subscriptions.insert(id, subscription);
+    // Returns true if it was removed
+    pub fn remove_subscription(&mut self, subscription_id: SubscriptionId) -> bool {
+        if self.schema.subscriptions.remove(&subscription_id).is_some() {
+            self.mark_updated();
+            return true;
+        }
+        false
+    }
+
+    /// Pauses or resumes the consumption of a Kafka subscription.
+    pub(in crate::schema) fn set_subscription_paused(
+        &mut self,
+        subscription_id: SubscriptionId,
+        paused: bool,
+    ) -> Result<(), SchemaError> {
+        let consumption = self.kafka_subscription_consumption(subscription_id)?;
+        if consumption.is_paused() != paused {
+            consumption.set_paused(paused);
+            self.mark_updated();
+        }
+        Ok(())
+    }
+
+    /// Moves the consumers of a Kafka subscription to `position`, in `partition` or in all the
+    /// partitions of the topic.
+    pub(in crate::schema) fn seek_subscription(
+        &mut self,
+        subscription_id: SubscriptionId,
+        position: SeekPosition,
+        partition: Option<i32>,
+    ) -> Result<(), SchemaError> {
+        self.kafka_subscription_consumption(subscription_id)?
+            .seek(position, partition);
+        self.mark_updated();
+        Ok(())
+    }
+
+    fn kafka_subscription_consumption(
+        &mut self,
+        subscription_id: SubscriptionId,
+    ) -> Result<&mut ConsumptionState, SchemaError> {
+        let subscription = self
+            .schema
+            .subscriptions
+            .get_mut(&subscription_id)
+            .ok_or_else(|| {
+                SchemaError::NotFound(format!("subscription with id '{subscription_id}'"))
+            })?;
+        if !matches!(subscription.source(), Source::Kafka { .. }) {
+            return Err(SchemaError::Subscription(
+                SubscriptionError::NotKafkaSource(subscription_id),
+            ));
+        }
+        Ok(subscription.consumption_mut())
+    }
+
+    pub(in crate::schema) fn add_schedule(
+        &mut self,
+        AddScheduleRequest {
+            cron,
+            timezone,
+            service,
+            handler,
+            key,
+            payload,
+            overlap_policy,
+        }: AddScheduleRequest,
+    ) -> Result<ScheduleId, SchemaError> {
+        let timezone = timezone.unwrap_or_else(|| DEFAULT_SCHEDULE_TIMEZONE.to_owned());
+        validate_schedule_expression(&cron, &timezone)?;
+
+        let handler_schemas = self
+            .schema
+            .active_service_revisions
+            .get(&service)
+            .and_then(|revision| revision.service_revision.handlers.get(&handler))
+            .ok_or_else(|| ScheduleError::TargetNotFound(service.clone(), handler.clone()))?;
+
+        let target = match (handler_schemas.target_ty, key) {
+            (InvocationTargetType::Service, None) => ScheduleTarget::Service {
+                name: service,
+                handler,
+            },
+            (InvocationTargetType::Service, Some(_)) => {
+                return Err(ScheduleError::UnexpectedKey(service, handler).into());
+            }
+            (InvocationTargetType::VirtualObject(handler_ty), Some(key)) => {
+                ScheduleTarget::VirtualObject {
+                    name: service,
+                    key,
+                    handler,
+                    handler_ty,
+                }
+            }
+            (InvocationTargetType::VirtualObject(_), None) => {
+                return Err(ScheduleError::MissingKey(service, handler).into());
+            }
+            (InvocationTargetType::Workflow(_), _) => {
+                return Err(ScheduleError::WorkflowTarget(service, handler).into());
+            }
+        };
+
+        let id = ScheduleId::new();
+        let schedule = Schedule::new(id, cron, timezone, target, payload, overlap_policy);
+
+        self.schema.schedules.insert(id, schedule);
         self.mark_updated();
 
         Ok(id)
     }
 
+    pub(in crate::schema) fn modify_schedule(
+        &mut self,
+        id: ScheduleId,
+        ModifyScheduleRequest {
+            cron,
+            timezone,
+            payload,
+            overlap_policy,
+        }: ModifyScheduleRequest,
+    ) -> Result<(), SchemaError> {
+        let Some(schedule) = self.schema.schedules.get_mut(&id) else {
+            return Err(SchemaError::NotFound(format!("schedule with id '{id}'")));
+        };
+
+        let mut modified = false;
+        if cron.is_some() || timezone.is_some() {
+            let cron = cron.unwrap_or_else(|| schedule.cron().to_owned());
+            let timezone = timezone.unwrap_or_else(|| schedule.timezone().to_owned());
+            validate_schedule_expression(&cron, &timezone)?;
+
+            if cron != schedule.cron() || timezone != schedule.timezone() {
+                schedule.set_cron(cron, timezone);
+                modified = true;
+            }
+        }
+        if let Some(payload) = payload
+            && &payload != schedule.payload()
+        {
+            schedule.set_payload(payload);
+            modified = true;
+        }
+        if let Some(overlap_policy) = overlap_policy
+            && overlap_policy != schedule.overlap_policy()
+        {
+            schedule.set_overlap_policy(overlap_policy);
+            modified = true;
+        }
+
+        if modified {
+            // Partitions re-register the next tick for the new revision, discarding the old one
+            schedule.bump_revision();
+            self.mark_updated();
+        }
+
+        Ok(())
+    }
+
     // Returns true if it was removed
-    pub fn remove_subscription(&mut self, subscription_id: SubscriptionId) -> bool {
-        if self.schema.subscriptions.remove(&subscription_id).is_some() {
+    pub fn remove_schedule(&mut self, id: ScheduleId) -> bool {
+        if self.schema.schedules.remove(&id).is_some() {
             self.mark_updated();
             return true;
         }
@@ -1056,10 +1522,7 @@ impl SchemaUpdater {
             .schema
             .subscriptions
             .values()
-            .filter(|s| {
-                let Source::Kafka { cluster, .. } = s.source();
-                cluster == kafka_cluster_name
-            })
+            .filter(|s| s.kafka_cluster() == Some(kafka_cluster_name))
             .map(|s| s.id())
         {
             match allow_orphan_subscriptions {
@@ -1130,6 +1593,22 @@ impl SchemaUpdater {
         name: &str,
         modify_service_request: ModifyServiceRequest,
     ) -> Result<(), SchemaError> {
+        for dead_letter in modify_service_request
+            .retry_policy_dead_letter
+            .iter()
+            .chain(
+                modify_service_request
+                    .handlers
+                    .values()
+                    .filter_map(|h| h.retry_policy_dead_letter.as_ref()),
+            )
+        {
+            self.validate_dead_letter_target(name, dead_letter)?;
+        }
+        if let Some(traffic_split) = &modify_service_request.traffic_split {
+            self.validate_traffic_split(name, traffic_split)?;
+        }
+
         self.apply_change_to_active_service_revision(name, |svc| {
             if let Some(new_public_value) = modify_service_request.public {
                 svc.public = new_public_value;
@@ -1159,6 +1638,37 @@ impl SchemaUpdater {
             if let Some(new_abort_timeout) = modify_service_request.abort_timeout {
                 svc.abort_timeout = Some(new_abort_timeout);
             }
+            if let Some(new_on_max_attempts) = modify_service_request.retry_policy_on_max_attempts {
+                svc.retry_policy_on_max_attempts = Some(new_on_max_attempts);
+            }
+            if let Some(new_dead_letter) = modify_service_request.retry_policy_dead_letter {
+                svc.retry_policy_dead_letter = Some(new_dead_letter);
+            }
+            if let Some(new_traffic_split) = modify_service_request.traffic_split {
+                // Routing everything to the latest deployment is the same as having no split
+                svc.traffic_split = (new_traffic_split.canary_weight < 100).then(|| TrafficSplit {
+                    stable_deployment_id: new_traffic_split.stable_deployment_id,
+                    canary_weight: new_traffic_split.canary_weight,
+                    rollback: new_traffic_split.rollback,
+                    rolled_back: false,
+                });
+            }
+            for (handler_name, modify_handler_request) in modify_service_request.handlers {
+                let Some(handler) = svc.handlers.get_mut(&handler_name) else {
+                    return Err(SchemaError::NotFound(format!(
+                        "handler {}/{handler_name}",
+                        svc.name
+                    )));
+                };
+                if let Some(new_on_max_attempts) =
+                    modify_handler_request.retry_policy_on_max_attempts
+                {
+                    handler.retry_policy_on_max_attempts = Some(new_on_max_attempts);
+                }
+                if let Some(new_dead_letter) = modify_handler_request.retry_policy_dead_letter {
+                    handler.retry_policy_dead_letter = Some(new_dead_letter);
+                }
+            }
             Ok(())
         })?;
 
@@ -1167,6 +1677,108 @@ impl SchemaUpdater {
         Ok(())
     }
 
+    fn validate_dead_letter_target(
+        &self,
+        service_name: &str,
+        dead_letter: &DeadLetterTarget,
+    ) -> Result<(), ServiceError> {
+        if dead_letter.service == service_name {
+            return Err(ServiceError::SelfReferencingDeadLetterTarget(
+                service_name.to_owned(),
+            ));
+        }
+        let Some(target_revision) = self
+            .schema
+            .active_service_revisions
+            .get(&dead_letter.service)
+            .filter(|rev| {
+                rev.service_revision
+                    .handlers
+                    .contains_key(&dead_letter.handler)
+            })
+        else {
+            return Err(ServiceError::DeadLetterTargetNotFound(dead_letter.clone()));
+        };
+        if target_revision.service_revision.ty != ServiceType::Service {
+            return Err(ServiceError::BadDeadLetterTargetType(
+                dead_letter.clone(),
+                target_revision.service_revision.ty,
+            ));
+        }
+        Ok(())
+    }
+
+    fn validate_traffic_split(
+        &self,
+        service_name: &str,
+        traffic_split: &ModifyTrafficSplitRequest,
+    ) -> Result<(), ServiceError> {
+        if traffic_split.canary_weight > 100 {
+            return Err(ServiceError::BadCanaryWeight(traffic_split.canary_weight));
+        }
+        if let Some(rollback) = &traffic_split.rollback
+            && rollback.max_failure_rate > 100
+        {
+            return Err(ServiceError::BadCanaryMaxFailureRate(
+                rollback.max_failure_rate,
+            ));
+        }
+
+        let stable_deployment_id = traffic_split.stable_deployment_id;
+        let is_previous_revision = self
+            .schema
+            .active_service_revisions
+            .get(service_name)
+            .filter(|active| active.deployment_id != stable_deployment_id)
+            .zip(
+                self.schema
+                    .deployments
+                    .get(&stable_deployment_id)
+                    .and_then(|dp| dp.services.get(service_name)),
+            )
+            .is_some_and(|(active, stable)| {
+                stable.revision < active.service_revision.revision
+                    && stable.ty == active.service_revision.ty
+            });
+        if !is_previous_revision {
+            return Err(ServiceError::BadStableDeployment(
+                service_name.to_owned(),
+                stable_deployment_id,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Routes all new invocations of the service to the stable deployment of its traffic split.
+    /// Returns `false` if the service has no split towards `canary_deployment_id` to roll back.
+    pub(in crate::schema) fn roll_back_traffic_split(
+        &mut self,
+        service_name: &str,
+        canary_deployment_id: DeploymentId,
+    ) -> Result<bool, SchemaError> {
+        let can_roll_back = self
+            .schema
+            .active_service_revisions
+            .get(service_name)
+            .filter(|active| active.deployment_id == canary_deployment_id)
+            .and_then(|active| active.service_revision.traffic_split.as_ref())
+            .is_some_and(|split| !split.rolled_back);
+        if !can_roll_back {
+            return Ok(false);
+        }
+
+        self.apply_change_to_active_service_revision(service_name, |svc| {
+            if let Some(split) = &mut svc.traffic_split {
+                split.canary_weight = 0;
+                split.rolled_back = true;
+            }
+            Ok(())
+        })?;
+        self.mark_updated();
+
+        Ok(true)
+    }
+
     fn apply_change_to_active_service_revision(
         &mut self,
         svc_name: &str,
@@ -1303,6 +1915,7 @@ impl Handler {
             enable_lazy_state: handler.enable_lazy_state,
             public: handler.ingress_private.map(bool::not),
             retry_policy_on_max_attempts,
+            retry_policy_dead_letter: None,
         })
     }
 
@@ -1403,6 +2016,17 @@ fn validate_service_name(name: &str) -> Result<(), ServiceError> {
     }
 }
 
+fn validate_schedule_expression(cron: &str, timezone: &str) -> Result<(), ScheduleError> {
+    let schedule = CronSchedule::parse(cron, timezone)?;
+    if schedule
+        .next_occurrence_after(MillisSinceEpoch::now())
+        .is_none()
+    {
+        return Err(ScheduleError::NoOccurrences(cron.to_owned()));
+    }
+    Ok(())
+}
+
 fn validate_kafka_cluster_properties(
     name: &str,
     properties: &HashMap<String, String>,
@@ -1417,6 +2041,72 @@ fn validate_kafka_cluster_properties(
     Ok(())
 }
 
+/// Validates the filtering and transformation options. The schema file of the payload decoding is
+/// read by the nodes running the consumers, hence it's not checked here.
+fn validate_kafka_transform_properties(metadata: &HashMap<String, String>) -> Result<(), String> {
+    if let Some(predicate) = metadata.get(KAFKA_FILTER_JSON_PATH_PROPERTY) {
+        predicate
+            .parse::<JsonPredicate>()
+            .map_err(|err| format!("invalid '{KAFKA_FILTER_JSON_PATH_PROPERTY}': {err:#}"))?;
+    }
+    if let Some(template) = metadata.get(KAFKA_KEY_TEMPLATE_PROPERTY) {
+        template
+            .parse::<KeyTemplate>()
+            .map_err(|err| format!("invalid '{KAFKA_KEY_TEMPLATE_PROPERTY}': {err:#}"))?;
+    }
+
+    let Some(format) = metadata.get(KAFKA_DECODE_PROPERTY) else {
+        return Ok(());
+    };
+    if format != "avro" && format != "protobuf" {
+        return Err(format!(
+            "unsupported '{KAFKA_DECODE_PROPERTY}' format '{format}', supported formats: [avro, protobuf]"
+        ));
+    }
+    if !metadata.contains_key(KAFKA_DECODE_SCHEMA_FILE_PROPERTY) {
+        return Err(format!(
+            "'{KAFKA_DECODE_PROPERTY}' requires the '{KAFKA_DECODE_SCHEMA_FILE_PROPERTY}' option"
+        ));
+    }
+    if format == "protobuf" && !metadata.contains_key(KAFKA_DECODE_MESSAGE_PROPERTY) {
+        return Err(format!(
+            "'{KAFKA_DECODE_PROPERTY}=protobuf' requires the '{KAFKA_DECODE_MESSAGE_PROPERTY}' option"
+        ));
+    }
+    if let Some(wire_format) = metadata.get(KAFKA_DECODE_WIRE_FORMAT_PROPERTY)
+        && wire_format != "raw"
+        && wire_format != "confluent"
+    {
+        return Err(format!(
+            "unsupported '{KAFKA_DECODE_WIRE_FORMAT_PROPERTY}' '{wire_format}', supported wire formats: [raw, confluent]"
+        ));
+    }
+    Ok(())
+}
+
+/// Webhook subscriptions must either authenticate the requests with a secret, or opt out
+/// explicitly.
+fn validate_webhook_properties(metadata: &HashMap<String, String>) -> Result<(), SchemaError> {
+    let allow_unauthenticated = match metadata.get(WEBHOOK_ALLOW_UNAUTHENTICATED_PROPERTY) {
+        Some(value) => value.parse::<bool>().map_err(|_| {
+            SchemaError::Subscription(SubscriptionError::InvalidWebhookAllowUnauthenticated(
+                value.clone(),
+            ))
+        })?,
+        None => false,
+    };
+    let has_secret = metadata
+        .get(WEBHOOK_SECRET_PROPERTY)
+        .is_some_and(|secret| !secret.is_empty());
+
+    if !has_secret && !allow_unauthenticated {
+        return Err(SchemaError::Subscription(
+            SubscriptionError::MissingWebhookSecret,
+        ));
+    }
+    Ok(())
+}
+
 fn check_ignored_kafka_properties(metadata: &HashMap<String, String>) {
     // These properties are ignored by our kafka consumer because they're implementation details
     if metadata.contains_key("enable.auto.commit") {
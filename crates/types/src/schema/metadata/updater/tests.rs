@@ -10,6 +10,7 @@
 
 use super::*;
 use std::convert::Infallible;
+use std::num::NonZeroU32;
 
 use crate::Versioned;
 use crate::schema::deployment::DeploymentResolver;
@@ -1303,6 +1304,77 @@ fn update_deployment_with_private_service() -> Result<(), SchemaError> {
     Ok(())
 }
 
+#[test]
+fn set_deployment_limits() -> Result<(), SchemaError> {
+    let mut updater = SchemaUpdater::default();
+
+    let deployment_id = updater
+        .add_deployment(add_deployment_request(vec![greeter_service()]))?
+        .1;
+    let schemas = updater.into_inner();
+    assert!(
+        schemas
+            .get_deployment(&deployment_id)
+            .unwrap()
+            .limits
+            .is_unlimited()
+    );
+
+    let limits = crate::schema::deployment::DeploymentLimits {
+        concurrency: NonZeroU32::new(10),
+        rate: Some("100/s".parse().unwrap()),
+        burst: NonZeroU32::new(20),
+    };
+    let version_before_modification = schemas.version();
+    let mut updater = SchemaUpdater::new(schemas);
+    updater.set_deployment_limits(deployment_id, limits)?;
+    let schemas = updater.into_inner();
+    assert!(version_before_modification < schemas.version());
+    assert_eq!(
+        schemas.get_deployment(&deployment_id).unwrap().limits,
+        limits
+    );
+
+    // Limits survive a deployment update
+    let mut updater = SchemaUpdater::new(schemas);
+    updater.update_deployment(update_deployment_request(
+        deployment_id,
+        vec![greeter_service()],
+    ))?;
+    let schemas = updater.into_inner();
+    assert_eq!(
+        schemas.get_deployment(&deployment_id).unwrap().limits,
+        limits
+    );
+
+    let mut updater = SchemaUpdater::new(schemas);
+    assert!(let SchemaError::Deployment(DeploymentError::BurstWithoutRate) = updater
+        .set_deployment_limits(
+            deployment_id,
+            crate::schema::deployment::DeploymentLimits {
+                burst: NonZeroU32::new(20),
+                ..Default::default()
+            }
+        )
+        .unwrap_err());
+    assert!(let SchemaError::NotFound(_) = updater
+        .set_deployment_limits(DeploymentId::new(), limits)
+        .unwrap_err());
+
+    // Resetting to unlimited
+    updater.set_deployment_limits(deployment_id, Default::default())?;
+    let schemas = updater.into_inner();
+    assert!(
+        schemas
+            .get_deployment(&deployment_id)
+            .unwrap()
+            .limits
+            .is_unlimited()
+    );
+
+    Ok(())
+}
+
 mod endpoint_manifest_options_propagation {
     use super::*;
 
@@ -2239,9 +2311,13 @@ mod modify_service {
     use super::*;
 
     use crate::config::{Configuration, DEFAULT_ABORT_TIMEOUT, DEFAULT_INACTIVITY_TIMEOUT};
+    use crate::identifiers::InvocationId;
     use crate::invocation::InvocationRetention;
-    use crate::schema::invocation_target::{InvocationAttemptOptions, InvocationTargetMetadata};
-    use crate::schema::service::ServiceMetadata;
+    use crate::invocation::InvocationTarget;
+    use crate::schema::invocation_target::{
+        DeadLetterResolver, InvocationAttemptOptions, InvocationTargetMetadata,
+    };
+    use crate::schema::service::{ServiceMetadata, TrafficSplit};
     use googletest::prelude::*;
     use restate_util_time::FriendlyDuration;
     use test_log::test;
@@ -2349,6 +2425,9 @@ mod modify_service {
                     workflow_completion_retention: None,
                     inactivity_timeout: Some(new_inactivity_timeout),
                     abort_timeout: Some(new_abort_timeout),
+                    retry_policy_on_max_attempts: None,
+                    retry_policy_dead_letter: None,
+                    handlers: Default::default(),
                 },
             )
         })
@@ -2441,6 +2520,9 @@ mod modify_service {
                     workflow_completion_retention: Some(new_workflow_completion_retention),
                     inactivity_timeout: Some(new_inactivity_timeout),
                     abort_timeout: Some(new_abort_timeout),
+                    retry_policy_on_max_attempts: None,
+                    retry_policy_dead_letter: None,
+                    handlers: Default::default(),
                 },
             )
         })
@@ -2504,6 +2586,330 @@ mod modify_service {
             })
         );
     }
+
+    #[test]
+    fn dead_letter_target() {
+        let schema = SchemaUpdater::update(Schema::default(), move |updater| {
+            updater
+                .add_deployment(add_deployment_request(vec![
+                    greeter_service(),
+                    another_greeter_service(),
+                ]))
+                .map(|_| ())
+        })
+        .unwrap();
+
+        let dead_letter = DeadLetterTarget {
+            service: ANOTHER_GREETER_SERVICE_NAME.to_owned(),
+            handler: "another_greeter".to_owned(),
+        };
+        let schema = SchemaUpdater::update(schema, |updater| {
+            updater.modify_service(
+                GREETER_SERVICE_NAME,
+                ModifyServiceRequest {
+                    handlers: HashMap::from([(
+                        GREET_HANDLER_NAME.to_owned(),
+                        ModifyHandlerRequest {
+                            retry_policy_on_max_attempts: Some(OnMaxAttempts::DeadLetter),
+                            retry_policy_dead_letter: Some(dead_letter.clone()),
+                        },
+                    )]),
+                    ..ModifyServiceRequest::default()
+                },
+            )
+        })
+        .unwrap();
+
+        assert_eq!(
+            schema
+                .resolve_invocation_retry_policy(None, GREETER_SERVICE_NAME, GREET_HANDLER_NAME)
+                .1,
+            OnMaxAttempts::DeadLetter
+        );
+        assert_eq!(
+            schema.resolve_dead_letter_target(None, GREETER_SERVICE_NAME, GREET_HANDLER_NAME),
+            Some(dead_letter)
+        );
+        assert_eq!(
+            schema.resolve_dead_letter_target(
+                None,
+                ANOTHER_GREETER_SERVICE_NAME,
+                "another_greeter"
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn dead_letter_target_without_on_max_attempts_is_ignored() {
+        let schema = SchemaUpdater::update(Schema::default(), move |updater| {
+            updater
+                .add_deployment(add_deployment_request(vec![
+                    greeter_service(),
+                    another_greeter_service(),
+                ]))
+                .map(|_| ())
+        })
+        .unwrap();
+
+        let schema = SchemaUpdater::update(schema, |updater| {
+            updater.modify_service(
+                GREETER_SERVICE_NAME,
+                ModifyServiceRequest {
+                    retry_policy_dead_letter: Some(DeadLetterTarget {
+                        service: ANOTHER_GREETER_SERVICE_NAME.to_owned(),
+                        handler: "another_greeter".to_owned(),
+                    }),
+                    ..ModifyServiceRequest::default()
+                },
+            )
+        })
+        .unwrap();
+
+        assert_eq!(
+            schema.resolve_dead_letter_target(None, GREETER_SERVICE_NAME, GREET_HANDLER_NAME),
+            None
+        );
+    }
+
+    #[test]
+    fn reject_invalid_dead_letter_targets() {
+        let schema = SchemaUpdater::update(Schema::default(), move |updater| {
+            updater
+                .add_deployment(add_deployment_request(vec![
+                    greeter_service(),
+                    another_greeter_service(),
+                ]))
+                .map(|_| ())
+        })
+        .unwrap();
+
+        let modify = |service: &str, handler: &str| {
+            let dead_letter = DeadLetterTarget {
+                service: service.to_owned(),
+                handler: handler.to_owned(),
+            };
+            SchemaUpdater::update(schema.clone(), move |updater| {
+                updater.modify_service(
+                    GREETER_SERVICE_NAME,
+                    ModifyServiceRequest {
+                        retry_policy_on_max_attempts: Some(OnMaxAttempts::DeadLetter),
+                        retry_policy_dead_letter: Some(dead_letter),
+                        ..ModifyServiceRequest::default()
+                    },
+                )
+            })
+        };
+
+        assert_that!(
+            modify(ANOTHER_GREETER_SERVICE_NAME, "unknown"),
+            err(pat!(SchemaError::Service(pat!(
+                ServiceError::DeadLetterTargetNotFound(_)
+            ))))
+        );
+        assert_that!(
+            modify(GREETER_SERVICE_NAME, GREET_HANDLER_NAME),
+            err(pat!(SchemaError::Service(pat!(
+                ServiceError::SelfReferencingDeadLetterTarget(_)
+            ))))
+        );
+    }
+
+    fn register_two_greeter_deployments() -> (DeploymentId, DeploymentId, Schema) {
+        let ((_, stable_deployment_id), schema) =
+            SchemaUpdater::update_and_return(Schema::default(), |updater| {
+                updater.add_deployment(AddDeploymentRequest {
+                    deployment_address: DeploymentAddress::mock_uri("http://localhost:9080"),
+                    ..add_deployment_request(vec![greeter_service()])
+                })
+            })
+            .unwrap();
+        let ((_, canary_deployment_id), schema) =
+            SchemaUpdater::update_and_return(schema, |updater| {
+                updater.add_deployment(AddDeploymentRequest {
+                    deployment_address: DeploymentAddress::mock_uri("http://localhost:9081"),
+                    ..add_deployment_request(vec![greeter_service()])
+                })
+            })
+            .unwrap();
+        (stable_deployment_id, canary_deployment_id, schema)
+    }
+
+    #[test]
+    fn traffic_split() {
+        let (stable_deployment_id, canary_deployment_id, schema) =
+            register_two_greeter_deployments();
+
+        let schema = SchemaUpdater::update(schema, |updater| {
+            updater.modify_service(
+                GREETER_SERVICE_NAME,
+                ModifyServiceRequest {
+                    traffic_split: Some(ModifyTrafficSplitRequest {
+                        stable_deployment_id,
+                        canary_weight: 30,
+                        rollback: None,
+                    }),
+                    ..ModifyServiceRequest::default()
+                },
+            )
+        })
+        .unwrap();
+        assert_that!(
+            schema.assert_service(GREETER_SERVICE_NAME),
+            pat!(ServiceMetadata {
+                deployment_id: eq(canary_deployment_id),
+                traffic_split: some(pat!(TrafficSplit {
+                    canary_deployment_id: eq(canary_deployment_id),
+                    stable_deployment_id: eq(stable_deployment_id),
+                    canary_weight: eq(30),
+                    rolled_back: eq(false)
+                }))
+            })
+        );
+
+        let target = InvocationTarget::service(GREETER_SERVICE_NAME, GREET_HANDLER_NAME);
+        let route = |schema: &Schema, invocation_id: &InvocationId| {
+            schema
+                .resolve_deployment_for_new_invocation(invocation_id, &target)
+                .unwrap()
+                .id
+        };
+        let invocation_ids: Vec<_> = (0..1000).map(|_| InvocationId::mock_random()).collect();
+        let routed_to_canary = invocation_ids
+            .iter()
+            .filter(|id| route(&schema, id) == canary_deployment_id)
+            .count();
+        assert!((200..400).contains(&routed_to_canary));
+        // Routing is stable for the same invocation
+        for invocation_id in &invocation_ids {
+            assert_eq!(route(&schema, invocation_id), route(&schema, invocation_id));
+        }
+
+        // Roll back
+        let schema = schema
+            .roll_back_traffic_split(GREETER_SERVICE_NAME, canary_deployment_id)
+            .unwrap();
+        assert!(
+            schema
+                .roll_back_traffic_split(GREETER_SERVICE_NAME, canary_deployment_id)
+                .is_none()
+        );
+        assert_that!(
+            schema.resolve_traffic_split(GREETER_SERVICE_NAME),
+            some(pat!(TrafficSplit {
+                canary_weight: eq(0),
+                rolled_back: eq(true)
+            }))
+        );
+        for invocation_id in &invocation_ids {
+            assert_eq!(route(&schema, invocation_id), stable_deployment_id);
+        }
+
+        // Promote the canary
+        let schema = SchemaUpdater::update(schema, |updater| {
+            updater.modify_service(
+                GREETER_SERVICE_NAME,
+                ModifyServiceRequest {
+                    traffic_split: Some(ModifyTrafficSplitRequest {
+                        stable_deployment_id,
+                        canary_weight: 100,
+                        rollback: None,
+                    }),
+                    ..ModifyServiceRequest::default()
+                },
+            )
+        })
+        .unwrap();
+        assert_that!(schema.resolve_traffic_split(GREETER_SERVICE_NAME), none());
+        for invocation_id in &invocation_ids {
+            assert_eq!(route(&schema, invocation_id), canary_deployment_id);
+        }
+    }
+
+    #[test]
+    fn registering_a_new_deployment_removes_the_traffic_split() {
+        let (stable_deployment_id, _, schema) = register_two_greeter_deployments();
+
+        let schema = SchemaUpdater::update(schema, |updater| {
+            updater.modify_service(
+                GREETER_SERVICE_NAME,
+                ModifyServiceRequest {
+                    traffic_split: Some(ModifyTrafficSplitRequest {
+                        stable_deployment_id,
+                        canary_weight: 10,
+                        rollback: None,
+                    }),
+                    ..ModifyServiceRequest::default()
+                },
+            )
+        })
+        .unwrap();
+        let schema = SchemaUpdater::update(schema, |updater| {
+            updater
+                .add_deployment(AddDeploymentRequest {
+                    deployment_address: DeploymentAddress::mock_uri("http://localhost:9082"),
+                    ..add_deployment_request(vec![greeter_service()])
+                })
+                .map(|_| ())
+        })
+        .unwrap();
+
+        assert_that!(schema.resolve_traffic_split(GREETER_SERVICE_NAME), none());
+    }
+
+    #[test]
+    fn reject_invalid_traffic_splits() {
+        let (stable_deployment_id, canary_deployment_id, schema) =
+            register_two_greeter_deployments();
+
+        let modify = |traffic_split: ModifyTrafficSplitRequest| {
+            SchemaUpdater::update(schema.clone(), move |updater| {
+                updater.modify_service(
+                    GREETER_SERVICE_NAME,
+                    ModifyServiceRequest {
+                        traffic_split: Some(traffic_split),
+                        ..ModifyServiceRequest::default()
+                    },
+                )
+            })
+        };
+
+        assert_that!(
+            modify(ModifyTrafficSplitRequest {
+                stable_deployment_id,
+                canary_weight: 101,
+                rollback: None,
+            }),
+            err(pat!(SchemaError::Service(pat!(
+                ServiceError::BadCanaryWeight(_)
+            ))))
+        );
+        assert_that!(
+            modify(ModifyTrafficSplitRequest {
+                stable_deployment_id,
+                canary_weight: 10,
+                rollback: Some(CanaryRollbackPolicy {
+                    max_failure_rate: 120,
+                    min_attempts: 10,
+                }),
+            }),
+            err(pat!(SchemaError::Service(pat!(
+                ServiceError::BadCanaryMaxFailureRate(_)
+            ))))
+        );
+        for bad_stable_deployment_id in [canary_deployment_id, DeploymentId::new()] {
+            assert_that!(
+                modify(ModifyTrafficSplitRequest {
+                    stable_deployment_id: bad_stable_deployment_id,
+                    canary_weight: 10,
+                    rollback: None,
+                }),
+                err(pat!(SchemaError::Service(pat!(
+                    ServiceError::BadStableDeployment(_, _)
+                ))))
+            );
+        }
+    }
 }
 
 mod kafka_cluster {
@@ -2514,7 +2920,9 @@ mod kafka_cluster {
     };
     use crate::schema::Redaction;
     use crate::schema::kafka::KafkaClusterResolver;
-    use crate::schema::subscriptions::SubscriptionResolver;
+    use crate::schema::subscriptions::{
+        SeekPosition, SubscriptionResolver, VIRTUAL_TOPIC_SERVICE_NAME,
+    };
     use googletest::prelude::*;
     use restate_test_util::{assert, assert_eq};
     use std::collections::HashMap;
@@ -2934,6 +3342,7 @@ mod kafka_cluster {
                 assert_eq!(cluster, "my-cluster");
                 assert_eq!(topic, "my-topic");
             }
+            source => panic!("unexpected source {source}"),
         }
     }
 
@@ -2963,6 +3372,428 @@ mod kafka_cluster {
         );
     }
 
+    #[test]
+    fn kafka_subscription_validates_transform_options() {
+        let mut updater = SchemaUpdater::default();
+        updater
+            .add_deployment(add_deployment_request(vec![greeter_service()]))
+            .unwrap();
+        updater
+            .add_kafka_cluster("my-cluster".parse().unwrap(), kafka_cluster_properties())
+            .unwrap();
+
+        let mut add_subscription = |option: &str, value: &str| {
+            updater.add_subscription(
+                "kafka://my-cluster/my-topic".parse().unwrap(),
+                format!("service://{}/greet", GREETER_SERVICE_NAME)
+                    .parse()
+                    .unwrap(),
+                Some(HashMap::from([(option.to_owned(), value.to_owned())])),
+            )
+        };
+
+        for (option, value) in [
+            (KAFKA_FILTER_JSON_PATH_PROPERTY, "type == \"created\""),
+            (KAFKA_FILTER_JSON_PATH_PROPERTY, "$.type == created"),
+            (KAFKA_KEY_TEMPLATE_PROPERTY, "{header.tenant"),
+            (KAFKA_KEY_TEMPLATE_PROPERTY, "{unknown}"),
+        ] {
+            assert_that!(
+                add_subscription(option, value),
+                err(pat!(SchemaError::Subscription(pat!(
+                    SubscriptionError::Validation(_)
+                ))))
+            );
+        }
+
+        assert_that!(
+            add_subscription(KAFKA_FILTER_JSON_PATH_PROPERTY, "$.type != \"deleted\""),
+            ok(anything())
+        );
+        assert_that!(
+            add_subscription(KAFKA_KEY_TEMPLATE_PROPERTY, "{header.tenant}-{$.id}"),
+            ok(anything())
+        );
+    }
+
+    #[test]
+    fn nats_and_webhook_subscriptions() {
+        let schema = Schema::default();
+
+        let ((nats_id, webhook_id), schema) = SchemaUpdater::update_and_return(schema, |updater| {
+            updater
+                .add_deployment(add_deployment_request(vec![greeter_service()]))
+                .unwrap();
+
+            let sink: http::Uri = format!("service://{}/greet", GREETER_SERVICE_NAME)
+                .parse()
+                .unwrap();
+            let nats_id = updater.add_subscription(
+                "nats://localhost:4222/orders".parse().unwrap(),
+                sink.clone(),
+                None,
+            )?;
+            let webhook_id = updater.add_subscription(
+                "webhook://github".parse().unwrap(),
+                sink,
+                Some(HashMap::from([(
+                    WEBHOOK_SECRET_PROPERTY.to_owned(),
+                    "my-secret".to_owned(),
+                )])),
+            )?;
+            Ok((nats_id, webhook_id))
+        })
+        .unwrap();
+
+        let nats = schema
+            .get_subscription(nats_id, Redaction::No)
+            .expect("subscription should exist");
+        assert_eq!(
+            nats.source(),
+            &Source::Nats {
+                server: "localhost:4222".to_owned(),
+                stream: "orders".to_owned(),
+            }
+        );
+        assert_eq!(nats.source(), &"nats://localhost:4222/orders");
+        // The durable consumer name defaults to the subscription id
+        assert_eq!(nats.metadata().get("consumer"), Some(&nats_id.to_string()));
+
+        let webhook = schema
+            .get_subscription(webhook_id, Redaction::No)
+            .expect("subscription should exist");
+        assert_eq!(
+            webhook.source(),
+            &Source::Webhook {
+                name: "github".to_owned()
+            }
+        );
+        assert_eq!(
+            webhook.metadata().get(WEBHOOK_SECRET_PROPERTY),
+            Some(&"my-secret".to_owned())
+        );
+    }
+
+    #[test]
+    fn webhook_subscription_requires_secret() {
+        let mut updater = SchemaUpdater::default();
+        updater
+            .add_deployment(add_deployment_request(vec![greeter_service()]))
+            .unwrap();
+        let sink: http::Uri = format!("service://{}/greet", GREETER_SERVICE_NAME)
+            .parse()
+            .unwrap();
+
+        for metadata in [
+            None,
+            Some(HashMap::from([(
+                WEBHOOK_SECRET_PROPERTY.to_owned(),
+                String::new(),
+            )])),
+            Some(HashMap::from([(
+                WEBHOOK_ALLOW_UNAUTHENTICATED_PROPERTY.to_owned(),
+                "false".to_owned(),
+            )])),
+        ] {
+            assert_that!(
+                updater.add_subscription(
+                    "webhook://github".parse().unwrap(),
+                    sink.clone(),
+                    metadata
+                ),
+                err(pat!(SchemaError::Subscription(pat!(
+                    SubscriptionError::MissingWebhookSecret
+                ))))
+            );
+        }
+
+        assert_that!(
+            updater.add_subscription(
+                "webhook://github".parse().unwrap(),
+                sink.clone(),
+                Some(HashMap::from([(
+                    WEBHOOK_ALLOW_UNAUTHENTICATED_PROPERTY.to_owned(),
+                    "yes".to_owned(),
+                )]))
+            ),
+            err(pat!(SchemaError::Subscription(pat!(
+                SubscriptionError::InvalidWebhookAllowUnauthenticated(_)
+            ))))
+        );
+
+        // Explicit opt-out
+        assert_that!(
+            updater.add_subscription(
+                "webhook://github".parse().unwrap(),
+                sink,
+                Some(HashMap::from([(
+                    WEBHOOK_ALLOW_UNAUTHENTICATED_PROPERTY.to_owned(),
+                    "true".to_owned(),
+                )]))
+            ),
+            ok(anything())
+        );
+    }
+
+    #[test]
+    fn nats_subscription_requires_stream() {
+        let mut updater = SchemaUpdater::default();
+        updater
+            .add_deployment(add_deployment_request(vec![greeter_service()]))
+            .unwrap();
+
+        let result = updater.add_subscription(
+            "nats://localhost:4222/".parse().unwrap(),
+            format!("service://{}/greet", GREETER_SERVICE_NAME)
+                .parse()
+                .unwrap(),
+            None,
+        );
+
+        assert_that!(
+            result,
+            err(pat!(SchemaError::Subscription(pat!(
+                SubscriptionError::InvalidNatsSourceStream(_)
+            ))))
+        );
+    }
+
+    #[test]
+    fn topic_subscription_publishes_to_kafka() {
+        let schema = Schema::default();
+
+        let (subscription_id, schema) = SchemaUpdater::update_and_return(schema, |updater| {
+            updater
+                .add_kafka_cluster("my-cluster".parse().unwrap(), kafka_cluster_properties())
+                .unwrap();
+
+            updater.add_subscription(
+                "topic://order-events".parse().unwrap(),
+                "kafka://my-cluster/orders".parse().unwrap(),
+                None,
+            )
+        })
+        .unwrap();
+
+        let subscription = schema
+            .get_subscription(subscription_id, Redaction::No)
+            .expect("subscription should exist");
+        assert!(subscription.is_egress());
+        assert_eq!(subscription.kafka_cluster(), Some("my-cluster"));
+        assert_eq!(subscription.sink(), &"kafka://my-cluster/orders");
+        assert_eq!(
+            subscription.metadata().get("group.id"),
+            Some(&subscription_id.to_string())
+        );
+
+        // The virtual topic can be called, but only from handlers
+        let target = schema
+            .resolve_latest_invocation_target(VIRTUAL_TOPIC_SERVICE_NAME, "order-events")
+            .expect("virtual topic should resolve");
+        assert!(!target.public);
+        assert!(
+            schema
+                .resolve_latest_invocation_target(VIRTUAL_TOPIC_SERVICE_NAME, "unknown")
+                .is_none()
+        );
+
+        // The cluster cannot be removed while the subscription publishes to it
+        let result = SchemaUpdater::update_and_return(schema, |updater| {
+            updater.remove_kafka_cluster("my-cluster", AllowOrphanSubscriptions::No)
+        });
+        assert_that!(
+            result,
+            err(pat!(SchemaError::KafkaCluster(pat!(
+                KafkaClusterError::RemovalLeadsToOrphanSubscription(_, _)
+            ))))
+        );
+    }
+
+    #[test]
+    fn output_subscription_publishes_to_kafka() {
+        let schema = Schema::default();
+
+        let (subscription_id, schema) = SchemaUpdater::update_and_return(schema, |updater| {
+            updater
+                .add_deployment(add_deployment_request(vec![greeter_service()]))
+                .unwrap();
+            updater
+                .add_kafka_cluster("my-cluster".parse().unwrap(), kafka_cluster_properties())
+                .unwrap();
+
+            updater.add_subscription(
+                format!("output://{GREETER_SERVICE_NAME}/{GREET_HANDLER_NAME}")
+                    .parse()
+                    .unwrap(),
+                "kafka://my-cluster/greetings".parse().unwrap(),
+                None,
+            )
+        })
+        .unwrap();
+
+        let subscription = schema
+            .get_subscription(subscription_id, Redaction::No)
+            .expect("subscription should exist");
+        assert!(subscription.is_egress());
+        assert_eq!(
+            subscription.source(),
+            &Source::Output {
+                service: GREETER_SERVICE_NAME.to_owned(),
+                handler: GREET_HANDLER_NAME.to_owned(),
+            }
+        );
+        assert_eq!(
+            Source::from_virtual_topic(&subscription.source().virtual_topic().unwrap()),
+            subscription.source().clone()
+        );
+        assert!(schema.publishes_output_of(GREETER_SERVICE_NAME, GREET_HANDLER_NAME));
+        assert!(!schema.publishes_output_of(GREETER_SERVICE_NAME, "unknown"));
+
+        // The outputs cannot be published to with calls to the virtual topic service
+        assert!(
+            schema
+                .resolve_latest_invocation_target(
+                    VIRTUAL_TOPIC_SERVICE_NAME,
+                    subscription.source().virtual_topic().unwrap()
+                )
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn output_source_requires_existing_handler_and_kafka_sink() {
+        let mut updater = SchemaUpdater::default();
+        updater
+            .add_deployment(add_deployment_request(vec![greeter_service()]))
+            .unwrap();
+        updater
+            .add_kafka_cluster("my-cluster".parse().unwrap(), kafka_cluster_properties())
+            .unwrap();
+
+        assert_that!(
+            updater.add_subscription(
+                format!("output://{GREETER_SERVICE_NAME}/unknown")
+                    .parse()
+                    .unwrap(),
+                "kafka://my-cluster/greetings".parse().unwrap(),
+                None,
+            ),
+            err(pat!(SchemaError::Subscription(pat!(
+                SubscriptionError::OutputSourceServiceNotFound(_)
+            ))))
+        );
+        assert_that!(
+            updater.add_subscription(
+                format!("output://{GREETER_SERVICE_NAME}/{GREET_HANDLER_NAME}")
+                    .parse()
+                    .unwrap(),
+                format!("service://{GREETER_SERVICE_NAME}/{GREET_HANDLER_NAME}")
+                    .parse()
+                    .unwrap(),
+                None,
+            ),
+            err(pat!(SchemaError::Subscription(pat!(
+                SubscriptionError::IncompatibleSourceAndSink(_, _)
+            ))))
+        );
+    }
+
+    #[test]
+    fn topic_source_requires_kafka_sink() {
+        let mut updater = SchemaUpdater::default();
+        updater
+            .add_deployment(add_deployment_request(vec![greeter_service()]))
+            .unwrap();
+
+        let result = updater.add_subscription(
+            "topic://order-events".parse().unwrap(),
+            format!("service://{}/greet", GREETER_SERVICE_NAME)
+                .parse()
+                .unwrap(),
+            None,
+        );
+
+        assert_that!(
+            result,
+            err(pat!(SchemaError::Subscription(pat!(
+                SubscriptionError::IncompatibleSourceAndSink(_, _)
+            ))))
+        );
+    }
+
+    #[test]
+    fn pause_resume_and_seek_kafka_subscription() {
+        let schema = Schema::default();
+
+        let (subscription_id, schema) = SchemaUpdater::update_and_return(schema, |updater| {
+            updater
+                .add_deployment(add_deployment_request(vec![greeter_service()]))
+                .unwrap();
+            updater
+                .add_kafka_cluster("my-cluster".parse().unwrap(), kafka_cluster_properties())
+                .unwrap();
+
+            let subscription_id = updater.add_subscription(
+                "kafka://my-cluster/my-topic".parse().unwrap(),
+                format!("service://{}/greet", GREETER_SERVICE_NAME)
+                    .parse()
+                    .unwrap(),
+                None,
+            )?;
+            updater.set_subscription_paused(subscription_id, true)?;
+            updater.seek_subscription(subscription_id, SeekPosition::Offset(10), Some(1))?;
+            Ok(subscription_id)
+        })
+        .unwrap();
+
+        let consumption = schema
+            .get_subscription(subscription_id, Redaction::No)
+            .unwrap()
+            .consumption()
+            .clone();
+        assert!(consumption.is_paused());
+        assert_eq!(consumption.last_seek().unwrap().generation, 1);
+        assert_eq!(consumption.seek_generation(0), 0);
+        assert_eq!(consumption.seek_generation(1), 1);
+
+        let (_, schema) = SchemaUpdater::update_and_return(schema, |updater| {
+            updater.set_subscription_paused(subscription_id, false)?;
+            updater.seek_subscription(subscription_id, SeekPosition::Earliest, None)
+        })
+        .unwrap();
+
+        let consumption = schema
+            .get_subscription(subscription_id, Redaction::No)
+            .unwrap()
+            .consumption()
+            .clone();
+        assert!(!consumption.is_paused());
+        assert_eq!(consumption.last_seek().unwrap().generation, 2);
+        assert_eq!(consumption.seek_generation(0), 2);
+        assert_eq!(consumption.seek_generation(1), 2);
+
+        // Only Kafka subscriptions can be paused
+        let result = SchemaUpdater::update_and_return(schema, |updater| {
+            let subscription_id = updater.add_subscription(
+                "webhook://github".parse().unwrap(),
+                format!("service://{}/greet", GREETER_SERVICE_NAME)
+                    .parse()
+                    .unwrap(),
+                Some(HashMap::from([(
+                    WEBHOOK_SECRET_PROPERTY.to_owned(),
+                    "my-secret".to_owned(),
+                )])),
+            )?;
+            updater.set_subscription_paused(subscription_id, true)
+        });
+        assert_that!(
+            result,
+            err(pat!(SchemaError::Subscription(pat!(
+                SubscriptionError::NotKafkaSource(_)
+            ))))
+        );
+    }
+
     #[test]
     fn get_kafka_cluster_and_subscriptions() {
         let schema = Schema::default();
@@ -3170,6 +4001,7 @@ mod kafka_cluster {
                 assert_eq!(cluster, "config-cluster");
                 assert_eq!(topic, "my-topic");
             }
+            source => panic!("unexpected source {source}"),
         }
 
         // get_kafka_cluster_and_subscriptions should work with config cluster
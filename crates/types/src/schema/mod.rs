@@ -16,6 +16,7 @@
 //!
 //! * Storing deployments, handle registration
 //! * Storing service/handler configurations
+//! * Storing subscriptions, Kafka clusters and schedules
 //!
 //! Check [`registry::SchemaRegistry`] for the schema registry implementation, implementing both read and write operations.
 //!
@@ -24,11 +25,13 @@
 //! The [`Schema`] data structure is a serializable representation of this schema registry.
 
 pub mod deployment;
+pub mod event_transform;
 pub mod info;
 pub mod invocation_target;
 pub mod kafka;
 mod metadata;
 pub mod registry;
+pub mod schedules;
 pub mod service;
 pub mod subscriptions;
 
@@ -26,20 +26,24 @@ use crate::deployment;
 use crate::deployment::{
     DeploymentAddress, Headers, HttpDeploymentAddress, LambdaDeploymentAddress,
 };
-use crate::identifiers::{DeploymentId, LambdaARN, ServiceRevision, SubscriptionId};
+use crate::identifiers::{DeploymentId, LambdaARN, ScheduleId, ServiceRevision, SubscriptionId};
 use crate::net::address::{AdvertisedAddress, HttpIngressPort};
-use crate::schema::deployment::{Deployment, DeploymentResolver, DeploymentType};
+use crate::schema::deployment::{Deployment, DeploymentLimits, DeploymentResolver, DeploymentType};
 use crate::schema::kafka::{KafkaCluster, KafkaClusterName, KafkaClusterResolver};
 use crate::schema::metadata::updater;
 use crate::schema::metadata::updater::{
     KafkaClusterError, SchemaError, SchemaUpdater, ServiceError,
 };
+use crate::schema::schedules::{Schedule, ScheduleResolver};
 use crate::schema::service::{HandlerMetadata, ServiceMetadata, ServiceMetadataResolver};
-use crate::schema::subscriptions::{ListSubscriptionFilter, Subscription, SubscriptionResolver};
+use crate::schema::subscriptions::{
+    ListSubscriptionFilter, SeekPosition, Subscription, SubscriptionResolver,
+};
 
 use crate::schema::Redaction;
 pub use crate::schema::metadata::updater::{
-    AddDeploymentResult, AllowBreakingChanges, AllowOrphanSubscriptions, ModifyServiceRequest,
+    AddDeploymentResult, AddScheduleRequest, AllowBreakingChanges, AllowOrphanSubscriptions,
+    ModifyHandlerRequest, ModifyScheduleRequest, ModifyServiceRequest, ModifyTrafficSplitRequest,
     Overwrite,
 };
 // -- Schema registry error and other types
@@ -258,6 +262,8 @@ pub struct RegisterDeploymentRequest {
 pub struct UpdateDeploymentRequest {
     pub update_deployment_address: Option<UpdateDeploymentAddress>,
     pub additional_headers: Option<Headers>,
+    /// Replaces the limits of the deployment, if set.
+    pub limits: Option<DeploymentLimits>,
     pub overwrite: Overwrite,
     pub apply_mode: ApplyMode,
 }
@@ -408,6 +414,7 @@ impl<Metadata: MetadataService, Discovery: DiscoveryClient, Telemetry>
         UpdateDeploymentRequest {
             update_deployment_address,
             additional_headers,
+            limits,
             overwrite,
             apply_mode,
         }: UpdateDeploymentRequest,
@@ -417,6 +424,69 @@ impl<Metadata: MetadataService, Discovery: DiscoveryClient, Telemetry>
             return Err(SchemaError::NotFound(deployment_id.to_string()).into());
         };
 
+        // Changing only the limits doesn't require discovering the deployment again
+        let update_deployment_request = if update_deployment_address.is_none()
+            && additional_headers.is_none()
+            && overwrite == Overwrite::No
+            && limits.is_some()
+        {
+            None
+        } else {
+            Some(
+                self.discover_deployment_update(
+                    deployment_id,
+                    existing_deployment,
+                    update_deployment_address,
+                    additional_headers,
+                    overwrite,
+                )
+                .await?,
+            )
+        };
+
+        let apply_update = |updater: &mut SchemaUpdater| {
+            if let Some(update_deployment_request) = &update_deployment_request {
+                updater.update_deployment(update_deployment_request.clone())?;
+            }
+            if let Some(limits) = limits {
+                updater.set_deployment_limits(deployment_id, limits)?;
+            }
+            Ok::<_, SchemaError>(())
+        };
+
+        if !apply_mode.should_apply() {
+            // --- Dry run
+            // Suppress logging output in case of a dry run
+            let schemas = tracing::subscriber::with_default(NoSubscriber::new(), || {
+                SchemaUpdater::update(self.metadata_service.get().clone(), apply_update)
+            })?;
+
+            Ok(schemas
+                .get_deployment_and_services(&deployment_id)
+                .expect("deployment was just added"))
+        } else {
+            // --- Apply the deployment update
+            let (_, schemas) = self
+                .metadata_service
+                .update(|schema| Ok(((), SchemaUpdater::update(schema, &apply_update)?)))
+                .await?;
+
+            let (deployment, services) = schemas
+                .get_deployment_and_services(&deployment_id)
+                .expect("deployment was just updated");
+
+            Ok((deployment, services))
+        }
+    }
+
+    async fn discover_deployment_update(
+        &self,
+        deployment_id: DeploymentId,
+        existing_deployment: Deployment,
+        update_deployment_address: Option<UpdateDeploymentAddress>,
+        additional_headers: Option<Headers>,
+        overwrite: Overwrite,
+    ) -> Result<updater::UpdateDeploymentRequest, SchemaRegistryError> {
         let existing_http_auth = match &existing_deployment.ty {
             DeploymentType::Http { auth, .. } => auth.clone(),
             DeploymentType::Lambda { .. } => None,
@@ -548,46 +618,13 @@ impl<Metadata: MetadataService, Discovery: DiscoveryClient, Telemetry>
             .map_err(SchemaRegistryErrorInner::Discovery)
             .map_err(SchemaRegistryError::from)?;
 
-        let update_deployment_request = updater::UpdateDeploymentRequest {
+        Ok(updater::UpdateDeploymentRequest {
             deployment_id,
             deployment_address,
             additional_headers,
             discovery_response,
             overwrite,
-        };
-
-        if !apply_mode.should_apply() {
-            // --- Dry run
-            // Suppress logging output in case of a dry run
-            let schemas = tracing::subscriber::with_default(NoSubscriber::new(), || {
-                SchemaUpdater::update(self.metadata_service.get().clone(), |updater| {
-                    updater.update_deployment(update_deployment_request)
-                })
-            })?;
-
-            Ok(schemas
-                .get_deployment_and_services(&deployment_id)
-                .expect("deployment was just added"))
-        } else {
-            // --- Apply the deployment update
-            let (_, schemas) = self
-                .metadata_service
-                .update(|schema| {
-                    Ok((
-                        (),
-                        SchemaUpdater::update(schema, |updater| {
-                            updater.update_deployment(update_deployment_request.clone())
-                        })?,
-                    ))
-                })
-                .await?;
-
-            let (deployment, services) = schemas
-                .get_deployment_and_services(&deployment_id)
-                .expect("deployment was just updated");
-
-            Ok((deployment, services))
-        }
+        })
     }
 }
 impl<Metadata: MetadataService, Discovery, Telemetry>
@@ -792,6 +829,51 @@ impl<Metadata: MetadataService, Discovery, Telemetry>
         Ok(subscription)
     }
 
+    pub async fn set_subscription_paused(
+        &self,
+        subscription_id: SubscriptionId,
+        paused: bool,
+    ) -> Result<Subscription, SchemaRegistryError> {
+        let (_, schema) = self
+            .metadata_service
+            .update(|schema| {
+                Ok((
+                    (),
+                    SchemaUpdater::update(schema, |updater| {
+                        updater.set_subscription_paused(subscription_id, paused)
+                    })?,
+                ))
+            })
+            .await?;
+
+        Ok(schema
+            .get_subscription(subscription_id, Redaction::Yes)
+            .expect("subscription was just updated"))
+    }
+
+    pub async fn seek_subscription(
+        &self,
+        subscription_id: SubscriptionId,
+        position: SeekPosition,
+        partition: Option<i32>,
+    ) -> Result<Subscription, SchemaRegistryError> {
+        let (_, schema) = self
+            .metadata_service
+            .update(|schema| {
+                Ok((
+                    (),
+                    SchemaUpdater::update(schema, |updater| {
+                        updater.seek_subscription(subscription_id, position, partition)
+                    })?,
+                ))
+            })
+            .await?;
+
+        Ok(schema
+            .get_subscription(subscription_id, Redaction::Yes)
+            .expect("subscription was just updated"))
+    }
+
     pub async fn create_kafka_cluster(
         &self,
         name: KafkaClusterName,
@@ -862,6 +944,79 @@ impl<Metadata: MetadataService, Discovery, Telemetry>
 
         Ok(())
     }
+
+    pub fn get_schedule(&self, schedule_id: ScheduleId) -> Option<Schedule> {
+        self.metadata_service.get().get_schedule(schedule_id)
+    }
+
+    pub fn list_schedules(&self) -> Vec<Schedule> {
+        self.metadata_service.get().list_schedules()
+    }
+
+    pub async fn create_schedule(
+        &self,
+        request: AddScheduleRequest,
+    ) -> Result<Schedule, SchemaRegistryError> {
+        let (schedule_id, schema) = self
+            .metadata_service
+            .update(|schema| {
+                SchemaUpdater::update_and_return(schema, |updater| {
+                    updater.add_schedule(request.clone())
+                })
+                .map_err(Into::into)
+            })
+            .await?;
+
+        Ok(schema
+            .get_schedule(schedule_id)
+            .expect("schedule was just added"))
+    }
+
+    pub async fn modify_schedule(
+        &self,
+        schedule_id: ScheduleId,
+        request: ModifyScheduleRequest,
+    ) -> Result<Schedule, SchemaRegistryError> {
+        let (_, schema) = self
+            .metadata_service
+            .update(|schema| {
+                Ok((
+                    (),
+                    SchemaUpdater::update(schema, |updater| {
+                        updater.modify_schedule(schedule_id, request.clone())
+                    })?,
+                ))
+            })
+            .await?;
+
+        Ok(schema
+            .get_schedule(schedule_id)
+            .expect("schedule was just modified"))
+    }
+
+    pub async fn delete_schedule(
+        &self,
+        schedule_id: ScheduleId,
+    ) -> Result<(), SchemaRegistryError> {
+        self.metadata_service
+            .update(|schema| {
+                Ok((
+                    (),
+                    SchemaUpdater::update(schema, |updater| {
+                        if updater.remove_schedule(schedule_id) {
+                            Ok(())
+                        } else {
+                            Err(SchemaError::NotFound(format!(
+                                "schedule with id '{schedule_id}'"
+                            )))
+                        }
+                    })?,
+                ))
+            })
+            .await?;
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
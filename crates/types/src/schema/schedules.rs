@@ -0,0 +1,388 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::fmt;
+use std::str::FromStr;
+
+use bytes::Bytes;
+use chrono::TimeZone;
+use serde::{Deserialize, Serialize};
+
+use crate::identifiers::{
+    InvocationId, InvocationUuid, PartitionKey, ScheduleId, WithPartitionKey, partitioner,
+};
+use crate::invocation::{InvocationTarget, VirtualObjectHandlerType};
+use crate::time::MillisSinceEpoch;
+
+/// Timezone used when a schedule doesn't specify one.
+pub const DEFAULT_SCHEDULE_TIMEZONE: &str = "UTC";
+
+/// # Overlap policy
+///
+/// What to do when a tick is due while the invocation started by the previous tick is still running.
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "utoipa-schema", derive(utoipa::ToSchema))]
+pub enum OverlapPolicy {
+    /// Start a new invocation on every tick.
+    #[default]
+    Allow,
+    /// Skip the tick if the invocation started by the previous tick has not completed yet.
+    Skip,
+}
+
+impl fmt::Display for OverlapPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OverlapPolicy::Allow => f.write_str("allow"),
+            OverlapPolicy::Skip => f.write_str("skip"),
+        }
+    }
+}
+
+/// Handler invoked on every tick of a schedule.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum ScheduleTarget {
+    Service {
+        name: String,
+        handler: String,
+    },
+    VirtualObject {
+        name: String,
+        key: String,
+        handler: String,
+        handler_ty: VirtualObjectHandlerType,
+    },
+}
+
+impl ScheduleTarget {
+    pub fn service_name(&self) -> &str {
+        match self {
+            ScheduleTarget::Service { name, .. } | ScheduleTarget::VirtualObject { name, .. } => {
+                name
+            }
+        }
+    }
+
+    pub fn handler_name(&self) -> &str {
+        match self {
+            ScheduleTarget::Service { handler, .. }
+            | ScheduleTarget::VirtualObject { handler, .. } => handler,
+        }
+    }
+
+    pub fn key(&self) -> Option<&str> {
+        match self {
+            ScheduleTarget::Service { .. } => None,
+            ScheduleTarget::VirtualObject { key, .. } => Some(key),
+        }
+    }
+
+    pub fn invocation_target(&self) -> InvocationTarget {
+        match self {
+            ScheduleTarget::Service { name, handler } => {
+                InvocationTarget::service(name.clone(), handler.clone())
+            }
+            ScheduleTarget::VirtualObject {
+                name,
+                key,
+                handler,
+                handler_ty,
+            } => InvocationTarget::virtual_object(
+                name.clone(),
+                key.clone(),
+                handler.clone(),
+                *handler_ty,
+            ),
+        }
+    }
+}
+
+impl fmt::Display for ScheduleTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScheduleTarget::Service { name, handler } => write!(f, "{name}/{handler}"),
+            ScheduleTarget::VirtualObject {
+                name, key, handler, ..
+            } => write!(f, "{name}/{key}/{handler}"),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ScheduleExpressionError {
+    #[error("invalid cron expression '{0}': {1}")]
+    Cron(String, #[source] croner::errors::CronError),
+    #[error("unknown timezone '{0}', expected an IANA timezone name like 'Europe/Berlin'")]
+    Timezone(String),
+}
+
+/// A cron expression together with the timezone it is evaluated in.
+#[derive(Debug, Clone)]
+pub struct CronSchedule {
+    cron: croner::Cron,
+    timezone: chrono_tz::Tz,
+}
+
+impl CronSchedule {
+    /// Parses a cron expression with 5 (minute granularity) or 6 (second granularity) fields.
+    pub fn parse(cron: &str, timezone: &str) -> Result<Self, ScheduleExpressionError> {
+        let parsed_cron = croner::Cron::from_str(cron)
+            .map_err(|err| ScheduleExpressionError::Cron(cron.to_owned(), err))?;
+        let timezone = chrono_tz::Tz::from_str(timezone)
+            .map_err(|_| ScheduleExpressionError::Timezone(timezone.to_owned()))?;
+
+        Ok(Self {
+            cron: parsed_cron,
+            timezone,
+        })
+    }
+
+    /// Returns the first occurrence strictly after `time`, or `None` if the expression has no
+    /// further occurrences.
+    pub fn next_occurrence_after(&self, time: MillisSinceEpoch) -> Option<MillisSinceEpoch> {
+        let time = self
+            .timezone
+            .timestamp_millis_opt(i64::try_from(time.as_u64()).ok()?)
+            .single()?;
+
+        self.cron
+            .find_next_occurrence(&time, false)
+            .ok()
+            .and_then(|next| u64::try_from(next.timestamp_millis()).ok())
+            .map(MillisSinceEpoch::new)
+    }
+}
+
+/// A recurring invocation of a handler, driven by a cron expression.
+///
+/// Ticks are executed by the partition processor owning [`Schedule::partition_key`], which keeps
+/// a durable timer for the next tick of every schedule it owns.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Schedule {
+    id: ScheduleId,
+    cron: String,
+    timezone: String,
+    target: ScheduleTarget,
+    #[serde(default, skip_serializing_if = "Bytes::is_empty")]
+    payload: Bytes,
+    #[serde(default)]
+    overlap_policy: OverlapPolicy,
+    /// Bumped on every modification, ticks registered for an older revision are discarded.
+    revision: u32,
+    created_at: MillisSinceEpoch,
+}
+
+impl Schedule {
+    pub(in crate::schema) fn new(
+        id: ScheduleId,
+        cron: String,
+        timezone: String,
+        target: ScheduleTarget,
+        payload: Bytes,
+        overlap_policy: OverlapPolicy,
+    ) -> Self {
+        Self {
+            id,
+            cron,
+            timezone,
+            target,
+            payload,
+            overlap_policy,
+            revision: 1,
+            created_at: MillisSinceEpoch::now(),
+        }
+    }
+
+    pub fn id(&self) -> ScheduleId {
+        self.id
+    }
+
+    pub fn cron(&self) -> &str {
+        &self.cron
+    }
+
+    pub fn timezone(&self) -> &str {
+        &self.timezone
+    }
+
+    pub fn target(&self) -> &ScheduleTarget {
+        &self.target
+    }
+
+    pub fn payload(&self) -> &Bytes {
+        &self.payload
+    }
+
+    pub fn overlap_policy(&self) -> OverlapPolicy {
+        self.overlap_policy
+    }
+
+    pub fn revision(&self) -> u32 {
+        self.revision
+    }
+
+    pub fn created_at(&self) -> MillisSinceEpoch {
+        self.created_at
+    }
+
+    pub fn cron_schedule(&self) -> Result<CronSchedule, ScheduleExpressionError> {
+        CronSchedule::parse(&self.cron, &self.timezone)
+    }
+
+    /// Returns the first tick strictly after `time`.
+    pub fn next_tick_after(&self, time: MillisSinceEpoch) -> Option<MillisSinceEpoch> {
+        // The expression is validated when the schedule is registered
+        self.cron_schedule().ok()?.next_occurrence_after(time)
+    }
+
+    /// The invocation id of the given tick. It's derived from the schedule id and the tick time,
+    /// so that every tick is executed at most once.
+    pub fn tick_invocation_id(&self, tick: MillisSinceEpoch) -> InvocationId {
+        let tick_key = format!("{}/{}", self.id, tick.as_u64());
+        InvocationId::from_parts(
+            self.partition_key(),
+            InvocationUuid::generate(&self.target.invocation_target(), Some(&tick_key)),
+        )
+    }
+
+    pub(in crate::schema) fn set_cron(&mut self, cron: String, timezone: String) {
+        self.cron = cron;
+        self.timezone = timezone;
+    }
+
+    pub(in crate::schema) fn set_payload(&mut self, payload: Bytes) {
+        self.payload = payload;
+    }
+
+    pub(in crate::schema) fn set_overlap_policy(&mut self, overlap_policy: OverlapPolicy) {
+        self.overlap_policy = overlap_policy;
+    }
+
+    pub(in crate::schema) fn bump_revision(&mut self) {
+        self.revision += 1;
+    }
+}
+
+impl WithPartitionKey for Schedule {
+    /// Virtual object ticks run in the partition owning the object key, so that the object state
+    /// and the timer live together. Service ticks are spread by schedule id.
+    fn partition_key(&self) -> PartitionKey {
+        match self.target.key() {
+            Some(key) => partitioner::HashPartitioner::compute_partition_key(key),
+            None => partitioner::HashPartitioner::compute_partition_key(self.id.to_bytes()),
+        }
+    }
+}
+
+pub trait ScheduleResolver {
+    fn get_schedule(&self, id: ScheduleId) -> Option<Schedule>;
+
+    fn list_schedules(&self) -> Vec<Schedule>;
+}
+
+#[cfg(feature = "test-util")]
+mod test_util {
+    use super::*;
+
+    impl Schedule {
+        /// A schedule invoking `MySvc/run` every minute.
+        pub fn mock(overlap_policy: OverlapPolicy) -> Self {
+            Self::new(
+                ScheduleId::new(),
+                "* * * * *".to_owned(),
+                DEFAULT_SCHEDULE_TIMEZONE.to_owned(),
+                ScheduleTarget::Service {
+                    name: "MySvc".to_owned(),
+                    handler: "run".to_owned(),
+                },
+                Bytes::new(),
+                overlap_policy,
+            )
+        }
+
+        /// Simulates a modification of the schedule.
+        pub fn mock_modify(&mut self) {
+            self.bump_revision();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn next_occurrence_in_timezone() {
+        let schedule = CronSchedule::parse("0 9 * * *", "Europe/Berlin").unwrap();
+
+        // 2024-01-15T00:00:00Z
+        let start = MillisSinceEpoch::new(1_705_276_800_000);
+        // 2024-01-15T08:00:00Z is 09:00 in Berlin (CET, UTC+1)
+        assert_eq!(
+            schedule.next_occurrence_after(start),
+            Some(MillisSinceEpoch::new(1_705_305_600_000))
+        );
+    }
+
+    #[test]
+    fn next_occurrence_is_exclusive() {
+        let schedule = CronSchedule::parse("*/10 * * * * *", DEFAULT_SCHEDULE_TIMEZONE).unwrap();
+
+        let tick = MillisSinceEpoch::new(1_705_276_800_000);
+        assert_eq!(
+            schedule.next_occurrence_after(tick),
+            Some(MillisSinceEpoch::new(1_705_276_810_000))
+        );
+    }
+
+    #[test]
+    fn invalid_expressions() {
+        assert!(matches!(
+            CronSchedule::parse("not a cron", DEFAULT_SCHEDULE_TIMEZONE),
+            Err(ScheduleExpressionError::Cron(..))
+        ));
+        assert!(matches!(
+            CronSchedule::parse("* * * * *", "Mars/Olympus_Mons"),
+            Err(ScheduleExpressionError::Timezone(..))
+        ));
+    }
+
+    #[test]
+    fn tick_invocation_id_is_deterministic() {
+        let schedule = Schedule::new(
+            ScheduleId::new(),
+            "* * * * *".to_owned(),
+            DEFAULT_SCHEDULE_TIMEZONE.to_owned(),
+            ScheduleTarget::VirtualObject {
+                name: "Counter".to_owned(),
+                key: "my-key".to_owned(),
+                handler: "add".to_owned(),
+                handler_ty: VirtualObjectHandlerType::Exclusive,
+            },
+            Bytes::new(),
+            OverlapPolicy::Allow,
+        );
+        let tick = MillisSinceEpoch::new(1_705_276_800_000);
+
+        assert_eq!(
+            schedule.tick_invocation_id(tick),
+            schedule.tick_invocation_id(tick)
+        );
+        assert_ne!(
+            schedule.tick_invocation_id(tick),
+            schedule.tick_invocation_id(MillisSinceEpoch::new(tick.as_u64() + 60_000))
+        );
+        assert_eq!(
+            schedule.tick_invocation_id(tick).partition_key(),
+            partitioner::HashPartitioner::compute_partition_key("my-key")
+        );
+    }
+}
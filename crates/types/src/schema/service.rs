@@ -18,14 +18,16 @@ use serde::Serialize;
 use restate_util_time::FriendlyDuration;
 
 use crate::config::{DEFAULT_ABORT_TIMEOUT, DEFAULT_INACTIVITY_TIMEOUT};
-use crate::identifiers::{DeploymentId, ServiceRevision};
+use crate::identifiers::{DeploymentId, InvocationId, ServiceRevision};
 use crate::invocation::{
     InvocationTargetType, ServiceType, VirtualObjectHandlerType, WorkflowHandlerType,
 };
 use crate::net::address::AdvertisedAddress;
 use crate::net::address::HttpIngressPort;
 use crate::schema::info::SchemaInfo;
-use crate::schema::invocation_target::{DEFAULT_IDEMPOTENCY_RETENTION, OnMaxAttempts};
+use crate::schema::invocation_target::{
+    DEFAULT_IDEMPOTENCY_RETENTION, DeadLetterTarget, OnMaxAttempts,
+};
 
 /// This API returns service metadata, as shown in the Admin API.
 ///
@@ -86,6 +88,13 @@ pub struct ServiceMetadata {
     /// Latest revision of the service.
     pub revision: ServiceRevision,
 
+    /// # Traffic split
+    ///
+    /// Split of new invocations between the deployment exposing the latest revision of the
+    /// service and a deployment exposing a previous revision.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub traffic_split: Option<TrafficSplit>,
+
     /// # Public
     ///
     /// If true, the service can be invoked through the ingress.
@@ -204,6 +213,93 @@ fn default_abort_timeout() -> Duration {
     DEFAULT_ABORT_TIMEOUT
 }
 
+/// # Traffic split
+///
+/// Routes part of the new invocations of a service to the deployment exposing its latest
+/// revision (the canary), and the rest to a deployment exposing a previous revision (the stable
+/// deployment). Invocations stay on the deployment they started on.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa-schema", derive(utoipa::ToSchema))]
+pub struct TrafficSplit {
+    /// # Canary deployment id
+    ///
+    /// Deployment exposing the latest revision of the service.
+    pub canary_deployment_id: DeploymentId,
+
+    /// # Stable deployment id
+    ///
+    /// Deployment exposing a previous revision of the service. It receives the new invocations
+    /// that are not routed to the canary deployment.
+    pub stable_deployment_id: DeploymentId,
+
+    /// # Canary weight
+    ///
+    /// Percentage of new invocations routed to the canary deployment, between 0 and 100.
+    pub canary_weight: u8,
+
+    /// # Rollback
+    ///
+    /// If set, the split is rolled back automatically when the canary deployment fails too often.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rollback: Option<CanaryRollbackPolicy>,
+
+    /// # Rolled back
+    ///
+    /// True if the split was rolled back automatically. A rolled back split routes all new
+    /// invocations to the stable deployment.
+    #[serde(default)]
+    pub rolled_back: bool,
+}
+
+impl TrafficSplit {
+    /// Returns the deployment a new invocation with the given id is routed to.
+    ///
+    /// The choice only depends on the invocation id, so all attempts of an invocation pick the
+    /// same deployment until it gets pinned.
+    pub fn route(&self, invocation_id: &InvocationId) -> DeploymentId {
+        let bucket = u128::from_be_bytes(invocation_id.invocation_uuid().to_bytes()) % 100;
+        if bucket < u128::from(self.canary_weight) {
+            self.canary_deployment_id
+        } else {
+            self.stable_deployment_id
+        }
+    }
+}
+
+/// # Canary rollback policy
+///
+/// Rolls back a traffic split when the failure rate of the attempts against the canary
+/// deployment exceeds a threshold.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "utoipa-schema", derive(utoipa::ToSchema))]
+pub struct CanaryRollbackPolicy {
+    /// # Max failure rate
+    ///
+    /// Percentage of failed attempts against the canary deployment above which the split is
+    /// rolled back, between 0 and 100.
+    pub max_failure_rate: u8,
+
+    /// # Min attempts
+    ///
+    /// Number of attempts against the canary deployment needed before the failure rate is
+    /// evaluated. Default: `20`.
+    #[serde(default = "default_canary_min_attempts")]
+    pub min_attempts: u32,
+}
+
+impl CanaryRollbackPolicy {
+    /// Returns `true` if the given outcomes of attempts against the canary deployment require
+    /// a rollback.
+    pub fn is_exceeded(&self, attempts: u32, failures: u32) -> bool {
+        attempts >= self.min_attempts.max(1)
+            && u64::from(failures) * 100 > u64::from(self.max_failure_rate) * u64::from(attempts)
+    }
+}
+
+fn default_canary_min_attempts() -> u32 {
+    20
+}
+
 /// # Service retry policy
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[cfg_attr(feature = "utoipa-schema", derive(utoipa::ToSchema))]
@@ -246,6 +342,12 @@ pub struct ServiceRetryPolicyMetadata {
     /// Behavior when max attempts are reached.
     #[serde(default)]
     pub on_max_attempts: OnMaxAttempts,
+
+    /// # Dead letter
+    ///
+    /// Handler receiving the invocations that exhausted their retries, when `on_max_attempts` is `DeadLetter`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dead_letter: Option<DeadLetterTarget>,
 }
 
 impl Default for ServiceRetryPolicyMetadata {
@@ -256,6 +358,7 @@ impl Default for ServiceRetryPolicyMetadata {
             max_attempts: None,
             max_interval: None,
             on_max_attempts: Default::default(),
+            dead_letter: None,
         }
     }
 }
@@ -492,6 +595,12 @@ pub struct HandlerRetryPolicyMetadata {
     /// Behavior when max attempts are reached.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub on_max_attempts: Option<OnMaxAttempts>,
+
+    /// # Dead letter
+    ///
+    /// Handler receiving the invocations that exhausted their retries, when `on_max_attempts` is `DeadLetter`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dead_letter: Option<DeadLetterTarget>,
 }
 
 #[cfg(feature = "test-util")]
@@ -8,7 +8,7 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fmt;
 
 use serde::Deserialize;
@@ -17,11 +17,118 @@ use serde::Serialize;
 use crate::identifiers::SubscriptionId;
 use crate::invocation::{VirtualObjectHandlerType, WorkflowHandlerType};
 use crate::schema::Redaction;
+use crate::time::MillisSinceEpoch;
+
+/// Subscription option holding the name of the durable JetStream consumer of a [`Source::Nats`].
+/// Defaults to the subscription id.
+pub const NATS_CONSUMER_PROPERTY: &str = "consumer";
+/// Subscription option holding the subject filter of the JetStream consumer of a [`Source::Nats`].
+pub const NATS_FILTER_SUBJECT_PROPERTY: &str = "filter_subject";
+/// Subscription options holding the credentials used to connect to the NATS server of a
+/// [`Source::Nats`].
+pub const NATS_USER_PROPERTY: &str = "user";
+pub const NATS_PASSWORD_PROPERTY: &str = "password";
+pub const NATS_TOKEN_PROPERTY: &str = "token";
+pub const NATS_CREDENTIALS_FILE_PROPERTY: &str = "credentials_file";
+/// Subscription option holding the secret that requests to a [`Source::Webhook`] must carry
+/// as bearer token. Required unless [`WEBHOOK_ALLOW_UNAUTHENTICATED_PROPERTY`] is `true`.
+pub const WEBHOOK_SECRET_PROPERTY: &str = "secret";
+/// Subscription option accepting requests without bearer token to a [`Source::Webhook`] which
+/// has no secret.
+pub const WEBHOOK_ALLOW_UNAUTHENTICATED_PROPERTY: &str = "allow_unauthenticated";
+
+/// Prefix of the subscription options interpreted by Restate itself. These options are not passed
+/// to the Kafka clients.
+pub const RESTATE_PROPERTY_PREFIX: &str = "restate.";
+/// Subscription options prefix of the header filters of a [`Source::Kafka`]. Only the records
+/// carrying the header `<name>` with the given value are consumed, e.g.
+/// `restate.filter.header.event-type=order.created`.
+pub const KAFKA_FILTER_HEADER_PROPERTY_PREFIX: &str = "restate.filter.header.";
+/// Subscription option holding a JSON path predicate on the payload of the records of a
+/// [`Source::Kafka`], e.g. `$.type == "order.created"`. Only the matching records are consumed.
+pub const KAFKA_FILTER_JSON_PATH_PROPERTY: &str = "restate.filter.json-path";
+/// Subscription option holding the template of the Virtual Object/Workflow key, e.g.
+/// `{header.tenant}-{key}`.
+pub const KAFKA_KEY_TEMPLATE_PROPERTY: &str = "restate.key-template";
+/// Subscription option holding the format the payloads of a [`Source::Kafka`] are decoded from
+/// to JSON, either `avro` or `protobuf`.
+pub const KAFKA_DECODE_PROPERTY: &str = "restate.decode";
+/// Subscription option holding the local file with the schema of the decoded payloads: an Avro
+/// schema, or a Protobuf file descriptor set.
+pub const KAFKA_DECODE_SCHEMA_FILE_PROPERTY: &str = "restate.decode.schema-file";
+/// Subscription option holding the fully qualified name of the decoded Protobuf message.
+pub const KAFKA_DECODE_MESSAGE_PROPERTY: &str = "restate.decode.message";
+/// Subscription option holding the wire format of the decoded payloads, either `raw` (default) or
+/// `confluent` for payloads prefixed with the Confluent schema registry header.
+pub const KAFKA_DECODE_WIRE_FORMAT_PROPERTY: &str = "restate.decode.wire-format";
+
+/// Name of the virtual service that handlers send one-way calls to in order to publish to a
+/// [`Source::Topic`]. The handler name is the name of the topic, and the key of the call, if
+/// any, is used as key of the published record.
+pub const VIRTUAL_TOPIC_SERVICE_NAME: &str = "restate.topic";
 
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 #[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub enum Source {
-    Kafka { cluster: String, topic: String },
+    Kafka {
+        cluster: String,
+        topic: String,
+    },
+    /// NATS JetStream stream, consumed through a durable pull consumer.
+    Nats {
+        server: String,
+        stream: String,
+    },
+    /// Events pushed over HTTP to the webhook listener of the ingress.
+    Webhook {
+        name: String,
+    },
+    /// Virtual topic that handlers publish to with one-way calls to
+    /// [`VIRTUAL_TOPIC_SERVICE_NAME`]. Can only be combined with a [`Sink::Kafka`].
+    Topic {
+        name: String,
+    },
+    /// Outputs of the invocations of a handler, published when the invocations complete. Can
+    /// only be combined with a [`Sink::Kafka`].
+    Output {
+        service: String,
+        handler: String,
+    },
+}
+
+impl Source {
+    /// Returns the name of the Kafka cluster, if this is a Kafka source.
+    pub fn kafka_cluster(&self) -> Option<&str> {
+        match self {
+            Source::Kafka { cluster, .. } => Some(cluster),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the virtual topic the records of this source are published to, if
+    /// this is an egress source. The outputs of a handler are published to the virtual topic
+    /// `<service>/<handler>`, which cannot collide with the names of [`Source::Topic`].
+    pub fn virtual_topic(&self) -> Option<String> {
+        match self {
+            Source::Topic { name } => Some(name.clone()),
+            Source::Output { service, handler } => Some(format!("{service}/{handler}")),
+            _ => None,
+        }
+    }
+
+    /// Returns the egress source publishing the records of the virtual topic `name`. This is the
+    /// inverse of [`Source::virtual_topic`].
+    pub fn from_virtual_topic(name: &str) -> Source {
+        match name.split_once('/') {
+            Some((service, handler)) => Source::Output {
+                service: service.to_owned(),
+                handler: handler.to_owned(),
+            },
+            None => Source::Topic {
+                name: name.to_owned(),
+            },
+        }
+    }
 }
 
 impl fmt::Display for Source {
@@ -30,6 +137,18 @@ impl fmt::Display for Source {
             Source::Kafka { cluster, topic, .. } => {
                 write!(f, "kafka://{cluster}/{topic}")
             }
+            Source::Nats { server, stream } => {
+                write!(f, "nats://{server}/{stream}")
+            }
+            Source::Webhook { name } => {
+                write!(f, "webhook://{name}")
+            }
+            Source::Topic { name } => {
+                write!(f, "topic://{name}")
+            }
+            Source::Output { service, handler } => {
+                write!(f, "output://{service}/{handler}")
+            }
         }
     }
 }
@@ -46,6 +165,19 @@ pub enum Sink {
     Invocation {
         event_invocation_target_template: EventInvocationTargetTemplate,
     },
+    /// Kafka topic the records of a [`Source::Topic`] or [`Source::Output`] are published to,
+    /// exactly once.
+    Kafka { cluster: String, topic: String },
+}
+
+impl Sink {
+    /// Returns the name of the Kafka cluster, if this is a Kafka sink.
+    pub fn kafka_cluster(&self) -> Option<&str> {
+        match self {
+            Sink::Kafka { cluster, .. } => Some(cluster),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
@@ -83,6 +215,9 @@ impl fmt::Display for Sink {
             } => {
                 write!(f, "service://{name}/{handler}")
             }
+            Sink::Kafka { cluster, topic } => {
+                write!(f, "kafka://{cluster}/{topic}")
+            }
         }
     }
 }
@@ -99,6 +234,109 @@ pub struct Subscription {
     source: Source,
     sink: Sink,
     metadata: HashMap<String, String>,
+    #[serde(default)]
+    consumption: ConsumptionState,
+}
+
+/// Position a Kafka subscription can be moved to with a seek.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize)]
+pub enum SeekPosition {
+    /// Oldest record retained by the topic.
+    Earliest,
+    /// Next record produced to the topic.
+    Latest,
+    /// Record with the given offset.
+    Offset(i64),
+    /// First record whose timestamp is greater than or equal to the given one.
+    Timestamp(MillisSinceEpoch),
+}
+
+impl fmt::Display for SeekPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SeekPosition::Earliest => f.write_str("earliest"),
+            SeekPosition::Latest => f.write_str("latest"),
+            SeekPosition::Offset(offset) => write!(f, "offset {offset}"),
+            SeekPosition::Timestamp(timestamp) => write!(f, "timestamp {timestamp}"),
+        }
+    }
+}
+
+/// Seek requested for a Kafka subscription.
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SubscriptionSeek {
+    /// Incremented by every seek of the subscription.
+    pub generation: u32,
+    pub position: SeekPosition,
+    /// Partition of the topic to move, or all the partitions if `None`.
+    pub partition: Option<i32>,
+}
+
+/// Consumption state of a Kafka subscription, changed with the pause, resume and seek
+/// operations.
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ConsumptionState {
+    #[serde(default)]
+    paused: bool,
+    /// Last seek requested. The consumers apply it to the partitions it moves, unless the
+    /// generation committed with the offset of the partition shows it was applied already.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    last_seek: Option<SubscriptionSeek>,
+    /// Generation of the last seek that moved all the partitions.
+    #[serde(default)]
+    topic_seek_generation: u32,
+    /// Generation of the last seek that moved a single partition, by partition.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    partition_seek_generations: BTreeMap<i32, u32>,
+}
+
+impl ConsumptionState {
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn last_seek(&self) -> Option<&SubscriptionSeek> {
+        self.last_seek.as_ref()
+    }
+
+    /// Returns the generation of the last seek that moved `partition`, or 0 if it was never
+    /// moved. Records consumed after a seek are deduplicated separately from the ones consumed
+    /// before, so that they can be replayed.
+    pub fn seek_generation(&self, partition: i32) -> u32 {
+        self.partition_seek_generations
+            .get(&partition)
+            .copied()
+            .unwrap_or_default()
+            .max(self.topic_seek_generation)
+    }
+
+    pub(crate) fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    pub(crate) fn seek(&mut self, position: SeekPosition, partition: Option<i32>) {
+        let generation = self
+            .last_seek
+            .as_ref()
+            .map(|seek| seek.generation)
+            .unwrap_or_default()
+            + 1;
+        match partition {
+            Some(partition) => {
+                self.partition_seek_generations
+                    .insert(partition, generation);
+            }
+            None => {
+                self.topic_seek_generation = generation;
+                self.partition_seek_generations.clear();
+            }
+        }
+        self.last_seek = Some(SubscriptionSeek {
+            generation,
+            position,
+            partition,
+        });
+    }
 }
 
 impl Subscription {
@@ -113,6 +351,7 @@ impl Subscription {
             source,
             sink,
             metadata,
+            consumption: ConsumptionState::default(),
         }
     }
 
@@ -135,6 +374,27 @@ impl Subscription {
     pub fn metadata_mut(&mut self) -> &mut HashMap<String, String> {
         &mut self.metadata
     }
+
+    pub fn consumption(&self) -> &ConsumptionState {
+        &self.consumption
+    }
+
+    pub(crate) fn consumption_mut(&mut self) -> &mut ConsumptionState {
+        &mut self.consumption
+    }
+
+    /// Returns the name of the Kafka cluster this subscription consumes from or publishes to.
+    pub fn kafka_cluster(&self) -> Option<&str> {
+        self.source
+            .kafka_cluster()
+            .or_else(|| self.sink.kafka_cluster())
+    }
+
+    /// Returns true if this subscription publishes the records of a virtual topic or the outputs
+    /// of a handler to Kafka, rather than invoking a handler.
+    pub fn is_egress(&self) -> bool {
+        matches!(self.sink, Sink::Kafka { .. })
+    }
 }
 
 pub enum ListSubscriptionFilter {
@@ -187,6 +447,10 @@ mod serde_hacks {
         Invocation {
             event_invocation_target_template: EventInvocationTargetTemplate,
         },
+        Kafka {
+            cluster: String,
+            topic: String,
+        },
     }
 
     impl From<Sink> for super::Sink {
@@ -231,6 +495,7 @@ mod serde_hacks {
                 } => Self::Invocation {
                     event_invocation_target_template,
                 },
+                Sink::Kafka { cluster, topic } => Self::Kafka { cluster, topic },
             }
         }
     }
@@ -243,6 +508,7 @@ mod serde_hacks {
                 } => Self::Invocation {
                     event_invocation_target_template,
                 },
+                super::Sink::Kafka { cluster, topic } => Self::Kafka { cluster, topic },
             }
         }
     }
@@ -271,6 +537,7 @@ pub mod mocks {
                     },
                 },
                 metadata: Default::default(),
+                consumption: Default::default(),
             }
         }
     }
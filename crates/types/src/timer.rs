@@ -8,10 +8,14 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use crate::identifiers::InvocationUuid;
 use crate::time::MillisSinceEpoch;
 use std::borrow::Borrow;
 use std::fmt::Debug;
 use std::hash::Hash;
+use std::sync::LazyLock;
+
+use tokio::sync::broadcast;
 
 pub trait Timer: Hash + Eq + Borrow<Self::TimerKey> {
     type TimerKey: TimerKey + Send;
@@ -24,3 +28,159 @@ pub trait Timer: Hash + Eq + Borrow<Self::TimerKey> {
 pub trait TimerKey: Ord + Clone + Hash + Debug {
     fn wake_up_time(&self) -> MillisSinceEpoch;
 }
+
+/// Fire requests that weren't handled by a partition leader yet are dropped beyond this.
+const PENDING_FIRE_REQUESTS: usize = 64;
+
+static TIME_CONTROL: LazyLock<TimeControl> = LazyLock::new(TimeControl::default);
+
+/// Fires timers of the partition processors ahead of their wake up time.
+///
+/// Only followed by the partition leaders of this process, if the `controllable_time`
+/// experimental feature is enabled. Requests are one-shot: the time observed by the timers isn't
+/// changed, so timers registered afterwards follow the wall clock again. This matches the SDKs,
+/// which compute the wake up time of a sleep from their own clock.
+#[derive(Debug)]
+pub struct TimeControl {
+    requests: broadcast::Sender<FireTimers>,
+}
+
+impl Default for TimeControl {
+    fn default() -> Self {
+        Self {
+            requests: broadcast::Sender::new(PENDING_FIRE_REQUESTS),
+        }
+    }
+}
+
+impl TimeControl {
+    /// The time control of this process.
+    pub fn global() -> &'static TimeControl {
+        &TIME_CONTROL
+    }
+
+    /// Fires every timer due until `until`. Returns the number of partition leaders notified.
+    pub fn fire_until(&self, until: MillisSinceEpoch) -> usize {
+        self.fire(FireTimers {
+            until,
+            invocation_uuid: None,
+        })
+    }
+
+    /// Fires the pending sleeps and the delayed start of an invocation. Returns the number of
+    /// partition leaders notified.
+    pub fn fire_invocation(&self, invocation_uuid: InvocationUuid) -> usize {
+        self.fire(FireTimers {
+            until: MillisSinceEpoch::MAX,
+            invocation_uuid: Some(invocation_uuid),
+        })
+    }
+
+    fn fire(&self, request: FireTimers) -> usize {
+        // fails only if no partition leader is subscribed
+        self.requests.send(request).unwrap_or_default()
+    }
+
+    /// Receives the requests to fire timers.
+    pub fn subscribe(&self) -> broadcast::Receiver<FireTimers> {
+        self.requests.subscribe()
+    }
+}
+
+/// A request to fire timers ahead of their wake up time, see [`TimeControl`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FireTimers {
+    /// Only the timers due until this time fire.
+    pub until: MillisSinceEpoch,
+    /// If set, only the timers waking up this invocation fire. That excludes the cleanup of its
+    /// status once it completed.
+    pub invocation_uuid: Option<InvocationUuid>,
+}
+
+impl FireTimers {
+    /// Whether a timer of `invocation_uuid` due at `wake_up_time` fires. `wakes_up_invocation`
+    /// tells whether the timer resumes or starts the invocation.
+    pub fn matches(
+        &self,
+        wake_up_time: MillisSinceEpoch,
+        invocation_uuid: InvocationUuid,
+        wakes_up_invocation: bool,
+    ) -> bool {
+        wake_up_time <= self.until
+            && self
+                .invocation_uuid
+                .is_none_or(|requested| wakes_up_invocation && requested == invocation_uuid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    #[test]
+    fn fire_until_matches_due_timers_of_every_invocation() {
+        let now = MillisSinceEpoch::now();
+        let request = FireTimers {
+            until: now + Duration::from_secs(60),
+            invocation_uuid: None,
+        };
+
+        assert!(request.matches(now, InvocationUuid::mock_random(), true));
+        assert!(request.matches(now, InvocationUuid::mock_random(), false));
+        assert!(request.matches(request.until, InvocationUuid::mock_random(), true));
+        assert!(!request.matches(
+            now + Duration::from_secs(61),
+            InvocationUuid::mock_random(),
+            true
+        ));
+    }
+
+    #[test]
+    fn fire_invocation_matches_only_its_wake_ups() {
+        let invocation_uuid = InvocationUuid::mock_random();
+        let request = FireTimers {
+            until: MillisSinceEpoch::MAX,
+            invocation_uuid: Some(invocation_uuid),
+        };
+        let in_a_week = MillisSinceEpoch::now() + Duration::from_secs(7 * 24 * 3600);
+
+        assert!(request.matches(in_a_week, invocation_uuid, true));
+        // the cleanup of the completed invocation doesn't fire
+        assert!(!request.matches(in_a_week, invocation_uuid, false));
+        assert!(!request.matches(in_a_week, InvocationUuid::mock_random(), true));
+    }
+
+    #[test]
+    fn requests_reach_the_subscribers() {
+        let time_control = TimeControl::default();
+        let until = MillisSinceEpoch::now();
+        // without partition leaders, requests are dropped
+        assert_eq!(time_control.fire_until(until), 0);
+
+        let mut first = time_control.subscribe();
+        let mut second = time_control.subscribe();
+        assert_eq!(time_control.fire_until(until), 2);
+        let invocation_uuid = InvocationUuid::mock_random();
+        assert_eq!(time_control.fire_invocation(invocation_uuid), 2);
+
+        for requests in [&mut first, &mut second] {
+            assert_eq!(
+                requests.try_recv().unwrap(),
+                FireTimers {
+                    until,
+                    invocation_uuid: None
+                }
+            );
+            assert_eq!(
+                requests.try_recv().unwrap(),
+                FireTimers {
+                    until: MillisSinceEpoch::MAX,
+                    invocation_uuid: Some(invocation_uuid)
+                }
+            );
+            assert!(requests.try_recv().is_err());
+        }
+    }
+}
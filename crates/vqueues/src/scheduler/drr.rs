@@ -444,7 +444,9 @@ mod tests {
     use restate_types::ServiceName;
     use restate_types::clock::UniqueTimestamp;
     use restate_types::identifiers::{PartitionId, PartitionKey};
+    use restate_types::live::Live;
     use restate_types::partitions::Partition;
+    use restate_types::schema::Schema;
     use restate_types::sharding::KeyRange;
     use restate_types::vqueues::VQueueId;
     use restate_types::vqueues::{EntryId, EntryKind};
@@ -651,6 +653,7 @@ mod tests {
             global_throttling,
             MemoryPool::unlimited(),
             NonZeroByteCount::new(NonZeroUsize::MIN),
+            Live::from_value(Schema::default()),
         )
         .await
         .expect("resource manager creation should succeed")
@@ -8,6 +8,7 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+mod deployment_limiter;
 mod invoker;
 mod invoker_memory;
 mod invoker_throttle;
@@ -31,20 +32,25 @@ use restate_storage_api::StorageError;
 use restate_storage_api::lock_table::LoadLocks;
 use restate_storage_api::vqueue_table::metadata::VQueueMeta;
 use restate_storage_api::vqueue_table::{EntryKey, EntryMetadata};
-use restate_types::identifiers::PartitionKey;
+use restate_types::identifiers::{DeploymentId, PartitionKey};
+use restate_types::live::Live;
+use restate_types::schema::Schema;
+use restate_types::schema::deployment::DeploymentLimits;
 use restate_types::vqueues::EntryKind;
 use restate_types::{LockName, Scope};
 use restate_util_string::ReString;
 use restate_worker_api::resources::{ResourceManagerUpdate, UserPermitKind};
 use restate_worker_api::{ResourceKind, UserLimitCounterEntry};
 
+use self::deployment_limiter::{DeploymentCheck, DeploymentLimiter};
 use self::invoker::InvokerConcurrencyLimiter;
 use self::invoker_memory::InvokerMemoryLimiter;
 use self::invoker_throttle::{InvokerThrottlingLimiter, ThrottlingAcquire};
 use self::locks::Locks;
 use self::permit::ProvisionalPermit;
-use self::user_limiter::UserLimiter;
+use self::user_limiter::{RateCheck, UserLimiter};
 use super::VQueueHandle;
+use super::clock::SchedulerClock;
 use super::eligible::EligibilityTracker;
 use crate::GlobalTokenBucket;
 
@@ -59,6 +65,9 @@ pub struct ResourceManager {
     invoker_throttling: InvokerThrottlingLimiter,
     invoker_memory: InvokerMemoryLimiter,
     user_limiter: UserLimiter,
+    deployment_limiter: DeploymentLimiter,
+    /// Resolves the deployments invocations run on, and their limits
+    schema: Live<Schema>,
     rx: mpsc::UnboundedReceiver<ResourceManagerUpdate>,
     // We need to keep this alive to:
     // - Keep the receiver alive even if we don't have any resource permits handed out
@@ -77,6 +86,7 @@ impl ResourceManager {
         global_throttling: Option<GlobalTokenBucket>,
         memory_pool: MemoryPool,
         initial_invocation_memory: NonZeroByteCount,
+        schema: Live<Schema>,
     ) -> Result<Self, StorageError> {
         let locks = Locks::create(storage).await?;
 
@@ -87,6 +97,8 @@ impl ResourceManager {
             invoker_throttling: InvokerThrottlingLimiter::new(global_throttling),
             invoker_memory: InvokerMemoryLimiter::new(memory_pool, initial_invocation_memory),
             user_limiter: UserLimiter::create(),
+            deployment_limiter: DeploymentLimiter::create(),
+            schema,
             locks,
             rx,
             tx: _tx,
@@ -117,7 +129,14 @@ impl ResourceManager {
             ResourceKind::InvokerMemory => {
                 self.invoker_memory.remove_from_waiters(handle);
             }
-            ResourceKind::DeploymentConcurrency => todo!(),
+            ResourceKind::DeploymentConcurrency { deployment_id } => {
+                self.deployment_limiter
+                    .remove_from_waiters(handle, deployment_id);
+            }
+            ResourceKind::DeploymentRate { deployment_id, .. } => {
+                self.deployment_limiter
+                    .remove_from_rate_waiters(handle, deployment_id);
+            }
             ResourceKind::LimitKeyConcurrency {
                 scope,
                 limit_key,
@@ -127,6 +146,19 @@ impl ResourceManager {
                 self.user_limiter
                     .remove_from_waiters(handle, scope, limit_key, *blocked_level);
             }
+            ResourceKind::LimitKeyRate {
+                scope,
+                limit_key,
+                blocked_level,
+                ..
+            } => {
+                self.user_limiter.remove_from_rate_waiters(
+                    handle,
+                    scope,
+                    limit_key,
+                    *blocked_level,
+                );
+            }
         }
     }
 
@@ -172,6 +204,26 @@ impl ResourceManager {
                     let woken = self.user_limiter.release_concurrency(&scope, &limit_key);
                     eligible.wake_up_queues(woken);
                 }
+                UserPermitKind::LimitKeyRate(scope, limit_key) => {
+                    // The permit was never used, the token can go back to the bucket.
+                    self.user_limiter.refund_rate_tokens(
+                        &scope,
+                        &limit_key,
+                        SchedulerClock.now_millis(),
+                    );
+                }
+                UserPermitKind::DeploymentConcurrency(deployment_id) => {
+                    let woken = self.deployment_limiter.release_concurrency(&deployment_id);
+                    eligible.wake_up_queues(woken);
+                }
+                UserPermitKind::DeploymentRate(deployment_id) => {
+                    let limits = self.deployment_limits(&deployment_id);
+                    self.deployment_limiter.refund_rate_token(
+                        &deployment_id,
+                        &limits,
+                        SchedulerClock.now_millis(),
+                    );
+                }
             }
         }
     }
@@ -180,9 +232,10 @@ impl ResourceManager {
         &mut self,
         cx: &mut std::task::Context<'_>,
         vqueue: VQueueHandle,
+        partition_key: PartitionKey,
         meta: &VQueueMeta,
         key: &EntryKey,
-        _metadata: &EntryMetadata,
+        metadata: &EntryMetadata,
         current_permit: &mut PermitBuilder,
     ) -> AcquireOutcome {
         if !current_permit.has_user_permit() {
@@ -239,11 +292,89 @@ impl ResourceManager {
                     });
                 }
 
-                // Stage the permit — counters are incremented in secure()
+                let now = SchedulerClock.now_millis();
+                let rate = self
+                    .user_limiter
+                    .check_rate_capacity(scope, meta.limit_key(), now);
+                if let RateCheck::Blocked {
+                    level: blocked_level,
+                    rule_handle,
+                    retry_at,
+                } = rate
+                {
+                    trace!(
+                        %scope,
+                        limit_key = %meta.limit_key(),
+                        blocked_at = %blocked_level,
+                        %retry_at,
+                        "User rate limit reached",
+                    );
+                    self.user_limiter.add_to_rate_waiters(
+                        vqueue,
+                        scope,
+                        meta.limit_key(),
+                        blocked_level,
+                        now,
+                    );
+                    return AcquireOutcome::BlockedOn(ResourceKind::LimitKeyRate {
+                        scope: scope.clone(),
+                        limit_key: meta.limit_key().clone(),
+                        blocked_level,
+                        blocked_rule: Some(rule_handle),
+                        estimated_retry_at: retry_at,
+                    });
+                }
+
+                // Stage the permit — counters are incremented and tokens are taken in secure()
                 provisional.add_permit(UserPermitKind::LimitKeyConcurrency(
                     scope.clone(),
                     meta.limit_key().clone(),
                 ));
+                if matches!(rate, RateCheck::Available) {
+                    provisional.add_permit(UserPermitKind::LimitKeyRate(
+                        scope.clone(),
+                        meta.limit_key().clone(),
+                    ));
+                }
+            }
+
+            // Invocations count against the limits of the deployment they are expected to run on
+            if key.kind() == EntryKind::Invocation
+                && let Some(deployment_id) =
+                    self.resolve_deployment(partition_key, meta, key, metadata)
+            {
+                let limits = self.deployment_limits(&deployment_id);
+                let now = SchedulerClock.now_millis();
+                match self.deployment_limiter.check(&deployment_id, &limits, now) {
+                    DeploymentCheck::ConcurrencyLimited => {
+                        trace!(%deployment_id, "Deployment concurrency limit reached");
+                        self.deployment_limiter
+                            .add_to_waiters(vqueue, deployment_id);
+                        return AcquireOutcome::BlockedOn(ResourceKind::DeploymentConcurrency {
+                            deployment_id,
+                        });
+                    }
+                    DeploymentCheck::RateLimited { retry_at } => {
+                        trace!(%deployment_id, %retry_at, "Deployment rate limit reached");
+                        self.deployment_limiter.add_to_rate_waiters(
+                            vqueue,
+                            deployment_id,
+                            &limits,
+                            now,
+                        );
+                        return AcquireOutcome::BlockedOn(ResourceKind::DeploymentRate {
+                            deployment_id,
+                            estimated_retry_at: retry_at,
+                        });
+                    }
+                    DeploymentCheck::Available { rate_limited } => {
+                        provisional
+                            .add_permit(UserPermitKind::DeploymentConcurrency(deployment_id));
+                        if rate_limited {
+                            provisional.add_permit(UserPermitKind::DeploymentRate(deployment_id));
+                        }
+                    }
+                }
             }
 
             // All user requirements are satisfied.
@@ -304,6 +435,14 @@ impl ResourceManager {
                                     self.user_limiter.release_concurrency(&scope, &limit_key);
                                 eligible.wake_up_queues(woken);
                             }
+                            UserPermitKind::DeploymentConcurrency(deployment_id) => {
+                                let woken =
+                                    self.deployment_limiter.release_concurrency(&deployment_id);
+                                eligible.wake_up_queues(woken);
+                            }
+                            // Rate tokens are not returned on release
+                            UserPermitKind::LimitKeyRate(..)
+                            | UserPermitKind::DeploymentRate(..) => {}
                         }
                     }
                 }
@@ -314,6 +453,28 @@ impl ResourceManager {
             }
         }
 
+        let woken = self
+            .user_limiter
+            .poll_rate_timers(cx, SchedulerClock.now_millis());
+        if !woken.is_empty() {
+            trace!(
+                "waking up {} vqueues because user rate limit tokens became available",
+                woken.len()
+            );
+            eligible.wake_up_queues(woken);
+        }
+
+        let woken = self
+            .deployment_limiter
+            .poll_rate_timers(cx, SchedulerClock.now_millis());
+        if !woken.is_empty() {
+            trace!(
+                "waking up {} vqueues because deployment rate limit tokens became available",
+                woken.len()
+            );
+            eligible.wake_up_queues(woken);
+        }
+
         while let Poll::Ready(Some(queue)) = self.invoker_concurrency.poll_head(cx) {
             // wake up this vqueue and shift all other waiters to need poll so
             // they can get a chance to be added to the ready ring if they are eligible and
@@ -332,6 +493,32 @@ impl ResourceManager {
         }
     }
 
+    /// Resolves the deployment the next attempt of the invocation is expected to run on: the
+    /// deployment it's pinned to, or the one a new invocation of the service is routed to.
+    fn resolve_deployment(
+        &mut self,
+        partition_key: PartitionKey,
+        meta: &VQueueMeta,
+        key: &EntryKey,
+        metadata: &EntryMetadata,
+    ) -> Option<DeploymentId> {
+        if let Some(pinned) = metadata.deployment.as_deref() {
+            return pinned.parse().ok();
+        }
+        let invocation_id = key.entry_id().to_invocation_id(partition_key)?;
+        self.schema
+            .live_load()
+            .route_new_invocation(meta.service_name()?, &invocation_id)
+    }
+
+    /// The current limits of the deployment. A removed deployment has no limits.
+    pub(super) fn deployment_limits(&mut self, deployment_id: &DeploymentId) -> DeploymentLimits {
+        self.schema
+            .live_load()
+            .deployment_limits(deployment_id)
+            .unwrap_or_default()
+    }
+
     /// Snapshot of every user-limit counter currently tracked by this partition's
     /// `UserLimiter`. The rows are stamped with the owning partition's key so that
     /// DataFusion can route them into the right scan.
@@ -339,7 +526,8 @@ impl ResourceManager {
         &self,
         partition_key: PartitionKey,
     ) -> Vec<UserLimitCounterEntry> {
-        self.user_limiter.scan_counters(partition_key)
+        self.user_limiter
+            .scan_counters(partition_key, SchedulerClock.now_millis())
     }
 
     /// Resolve a user-limit rule handle into its pattern string, or `None` if
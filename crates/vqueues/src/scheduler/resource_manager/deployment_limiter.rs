@@ -0,0 +1,435 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::task::{Context, Poll};
+
+use hashbrown::HashMap;
+use tokio_util::time::{DelayQueue, delay_queue};
+
+use restate_types::identifiers::DeploymentId;
+use restate_types::schema::deployment::DeploymentLimits;
+use restate_types::time::MillisSinceEpoch;
+
+use super::Waiters;
+use super::user_limiter::{Bucket, RateState, TokenRate, arm_rate_timer};
+use crate::scheduler::VQueueHandle;
+
+/// Outcome of [`DeploymentLimiter::check`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum DeploymentCheck {
+    /// The invocation can start. If `rate_limited` is set, it needs to take a token.
+    Available { rate_limited: bool },
+    /// The deployment reached its concurrency limit.
+    ConcurrencyLimited,
+    /// The deployment reached its rate limit.
+    RateLimited {
+        /// Best-effort estimate for when the next token becomes available.
+        retry_at: MillisSinceEpoch,
+    },
+}
+
+/// Enforces the [`DeploymentLimits`] of the deployments invocations run against.
+///
+/// Invocations count against the concurrency of their deployment until their permit is
+/// released, vqueues blocked on the concurrency limit are woken up one by one as invocations
+/// end. Rate limits use the same lazily refilled token buckets as the user limits.
+pub struct DeploymentLimiter {
+    deployments: HashMap<DeploymentId, DeploymentState>,
+    /// Wakes up rate waiters and refills partially drained token buckets.
+    rate_timers: DelayQueue<DeploymentId>,
+}
+
+#[derive(Debug, Default)]
+struct DeploymentState {
+    /// Invocations currently running against the deployment.
+    running: u32,
+    /// Vqueues waiting for a running invocation to end, in arrival order.
+    waiters: Waiters,
+    /// The rate the token bucket was last used with.
+    token_rate: Option<TokenRate>,
+    rate: RateState,
+}
+
+impl DeploymentState {
+    fn is_idle(&self) -> bool {
+        self.running == 0 && self.waiters.is_empty() && self.rate.is_idle()
+    }
+}
+
+impl DeploymentLimiter {
+    pub fn create() -> Self {
+        Self {
+            deployments: HashMap::new(),
+            rate_timers: DelayQueue::new(),
+        }
+    }
+
+    /// Checks whether an invocation can start against the deployment under the given limits.
+    ///
+    /// Like the other checks of the resource manager, this is side-effect free. The capacity is
+    /// taken in [`Self::increment`] and [`Self::consume_rate_token`].
+    pub(super) fn check(
+        &self,
+        deployment_id: &DeploymentId,
+        limits: &DeploymentLimits,
+        now: MillisSinceEpoch,
+    ) -> DeploymentCheck {
+        let state = self.deployments.get(deployment_id);
+
+        if limits
+            .concurrency
+            .is_some_and(|concurrency| state.map_or(0, |state| state.running) >= concurrency.get())
+        {
+            return DeploymentCheck::ConcurrencyLimited;
+        }
+
+        let Some(rate) = token_rate(limits) else {
+            return DeploymentCheck::Available {
+                rate_limited: false,
+            };
+        };
+        let (tokens, queued) = state.map_or((rate.burst, 0), |state| {
+            (state.rate.tokens(now, &rate), state.rate.waiters.len())
+        });
+        // Vqueues already waiting for a token are served first.
+        let needed = queued as f64 + 1.0;
+        if tokens >= needed {
+            DeploymentCheck::Available { rate_limited: true }
+        } else {
+            DeploymentCheck::RateLimited {
+                retry_at: rate.deadline(now, tokens, needed),
+            }
+        }
+    }
+
+    /// Counts an invocation starting against the deployment.
+    pub(super) fn increment(&mut self, deployment_id: DeploymentId) {
+        self.deployments.entry(deployment_id).or_default().running += 1;
+    }
+
+    /// Accounts for an invocation that stopped running against the deployment, and returns
+    /// the vqueue to wake up because of it, if any.
+    pub(super) fn release_concurrency(
+        &mut self,
+        deployment_id: &DeploymentId,
+    ) -> Option<VQueueHandle> {
+        let state = self.deployments.get_mut(deployment_id)?;
+        state.running = state.running.saturating_sub(1);
+        let woken = state.waiters.pop_front();
+        self.prune(deployment_id);
+        woken
+    }
+
+    /// Adds a vqueue to the concurrency waiters of the deployment.
+    pub(super) fn add_to_waiters(&mut self, handle: VQueueHandle, deployment_id: DeploymentId) {
+        self.deployments
+            .entry(deployment_id)
+            .or_default()
+            .waiters
+            .push_back(handle);
+    }
+
+    /// Removes a vqueue from the concurrency waiters of the deployment.
+    pub(super) fn remove_from_waiters(
+        &mut self,
+        handle: VQueueHandle,
+        deployment_id: &DeploymentId,
+    ) {
+        if let Some(state) = self.deployments.get_mut(deployment_id) {
+            state.waiters.retain(|h| *h != handle);
+            self.prune(deployment_id);
+        }
+    }
+
+    /// Takes a token from the token bucket of the deployment.
+    pub(super) fn consume_rate_token(
+        &mut self,
+        deployment_id: DeploymentId,
+        limits: &DeploymentLimits,
+        now: MillisSinceEpoch,
+    ) {
+        let Some(rate) = token_rate(limits) else {
+            return;
+        };
+        let state = self.deployments.entry(deployment_id).or_default();
+        state.token_rate = Some(rate);
+
+        let tokens = state.rate.tokens(now, &rate) - 1.0;
+        state.rate.bucket = Some(Bucket {
+            tokens,
+            updated_at: now,
+        });
+        // Keep the state around until its bucket is full again
+        let full_at = rate.deadline(now, tokens, rate.burst);
+        arm_rate_timer(&mut self.rate_timers, &mut state.rate, now, full_at, || {
+            deployment_id
+        });
+    }
+
+    /// Gives back the token taken by [`Self::consume_rate_token`] for a permit that was never
+    /// used.
+    pub(super) fn refund_rate_token(
+        &mut self,
+        deployment_id: &DeploymentId,
+        limits: &DeploymentLimits,
+        now: MillisSinceEpoch,
+    ) {
+        let Some(rate) = token_rate(limits) else {
+            return;
+        };
+        if let Some(state) = self.deployments.get_mut(deployment_id)
+            && state.rate.bucket.is_some()
+        {
+            let tokens = state.rate.tokens(now, &rate) + 1.0;
+            state.rate.bucket = (tokens < rate.burst).then_some(Bucket {
+                tokens,
+                updated_at: now,
+            });
+            self.prune(deployment_id);
+        }
+    }
+
+    /// Adds a vqueue to the rate waiters of the deployment, and makes sure a timer is armed to
+    /// wake it up once a token is available.
+    pub(super) fn add_to_rate_waiters(
+        &mut self,
+        handle: VQueueHandle,
+        deployment_id: DeploymentId,
+        limits: &DeploymentLimits,
+        now: MillisSinceEpoch,
+    ) {
+        let rate = token_rate(limits);
+        let state = self.deployments.entry(deployment_id).or_default();
+        state.token_rate = rate;
+        state.rate.waiters.push_back(handle);
+
+        let wake_at = match rate {
+            Some(rate) => rate.deadline(now, state.rate.tokens(now, &rate), 1.0),
+            None => now,
+        };
+        arm_rate_timer(&mut self.rate_timers, &mut state.rate, now, wake_at, || {
+            deployment_id
+        });
+    }
+
+    /// Removes a vqueue from the rate waiters of the deployment.
+    pub(super) fn remove_from_rate_waiters(
+        &mut self,
+        handle: VQueueHandle,
+        deployment_id: &DeploymentId,
+    ) {
+        if let Some(state) = self.deployments.get_mut(deployment_id) {
+            state.rate.waiters.retain(|h| *h != handle);
+            self.prune(deployment_id);
+        }
+    }
+
+    /// Polls the rate timers and returns the vqueues to wake up because tokens became
+    /// available.
+    pub(super) fn poll_rate_timers(
+        &mut self,
+        cx: &mut Context<'_>,
+        now: MillisSinceEpoch,
+    ) -> Vec<VQueueHandle> {
+        let mut woken = Vec::new();
+        while let Poll::Ready(Some(expired)) = self.rate_timers.poll_expired(cx) {
+            let timer_key = expired.key();
+            let deployment_id = expired.into_inner();
+            self.on_rate_timer(deployment_id, timer_key, now, &mut woken);
+        }
+        woken
+    }
+
+    /// Wakes up to one rate waiter per available token, oldest first, re-arms the timer if
+    /// needed, and drops the state of the deployment once it's not needed anymore.
+    fn on_rate_timer(
+        &mut self,
+        deployment_id: DeploymentId,
+        timer_key: delay_queue::Key,
+        now: MillisSinceEpoch,
+        woken: &mut Vec<VQueueHandle>,
+    ) {
+        let Some(state) = self.deployments.get_mut(&deployment_id) else {
+            return;
+        };
+        if state.rate.timer.is_some_and(|(key, _)| key == timer_key) {
+            state.rate.timer = None;
+        }
+
+        let next_wake_up = match state.token_rate {
+            None => {
+                // The deployment doesn't limit the rate (anymore)
+                state.rate.bucket = None;
+                woken.extend(state.rate.waiters.drain(..));
+                None
+            }
+            Some(rate) => {
+                let tokens = state.rate.tokens(now, &rate);
+                let to_wake = (tokens.max(0.0) as usize).min(state.rate.waiters.len());
+                woken.extend(state.rate.waiters.drain(..to_wake));
+
+                if !state.rate.waiters.is_empty() {
+                    // The woken vqueues will take the available tokens
+                    Some(rate.deadline(now, tokens, to_wake as f64 + 1.0))
+                } else if tokens >= rate.burst {
+                    state.rate.bucket = None;
+                    None
+                } else {
+                    Some(rate.deadline(now, tokens, rate.burst))
+                }
+            }
+        };
+
+        match next_wake_up {
+            Some(wake_at) => {
+                arm_rate_timer(&mut self.rate_timers, &mut state.rate, now, wake_at, || {
+                    deployment_id
+                })
+            }
+            None => self.prune(&deployment_id),
+        }
+    }
+
+    /// Drops the state of the deployment if nothing runs against it or waits for it, and its
+    /// token bucket is full.
+    fn prune(&mut self, deployment_id: &DeploymentId) {
+        if self
+            .deployments
+            .get(deployment_id)
+            .is_some_and(DeploymentState::is_idle)
+            && let Some(state) = self.deployments.remove(deployment_id)
+            && let Some((timer_key, _)) = state.rate.timer
+        {
+            self.rate_timers.remove(&timer_key);
+        }
+    }
+}
+
+fn token_rate(limits: &DeploymentLimits) -> Option<TokenRate> {
+    Some(TokenRate::new(limits.rate?, limits.effective_burst()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+    use std::time::Duration;
+
+    use slotmap::SlotMap;
+
+    use super::*;
+
+    fn limits(
+        concurrency: Option<u32>,
+        rate: Option<&str>,
+        burst: Option<u32>,
+    ) -> DeploymentLimits {
+        DeploymentLimits {
+            concurrency: concurrency.and_then(NonZeroU32::new),
+            rate: rate.map(|rate| rate.parse().unwrap()),
+            burst: burst.and_then(NonZeroU32::new),
+        }
+    }
+
+    #[test]
+    fn concurrency_waiters_are_woken_up_one_by_one_on_release() {
+        let mut handles = SlotMap::<VQueueHandle, ()>::with_key();
+        let vq1 = handles.insert(());
+        let vq2 = handles.insert(());
+
+        let deployment_id = DeploymentId::new();
+        let limits = limits(Some(1), None, None);
+        let now = MillisSinceEpoch::new(1_000);
+        let mut limiter = DeploymentLimiter::create();
+
+        assert_eq!(
+            limiter.check(&deployment_id, &limits, now),
+            DeploymentCheck::Available {
+                rate_limited: false
+            }
+        );
+        limiter.increment(deployment_id);
+        assert_eq!(
+            limiter.check(&deployment_id, &limits, now),
+            DeploymentCheck::ConcurrencyLimited
+        );
+        limiter.add_to_waiters(vq1, deployment_id);
+        limiter.add_to_waiters(vq2, deployment_id);
+
+        assert_eq!(limiter.release_concurrency(&deployment_id), Some(vq1));
+        assert_eq!(
+            limiter.check(&deployment_id, &limits, now),
+            DeploymentCheck::Available {
+                rate_limited: false
+            }
+        );
+
+        limiter.remove_from_waiters(vq2, &deployment_id);
+        assert!(limiter.deployments.is_empty());
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn rate_waiters_are_woken_up_when_tokens_are_available() {
+        let mut handles = SlotMap::<VQueueHandle, ()>::with_key();
+        let vq1 = handles.insert(());
+        let vq2 = handles.insert(());
+
+        let deployment_id = DeploymentId::new();
+        let limits = limits(None, Some("1/s"), None);
+        let now = MillisSinceEpoch::new(1_000);
+        let mut limiter = DeploymentLimiter::create();
+        let mut cx = Context::from_waker(std::task::Waker::noop());
+
+        assert_eq!(
+            limiter.check(&deployment_id, &limits, now),
+            DeploymentCheck::Available { rate_limited: true }
+        );
+        limiter.consume_rate_token(deployment_id, &limits, now);
+        assert_eq!(
+            limiter.check(&deployment_id, &limits, now),
+            DeploymentCheck::RateLimited {
+                retry_at: MillisSinceEpoch::new(2_000)
+            }
+        );
+        limiter.add_to_rate_waiters(vq1, deployment_id, &limits, now);
+        // Queued vqueues are served first
+        assert_eq!(
+            limiter.check(&deployment_id, &limits, now),
+            DeploymentCheck::RateLimited {
+                retry_at: MillisSinceEpoch::new(3_000)
+            }
+        );
+        limiter.add_to_rate_waiters(vq2, deployment_id, &limits, now);
+        assert!(limiter.poll_rate_timers(&mut cx, now).is_empty());
+
+        tokio::time::advance(Duration::from_secs(1)).await;
+        let now = MillisSinceEpoch::new(2_000);
+        assert_eq!(limiter.poll_rate_timers(&mut cx, now), vec![vq1]);
+
+        // A token taken by a permit that was never used goes back to the bucket
+        limiter.consume_rate_token(deployment_id, &limits, now);
+        limiter.refund_rate_token(&deployment_id, &limits, now);
+
+        limiter.remove_from_rate_waiters(vq2, &deployment_id);
+        assert_eq!(
+            limiter.check(&deployment_id, &limits, now),
+            DeploymentCheck::Available { rate_limited: true }
+        );
+        limiter.consume_rate_token(deployment_id, &limits, now);
+
+        // The state is dropped once the bucket is full again
+        tokio::time::advance(Duration::from_secs(1)).await;
+        assert!(
+            limiter
+                .poll_rate_timers(&mut cx, MillisSinceEpoch::new(3_000))
+                .is_empty()
+        );
+        assert!(limiter.deployments.is_empty());
+    }
+}
@@ -18,6 +18,7 @@ use restate_worker_api::resources::{
 };
 
 use super::ResourceManager;
+use crate::scheduler::clock::SchedulerClock;
 
 // Holds incrementally secured resources
 #[derive(Default)]
@@ -123,6 +124,26 @@ impl ProvisionalPermit {
                         super::user_limiter::LimitKind::Concurrency,
                     );
                 }
+                UserPermitKind::LimitKeyRate(scope, limit_key) => {
+                    resource_manager.user_limiter.consume_rate_tokens(
+                        scope,
+                        limit_key,
+                        SchedulerClock.now_millis(),
+                    );
+                }
+                UserPermitKind::DeploymentConcurrency(deployment_id) => {
+                    resource_manager
+                        .deployment_limiter
+                        .increment(*deployment_id);
+                }
+                UserPermitKind::DeploymentRate(deployment_id) => {
+                    let limits = resource_manager.deployment_limits(deployment_id);
+                    resource_manager.deployment_limiter.consume_rate_token(
+                        *deployment_id,
+                        &limits,
+                        SchedulerClock.now_millis(),
+                    );
+                }
             }
         }
 
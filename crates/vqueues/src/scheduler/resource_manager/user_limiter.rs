@@ -151,25 +151,48 @@
 //! ### Trie pruning
 //!
 //! After each release, empty trie nodes are pruned bottom-up: L2 leaves, then L1 nodes, then
-//! scope nodes are removed when they have zero usage, no waiters, and no children. This prevents
-//! unbounded trie growth from transient keys.
+//! scope nodes are removed when they have zero usage, no waiters, a full token bucket, and no
+//! children. This prevents unbounded trie growth from transient keys.
 //!
 //! Note: usage and waiter counts are related but not strictly coupled. For example, usage can
 //! transiently be zero while waiters still exist if many releases happen before woken vqueues
 //! are re-evaluated.
+//!
+//! ### Rate limits
+//!
+//! Rules can additionally carry a rate (e.g. `100/s`) and a burst. Each trie node governed by
+//! such a rule owns a token bucket ([`RateState`]) holding up to `burst` tokens and refilling at
+//! the configured rate. Acquiring a permit takes one token from every rate-limited level on the
+//! path; tokens are not returned when the permit is released. Buckets are refilled lazily from the
+//! time elapsed since they were last touched, so they don't need any background work.
+//!
+//! Vqueues blocked on a rate limit wait in a separate FIFO list on the narrowest blocked node, and
+//! a timer is armed for when the next token becomes available at that node. When the timer fires,
+//! we wake as many waiters as there are whole tokens, oldest first. A vqueue that isn't queued
+//! yet only gets a token if there are more tokens than queued waiters, so newcomers cannot
+//! overtake vqueues which have been waiting already.
+//!
+//! A node with a partially drained bucket is kept in the trie, and has a timer armed for when
+//! the bucket is full again. Dropping it earlier would reset its bucket and allow exceeding the
+//! rate.
 
 use std::collections::VecDeque;
 use std::fmt;
 use std::num::NonZeroU32;
+use std::task::{Context, Poll};
+use std::time::Duration;
 
 use arrayvec::ArrayVec;
 use hashbrown::HashMap;
+use tokio_util::time::{DelayQueue, delay_queue};
 
 use restate_limiter::{
     Level, Limit, LimitKey, Pattern, RuleHandle, RulePattern, Rules, StructuredLimits,
 };
 use restate_types::Scope;
 use restate_types::identifiers::PartitionKey;
+use restate_types::rate::Rate;
+use restate_types::time::MillisSinceEpoch;
 use restate_util_string::{ReString, RestrictedValue};
 use restate_worker_api::UserLimitCounterEntry;
 use restate_worker_api::resources::{RuleUpdate, UserLimits};
@@ -208,6 +231,8 @@ impl Usage {
 pub struct UserLimiter {
     state: State,
     rules: Rules<ReString, UserLimits>,
+    /// Wakes up rate waiters and refills partially drained token buckets.
+    rate_timers: DelayQueue<RateTimer>,
 }
 
 impl UserLimiter {
@@ -215,6 +240,7 @@ impl UserLimiter {
         Self {
             rules: Rules::default(),
             state: Default::default(),
+            rate_timers: DelayQueue::new(),
         }
     }
 
@@ -311,6 +337,238 @@ impl UserLimiter {
         woken
     }
 
+    /// Checks whether a rate token is available at all rate-limited levels of the given
+    /// scope + limit key.
+    ///
+    /// Like [`Self::check_concurrency_capacity`], this is side-effect free. Tokens are taken
+    /// in [`Self::consume_rate_tokens`].
+    pub(super) fn check_rate_capacity(
+        &self,
+        scope: &Scope,
+        limit_key: &LimitKey<ReString>,
+        now: MillisSinceEpoch,
+    ) -> RateCheck {
+        let limits = self.rules.lookup(scope.as_str(), limit_key);
+        let mut result = RateCheck::Unlimited;
+
+        // Levels are visited top-down, so the last blocked level is the narrowest one
+        for level in levels_of(limit_key) {
+            let Limit::Defined(handle, user_limits) = limits.limit_at(level) else {
+                continue;
+            };
+            let Some(rate) = TokenRate::from_limits(user_limits) else {
+                continue;
+            };
+
+            let (tokens, queued) = self
+                .state
+                .rate_state(scope, limit_key, level)
+                .map_or((rate.burst, 0), |state| {
+                    (state.tokens(now, &rate), state.waiters.len())
+                });
+            // Vqueues already waiting at this level are served first.
+            let needed = queued as f64 + 1.0;
+            if tokens >= needed {
+                if matches!(result, RateCheck::Unlimited) {
+                    result = RateCheck::Available;
+                }
+                continue;
+            }
+
+            let retry_at = rate.deadline(now, tokens, needed);
+            result = RateCheck::Blocked {
+                level,
+                rule_handle: *handle,
+                retry_at: match result {
+                    RateCheck::Blocked {
+                        retry_at: previous, ..
+                    } => previous.max(retry_at),
+                    _ => retry_at,
+                },
+            };
+        }
+        result
+    }
+
+    /// Takes one token from the token bucket of every rate-limited level along the path.
+    pub(super) fn consume_rate_tokens(
+        &mut self,
+        scope: &Scope,
+        limit_key: &LimitKey<ReString>,
+        now: MillisSinceEpoch,
+    ) {
+        let limits = self.rules.lookup(scope.as_str(), limit_key);
+        for level in levels_of(limit_key) {
+            let Limit::Defined(_, user_limits) = limits.limit_at(level) else {
+                continue;
+            };
+            let Some(rate) = TokenRate::from_limits(user_limits) else {
+                continue;
+            };
+
+            let state = self.state.rate_state_entry(scope, limit_key, level);
+            let tokens = state.tokens(now, &rate) - 1.0;
+            state.bucket = Some(Bucket {
+                tokens,
+                updated_at: now,
+            });
+            // Keep the node around until its bucket is full again
+            let full_at = rate.deadline(now, tokens, rate.burst);
+            arm_rate_timer(&mut self.rate_timers, state, now, full_at, || RateTimer {
+                scope: scope.clone(),
+                node_key: node_key(limit_key, level),
+            });
+        }
+    }
+
+    /// Gives back the tokens taken by [`Self::consume_rate_tokens`] for a permit that
+    /// was never used.
+    pub(super) fn refund_rate_tokens(
+        &mut self,
+        scope: &Scope,
+        limit_key: &LimitKey<ReString>,
+        now: MillisSinceEpoch,
+    ) {
+        let limits = self.rules.lookup(scope.as_str(), limit_key);
+        for level in levels_of(limit_key) {
+            let Limit::Defined(_, user_limits) = limits.limit_at(level) else {
+                continue;
+            };
+            let Some(rate) = TokenRate::from_limits(user_limits) else {
+                continue;
+            };
+
+            if let Some(state) = self.state.rate_state_mut(scope, limit_key, level)
+                && state.bucket.is_some()
+            {
+                let tokens = state.tokens(now, &rate) + 1.0;
+                state.bucket = (tokens < rate.burst).then_some(Bucket {
+                    tokens,
+                    updated_at: now,
+                });
+            }
+        }
+    }
+
+    /// Adds a vqueue to the rate waiter list at the specified trie node, and makes sure a
+    /// timer is armed to wake it up once a token is available.
+    pub(super) fn add_to_rate_waiters(
+        &mut self,
+        handle: VQueueHandle,
+        scope: &Scope,
+        limit_key: &LimitKey<ReString>,
+        blocked_level: Level,
+        now: MillisSinceEpoch,
+    ) {
+        let rate = self.token_rate_at(scope, limit_key, blocked_level);
+        let state = self.state.rate_state_entry(scope, limit_key, blocked_level);
+        state.waiters.push_back(handle);
+
+        let wake_at = match rate {
+            Some(rate) => rate.deadline(now, state.tokens(now, &rate), 1.0),
+            None => now,
+        };
+        arm_rate_timer(&mut self.rate_timers, state, now, wake_at, || RateTimer {
+            scope: scope.clone(),
+            node_key: node_key(limit_key, blocked_level),
+        });
+    }
+
+    /// Removes a vqueue from the rate waiter list using caller-provided routing info.
+    pub(super) fn remove_from_rate_waiters(
+        &mut self,
+        handle: VQueueHandle,
+        scope: &Scope,
+        limit_key: &LimitKey<ReString>,
+        blocked_level: Level,
+    ) {
+        if let Some(state) = self.state.rate_state_mut(scope, limit_key, blocked_level) {
+            state.waiters.retain(|h| *h != handle);
+        }
+    }
+
+    /// Polls the rate timers and returns the vqueues to wake up because tokens became
+    /// available.
+    pub(super) fn poll_rate_timers(
+        &mut self,
+        cx: &mut Context<'_>,
+        now: MillisSinceEpoch,
+    ) -> Vec<VQueueHandle> {
+        let mut woken = Vec::new();
+        while let Poll::Ready(Some(expired)) = self.rate_timers.poll_expired(cx) {
+            let timer_key = expired.key();
+            let RateTimer { scope, node_key } = expired.into_inner();
+            self.on_rate_timer(&scope, &node_key, timer_key, now, &mut woken);
+        }
+        woken
+    }
+
+    /// Wakes up to one waiter per available token at the node, oldest first, re-arms the
+    /// timer if needed, and prunes the node once it's not needed anymore.
+    fn on_rate_timer(
+        &mut self,
+        scope: &Scope,
+        node_key: &LimitKey<ReString>,
+        timer_key: delay_queue::Key,
+        now: MillisSinceEpoch,
+        woken: &mut Vec<VQueueHandle>,
+    ) {
+        let level = node_level(node_key);
+        let rate = self.token_rate_at(scope, node_key, level);
+        let Some(state) = self.state.rate_state_mut(scope, node_key, level) else {
+            return;
+        };
+        if state.timer.is_some_and(|(key, _)| key == timer_key) {
+            state.timer = None;
+        }
+
+        let next_wake_up = match rate {
+            None => {
+                // The rule doesn't limit the rate (anymore)
+                state.bucket = None;
+                woken.extend(state.waiters.drain(..));
+                None
+            }
+            Some(rate) => {
+                let tokens = state.tokens(now, &rate);
+                let to_wake = (tokens.max(0.0) as usize).min(state.waiters.len());
+                woken.extend(state.waiters.drain(..to_wake));
+
+                if !state.waiters.is_empty() {
+                    // The woken vqueues will take the available tokens
+                    Some(rate.deadline(now, tokens, to_wake as f64 + 1.0))
+                } else if tokens >= rate.burst {
+                    state.bucket = None;
+                    None
+                } else {
+                    Some(rate.deadline(now, tokens, rate.burst))
+                }
+            }
+        };
+
+        match next_wake_up {
+            Some(wake_at) => {
+                arm_rate_timer(&mut self.rate_timers, state, now, wake_at, || RateTimer {
+                    scope: scope.clone(),
+                    node_key: node_key.clone(),
+                })
+            }
+            None => self.state.prune(scope, node_key),
+        }
+    }
+
+    fn token_rate_at(
+        &self,
+        scope: &Scope,
+        limit_key: &LimitKey<ReString>,
+        level: Level,
+    ) -> Option<TokenRate> {
+        match self.rules.lookup(scope.as_str(), limit_key).limit_at(level) {
+            Limit::Undefined => None,
+            Limit::Defined(_, user_limits) => TokenRate::from_limits(user_limits),
+        }
+    }
+
     /// Resolves a rule handle to its pattern. Returns `None` if the handle is stale
     /// (rule was removed since the handle was captured).
     #[allow(dead_code)]
@@ -325,78 +583,107 @@ impl UserLimiter {
     /// The emitted rows are what the `sys_user_limits` DataFusion table
     /// surfaces; callers stamp the `partition_key` from the owning partition
     /// before handing the rows back.
-    pub fn scan_counters(&self, partition_key: PartitionKey) -> Vec<UserLimitCounterEntry> {
+    pub fn scan_counters(
+        &self,
+        partition_key: PartitionKey,
+        now: MillisSinceEpoch,
+    ) -> Vec<UserLimitCounterEntry> {
         let mut out = Vec::new();
         for (scope, scope_node) in &self.state.scopes {
             let scope_name = scope.as_str().to_owned();
 
             // Scope-level row
             let scope_limits = self.rules.lookup(scope.as_str(), &LimitKey::None);
-            let (scope_limit, scope_rule) =
-                limit_and_pattern(scope_limits.limit_at(Level::Scope), &self.rules);
-            out.push(UserLimitCounterEntry {
+            out.push(self.counter_entry(
                 partition_key,
-                scope: scope_name.clone(),
-                l1: None,
-                l2: None,
-                level: Level::Scope,
-                usage: scope_node.value.concurrency,
-                concurrency_limit: scope_limit,
-                rule_pattern: scope_rule,
-                num_waiters: scope_node.waiters.len() as u64,
-            });
+                now,
+                scope_name.clone(),
+                (None, None),
+                Level::Scope,
+                scope_limits.limit_at(Level::Scope),
+                &scope_node.value,
+                &scope_node.waiters,
+                &scope_node.rate,
+            ));
 
             for (l1_key, l1_node) in &scope_node.l1 {
                 let l1_limit_key = LimitKey::L1(l1_key.clone());
                 let l1_limits = self.rules.lookup(scope.as_str(), &l1_limit_key);
-                let (l1_limit, l1_rule) =
-                    limit_and_pattern(l1_limits.limit_at(Level::Level1), &self.rules);
-                out.push(UserLimitCounterEntry {
+                out.push(self.counter_entry(
                     partition_key,
-                    scope: scope_name.clone(),
-                    l1: Some(l1_key.as_str().to_owned()),
-                    l2: None,
-                    level: Level::Level1,
-                    usage: l1_node.value.concurrency,
-                    concurrency_limit: l1_limit,
-                    rule_pattern: l1_rule,
-                    num_waiters: l1_node.waiters.len() as u64,
-                });
+                    now,
+                    scope_name.clone(),
+                    (Some(l1_key.as_str().to_owned()), None),
+                    Level::Level1,
+                    l1_limits.limit_at(Level::Level1),
+                    &l1_node.value,
+                    &l1_node.waiters,
+                    &l1_node.rate,
+                ));
 
                 for (l2_key, l2_leaf) in &l1_node.l2 {
                     let l2_limit_key = LimitKey::L2(l1_key.clone(), l2_key.clone());
                     let l2_limits = self.rules.lookup(scope.as_str(), &l2_limit_key);
-                    let (l2_limit, l2_rule) =
-                        limit_and_pattern(l2_limits.limit_at(Level::Level2), &self.rules);
-                    out.push(UserLimitCounterEntry {
+                    out.push(self.counter_entry(
                         partition_key,
-                        scope: scope_name.clone(),
-                        l1: Some(l1_key.as_str().to_owned()),
-                        l2: Some(l2_key.as_str().to_owned()),
-                        level: Level::Level2,
-                        usage: l2_leaf.value.concurrency,
-                        concurrency_limit: l2_limit,
-                        rule_pattern: l2_rule,
-                        num_waiters: l2_leaf.waiters.len() as u64,
-                    });
+                        now,
+                        scope_name.clone(),
+                        (
+                            Some(l1_key.as_str().to_owned()),
+                            Some(l2_key.as_str().to_owned()),
+                        ),
+                        Level::Level2,
+                        l2_limits.limit_at(Level::Level2),
+                        &l2_leaf.value,
+                        &l2_leaf.waiters,
+                        &l2_leaf.rate,
+                    ));
                 }
             }
         }
         out
     }
-}
 
-/// Resolve a `Limit<&UserLimits>` into its concrete (limit_value, pattern_display) pair.
-fn limit_and_pattern(
-    limit: &Limit<&UserLimits>,
-    rules: &Rules<ReString, UserLimits>,
-) -> (Option<u32>, Option<String>) {
-    match limit {
-        Limit::Undefined => (None, None),
-        Limit::Defined(handle, user_limits) => {
-            let value = user_limits.concurrency.map(NonZeroU32::get);
-            let pattern = rules.get_pattern(*handle).map(ToString::to_string);
-            (value, pattern)
+    #[allow(clippy::too_many_arguments)]
+    fn counter_entry(
+        &self,
+        partition_key: PartitionKey,
+        now: MillisSinceEpoch,
+        scope: String,
+        (l1, l2): (Option<String>, Option<String>),
+        level: Level,
+        limit: &Limit<&UserLimits>,
+        usage: &Usage,
+        waiters: &VecDeque<VQueueHandle>,
+        rate_state: &RateState,
+    ) -> UserLimitCounterEntry {
+        let (user_limits, rule_pattern) = match limit {
+            Limit::Undefined => (None, None),
+            Limit::Defined(handle, user_limits) => (
+                Some(*user_limits),
+                self.rules.get_pattern(*handle).map(ToString::to_string),
+            ),
+        };
+        let rate = user_limits.and_then(TokenRate::from_limits);
+
+        UserLimitCounterEntry {
+            partition_key,
+            scope,
+            l1,
+            l2,
+            level,
+            usage: usage.concurrency,
+            concurrency_limit: user_limits
+                .and_then(|limits| limits.concurrency)
+                .map(NonZeroU32::get),
+            rate_limit: user_limits.and_then(|limits| limits.rate),
+            rate_burst: user_limits
+                .and_then(UserLimits::effective_burst)
+                .map(NonZeroU32::get),
+            available_tokens: rate.map(|rate| rate_state.tokens(now, &rate).max(0.0) as u32),
+            rule_pattern,
+            num_waiters: waiters.len() as u64,
+            num_rate_waiters: rate_state.waiters.len() as u64,
         }
     }
 }
@@ -406,46 +693,181 @@ struct State {
     scopes: HashMap<Scope, ScopeNode>,
 }
 
-/// Scope-level trie node with usage counter, waiter list, and L1 children.
+/// Scope-level trie node with usage counter, waiter list, rate state, and L1 children.
 #[derive(Debug, Default)]
 struct ScopeNode {
     value: Usage,
     waiters: VecDeque<VQueueHandle>,
+    rate: RateState,
     l1: HashMap<RestrictedValue<ReString>, L1Node>,
 }
 
-/// L1-level trie node with usage counter, waiter list, and L2 children.
+/// L1-level trie node with usage counter, waiter list, rate state, and L2 children.
 #[derive(Debug, Default)]
 struct L1Node {
     value: Usage,
     waiters: VecDeque<VQueueHandle>,
+    rate: RateState,
     l2: HashMap<RestrictedValue<ReString>, L2Leaf>,
 }
 
 impl ScopeNode {
-    /// Returns true if this node has no usage, no waiters, and no children.
+    /// Returns true if this node has no usage, no waiters, a full bucket, and no children.
     fn is_unused(&self) -> bool {
-        self.value.is_zero() && self.waiters.is_empty() && self.l1.is_empty()
+        self.value.is_zero() && self.waiters.is_empty() && self.rate.is_idle() && self.l1.is_empty()
     }
 }
 
 impl L1Node {
-    /// Returns true if this node has no usage, no waiters, and no children.
+    /// Returns true if this node has no usage, no waiters, a full bucket, and no children.
     fn is_unused(&self) -> bool {
-        self.value.is_zero() && self.waiters.is_empty() && self.l2.is_empty()
+        self.value.is_zero() && self.waiters.is_empty() && self.rate.is_idle() && self.l2.is_empty()
     }
 }
 
-/// L2-level trie leaf with usage counter and waiter list.
+/// L2-level trie leaf with usage counter, waiter list, and rate state.
 #[derive(Debug, Default)]
 struct L2Leaf {
     value: Usage,
     waiters: VecDeque<VQueueHandle>,
+    rate: RateState,
 }
 
 impl L2Leaf {
     fn is_unused(&self) -> bool {
-        self.value.is_zero() && self.waiters.is_empty()
+        self.value.is_zero() && self.waiters.is_empty() && self.rate.is_idle()
+    }
+}
+
+/// Token bucket and rate waiters of a trie node.
+#[derive(Debug, Default)]
+pub(super) struct RateState {
+    /// `None` means the bucket is full.
+    pub(super) bucket: Option<Bucket>,
+    /// Vqueues waiting for a token at this node, in arrival order.
+    pub(super) waiters: VecDeque<VQueueHandle>,
+    /// The armed timer of this node and its deadline.
+    pub(super) timer: Option<(delay_queue::Key, MillisSinceEpoch)>,
+}
+
+impl RateState {
+    /// Tokens available at `now`, given the current rate of the node.
+    pub(super) fn tokens(&self, now: MillisSinceEpoch, rate: &TokenRate) -> f64 {
+        match &self.bucket {
+            None => rate.burst,
+            Some(bucket) => {
+                let elapsed = now.as_u64().saturating_sub(bucket.updated_at.as_u64());
+                (bucket.tokens + rate.refilled(elapsed)).min(rate.burst)
+            }
+        }
+    }
+
+    /// Returns true if the bucket is full and nobody waits for tokens.
+    pub(super) fn is_idle(&self) -> bool {
+        self.bucket.is_none() && self.waiters.is_empty()
+    }
+}
+
+/// Bucket fill level as of `updated_at`. Refilled lazily on read.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Bucket {
+    pub(super) tokens: f64,
+    pub(super) updated_at: MillisSinceEpoch,
+}
+
+/// Token-bucket parameters of a rule's rate limit: `tokens` are refilled every `period_ms`.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct TokenRate {
+    tokens: f64,
+    period_ms: f64,
+    pub(super) burst: f64,
+}
+
+impl TokenRate {
+    pub(super) fn new(rate: Rate, burst: NonZeroU32) -> Self {
+        Self {
+            tokens: rate.get().get() as f64,
+            period_ms: rate.period().as_millis() as f64,
+            burst: burst.get() as f64,
+        }
+    }
+
+    fn from_limits(limits: &UserLimits) -> Option<Self> {
+        Some(Self::new(limits.rate?, limits.effective_burst()?))
+    }
+
+    /// Tokens refilled in `elapsed_ms`.
+    fn refilled(&self, elapsed_ms: u64) -> f64 {
+        // Multiply first to keep whole periods exact
+        elapsed_ms as f64 * self.tokens / self.period_ms
+    }
+
+    /// Returns the time at which a bucket holding `tokens` at `now` reaches `needed` tokens.
+    pub(super) fn deadline(
+        &self,
+        now: MillisSinceEpoch,
+        tokens: f64,
+        needed: f64,
+    ) -> MillisSinceEpoch {
+        if tokens >= needed {
+            return now;
+        }
+        let millis = ((needed - tokens) * self.period_ms / self.tokens).ceil() as u64;
+        now + Duration::from_millis(millis)
+    }
+}
+
+/// Identifies the trie node a rate timer belongs to. The depth of `node_key`
+/// determines the level of the node.
+#[derive(Debug)]
+struct RateTimer {
+    scope: Scope,
+    node_key: LimitKey<ReString>,
+}
+
+/// Arms the rate timer of a node for `deadline`, unless it's armed for an earlier time already.
+/// `timer` identifies the node once the timer fires.
+pub(super) fn arm_rate_timer<T>(
+    timers: &mut DelayQueue<T>,
+    state: &mut RateState,
+    now: MillisSinceEpoch,
+    deadline: MillisSinceEpoch,
+    timer: impl FnOnce() -> T,
+) {
+    let timeout = Duration::from_millis(deadline.as_u64().saturating_sub(now.as_u64()));
+    match &mut state.timer {
+        Some((_, armed_deadline)) if *armed_deadline <= deadline => {}
+        Some((key, armed_deadline)) => {
+            timers.reset(key, timeout);
+            *armed_deadline = deadline;
+        }
+        None => {
+            let key = timers.insert(timer(), timeout);
+            state.timer = Some((key, deadline));
+        }
+    }
+}
+
+/// The levels applicable to a limit key, top-down.
+fn levels_of(limit_key: &LimitKey<ReString>) -> impl Iterator<Item = Level> {
+    (1..=limit_key.depth() + 1).filter_map(Level::from_u8)
+}
+
+/// The level of the trie node identified by `node_key`.
+fn node_level(node_key: &LimitKey<ReString>) -> Level {
+    match node_key {
+        LimitKey::None => Level::Scope,
+        LimitKey::L1(_) => Level::Level1,
+        LimitKey::L2(_, _) => Level::Level2,
+    }
+}
+
+/// The key of the trie node at `level` along the path of `limit_key`.
+fn node_key(limit_key: &LimitKey<ReString>, level: Level) -> LimitKey<ReString> {
+    match (level, limit_key) {
+        (Level::Scope, _) | (_, LimitKey::None) => LimitKey::None,
+        (Level::Level1, LimitKey::L1(l1) | LimitKey::L2(l1, _)) => LimitKey::L1(l1.clone()),
+        (Level::Level2, _) => limit_key.clone(),
     }
 }
 
@@ -559,7 +981,17 @@ impl State {
             woken.push(h);
         }
 
-        // Prune empty nodes bottom-up
+        self.prune(scope, limit_key);
+
+        woken
+    }
+
+    /// Prunes empty nodes along the path bottom-up.
+    fn prune(&mut self, scope: &Scope, limit_key: &LimitKey<ReString>) {
+        let Some(scope_node) = self.scopes.get_mut(scope) else {
+            return;
+        };
+
         match limit_key {
             LimitKey::None => {}
             LimitKey::L1(l1) => {
@@ -581,8 +1013,82 @@ impl State {
         if scope_node.is_unused() {
             self.scopes.remove(scope);
         }
+    }
 
-        woken
+    fn rate_state(
+        &self,
+        scope: &Scope,
+        limit_key: &LimitKey<ReString>,
+        level: Level,
+    ) -> Option<&RateState> {
+        let scope_node = self.scopes.get(scope)?;
+        match level {
+            Level::Scope => Some(&scope_node.rate),
+            Level::Level1 => scope_node.l1.get(limit_key.level1()?).map(|n| &n.rate),
+            Level::Level2 => scope_node
+                .l1
+                .get(limit_key.level1()?)?
+                .l2
+                .get(limit_key.level2()?)
+                .map(|leaf| &leaf.rate),
+        }
+    }
+
+    fn rate_state_mut(
+        &mut self,
+        scope: &Scope,
+        limit_key: &LimitKey<ReString>,
+        level: Level,
+    ) -> Option<&mut RateState> {
+        let scope_node = self.scopes.get_mut(scope)?;
+        match level {
+            Level::Scope => Some(&mut scope_node.rate),
+            Level::Level1 => scope_node
+                .l1
+                .get_mut(limit_key.level1()?)
+                .map(|n| &mut n.rate),
+            Level::Level2 => scope_node
+                .l1
+                .get_mut(limit_key.level1()?)?
+                .l2
+                .get_mut(limit_key.level2()?)
+                .map(|leaf| &mut leaf.rate),
+        }
+    }
+
+    /// Like [`Self::rate_state_mut`], but creates the missing nodes along the path.
+    fn rate_state_entry(
+        &mut self,
+        scope: &Scope,
+        limit_key: &LimitKey<ReString>,
+        level: Level,
+    ) -> &mut RateState {
+        let scope_node = self.scopes.entry_ref(scope).or_default();
+        match level {
+            Level::Scope => &mut scope_node.rate,
+            Level::Level1 => {
+                let l1_key = limit_key
+                    .level1()
+                    .expect("L1 key required for Level1 rate state");
+                &mut scope_node.l1.entry_ref(l1_key).or_default().rate
+            }
+            Level::Level2 => {
+                let l1_key = limit_key
+                    .level1()
+                    .expect("L1 key required for Level2 rate state");
+                let l2_key = limit_key
+                    .level2()
+                    .expect("L2 key required for Level2 rate state");
+                &mut scope_node
+                    .l1
+                    .entry_ref(l1_key)
+                    .or_default()
+                    .l2
+                    .entry_ref(l2_key)
+                    .or_default()
+                    .rate
+            }
+        }
     }
 
     fn add_to_waiters(
@@ -675,6 +1181,7 @@ impl State {
             RulePattern::Scope(scope_pat) => {
                 for_each_matching_scope(&mut self.scopes, scope_pat, |scope_node| {
                     woken.extend(scope_node.waiters.drain(..));
+                    woken.extend(scope_node.rate.waiters.drain(..));
                 });
             }
             RulePattern::L1 {
@@ -684,6 +1191,7 @@ impl State {
                 for_each_matching_scope(&mut self.scopes, scope_pat, |scope_node| {
                     for_each_matching(&mut scope_node.l1, l1_pat, |l1_node| {
                         woken.extend(l1_node.waiters.drain(..));
+                        woken.extend(l1_node.rate.waiters.drain(..));
                     });
                 });
             }
@@ -696,6 +1204,7 @@ impl State {
                     for_each_matching(&mut scope_node.l1, l1_pat, |l1_node| {
                         for_each_matching(&mut l1_node.l2, l2_pat, |l2_leaf| {
                             woken.extend(l2_leaf.waiters.drain(..));
+                            woken.extend(l2_leaf.rate.waiters.drain(..));
                         });
                     });
                 });
@@ -845,6 +1354,22 @@ fn for_each_matching<V>(
     }
 }
 
+/// Result of a rate check across all applicable hierarchy levels.
+#[derive(Clone, Copy, Debug)]
+pub enum RateCheck {
+    /// No rule limits the rate of the scope + limit key.
+    Unlimited,
+    /// A token is available at every rate-limited level.
+    Available,
+    /// No token is available at `level`, the narrowest such level.
+    Blocked {
+        level: Level,
+        rule_handle: RuleHandle,
+        /// When tokens are expected to be available at all blocked levels.
+        retry_at: MillisSinceEpoch,
+    },
+}
+
 /// Result of a capacity check across all applicable hierarchy levels.
 #[derive(Clone, Debug)]
 pub struct CapacityResult {
@@ -1010,20 +1535,39 @@ mod tests {
         UserLimits::new(NonZeroU32::new(concurrency))
     }
 
+    fn rate_limits(rate: &str, burst: Option<u32>) -> UserLimits {
+        UserLimits::default()
+            .with_rate(Some(rate.parse().unwrap()), burst.and_then(NonZeroU32::new))
+    }
+
     /// Creates a UserLimiter with the given rules.
     fn limiter_with_rules(specs: &[(&str, u32)]) -> UserLimiter {
-        let rules = Rules::from_rules(specs.iter().map(|(pat, limit)| {
-            (
-                pat.parse::<RulePattern<ReString>>().unwrap(),
-                limits(*limit),
-            )
-        }));
+        limiter_with_limits(
+            specs
+                .iter()
+                .map(|(pat, limit)| (*pat, limits(*limit)))
+                .collect(),
+        )
+    }
+
+    /// Creates a UserLimiter with the given rules, carrying arbitrary limits.
+    fn limiter_with_limits(specs: Vec<(&str, UserLimits)>) -> UserLimiter {
+        let rules = Rules::from_rules(
+            specs
+                .into_iter()
+                .map(|(pat, limits)| (pat.parse::<RulePattern<ReString>>().unwrap(), limits)),
+        );
         UserLimiter {
             rules,
             state: Default::default(),
+            rate_timers: DelayQueue::new(),
         }
     }
 
+    fn millis(ms: u64) -> MillisSinceEpoch {
+        MillisSinceEpoch::new(ms)
+    }
+
     // Fake VQueueHandle for testing. The slotmap key needs to be created via a SlotMap.
     fn make_handles(n: usize) -> (slotmap::SlotMap<VQueueHandle, ()>, Vec<VQueueHandle>) {
         let mut sm = slotmap::SlotMap::with_key();
@@ -1291,4 +1835,137 @@ mod tests {
         }]);
         assert_eq!(woken.len(), 3);
     }
+
+    #[tokio::test(start_paused = true)]
+    async fn rate_limit_blocks_when_tokens_are_exhausted() {
+        // 2 tokens per second, burst defaults to 2
+        let mut limiter = limiter_with_limits(vec![("*", rate_limits("2/s", None))]);
+        let s = scope("s1");
+        let t0 = 1_000_000;
+
+        assert!(matches!(
+            limiter.check_rate_capacity(&s, &LimitKey::None, millis(t0)),
+            RateCheck::Available
+        ));
+        // Without rate rules, the rate is unlimited
+        let unlimited = limiter_with_rules(&[("*", 10)]);
+        assert!(matches!(
+            unlimited.check_rate_capacity(&s, &LimitKey::None, millis(t0)),
+            RateCheck::Unlimited
+        ));
+
+        limiter.consume_rate_tokens(&s, &LimitKey::None, millis(t0));
+        limiter.consume_rate_tokens(&s, &LimitKey::None, millis(t0));
+
+        let RateCheck::Blocked {
+            level,
+            rule_handle,
+            retry_at,
+        } = limiter.check_rate_capacity(&s, &LimitKey::None, millis(t0))
+        else {
+            panic!("expected to be blocked on the rate limit");
+        };
+        assert_eq!(level, Level::Scope);
+        assert_eq!(limiter.resolve_rule(rule_handle).unwrap().to_string(), "*");
+        assert_eq!(retry_at, millis(t0 + 500));
+
+        // A token is refilled after 500ms
+        assert!(matches!(
+            limiter.check_rate_capacity(&s, &LimitKey::None, millis(t0 + 500)),
+            RateCheck::Available
+        ));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn rate_limit_reports_narrowest_blocked_level() {
+        let mut limiter = limiter_with_limits(vec![
+            ("*", rate_limits("100/s", None)),
+            ("s1/*/t1", rate_limits("1/m", Some(1))),
+        ]);
+        let s = scope("s1");
+        let lk = limit_key("foo/t1");
+        let now = millis(1_000_000);
+
+        limiter.consume_rate_tokens(&s, &lk, now);
+
+        let RateCheck::Blocked {
+            level, retry_at, ..
+        } = limiter.check_rate_capacity(&s, &lk, now)
+        else {
+            panic!("expected to be blocked on the rate limit");
+        };
+        assert_eq!(level, Level::Level2);
+        assert_eq!(retry_at, now + Duration::from_secs(60));
+
+        // Other L2 keys have their own bucket
+        assert!(matches!(
+            limiter.check_rate_capacity(&s, &limit_key("foo/t2"), now),
+            RateCheck::Available
+        ));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn rate_waiters_are_woken_in_order_as_tokens_refill() {
+        let mut limiter = limiter_with_limits(vec![("*", rate_limits("2/s", None))]);
+        let (_sm, handles) = make_handles(3);
+        let s = scope("s1");
+        let t0 = 1_000_000;
+        let mut cx = Context::from_waker(std::task::Waker::noop());
+
+        limiter.consume_rate_tokens(&s, &LimitKey::None, millis(t0));
+        limiter.consume_rate_tokens(&s, &LimitKey::None, millis(t0));
+        for handle in &handles {
+            limiter.add_to_rate_waiters(*handle, &s, &LimitKey::None, Level::Scope, millis(t0));
+        }
+        assert!(limiter.poll_rate_timers(&mut cx, millis(t0)).is_empty());
+
+        // One token is available after 500ms, which wakes the oldest waiter only
+        tokio::time::advance(Duration::from_millis(500)).await;
+        let woken = limiter.poll_rate_timers(&mut cx, millis(t0 + 500));
+        assert_eq!(woken, vec![handles[0]]);
+
+        // Newcomers cannot overtake the vqueues which are still queued
+        assert!(matches!(
+            limiter.check_rate_capacity(&s, &LimitKey::None, millis(t0 + 500)),
+            RateCheck::Blocked { .. }
+        ));
+        limiter.consume_rate_tokens(&s, &LimitKey::None, millis(t0 + 500));
+
+        tokio::time::advance(Duration::from_millis(500)).await;
+        let woken = limiter.poll_rate_timers(&mut cx, millis(t0 + 1000));
+        assert_eq!(woken, vec![handles[1]]);
+        assert_eq!(limiter.state.scopes[&s].rate.waiters.len(), 1);
+
+        // Removing the rate rule wakes up the remaining waiter
+        let woken = limiter.apply_rule_updates(vec![RuleUpdate::Remove { pattern: rule("*") }]);
+        assert_eq!(woken, vec![handles[2]]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn drained_bucket_keeps_node_until_refilled() {
+        let mut limiter = limiter_with_limits(vec![("*", rate_limits("1/s", Some(2)))]);
+        let s = scope("s1");
+        let lk = limit_key("foo");
+        let t0 = 1_000_000;
+        let mut cx = Context::from_waker(std::task::Waker::noop());
+
+        limiter.increment_all(&s, &lk, LimitKind::Concurrency);
+        limiter.consume_rate_tokens(&s, &lk, millis(t0));
+        limiter.release_concurrency(&s, &lk);
+
+        // The scope node holds the partially drained bucket, the L1 node is gone
+        assert!(limiter.state.scopes.contains_key(&s));
+        assert!(limiter.state.scopes[&s].l1.is_empty());
+
+        tokio::time::advance(Duration::from_secs(1)).await;
+        assert!(
+            limiter
+                .poll_rate_timers(&mut cx, millis(t0 + 1000))
+                .is_empty()
+        );
+        assert!(
+            !limiter.state.scopes.contains_key(&s),
+            "scope node should be pruned once its bucket is full"
+        );
+    }
 }
@@ -126,10 +126,12 @@ impl WaitBucket {
         match r {
             ResourceKind::Lock { .. } => WaitBucket::Lock,
             ResourceKind::LimitKeyConcurrency { .. } => WaitBucket::ConcurrencyRules,
+            ResourceKind::LimitKeyRate { .. } => WaitBucket::ThrottlingRules,
             ResourceKind::InvokerConcurrency => WaitBucket::InvokerConcurrency,
             ResourceKind::InvokerMemory => WaitBucket::InvokerMemory,
             ResourceKind::InvokerThrottling { .. } => WaitBucket::InvokerThrottling,
-            ResourceKind::DeploymentConcurrency => WaitBucket::DeploymentConcurrency,
+            ResourceKind::DeploymentConcurrency { .. } => WaitBucket::DeploymentConcurrency,
+            ResourceKind::DeploymentRate { .. } => WaitBucket::ThrottlingRules,
         }
     }
 }
@@ -285,6 +287,7 @@ impl<S: VQueueStore> VQueueState<S> {
         match resources.poll_acquire_permit(
             cx,
             handle,
+            slot.vqueue_id().partition_key(),
             slot.meta(),
             inbox_head_key,
             &inbox_head_value.metadata,
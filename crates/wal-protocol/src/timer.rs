@@ -9,7 +9,7 @@
 // by the Apache License, Version 2.0.
 
 use restate_storage_api::timer_table::{Timer, TimerKey, TimerKeyKind};
-use restate_types::identifiers::{EntryIndex, InvocationId};
+use restate_types::identifiers::{EntryIndex, InvocationId, ScheduleId};
 use restate_types::invocation::ServiceInvocation;
 use restate_types::time::MillisSinceEpoch;
 use std::borrow::Borrow;
@@ -62,6 +62,23 @@ impl TimerKeyValue {
         Self { timer_key, value }
     }
 
+    pub fn schedule_tick(
+        wake_up_time: MillisSinceEpoch,
+        schedule_id: ScheduleId,
+        revision: u32,
+        invocation_id: InvocationId,
+        previous_invocation_id: Option<InvocationId>,
+    ) -> Self {
+        let (timer_key, value) = Timer::schedule_tick(
+            wake_up_time.as_u64(),
+            schedule_id,
+            revision,
+            invocation_id,
+            previous_invocation_id,
+        );
+        Self { timer_key, value }
+    }
+
     pub fn into_inner(self) -> (TimerKey, Timer) {
         (self.timer_key, self.value)
     }
@@ -135,6 +152,9 @@ impl fmt::Display for TimerKeyDisplay<'_> {
             TimerKeyKind::CleanInvocationStatus { invocation_uuid } => {
                 write!(f, "Clean invocation status '{invocation_uuid}'")
             }
+            TimerKeyKind::ScheduleTick { invocation_uuid } => {
+                write!(f, "Schedule tick '{invocation_uuid}'")
+            }
         }
     }
 }
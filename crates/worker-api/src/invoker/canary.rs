@@ -0,0 +1,106 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+
+use restate_types::identifiers::DeploymentId;
+
+/// Outcomes of the attempts against a canary deployment.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AttemptOutcomes {
+    pub attempts: u32,
+    pub failures: u32,
+}
+
+/// Outcomes of the attempts against the canary deployments of traffic splits with a rollback
+/// policy, shared by all invokers running on a node.
+///
+/// Outcomes are kept per service, since a deployment can be the canary of several services.
+#[derive(Debug, Clone, Default)]
+pub struct CanaryOutcomes {
+    inner: Arc<Mutex<HashMap<(String, DeploymentId), AttemptOutcomes>>>,
+}
+
+impl CanaryOutcomes {
+    /// Records the outcome of an attempt of the given service against its canary deployment.
+    pub fn record(&self, service_name: &str, canary_deployment_id: DeploymentId, failed: bool) {
+        let mut outcomes = self.inner.lock();
+        let outcomes = outcomes
+            .entry((service_name.to_owned(), canary_deployment_id))
+            .or_default();
+        outcomes.attempts = outcomes.attempts.saturating_add(1);
+        if failed {
+            outcomes.failures = outcomes.failures.saturating_add(1);
+        }
+    }
+
+    /// Returns the recorded outcomes, keyed by service and canary deployment.
+    pub fn snapshot(&self) -> Vec<(String, DeploymentId, AttemptOutcomes)> {
+        self.inner
+            .lock()
+            .iter()
+            .map(|((service_name, deployment_id), outcomes)| {
+                (service_name.clone(), *deployment_id, *outcomes)
+            })
+            .collect()
+    }
+
+    /// Forgets the outcomes of the given service against the given canary deployment.
+    pub fn remove(&self, service_name: &str, canary_deployment_id: DeploymentId) {
+        self.inner
+            .lock()
+            .remove(&(service_name.to_owned(), canary_deployment_id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_per_service_and_deployment() {
+        let outcomes = CanaryOutcomes::default();
+        let deployment_id = DeploymentId::new();
+
+        outcomes.record("Greeter", deployment_id, false);
+        outcomes.record("Greeter", deployment_id, true);
+        outcomes.record("Counter", deployment_id, true);
+
+        let mut snapshot = outcomes.snapshot();
+        snapshot.sort_by(|a, b| a.0.cmp(&b.0));
+        assert_eq!(
+            snapshot,
+            vec![
+                (
+                    "Counter".to_owned(),
+                    deployment_id,
+                    AttemptOutcomes {
+                        attempts: 1,
+                        failures: 1
+                    }
+                ),
+                (
+                    "Greeter".to_owned(),
+                    deployment_id,
+                    AttemptOutcomes {
+                        attempts: 2,
+                        failures: 1
+                    }
+                ),
+            ]
+        );
+
+        outcomes.remove("Greeter", deployment_id);
+        assert_eq!(outcomes.snapshot().len(), 1);
+    }
+}
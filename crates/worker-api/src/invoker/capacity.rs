@@ -14,6 +14,9 @@ use restate_futures_util::concurrency::Concurrency;
 use restate_memory::{MemoryPool, NonZeroByteCount};
 use restate_types::config::{DEFAULT_PER_INVOCATION_INITIAL_MEMORY, ThrottlingOptions};
 
+use super::canary::CanaryOutcomes;
+use super::circuit_breaker::DeploymentCircuitBreakers;
+
 pub type TokenBucket<C = gardal::TokioClock> = gardal::SharedTokenBucket<C>;
 
 #[derive(Clone)]
@@ -24,16 +27,22 @@ pub struct InvokerCapacity {
     pub memory_pool: MemoryPool,
     /// Outbound initial memory in bytes reserved from the memory pool per invocation.
     pub initial_invocation_memory: NonZeroByteCount,
+    /// Circuit breakers of the deployments invoked from this node.
+    pub circuit_breakers: DeploymentCircuitBreakers,
+    /// Outcomes of the attempts against canary deployments invoked from this node.
+    pub canary_outcomes: CanaryOutcomes,
 }
 
 impl InvokerCapacity {
-    pub const fn new_unlimited() -> Self {
+    pub fn new_unlimited() -> Self {
         Self {
             concurrency: Concurrency::new_unlimited(),
             invocation_token_bucket: None,
             action_token_bucket: None,
             memory_pool: MemoryPool::unlimited(),
             initial_invocation_memory: DEFAULT_PER_INVOCATION_INITIAL_MEMORY,
+            circuit_breakers: DeploymentCircuitBreakers::default(),
+            canary_outcomes: CanaryOutcomes::default(),
         }
     }
 
@@ -54,6 +63,8 @@ impl InvokerCapacity {
             }),
             memory_pool,
             initial_invocation_memory,
+            circuit_breakers: DeploymentCircuitBreakers::default(),
+            canary_outcomes: CanaryOutcomes::default(),
         }
     }
 }
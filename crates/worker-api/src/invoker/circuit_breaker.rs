@@ -0,0 +1,306 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use parking_lot::Mutex;
+
+use restate_types::config::DeploymentCircuitBreakerOptions;
+use restate_types::identifiers::DeploymentId;
+
+/// State of the circuit breaker of a deployment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::IntoStaticStr, derive_more::Display)]
+#[strum(serialize_all = "kebab-case")]
+#[display(rename_all = "kebab-case")]
+pub enum CircuitState {
+    /// Attempts against the deployment go through.
+    Closed,
+    /// Attempts against the deployment are held.
+    Open,
+    /// A single probe attempt is let through to find out whether the deployment recovered.
+    HalfOpen,
+}
+
+/// Decides whether an attempt against a deployment can start.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Admission {
+    /// Start the attempt.
+    Allowed,
+    /// Start the attempt as the probe of a half-open circuit breaker.
+    Probe,
+    /// Hold the attempt for at least the given duration.
+    Held(Duration),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Breaker {
+    Closed { consecutive_failures: u32 },
+    Open { until: Instant },
+    HalfOpen { probe_started_at: Instant },
+}
+
+impl Breaker {
+    fn state(&self) -> CircuitState {
+        match self {
+            Breaker::Closed { .. } => CircuitState::Closed,
+            Breaker::Open { .. } => CircuitState::Open,
+            Breaker::HalfOpen { .. } => CircuitState::HalfOpen,
+        }
+    }
+}
+
+/// Circuit breakers keyed by deployment, shared by all invokers running on a node.
+///
+/// Only deployments that recently failed are tracked. A deployment without an entry has a
+/// closed circuit breaker.
+#[derive(Debug, Clone, Default)]
+pub struct DeploymentCircuitBreakers {
+    inner: Arc<Mutex<HashMap<DeploymentId, Breaker>>>,
+}
+
+impl DeploymentCircuitBreakers {
+    /// Returns `true` if no deployment recently failed.
+    pub fn is_empty(&self) -> bool {
+        self.inner.lock().is_empty()
+    }
+
+    /// Decides whether an attempt against the given deployment can start now.
+    ///
+    /// An open circuit breaker turns half-open once its open duration has elapsed, and lets
+    /// the next attempt through as probe. If the probe does not report back within another
+    /// open duration (e.g. because it was aborted), a new probe is let through.
+    pub fn admit(
+        &self,
+        deployment_id: &DeploymentId,
+        options: &DeploymentCircuitBreakerOptions,
+        now: Instant,
+    ) -> Admission {
+        if !options.enabled {
+            return Admission::Allowed;
+        }
+
+        let open_duration = options.open_duration.to_std();
+        let mut breakers = self.inner.lock();
+        let Some(breaker) = breakers.get_mut(deployment_id) else {
+            return Admission::Allowed;
+        };
+
+        match *breaker {
+            Breaker::Closed { .. } => Admission::Allowed,
+            Breaker::Open { until } if now < until => Admission::Held(until - now),
+            Breaker::HalfOpen { probe_started_at } if now < probe_started_at + open_duration => {
+                Admission::Held(probe_started_at + open_duration - now)
+            }
+            Breaker::Open { .. } | Breaker::HalfOpen { .. } => {
+                *breaker = Breaker::HalfOpen {
+                    probe_started_at: now,
+                };
+                Admission::Probe
+            }
+        }
+    }
+
+    /// Records that the deployment answered an attempt. Returns `true` if this closed a
+    /// previously open or half-open circuit breaker.
+    pub fn record_success(&self, deployment_id: &DeploymentId) -> bool {
+        let mut breakers = self.inner.lock();
+        if breakers.is_empty() {
+            return false;
+        }
+
+        breakers
+            .remove(deployment_id)
+            .is_some_and(|breaker| breaker.state() != CircuitState::Closed)
+    }
+
+    /// Records a connection error or 5xx response of the deployment. Returns `true` if this
+    /// opened the circuit breaker.
+    pub fn record_failure(
+        &self,
+        deployment_id: &DeploymentId,
+        options: &DeploymentCircuitBreakerOptions,
+        now: Instant,
+    ) -> bool {
+        if !options.enabled {
+            return false;
+        }
+
+        let open = Breaker::Open {
+            until: now + options.open_duration.to_std(),
+        };
+        let mut breakers = self.inner.lock();
+        let breaker = breakers.entry(*deployment_id).or_insert(Breaker::Closed {
+            consecutive_failures: 0,
+        });
+
+        match breaker {
+            Breaker::Closed {
+                consecutive_failures,
+            } => {
+                *consecutive_failures += 1;
+                if *consecutive_failures >= options.failure_threshold.get() {
+                    *breaker = open;
+                    true
+                } else {
+                    false
+                }
+            }
+            Breaker::HalfOpen { .. } => {
+                // The probe failed, the deployment is still down
+                *breaker = open;
+                true
+            }
+            // Failures of attempts which started before the circuit breaker opened
+            Breaker::Open { .. } => false,
+        }
+    }
+
+    /// Returns the state of the circuit breaker of the given deployment.
+    pub fn state(&self, deployment_id: &DeploymentId) -> CircuitState {
+        self.inner
+            .lock()
+            .get(deployment_id)
+            .map(Breaker::state)
+            .unwrap_or(CircuitState::Closed)
+    }
+
+    /// Returns the number of deployments whose circuit breaker is open or half-open.
+    pub fn num_tripped(&self) -> usize {
+        self.inner
+            .lock()
+            .values()
+            .filter(|breaker| breaker.state() != CircuitState::Closed)
+            .count()
+    }
+
+    /// Returns the state of the circuit breakers of all deployments which recently failed.
+    pub fn snapshot(&self) -> Vec<(DeploymentId, CircuitState)> {
+        self.inner
+            .lock()
+            .iter()
+            .map(|(deployment_id, breaker)| (*deployment_id, breaker.state()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroU32;
+
+    use super::*;
+
+    /// Opens after 3 failures, for the default open duration of 10 seconds
+    fn options() -> DeploymentCircuitBreakerOptions {
+        DeploymentCircuitBreakerOptions {
+            failure_threshold: NonZeroU32::new(3).unwrap(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn trips_after_consecutive_failures() {
+        let breakers = DeploymentCircuitBreakers::default();
+        let deployment_id = DeploymentId::new();
+        let options = options();
+        let now = Instant::now();
+
+        assert!(!breakers.record_failure(&deployment_id, &options, now));
+        assert!(!breakers.record_failure(&deployment_id, &options, now));
+        // A success in between resets the count
+        assert!(!breakers.record_success(&deployment_id));
+        assert!(!breakers.record_failure(&deployment_id, &options, now));
+        assert!(!breakers.record_failure(&deployment_id, &options, now));
+        assert_eq!(
+            breakers.admit(&deployment_id, &options, now),
+            Admission::Allowed
+        );
+
+        assert!(breakers.record_failure(&deployment_id, &options, now));
+        assert_eq!(breakers.state(&deployment_id), CircuitState::Open);
+        assert_eq!(breakers.num_tripped(), 1);
+        assert_eq!(
+            breakers.snapshot(),
+            vec![(deployment_id, CircuitState::Open)]
+        );
+        assert_eq!(
+            breakers.admit(&deployment_id, &options, now + Duration::from_secs(4)),
+            Admission::Held(Duration::from_secs(6))
+        );
+
+        // Other deployments are not affected
+        assert_eq!(
+            breakers.admit(&DeploymentId::new(), &options, now),
+            Admission::Allowed
+        );
+    }
+
+    #[test]
+    fn half_open_probe() {
+        let breakers = DeploymentCircuitBreakers::default();
+        let deployment_id = DeploymentId::new();
+        let options = options();
+        let now = Instant::now();
+        for _ in 0..3 {
+            breakers.record_failure(&deployment_id, &options, now);
+        }
+
+        let after_open = now + Duration::from_secs(10);
+        assert_eq!(
+            breakers.admit(&deployment_id, &options, after_open),
+            Admission::Probe
+        );
+        assert_eq!(breakers.state(&deployment_id), CircuitState::HalfOpen);
+        // Only a single probe is let through
+        assert_eq!(
+            breakers.admit(&deployment_id, &options, after_open),
+            Admission::Held(Duration::from_secs(10))
+        );
+
+        // A failed probe opens the circuit breaker again
+        assert!(breakers.record_failure(&deployment_id, &options, after_open));
+        assert_eq!(breakers.state(&deployment_id), CircuitState::Open);
+
+        // A successful probe closes it
+        let after_reopen = after_open + Duration::from_secs(10);
+        assert_eq!(
+            breakers.admit(&deployment_id, &options, after_reopen),
+            Admission::Probe
+        );
+        assert!(breakers.record_success(&deployment_id));
+        assert_eq!(breakers.state(&deployment_id), CircuitState::Closed);
+        assert_eq!(
+            breakers.admit(&deployment_id, &options, after_reopen),
+            Admission::Allowed
+        );
+    }
+
+    #[test]
+    fn lost_probe_is_replaced() {
+        let breakers = DeploymentCircuitBreakers::default();
+        let deployment_id = DeploymentId::new();
+        let options = options();
+        let now = Instant::now();
+        for _ in 0..3 {
+            breakers.record_failure(&deployment_id, &options, now);
+        }
+
+        let probe_at = now + Duration::from_secs(10);
+        assert_eq!(
+            breakers.admit(&deployment_id, &options, probe_at),
+            Admission::Probe
+        );
+        assert_eq!(
+            breakers.admit(&deployment_id, &options, probe_at + Duration::from_secs(10)),
+            Admission::Probe
+        );
+    }
+}
@@ -8,7 +8,9 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+pub mod canary;
 pub mod capacity;
+pub mod circuit_breaker;
 mod effects;
 pub mod entry_enricher;
 mod handle;
@@ -16,6 +16,7 @@ use restate_limiter::LimitKey;
 use restate_memory::MemoryLease;
 use restate_storage_api::vqueue_table::EntryMetadata;
 use restate_types::Scope;
+use restate_types::identifiers::DeploymentId;
 use restate_util_string::ReString;
 
 // Re-export so consumers can keep importing from `restate_worker_api::resources`.
@@ -30,8 +31,15 @@ pub enum ResourceManagerUpdate {
 }
 
 pub enum UserPermitKind {
-    // todo: DeploymentConcurrency,
+    /// Counts against the concurrency of the deployment the invocation is expected to run on.
+    DeploymentConcurrency(DeploymentId),
+    /// A token taken from the rate limit of the deployment the invocation is expected to run
+    /// on. Tokens are not given back when the permit is released.
+    DeploymentRate(DeploymentId),
     LimitKeyConcurrency(Scope, LimitKey<ReString>),
+    /// A token taken from the rate limits of the scope + limit key. Tokens are
+    /// not given back when the permit is released.
+    LimitKeyRate(Scope, LimitKey<ReString>),
 }
 
 #[derive(Default, Clone, Copy)]
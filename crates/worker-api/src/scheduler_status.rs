@@ -11,6 +11,7 @@
 use restate_clock::RoughTimestamp;
 use restate_limiter::{Level, LimitKey, RuleHandle};
 use restate_storage_api::vqueue_table::stats::WaitStats;
+use restate_types::identifiers::DeploymentId;
 use restate_types::time::MillisSinceEpoch;
 use restate_types::vqueues::EntryId;
 use restate_types::{LockName, Scope};
@@ -100,7 +101,13 @@ pub enum ResourceKind {
     /// Invoker needs to allocate memory for an invocation
     InvokerMemory,
     /// Waiting for deployment-level concurrency tokens to be available
-    DeploymentConcurrency,
+    DeploymentConcurrency { deployment_id: DeploymentId },
+    /// Waiting for deployment-level rate limit tokens to be available.
+    DeploymentRate {
+        deployment_id: DeploymentId,
+        /// Best-effort estimate for when the next token of the deployment becomes available.
+        estimated_retry_at: MillisSinceEpoch,
+    },
     /// Waiting for user-defined concurrency to be acquired.
     /// Carries routing info so the eligibility tracker can return it for waiter removal.
     LimitKeyConcurrency {
@@ -111,6 +118,19 @@ pub enum ResourceKind {
         /// May be stale if the rule was removed since blocking.
         blocked_rule: Option<RuleHandle>,
     },
+    /// Waiting for user-defined rate limit tokens to be available.
+    /// Carries routing info so the eligibility tracker can return it for waiter removal.
+    LimitKeyRate {
+        scope: Scope,
+        limit_key: LimitKey<ReString>,
+        blocked_level: Level,
+        /// Handle to the blocking rule. Resolve via the rules store for display.
+        /// May be stale if the rule was removed since blocking.
+        blocked_rule: Option<RuleHandle>,
+        /// Best-effort estimate for when the next token becomes available at the
+        /// blocked level.
+        estimated_retry_at: MillisSinceEpoch,
+    },
 }
 
 impl ResourceKind {
@@ -135,7 +155,18 @@ impl ResourceKind {
                 }
             }
             ResourceKind::InvokerMemory => BlockedResource::InvokerMemory,
-            ResourceKind::DeploymentConcurrency => BlockedResource::DeploymentConcurrency,
+            ResourceKind::DeploymentConcurrency { deployment_id } => {
+                BlockedResource::DeploymentConcurrency {
+                    deployment_id: *deployment_id,
+                }
+            }
+            ResourceKind::DeploymentRate {
+                deployment_id,
+                estimated_retry_at,
+            } => BlockedResource::DeploymentRate {
+                deployment_id: *deployment_id,
+                estimated_retry_at: *estimated_retry_at,
+            },
             ResourceKind::LimitKeyConcurrency {
                 scope,
                 limit_key,
@@ -147,6 +178,19 @@ impl ResourceKind {
                 blocked_level: *blocked_level,
                 blocked_rule: blocked_rule.and_then(resolve_rule),
             },
+            ResourceKind::LimitKeyRate {
+                scope,
+                limit_key,
+                blocked_level,
+                blocked_rule,
+                estimated_retry_at,
+            } => BlockedResource::LimitKeyRate {
+                scope: scope.clone(),
+                limit_key: limit_key.clone(),
+                blocked_level: *blocked_level,
+                blocked_rule: blocked_rule.and_then(resolve_rule),
+                estimated_retry_at: *estimated_retry_at,
+            },
         }
     }
 }
@@ -186,7 +230,13 @@ pub enum BlockedResource {
     /// Waiting on the invoker memory pool.
     InvokerMemory,
     /// Waiting on deployment-level concurrency capacity.
-    DeploymentConcurrency,
+    DeploymentConcurrency { deployment_id: DeploymentId },
+    /// Waiting on deployment-level rate limits.
+    DeploymentRate {
+        deployment_id: DeploymentId,
+        /// Best-effort estimate for when this queue can retry token acquisition.
+        estimated_retry_at: MillisSinceEpoch,
+    },
     /// Waiting on user-defined concurrency limits.
     LimitKeyConcurrency {
         scope: Scope,
@@ -196,6 +246,17 @@ pub enum BlockedResource {
         /// the rule was removed since the queue became blocked.
         blocked_rule: Option<ReString>,
     },
+    /// Waiting on user-defined rate limits.
+    LimitKeyRate {
+        scope: Scope,
+        limit_key: LimitKey<ReString>,
+        blocked_level: Level,
+        /// Display form of the rule that's holding this queue back. `None` if
+        /// the rule was removed since the queue became blocked.
+        blocked_rule: Option<ReString>,
+        /// Best-effort estimate for when this queue can retry token acquisition.
+        estimated_retry_at: MillisSinceEpoch,
+    },
 }
 
 impl std::fmt::Display for BlockedResource {
@@ -211,7 +272,17 @@ impl std::fmt::Display for BlockedResource {
                 None => f.write_str("InvokerThrottling"),
             },
             BlockedResource::InvokerMemory => f.write_str("InvokerMemory"),
-            BlockedResource::DeploymentConcurrency => f.write_str("DeploymentConcurrency"),
+            BlockedResource::DeploymentConcurrency { deployment_id } => {
+                write!(f, "DeploymentConcurrency({deployment_id})")
+            }
+            BlockedResource::DeploymentRate {
+                deployment_id,
+                estimated_retry_at,
+            } => write!(
+                f,
+                "DeploymentRate({deployment_id}, retry_at_ts={})",
+                estimated_retry_at.as_u64()
+            ),
             BlockedResource::LimitKeyConcurrency {
                 scope,
                 limit_key,
@@ -227,6 +298,20 @@ impl std::fmt::Display for BlockedResource {
                     None => write!(f, ", rule=[removed])"),
                 }
             }
+            BlockedResource::LimitKeyRate {
+                scope,
+                limit_key,
+                blocked_level,
+                blocked_rule,
+                estimated_retry_at,
+            } => {
+                write!(f, "LimitKeyRate({scope}/{limit_key}, level={blocked_level}")?;
+                match blocked_rule {
+                    Some(rule) => write!(f, ", rule={rule}")?,
+                    None => write!(f, ", rule=[removed]")?,
+                }
+                write!(f, ", retry_at_ts={})", estimated_retry_at.as_u64())
+            }
         }
     }
 }
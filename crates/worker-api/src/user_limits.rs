@@ -10,6 +10,7 @@
 
 use restate_limiter::Level;
 use restate_types::identifiers::PartitionKey;
+use restate_types::rate::Rate;
 
 /// A snapshot of a single counter in the user-limiter trie, exposed to external
 /// introspection surfaces (e.g. the `sys_user_limits` DataFusion table).
@@ -35,9 +36,19 @@ pub struct UserLimitCounterEntry {
     /// Configured concurrency limit for this counter. `None` means unlimited
     /// (either no rule matched, or the matching rule leaves concurrency undefined).
     pub concurrency_limit: Option<u32>,
+    /// Configured rate limit for this counter. `None` means unlimited.
+    pub rate_limit: Option<Rate>,
+    /// Capacity of the token bucket enforcing `rate_limit`. `None` if the rate
+    /// is unlimited.
+    pub rate_burst: Option<u32>,
+    /// Whole tokens currently available in the token bucket. `None` if the rate
+    /// is unlimited.
+    pub available_tokens: Option<u32>,
     /// Human-readable form of the rule that applies at this counter, if any.
     /// `None` when the counter has no matching rule (i.e. is unlimited).
     pub rule_pattern: Option<String>,
-    /// Number of vqueues currently waiting behind this counter.
+    /// Number of vqueues currently waiting behind this counter for concurrency.
     pub num_waiters: u64,
+    /// Number of vqueues currently waiting behind this counter for rate tokens.
+    pub num_rate_waiters: u64,
 }
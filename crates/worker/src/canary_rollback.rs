@@ -0,0 +1,139 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Node-level task rolling back traffic splits whose canary deployment fails too often.
+//!
+//! The invokers running on this node count the outcomes of their attempts against canary
+//! deployments. This task periodically compares these counts with the rollback policy of the
+//! traffic split, and rolls the split back in the schema once the policy is exceeded. Every
+//! node decides based on the attempts it ran itself.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use metrics::counter;
+use tokio::time::MissedTickBehavior;
+use tracing::{debug, info, warn};
+
+use restate_core::{Metadata, MetadataWriter, cancellation_watcher};
+use restate_metadata_store::ReadModifyWriteError;
+use restate_types::identifiers::DeploymentId;
+use restate_types::schema::Schema;
+use restate_types::schema::deployment::DeploymentResolver;
+use restate_worker_api::invoker::canary::CanaryOutcomes;
+
+use crate::metric_definitions::TRAFFIC_SPLIT_ROLLBACKS;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, thiserror::Error)]
+#[error("no traffic split to roll back")]
+struct NothingToRollBack;
+
+pub struct CanaryRollback {
+    metadata_writer: MetadataWriter,
+    canary_outcomes: CanaryOutcomes,
+}
+
+impl CanaryRollback {
+    pub fn new(metadata_writer: MetadataWriter, canary_outcomes: CanaryOutcomes) -> Self {
+        Self {
+            metadata_writer,
+            canary_outcomes,
+        }
+    }
+
+    /// Long-running task. Exits cleanly when the task center requests cancellation.
+    pub async fn run(self) {
+        let mut tick = tokio::time::interval(CHECK_INTERVAL);
+        tick.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+        let mut cancellation = std::pin::pin!(cancellation_watcher());
+
+        loop {
+            tokio::select! {
+                _ = &mut cancellation => break,
+                _ = tick.tick() => {
+                    self.check_once().await;
+                }
+            }
+        }
+    }
+
+    async fn check_once(&self) {
+        for (service_name, canary_deployment_id, outcomes) in self.canary_outcomes.snapshot() {
+            let policy = Metadata::with_current(|m| m.schema())
+                .resolve_traffic_split(&service_name)
+                .filter(|split| {
+                    split.canary_deployment_id == canary_deployment_id && !split.rolled_back
+                })
+                .and_then(|split| split.rollback);
+            let Some(policy) = policy else {
+                // The split was changed in the meantime, the outcomes are stale
+                self.canary_outcomes
+                    .remove(&service_name, canary_deployment_id);
+                continue;
+            };
+
+            if !policy.is_exceeded(outcomes.attempts, outcomes.failures) {
+                continue;
+            }
+
+            warn!(
+                restate.deployment.id = %canary_deployment_id,
+                "Canary deployment of service '{service_name}' failed {} out of {} attempts, rolling back its traffic split",
+                outcomes.failures,
+                outcomes.attempts
+            );
+            match self.roll_back(&service_name, canary_deployment_id).await {
+                Ok(()) => {
+                    info!(
+                        restate.deployment.id = %canary_deployment_id,
+                        "Rolled back the traffic split of service '{service_name}'"
+                    );
+                    counter!(TRAFFIC_SPLIT_ROLLBACKS).increment(1);
+                    self.canary_outcomes
+                        .remove(&service_name, canary_deployment_id);
+                }
+                Err(ReadModifyWriteError::FailedOperation(NothingToRollBack)) => {
+                    debug!(
+                        "Traffic split of service '{service_name}' was already changed, not rolling it back"
+                    );
+                    self.canary_outcomes
+                        .remove(&service_name, canary_deployment_id);
+                }
+                Err(err) => {
+                    // Outcomes are kept, the next check tries again
+                    warn!(
+                        "Failed rolling back the traffic split of service '{service_name}': {err}"
+                    );
+                }
+            }
+        }
+    }
+
+    async fn roll_back(
+        &self,
+        service_name: &str,
+        canary_deployment_id: DeploymentId,
+    ) -> Result<(), ReadModifyWriteError<NothingToRollBack>> {
+        self.metadata_writer
+            .global_metadata()
+            .read_modify_write(|schema: Option<Arc<Schema>>| {
+                schema
+                    .and_then(|schema| {
+                        schema.roll_back_traffic_split(service_name, canary_deployment_id)
+                    })
+                    .ok_or(NothingToRollBack)
+            })
+            .await?;
+        Ok(())
+    }
+}
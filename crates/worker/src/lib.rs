@@ -10,6 +10,7 @@
 
 extern crate core;
 
+mod canary_rollback;
 mod error;
 mod handle;
 mod invoker_integration;
@@ -65,6 +66,8 @@ pub use self::handle::*;
 pub use crate::rule_book_cache::RuleBookCacheHandle;
 pub use crate::subscription_controller::SubscriptionController;
 pub use crate::subscription_integration::SubscriptionControllerHandle;
+pub use restate_ingress_kafka::{ConsumerLagRegistry, PartitionConsumerLag};
+pub use restate_worker_api::invoker::circuit_breaker::{CircuitState, DeploymentCircuitBreakers};
 
 type PartitionProcessorBuilder = partition::PartitionProcessorBuilder;
 
@@ -219,6 +222,15 @@ where
         self.partition_processor_manager.rule_book_cache_handle()
     }
 
+    pub fn kafka_consumer_lag(&self) -> ConsumerLagRegistry {
+        self.ingress_kafka.consumer_lag()
+    }
+
+    pub fn deployment_circuit_breakers(&self) -> DeploymentCircuitBreakers {
+        self.partition_processor_manager
+            .deployment_circuit_breakers()
+    }
+
     pub async fn run(self) -> anyhow::Result<()> {
         TaskCenter::spawn_child(
             TaskKind::MetadataBackgroundSync,
@@ -61,6 +61,8 @@ pub const PARTITION_RECORD_COMMITTED_TO_READ_LATENCY_SECONDS: &str =
 pub const PARTITION_SHUFFLE_MESSAGE_COUNT: &str = "restate.partition.shuffle.message.count";
 pub const PARTITION_SHUFFLE_INFLIGHT_COUNT: &str = "restate.partition.shuffle.inflight.count";
 
+pub const TRAFFIC_SPLIT_ROLLBACKS: &str = "restate.traffic_split.rollbacks.total";
+
 pub(crate) fn describe_metrics() {
     describe_gauge!(
         PARTITION_BLOCKED_FLARE,
@@ -108,6 +110,12 @@ pub(crate) fn describe_metrics() {
         "Duration between the record commit time to read time"
     );
 
+    describe_counter!(
+        TRAFFIC_SPLIT_ROLLBACKS,
+        Unit::Count,
+        "Number of traffic splits rolled back by this node because their canary deployment failed too often"
+    );
+
     describe_gauge!(
         NUM_PARTITIONS,
         Unit::Count,
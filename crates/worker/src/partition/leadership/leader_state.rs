@@ -22,8 +22,9 @@ use futures::stream::FuturesUnordered;
 use futures::{FutureExt, StreamExt, stream};
 use itertools::Itertools;
 use metrics::counter;
-use tokio_stream::wrappers::{ReceiverStream, WatchStream};
-use tracing::{debug, error, trace};
+use tokio_stream::wrappers::errors::BroadcastStreamRecvError;
+use tokio_stream::wrappers::{BroadcastStream, ReceiverStream, WatchStream};
+use tracing::{debug, error, trace, warn};
 
 use restate_bifrost::CommitToken;
 use restate_core::network::{Oneshot, Reciprocal};
@@ -31,7 +32,9 @@ use restate_core::{Metadata, MetadataKind, TaskCenter, TaskHandle, TaskId};
 use restate_invoker_impl::InvokerHandle as InvokerChannelServiceHandle;
 use restate_limiter::RuleBook;
 use restate_partition_store::PartitionDb;
+use restate_storage_api::timer_table::Timer;
 use restate_storage_api::vqueue_table::scheduler::SchedulerDecisionsCommand;
+use restate_types::config::Configuration;
 use restate_types::identifiers::{
     InvocationId, LeaderEpoch, PartitionId, PartitionKey, PartitionProcessorRpcRequestId,
     WithPartitionKey,
@@ -44,6 +47,7 @@ use restate_types::net::partition_processor::{
     PartitionProcessorRpcError, PartitionProcessorRpcResponse,
 };
 use restate_types::sharding::KeyRange;
+use restate_types::timer::{FireTimers, TimeControl};
 use restate_types::{RESTATE_VERSION_1_7_0, SemanticRestateVersion, Version, Versioned, vqueues};
 use restate_vqueues::VQueueEvent;
 use restate_vqueues::scheduler::Decisions;
@@ -106,6 +110,8 @@ pub struct LeaderState {
     shuffle_stream: ReceiverStream<shuffle::OutboxTruncation>,
     schema_stream: WatchStream<Version>,
     rule_book_stream: WatchStream<Arc<RuleBook>>,
+    /// Set if the `controllable_time` experimental feature is enabled.
+    fire_timers_stream: Option<BroadcastStream<FireTimers>>,
     cleaner_handle: CleanerHandle,
     trimmer_task_id: TaskId,
     durability_tracker: DurabilityTracker,
@@ -151,6 +157,11 @@ impl LeaderState {
                 WatchStream::new(m.watch(MetadataKind::Schema))
             }),
             rule_book_stream: WatchStream::new(rule_book_rx),
+            fire_timers_stream: Configuration::pinned()
+                .common
+                .experimental
+                .is_controllable_time_enabled()
+                .then(|| BroadcastStream::new(TimeControl::global().subscribe())),
             timer_service: Box::pin(timer_service),
             scheduler,
             invoker_handle,
@@ -242,6 +253,19 @@ impl LeaderState {
             )
         });
 
+        let partition_id = self.partition_id;
+        let fire_timers_stream = stream::iter(self.fire_timers_stream.as_mut())
+            .flatten()
+            .filter_map(|request| {
+                std::future::ready(match request {
+                    Ok(request) => Some(ActionEffect::FireTimers(request)),
+                    Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+                        warn!(%partition_id, "Skipped {skipped} requests to fire timers");
+                        None
+                    }
+                })
+            });
+
         let invoker_stream = (&mut self.invoker_stream).map(ActionEffect::Invoker);
         let shuffle_stream = (&mut self.shuffle_stream).map(ActionEffect::Shuffle);
         let cleaner_stream = self.cleaner_handle.effects().map(ActionEffect::Cleaner);
@@ -261,7 +285,8 @@ impl LeaderState {
             awaiting_rpc_self_propose_stream,
             dur_tracker_stream,
             schema_stream,
-            rule_book_stream
+            rule_book_stream,
+            fire_timers_stream
         );
         let mut all_streams = all_streams.ready_chunks(BATCH_READY_UP_TO);
 
@@ -451,6 +476,40 @@ impl LeaderState {
                         .self_propose(timer.invocation_id().partition_key(), Command::Timer(timer))
                         .await?;
                 }
+                ActionEffect::FireTimers(request) => {
+                    let timers: Vec<_> = self
+                        .timer_service
+                        .timers()
+                        .filter(|timer| {
+                            request.matches(
+                                timer.wake_up_time(),
+                                timer.invocation_id().invocation_uuid(),
+                                !matches!(
+                                    timer.value(),
+                                    Timer::CleanInvocationStatus(_) | Timer::ScheduleTick { .. }
+                                ),
+                            )
+                        })
+                        .cloned()
+                        .sorted_unstable_by(|a, b| a.key().cmp(b.key()))
+                        .collect();
+                    debug!(
+                        partition_id = %self.partition_id,
+                        "Firing {} timers ahead of time", timers.len()
+                    );
+                    for timer in timers {
+                        // so that the timer service doesn't fire it a second time
+                        self.timer_service
+                            .as_mut()
+                            .remove_timer(timer.key().clone());
+                        self.self_proposer
+                            .self_propose(
+                                timer.invocation_id().partition_key(),
+                                Command::Timer(timer),
+                            )
+                            .await?;
+                    }
+                }
                 ActionEffect::Cleaner(effect) => {
                     let (invocation_id, cmd) = match effect {
                         CleanerEffect::PurgeJournal(invocation_id) => (
@@ -30,6 +30,7 @@ use restate_core::network::{Oneshot, Reciprocal, TransportConnect};
 use restate_core::{Metadata, ShutdownError, TaskCenter, TaskKind, my_node_id};
 use restate_errors::NotRunningError;
 use restate_ingestion_client::IngestionClient;
+use restate_ingress_kafka::KafkaEgress;
 use restate_invoker_impl::{
     InvokerHandle as InvokerChannelServiceHandle, Service as InvokerService,
 };
@@ -63,6 +64,7 @@ use restate_types::partitions::state::PartitionReplicaSetStates;
 use restate_types::partitions::{Partition, PartitionFeatureChange};
 use restate_types::schema::Schema;
 use restate_types::storage::{StorageDecodeError, StorageEncodeError};
+use restate_types::timer::FireTimers;
 use restate_types::{GenerationalNodeId, SemanticRestateVersion};
 use restate_util_time::DurationExt;
 use restate_vqueues::scheduler::{self};
@@ -151,6 +153,7 @@ pub(crate) enum ActionEffect {
     Invoker(InvokerEffect),
     Shuffle(shuffle::OutboxTruncation),
     Timer(TimerKeyValue),
+    FireTimers(FireTimers),
     Cleaner(cleaner::CleanerEffect),
     PartitionMaintenance(UpdatePartitionDurabilityCommand),
     UpsertSchema(Schema),
@@ -443,6 +446,8 @@ where
                 self.invoker_capacity.invocation_token_bucket.clone(),
                 self.invoker_capacity.action_token_bucket.clone(),
                 self.invoker_capacity.memory_pool.clone(),
+                self.invoker_capacity.circuit_breakers.clone(),
+                self.invoker_capacity.canary_outcomes.clone(),
             )?;
 
             let mut invoker_handle = invoker.handle();
@@ -482,6 +487,7 @@ where
                     self.invoker_capacity.invocation_token_bucket.clone(),
                     self.invoker_capacity.memory_pool.clone(),
                     self.invoker_capacity.initial_invocation_memory,
+                    Metadata::with_current(|m| m.updateable_schema()),
                 )
                 .await?,
                 partition_store.partition_db().clone(),
@@ -499,20 +505,32 @@ where
             let (fencing_tokens, next_fencing_token) =
                 Self::resume_invoked_invocations(&mut invoker_handle, &mut partition_store).await?;
 
+            // Firing timers ahead of time needs all of them in memory
+            let num_timers_in_memory_limit =
+                if config.common.experimental.is_controllable_time_enabled() {
+                    None
+                } else {
+                    config.worker.num_timers_in_memory_limit()
+                };
             let timer_service = TimerService::new(
                 TokioClock,
-                config.worker.num_timers_in_memory_limit(),
+                num_timers_in_memory_limit,
                 TimerReader::from(partition_store.clone()),
             );
 
             let (shuffle_tx, shuffle_rx) = mpsc::channel(config.worker.internal_queue_length());
 
+            let adopted_outbox = partition_store.get_adopted_outbox().await?;
             let shuffle = Shuffle::new(
-                ShuffleMetadata::new(self.partition.partition_id, *leader_epoch),
+                ShuffleMetadata::new(self.partition.partition_id, *leader_epoch, adopted_outbox),
                 OutboxReader::from(partition_store.clone()),
                 shuffle_tx,
                 config.worker.internal_queue_length(),
                 self.ingestion_client.clone(),
+                KafkaEgress::new(
+                    self.partition.partition_id,
+                    Metadata::with_current(|m| m.updateable_schema()),
+                ),
             );
 
             let shuffle_hint_tx = shuffle.create_hint_sender();
@@ -585,6 +603,14 @@ where
                 feature_changes.push(PartitionFeatureChange::EnableUniqueRandomSeeds);
             }
 
+            // Schedule ticks persist new timer and invocation source variants, so they are only
+            // fired once the operator opted in after upgrading every node.
+            if config.common.experimental.is_schedules_enabled()
+                && !state_machine_features.is_schedules_enabled()
+            {
+                feature_changes.push(PartitionFeatureChange::EnableSchedules);
+            }
+
             if !feature_changes.is_empty() {
                 // Smallest version that supports every listed feature, but never below
                 // the partition's current min_restate_version.
@@ -992,6 +1018,10 @@ mod tests {
         fn is_unique_random_seeds_enabled(&self) -> bool {
             false
         }
+
+        fn is_schedules_enabled(&self) -> bool {
+            false
+        }
     }
 
     #[test(restate_core::test)]
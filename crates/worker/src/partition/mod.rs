@@ -798,6 +798,13 @@ where
         partition_store: &mut PartitionStore,
         schemas: &Schema,
     ) {
+        if self.is_log_permanently_sealed() {
+            response_tx.send(Err(PartitionProcessorRpcError::NotLeader(
+                self.partition_store.partition_id(),
+            )));
+            return;
+        }
+
         let _ = rpc::RpcHandler::handle(
             rpc::RpcContext::new(&mut self.leadership_state, schemas, partition_store),
             body,
@@ -806,6 +813,18 @@ where
         .await;
     }
 
+    /// A permanently sealed log (e.g. while the partition is being split) does not accept any
+    /// writes. Requests are rejected as not-leader so that callers retry them, possibly against
+    /// the partitions which replaced this one.
+    fn is_log_permanently_sealed(&self) -> bool {
+        let log_id = self.partition_store.partition().log_id();
+        Metadata::with_current(|m| {
+            m.logs_ref()
+                .chain(&log_id)
+                .is_some_and(|chain| chain.is_permanently_sealed())
+        })
+    }
+
     async fn on_rpc(
         &mut self,
         msg: ServiceMessage<PartitionLeaderService>,
@@ -924,6 +943,16 @@ where
     async fn on_pp_ingest_request(&mut self, msg: Incoming<Rpc<ReceivedIngestRequest>>) {
         let (reciprocal, request) = msg.split();
 
+        if self.is_log_permanently_sealed() {
+            reciprocal.send(
+                ResponseStatus::NotLeader {
+                    of: self.partition_store.partition_id(),
+                }
+                .into(),
+            );
+            return;
+        }
+
         self.leadership_state
             .forward_many_with_callback(
                 request.records.into_iter(),
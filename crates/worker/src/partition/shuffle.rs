@@ -18,7 +18,9 @@ use tracing::debug;
 use restate_core::cancellation_token;
 use restate_core::network::TransportConnect;
 use restate_ingestion_client::IngestionClient;
+use restate_ingress_kafka::KafkaEgress;
 use restate_storage_api::deduplication_table::DedupInformation;
+use restate_storage_api::fsm_table::AdoptedOutbox;
 use restate_storage_api::outbox_table::OutboxMessage;
 use restate_types::identifiers::{LeaderEpoch, PartitionId, PartitionKey, WithPartitionKey};
 use restate_types::message::MessageIndex;
@@ -81,7 +83,7 @@ fn create_header(
         dest: Destination::Processor {
             partition_key: dest_partition_key,
             dedup: Some(DedupInformation::cross_partition(
-                shuffle_metadata.partition_id,
+                shuffle_metadata.producer_of(seq_number),
                 seq_number,
             )),
         },
@@ -148,19 +150,34 @@ impl HintSender {
     }
 }
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Clone)]
 pub(crate) struct ShuffleMetadata {
     partition_id: PartitionId,
     leader_epoch: LeaderEpoch,
+    adopted_outbox: AdoptedOutbox,
 }
 
 impl ShuffleMetadata {
-    pub(crate) fn new(partition_id: PartitionId, leader_epoch: LeaderEpoch) -> Self {
+    pub(crate) fn new(
+        partition_id: PartitionId,
+        leader_epoch: LeaderEpoch,
+        adopted_outbox: AdoptedOutbox,
+    ) -> Self {
         ShuffleMetadata {
             partition_id,
             leader_epoch,
+            adopted_outbox,
         }
     }
+
+    /// Messages adopted from the partition this partition was split from are sent under the
+    /// producer id of the partition which created them, since receivers already deduplicate
+    /// them under that id.
+    fn producer_of(&self, seq_number: MessageIndex) -> PartitionId {
+        self.adopted_outbox
+            .producer_of(seq_number)
+            .unwrap_or(self.partition_id)
+    }
 }
 
 pub(crate) struct Shuffle<T, OR> {
@@ -172,6 +189,8 @@ pub(crate) struct Shuffle<T, OR> {
     hint_rx: async_channel::Receiver<NewOutboxMessage>,
     // used to create the senders into the shuffle
     hint_tx: async_channel::Sender<NewOutboxMessage>,
+    // publishes the calls to virtual topics
+    egress: KafkaEgress,
 }
 
 impl<T, OR> Shuffle<T, OR>
@@ -185,6 +204,7 @@ where
         truncation_tx: mpsc::Sender<OutboxTruncation>,
         channel_size: usize,
         ingestion_client: IngestionClient<T, Envelope>,
+        egress: KafkaEgress,
     ) -> Self {
         let (hint_tx, hint_rx) = async_channel::bounded(channel_size);
 
@@ -195,6 +215,7 @@ where
             hint_rx,
             hint_tx,
             ingestion_client,
+            egress,
         }
     }
 
@@ -223,24 +244,31 @@ where
             outbox_reader,
             truncation_tx,
             ingestion_client,
+            egress,
             ..
         } = self;
 
-        debug!(restate.partition.id = %metadata.partition_id, "Running shuffle");
+        let partition_id = metadata.partition_id;
+        debug!(restate.partition.id = %partition_id, "Running shuffle");
 
-        let mut state_machine =
-            state_machine::StateMachine::new(metadata, ingestion_client, outbox_reader, hint_rx);
+        let mut state_machine = state_machine::StateMachine::new(
+            metadata,
+            ingestion_client,
+            egress,
+            outbox_reader,
+            hint_rx,
+        );
 
         let mut inflight = VecDeque::new();
 
         let ingested_counter = counter!(
             PARTITION_SHUFFLE_MESSAGE_COUNT,
-            PARTITION_LABEL => self.metadata.partition_id.to_string(),
+            PARTITION_LABEL => partition_id.to_string(),
         );
 
         let inflight_count = histogram!(
             PARTITION_SHUFFLE_INFLIGHT_COUNT,
-            PARTITION_LABEL => self.metadata.partition_id.to_string(),
+            PARTITION_LABEL => partition_id.to_string(),
         );
 
         loop {
@@ -264,12 +292,24 @@ where
 
 mod state_machine {
     use std::cmp::Ordering;
+    use std::pin::Pin;
+    use std::sync::Arc;
+    use std::task::{Context, Poll};
+    use std::time::Duration;
 
+    use futures::FutureExt;
+    use futures::future::BoxFuture;
     use tokio_util::sync::ReusableBoxFuture;
 
     use restate_core::network::TransportConnect;
-    use restate_ingestion_client::{IngestFuture, IngestionClient, RecordCommit};
+    use restate_ingestion_client::{CancelledError, IngestFuture, IngestionClient, RecordCommit};
+    use restate_ingress_kafka::KafkaEgress;
     use restate_storage_api::outbox_table::OutboxMessage;
+    use restate_types::errors::{InvocationError, codes};
+    use restate_types::invocation::{
+        InvocationResponse, ResponseResult, ServiceInvocation, ServiceInvocationResponseSink,
+    };
+    use restate_types::retries::RetryPolicy;
     use restate_types::{identifiers::WithPartitionKey, message::MessageIndex};
     use restate_wal_protocol::Envelope;
 
@@ -296,15 +336,56 @@ mod state_machine {
         (result, outbox_reader)
     }
 
+    /// Delays between the attempts of publishing a record to Kafka, which is retried until it
+    /// succeeds.
+    fn publish_retry_policy() -> RetryPolicy {
+        RetryPolicy::exponential(
+            Duration::from_millis(100),
+            2.0,
+            None,
+            Some(Duration::from_secs(10)),
+        )
+    }
+
+    /// Resolves once a shuffled outbox message can be truncated.
+    pub enum ShuffleCommit {
+        /// The message has been ingested into its destination partition.
+        Ingested(RecordCommit<MessageIndex>),
+        /// The call to a virtual topic has been published. The shuffle does not read the following
+        /// messages before, so that the records are published in the order of the outbox and a
+        /// failing Kafka sink holds back the outbox instead of dropping records.
+        Published(MessageIndex),
+    }
+
+    impl Future for ShuffleCommit {
+        type Output = Result<MessageIndex, CancelledError>;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            match self.get_mut() {
+                ShuffleCommit::Ingested(commit) => commit.poll_unpin(cx),
+                ShuffleCommit::Published(sn) => Poll::Ready(Ok(*sn)),
+            }
+        }
+    }
+
     enum State {
         Idle,
         ReadingOutbox,
-        Ingesting { ingest: IngestFuture, sn: u64 },
+        Ingesting {
+            ingest: IngestFuture,
+            sn: u64,
+        },
+        Publishing {
+            publish: BoxFuture<'static, ()>,
+            sn: u64,
+        },
     }
 
     pub struct StateMachine<T, R> {
         metadata: ShuffleMetadata,
         ingestion: IngestionClient<T, Envelope>,
+        // shared with the pending publish, which outlives a cancelled `shuffle_next_message`
+        egress: Arc<tokio::sync::Mutex<KafkaEgress>>,
         hint_rx: async_channel::Receiver<NewOutboxMessage>,
         reader: Option<R>,
         read_fut: ReadFuture<R>,
@@ -320,12 +401,14 @@ mod state_machine {
         pub fn new(
             metadata: ShuffleMetadata,
             ingestion: IngestionClient<T, Envelope>,
+            egress: KafkaEgress,
             reader: R,
             hint_rx: async_channel::Receiver<NewOutboxMessage>,
         ) -> Self {
             Self {
                 metadata,
                 ingestion,
+                egress: Arc::new(tokio::sync::Mutex::new(egress)),
                 hint_rx,
                 reader: None,
                 read_fut: ReusableBoxFuture::new(get_next_message(reader, 0)),
@@ -334,7 +417,7 @@ mod state_machine {
             }
         }
 
-        pub async fn shuffle_next_message(&mut self) -> anyhow::Result<RecordCommit<u64>> {
+        pub async fn shuffle_next_message(&mut self) -> anyhow::Result<ShuffleCommit> {
             loop {
                 match &mut self.state {
                     State::Idle => {
@@ -349,22 +432,11 @@ mod state_machine {
 
                         match sn.cmp(&self.next_sequence_number) {
                             Ordering::Equal => {
-                                let envelope =
-                                    wrap_outbox_message_in_envelope(message, sn, &self.metadata);
-                                self.state = State::Ingesting {
-                                    ingest: self
-                                        .ingestion
-                                        .ingest(envelope.partition_key(), envelope),
-                                    sn,
-                                };
+                                self.shuffle(sn, message);
                             }
                             Ordering::Greater => {
                                 // Missed hints; we need to do an outbox scan
-                                self.read_fut.set(get_next_message(
-                                    self.reader.take().unwrap(),
-                                    self.next_sequence_number,
-                                ));
-                                self.state = State::ReadingOutbox;
+                                self.read_next_message(self.next_sequence_number);
                             }
                             Ordering::Less => {
                                 // this is a hint for a message that we have already sent, so we can ignore it
@@ -376,14 +448,18 @@ mod state_machine {
                         let commit_token = ingest.await?.map(|_| sn);
 
                         // read next message
-                        self.next_sequence_number = sn + 1;
-                        self.read_fut.set(get_next_message(
-                            self.reader.take().unwrap(),
-                            self.next_sequence_number,
-                        ));
-                        self.state = State::ReadingOutbox;
-
-                        return Ok(commit_token);
+                        self.read_next_message(sn + 1);
+
+                        return Ok(ShuffleCommit::Ingested(commit_token));
+                    }
+                    State::Publishing { publish, sn } => {
+                        let sn = *sn;
+                        publish.await;
+
+                        // read next message
+                        self.read_next_message(sn + 1);
+
+                        return Ok(ShuffleCommit::Published(sn));
                     }
                     State::ReadingOutbox => {
                         let (result, reader) = self.read_fut.get_pin().await;
@@ -398,20 +474,76 @@ mod state_machine {
                                     sn >= self.next_sequence_number,
                                     "message sequence numbers must not decrease"
                                 );
-                                let envelope =
-                                    wrap_outbox_message_in_envelope(message, sn, &self.metadata);
-                                self.state = State::Ingesting {
-                                    ingest: self
-                                        .ingestion
-                                        .ingest(envelope.partition_key(), envelope),
-                                    sn,
-                                };
+                                self.shuffle(sn, message);
                             }
                         }
                     }
                 }
             }
         }
+
+        /// Ingests the message into its destination partition, or publishes it if it's a call
+        /// to a virtual topic.
+        fn shuffle(&mut self, sn: MessageIndex, message: OutboxMessage) {
+            match message {
+                OutboxMessage::ServiceInvocation(invocation)
+                    if KafkaEgress::is_virtual_topic(&invocation.invocation_target) =>
+                {
+                    if let Some(ServiceInvocationResponseSink::PartitionProcessor(target)) =
+                        invocation.response_sink
+                    {
+                        // Virtual topics have no response, reject request-response calls
+                        self.shuffle(
+                            sn,
+                            OutboxMessage::ServiceResponse(InvocationResponse {
+                                target,
+                                result: ResponseResult::Failure(InvocationError::new(
+                                    codes::BAD_REQUEST,
+                                    format!(
+                                        "calls to the virtual topic '{}' must be one-way calls",
+                                        invocation.invocation_target.handler_name()
+                                    ),
+                                )),
+                            }),
+                        );
+                        return;
+                    }
+
+                    self.state = State::Publishing {
+                        publish: Box::pin(publish(Arc::clone(&self.egress), sn, invocation)),
+                        sn,
+                    };
+                }
+                message => {
+                    let envelope = wrap_outbox_message_in_envelope(message, sn, &self.metadata);
+                    self.state = State::Ingesting {
+                        ingest: self.ingestion.ingest(envelope.partition_key(), envelope),
+                        sn,
+                    };
+                }
+            }
+        }
+
+        fn read_next_message(&mut self, next_sequence_number: MessageIndex) {
+            self.next_sequence_number = next_sequence_number;
+            self.read_fut.set(get_next_message(
+                self.reader.take().unwrap(),
+                self.next_sequence_number,
+            ));
+            self.state = State::ReadingOutbox;
+        }
+    }
+
+    async fn publish(
+        egress: Arc<tokio::sync::Mutex<KafkaEgress>>,
+        sn: MessageIndex,
+        invocation: Box<ServiceInvocation>,
+    ) {
+        egress
+            .lock()
+            .await
+            .publish_with_retry(sn, &invocation, &publish_retry_policy())
+            .await;
     }
 }
 
@@ -438,9 +570,11 @@ mod ingestion_client_tests {
     use restate_core::network::{
         BackPressureMode, FailingConnector, ServiceMessage, ServiceStream,
     };
-    use restate_core::{TaskCenter, TaskKind, TestCoreEnv, TestCoreEnvBuilder};
+    use restate_core::{Metadata, TaskCenter, TaskKind, TestCoreEnv, TestCoreEnvBuilder};
 
+    use restate_ingress_kafka::KafkaEgress;
     use restate_storage_api::StorageError;
+    use restate_storage_api::fsm_table::{AdoptedOutbox, AdoptedOutboxRange};
     use restate_storage_api::outbox_table::OutboxMessage;
     use restate_types::Version;
     use restate_types::identifiers::{InvocationId, LeaderEpoch, PartitionId};
@@ -627,7 +761,11 @@ mod ingestion_client_tests {
             PartitionTable::with_equally_sized_partitions(Version::MIN, 1),
         );
 
-        let metadata = ShuffleMetadata::new(PartitionId::from(0), LeaderEpoch::from(0));
+        let metadata = ShuffleMetadata::new(
+            PartitionId::from(0),
+            LeaderEpoch::from(0),
+            AdoptedOutbox::default(),
+        );
 
         let partition_replica_set_states = PartitionReplicaSetStates::default();
 
@@ -657,7 +795,15 @@ mod ingestion_client_tests {
 
         let (truncation_tx, _truncation_rx) = mpsc::channel(1);
 
-        let shuffle = Shuffle::new(metadata, outbox_reader, truncation_tx, 1, ingestion.clone());
+        let egress = KafkaEgress::new(metadata.partition_id, env.metadata.updateable_schema());
+        let shuffle = Shuffle::new(
+            metadata,
+            outbox_reader,
+            truncation_tx,
+            1,
+            ingestion.clone(),
+            egress,
+        );
 
         ShuffleEnv {
             env,
@@ -667,6 +813,32 @@ mod ingestion_client_tests {
         }
     }
 
+    #[test]
+    fn adopted_outbox_messages_keep_their_producer() {
+        let metadata = ShuffleMetadata::new(
+            PartitionId::from(3),
+            LeaderEpoch::from(1),
+            AdoptedOutbox {
+                ranges: vec![
+                    AdoptedOutboxRange {
+                        producer: PartitionId::from(0),
+                        end: 5,
+                    },
+                    AdoptedOutboxRange {
+                        producer: PartitionId::from(1),
+                        end: 10,
+                    },
+                ],
+            },
+        );
+
+        assert_eq!(metadata.producer_of(0), PartitionId::from(0));
+        assert_eq!(metadata.producer_of(4), PartitionId::from(0));
+        assert_eq!(metadata.producer_of(5), PartitionId::from(1));
+        assert_eq!(metadata.producer_of(9), PartitionId::from(1));
+        assert_eq!(metadata.producer_of(10), PartitionId::from(3));
+    }
+
     #[test(restate_core::test)]
     async fn shuffle_consecutive_outbox() -> anyhow::Result<()> {
         let expected_messages = iter::repeat_with(|| Some(ServiceInvocation::mock()))
@@ -747,7 +919,7 @@ mod ingestion_client_tests {
             let total_restarts = Arc::clone(&total_restarts);
             async move {
                 let mut shuffle = shuffle_env.shuffle;
-                let metadata = shuffle.metadata;
+                let metadata = shuffle.metadata.clone();
                 let truncation_tx = shuffle.truncation_tx.clone();
                 let mut processed_range = 0;
                 let mut num_restarts = 0;
@@ -771,11 +943,15 @@ mod ingestion_client_tests {
                     }
 
                     shuffle = Shuffle::new(
-                        metadata,
+                        metadata.clone(),
                         Arc::clone(&outbox_reader),
                         truncation_tx.clone(),
                         1,
                         shuffle_env.ingestion.clone(),
+                        KafkaEgress::new(
+                            metadata.partition_id,
+                            Metadata::with_current(|m| m.updateable_schema()),
+                        ),
                     );
                 }
 
@@ -11,6 +11,7 @@
 use restate_storage_api::fsm_table::WriteFsmTable;
 use restate_storage_api::inbox_table::ReadInboxTable;
 use restate_storage_api::invocation_status_table::ReadInvocationStatusTable;
+use restate_storage_api::timer_table::WriteTimerTable;
 use restate_types::partitions::features::PartitionFeatureChange;
 use restate_types::sharding::KeyRange;
 use restate_wal_protocol::control::VersionBarrierCommand;
@@ -60,13 +61,15 @@ where
         // point. Pre-existing invocations without a stored random seed keep working via the
         // `to_random_seed()` fallback in `invoker_storage_reader.rs`.
         PartitionFeatureChange::EnableUniqueRandomSeeds => Ok(false),
+        // No schedule tick was registered before, they are registered when the feature is applied.
+        PartitionFeatureChange::EnableSchedules => Ok(false),
     }
 }
 
 impl<'ctx, 's: 'ctx, S> CommandHandler<&'ctx mut StateMachineApplyContext<'s, S>>
     for OnVersionBarrierCommand
 where
-    S: WriteFsmTable + ReadInboxTable + ReadInvocationStatusTable,
+    S: WriteFsmTable + ReadInboxTable + ReadInvocationStatusTable + WriteTimerTable,
 {
     async fn apply(self, ctx: &'ctx mut StateMachineApplyContext<'s, S>) -> Result<(), Error> {
         // Defense-in-depth: every feature change ID carried by the barrier must be known to this
@@ -142,6 +145,11 @@ where
             );
         }
 
+        if flip_on_changes.contains(&PartitionFeatureChange::EnableSchedules) {
+            // Schedules registered while the feature was disabled start ticking now
+            ctx.register_schedule_ticks(None)?;
+        }
+
         Ok(())
     }
 }
@@ -76,15 +76,19 @@ use restate_types::errors::{
 };
 use restate_types::identifiers::{
     AwakeableIdentifier, EntryIndex, ExternalSignalIdentifier, InvocationId,
-    PartitionProcessorRpcRequestId, ServiceId, StateMutationId,
+    PartitionProcessorRpcRequestId, ScheduleId, ServiceId, StateMutationId,
 };
 use restate_types::identifiers::{DeploymentId, WithPartitionKey};
 use restate_types::invocation::client::{
     CancelInvocationResponse, InvocationOutputResponse, KillInvocationResponse,
     PauseInvocationResponse, PurgeInvocationResponse, ResumeInvocationResponse,
 };
+use restate_types::invocation::dead_letter::{
+    DEAD_LETTER_CONTENT_TYPE, DeadLetterJournalSummary, DeadLetterRequest,
+    dead_letter_invocation_id, is_dead_letter_invocation,
+};
 use restate_types::invocation::{
-    AttachInvocationRequest, IngressInvocationResponseSink, InvocationInput,
+    AttachInvocationRequest, Header, IngressInvocationResponseSink, InvocationInput,
     InvocationMutationResponseSink, InvocationQuery, InvocationResponse, InvocationTarget,
     InvocationTargetType, InvocationTermination, JournalCompletionTarget, NotifySignalRequest,
     PurgeInvocationRequest, ResponseResult, RestartAsNewInvocationRequest, ResumeInvocationRequest,
@@ -110,6 +114,11 @@ use restate_types::logs::Lsn;
 use restate_types::message::MessageIndex;
 use restate_types::partitions::features::{PartitionFeatureChange, PersistedStateMachineFeatures};
 use restate_types::schema::Schema;
+use restate_types::schema::invocation_target::DeadLetterResolver;
+use restate_types::schema::schedules::{OverlapPolicy, ScheduleResolver};
+use restate_types::schema::subscriptions::{
+    Source as SubscriptionSource, VIRTUAL_TOPIC_SERVICE_NAME,
+};
 use restate_types::service_protocol::ServiceProtocolVersion;
 use restate_types::sharding::KeyRange;
 use restate_types::state_mut::ExternalStateMutation;
@@ -158,6 +167,11 @@ pub(crate) trait StateMachineFeatures {
     ///
     /// *Since v1.7.0*
     fn is_unique_random_seeds_enabled(&self) -> bool;
+
+    /// Whether the ticks of cron schedules are fired on this partition.
+    ///
+    /// *Since v1.7.1*
+    fn is_schedules_enabled(&self) -> bool;
 }
 
 impl<T: StateMachineFeatures> StateMachineFeatures for &T {
@@ -172,6 +186,10 @@ impl<T: StateMachineFeatures> StateMachineFeatures for &T {
     fn is_unique_random_seeds_enabled(&self) -> bool {
         (*self).is_unique_random_seeds_enabled()
     }
+
+    fn is_schedules_enabled(&self) -> bool {
+        (*self).is_schedules_enabled()
+    }
 }
 
 impl StateMachineFeatures for StateMachine {
@@ -186,6 +204,10 @@ impl StateMachineFeatures for StateMachine {
     fn is_unique_random_seeds_enabled(&self) -> bool {
         self.enabled_features.unique_random_seeds
     }
+
+    fn is_schedules_enabled(&self) -> bool {
+        self.enabled_features.schedules
+    }
 }
 
 impl<S> StateMachineFeatures for StateMachineApplyContext<'_, S> {
@@ -200,6 +222,10 @@ impl<S> StateMachineFeatures for StateMachineApplyContext<'_, S> {
     fn is_unique_random_seeds_enabled(&self) -> bool {
         self.enabled_features.unique_random_seeds
     }
+
+    fn is_schedules_enabled(&self) -> bool {
+        self.enabled_features.schedules
+    }
 }
 
 pub struct StateMachine {
@@ -507,6 +533,20 @@ impl<S> StateMachineApplyContext<'_, S> {
                     "Register cleanup invocation status timer"
                 )
             }
+            Timer::ScheduleTick {
+                schedule_id,
+                invocation_id,
+                ..
+            } => {
+                debug_if_leader!(
+                    self.is_leader,
+                    restate.invocation.id = %invocation_id,
+                    restate.timer.wake_up_time = %timer_value.wake_up_time(),
+                    restate.timer.key = %TimerKeyDisplay(timer_value.key()),
+                    "Register tick timer for schedule {}",
+                    schedule_id
+                )
+            }
         };
 
         self.storage
@@ -878,7 +918,8 @@ impl<S> StateMachineApplyContext<'_, S> {
                 {
                     // only update if schema is none or has a smaller version
                     debug!("Schema updated to version '{}'", upsert.schema.version());
-                    *self.schema = Some(upsert.schema);
+                    let previous_schema = self.schema.replace(upsert.schema);
+                    self.register_schedule_ticks(previous_schema.as_ref())?;
                 }
 
                 Ok(())
@@ -2568,6 +2609,7 @@ impl<S> StateMachineApplyContext<'_, S> {
             + WriteJournalEventsTable,
     {
         let (key, value) = timer_value.into_inner();
+        let wake_up_time = MillisSinceEpoch::from(key.timestamp);
         self.do_delete_timer(key).await?;
 
         match value {
@@ -2613,7 +2655,159 @@ impl<S> StateMachineApplyContext<'_, S> {
                 Ok(())
             }
             Timer::NeoInvoke(ref invocation_id) => self.on_neo_invoke_timer(invocation_id).await,
+            Timer::ScheduleTick {
+                schedule_id,
+                revision,
+                invocation_id,
+                previous_invocation_id,
+            } => {
+                self.on_schedule_tick_timer(
+                    wake_up_time,
+                    schedule_id,
+                    revision,
+                    invocation_id,
+                    previous_invocation_id,
+                )
+                .await
+            }
+        }
+    }
+
+    /// Registers the next tick of every schedule owned by this partition which was added or
+    /// modified since `previous_schema`. Ticks registered for an older revision are discarded
+    /// when they fire.
+    fn register_schedule_ticks(&mut self, previous_schema: Option<&Schema>) -> Result<(), Error>
+    where
+        S: WriteTimerTable,
+    {
+        if !self.is_schedules_enabled() {
+            return Ok(());
         }
+        let Some(schema) = self.schema.as_ref() else {
+            return Ok(());
+        };
+
+        let ticks: Vec<_> = schema
+            .list_schedules()
+            .into_iter()
+            .filter(|schedule| self.partition_key_range.contains(&schedule.partition_key()))
+            .filter(|schedule| {
+                previous_schema
+                    .and_then(|previous_schema| previous_schema.get_schedule(schedule.id()))
+                    .is_none_or(|previous| previous.revision() != schedule.revision())
+            })
+            .filter_map(|schedule| {
+                let tick = schedule.next_tick_after(self.record_created_at)?;
+                Some(TimerKeyValue::schedule_tick(
+                    tick,
+                    schedule.id(),
+                    schedule.revision(),
+                    schedule.tick_invocation_id(tick),
+                    None,
+                ))
+            })
+            .collect();
+
+        for tick in ticks {
+            self.register_timer(tick, ServiceInvocationSpanContext::empty())?;
+        }
+
+        Ok(())
+    }
+
+    async fn on_schedule_tick_timer(
+        &mut self,
+        tick: MillisSinceEpoch,
+        schedule_id: ScheduleId,
+        revision: u32,
+        invocation_id: InvocationId,
+        mut previous_invocation_id: Option<InvocationId>,
+    ) -> Result<(), Error>
+    where
+        S: WriteOutboxTable
+            + WriteFsmTable
+            + ReadInvocationStatusTable
+            + WriteInvocationStatusTable
+            + ReadVirtualObjectStatusTable
+            + WriteVirtualObjectStatusTable
+            + WriteTimerTable
+            + WriteInboxTable
+            + WriteVQueueTable
+            + ReadVQueueTable
+            + WriteJournalTable
+            + WriteLockTable
+            + journal_table_v2::WriteJournalTable,
+    {
+        let Some(schedule) = self
+            .schema
+            .as_ref()
+            .and_then(|schema| schema.get_schedule(schedule_id))
+            .filter(|schedule| schedule.revision() == revision)
+        else {
+            debug_if_leader!(
+                self.is_leader,
+                "Discarding tick of schedule {schedule_id} with revision {revision}, the schedule was removed or modified"
+            );
+            return Ok(());
+        };
+
+        if !matches!(
+            self.get_invocation_status(&invocation_id).await?,
+            InvocationStatus::Free
+        ) {
+            // This tick was already executed, the next one has been registered back then
+            return Ok(());
+        }
+
+        let previous_running = match previous_invocation_id {
+            Some(previous_invocation_id) => !matches!(
+                self.get_invocation_status(&previous_invocation_id).await?,
+                InvocationStatus::Free | InvocationStatus::Completed(_)
+            ),
+            None => false,
+        };
+
+        if schedule.overlap_policy() == OverlapPolicy::Skip && previous_running {
+            debug_if_leader!(
+                self.is_leader,
+                "Skipping tick of schedule {schedule_id}, the invocation started by the previous tick is still running"
+            );
+        } else {
+            debug_if_leader!(
+                self.is_leader,
+                restate.invocation.id = %invocation_id,
+                "Execute tick of schedule {schedule_id}"
+            );
+            let mut service_invocation = ServiceInvocation::initialize(
+                invocation_id,
+                schedule.target().invocation_target(),
+                Source::Schedule(schedule_id),
+            );
+            if !schedule.payload().is_empty() {
+                service_invocation.argument = schedule.payload().clone();
+                service_invocation
+                    .headers
+                    .push(Header::new("content-type", "application/json"));
+            }
+            self.on_service_invocation(service_invocation).await?;
+            previous_invocation_id = Some(invocation_id);
+        }
+
+        // Ticks missed while the partition was unavailable are not executed retroactively
+        if let Some(next_tick) = schedule.next_tick_after(tick.max(self.record_created_at)) {
+            self.register_timer(
+                TimerKeyValue::schedule_tick(
+                    next_tick,
+                    schedule_id,
+                    revision,
+                    schedule.tick_invocation_id(next_tick),
+                    previous_invocation_id,
+                ),
+                ServiceInvocationSpanContext::empty(),
+            )?;
+        }
+
+        Ok(())
     }
 
     async fn on_neo_invoke_timer(&mut self, invocation_id: &InvocationId) -> Result<(), Error>
@@ -2704,6 +2898,160 @@ impl<S> StateMachineApplyContext<'_, S> {
         Ok(())
     }
 
+    /// Forwards the failed invocation to the dead-letter handler, if one is configured.
+    async fn send_to_dead_letter(
+        &mut self,
+        invocation_id: InvocationId,
+        invocation_metadata: &InFlightInvocationMetadata,
+        use_journal_table_v2: bool,
+        failure: &InvocationError,
+    ) -> Result<(), Error>
+    where
+        S: journal_table_v2::ReadJournalTable + WriteOutboxTable + WriteFsmTable,
+    {
+        let invocation_target = &invocation_metadata.invocation_target;
+        let Some(dead_letter) = self.schema.as_ref().and_then(|schema| {
+            schema.resolve_dead_letter_target(
+                invocation_metadata
+                    .pinned_deployment
+                    .as_ref()
+                    .map(|pd| &pd.deployment_id),
+                invocation_target.service_name(),
+                invocation_target.handler_name(),
+            )
+        }) else {
+            return Ok(());
+        };
+
+        if dead_letter.service == **invocation_target.service_name() {
+            // Avoid dead-letter loops, this is validated when configuring the target as well
+            return Ok(());
+        }
+        if is_dead_letter_invocation(
+            invocation_id,
+            invocation_target,
+            &invocation_metadata.source,
+        ) {
+            // Services might use each other as dead-letter handler, forward a failure only once
+            debug_if_leader!(
+                self.is_leader,
+                restate.invocation.id = %invocation_id,
+                "Not forwarding the failed dead-letter invocation to the dead-letter handler {}/{}",
+                dead_letter.service,
+                dead_letter.handler
+            );
+            return Ok(());
+        }
+
+        let input = if use_journal_table_v2 {
+            match journal_table_v2::ReadJournalTable::get_journal_entry(
+                self.storage,
+                invocation_id,
+                0,
+            )
+            .await?
+            {
+                Some(entry) => Some(entry.decode::<ServiceProtocolV4Codec, InputCommand>()?),
+                None => None,
+            }
+        } else {
+            None
+        };
+
+        let dead_letter_target =
+            InvocationTarget::service(dead_letter.service, dead_letter.handler);
+        let dead_letter_invocation_id =
+            dead_letter_invocation_id(invocation_id, &dead_letter_target);
+        debug_if_leader!(
+            self.is_leader,
+            restate.invocation.id = %invocation_id,
+            "Forwarding invocation to dead-letter handler {dead_letter_target} with invocation id {dead_letter_invocation_id}"
+        );
+
+        let request = DeadLetterRequest::new(
+            invocation_id,
+            invocation_target,
+            input
+                .as_ref()
+                .map(|input| (&input.payload, input.headers.as_slice())),
+            failure,
+            DeadLetterJournalSummary {
+                length: invocation_metadata.journal_metadata.length,
+                commands: invocation_metadata.journal_metadata.commands,
+            },
+        );
+
+        let mut service_invocation = ServiceInvocation::initialize(
+            dead_letter_invocation_id,
+            dead_letter_target,
+            Source::Service(invocation_id, invocation_target.clone()),
+        );
+        service_invocation.argument = request.encode();
+        service_invocation
+            .headers
+            .push(Header::new("content-type", DEAD_LETTER_CONTENT_TYPE));
+
+        self.handle_outgoing_message(OutboxMessage::ServiceInvocation(Box::new(
+            service_invocation,
+        )))
+    }
+
+    /// Writes the output of the invocation to the outbox as a call to the virtual topic of its
+    /// [`SubscriptionSource::Output`] subscriptions, which the shuffle publishes to Kafka.
+    fn publish_invocation_output(
+        &mut self,
+        invocation_id: InvocationId,
+        invocation_target: &InvocationTarget,
+        response_result: &ResponseResult,
+    ) -> Result<(), Error>
+    where
+        S: WriteOutboxTable + WriteFsmTable,
+    {
+        let topic = SubscriptionSource::Output {
+            service: invocation_target.service_name().to_string(),
+            handler: invocation_target.handler_name().to_string(),
+        }
+        .virtual_topic()
+        .expect("output sources have a virtual topic");
+
+        let mut service_invocation = ServiceInvocation::initialize(
+            invocation_id,
+            InvocationTarget::virtual_object(
+                VIRTUAL_TOPIC_SERVICE_NAME,
+                invocation_target.key().cloned().unwrap_or_default(),
+                topic,
+                VirtualObjectHandlerType::Shared,
+            ),
+            Source::Service(invocation_id, invocation_target.clone()),
+        );
+        service_invocation.headers.push(Header::new(
+            "restate.invocation.target",
+            invocation_target.to_string(),
+        ));
+        match response_result {
+            ResponseResult::Success(output) => {
+                service_invocation.argument = output.clone();
+                service_invocation
+                    .headers
+                    .push(Header::new("restate.invocation.status", "succeeded"));
+            }
+            ResponseResult::Failure(err) => {
+                service_invocation.argument = Bytes::copy_from_slice(err.message().as_bytes());
+                service_invocation
+                    .headers
+                    .push(Header::new("restate.invocation.status", "failed"));
+                service_invocation.headers.push(Header::new(
+                    "restate.invocation.error.code",
+                    u16::from(err.code()).to_string(),
+                ));
+            }
+        }
+
+        self.handle_outgoing_message(OutboxMessage::ServiceInvocation(Box::new(
+            service_invocation,
+        )))
+    }
+
     async fn on_invoker_effect(
         &mut self,
         effect: Effect,
@@ -2883,11 +3231,20 @@ impl<S> StateMachineApplyContext<'_, S> {
                 .await?;
             }
             InvokerEffectKind::Failed(e) => {
+                let use_journal_table_v2 = should_use_journal_table_v2(&invocation_status);
+                let invocation_metadata = invocation_status
+                    .into_invocation_metadata()
+                    .expect("Must be present if status is invoked");
+                self.send_to_dead_letter(
+                    effect.invocation_id,
+                    &invocation_metadata,
+                    use_journal_table_v2,
+                    &e,
+                )
+                .await?;
                 self.end_invocation(
                     effect.invocation_id,
-                    invocation_status
-                        .into_invocation_metadata()
-                        .expect("Must be present if status is invoked"),
+                    invocation_metadata,
                     None,
                     Some(ResponseResult::Failure(e)),
                 )
@@ -3023,11 +3380,21 @@ impl<S> StateMachineApplyContext<'_, S> {
             .as_ref()
             .map(|pd| pd.service_protocol_version);
 
+        let publish_output = self.schema.as_ref().is_some_and(|schema| {
+            schema.publishes_output_of(
+                invocation_target.service_name(),
+                invocation_target.handler_name(),
+            )
+        });
+
         let vqueue_id = invocation_metadata.vqueue_id.clone();
         let mut end_status = vqueue_table::Status::Succeeded;
-        // If there are any response sinks, or we need to store back the completed status,
-        //  we need to find the latest output entry
-        if !invocation_metadata.response_sinks.is_empty() || !completion_retention.is_zero() {
+        // If there are any response sinks, we need to store back the completed status, or to
+        //  publish the output, we need to find the latest output entry
+        if !invocation_metadata.response_sinks.is_empty()
+            || !completion_retention.is_zero()
+            || publish_output
+        {
             let response_result = if let Some(response_result) = response_result_override {
                 response_result
             } else if let Some(response_result) = self
@@ -3072,6 +3439,14 @@ impl<S> StateMachineApplyContext<'_, S> {
                 Some(&invocation_metadata.invocation_target),
             )?;
 
+            if publish_output {
+                self.publish_invocation_output(
+                    invocation_id,
+                    &invocation_target,
+                    &response_result,
+                )?;
+            }
+
             // Notify invocation result
             self.emit_invocation_end_span(
                 &invocation_id,
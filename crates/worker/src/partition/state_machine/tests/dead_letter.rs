@@ -0,0 +1,106 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use super::*;
+
+use restate_storage_api::outbox_table::OutboxMessage;
+use restate_types::invocation::dead_letter::dead_letter_invocation_id;
+use restate_types::schema::Schema;
+use restate_types::schema::invocation_target::DeadLetterTarget;
+use restate_wal_protocol::control::UpsertSchemaCommand;
+
+/// `A` and `B` use each other as dead-letter handler.
+fn mutual_dead_letter_schema() -> Schema {
+    Schema::default()
+        .with_mock_services(&[("A", "run"), ("B", "run")])
+        .with_mock_dead_letter(
+            "A",
+            DeadLetterTarget {
+                service: "B".to_owned(),
+                handler: "run".to_owned(),
+            },
+        )
+        .with_mock_dead_letter(
+            "B",
+            DeadLetterTarget {
+                service: "A".to_owned(),
+                handler: "run".to_owned(),
+            },
+        )
+}
+
+fn forwarded_invocations(actions: Vec<Action>) -> Vec<ServiceInvocation> {
+    actions
+        .into_iter()
+        .filter_map(|action| match action {
+            Action::NewOutboxMessage {
+                message: OutboxMessage::ServiceInvocation(service_invocation),
+                ..
+            } => Some(*service_invocation),
+            _ => None,
+        })
+        .collect()
+}
+
+fn failed_effect(invocation_id: InvocationId) -> v2::Envelope<v2::Raw> {
+    commands::InvokerEffectCommand::test_envelope(Effect {
+        invocation_id,
+        kind: InvokerEffectKind::Failed(InvocationError::new(codes::INTERNAL, "boom")),
+    })
+}
+
+#[restate_core::test]
+async fn dead_letter_invocation_is_not_forwarded_again() {
+    let mut test_env = TestEnv::create().await;
+    test_env
+        .apply(commands::UpsertSchemaCommand::test_envelope(
+            UpsertSchemaCommand {
+                partition_key_range: Keys::RangeInclusive(PartitionKey::MIN..=PartitionKey::MAX),
+                schema: mutual_dead_letter_schema(),
+            },
+        ))
+        .await;
+
+    let invocation_id = fixtures::mock_start_invocation_with_invocation_target(
+        &mut test_env,
+        InvocationTarget::service("A", "run"),
+    )
+    .await;
+
+    // The failure of A is forwarded to B
+    let mut forwarded = forwarded_invocations(test_env.apply(failed_effect(invocation_id)).await);
+    assert_that!(forwarded, len(eq(1)));
+    let dead_letter_invocation = forwarded.remove(0);
+    let dead_letter_target = InvocationTarget::service("B", "run");
+    assert_that!(
+        dead_letter_invocation.invocation_id,
+        eq(dead_letter_invocation_id(
+            invocation_id,
+            &dead_letter_target
+        ))
+    );
+    assert_that!(
+        dead_letter_invocation.invocation_target,
+        eq(dead_letter_target)
+    );
+
+    // The dead-letter invocation fails as well, and it's not forwarded back to A
+    let forwarded_invocation_id = dead_letter_invocation.invocation_id;
+    test_env
+        .apply(commands::InvokeCommand::test_envelope(
+            dead_letter_invocation,
+        ))
+        .await;
+    let forwarded =
+        forwarded_invocations(test_env.apply(failed_effect(forwarded_invocation_id)).await);
+    assert_that!(forwarded, empty());
+
+    test_env.shutdown().await;
+}
@@ -12,11 +12,14 @@ use std::num::NonZeroUsize;
 
 use super::*;
 
+mod dead_letter;
 mod delayed_send;
 pub mod fixtures;
 mod idempotency;
 mod kill_cancel;
 pub mod matchers;
+mod output_subscriptions;
+mod schedules;
 mod workflow;
 
 use crate::partition::state_machine::tests::fixtures::{
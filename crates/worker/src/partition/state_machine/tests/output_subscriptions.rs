@@ -0,0 +1,117 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use super::*;
+
+use restate_storage_api::outbox_table::OutboxMessage;
+use restate_types::identifiers::SubscriptionId;
+use restate_types::invocation::Header;
+use restate_types::schema::Schema;
+use restate_types::schema::subscriptions::{
+    Sink, Source as SubscriptionSource, Subscription, VIRTUAL_TOPIC_SERVICE_NAME,
+};
+use restate_wal_protocol::control::UpsertSchemaCommand;
+
+fn published_invocations(actions: Vec<Action>) -> Vec<ServiceInvocation> {
+    actions
+        .into_iter()
+        .filter_map(|action| match action {
+            Action::NewOutboxMessage {
+                message: OutboxMessage::ServiceInvocation(service_invocation),
+                ..
+            } if service_invocation.invocation_target.service_name()
+                == VIRTUAL_TOPIC_SERVICE_NAME =>
+            {
+                Some(*service_invocation)
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+async fn start_invocation(test_env: &mut TestEnv, schema: Schema) -> InvocationId {
+    test_env
+        .apply(commands::UpsertSchemaCommand::test_envelope(
+            UpsertSchemaCommand {
+                partition_key_range: Keys::RangeInclusive(PartitionKey::MIN..=PartitionKey::MAX),
+                schema,
+            },
+        ))
+        .await;
+
+    fixtures::mock_start_invocation_with_invocation_target(
+        test_env,
+        InvocationTarget::service("A", "run"),
+    )
+    .await
+}
+
+fn failed_effect(invocation_id: InvocationId) -> v2::Envelope<v2::Raw> {
+    commands::InvokerEffectCommand::test_envelope(Effect {
+        invocation_id,
+        kind: InvokerEffectKind::Failed(InvocationError::new(codes::INTERNAL, "boom")),
+    })
+}
+
+#[restate_core::test]
+async fn output_is_published_to_virtual_topic() {
+    let mut test_env = TestEnv::create().await;
+    let output_source = SubscriptionSource::Output {
+        service: "A".to_owned(),
+        handler: "run".to_owned(),
+    };
+    let schema = Schema::default()
+        .with_mock_services(&[("A", "run")])
+        .with_mock_subscription(Subscription::new(
+            SubscriptionId::new(),
+            output_source.clone(),
+            Sink::Kafka {
+                cluster: "my-cluster".to_owned(),
+                topic: "a-outputs".to_owned(),
+            },
+            Default::default(),
+        ));
+    let invocation_id = start_invocation(&mut test_env, schema).await;
+
+    let mut published = published_invocations(test_env.apply(failed_effect(invocation_id)).await);
+    assert_that!(published, len(eq(1)));
+    let record = published.remove(0);
+    assert_that!(record.invocation_id, eq(invocation_id));
+    assert_that!(
+        record.invocation_target.handler_name().to_string(),
+        eq(output_source.virtual_topic().unwrap())
+    );
+    assert_that!(record.argument, eq(Bytes::from_static(b"boom")));
+    assert_that!(
+        record.headers,
+        all!(
+            contains(eq(Header::new("restate.invocation.target", "A/run"))),
+            contains(eq(Header::new("restate.invocation.status", "failed"))),
+            contains(eq(Header::new("restate.invocation.error.code", "500")))
+        )
+    );
+
+    test_env.shutdown().await;
+}
+
+#[restate_core::test]
+async fn output_is_not_published_without_subscription() {
+    let mut test_env = TestEnv::create().await;
+    let invocation_id = start_invocation(
+        &mut test_env,
+        Schema::default().with_mock_services(&[("A", "run")]),
+    )
+    .await;
+
+    let published = published_invocations(test_env.apply(failed_effect(invocation_id)).await);
+    assert_that!(published, empty());
+
+    test_env.shutdown().await;
+}
@@ -0,0 +1,211 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use super::*;
+
+use restate_storage_api::timer_table::Timer;
+use restate_types::SemanticRestateVersion;
+use restate_types::partitions::PartitionFeatureChange;
+use restate_types::schema::Schema;
+use restate_types::schema::schedules::{OverlapPolicy, Schedule};
+use restate_wal_protocol::control::{UpsertSchemaCommand, VersionBarrierCommand};
+
+fn upsert_schema(schema: Schema) -> v2::Envelope<v2::Raw> {
+    commands::UpsertSchemaCommand::test_envelope(UpsertSchemaCommand {
+        partition_key_range: Keys::RangeInclusive(PartitionKey::MIN..=PartitionKey::MAX),
+        schema,
+    })
+}
+
+fn schedules_enabled() -> PersistedStateMachineFeatures {
+    PersistedStateMachineFeatures::from_iter([PartitionFeatureChange::EnableSchedules])
+}
+
+/// Returns the schedule tick timers registered by the given actions.
+fn registered_ticks(actions: &[Action]) -> Vec<TimerKeyValue> {
+    actions
+        .iter()
+        .filter_map(|action| match action {
+            Action::RegisterTimer { timer_value }
+                if matches!(timer_value.value(), Timer::ScheduleTick { .. }) =>
+            {
+                Some(timer_value.clone())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn tick_invocation_ids(tick: &TimerKeyValue) -> (InvocationId, Option<InvocationId>) {
+    match tick.value() {
+        Timer::ScheduleTick {
+            invocation_id,
+            previous_invocation_id,
+            ..
+        } => (*invocation_id, *previous_invocation_id),
+        _ => panic!("expected a schedule tick timer"),
+    }
+}
+
+#[restate_core::test]
+async fn tick_starts_invocation_and_registers_next_tick() {
+    let mut test_env = TestEnv::create_with_features(schedules_enabled()).await;
+    let schedule = Schedule::mock(OverlapPolicy::Allow);
+
+    let actions = test_env
+        .apply(upsert_schema(
+            Schema::default().with_mock_schedule(schedule),
+        ))
+        .await;
+    let ticks = registered_ticks(&actions);
+    assert_that!(ticks, len(eq(1)));
+    let (first_invocation_id, previous_invocation_id) = tick_invocation_ids(&ticks[0]);
+    assert_that!(previous_invocation_id, none());
+
+    let actions = test_env
+        .apply(commands::TimerCommand::test_envelope(ticks[0].clone()))
+        .await;
+    assert_that!(
+        actions,
+        contains(matchers::actions::invoke_for_id(first_invocation_id))
+    );
+    assert_that!(
+        test_env
+            .storage()
+            .get_invocation_status(&first_invocation_id)
+            .await,
+        ok(pat!(InvocationStatus::Invoked { .. }))
+    );
+
+    let next_ticks = registered_ticks(&actions);
+    assert_that!(next_ticks, len(eq(1)));
+    assert!(next_ticks[0].wake_up_time() > ticks[0].wake_up_time());
+    let (next_invocation_id, previous_invocation_id) = tick_invocation_ids(&next_ticks[0]);
+    assert_ne!(next_invocation_id, first_invocation_id);
+    assert_that!(previous_invocation_id, some(eq(first_invocation_id)));
+
+    // Firing the same tick again doesn't start a second invocation
+    let actions = test_env
+        .apply(commands::TimerCommand::test_envelope(ticks[0].clone()))
+        .await;
+    assert_that!(
+        actions,
+        not(contains(matchers::actions::invoke_for_id(
+            first_invocation_id
+        )))
+    );
+
+    test_env.shutdown().await;
+}
+
+#[restate_core::test]
+async fn skip_policy_skips_tick_while_previous_invocation_runs() {
+    let mut test_env = TestEnv::create_with_features(schedules_enabled()).await;
+    let schedule = Schedule::mock(OverlapPolicy::Skip);
+
+    let actions = test_env
+        .apply(upsert_schema(
+            Schema::default().with_mock_schedule(schedule),
+        ))
+        .await;
+    let first_tick = registered_ticks(&actions).remove(0);
+    let (first_invocation_id, _) = tick_invocation_ids(&first_tick);
+
+    let actions = test_env
+        .apply(commands::TimerCommand::test_envelope(first_tick))
+        .await;
+    let second_tick = registered_ticks(&actions).remove(0);
+    let (second_invocation_id, _) = tick_invocation_ids(&second_tick);
+
+    // The first invocation is still running, so the second tick is skipped
+    let actions = test_env
+        .apply(commands::TimerCommand::test_envelope(second_tick))
+        .await;
+    assert_that!(
+        actions,
+        not(contains(matchers::actions::invoke_for_id(
+            second_invocation_id
+        )))
+    );
+    assert_that!(
+        test_env
+            .storage()
+            .get_invocation_status(&second_invocation_id)
+            .await,
+        ok(pat!(InvocationStatus::Free))
+    );
+
+    // The skipped tick still registers the next one, which keeps tracking the running invocation
+    let third_ticks = registered_ticks(&actions);
+    assert_that!(third_ticks, len(eq(1)));
+    let (_, previous_invocation_id) = tick_invocation_ids(&third_ticks[0]);
+    assert_that!(previous_invocation_id, some(eq(first_invocation_id)));
+
+    test_env.shutdown().await;
+}
+
+#[restate_core::test]
+async fn tick_of_older_revision_is_discarded() {
+    let mut test_env = TestEnv::create_with_features(schedules_enabled()).await;
+    let mut schedule = Schedule::mock(OverlapPolicy::Allow);
+
+    let schema = Schema::default().with_mock_schedule(schedule.clone());
+    let actions = test_env.apply(upsert_schema(schema.clone())).await;
+    let old_tick = registered_ticks(&actions).remove(0);
+    let (old_invocation_id, _) = tick_invocation_ids(&old_tick);
+
+    // Modifying the schedule registers a tick for the new revision
+    schedule.mock_modify();
+    let actions = test_env
+        .apply(upsert_schema(schema.with_mock_schedule(schedule)))
+        .await;
+    assert_that!(registered_ticks(&actions), len(eq(1)));
+
+    // The tick of the old revision neither starts an invocation nor registers a new tick
+    let actions = test_env
+        .apply(commands::TimerCommand::test_envelope(old_tick))
+        .await;
+    assert_that!(
+        actions,
+        not(contains(matchers::actions::invoke_for_id(
+            old_invocation_id
+        )))
+    );
+    assert_that!(registered_ticks(&actions), empty());
+
+    test_env.shutdown().await;
+}
+
+#[restate_core::test]
+async fn ticks_are_registered_once_schedules_are_enabled() {
+    let mut test_env = TestEnv::create().await;
+
+    // Without the feature, schedules don't tick
+    let actions = test_env
+        .apply(upsert_schema(
+            Schema::default().with_mock_schedule(Schedule::mock(OverlapPolicy::Allow)),
+        ))
+        .await;
+    assert_that!(registered_ticks(&actions), empty());
+
+    let actions = test_env
+        .apply(commands::VersionBarrierCommand::test_envelope(
+            VersionBarrierCommand {
+                version: SemanticRestateVersion::current().clone(),
+                human_reason: None,
+                partition_key_range: Keys::RangeInclusive(PartitionKey::MIN..=PartitionKey::MAX),
+                feature_changes: vec![PartitionFeatureChange::EnableSchedules.id()],
+            },
+        ))
+        .await;
+    assert_that!(registered_ticks(&actions), len(eq(1)));
+
+    test_env.shutdown().await;
+}
@@ -82,8 +82,10 @@ use restate_util_string::format_restring;
 use restate_util_time::DurationExt;
 use restate_wal_protocol::Envelope;
 use restate_worker_api::invoker::capacity::InvokerCapacity;
+use restate_worker_api::invoker::circuit_breaker::DeploymentCircuitBreakers;
 use restate_worker_api::{ProcessorsManagerCommand, ProcessorsManagerHandle};
 
+use crate::canary_rollback::CanaryRollback;
 use crate::metric_definitions::{
     ERROR_STOP, FLARE_REASON_SNAPSHOT_UNAVAILABLE, GAP_STOP, PARTITION_BLOCKED_FLARE,
     PARTITION_IS_EFFECTIVE_LEADER, PARTITION_START, REASON_LABEL, STARTUP_ERROR_STOP, TYPE_LABEL,
@@ -289,6 +291,10 @@ where
         self.rule_book_cache.clone()
     }
 
+    pub fn deployment_circuit_breakers(&self) -> DeploymentCircuitBreakers {
+        self.invoker_capacity.circuit_breakers.clone()
+    }
+
     pub async fn run(mut self) -> anyhow::Result<()> {
         let mut shutdown = std::pin::pin!(cancellation_watcher());
 
@@ -300,6 +306,17 @@ where
             )?;
         }
 
+        TaskCenter::spawn_child(
+            TaskKind::Background,
+            "canary-rollback",
+            CanaryRollback::new(
+                self.metadata_writer.clone(),
+                self.invoker_capacity.canary_outcomes.clone(),
+            )
+            .run()
+            .map(|()| Ok(())),
+        )?;
+
         let metadata = Metadata::current();
 
         let mut partition_table_version_watcher = metadata.watch(MetadataKind::PartitionTable);
@@ -344,10 +361,10 @@ where
                 }
                 _ = partition_table_version_watcher.changed() => {
                     gauge!(NUM_PARTITIONS).set(self.partition_table.live_load().len() as f64);
-                    if self.wait_for_partition_table_update {
+                    if self.wait_for_partition_table_update || self.runs_split_partition() {
                         self.wait_for_partition_table_update = false;
                         // we might have not started some followers because of missing partition table
-                        // information
+                        // information, or we might run processors of partitions which were split
                         self.on_replica_set_state_changes(&replica_set_states);
                     }
                 }
@@ -1301,12 +1318,15 @@ where
     fn on_replica_set_state_changes(&mut self, replica_set_states: &PartitionReplicaSetStates) {
         let my_node_id = Metadata::with_current(|m| m.my_node_id().as_plain());
         let mut running_processors: HashSet<_> = self.processor_states.keys().copied().collect();
+        let split_partitions = self.split_partitions();
 
         // Not ideal to have to iterate over all replica states. An index per node id could help.
         // In practice, this is probably not a problem because the replica sets won't change that
         // often.
         for (partition_id, membership_state) in replica_set_states.iter() {
-            if membership_state.contains(my_node_id) {
+            // the log of a split partition is permanently sealed; its replica set might not have
+            // been cleaned up yet
+            if membership_state.contains(my_node_id) && !split_partitions.contains(&partition_id) {
                 if !self.processor_states.contains_key(&partition_id) {
                     self.start_partition_processor(partition_id, None);
                 }
@@ -1319,7 +1339,7 @@ where
         // configuration. Let's terminate them.
         for partition_id in running_processors.into_iter() {
             if let Some(processor) = self.processor_states.get_mut(&partition_id) {
-                debug!(%partition_id, "Stop partition processor because it is no longer a member of the partition configuration");
+                debug!(%partition_id, "Stop partition processor because it is no longer a member of the partition configuration or the partition was split");
                 processor.stop();
 
                 if self.pending_snapshots.contains_key(&partition_id) {
@@ -1334,6 +1354,22 @@ where
         gauge!(NUM_ACTIVE_PARTITIONS).set(self.processor_states.len() as f64);
     }
 
+    /// Partitions which were replaced by the partitions split from them.
+    fn split_partitions(&mut self) -> HashSet<PartitionId> {
+        self.partition_table
+            .live_load()
+            .iter()
+            .filter_map(|(_, partition)| partition.split_from())
+            .collect()
+    }
+
+    fn runs_split_partition(&mut self) -> bool {
+        let split_partitions = self.split_partitions();
+        self.processor_states
+            .keys()
+            .any(|partition_id| split_partitions.contains(partition_id))
+    }
+
     /// Starts a partition processor if this node is part of the replica set of the given partition.
     /// Returns true if this node is part of the replica set of the given partition. Otherwise, false.
     fn restart_partition_processor_if_replica(
@@ -1350,6 +1386,7 @@ where
             .replica_set_states
             .membership_state(partition_id)
             .contains(Metadata::with_current(|m| m.my_node_id().as_plain()))
+            && !self.split_partitions().contains(&partition_id)
         {
             self.start_partition_processor(
                 partition_id,
@@ -0,0 +1,178 @@
+# Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+# All rights reserved.
+#
+# Use of this software is governed by the Business Source License
+# included in the LICENSE file.
+#
+# As of the Change Date specified in that file, in accordance with
+# the Business Source License, use of this software will be governed
+# by the Apache License, Version 2.0.
+
+"""ctypes bindings of the restate-lite C API (lite/include/restate_lite.h).
+
+Example:
+
+    with RestateLite.start("target/release/librestate_lite.so") as restate:
+        restate.discover_deployment("http://localhost:9080")
+        print(restate.bound_addresses())
+"""
+
+import ctypes
+from dataclasses import dataclass
+
+ABI_VERSION = 1
+
+_OK = 0
+_ALREADY_RUNNING = 3
+
+ADDRESS_KINDS = {0: "tcp", 1: "unix", 2: "http"}
+
+LOG_ERROR, LOG_WARN, LOG_INFO, LOG_DEBUG, LOG_TRACE = 1, 2, 3, 4, 5
+
+
+class _Options(ctypes.Structure):
+    _fields_ = [
+        ("use_random_ports", ctypes.c_bool),
+        ("enable_tcp", ctypes.c_bool),
+        ("memory_budget", ctypes.c_size_t),
+        ("data_dir", ctypes.c_char_p),
+        ("controllable_time", ctypes.c_bool),
+    ]
+
+
+class _Address(ctypes.Structure):
+    _fields_ = [
+        ("kind", ctypes.c_int),
+        ("name", ctypes.c_char_p),
+        ("address", ctypes.c_char_p),
+    ]
+
+
+LOG_CALLBACK = ctypes.CFUNCTYPE(
+    None, ctypes.c_void_p, ctypes.c_int, ctypes.c_char_p, ctypes.c_char_p
+)
+
+
+@dataclass
+class Address:
+    kind: str
+    name: str
+    address: str
+
+
+class RestateLiteError(Exception):
+    pass
+
+
+class AlreadyRunningError(RestateLiteError):
+    """Raised when starting a server while another one runs in this process."""
+
+
+def _load(path):
+    lib = ctypes.CDLL(path)
+    lib.restate_lite_abi_version.restype = ctypes.c_uint32
+    lib.restate_lite_last_error.restype = ctypes.c_char_p
+    lib.restate_lite_options_init.argtypes = [ctypes.POINTER(_Options)]
+    lib.restate_lite_set_log_callback.argtypes = [LOG_CALLBACK, ctypes.c_int, ctypes.c_void_p]
+    lib.restate_lite_start.argtypes = [ctypes.POINTER(_Options), ctypes.POINTER(ctypes.c_void_p)]
+    lib.restate_lite_stop.argtypes = [ctypes.c_void_p]
+    lib.restate_lite_discover_deployment.argtypes = [ctypes.c_void_p, ctypes.c_char_p]
+    for name in ("restate_lite_bound_addresses", "restate_lite_advertised_addresses"):
+        getattr(lib, name).argtypes = [
+            ctypes.c_void_p,
+            ctypes.POINTER(ctypes.POINTER(_Address)),
+            ctypes.POINTER(ctypes.c_size_t),
+        ]
+    lib.restate_lite_addresses_free.argtypes = [ctypes.POINTER(_Address), ctypes.c_size_t]
+
+    version = lib.restate_lite_abi_version()
+    if version != ABI_VERSION:
+        raise RestateLiteError(f"unsupported restate-lite ABI version {version}")
+    return lib
+
+
+def _check(lib, status):
+    if status != _OK:
+        message = lib.restate_lite_last_error()
+        error = AlreadyRunningError if status == _ALREADY_RUNNING else RestateLiteError
+        raise error(message.decode() if message else f"status {status}")
+
+
+class RestateLite:
+    """An embedded Restate server. Only one server can run per process at a time."""
+
+    # keeps the callback alive, it can only be set once per process
+    _log_callback = None
+
+    @classmethod
+    def set_log_callback(cls, lib_path, callback, max_level=LOG_INFO):
+        """Forwards the server logs to callback(level, target, message)."""
+        lib = _load(lib_path)
+
+        def forward(_user_data, level, target, message):
+            callback(level, target.decode(), message.decode())
+
+        cls._log_callback = LOG_CALLBACK(forward)
+        _check(lib, lib.restate_lite_set_log_callback(cls._log_callback, max_level, None))
+
+    @classmethod
+    def start(
+        cls,
+        lib_path,
+        data_dir=None,
+        use_random_ports=True,
+        enable_tcp=True,
+        controllable_time=False,
+    ):
+        lib = _load(lib_path)
+        options = _Options()
+        lib.restate_lite_options_init(ctypes.byref(options))
+        options.use_random_ports = use_random_ports
+        options.enable_tcp = enable_tcp
+        options.controllable_time = controllable_time
+        if data_dir is not None:
+            options.data_dir = str(data_dir).encode()
+
+        handle = ctypes.c_void_p()
+        _check(lib, lib.restate_lite_start(ctypes.byref(options), ctypes.byref(handle)))
+        return cls(lib, handle)
+
+    def __init__(self, lib, handle):
+        self._lib = lib
+        self._handle = handle
+
+    def discover_deployment(self, url):
+        _check(self._lib, self._lib.restate_lite_discover_deployment(self._handle, url.encode()))
+
+    def bound_addresses(self):
+        return self._addresses(self._lib.restate_lite_bound_addresses)
+
+    def advertised_addresses(self):
+        return self._addresses(self._lib.restate_lite_advertised_addresses)
+
+    def _addresses(self, fn):
+        addresses = ctypes.POINTER(_Address)()
+        length = ctypes.c_size_t()
+        _check(self._lib, fn(self._handle, ctypes.byref(addresses), ctypes.byref(length)))
+        try:
+            return [
+                Address(
+                    ADDRESS_KINDS[addresses[i].kind],
+                    addresses[i].name.decode(),
+                    addresses[i].address.decode(),
+                )
+                for i in range(length.value)
+            ]
+        finally:
+            self._lib.restate_lite_addresses_free(addresses, length)
+
+    def stop(self):
+        if self._handle:
+            handle, self._handle = self._handle, None
+            _check(self._lib, self._lib.restate_lite_stop(handle))
+
+    def __enter__(self):
+        return self
+
+    def __exit__(self, *_exc):
+        self.stop()
@@ -0,0 +1,230 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+/**
+ * koffi bindings of the restate-lite C API (lite/include/restate_lite.h).
+ *
+ * The blocking calls run on the libuv thread pool, so that the event loop keeps serving the
+ * deployments being discovered and the log callback. Their errors are read with
+ * restate_lite_take_last_error, since the thread-local one isn't reachable from JavaScript.
+ *
+ * Example:
+ *
+ *     const restate = await RestateLite.start("target/release/librestate_lite.so");
+ *     try {
+ *       await restate.discoverDeployment("http://localhost:9080");
+ *       console.log(restate.boundAddresses());
+ *     } finally {
+ *       await restate.stop();
+ *     }
+ */
+
+import koffi from "koffi";
+
+export const ABI_VERSION = 1;
+
+const OK = 0;
+const ALREADY_RUNNING = 3;
+
+const ADDRESS_KINDS = ["tcp", "unix", "http"] as const;
+
+export enum LogLevel {
+  Error = 1,
+  Warn = 2,
+  Info = 3,
+  Debug = 4,
+  Trace = 5,
+}
+
+export interface Address {
+  kind: (typeof ADDRESS_KINDS)[number];
+  name: string;
+  address: string;
+}
+
+export interface StartOptions {
+  dataDir?: string;
+  useRandomPorts?: boolean;
+  enableTcp?: boolean;
+  controllableTime?: boolean;
+}
+
+export type LogCallback = (level: LogLevel, target: string, message: string) => void;
+
+export class RestateLiteError extends Error {}
+
+/** Thrown when starting a server while another one runs in this process. */
+export class AlreadyRunningError extends RestateLiteError {}
+
+koffi.struct("restate_lite_options_t", {
+  use_random_ports: "bool",
+  enable_tcp: "bool",
+  memory_budget: "size_t",
+  data_dir: "const char *",
+  controllable_time: "bool",
+});
+koffi.struct("restate_lite_address_t", {
+  kind: "int",
+  name: "char *",
+  address: "char *",
+});
+koffi.opaque("restate_lite_t");
+const logCallbackType = koffi.proto(
+  "void restate_lite_log_callback_t(void *user_data, int level, const char *target, const char *message)",
+);
+
+interface Bindings {
+  takeLastError: koffi.KoffiFunction;
+  optionsInit: koffi.KoffiFunction;
+  setLogCallback: koffi.KoffiFunction;
+  start: koffi.KoffiFunction;
+  stop: koffi.KoffiFunction;
+  discoverDeployment: koffi.KoffiFunction;
+  boundAddresses: koffi.KoffiFunction;
+  advertisedAddresses: koffi.KoffiFunction;
+  addressesFree: koffi.KoffiFunction;
+}
+
+const loaded = new Map<string, Bindings>();
+
+function load(path: string): Bindings {
+  const cached = loaded.get(path);
+  if (cached) {
+    return cached;
+  }
+
+  const lib = koffi.load(path);
+  const version = lib.func("uint32_t restate_lite_abi_version()")();
+  if (version !== ABI_VERSION) {
+    throw new RestateLiteError(`unsupported restate-lite ABI version ${version}`);
+  }
+  // frees the returned string once it's decoded
+  const ownedString = koffi.disposable(
+    "restate_lite_owned_string",
+    "str",
+    lib.func("void restate_lite_string_free(char *s)"),
+  );
+  const bindings: Bindings = {
+    takeLastError: lib.func("restate_lite_take_last_error", ownedString, []),
+    optionsInit: lib.func(
+      "void restate_lite_options_init(_Out_ restate_lite_options_t *options)",
+    ),
+    setLogCallback: lib.func(
+      "int restate_lite_set_log_callback(restate_lite_log_callback_t *callback, int max_level, void *user_data)",
+    ),
+    start: lib.func(
+      "int restate_lite_start(const restate_lite_options_t *options, _Out_ restate_lite_t **out)",
+    ),
+    stop: lib.func("int restate_lite_stop(restate_lite_t *restate)"),
+    discoverDeployment: lib.func(
+      "int restate_lite_discover_deployment(const restate_lite_t *restate, const char *url)",
+    ),
+    boundAddresses: lib.func(
+      "int restate_lite_bound_addresses(const restate_lite_t *restate, _Out_ void **out, _Out_ size_t *out_len)",
+    ),
+    advertisedAddresses: lib.func(
+      "int restate_lite_advertised_addresses(const restate_lite_t *restate, _Out_ void **out, _Out_ size_t *out_len)",
+    ),
+    addressesFree: lib.func("void restate_lite_addresses_free(void *addresses, size_t len)"),
+  };
+  loaded.set(path, bindings);
+  return bindings;
+}
+
+function check(lib: Bindings, status: number) {
+  if (status !== OK) {
+    const message: string | null = lib.takeLastError();
+    const ErrorType = status === ALREADY_RUNNING ? AlreadyRunningError : RestateLiteError;
+    throw new ErrorType(message ?? `status ${status}`);
+  }
+}
+
+/** Runs `fn` on the libuv thread pool and resolves with its status. */
+function callAsync(fn: koffi.KoffiFunction, ...args: unknown[]): Promise<number> {
+  return new Promise((resolve, reject) => {
+    fn.async(...args, (err: unknown, status: number) => (err ? reject(err) : resolve(status)));
+  });
+}
+
+/** An embedded Restate server. Only one server can run per process at a time. */
+export class RestateLite {
+  // keeps the callback alive, it can only be set once per process
+  private static logCallback: koffi.IKoffiRegisteredCallback | undefined;
+
+  /** Forwards the server logs to `callback`, must be set before starting the server. */
+  static setLogCallback(libPath: string, callback: LogCallback, maxLevel = LogLevel.Info) {
+    const lib = load(libPath);
+    RestateLite.logCallback = koffi.register(
+      (_userData: unknown, level: LogLevel, target: string, message: string) =>
+        callback(level, target, message),
+      koffi.pointer(logCallbackType),
+    );
+    check(lib, lib.setLogCallback(RestateLite.logCallback, maxLevel, null));
+  }
+
+  static async start(libPath: string, options: StartOptions = {}): Promise<RestateLite> {
+    const lib = load(libPath);
+    const nativeOptions: Record<string, unknown> = {};
+    lib.optionsInit(nativeOptions);
+    nativeOptions.use_random_ports = options.useRandomPorts ?? true;
+    nativeOptions.enable_tcp = options.enableTcp ?? true;
+    nativeOptions.controllable_time = options.controllableTime ?? false;
+    nativeOptions.data_dir = options.dataDir ?? null;
+
+    const handle: unknown[] = [null];
+    check(lib, await callAsync(lib.start, nativeOptions, handle));
+    return new RestateLite(lib, handle[0]);
+  }
+
+  private constructor(
+    private readonly lib: Bindings,
+    private handle: unknown,
+  ) {}
+
+  async discoverDeployment(url: string): Promise<void> {
+    check(this.lib, await callAsync(this.lib.discoverDeployment, this.handle, url));
+  }
+
+  boundAddresses(): Address[] {
+    return this.addresses(this.lib.boundAddresses);
+  }
+
+  advertisedAddresses(): Address[] {
+    return this.addresses(this.lib.advertisedAddresses);
+  }
+
+  private addresses(fn: koffi.KoffiFunction): Address[] {
+    const addresses: unknown[] = [null];
+    const length: number[] = [0];
+    check(this.lib, fn(this.handle, addresses, length));
+    try {
+      const decoded = koffi.decode(addresses[0], "restate_lite_address_t", length[0]) as {
+        kind: number;
+        name: string;
+        address: string;
+      }[];
+      return decoded.map(({ kind, name, address }) => ({
+        kind: ADDRESS_KINDS[kind],
+        name,
+        address,
+      }));
+    } finally {
+      this.lib.addressesFree(addresses[0], length[0]);
+    }
+  }
+
+  async stop(): Promise<void> {
+    if (this.handle !== null) {
+      const handle = this.handle;
+      this.handle = null;
+      check(this.lib, await callAsync(this.lib.stop, handle));
+    }
+  }
+}
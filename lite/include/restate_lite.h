@@ -0,0 +1,130 @@
+/*
+ * Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+ * All rights reserved.
+ *
+ * Use of this software is governed by the Business Source License
+ * included in the LICENSE file.
+ *
+ * As of the Change Date specified in that file, in accordance with
+ * the Business Source License, use of this software will be governed
+ * by the Apache License, Version 2.0.
+ */
+
+/*
+ * C API of restate-lite, an embedded single-process Restate server.
+ *
+ * Must be kept in sync with lite/src/ffi.rs.
+ *
+ * Every function returning a restate_lite_status_t reports failures through
+ * restate_lite_last_error(), which returns the message of the last error on the
+ * calling thread. Callers dispatching the calls to a thread pool use
+ * restate_lite_take_last_error() instead.
+ */
+
+#ifndef RESTATE_LITE_H
+#define RESTATE_LITE_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+#define RESTATE_LITE_ABI_VERSION 1
+
+typedef enum {
+    RESTATE_LITE_OK = 0,
+    RESTATE_LITE_ERROR = 1,
+    RESTATE_LITE_INVALID_ARGUMENT = 2,
+    RESTATE_LITE_ALREADY_RUNNING = 3,
+} restate_lite_status_t;
+
+typedef enum {
+    RESTATE_LITE_ADDRESS_TCP = 0,
+    RESTATE_LITE_ADDRESS_UNIX = 1,
+    RESTATE_LITE_ADDRESS_HTTP = 2,
+} restate_lite_address_kind_t;
+
+typedef enum {
+    RESTATE_LITE_LOG_ERROR = 1,
+    RESTATE_LITE_LOG_WARN = 2,
+    RESTATE_LITE_LOG_INFO = 3,
+    RESTATE_LITE_LOG_DEBUG = 4,
+    RESTATE_LITE_LOG_TRACE = 5,
+} restate_lite_log_level_t;
+
+typedef struct {
+    bool use_random_ports;
+    bool enable_tcp;
+    /* In bytes, must not be zero. */
+    size_t memory_budget;
+    /* Nullable, a nul-terminated UTF-8 path. */
+    const char *data_dir;
+    /* Lets the admin API fire timers ahead of time. */
+    bool controllable_time;
+} restate_lite_options_t;
+
+typedef struct {
+    restate_lite_address_kind_t kind;
+    char *name;
+    char *address;
+} restate_lite_address_t;
+
+typedef struct RestateLite restate_lite_t;
+
+/* target and message are only valid for the duration of the call. */
+typedef void (*restate_lite_log_callback_t)(void *user_data,
+                                            restate_lite_log_level_t level,
+                                            const char *target,
+                                            const char *message);
+
+/* Returns RESTATE_LITE_ABI_VERSION of the loaded library. */
+uint32_t restate_lite_abi_version(void);
+
+/* Valid until the next restate-lite call on the calling thread, NULL if the
+ * last call succeeded. */
+const char *restate_lite_last_error(void);
+
+/* Returns the last error of any thread and clears it, NULL if there's none. The
+ * string must be released with restate_lite_string_free. */
+char *restate_lite_take_last_error(void);
+void restate_lite_string_free(char *s);
+
+/* Fills options with the defaults. */
+void restate_lite_options_init(restate_lite_options_t *options);
+
+/* Forwards the server logs up to max_level to callback. Can only be set once
+ * per process. The callback is invoked from arbitrary threads. */
+restate_lite_status_t restate_lite_set_log_callback(restate_lite_log_callback_t callback,
+                                                    restate_lite_log_level_t max_level,
+                                                    void *user_data);
+
+/* Starts a server and blocks until it's ready. options can be NULL to use the
+ * defaults. Only one server can run per process at a time, starting another one
+ * returns RESTATE_LITE_ALREADY_RUNNING. */
+restate_lite_status_t restate_lite_start(const restate_lite_options_t *options,
+                                         restate_lite_t **out);
+
+/* Stops and releases the server. */
+restate_lite_status_t restate_lite_stop(restate_lite_t *restate);
+
+/* Registers the deployment at url and blocks until its discovery completed. */
+restate_lite_status_t restate_lite_discover_deployment(const restate_lite_t *restate,
+                                                       const char *url);
+
+/* The returned lists must be released with restate_lite_addresses_free. */
+restate_lite_status_t restate_lite_bound_addresses(const restate_lite_t *restate,
+                                                   restate_lite_address_t **out,
+                                                   size_t *out_len);
+restate_lite_status_t restate_lite_advertised_addresses(const restate_lite_t *restate,
+                                                        restate_lite_address_t **out,
+                                                        size_t *out_len);
+void restate_lite_addresses_free(restate_lite_address_t *addresses, size_t len);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* RESTATE_LITE_H */
@@ -0,0 +1,694 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! C ABI of restate-lite, used by the SDK test suites to run an embedded Restate server.
+//!
+//! The declarations are mirrored in `include/restate_lite.h`. Functions return a
+//! [`RestateLiteStatus`]; on failure, [`restate_lite_last_error`] returns the message of the
+//! last error that occurred on the calling thread. Callers that dispatch the calls to a thread
+//! pool, like the Node.js bindings, use [`restate_lite_take_last_error`] instead.
+
+use std::cell::RefCell;
+use std::ffi::{CStr, CString, c_char, c_void};
+use std::fmt::Write as _;
+use std::num::NonZero;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::path::PathBuf;
+use std::ptr;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use tokio::runtime::Runtime;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::{Context, Layer, SubscriberExt};
+
+use crate::{AddressKind, AddressMeta, Options, Restate};
+
+/// Version of the C ABI, bumped on every incompatible change.
+pub const RESTATE_LITE_ABI_VERSION: u32 = 1;
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestateLiteStatus {
+    Ok = 0,
+    Error = 1,
+    InvalidArgument = 2,
+    /// A server is already running in this process.
+    AlreadyRunning = 3,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestateLiteAddressKind {
+    Tcp = 0,
+    Unix = 1,
+    Http = 2,
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RestateLiteLogLevel {
+    Error = 1,
+    Warn = 2,
+    Info = 3,
+    Debug = 4,
+    Trace = 5,
+}
+
+/// Mirrors [`Options`]. Initialize with [`restate_lite_options_init`] to get the defaults.
+#[repr(C)]
+pub struct RestateLiteOptions {
+    pub use_random_ports: bool,
+    pub enable_tcp: bool,
+    /// In bytes, must not be zero.
+    pub memory_budget: usize,
+    /// Nullable, a nul-terminated UTF-8 path.
+    pub data_dir: *const c_char,
+    pub controllable_time: bool,
+}
+
+/// An address the server listens on or advertises. The strings are owned by the list returned
+/// from [`restate_lite_bound_addresses`] or [`restate_lite_advertised_addresses`].
+#[repr(C)]
+pub struct RestateLiteAddress {
+    pub kind: RestateLiteAddressKind,
+    pub name: *mut c_char,
+    pub address: *mut c_char,
+}
+
+/// Receives the log events of the embedded server. `target` and `message` are only valid for
+/// the duration of the call.
+pub type RestateLiteLogCallback = extern "C" fn(
+    user_data: *mut c_void,
+    level: RestateLiteLogLevel,
+    target: *const c_char,
+    message: *const c_char,
+);
+
+/// An embedded Restate server together with the tokio runtime it runs on.
+pub struct RestateLite {
+    runtime: Runtime,
+    restate: Restate,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// The last error of any thread, until it's taken by [`restate_lite_take_last_error`].
+static LAST_PROCESS_ERROR: Mutex<Option<CString>> = Mutex::new(None);
+
+/// Set while a server started by [`restate_lite_start`] wasn't stopped yet.
+static SERVER_RUNNING: AtomicBool = AtomicBool::new(false);
+
+fn set_last_error(status: RestateLiteStatus, err: impl std::fmt::Display) -> RestateLiteStatus {
+    let message = CString::new(err.to_string().replace('\0', " ")).expect("nul bytes are replaced");
+    *LAST_PROCESS_ERROR
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(message.clone());
+    LAST_ERROR.with(|last_error| *last_error.borrow_mut() = Some(message));
+    status
+}
+
+/// Runs `f`, turning errors and panics into a status so that they never cross the C boundary.
+fn guard(f: impl FnOnce() -> Result<(), RestateLiteStatus>) -> RestateLiteStatus {
+    LAST_ERROR.with(|last_error| *last_error.borrow_mut() = None);
+    match catch_unwind(AssertUnwindSafe(f)) {
+        Ok(Ok(())) => RestateLiteStatus::Ok,
+        Ok(Err(status)) => status,
+        Err(panic) => {
+            let message = panic
+                .downcast_ref::<&str>()
+                .copied()
+                .or_else(|| panic.downcast_ref::<String>().map(String::as_str))
+                .unwrap_or("unknown panic");
+            set_last_error(RestateLiteStatus::Error, format!("panicked: {message}"))
+        }
+    }
+}
+
+fn invalid_argument(message: &str) -> RestateLiteStatus {
+    set_last_error(RestateLiteStatus::InvalidArgument, message)
+}
+
+/// # Safety
+///
+/// `s` must be null or point to a nul-terminated string.
+unsafe fn str_arg<'a>(s: *const c_char, name: &str) -> Result<Option<&'a str>, RestateLiteStatus> {
+    if s.is_null() {
+        return Ok(None);
+    }
+    // SAFETY: guaranteed by the caller
+    unsafe { CStr::from_ptr(s) }
+        .to_str()
+        .map(Some)
+        .map_err(|_| invalid_argument(&format!("{name} is not valid UTF-8")))
+}
+
+/// Returns [`RESTATE_LITE_ABI_VERSION`].
+#[unsafe(no_mangle)]
+pub extern "C" fn restate_lite_abi_version() -> u32 {
+    RESTATE_LITE_ABI_VERSION
+}
+
+/// Returns the message of the last error on the calling thread, or null. The string is valid
+/// until the next restate-lite call on the same thread.
+#[unsafe(no_mangle)]
+pub extern "C" fn restate_lite_last_error() -> *const c_char {
+    LAST_ERROR.with(|last_error| {
+        last_error
+            .borrow()
+            .as_ref()
+            .map_or(ptr::null(), |message| message.as_ptr())
+    })
+}
+
+/// Returns the message of the last error of any thread and clears it, or null. Unlike
+/// [`restate_lite_last_error`], this works when the calls are dispatched to a thread pool. The
+/// string must be released with [`restate_lite_string_free`].
+#[unsafe(no_mangle)]
+pub extern "C" fn restate_lite_take_last_error() -> *mut c_char {
+    LAST_PROCESS_ERROR
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .take()
+        .map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// Releases a string returned by [`restate_lite_take_last_error`].
+///
+/// # Safety
+///
+/// `s` must be null or a string returned by [`restate_lite_take_last_error`] that wasn't
+/// released yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn restate_lite_string_free(s: *mut c_char) {
+    if s.is_null() {
+        return;
+    }
+    // SAFETY: the string was created by `CString::into_raw`
+    drop(unsafe { CString::from_raw(s) });
+}
+
+/// Fills `options` with the default options.
+///
+/// # Safety
+///
+/// `options` must be null or valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn restate_lite_options_init(options: *mut RestateLiteOptions) {
+    if options.is_null() {
+        return;
+    }
+    let defaults = Options::default();
+    // SAFETY: guaranteed by the caller
+    unsafe {
+        options.write(RestateLiteOptions {
+            use_random_ports: defaults.use_random_ports,
+            enable_tcp: defaults.enable_tcp,
+            memory_budget: defaults.memory_budget.get(),
+            data_dir: ptr::null(),
+            controllable_time: defaults.controllable_time,
+        })
+    };
+}
+
+/// Forwards the logs of the embedded server up to `max_level` to `callback`. Can only be set
+/// once per process, and must be set before the first server is started to capture all logs.
+///
+/// # Safety
+///
+/// `callback` is invoked from arbitrary threads, possibly concurrently, with `user_data`. It must
+/// stay valid for the rest of the process lifetime.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn restate_lite_set_log_callback(
+    callback: Option<RestateLiteLogCallback>,
+    max_level: RestateLiteLogLevel,
+    user_data: *mut c_void,
+) -> RestateLiteStatus {
+    guard(|| {
+        let callback = callback.ok_or_else(|| invalid_argument("callback must not be null"))?;
+        let layer = LogCallbackLayer {
+            callback,
+            user_data: UserData(user_data),
+        }
+        .with_filter(LevelFilter::from_level(Level::from(max_level)));
+        tracing::subscriber::set_global_default(tracing_subscriber::registry().with(layer))
+            .map_err(|err| set_last_error(RestateLiteStatus::Error, err))
+    })
+}
+
+/// Starts an embedded server and blocks until it's ready to serve requests. On success, `out`
+/// holds the server which must be released with [`restate_lite_stop`]. Only one server can run
+/// per process at a time, starting another one fails with
+/// [`RestateLiteStatus::AlreadyRunning`].
+///
+/// # Safety
+///
+/// `options` must be null or point to initialized options, null uses the defaults. `out` must be
+/// valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn restate_lite_start(
+    options: *const RestateLiteOptions,
+    out: *mut *mut RestateLite,
+) -> RestateLiteStatus {
+    guard(|| {
+        if out.is_null() {
+            return Err(invalid_argument("out must not be null"));
+        }
+        // SAFETY: guaranteed by the caller
+        let options = match unsafe { options.as_ref() } {
+            Some(options) => Options {
+                use_random_ports: options.use_random_ports,
+                enable_tcp: options.enable_tcp,
+                memory_budget: NonZero::new(options.memory_budget)
+                    .ok_or_else(|| invalid_argument("memory_budget must not be zero"))?,
+                // SAFETY: guaranteed by the caller
+                data_dir: unsafe { str_arg(options.data_dir, "data_dir")? }.map(PathBuf::from),
+                controllable_time: options.controllable_time,
+            },
+            None => Options::default(),
+        };
+
+        if SERVER_RUNNING.swap(true, Ordering::AcqRel) {
+            return Err(set_last_error(
+                RestateLiteStatus::AlreadyRunning,
+                "a restate-lite server is already running in this process",
+            ));
+        }
+        let server = create_server(options)
+            .inspect_err(|_| SERVER_RUNNING.store(false, Ordering::Release))?;
+
+        // SAFETY: guaranteed by the caller
+        unsafe { out.write(Box::into_raw(Box::new(server))) };
+        Ok(())
+    })
+}
+
+fn create_server(options: Options) -> Result<RestateLite, RestateLiteStatus> {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .thread_name("restate-lite")
+        .build()
+        .map_err(|err| set_last_error(RestateLiteStatus::Error, err))?;
+    let restate = runtime
+        .block_on(Restate::create(options))
+        .map_err(|err| set_last_error(RestateLiteStatus::Error, format!("{err:#}")))?;
+    Ok(RestateLite { runtime, restate })
+}
+
+/// Stops the server and releases it. `restate` must not be used afterwards.
+///
+/// # Safety
+///
+/// `restate` must be null or a server returned by [`restate_lite_start`] that wasn't stopped yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn restate_lite_stop(restate: *mut RestateLite) -> RestateLiteStatus {
+    guard(|| {
+        if restate.is_null() {
+            return Ok(());
+        }
+        // SAFETY: guaranteed by the caller
+        let RestateLite { runtime, restate } = *unsafe { Box::from_raw(restate) };
+        let result = runtime
+            .block_on(restate.stop())
+            .map_err(|err| set_last_error(RestateLiteStatus::Error, format!("{err:#}")));
+        drop(runtime);
+        SERVER_RUNNING.store(false, Ordering::Release);
+        result
+    })
+}
+
+/// Registers the deployment at `url` and blocks until its discovery completed.
+///
+/// # Safety
+///
+/// `restate` must be a running server, `url` must point to a nul-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn restate_lite_discover_deployment(
+    restate: *const RestateLite,
+    url: *const c_char,
+) -> RestateLiteStatus {
+    guard(|| {
+        // SAFETY: guaranteed by the caller
+        let restate = unsafe { restate.as_ref() }
+            .ok_or_else(|| invalid_argument("restate must not be null"))?;
+        // SAFETY: guaranteed by the caller
+        let url = unsafe { str_arg(url, "url")? }
+            .ok_or_else(|| invalid_argument("url must not be null"))?;
+        restate
+            .runtime
+            .block_on(restate.restate.discover_deployment(url))
+            .map_err(|err| set_last_error(RestateLiteStatus::Error, format!("{err:#}")))
+    })
+}
+
+/// Lists the addresses the server is bound to. The list must be released with
+/// [`restate_lite_addresses_free`].
+///
+/// # Safety
+///
+/// `restate` must be a running server, `out` and `out_len` must be valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn restate_lite_bound_addresses(
+    restate: *const RestateLite,
+    out: *mut *mut RestateLiteAddress,
+    out_len: *mut usize,
+) -> RestateLiteStatus {
+    // SAFETY: guaranteed by the caller
+    unsafe { write_addresses(restate, out, out_len, Restate::get_bound_addresses) }
+}
+
+/// Lists the addresses the server advertises. The list must be released with
+/// [`restate_lite_addresses_free`].
+///
+/// # Safety
+///
+/// `restate` must be a running server, `out` and `out_len` must be valid for writes.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn restate_lite_advertised_addresses(
+    restate: *const RestateLite,
+    out: *mut *mut RestateLiteAddress,
+    out_len: *mut usize,
+) -> RestateLiteStatus {
+    // SAFETY: guaranteed by the caller
+    unsafe { write_addresses(restate, out, out_len, Restate::get_advertised_addresses) }
+}
+
+/// Releases a list of addresses.
+///
+/// # Safety
+///
+/// `addresses` and `len` must be a list returned by [`restate_lite_bound_addresses`] or
+/// [`restate_lite_advertised_addresses`] that wasn't released yet.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn restate_lite_addresses_free(
+    addresses: *mut RestateLiteAddress,
+    len: usize,
+) {
+    if addresses.is_null() {
+        return;
+    }
+    // SAFETY: guaranteed by the caller
+    let addresses = unsafe { Box::from_raw(ptr::slice_from_raw_parts_mut(addresses, len)) };
+    for address in addresses {
+        // SAFETY: the strings were created by `CString::into_raw`
+        drop(unsafe { CString::from_raw(address.name) });
+        // SAFETY: the strings were created by `CString::into_raw`
+        drop(unsafe { CString::from_raw(address.address) });
+    }
+}
+
+unsafe fn write_addresses(
+    restate: *const RestateLite,
+    out: *mut *mut RestateLiteAddress,
+    out_len: *mut usize,
+    get_addresses: fn(&Restate) -> Vec<AddressMeta>,
+) -> RestateLiteStatus {
+    guard(|| {
+        // SAFETY: guaranteed by the caller
+        let restate = unsafe { restate.as_ref() }
+            .ok_or_else(|| invalid_argument("restate must not be null"))?;
+        if out.is_null() || out_len.is_null() {
+            return Err(invalid_argument("out and out_len must not be null"));
+        }
+
+        let addresses: Box<[RestateLiteAddress]> = get_addresses(&restate.restate)
+            .into_iter()
+            .map(|address| RestateLiteAddress {
+                kind: match address.kind {
+                    AddressKind::Tcp => RestateLiteAddressKind::Tcp,
+                    AddressKind::Unix => RestateLiteAddressKind::Unix,
+                    AddressKind::Http => RestateLiteAddressKind::Http,
+                },
+                // names and addresses never contain nul bytes
+                name: CString::new(address.name).unwrap_or_default().into_raw(),
+                address: CString::new(address.address).unwrap_or_default().into_raw(),
+            })
+            .collect();
+
+        // SAFETY: guaranteed by the caller
+        unsafe {
+            out_len.write(addresses.len());
+            out.write(Box::into_raw(addresses).cast());
+        }
+        Ok(())
+    })
+}
+
+impl From<RestateLiteLogLevel> for Level {
+    fn from(value: RestateLiteLogLevel) -> Self {
+        match value {
+            RestateLiteLogLevel::Error => Level::ERROR,
+            RestateLiteLogLevel::Warn => Level::WARN,
+            RestateLiteLogLevel::Info => Level::INFO,
+            RestateLiteLogLevel::Debug => Level::DEBUG,
+            RestateLiteLogLevel::Trace => Level::TRACE,
+        }
+    }
+}
+
+impl From<Level> for RestateLiteLogLevel {
+    fn from(value: Level) -> Self {
+        match value {
+            Level::ERROR => RestateLiteLogLevel::Error,
+            Level::WARN => RestateLiteLogLevel::Warn,
+            Level::INFO => RestateLiteLogLevel::Info,
+            Level::DEBUG => RestateLiteLogLevel::Debug,
+            _ => RestateLiteLogLevel::Trace,
+        }
+    }
+}
+
+struct UserData(*mut c_void);
+
+// SAFETY: the caller of `restate_lite_set_log_callback` guarantees that the callback can be
+// invoked with `user_data` from any thread.
+unsafe impl Send for UserData {}
+// SAFETY: see above
+unsafe impl Sync for UserData {}
+
+struct LogCallbackLayer {
+    callback: RestateLiteLogCallback,
+    user_data: UserData,
+}
+
+impl<S: Subscriber> Layer<S> for LogCallbackLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut visitor = MessageVisitor::default();
+        event.record(&mut visitor);
+        let metadata = event.metadata();
+        let (Ok(target), Ok(message)) = (
+            CString::new(metadata.target()),
+            CString::new(visitor.into_message()),
+        ) else {
+            return;
+        };
+        (self.callback)(
+            self.user_data.0,
+            RestateLiteLogLevel::from(*metadata.level()),
+            target.as_ptr(),
+            message.as_ptr(),
+        );
+    }
+}
+
+/// Formats an event as its message followed by its fields, `message key=value ...`.
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl MessageVisitor {
+    fn into_message(mut self) -> String {
+        self.message.push_str(&self.fields);
+        self.message
+    }
+}
+
+impl Visit for MessageVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{value:?}");
+        } else {
+            let _ = write!(self.fields, " {}={value:?}", field.name());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::MutexGuard;
+
+    use super::*;
+
+    /// Serializes the tests using the process-wide state.
+    fn lock_process() -> MutexGuard<'static, ()> {
+        static PROCESS: Mutex<()> = Mutex::new(());
+        PROCESS
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn take_last_error() -> Option<String> {
+        let message = restate_lite_take_last_error();
+        if message.is_null() {
+            return None;
+        }
+        // SAFETY: returned by `restate_lite_take_last_error`
+        let owned = unsafe { CStr::from_ptr(message) }
+            .to_string_lossy()
+            .into_owned();
+        // SAFETY: returned by `restate_lite_take_last_error`
+        unsafe { restate_lite_string_free(message) };
+        Some(owned)
+    }
+
+    fn default_options() -> RestateLiteOptions {
+        let mut options = std::mem::MaybeUninit::uninit();
+        // SAFETY: `options` is valid for writes
+        unsafe { restate_lite_options_init(options.as_mut_ptr()) };
+        // SAFETY: initialized above
+        unsafe { options.assume_init() }
+    }
+
+    #[test]
+    fn options_init_uses_defaults() {
+        let options = default_options();
+        let defaults = Options::default();
+
+        assert_eq!(options.use_random_ports, defaults.use_random_ports);
+        assert_eq!(options.enable_tcp, defaults.enable_tcp);
+        assert_eq!(options.memory_budget, defaults.memory_budget.get());
+        assert!(options.data_dir.is_null());
+        assert_eq!(options.controllable_time, defaults.controllable_time);
+    }
+
+    #[test]
+    fn start_rejects_invalid_arguments() {
+        let _process = lock_process();
+        let mut out = ptr::null_mut();
+
+        // SAFETY: null options use the defaults
+        let status = unsafe { restate_lite_start(ptr::null(), ptr::null_mut()) };
+        assert_eq!(status, RestateLiteStatus::InvalidArgument);
+        // SAFETY: `restate_lite_last_error` returns null or a valid string
+        let message = unsafe { CStr::from_ptr(restate_lite_last_error()) };
+        assert_eq!(message.to_str().unwrap(), "out must not be null");
+
+        let mut options = default_options();
+        options.memory_budget = 0;
+        // SAFETY: `options` is initialized and `out` is valid for writes
+        let status = unsafe { restate_lite_start(&options, &mut out) };
+        assert_eq!(status, RestateLiteStatus::InvalidArgument);
+
+        let data_dir = c"\xff";
+        let mut options = default_options();
+        options.data_dir = data_dir.as_ptr();
+        // SAFETY: `options` is initialized and `out` is valid for writes
+        let status = unsafe { restate_lite_start(&options, &mut out) };
+        assert_eq!(status, RestateLiteStatus::InvalidArgument);
+        assert!(out.is_null());
+    }
+
+    #[test]
+    fn calls_reject_null_server() {
+        let _process = lock_process();
+        // SAFETY: null servers are rejected
+        let status = unsafe { restate_lite_discover_deployment(ptr::null(), c"url".as_ptr()) };
+        assert_eq!(status, RestateLiteStatus::InvalidArgument);
+
+        let mut addresses = ptr::null_mut();
+        let mut len = 0;
+        // SAFETY: null servers are rejected
+        let status = unsafe { restate_lite_bound_addresses(ptr::null(), &mut addresses, &mut len) };
+        assert_eq!(status, RestateLiteStatus::InvalidArgument);
+
+        // SAFETY: stopping null is a no-op
+        let status = unsafe { restate_lite_stop(ptr::null_mut()) };
+        assert_eq!(status, RestateLiteStatus::Ok);
+        assert!(restate_lite_last_error().is_null());
+    }
+
+    #[test]
+    fn last_error_is_taken_once() {
+        let _process = lock_process();
+        set_last_error(RestateLiteStatus::Error, "some\0error");
+
+        assert_eq!(take_last_error().as_deref(), Some("some error"));
+        assert_eq!(take_last_error(), None);
+    }
+
+    #[test]
+    fn second_start_fails() {
+        let _process = lock_process();
+        // Pretends a server is running, starting one for real is covered by `tests/cdylib.rs`
+        SERVER_RUNNING.store(true, Ordering::Release);
+        let mut out = ptr::null_mut();
+        // SAFETY: null options use the defaults and `out` is valid for writes
+        let status = unsafe { restate_lite_start(ptr::null(), &mut out) };
+        SERVER_RUNNING.store(false, Ordering::Release);
+
+        assert_eq!(status, RestateLiteStatus::AlreadyRunning);
+        assert!(out.is_null());
+    }
+
+    #[test]
+    fn log_events_are_forwarded() {
+        extern "C" fn collect(
+            user_data: *mut c_void,
+            level: RestateLiteLogLevel,
+            target: *const c_char,
+            message: *const c_char,
+        ) {
+            // SAFETY: `user_data` points to the vector below
+            let logs = unsafe { &*user_data.cast::<Mutex<Vec<(RestateLiteLogLevel, String)>>>() };
+            // SAFETY: valid for the duration of the call
+            let (target, message) = unsafe { (CStr::from_ptr(target), CStr::from_ptr(message)) };
+            logs.lock().unwrap().push((
+                level,
+                format!(
+                    "{}: {}",
+                    target.to_str().unwrap(),
+                    message.to_str().unwrap()
+                ),
+            ));
+        }
+
+        let logs = Mutex::new(Vec::new());
+        let layer = LogCallbackLayer {
+            callback: collect,
+            user_data: UserData(ptr::from_ref(&logs).cast_mut().cast()),
+        }
+        .with_filter(LevelFilter::INFO);
+        tracing::subscriber::with_default(tracing_subscriber::registry().with(layer), || {
+            tracing::info!(target: "lite", partition = 1, "started");
+            tracing::debug!(target: "lite", "filtered");
+            tracing::warn!(target: "lite", reason = "test", "stopping");
+        });
+
+        assert_eq!(
+            logs.into_inner().unwrap(),
+            vec![
+                (
+                    RestateLiteLogLevel::Info,
+                    "lite: started partition=1".to_owned()
+                ),
+                (
+                    RestateLiteLogLevel::Warn,
+                    "lite: stopping reason=\"test\"".to_owned()
+                ),
+            ]
+        );
+    }
+}
@@ -9,6 +9,7 @@
 // by the Apache License, Version 2.0.
 
 pub mod build_info;
+pub mod ffi;
 
 use std::num::NonZero;
 use std::path::PathBuf;
@@ -69,6 +70,9 @@ pub struct Options {
     pub enable_tcp: bool,
     pub memory_budget: NonZero<usize>,
     pub data_dir: Option<PathBuf>,
+    /// Lets the admin API fire timers ahead of time, see the `controllable_time` experimental
+    /// feature.
+    pub controllable_time: bool,
 }
 
 impl Default for Options {
@@ -78,6 +82,7 @@ impl Default for Options {
             use_random_ports: false,
             enable_tcp: false,
             data_dir: None,
+            controllable_time: false,
         }
     }
 }
@@ -185,7 +190,10 @@ impl Restate {
             common_builder.base_dir(data_dir);
         }
 
-        let common = common_builder.build()?;
+        let mut common = common_builder.build()?;
+        common
+            .experimental
+            .set_controllable_time(opts.controllable_time);
 
         let bifrost = BifrostOptionsBuilder::default()
             .default_provider(ProviderKind::Local)
@@ -0,0 +1,144 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Loads the shared library the way the SDK bindings do, and drives a server through its C API.
+
+use std::env::consts::{DLL_PREFIX, DLL_SUFFIX};
+use std::ffi::{CStr, CString, c_char};
+use std::path::PathBuf;
+use std::ptr;
+
+use libloading::{Library, Symbol};
+
+use restate_lite::ffi::{
+    RESTATE_LITE_ABI_VERSION, RestateLite, RestateLiteAddress, RestateLiteOptions,
+    RestateLiteStatus,
+};
+
+/// The shared library is built next to the test binary, in `deps` or its parent directory.
+fn library_path() -> PathBuf {
+    let name = format!("{DLL_PREFIX}restate_lite{DLL_SUFFIX}");
+    let exe = std::env::current_exe().expect("test binary path");
+    exe.ancestors()
+        .skip(1)
+        .take(2)
+        .map(|dir| dir.join(&name))
+        .find(|path| path.exists())
+        .unwrap_or_else(|| panic!("{name} is not built next to {}", exe.display()))
+}
+
+struct CApi {
+    // keeps the symbols valid
+    _library: Library,
+    abi_version: extern "C" fn() -> u32,
+    take_last_error: extern "C" fn() -> *mut c_char,
+    string_free: unsafe extern "C" fn(*mut c_char),
+    options_init: unsafe extern "C" fn(*mut RestateLiteOptions),
+    start:
+        unsafe extern "C" fn(*const RestateLiteOptions, *mut *mut RestateLite) -> RestateLiteStatus,
+    stop: unsafe extern "C" fn(*mut RestateLite) -> RestateLiteStatus,
+    bound_addresses: unsafe extern "C" fn(
+        *const RestateLite,
+        *mut *mut RestateLiteAddress,
+        *mut usize,
+    ) -> RestateLiteStatus,
+    addresses_free: unsafe extern "C" fn(*mut RestateLiteAddress, usize),
+}
+
+impl CApi {
+    fn load() -> Self {
+        // SAFETY: the library has no initialization routines
+        let library = unsafe { Library::new(library_path()) }.expect("loadable library");
+        // SAFETY: the signatures match the declarations in `src/ffi.rs`
+        unsafe {
+            Self {
+                abi_version: *symbol(&library, "restate_lite_abi_version"),
+                take_last_error: *symbol(&library, "restate_lite_take_last_error"),
+                string_free: *symbol(&library, "restate_lite_string_free"),
+                options_init: *symbol(&library, "restate_lite_options_init"),
+                start: *symbol(&library, "restate_lite_start"),
+                stop: *symbol(&library, "restate_lite_stop"),
+                bound_addresses: *symbol(&library, "restate_lite_bound_addresses"),
+                addresses_free: *symbol(&library, "restate_lite_addresses_free"),
+                _library: library,
+            }
+        }
+    }
+
+    fn take_last_error(&self) -> String {
+        let message = (self.take_last_error)();
+        assert!(!message.is_null(), "an error was reported");
+        // SAFETY: returned by `restate_lite_take_last_error`
+        let owned = unsafe { CStr::from_ptr(message) }
+            .to_string_lossy()
+            .into_owned();
+        // SAFETY: returned by `restate_lite_take_last_error`
+        unsafe { (self.string_free)(message) };
+        owned
+    }
+}
+
+/// # Safety
+///
+/// `T` must match the signature of the symbol.
+unsafe fn symbol<'lib, T>(library: &'lib Library, name: &str) -> Symbol<'lib, T> {
+    // SAFETY: guaranteed by the caller
+    unsafe { library.get(name.as_bytes()) }.unwrap_or_else(|err| panic!("{name}: {err}"))
+}
+
+#[test]
+fn start_and_stop_through_the_c_api() {
+    let api = CApi::load();
+    assert_eq!((api.abi_version)(), RESTATE_LITE_ABI_VERSION);
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let data_dir = CString::new(temp_dir.path().to_str().unwrap()).unwrap();
+    let mut options = std::mem::MaybeUninit::uninit();
+    // SAFETY: `options` is valid for writes
+    unsafe { (api.options_init)(options.as_mut_ptr()) };
+    // SAFETY: initialized above
+    let mut options = unsafe { options.assume_init() };
+    options.use_random_ports = true;
+    options.data_dir = data_dir.as_ptr();
+
+    // SAFETY: `options` is initialized
+    let status = unsafe { (api.start)(&options, ptr::null_mut()) };
+    assert_eq!(status, RestateLiteStatus::InvalidArgument);
+    assert_eq!(api.take_last_error(), "out must not be null");
+
+    let mut restate = ptr::null_mut();
+    // SAFETY: `options` is initialized and `restate` is valid for writes
+    let status = unsafe { (api.start)(&options, &mut restate) };
+    assert_eq!(status, RestateLiteStatus::Ok, "{}", api.take_last_error());
+
+    // Only one server can run per process
+    let mut second = ptr::null_mut();
+    // SAFETY: `options` is initialized and `second` is valid for writes
+    let status = unsafe { (api.start)(&options, &mut second) };
+    assert_eq!(status, RestateLiteStatus::AlreadyRunning);
+    assert!(second.is_null());
+    assert_eq!(
+        api.take_last_error(),
+        "a restate-lite server is already running in this process"
+    );
+
+    let mut addresses = ptr::null_mut();
+    let mut len = 0;
+    // SAFETY: `restate` is running, `addresses` and `len` are valid for writes
+    let status = unsafe { (api.bound_addresses)(restate, &mut addresses, &mut len) };
+    assert_eq!(status, RestateLiteStatus::Ok);
+    assert!(len > 0);
+    // SAFETY: returned by `restate_lite_bound_addresses`
+    unsafe { (api.addresses_free)(addresses, len) };
+
+    // SAFETY: `restate` is running
+    let status = unsafe { (api.stop)(restate) };
+    assert_eq!(status, RestateLiteStatus::Ok);
+}
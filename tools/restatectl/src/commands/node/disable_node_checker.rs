@@ -152,7 +152,8 @@ impl<'a, 'b> DisableNodeChecker<'a, 'b> {
                             )));
                         }
                     }
-                    InternalKind::Sealed => {}
+                    // offloaded segments are served from the object store
+                    InternalKind::Sealed | InternalKind::ObjectStore => {}
                 }
             }
         }
@@ -12,6 +12,7 @@ mod gen_metadata;
 mod leader;
 pub mod list;
 mod reconfigure;
+mod split;
 
 use cling::prelude::*;
 
@@ -24,6 +25,8 @@ pub enum Partitions {
     GenerateMetadata(gen_metadata::GeneratePartitionTableOpts),
     /// Reconfigures the processors of the specified partition
     Reconfigure(reconfigure::ReconfigureOpts),
+    /// Splits a partition into two partitions to scale out the cluster
+    Split(split::SplitOpts),
     /// Control leader election policy for partitions
     #[clap(subcommand)]
     Leader(leader::Leader),
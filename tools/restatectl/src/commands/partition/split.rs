@@ -0,0 +1,77 @@
+// Copyright (c) 2023 - 2026 Restate Software, Inc., Restate GmbH.
+// All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use anyhow::bail;
+use cling::prelude::*;
+
+use restate_cli_util::ui::console::confirm_or_exit;
+use restate_cli_util::{CliContext, c_println, c_success};
+use restate_core::protobuf::cluster_ctrl_svc::{SplitPartitionRequest, new_cluster_ctrl_client};
+use restate_types::identifiers::PartitionId;
+use restate_types::nodes_config::Role;
+
+use crate::connection::ConnectionInfo;
+
+#[derive(Run, Parser, Collect, Clone, Debug)]
+#[cling(run = "split_partition")]
+#[command(
+    after_long_help = "Splits a partition into two new partitions, each covering one half of its key range. \
+    The log of the partition is sealed, and the new partitions start from a snapshot of the partition \
+    taken at the sealed tail. Requires a snapshot repository to be configured."
+)]
+pub struct SplitOpts {
+    /// The id of the partition to split
+    #[arg(required = true)]
+    partition_id: PartitionId,
+}
+
+async fn split_partition(connection: &ConnectionInfo, opts: &SplitOpts) -> anyhow::Result<()> {
+    let partition_table = connection.get_partition_table().await?;
+    let Some(partition) = partition_table.get(&opts.partition_id) else {
+        bail!("Partition {} does not exist.", opts.partition_id);
+    };
+
+    c_println!(
+        "Partition {} covers the partition keys {}..={}. Splitting it seals its log, \
+        writes for its keys stall until the new partitions have taken over.",
+        opts.partition_id,
+        partition.key_range.start(),
+        partition.key_range.end(),
+    );
+    confirm_or_exit("Split the partition?")?;
+
+    let request = SplitPartitionRequest {
+        partition_id: opts.partition_id.into(),
+    };
+
+    let response = connection
+        .try_each(Some(Role::Admin), |channel| async {
+            new_cluster_ctrl_client(channel, &CliContext::get().network)
+                .split_partition(request)
+                .await
+        })
+        .await?
+        .into_inner();
+
+    c_success!(
+        "Split partition {} at LSN {} into partitions {}, bootstrapping from snapshot {}",
+        opts.partition_id,
+        response.sealed_lsn,
+        response
+            .partition_ids
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(" and "),
+        response.snapshot_id,
+    );
+
+    Ok(())
+}